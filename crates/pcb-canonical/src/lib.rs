@@ -1,16 +1,33 @@
 //! Canonical tar archive and content hashing utilities.
 //!
 //! This module implements deterministic tar archives and BLAKE3 content hashing
-//! for package integrity verification.
+//! for package integrity verification. Publishing, dependency resolution, and
+//! release metadata all hash packages through it, so a hash computed by any of
+//! them can be checked by the others.
 //!
 //! ## Canonicalization Rules
 //!
 //! To ensure byte-identical archives across platforms:
-//! - Paths are normalized to NFC Unicode form (macOS uses NFD, Linux uses NFC)
-//! - Paths use forward slashes and are sorted by byte value (not path components)
-//! - Metadata is normalized: mtime=0, uid=0, gid=0, mode=0644, empty user/group names
-//! - Only regular files are included (directories are implicit)
-//! - Generated resolver state such as `pcb.sum` is excluded
+//!
+//! - **Selection:** regular files under the package directory, respecting
+//!   `.gitignore`. Generated resolver state such as `pcb.sum` is excluded, as
+//!   are nested packages (subdirectories with their own `pcb.toml`) by default.
+//!   Directories are implicit, so empty ones don't count.
+//! - **Paths:** relative, UTF-8, normalized to NFC Unicode form (macOS uses NFD,
+//!   Linux uses NFC), with `/` as the separator on every platform. See
+//!   [`canonical_path`].
+//! - **Ordering:** entries are sorted by the bytes of their canonical path, not
+//!   by path components, so `a-c` sorts before `a/b`.
+//! - **Metadata:** GNU headers with mtime=0, uid=0, gid=0, empty user/group
+//!   names, and mode 0644 whatever the file's permissions, so executable bits
+//!   and timestamps never change a hash.
+//! - **Contents:** hashed byte for byte. Line endings are *not* normalized; a
+//!   checkout that converts them (e.g. git's `core.autocrlf`) hashes
+//!   differently from the published package.
+//! - **Encoding:** the archive, ending with two 512-byte zero blocks, is hashed
+//!   with BLAKE3 and written as [`HASH_PREFIX`] followed by the standard base64
+//!   of the digest. Manifest hashes use the same encoding over the raw
+//!   `pcb.toml` text.
 
 use std::fs;
 use std::io::Cursor;
@@ -23,6 +40,9 @@ use ignore::WalkBuilder;
 use tar::{Builder, Header};
 use unicode_normalization::UnicodeNormalization;
 
+/// Prefix of every hash this crate produces, naming the hashing scheme.
+pub const HASH_PREFIX: &str = "h1:";
+
 #[derive(Debug, Clone, Copy)]
 pub struct CanonicalTarOptions {
     pub exclude_nested_packages: bool,
@@ -36,12 +56,12 @@ impl Default for CanonicalTarOptions {
     }
 }
 
-/// Convert a path to a canonical tar path string.
+/// Convert a relative path to its canonical tar path string.
 ///
 /// - Converts to UTF-8 (errors on non-UTF-8 paths)
 /// - Normalizes to NFC Unicode form for cross-platform consistency
 /// - Uses forward slashes
-fn canonicalize_path(path: &Path) -> Result<String> {
+pub fn canonical_path(path: &Path) -> Result<String> {
     let s = path
         .to_str()
         .with_context(|| format!("non-UTF-8 path: {:?}", path))?;
@@ -70,7 +90,7 @@ fn collect_canonical_entries(
         if is_generated_state_file(Path::new(filename)) {
             return Ok(Vec::new());
         }
-        let canonical = canonicalize_path(Path::new(filename))?;
+        let canonical = canonical_path(Path::new(filename))?;
         return Ok(vec![(PathBuf::from(filename), canonical)]);
     }
 
@@ -103,7 +123,7 @@ fn collect_canonical_entries(
         // Only include files - directories are implicit from file paths in tar
         // This avoids issues with empty directories (which git doesn't track anyway)
         if file_type.is_file() && !is_generated_state_file(rel_path) {
-            let canonical = canonicalize_path(rel_path)?;
+            let canonical = canonical_path(rel_path)?;
             entries.push((rel_path.to_path_buf(), canonical));
         }
     }
@@ -150,6 +170,41 @@ pub fn list_canonical_tar_entries(
         .collect())
 }
 
+/// A file that would be included in a canonical tar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalEntry {
+    /// Canonical archive path (NFC, forward slashes).
+    pub path: String,
+    /// Location of the file on disk.
+    pub source: PathBuf,
+    pub size: u64,
+}
+
+/// List canonical tar entries with their on-disk location and size, in
+/// archive order.
+pub fn canonical_tar_entries(
+    path: &Path,
+    options: Option<CanonicalTarOptions>,
+) -> Result<Vec<CanonicalEntry>> {
+    let is_file = path.is_file();
+    collect_canonical_entries(path, options.unwrap_or_default())?
+        .into_iter()
+        .map(|(rel_path, canonical)| {
+            let source = if is_file {
+                path.to_path_buf()
+            } else {
+                path.join(&rel_path)
+            };
+            let size = fs::metadata(&source)?.len();
+            Ok(CanonicalEntry {
+                path: canonical,
+                source,
+                size,
+            })
+        })
+        .collect()
+}
+
 /// Create a canonical, deterministic tar archive from a directory or file
 ///
 /// Rules from packaging.md:
@@ -211,8 +266,7 @@ pub fn compute_content_hash_from_dir(cache_dir: &Path) -> Result<String> {
     // Stream canonical tar directly to BLAKE3 hasher (avoids buffering entire tar in memory)
     let mut hasher = blake3::Hasher::new();
     create_canonical_tar(cache_dir, &mut hasher, None)?;
-    let hash = hasher.finalize();
-    Ok(format!("h1:{}", STANDARD.encode(hash.as_bytes())))
+    Ok(encode_hash(hasher.finalize()))
 }
 
 /// Compute content hash from in-memory files.
@@ -228,7 +282,7 @@ where
         if is_generated_state_file(path) {
             continue;
         }
-        let canonical = canonicalize_path(path)?;
+        let canonical = canonical_path(path)?;
         entries.push((canonical, contents));
     }
     entries.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
@@ -253,14 +307,74 @@ where
 
         builder.finish()?;
     }
-    let hash = hasher.finalize();
-    Ok(format!("h1:{}", STANDARD.encode(hash.as_bytes())))
+    Ok(encode_hash(hasher.finalize()))
 }
 
 /// Compute manifest hash for a pcb.toml file
 ///
 /// Format: h1:<base64-encoded-blake3>
 pub fn compute_manifest_hash(manifest_content: &str) -> String {
-    let hash = blake3::hash(manifest_content.as_bytes());
-    format!("h1:{}", STANDARD.encode(hash.as_bytes()))
+    encode_hash(blake3::hash(manifest_content.as_bytes()))
+}
+
+fn encode_hash(hash: blake3::Hash) -> String {
+    format!("{HASH_PREFIX}{}", STANDARD.encode(hash.as_bytes()))
+}
+
+/// Content and manifest hashes of a package, as recorded in the annotation of
+/// its release tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageHashes {
+    pub content: String,
+    pub manifest: String,
+}
+
+impl PackageHashes {
+    /// Hash the package in `dir`, which must contain a `pcb.toml`.
+    pub fn compute(dir: &Path) -> Result<Self> {
+        let manifest_path = dir.join("pcb.toml");
+        let manifest = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        Ok(Self {
+            content: compute_content_hash_from_dir(dir)?,
+            manifest: compute_manifest_hash(&manifest),
+        })
+    }
+
+    /// Release tag annotation recording these hashes for `url` at `version`:
+    ///
+    /// ```text
+    /// github.com/acme/lib v1.2.0 h1:...
+    /// github.com/acme/lib v1.2.0/pcb.toml h1:...
+    /// ```
+    pub fn tag_annotation(&self, url: &str, version: &str) -> String {
+        format!(
+            "{url} v{version} {}\n{url} v{version}/pcb.toml {}",
+            self.content, self.manifest
+        )
+    }
+
+    /// Parse hashes from a tag annotation written by [`Self::tag_annotation`].
+    /// Returns `None` unless both hashes are present.
+    pub fn from_tag_annotation(body: &str) -> Option<Self> {
+        let mut content = None;
+        let mut manifest = None;
+
+        for line in body.lines() {
+            let line = line.trim();
+            if let Some(hash_start) = line.find(&format!(" {HASH_PREFIX}")) {
+                let hash = line[hash_start + 1..].to_string();
+                if line[..hash_start].ends_with("/pcb.toml") {
+                    manifest = Some(hash);
+                } else {
+                    content = Some(hash);
+                }
+            }
+        }
+
+        Some(Self {
+            content: content?,
+            manifest: manifest?,
+        })
+    }
 }
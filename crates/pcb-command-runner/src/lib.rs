@@ -1,7 +1,8 @@
 use std::{
-    fs::File,
+    fmt,
+    fs::{self, File},
     io::{Read, Write},
-    path::Path,
+    path::{Path, PathBuf},
     process::{Command, Stdio},
     thread,
     time::{Duration, Instant},
@@ -58,6 +59,86 @@ impl CommandOutput {
         file.write_all(&self.plain_output)?;
         Ok(())
     }
+
+    /// The last `lines` lines of the plain output
+    pub fn tail(&self, lines: usize) -> String {
+        let output = self.plain_as_string();
+        let all: Vec<&str> = output.trim_end().lines().collect();
+        all[all.len().saturating_sub(lines)..].join("\n")
+    }
+}
+
+/// Error for a command that ran but exited unsuccessfully.
+///
+/// Carries the captured output so callers can show the relevant part of it,
+/// e.g. with [`CommandOutput::tail`], instead of pointing at a log file.
+#[derive(Debug)]
+pub struct CommandFailed {
+    pub message: String,
+    pub output: CommandOutput,
+}
+
+impl fmt::Display for CommandFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CommandFailed {}
+
+/// Timestamped per-run log files in one directory, keeping the newest `keep`.
+///
+/// Files are named `<prefix>-<timestamp>.log`, so they sort by age.
+#[derive(Clone, Debug)]
+pub struct LogRotation {
+    dir: PathBuf,
+    prefix: String,
+    keep: usize,
+}
+
+impl LogRotation {
+    pub fn new(dir: impl Into<PathBuf>, prefix: impl Into<String>, keep: usize) -> Self {
+        Self {
+            dir: dir.into(),
+            prefix: prefix.into(),
+            keep: keep.max(1),
+        }
+    }
+
+    /// Path of the log file for a run starting now
+    pub fn next_path(&self) -> PathBuf {
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S-%3f");
+        self.dir.join(format!("{}-{timestamp}.log", self.prefix))
+    }
+
+    /// Create the log file at `path` and delete the oldest logs beyond `keep`
+    pub fn create(&self, path: &Path) -> Result<File> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let file =
+            File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let files = self.files()?;
+        for old in &files[..files.len().saturating_sub(self.keep)] {
+            let _ = fs::remove_file(old);
+        }
+        Ok(file)
+    }
+
+    /// Log files of this rotation, oldest first
+    pub fn files(&self) -> Result<Vec<PathBuf>> {
+        let prefix = format!("{}-", self.prefix);
+        let mut files: Vec<PathBuf> = fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to read {}", self.dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".log"))
+            })
+            .collect();
+        files.sort();
+        Ok(files)
+    }
 }
 
 /// Options for running a command
@@ -405,6 +486,39 @@ mod tests {
         assert_eq!(colored_output.plain_as_string().trim(), "Red Green");
     }
 
+    #[test]
+    fn test_output_tail() {
+        let output = CommandRunner::new("sh")
+            .arg("-c")
+            .arg("printf 'one\\ntwo\\nthree\\n'")
+            .run()
+            .unwrap();
+
+        assert_eq!(output.tail(2), "two\nthree");
+        assert_eq!(output.tail(10), "one\ntwo\nthree");
+    }
+
+    #[test]
+    fn test_log_rotation_keeps_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let rotation = LogRotation::new(dir.path().join("logs"), "layout", 2);
+        for run in 0..3 {
+            let path = rotation
+                .next_path()
+                .with_file_name(format!("layout-2026010{run}.log"));
+            rotation.create(&path).unwrap();
+        }
+        fs::write(dir.path().join("logs").join("other.log"), "").unwrap();
+
+        let names: Vec<_> = rotation
+            .files()
+            .unwrap()
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["layout-20260101.log", "layout-20260102.log"]);
+    }
+
     #[test]
     fn test_with_stdin_input() {
         let output = CommandRunner::new("cat")
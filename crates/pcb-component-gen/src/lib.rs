@@ -1,7 +1,7 @@
 use anyhow::Result;
 use deunicode::deunicode;
 use minijinja::Environment;
-use pcb_eda::{Pin, Symbol};
+use pcb_eda::{Pin, PinElectricalType, Symbol};
 use std::collections::{BTreeMap, BTreeSet};
 
 const COMPONENT_ZEN_TEMPLATE: &str = include_str!("../templates/component.zen.jinja");
@@ -110,18 +110,10 @@ struct SignalPinMetadata {
     saw_non_no_connect: bool,
 }
 
-fn pin_type_candidates(pin: &Pin) -> impl Iterator<Item = &str> {
-    pin.electrical_type.as_deref().into_iter().chain(
-        pin.alternates
-            .iter()
-            .filter_map(|alt| alt.electrical_type.as_deref()),
-    )
-}
-
 fn update_signal_pin_metadata(metadata: &mut SignalPinMetadata, pin: &Pin) {
-    for pin_type in pin_type_candidates(pin) {
+    for pin_type in pin.electrical_types() {
         metadata.saw_pin_type = true;
-        if pin_type != "no_connect" {
+        if pin_type != PinElectricalType::NoConnect {
             metadata.saw_non_no_connect = true;
         }
     }
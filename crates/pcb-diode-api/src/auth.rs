@@ -15,6 +15,11 @@ use crate::aws_auth::{self, AwsDiodeToken};
 
 const NOT_AUTHENTICATED_MESSAGE: &str = "Not authenticated. Run `pcb auth login` to authenticate.";
 const DIODE_API_AUTH_NONE: &str = "none";
+/// Machine-to-machine token for headless use (CI), bypassing interactive login.
+const SERVICE_TOKEN_ENV: &str = "DIODE_API_TOKEN";
+/// Where interactive tokens are stored: `keychain` or `file`.
+const AUTH_STORAGE_ENV: &str = "PCB_AUTH_STORAGE";
+const KEYCHAIN_SERVICE: &str = "pcb";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthTokens {
@@ -26,11 +31,7 @@ pub struct AuthTokens {
 
 impl AuthTokens {
     pub fn is_expired(&self) -> bool {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
-        self.expires_at - now < 300
+        self.expires_at - unix_now() < 300
     }
 
     pub fn time_until_expiry(&self) -> String {
@@ -39,11 +40,7 @@ impl AuthTokens {
 }
 
 fn time_until_expiry(expires_at: i64) -> String {
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs() as i64;
-    let remaining = expires_at - now;
+    let remaining = expires_at - unix_now();
 
     if remaining <= 0 {
         "expired".to_string()
@@ -56,6 +53,98 @@ fn time_until_expiry(expires_at: i64) -> String {
     }
 }
 
+/// A service token supplied through `DIODE_API_TOKEN`.
+///
+/// Service tokens are issued for CI and other headless environments. When the
+/// token is a JWT, its `scope`/`scopes` and `exp` claims are read so commands can
+/// fail early with a clear message instead of a 403 from the API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceToken {
+    pub token: String,
+    /// Granted scopes, if the token declares them
+    pub scopes: Option<Vec<String>>,
+    pub expires_at: Option<i64>,
+}
+
+impl ServiceToken {
+    pub fn from_env() -> Option<Self> {
+        let token = std::env::var(SERVICE_TOKEN_ENV).ok()?;
+        let token = token.trim();
+        (!token.is_empty()).then(|| Self::parse(token))
+    }
+
+    fn parse(token: &str) -> Self {
+        #[derive(Deserialize)]
+        struct Claims {
+            scope: Option<String>,
+            scopes: Option<Vec<String>>,
+            exp: Option<i64>,
+        }
+
+        let claims = token.split('.').nth(1).and_then(|payload| {
+            use base64::Engine;
+            let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .decode(payload.trim_end_matches('='))
+                .ok()?;
+            serde_json::from_slice::<Claims>(&bytes).ok()
+        });
+
+        let (scopes, expires_at) = match claims {
+            Some(claims) => (
+                claims.scopes.or_else(|| {
+                    claims
+                        .scope
+                        .map(|scope| scope.split_whitespace().map(str::to_string).collect())
+                }),
+                claims.exp,
+            ),
+            None => (None, None),
+        };
+
+        Self {
+            token: token.to_string(),
+            scopes,
+            expires_at,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= unix_now())
+    }
+
+    /// Whether the token grants `scope`. Tokens without declared scopes are
+    /// assumed to grant everything; the API remains the final authority.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes
+            .as_ref()
+            .is_none_or(|scopes| scopes.iter().any(|s| s == scope || s == "*"))
+    }
+}
+
+/// Fail early if the configured service token does not grant `scope`.
+pub fn require_scope(scope: &str) -> Result<()> {
+    if api_auth_disabled() {
+        return Ok(());
+    }
+    if let Some(token) = ServiceToken::from_env()
+        && !token.has_scope(scope)
+    {
+        anyhow::bail!(
+            "{SERVICE_TOKEN_ENV} does not grant the `{scope}` scope (granted: {})",
+            token.scopes.unwrap_or_default().join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
 impl WorkspaceContext {
     pub fn token(&self) -> Result<String> {
         get_valid_token_with_context(self)
@@ -85,7 +174,61 @@ fn get_auth_file_path(ctx: &WorkspaceContext) -> Result<PathBuf> {
     Ok(scoped_dir.join(format!("{slug}.toml")))
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenStorage {
+    Keychain,
+    File,
+}
+
+impl TokenStorage {
+    /// Keychain on macOS and Windows, where the OS keychain persists across
+    /// sessions; the auth file elsewhere. `PCB_AUTH_STORAGE` overrides either.
+    fn current() -> Self {
+        match std::env::var(AUTH_STORAGE_ENV).as_deref() {
+            Ok("keychain") => TokenStorage::Keychain,
+            Ok("file") => TokenStorage::File,
+            _ if cfg!(any(target_os = "macos", target_os = "windows")) => TokenStorage::Keychain,
+            _ => TokenStorage::File,
+        }
+    }
+
+    fn display_name(self) -> &'static str {
+        match self {
+            TokenStorage::Keychain => "OS keychain",
+            TokenStorage::File => "auth file",
+        }
+    }
+}
+
+fn keychain_entry(ctx: &WorkspaceContext) -> Result<keyring::Entry> {
+    let account = crate::endpoint::auth_scope_slug(ctx.api_base_url());
+    keyring::Entry::new(KEYCHAIN_SERVICE, &account).context("Failed to open OS keychain")
+}
+
+fn load_keychain_tokens(ctx: &WorkspaceContext) -> Option<AuthTokens> {
+    match keychain_entry(ctx).and_then(|entry| Ok(entry.get_password()?)) {
+        Ok(contents) => match toml::from_str(&contents) {
+            Ok(tokens) => Some(tokens),
+            Err(err) => {
+                log::debug!("ignoring malformed keychain auth entry: {err}");
+                None
+            }
+        },
+        Err(err) => {
+            log::debug!("no keychain auth entry: {err}");
+            None
+        }
+    }
+}
+
 fn load_tokens_with_context(ctx: &WorkspaceContext) -> Result<Option<AuthTokens>> {
+    if TokenStorage::current() == TokenStorage::Keychain
+        && let Some(tokens) = load_keychain_tokens(ctx)
+    {
+        return Ok(Some(tokens));
+    }
+
+    // Auth file: the configured storage, or tokens saved before keychain support.
     let path = get_auth_file_path(ctx)?;
     if !path.exists() {
         return Ok(None);
@@ -115,6 +258,19 @@ fn save_tokens(
     let contents = toml::to_string(&tokens)?;
 
     let auth_path = get_auth_file_path(ctx)?;
+    if TokenStorage::current() == TokenStorage::Keychain {
+        match keychain_entry(ctx).and_then(|entry| Ok(entry.set_password(&contents)?)) {
+            Ok(()) => {
+                // Drop any plaintext copy once the keychain holds the tokens.
+                if auth_path.exists() {
+                    fs::remove_file(&auth_path)?;
+                }
+                return Ok(());
+            }
+            Err(err) => log::warn!("OS keychain unavailable, storing tokens in auth file: {err}"),
+        }
+    }
+
     AtomicFile::new(&auth_path, OverwriteBehavior::AllowOverwrite)
         .write(|f| {
             f.write_all(contents.as_bytes())?;
@@ -126,6 +282,14 @@ fn save_tokens(
 }
 
 fn clear_tokens_with_context(ctx: &WorkspaceContext) -> Result<()> {
+    if TokenStorage::current() == TokenStorage::Keychain
+        && let Ok(entry) = keychain_entry(ctx)
+    {
+        match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(err) => log::warn!("Failed to remove tokens from OS keychain: {err}"),
+        }
+    }
     let path = get_auth_file_path(ctx)?;
     if path.exists() {
         fs::remove_file(&path)?;
@@ -163,6 +327,11 @@ fn refresh_tokens_with_context(ctx: &WorkspaceContext) -> Result<AuthTokens> {
         })
         .send()?;
 
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        // The refresh token was revoked or expired; a new login is required.
+        clear_tokens_with_context(ctx)?;
+        anyhow::bail!("Session expired. Run `pcb auth login` to re-authenticate.");
+    }
     if !response.status().is_success() {
         anyhow::bail!("Token refresh failed: {}", response.status());
     }
@@ -203,6 +372,13 @@ fn get_valid_token_with_sources(
     aws_service_token: impl Fn(&WorkspaceContext) -> Result<AwsDiodeToken>,
     refresh_tokens: impl Fn(&WorkspaceContext) -> Result<AuthTokens>,
 ) -> Result<String> {
+    if let Some(service_token) = ServiceToken::from_env() {
+        if service_token.is_expired() {
+            anyhow::bail!("{SERVICE_TOKEN_ENV} has expired; issue a new service token");
+        }
+        return Ok(service_token.token);
+    }
+
     let aws_token = || {
         aws_service_token(ctx)
             .map(|token| token.access_token)
@@ -346,9 +522,22 @@ pub fn status_with_context(ctx: &WorkspaceContext) -> Result<()> {
         return Ok(());
     }
 
+    if let Some(token) = ServiceToken::from_env() {
+        println!("  Status: Service token");
+        println!("  Method: {SERVICE_TOKEN_ENV}");
+        if let Some(scopes) = &token.scopes {
+            println!("  Scopes: {}", scopes.join(", "));
+        }
+        if let Some(expires_at) = token.expires_at {
+            println!("  Token expires in: {}", time_until_expiry(expires_at));
+        }
+        return Ok(());
+    }
+
     match load_tokens_with_context(ctx)? {
         Some(tokens) => {
             println!("  Status: Logged in");
+            println!("  Storage: {}", TokenStorage::current().display_name());
             if let Some(email) = &tokens.email {
                 println!("  Email: {}", email);
             }
@@ -521,19 +710,71 @@ mod tests {
         }
     }
 
-    fn unix_now() -> i64 {
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64
+    struct IsolatedEnv {
+        _config_dir: EnvGuard,
+        _storage: EnvGuard,
+        _service_token: EnvGuard,
     }
 
-    fn isolated_context() -> (tempfile::TempDir, EnvGuard, WorkspaceContext) {
+    fn isolated_context() -> (tempfile::TempDir, IsolatedEnv, WorkspaceContext) {
         let tempdir = tempfile::tempdir().unwrap();
-        let guard = EnvGuard::set("PCB_CONFIG_DIR", tempdir.path());
+        let guard = IsolatedEnv {
+            _config_dir: EnvGuard::set("PCB_CONFIG_DIR", tempdir.path()),
+            _storage: EnvGuard::set_str(AUTH_STORAGE_ENV, "file"),
+            _service_token: EnvGuard::set_str(SERVICE_TOKEN_ENV, ""),
+        };
         (tempdir, guard, WorkspaceContext::default())
     }
 
+    fn jwt(claims: &str) -> String {
+        use base64::Engine;
+        let encode = |s: &str| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(s);
+        format!("{}.{}.sig", encode(r#"{"alg":"none"}"#), encode(claims))
+    }
+
+    #[test]
+    fn service_token_reads_scopes_and_expiry_from_jwt() {
+        let token = ServiceToken::parse(&jwt(r#"{"scope":"search scan","exp":4102444800}"#));
+        assert_eq!(
+            token.scopes,
+            Some(vec!["search".to_string(), "scan".to_string()])
+        );
+        assert!(token.has_scope("scan"));
+        assert!(!token.has_scope("publish"));
+        assert!(!token.is_expired());
+
+        let expired = ServiceToken::parse(&jwt(r#"{"scopes":["search"],"exp":1}"#));
+        assert!(expired.is_expired());
+
+        let opaque = ServiceToken::parse("opaque-token");
+        assert_eq!(opaque.scopes, None);
+        assert!(opaque.has_scope("publish"));
+    }
+
+    #[test]
+    #[serial]
+    fn service_token_env_takes_precedence_over_stored_tokens() {
+        let (_tempdir, _guard, ctx) = isolated_context();
+        save_tokens(
+            &ctx,
+            "local-token",
+            "refresh-token",
+            unix_now() + 3600,
+            None,
+        )
+        .unwrap();
+        let _token_guard = EnvGuard::set_str(SERVICE_TOKEN_ENV, "ci-token");
+
+        let token = get_valid_token_with_sources(
+            &ctx,
+            |_| anyhow::bail!("aws should not be called"),
+            |_| anyhow::bail!("refresh should not be called"),
+        )
+        .unwrap();
+
+        assert_eq!(token, "ci-token");
+    }
+
     fn service_token(access_token: &str) -> AwsDiodeToken {
         AwsDiodeToken {
             access_token: access_token.to_string(),
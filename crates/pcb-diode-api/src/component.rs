@@ -4,16 +4,17 @@ use colored::Colorize;
 use indicatif::ProgressBar;
 use inquire::{Select, Text};
 use pcb_eda::kicad::metadata::SymbolMetadata;
-use pcb_sexpr::formatter::{FormatMode, format_tree};
+use pcb_sexpr::formatter::{KicadFileKind, format_kicad};
 use pcb_sexpr::kicad::symbol::{
     find_symbol_index, kicad_symbol_lib_items_mut, rewrite_symbol_properties, symbol_names,
     symbol_properties,
 };
-use pcb_zen_core::config::find_workspace_root;
+use pcb_zen_core::config::{AssetProvenance, find_workspace_root};
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -262,6 +263,36 @@ fn show_component_added(workspace_root: &Path, result: &AddComponentResult) {
     if let Some(module_url) = infer_component_module_url(workspace_root, &result.component_path) {
         eprintln!("  Use with: Module(\"{}\")", module_url);
     }
+    if let Some(footprint_path) = &result.footprint_path {
+        show_footprint_preview(footprint_path);
+    }
+}
+
+/// Draw the footprint inline on stderr in terminals that support images.
+/// Previews are best effort; failures are only logged.
+fn show_footprint_preview(footprint_path: &Path) {
+    const FOOTPRINT_PREVIEW_PX: u32 = 320;
+
+    if !std::io::stderr().is_terminal()
+        || !crate::registry::tui::image::ImageProtocol::detect().is_supported()
+    {
+        return;
+    }
+    let preview = pcb_eda::KicadFootprint::from_file(footprint_path).and_then(|footprint| {
+        pcb_eda::kicad::footprint_preview::footprint_png(&footprint, FOOTPRINT_PREVIEW_PX)
+    });
+    match preview {
+        Ok(png) => {
+            let mut stderr = std::io::stderr().lock();
+            if pcb_ir::render::write_kitty_png(&mut stderr, &png)
+                .and_then(|()| writeln!(stderr))
+                .is_err()
+            {
+                log::debug!("Failed to write footprint preview");
+            }
+        }
+        Err(e) => log::debug!("No footprint preview for {}: {e}", footprint_path.display()),
+    }
 }
 
 fn component_display_path<'a>(workspace_root: &'a Path, component_path: &'a Path) -> &'a Path {
@@ -470,6 +501,7 @@ pub fn add_component_to_workspace(
         return Ok(AddComponentResult {
             component_path: zen_file,
             datasheet_path: None,
+            footprint_path: None,
             part_number: identity.part_number,
             already_exists: true,
         });
@@ -658,6 +690,15 @@ pub fn add_component_to_workspace(
         }
     }
 
+    // Hash downloaded assets before finalize embeds (and removes) the STEP file.
+    let provenance = asset_provenance(
+        &component_dir,
+        &files,
+        download.step_url.as_deref(),
+        download.datasheet_url.as_deref(),
+        datasheet_outcome,
+    )?;
+
     // Finalize: embed STEP, generate .zen file
     finalize_component(
         &component_dir,
@@ -673,9 +714,17 @@ pub fn add_component_to_workspace(
         );
     }
 
+    if !provenance.is_empty() {
+        pcb_zen::provenance::record_assets(&component_dir, provenance)?;
+    }
+
     Ok(AddComponentResult {
         component_path: zen_file,
         datasheet_path: datasheet_ref.map(|_| files.pdf_path.clone()),
+        footprint_path: files
+            .footprint_path
+            .is_file()
+            .then(|| files.footprint_path.clone()),
         part_number: identity.part_number,
         already_exists: false,
     })
@@ -684,10 +733,46 @@ pub fn add_component_to_workspace(
 pub struct AddComponentResult {
     pub component_path: PathBuf,
     pub datasheet_path: Option<PathBuf>,
+    pub footprint_path: Option<PathBuf>,
     pub part_number: String,
     pub already_exists: bool,
 }
 
+/// Provenance entries for the STEP model and fallback datasheet downloaded into `component_dir`.
+fn asset_provenance(
+    component_dir: &Path,
+    files: &ComponentFilePaths,
+    step_url: Option<&str>,
+    datasheet_url: Option<&str>,
+    datasheet_outcome: DatasheetProcessingOutcome,
+) -> Result<Vec<AssetProvenance>> {
+    let mut assets = Vec::new();
+    let mut record = |path: &Path, url: &str, embedded: bool| -> Result<()> {
+        let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let rel_path = path.strip_prefix(component_dir).unwrap_or(path);
+        assets.push(AssetProvenance {
+            path: rel_path.to_string_lossy().replace('\\', "/"),
+            url: url.to_string(),
+            sha256: pcb_zen::provenance::sha256_hex(&bytes),
+            embedded,
+        });
+        Ok(())
+    };
+
+    if let Some(url) = step_url
+        && files.step_path.exists()
+    {
+        // finalize_component embeds the model into the footprint when both exist.
+        record(&files.step_path, url, files.footprint_path.exists())?;
+    }
+    if let Some(url) = datasheet_url
+        && datasheet_outcome == DatasheetProcessingOutcome::FallbackDownloaded
+    {
+        record(&files.pdf_path, url, false)?;
+    }
+    Ok(assets)
+}
+
 /// Build component directory path: components/<manufacturer>/<mpn>/
 fn component_dir_path(workspace_root: &Path, manufacturer: Option<&str>, mpn: &str) -> PathBuf {
     let sanitized_mfr = manufacturer
@@ -817,7 +902,7 @@ fn rewrite_symbol_component_metadata_text(
     }
 
     rewrite_symbol_properties(symbol_items, &next_properties);
-    Ok(format_tree(&parsed, FormatMode::Normal))
+    Ok(format_kicad(&parsed, KicadFileKind::Symbol))
 }
 
 fn only_symbol_in_library_mut<'a>(
@@ -1266,6 +1351,7 @@ pub fn execute(args: SearchArgs) -> Result<()> {
     }
 
     let registry_selectors = args.registries;
+    crate::auth::require_scope("search")?;
 
     // Search mode (local registry database with TUI or API)
     let query = args.query.as_deref().unwrap_or("");
@@ -1339,7 +1425,7 @@ pub fn execute_component_from_id(
 /// Execute the component search TUI in WebComponents mode only (no registry access)
 pub fn execute_web_components_tui(workspace_root: &Path) -> Result<()> {
     let tui_result = crate::registry::tui::run_web_components_only()?;
-    if let Some(component) = tui_result.selected_component {
+    for component in tui_result.selected_components {
         handle_tui_component_selection(component, workspace_root)?;
     }
     Ok(())
@@ -1355,15 +1441,18 @@ fn execute_search(
 ) -> Result<()> {
     use crate::registry::tui::SearchMode;
 
-    // If no query provided, launch interactive TUI
-    if query.is_empty() {
+    // Launch the interactive TUI without a query, or with one when attached to a
+    // terminal. Pipes and `--json` get plain output.
+    let interactive = !json && std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+    if query.is_empty() || interactive {
         let tui_result = crate::registry::tui::run_with_mode_and_registry_index(
             mode,
             registry_index.map(Path::to_path_buf),
             registry_selectors.to_vec(),
             Some(workspace_root.to_path_buf()),
+            query,
         )?;
-        if let Some(component) = tui_result.selected_component {
+        for component in tui_result.selected_components {
             handle_tui_component_selection(component, workspace_root)?;
         }
         return Ok(());
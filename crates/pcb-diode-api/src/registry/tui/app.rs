@@ -32,6 +32,7 @@ use crossterm::{
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use pcb_eda::kicad::footprint_preview::footprint_png;
 use ratatui::{Terminal, backend::CrosstermBackend, widgets::ListState};
 use ratatui_image::picker::Picker;
 use std::io::{self, Stdout};
@@ -346,6 +347,41 @@ pub enum DownloadState {
     Failed(String),
 }
 
+/// Sort order for web component results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResultSort {
+    /// Order returned by the search API
+    #[default]
+    Relevance,
+    PartNumber,
+    Manufacturer,
+    Package,
+    /// Highest known stock first; parts without availability data last
+    Stock,
+}
+
+impl ResultSort {
+    pub fn next(self) -> Self {
+        match self {
+            ResultSort::Relevance => ResultSort::PartNumber,
+            ResultSort::PartNumber => ResultSort::Manufacturer,
+            ResultSort::Manufacturer => ResultSort::Package,
+            ResultSort::Package => ResultSort::Stock,
+            ResultSort::Stock => ResultSort::Relevance,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ResultSort::Relevance => "relevance",
+            ResultSort::PartNumber => "part number",
+            ResultSort::Manufacturer => "manufacturer",
+            ResultSort::Package => "package",
+            ResultSort::Stock => "stock",
+        }
+    }
+}
+
 /// Command palette commands
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Command {
@@ -353,6 +389,10 @@ pub enum Command {
     ToggleDebugPanels,
     UpdateRegistryIndex,
     OpenInDigikey,
+    SortResults,
+    FilterPackage,
+    ToggleInStock,
+    OpenDatasheet,
 }
 
 impl Command {
@@ -361,6 +401,10 @@ impl Command {
         Command::ToggleDebugPanels,
         Command::UpdateRegistryIndex,
         Command::OpenInDigikey,
+        Command::SortResults,
+        Command::FilterPackage,
+        Command::ToggleInStock,
+        Command::OpenDatasheet,
     ];
 
     /// Short machine-readable name
@@ -370,6 +414,10 @@ impl Command {
             Command::ToggleDebugPanels => "toggle-debug-panels",
             Command::UpdateRegistryIndex => "update-registry-index",
             Command::OpenInDigikey => "open-in-digikey",
+            Command::SortResults => "sort-results",
+            Command::FilterPackage => "filter-package",
+            Command::ToggleInStock => "toggle-in-stock",
+            Command::OpenDatasheet => "open-datasheet",
         }
     }
 
@@ -382,6 +430,10 @@ impl Command {
             }
             Command::UpdateRegistryIndex => "Force re-download the registry index",
             Command::OpenInDigikey => "Open the selected part on Digikey",
+            Command::SortResults => "Cycle the sort column of component results",
+            Command::FilterPackage => "Cycle the package filter of component results",
+            Command::ToggleInStock => "Hide component results known to be out of stock",
+            Command::OpenDatasheet => "Open the selected component's datasheet",
         }
     }
 
@@ -406,22 +458,27 @@ impl Command {
     }
 
     /// Check if command is enabled given current app state
-    pub fn is_enabled(
-        &self,
-        selected_symbol: Option<&RegistrySymbol>,
-        available_modes: &[SearchMode],
-    ) -> bool {
+    pub fn is_enabled(&self, app: &App) -> bool {
         match self {
-            Command::CycleMode => available_modes.len() > 1,
-            Command::UpdateRegistryIndex => available_modes.iter().any(|m| m.requires_registry()),
+            Command::CycleMode => app.available_modes.len() > 1,
+            Command::UpdateRegistryIndex => {
+                app.available_modes.iter().any(|m| m.requires_registry())
+            }
             Command::ToggleDebugPanels => true,
             Command::OpenInDigikey => {
                 // Only enabled if we have a component with DigiKey product URL
-                selected_symbol
+                app.selected_symbol
+                    .as_ref()
                     .and_then(|p| p.digikey.as_ref())
                     .and_then(|dk| dk.product_url.as_ref())
                     .is_some()
             }
+            Command::SortResults | Command::FilterPackage | Command::ToggleInStock => {
+                app.mode == SearchMode::WebComponents
+            }
+            Command::OpenDatasheet => app
+                .selected_web_component()
+                .is_some_and(|result| !result.datasheets.is_empty()),
         }
     }
 }
@@ -470,6 +527,10 @@ pub struct App {
     pub selected_symbol: Option<RegistrySymbol>,
     /// Cached selected KiCad symbol details (fetched asynchronously)
     pub selected_kicad_symbol: Option<KicadSymbol>,
+    /// `kicad-footprints` directory of the workspace's stdlib, for previews
+    footprints_dir: Option<PathBuf>,
+    /// PNG preview of a KiCad symbol's footprint, by symbol id
+    kicad_footprint_preview: Option<(i64, Vec<u8>)>,
     /// Cached dependencies/dependents for selected registry module
     pub module_relations: ModuleRelations,
     /// Channel to send detail requests to worker
@@ -506,8 +567,18 @@ pub struct App {
     pub component_search_started: Instant,
     /// List state for component results (handles selection + scroll)
     pub component_list_state: ListState,
-    /// Selected component to download after TUI exits (WebComponents mode)
-    pub selected_component_for_download: Option<crate::component::ComponentSearchResult>,
+    /// Unfiltered, unsorted results of the last component search
+    component_results_unfiltered: Vec<crate::component::ComponentSearchResult>,
+    /// Sort order applied to component results
+    pub result_sort: ResultSort,
+    /// Only show component results with this package category
+    pub package_filter: Option<String>,
+    /// Hide component results known to be out of stock
+    pub in_stock_only: bool,
+    /// Components marked for download with Tab, in marking order
+    pub marked_components: Vec<crate::component::ComponentSearchResult>,
+    /// Components to download after TUI exits (WebComponents mode)
+    pub selected_components_for_download: Vec<crate::component::ComponentSearchResult>,
     /// Available search modes (determines which modes can be cycled to)
     pub available_modes: Vec<SearchMode>,
     /// Registry search scope, when registry modes are available.
@@ -538,6 +609,8 @@ pub struct Preflight {
     pub registry_scope: Option<RegistrySearchScope>,
     /// Pre-fetched KiCad symbols index metadata (avoids duplicate request during download)
     pub kicad_symbols_metadata: Option<KicadSymbolsIndexMetadata>,
+    /// `kicad-footprints` directory of the workspace's stdlib, for previews
+    pub footprints_dir: Option<PathBuf>,
     /// Non-fatal startup warning to surface in the TUI.
     pub warning: Option<String>,
 }
@@ -549,6 +622,7 @@ impl Preflight {
             available_modes: vec![SearchMode::WebComponents],
             registry_scope: None,
             kicad_symbols_metadata: None,
+            footprints_dir: None,
             warning: None,
         }
     }
@@ -561,6 +635,7 @@ impl App {
             available_modes,
             registry_scope,
             kicad_symbols_metadata,
+            footprints_dir,
             warning,
         } = preflight;
         let (query_tx, query_rx) = mpsc::channel::<SearchQuery>();
@@ -633,6 +708,8 @@ impl App {
             selected_module: None,
             selected_symbol: None,
             selected_kicad_symbol: None,
+            footprints_dir,
+            kicad_footprint_preview: None,
             module_relations: ModuleRelations::default(),
             detail_tx,
             detail_rx,
@@ -651,7 +728,12 @@ impl App {
             component_searching: false,
             component_search_started: Instant::now(),
             component_list_state: ListState::default(),
-            selected_component_for_download: None,
+            component_results_unfiltered: Vec::new(),
+            result_sort: ResultSort::default(),
+            package_filter: None,
+            in_stock_only: false,
+            marked_components: Vec::new(),
+            selected_components_for_download: Vec::new(),
             available_modes,
             registry_scope,
             availability_tx,
@@ -935,7 +1017,47 @@ impl App {
             self.pending_detail_for = None;
             self.detail_request_started = None;
             self.enqueue_selected_image_request();
+            self.update_kicad_footprint_preview();
+        }
+    }
+
+    /// Render the selected KiCad symbol's footprint, when the workspace's
+    /// stdlib has it. Footprints are local files, so this is done inline.
+    fn update_kicad_footprint_preview(&mut self) {
+        const FOOTPRINT_PREVIEW_PX: u32 = 400;
+
+        let Some(symbol) = self.selected_kicad_symbol.as_ref() else {
+            return;
+        };
+        if self.picker.is_none()
+            || self
+                .kicad_footprint_preview
+                .as_ref()
+                .is_some_and(|(id, _)| *id == symbol.id)
+        {
+            return;
         }
+        let Some(path) = self.footprints_dir.as_ref().map(|dir| {
+            dir.join(format!("{}.pretty", symbol.footprint_library))
+                .join(format!("{}.kicad_mod", symbol.footprint_name))
+        }) else {
+            return;
+        };
+        let preview = pcb_eda::KicadFootprint::from_file(&path)
+            .and_then(|footprint| footprint_png(&footprint, FOOTPRINT_PREVIEW_PX));
+        self.kicad_footprint_preview = match preview {
+            Ok(png) => Some((symbol.id, png)),
+            Err(e) => {
+                log::debug!("No footprint preview for {}: {e}", path.display());
+                None
+            }
+        };
+    }
+
+    /// Footprint preview of the selected KiCad symbol, if one was rendered.
+    pub(super) fn selected_kicad_footprint_preview(&self) -> Option<&[u8]> {
+        let (id, png) = self.kicad_footprint_preview.as_ref()?;
+        (self.selected_kicad_symbol.as_ref()?.id == *id).then_some(png.as_slice())
     }
 
     fn enqueue_selected_image_request(&mut self) {
@@ -970,7 +1092,17 @@ impl App {
                     self.toast = Some(Toast::error(error.clone(), Duration::from_secs(5)));
                 }
 
+                self.component_results_unfiltered = results.results.clone();
                 self.component_results = results;
+                if let Some(package) = &self.package_filter
+                    && !self
+                        .component_results_unfiltered
+                        .iter()
+                        .any(|r| r.package_category.as_ref() == Some(package))
+                {
+                    self.package_filter = None;
+                }
+                self.apply_component_view();
 
                 // Reset selection to first item
                 self.component_list_state = ListState::default();
@@ -1050,9 +1182,167 @@ impl App {
 
     /// Poll for availability responses from worker (non-blocking)
     fn poll_availability_responses(&mut self) {
+        let mut updated = false;
         while let Ok(resp) = self.availability_rx.try_recv() {
             self.availability_store.apply_response(resp);
+            updated = true;
+        }
+        if updated
+            && self.mode == SearchMode::WebComponents
+            && (self.in_stock_only || self.result_sort == ResultSort::Stock)
+        {
+            self.apply_component_view();
+        }
+    }
+
+    /// Best known stock for a component result, if availability has loaded.
+    fn component_stock(&self, result: &crate::component::ComponentSearchResult) -> Option<i32> {
+        let (availability, _) = self.availability_for_lookup(
+            Some(result.part_number.as_str()),
+            result.manufacturer.as_deref(),
+        );
+        let availability = availability?;
+        [&availability.us, &availability.global]
+            .into_iter()
+            .flatten()
+            .map(|summary| summary.stock)
+            .max()
+            .or(Some(0))
+    }
+
+    /// Rebuild the visible component results from the unfiltered results,
+    /// applying the package filter, stock filter, and sort order. Keeps the
+    /// current selection when it is still visible.
+    fn apply_component_view(&mut self) {
+        let selected_id = self
+            .selected_web_component()
+            .map(|result| result.component_id.clone());
+
+        let mut results: Vec<_> = self
+            .component_results_unfiltered
+            .iter()
+            .filter(|r| {
+                self.package_filter
+                    .as_ref()
+                    .is_none_or(|package| r.package_category.as_ref() == Some(package))
+            })
+            .filter(|r| !self.in_stock_only || self.component_stock(r) != Some(0))
+            .cloned()
+            .collect();
+
+        match self.result_sort {
+            ResultSort::Relevance => {}
+            ResultSort::PartNumber => results.sort_by(|a, b| a.part_number.cmp(&b.part_number)),
+            ResultSort::Manufacturer => results.sort_by(|a, b| a.manufacturer.cmp(&b.manufacturer)),
+            ResultSort::Package => {
+                results.sort_by(|a, b| a.package_category.cmp(&b.package_category))
+            }
+            ResultSort::Stock => {
+                results.sort_by_key(|r| std::cmp::Reverse(self.component_stock(r)))
+            }
+        }
+        self.component_results.results = results;
+
+        let index = selected_id
+            .and_then(|id| {
+                self.component_results
+                    .results
+                    .iter()
+                    .position(|r| r.component_id == id)
+            })
+            .or_else(|| (!self.component_results.results.is_empty()).then_some(0));
+        self.component_list_state.select(index);
+    }
+
+    pub fn selected_web_component(&self) -> Option<&crate::component::ComponentSearchResult> {
+        if self.mode != SearchMode::WebComponents {
+            return None;
         }
+        self.component_list_state
+            .selected()
+            .and_then(|idx| self.component_results.results.get(idx))
+    }
+
+    pub fn is_marked(&self, result: &crate::component::ComponentSearchResult) -> bool {
+        self.marked_components
+            .iter()
+            .any(|marked| marked.component_id == result.component_id)
+    }
+
+    /// Toggle the download mark on the selected component (WebComponents mode)
+    fn toggle_mark_selected(&mut self) {
+        let Some(result) = self.selected_web_component().cloned() else {
+            return;
+        };
+        if self.is_marked(&result) {
+            self.marked_components
+                .retain(|marked| marked.component_id != result.component_id);
+        } else {
+            self.marked_components.push(result);
+        }
+    }
+
+    fn cycle_result_sort(&mut self) {
+        self.result_sort = self.result_sort.next();
+        self.apply_component_view();
+        self.toast = Some(Toast::new(
+            format!("Sorted by {}", self.result_sort.display_name()),
+            Duration::from_secs(2),
+        ));
+    }
+
+    fn cycle_package_filter(&mut self) {
+        let packages: std::collections::BTreeSet<&String> = self
+            .component_results_unfiltered
+            .iter()
+            .filter_map(|r| r.package_category.as_ref())
+            .collect();
+        let next = match &self.package_filter {
+            None => packages.first().map(|p| p.to_string()),
+            Some(current) => packages
+                .range::<&String, _>((
+                    std::ops::Bound::Excluded(current),
+                    std::ops::Bound::Unbounded,
+                ))
+                .next()
+                .map(|p| p.to_string()),
+        };
+        self.package_filter = next;
+        self.apply_component_view();
+        let message = match &self.package_filter {
+            Some(package) => format!("Package: {package}"),
+            None => "Package filter cleared".to_string(),
+        };
+        self.toast = Some(Toast::new(message, Duration::from_secs(2)));
+    }
+
+    fn toggle_in_stock_filter(&mut self) {
+        self.in_stock_only = !self.in_stock_only;
+        self.apply_component_view();
+        let state = if self.in_stock_only { "on" } else { "off" };
+        self.toast = Some(Toast::new(
+            format!("In-stock filter {state}"),
+            Duration::from_secs(2),
+        ));
+    }
+
+    fn open_selected_datasheet(&mut self) {
+        let Some(url) = self
+            .selected_web_component()
+            .and_then(|result| result.datasheets.first())
+            .cloned()
+        else {
+            self.toast = Some(Toast::error(
+                "No datasheet for this part".to_string(),
+                Duration::from_secs(2),
+            ));
+            return;
+        };
+        self.toast = Some(if open::that(&url).is_ok() {
+            Toast::new("Opened datasheet".to_string(), Duration::from_secs(2))
+        } else {
+            Toast::error("Failed to open browser".to_string(), Duration::from_secs(2))
+        });
     }
 
     /// Poll for registry image responses from workers (non-blocking).
@@ -1218,13 +1508,13 @@ impl App {
         }
     }
 
-    /// Select component for download and exit TUI (New mode)
+    /// Select marked components (or the highlighted one) for download and exit TUI
     fn select_component_for_download(&mut self) {
-        let selected_index = self.component_list_state.selected();
-        if let Some(idx) = selected_index
-            && let Some(result) = self.component_results.results.get(idx)
-        {
-            self.selected_component_for_download = Some(result.clone());
+        if !self.marked_components.is_empty() {
+            self.selected_components_for_download = std::mem::take(&mut self.marked_components);
+            self.should_quit = true;
+        } else if let Some(result) = self.selected_web_component().cloned() {
+            self.selected_components_for_download = vec![result];
             self.should_quit = true;
         }
     }
@@ -1412,6 +1702,10 @@ impl App {
                     ));
                 }
             }
+            Command::SortResults => self.cycle_result_sort(),
+            Command::FilterPackage => self.cycle_package_filter(),
+            Command::ToggleInStock => self.toggle_in_stock_filter(),
+            Command::OpenDatasheet => self.open_selected_datasheet(),
         }
     }
 
@@ -1472,7 +1766,7 @@ impl App {
                         if let Some(&cmd) = self
                             .command_palette_filtered
                             .get(self.command_palette_index)
-                            && cmd.is_enabled(self.selected_symbol.as_ref(), &self.available_modes)
+                            && cmd.is_enabled(self)
                         {
                             self.close_command_palette();
                             self.execute_command(cmd);
@@ -1509,6 +1803,19 @@ impl App {
                     self.scroll_up(1)
                 }
                 (KeyCode::Enter, _) => self.handle_enter(),
+                (KeyCode::Tab, _) if self.mode == SearchMode::WebComponents => {
+                    self.toggle_mark_selected()
+                }
+                (KeyCode::Char('t'), KeyModifiers::CONTROL)
+                    if self.mode == SearchMode::WebComponents =>
+                {
+                    self.cycle_result_sort()
+                }
+                (KeyCode::Char('d'), KeyModifiers::CONTROL)
+                    if self.mode == SearchMode::WebComponents =>
+                {
+                    self.open_selected_datasheet()
+                }
                 (KeyCode::Char('b'), KeyModifiers::CONTROL) | (KeyCode::PageUp, _) => {
                     self.scroll_down(20)
                 }
@@ -1582,8 +1889,8 @@ fn ordered_availability_requests(
 
 /// Result from running the TUI
 pub struct TuiResult {
-    /// Component selected for download (WebComponents mode only)
-    pub selected_component: Option<crate::component::ComponentSearchResult>,
+    /// Components selected for download (WebComponents mode only)
+    pub selected_components: Vec<crate::component::ComponentSearchResult>,
 }
 
 /// Determine the preflight configuration based on auth and local-index access
@@ -1609,6 +1916,7 @@ fn compute_preflight(
             available_modes,
             registry_scope,
             kicad_symbols_metadata: None,
+            footprints_dir: workspace_footprints_dir(workspace_root),
             warning: None,
         });
     }
@@ -1653,10 +1961,15 @@ fn compute_preflight(
         available_modes,
         registry_scope,
         kicad_symbols_metadata,
+        footprints_dir: workspace_footprints_dir(workspace_root),
         warning,
     })
 }
 
+fn workspace_footprints_dir(workspace_root: Option<&Path>) -> Option<PathBuf> {
+    workspace_root.map(|root| pcb_zen_core::workspace_stdlib_root(root).join("kicad-footprints"))
+}
+
 fn current_workspace_root() -> Option<PathBuf> {
     let cwd = std::env::current_dir().ok()?;
     let file_provider = pcb_zen_core::DefaultFileProvider::new();
@@ -1667,21 +1980,23 @@ fn current_workspace_root() -> Option<PathBuf> {
 pub fn run() -> Result<TuiResult> {
     let workspace_root = current_workspace_root();
     let preflight = compute_preflight(None, &[], workspace_root.as_deref())?;
-    run_with_preflight(preflight)
+    run_with_preflight(preflight, "")
 }
 
 /// Run the TUI with an explicit starting mode
 /// - If mode is Some, use that mode (but available modes still depend on registry access)
 /// - If mode is None, use default behavior (registry:modules if registry access available, web:components otherwise)
 pub fn run_with_mode(mode: Option<SearchMode>) -> Result<TuiResult> {
-    run_with_mode_and_registry_index(mode, None, Vec::new(), current_workspace_root())
+    run_with_mode_and_registry_index(mode, None, Vec::new(), current_workspace_root(), "")
 }
 
+/// Run the TUI with an explicit mode, registry index, and initial query.
 pub fn run_with_mode_and_registry_index(
     mode: Option<SearchMode>,
     registry_db_path_override: Option<PathBuf>,
     registry_selectors: Vec<String>,
     workspace_root: Option<PathBuf>,
+    initial_query: &str,
 ) -> Result<TuiResult> {
     let mut preflight = compute_preflight(
         registry_db_path_override,
@@ -1698,7 +2013,7 @@ pub fn run_with_mode_and_registry_index(
             // This handles the case where user requests registry:modules but isn't auth'd
         }
     }
-    run_with_preflight(preflight)
+    run_with_preflight(preflight, initial_query)
 }
 
 /// Run the TUI in WebComponents mode only (for pcb new component)
@@ -1706,10 +2021,10 @@ pub fn run_web_components_only() -> Result<TuiResult> {
     // Check authentication first
     crate::auth::get_api_token()?;
 
-    run_with_preflight(Preflight::web_only())
+    run_with_preflight(Preflight::web_only(), "")
 }
 
-fn run_with_preflight(preflight: Preflight) -> Result<TuiResult> {
+fn run_with_preflight(preflight: Preflight, initial_query: &str) -> Result<TuiResult> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, SetCursorStyle::BlinkingBar)?;
@@ -1717,6 +2032,11 @@ fn run_with_preflight(preflight: Preflight) -> Result<TuiResult> {
     let mut terminal = Terminal::new(backend)?;
 
     let mut app = App::new(preflight);
+    if !initial_query.is_empty() {
+        app.search_input.text = initial_query.to_string();
+        app.search_input.move_end();
+        app.on_input_change();
+    }
 
     let result = run_loop(&mut terminal, &mut app);
 
@@ -1731,7 +2051,7 @@ fn run_with_preflight(preflight: Preflight) -> Result<TuiResult> {
     result?;
 
     Ok(TuiResult {
-        selected_component: app.selected_component_for_download,
+        selected_components: app.selected_components_for_download,
     })
 }
 
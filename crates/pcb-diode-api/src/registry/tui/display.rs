@@ -278,6 +278,8 @@ pub struct WebComponentDisplay {
     pub manufacturer: Option<String>,
     pub package: Option<String>,
     pub description: Option<String>,
+    /// Marked for multi-select download in the TUI
+    pub marked: bool,
 }
 
 impl WebComponentDisplay {
@@ -302,6 +304,7 @@ impl WebComponentDisplay {
             manufacturer: result.manufacturer.clone(),
             package: result.package_category.clone(),
             description: result.description.clone(),
+            marked: false,
         }
     }
 
@@ -400,11 +403,18 @@ impl WebComponentDisplay {
             base_style.fg(Color::Green)
         };
 
-        let line1 = Line::from(vec![
+        let mut line1_spans = vec![
             Span::styled(prefix.to_string(), prefix_style),
             Span::styled(" ".to_string(), base_style),
-            Span::styled(self.path.clone(), path_style),
-        ]);
+        ];
+        if self.marked {
+            line1_spans.push(Span::styled(
+                "● ".to_string(),
+                base_style.fg(Color::Magenta).add_modifier(Modifier::BOLD),
+            ));
+        }
+        line1_spans.push(Span::styled(self.path.clone(), path_style));
+        let line1 = Line::from(line1_spans);
 
         let dim_bracket = Style::default()
             .fg(Color::DarkGray)
@@ -1,7 +1,7 @@
 mod app;
 mod availability;
 pub(crate) mod display;
-mod image;
+pub(crate) mod image;
 pub mod search;
 mod ui;
 
@@ -213,7 +213,7 @@ fn render_results_count(frame: &mut Frame, app: &App, area: Rect) {
                     Style::default().fg(Color::DarkGray),
                 )])
             } else {
-                Line::from(vec![
+                let mut spans = vec![
                     Span::styled(
                         format!("  {} results ", count),
                         Style::default().fg(Color::DarkGray),
@@ -224,7 +224,9 @@ fn render_results_count(frame: &mut Frame, app: &App, area: Rect) {
                             .fg(Color::Yellow)
                             .add_modifier(Modifier::DIM),
                     ),
-                ])
+                ];
+                spans.extend(component_view_spans(app));
+                Line::from(spans)
             }
         }
     };
@@ -233,6 +235,31 @@ fn render_results_count(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(para, area);
 }
 
+/// Sort, filter, and mark indicators for web component results
+fn component_view_spans(app: &App) -> Vec<Span<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut spans = Vec::new();
+    if app.result_sort != super::app::ResultSort::Relevance {
+        spans.push(Span::styled(
+            format!(" · sort: {}", app.result_sort.display_name()),
+            dim,
+        ));
+    }
+    if let Some(package) = &app.package_filter {
+        spans.push(Span::styled(format!(" · package: {package}"), dim));
+    }
+    if app.in_stock_only {
+        spans.push(Span::styled(" · in stock".to_string(), dim));
+    }
+    if !app.marked_components.is_empty() {
+        spans.push(Span::styled(
+            format!(" · {} marked", app.marked_components.len()),
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+    spans
+}
+
 /// Render the four debug panels (Semantic / Trigram / Word / Docs) for any hit type.
 fn render_panel_set<T>(
     frame: &mut Frame,
@@ -455,7 +482,8 @@ fn render_component_list(frame: &mut Frame, app: &mut App, area: Rect) {
                 Style::default()
             };
 
-            let display = WebComponentDisplay::from_component(result);
+            let mut display = WebComponentDisplay::from_component(result);
+            display.marked = app.is_marked(result);
             let lines = display.to_tui_lines(is_selected, base_style, prefix_style);
 
             let item = ListItem::new(lines);
@@ -981,12 +1009,11 @@ fn render_kicad_symbol_details(frame: &mut Frame, app: &mut App, symbol: &KicadS
         }
     }
 
+    let symbol_image = symbol.image_data.as_deref().filter(|data| !data.is_empty());
+    let footprint_image = app.selected_kicad_footprint_preview();
     let has_image = app.image_protocol.is_supported()
         && app.picker.is_some()
-        && symbol
-            .image_data
-            .as_ref()
-            .is_some_and(|data| !data.is_empty());
+        && (symbol_image.is_some() || footprint_image.is_some());
     if has_image {
         let header_height = lines.len() as u16;
         let chunks = Layout::default()
@@ -1000,8 +1027,16 @@ fn render_kicad_symbol_details(frame: &mut Frame, app: &mut App, symbol: &KicadS
             .split(area);
 
         frame.render_widget(Paragraph::new(lines), chunks[0]);
-        if let Some(data) = symbol.image_data.as_deref() {
-            render_image_bytes(frame, app, data, chunks[1]);
+        let images: Vec<&[u8]> = symbol_image.into_iter().chain(footprint_image).collect();
+        let image_areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![
+                Constraint::Ratio(1, images.len() as u32);
+                images.len()
+            ])
+            .split(chunks[1]);
+        for (data, image_area) in images.into_iter().zip(image_areas.iter()) {
+            render_image_bytes(frame, app, data, *image_area);
         }
         let lines = render_kicad_symbol_detail_lines(
             app,
@@ -1721,9 +1756,11 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
 
     // Mode-specific Enter action
     let enter_action = if app.mode.requires_local_index() {
-        "Enter copy"
+        "Enter copy".to_string()
+    } else if app.marked_components.is_empty() {
+        "Enter add".to_string()
     } else {
-        "Enter add"
+        format!("Enter add {}", app.marked_components.len())
     };
 
     let mut spans = vec![
@@ -1746,6 +1783,18 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         ]);
     }
 
+    if app.mode == super::app::SearchMode::WebComponents {
+        spans.extend([
+            Span::styled(" [", bracket),
+            Span::styled("Tab mark", dim),
+            Span::styled("] [", bracket),
+            Span::styled("^t sort", dim),
+            Span::styled("] [", bracket),
+            Span::styled("^d datasheet", dim),
+            Span::styled("]", bracket),
+        ]);
+    }
+
     spans.extend([
         Span::styled(" [", bracket),
         Span::styled("^o cmds", dim),
@@ -1902,7 +1951,7 @@ fn render_command_palette(frame: &mut Frame, app: &App) {
         .enumerate()
         .map(|(i, cmd)| {
             let is_selected = i == app.command_palette_index;
-            let is_enabled = cmd.is_enabled(app.selected_symbol.as_ref(), &app.available_modes);
+            let is_enabled = cmd.is_enabled(app);
 
             if is_selected {
                 let base_bg = Style::default().bg(selection_bg);
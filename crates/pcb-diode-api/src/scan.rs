@@ -339,6 +339,7 @@ pub struct ScanArgs {
 pub fn execute(args: ScanArgs) -> Result<()> {
     let input = parse_scan_input(&args.input)?;
 
+    crate::auth::require_scope("scan")?;
     let token = crate::auth::get_api_token()?;
     let (resolve_input, input_pdf_path) = match input {
         ScanInput::LocalPdf(file) => (
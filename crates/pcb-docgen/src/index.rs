@@ -0,0 +1,258 @@
+//! Persisted documentation index of cached remote packages.
+//!
+//! A remote package never changes once a version of it is in the package
+//! cache, so its docs are generated on first use and stored as JSON under
+//! `~/.pcb/cache/docs_v<N>/<module path>/<version>.json`. LSP completion,
+//! `pcb doc`, and `pcb info` read the same index instead of re-parsing and
+//! re-evaluating every file of the package.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+
+use anyhow::{Context, Result};
+use pcb_zen_core::{SymbolInfo, SymbolKind};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{DocString, FileDoc, LibraryDoc};
+
+/// Bump this when changing the index format. Encoded in the directory name so
+/// a new version just regenerates the indexes.
+const INDEX_VERSION: u32 = 3;
+
+/// Indexes loaded by this process, by index file. Failures are kept too, so
+/// a package that can't be documented isn't re-evaluated on every request.
+static LOADED: LazyLock<Mutex<HashMap<PathBuf, Result<Arc<PackageIndex>, String>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// A remote package version checked out in the package cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedPackage {
+    pub root: PathBuf,
+    pub module_path: String,
+    pub version: String,
+}
+
+impl CachedPackage {
+    /// The cached package containing `path`, or `None` if `path` is not in
+    /// the package cache.
+    pub fn containing(path: &Path) -> Option<Self> {
+        Self::containing_in(path, &pcb_zen::cache_index::cache_base())
+    }
+
+    fn containing_in(path: &Path, cache: &Path) -> Option<Self> {
+        let cache = cache.canonicalize().ok()?;
+        let path = path.canonicalize().ok()?;
+        path.ancestors()
+            .take_while(|dir| *dir != cache)
+            .find_map(|dir| {
+                let version = dir.file_name()?.to_str()?;
+                let module_path = dir.parent()?.strip_prefix(&cache).ok()?;
+                let is_package = !module_path.as_os_str().is_empty()
+                    && semver::Version::parse(version).is_ok()
+                    && dir.join("pcb.toml").is_file();
+                is_package.then(|| Self {
+                    root: dir.to_path_buf(),
+                    module_path: module_path.to_string_lossy().replace('\\', "/"),
+                    version: version.to_string(),
+                })
+            })
+    }
+
+    /// `path` relative to the package root, as stored in the index.
+    pub fn relative_path(&self, path: &Path) -> Option<String> {
+        let path = path.canonicalize().ok()?;
+        Some(
+            path.strip_prefix(&self.root)
+                .ok()?
+                .to_string_lossy()
+                .replace('\\', "/"),
+        )
+    }
+
+    fn index_path(&self, cache: &Path) -> PathBuf {
+        cache
+            .join(format!("docs_v{INDEX_VERSION}"))
+            .join(&self.module_path)
+            .join(format!("{}.json", self.version))
+    }
+}
+
+/// Docs of every file in a package.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageIndex {
+    pub files: Vec<IndexedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedFile {
+    /// Documentation as rendered by `pcb doc`, with a package-relative path
+    pub doc: FileDoc,
+    /// Symbols the file exports to `load()`
+    pub exports: LibraryDoc,
+}
+
+impl PackageIndex {
+    /// The indexed file at the package-relative `rel_path`.
+    pub fn file(&self, rel_path: &str) -> Option<&IndexedFile> {
+        self.files.iter().find(|file| file.doc.path() == rel_path)
+    }
+}
+
+impl IndexedFile {
+    /// Exported symbols with their completion metadata.
+    pub fn symbols(&self) -> Vec<(String, SymbolInfo)> {
+        let info = |kind, type_name: &str, doc: Option<&DocString>| SymbolInfo {
+            kind,
+            parameters: None,
+            source_path: None,
+            type_name: type_name.to_string(),
+            documentation: doc.map(render_docstring),
+        };
+
+        let exports = &self.exports;
+        let functions = exports.functions.iter().map(|function| {
+            let signature = function
+                .signature
+                .trim_start_matches("def ")
+                .trim_end_matches(':');
+            (
+                function.name.clone(),
+                info(SymbolKind::Function, signature, function.doc.as_ref()),
+            )
+        });
+        let types = exports.types.iter().map(|ty| {
+            let kind = if ty.kind == "interface" {
+                SymbolKind::Interface
+            } else {
+                SymbolKind::Class
+            };
+            (ty.name.clone(), info(kind, &ty.kind, None))
+        });
+        let constants = exports.constants.iter().map(|constant| {
+            (
+                constant.name.clone(),
+                info(SymbolKind::Variable, "constant", None),
+            )
+        });
+        functions.chain(types).chain(constants).collect()
+    }
+}
+
+fn render_docstring(doc: &DocString) -> String {
+    if doc.description.is_empty() {
+        doc.summary.clone()
+    } else {
+        format!("{}\n\n{}", doc.summary, doc.description)
+    }
+}
+
+/// The index of `package`, read from the package cache or generated and
+/// saved there on first use.
+pub fn package_index(package: &CachedPackage) -> Result<Arc<PackageIndex>> {
+    let index_path = package.index_path(&pcb_zen::cache_index::cache_base());
+    let mut loaded = LOADED.lock().expect("docs index mutex poisoned");
+    let index = loaded
+        .entry(index_path.clone())
+        .or_insert_with(|| {
+            load_or_build(package, &index_path)
+                .map(Arc::new)
+                .map_err(|e| format!("{e:#}"))
+        })
+        .clone();
+    index.map_err(anyhow::Error::msg)
+}
+
+/// Exported symbols of `path` from the index of its cached package, or `None`
+/// if `path` is not in a cached package or the package can't be indexed.
+pub fn indexed_symbols(path: &Path) -> Option<Vec<(String, SymbolInfo)>> {
+    let package = CachedPackage::containing(path)?;
+    let rel_path = package.relative_path(path)?;
+    let index = package_index(&package)
+        .inspect_err(|e| log::warn!("{e}"))
+        .ok()?;
+    Some(index.file(&rel_path)?.symbols())
+}
+
+fn load_or_build(package: &CachedPackage, index_path: &Path) -> Result<PackageIndex> {
+    if let Ok(json) = fs::read_to_string(index_path)
+        && let Ok(index) = serde_json::from_str(&json)
+    {
+        return Ok(index);
+    }
+
+    let index = build(&package.root).with_context(|| {
+        format!(
+            "Failed to index {}@{}",
+            package.module_path, package.version
+        )
+    })?;
+
+    // Write through a temporary file so concurrent readers never see a
+    // partial index.
+    if let Some(parent) = index_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp_path = index_path.with_extension(format!("json.{}", std::process::id()));
+    fs::write(&tmp_path, serde_json::to_string(&index)?)
+        .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+    fs::rename(&tmp_path, index_path)
+        .with_context(|| format!("Failed to write {}", index_path.display()))?;
+    Ok(index)
+}
+
+fn build(package_root: &Path) -> Result<PackageIndex> {
+    let resolution = crate::resolve_package(package_root)?;
+    let mut files = Vec::new();
+    for path in crate::collect_zen_files(package_root, None)? {
+        let file_path = crate::get_file_path(package_root, &path);
+        let exports = fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| crate::parser::parse_library(file_path.clone(), &content));
+        match (
+            crate::document_file(package_root, &path, &resolution),
+            exports,
+        ) {
+            (Ok(doc), Ok(exports)) => files.push(IndexedFile { doc, exports }),
+            (Err(e), _) | (_, Err(e)) => {
+                log::warn!("Skipping {file_path} in docs index: {e}");
+            }
+        }
+    }
+    Ok(PackageIndex { files })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_cached_package_of_file() {
+        let cache = tempfile::tempdir().unwrap();
+        let root = cache.path().join("github.com/acme/parts/sensors/1.2.0");
+        fs::create_dir_all(root.join("modules")).unwrap();
+        fs::write(root.join("pcb.toml"), "").unwrap();
+        fs::write(root.join("modules/Bme280.zen"), "").unwrap();
+
+        let package =
+            CachedPackage::containing_in(&root.join("modules/Bme280.zen"), cache.path()).unwrap();
+        assert_eq!(package.module_path, "github.com/acme/parts/sensors");
+        assert_eq!(package.version, "1.2.0");
+        assert_eq!(
+            package.relative_path(&root.join("modules/Bme280.zen")),
+            Some("modules/Bme280.zen".to_string())
+        );
+        assert!(
+            package
+                .index_path(cache.path())
+                .ends_with("github.com/acme/parts/sensors/1.2.0.json")
+        );
+
+        let outside = tempfile::tempdir().unwrap();
+        assert_eq!(
+            CachedPackage::containing_in(outside.path(), cache.path()),
+            None
+        );
+    }
+}
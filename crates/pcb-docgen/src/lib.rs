@@ -2,25 +2,58 @@
 //!
 //! This crate parses `.zen` files from a package directory, extracts docstrings
 //! and module signatures, and generates markdown documentation.
+//!
+//! Package-level generation evaluates files from disk and needs the default
+//! `native` feature. [`document_source`] works on one already-evaluated,
+//! in-memory file and also builds for wasm. Docs of cached remote packages
+//! are persisted per package version by [`index`].
 
+#[cfg(feature = "native")]
+pub mod index;
 mod parser;
 mod render;
 mod signature;
 mod types;
 
-use anyhow::{Context, Result};
+#[cfg(feature = "native")]
+use anyhow::Context;
+use anyhow::Result;
+#[cfg(feature = "native")]
 use pcb_zen_core::DefaultFileProvider;
+use pcb_zen_core::EvalOutput;
+#[cfg(feature = "native")]
+use pcb_zen_core::resolution::ResolutionResult;
+#[cfg(feature = "native")]
 use std::fs;
+#[cfg(feature = "native")]
 use std::path::{Path, PathBuf};
+#[cfg(feature = "native")]
 use walkdir::WalkDir;
 
+pub use render::{render_docs, render_example};
 pub use types::*;
 
+/// Document a single in-memory file from its source and evaluation output.
+///
+/// Files without a signature or instances are documented as libraries.
+pub fn document_source(display_path: &str, content: &str, output: &EvalOutput) -> Result<FileDoc> {
+    Ok(match signature::signature_from_output(output) {
+        Some(signature) => FileDoc::Module(ModuleDoc {
+            path: display_path.to_string(),
+            file_doc: parser::extract_file_docstring(content),
+            signature,
+            footprint: None,
+        }),
+        None => FileDoc::Library(parser::parse_library(display_path.to_string(), content)?),
+    })
+}
+
 /// Generate documentation for a Zener package.
 ///
 /// - `package_url`: Used as the h1 header (e.g. "github.com/diodeinc/stdlib")
 /// - `display_path`: Path shown in source comment; defaults to package_root if None
 /// - `filter`: Optional path prefix to filter files (e.g. "generics" or "Module.zen")
+#[cfg(feature = "native")]
 pub fn generate_docs(
     package_root: &Path,
     package_url: Option<&str>,
@@ -31,49 +64,29 @@ pub fn generate_docs(
     let package_root = package_root
         .canonicalize()
         .unwrap_or_else(|_| package_root.to_path_buf());
-    let zen_files = collect_zen_files(&package_root, filter)?;
-    let file_provider = DefaultFileProvider::new();
-    let workspace_info =
-        pcb_zen::get_workspace_info(&file_provider, &package_root).with_context(|| {
-            format!(
-                "Failed to load workspace info for {}",
-                package_root.display()
-            )
-        })?;
-    let resolution = pcb_zen::resolve_workspace_dependencies(workspace_info, &package_root, false)
-        .with_context(|| {
-            format!(
-                "Failed to resolve dependencies for {}",
-                package_root.display()
-            )
-        })?;
-
-    let mut files = Vec::new();
-
-    for path in zen_files {
-        let content = fs::read_to_string(&path)
-            .with_context(|| format!("Failed to read {}", path.display()))?;
-
-        let file_path = get_file_path(&package_root, &path);
-
-        match signature::try_get_signature(&path, &resolution) {
-            signature::SignatureResult::Module(sig) => {
-                files.push(FileDoc::Module(ModuleDoc {
-                    path: file_path,
-                    file_doc: parser::extract_file_docstring(&content),
-                    signature: sig,
-                }));
-            }
-            signature::SignatureResult::Library => {
-                files.push(FileDoc::Library(parser::parse_library(
-                    file_path, &content,
-                )?));
-            }
-            signature::SignatureResult::Error(e) => {
-                eprintln!("Warning: Failed to parse {}: {}", file_path, e);
+    let mut files = match index::CachedPackage::containing(&package_root) {
+        Some(package) if package.root == package_root => index::package_index(&package)?
+            .files
+            .iter()
+            .map(|file| file.doc.clone())
+            .filter(|doc| matches_filter(doc.path(), filter))
+            .collect(),
+        _ => {
+            let resolution = resolve_package(&package_root)?;
+            let mut files = Vec::new();
+            for path in collect_zen_files(&package_root, filter)? {
+                match document_file(&package_root, &path, &resolution) {
+                    Ok(doc) => files.push(doc),
+                    Err(e) => eprintln!(
+                        "Warning: Failed to parse {}: {}",
+                        get_file_path(&package_root, &path),
+                        e
+                    ),
+                }
             }
+            files
         }
-    }
+    };
 
     // Sort by path
     files.sort_by(|a, b| a.path().cmp(b.path()));
@@ -94,11 +107,105 @@ pub fn generate_docs(
     })
 }
 
+/// Extract documentation for a single `.zen` file.
+///
+/// Returns `Ok(None)` if the file is a library rather than an instantiable module.
+#[cfg(feature = "native")]
+pub fn document_module(
+    path: &Path,
+    display_path: &str,
+    resolution: &ResolutionResult,
+) -> Result<Option<ModuleDoc>> {
+    if let Some(package) = index::CachedPackage::containing(path)
+        && let Some(rel_path) = package.relative_path(path)
+        && let Some(file) = index::package_index(&package)?.file(&rel_path)
+    {
+        return Ok(match &file.doc {
+            FileDoc::Module(module) => Some(ModuleDoc {
+                path: display_path.to_string(),
+                ..module.clone()
+            }),
+            FileDoc::Library(_) => None,
+        });
+    }
+
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    match signature::try_get_signature(path, resolution) {
+        signature::SignatureResult::Module(signature) => Ok(Some(ModuleDoc {
+            path: display_path.to_string(),
+            file_doc: parser::extract_file_docstring(&content),
+            signature,
+            footprint: sibling_footprint(path),
+        })),
+        signature::SignatureResult::Library => Ok(None),
+        signature::SignatureResult::Error(e) => Err(e),
+    }
+}
+
+/// Resolve the dependencies of the package at `package_root` for evaluation.
+#[cfg(feature = "native")]
+fn resolve_package(package_root: &Path) -> Result<ResolutionResult> {
+    let file_provider = DefaultFileProvider::new();
+    let workspace_info =
+        pcb_zen::get_workspace_info(&file_provider, package_root).with_context(|| {
+            format!(
+                "Failed to load workspace info for {}",
+                package_root.display()
+            )
+        })?;
+    pcb_zen::resolve_workspace_dependencies(workspace_info, package_root, false).with_context(
+        || {
+            format!(
+                "Failed to resolve dependencies for {}",
+                package_root.display()
+            )
+        },
+    )
+}
+
+/// Document one file of the package at `package_root` as a module or library.
+#[cfg(feature = "native")]
+fn document_file(
+    package_root: &Path,
+    path: &Path,
+    resolution: &ResolutionResult,
+) -> Result<FileDoc> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let file_path = get_file_path(package_root, path);
+
+    match signature::try_get_signature(path, resolution) {
+        signature::SignatureResult::Module(signature) => Ok(FileDoc::Module(ModuleDoc {
+            path: file_path,
+            file_doc: parser::extract_file_docstring(&content),
+            signature,
+            footprint: sibling_footprint(path),
+        })),
+        signature::SignatureResult::Library => Ok(FileDoc::Library(parser::parse_library(
+            file_path, &content,
+        )?)),
+        signature::SignatureResult::Error(e) => Err(e),
+    }
+}
+
+/// The `<name>.kicad_mod` next to module `<name>.zen`, if there is one.
+#[cfg(feature = "native")]
+fn sibling_footprint(path: &Path) -> Option<String> {
+    let footprint = path.with_extension("kicad_mod");
+    if !footprint.is_file() {
+        return None;
+    }
+    Some(footprint.file_name()?.to_string_lossy().into_owned())
+}
+
 /// Collect all .zen files, excluding test/ and hidden directories.
 ///
 /// If `filter` is provided, only files whose relative path starts with the filter
 /// prefix are included. The filter can be a directory prefix (e.g., "generics")
 /// or a specific file (e.g., "Module.zen").
+#[cfg(feature = "native")]
 fn collect_zen_files(root: &Path, filter: Option<&str>) -> Result<Vec<PathBuf>> {
     // Canonicalize root to ensure strip_prefix works correctly with WalkDir paths
     let canonical_root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
@@ -115,17 +222,7 @@ fn collect_zen_files(root: &Path, filter: Option<&str>) -> Result<Vec<PathBuf>>
                 s == "test" || s.starts_with('.')
             })
         })
-        .filter(|e| {
-            // Apply filter if provided
-            if let Some(filter) = filter {
-                let rel_path = e.path().strip_prefix(&canonical_root).unwrap_or(e.path());
-                let rel_str = rel_path.to_string_lossy().replace('\\', "/");
-                // Match if relative path starts with filter or equals filter
-                rel_str.starts_with(filter) || rel_str == filter
-            } else {
-                true
-            }
-        })
+        .filter(|e| matches_filter(&get_file_path(&canonical_root, e.path()), filter))
         .map(|e| e.into_path())
         .collect();
 
@@ -133,7 +230,15 @@ fn collect_zen_files(root: &Path, filter: Option<&str>) -> Result<Vec<PathBuf>>
     Ok(files)
 }
 
+/// Whether a package-relative path passes the `pcb doc` path filter: a
+/// directory prefix or a specific file.
+#[cfg(feature = "native")]
+fn matches_filter(rel_path: &str, filter: Option<&str>) -> bool {
+    filter.is_none_or(|filter| rel_path.starts_with(filter))
+}
+
 /// Get the file path relative to package root.
+#[cfg(feature = "native")]
 fn get_file_path(root: &Path, path: &Path) -> String {
     path.strip_prefix(root)
         .unwrap_or(path)
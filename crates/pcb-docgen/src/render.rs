@@ -209,6 +209,7 @@ fn render_module(module: &ModuleDoc, depth: usize) -> String {
             let allowed = param
                 .allowed_repr
                 .clone()
+                .or_else(|| param.range_repr.clone())
                 .unwrap_or_default()
                 .replace('|', "\\|");
             out.push_str(&format!(
@@ -219,9 +220,90 @@ fn render_module(module: &ModuleDoc, depth: usize) -> String {
         out.push('\n');
     }
 
+    if let Some(footprint) = &module.footprint {
+        out.push_str(&format!("**Footprint:** `{footprint}`\n\n"));
+    }
+
+    for connector in &module.signature.connectors {
+        let cell = |value: Option<&str>| value.unwrap_or("").replace('|', "\\|");
+        out.push_str(&format!("**Connector {}:**\n\n", connector.refdes));
+        out.push_str("| Pad | Pin | Net | Signal | Voltage |\n");
+        out.push_str("|-----|-----|-----|--------|---------|\n");
+        for pin in &connector.pins {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                cell(Some(&pin.pad)),
+                cell(Some(&pin.pin)),
+                cell(pin.net.as_deref()),
+                cell(pin.description.as_deref()),
+                cell(pin.voltage.as_deref())
+            ));
+        }
+        out.push('\n');
+    }
+
+    if !module.signature.annotations.is_empty() {
+        out.push_str("**Notes:**\n\n");
+        for annotation in &module.signature.annotations {
+            let label = match annotation.kind.as_str() {
+                "warning" => "Warning",
+                "dnp" => "DNP",
+                _ => "Note",
+            };
+            match &annotation.target {
+                Some(target) => out.push_str(&format!(
+                    "- **{label}** (`{target}`): {}\n",
+                    annotation.text
+                )),
+                None => out.push_str(&format!("- **{label}**: {}\n", annotation.text)),
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Render an example instantiation of a module loaded from `load_path`.
+///
+/// Only required configs are passed; optional configs keep their defaults.
+pub fn render_example(module: &ModuleDoc, load_path: &str) -> String {
+    let filename = module.path.rsplit('/').next().unwrap_or(&module.path);
+    let name = filename.strip_suffix(".zen").unwrap_or(filename);
+
+    let mut out = format!("{name} = Module(\"{load_path}\")\n\n{name}(\n");
+    out.push_str(&format!("    name=\"{name}\",\n"));
+    for param in &module.signature.configs {
+        if param.has_default || param.optional {
+            continue;
+        }
+        out.push_str(&format!("    {}={},\n", param.name, example_value(param)));
+    }
+    for io in &module.signature.ios {
+        out.push_str(&format!("    {0}={0},\n", io.name));
+    }
+    out.push_str(")\n");
     out
 }
 
+/// Placeholder value for a required config in an example instantiation.
+fn example_value(param: &ParamDoc) -> String {
+    if let Some(first) = param
+        .allowed_repr
+        .as_deref()
+        .and_then(|allowed| allowed.split(", ").next())
+    {
+        return first.to_string();
+    }
+    match param.type_repr.as_str() {
+        "str" => "\"\"".to_string(),
+        "int" => "0".to_string(),
+        "float" => "0.0".to_string(),
+        "bool" => "False".to_string(),
+        _ => "None".to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -271,6 +353,8 @@ mod tests {
                         optional: false,
                         direction: None,
                         allowed_repr: None,
+                        range_repr: None,
+                        help: None,
                     },
                     ParamDoc {
                         name: "value".to_string(),
@@ -280,6 +364,8 @@ mod tests {
                         optional: false,
                         direction: None,
                         allowed_repr: None,
+                        range_repr: None,
+                        help: None,
                     },
                 ],
                 ios: vec![
@@ -291,6 +377,8 @@ mod tests {
                         optional: false,
                         direction: Some(IoDirection::Input),
                         allowed_repr: None,
+                        range_repr: None,
+                        help: None,
                     },
                     ParamDoc {
                         name: "P2".to_string(),
@@ -300,15 +388,79 @@ mod tests {
                         optional: false,
                         direction: Some(IoDirection::Output),
                         allowed_repr: None,
+                        range_repr: None,
+                        help: None,
                     },
                 ],
+                annotations: vec![
+                    AnnotationDoc {
+                        kind: "dnp".to_string(),
+                        text: "Fit only for 5V input".to_string(),
+                        target: Some("R_TERM".to_string()),
+                    },
+                    AnnotationDoc {
+                        kind: "note".to_string(),
+                        text: "Values per datasheet Table 3".to_string(),
+                        target: None,
+                    },
+                ],
+                connectors: vec![ConnectorDoc {
+                    refdes: "J1".to_string(),
+                    pins: vec![ConnectorPinDoc {
+                        pad: "1".to_string(),
+                        pin: "P1".to_string(),
+                        net: Some("VBUS".to_string()),
+                        description: Some("USB power".to_string()),
+                        voltage: Some("5V".to_string()),
+                    }],
+                }],
             },
+            footprint: Some("Resistor.kicad_mod".to_string()),
         };
 
         let output = render_module(&module, 3);
         assert!(output.contains("### Resistor.zen"));
         assert!(output.contains("| P1 | Net | input |"));
         assert!(output.contains("| package |"));
+        assert!(output.contains("**Footprint:** `Resistor.kicad_mod`\n"));
+        assert!(output.contains("**Connector J1:**\n\n"));
+        assert!(output.contains("| 1 | P1 | VBUS | USB power | 5V |\n"));
+        assert!(output.contains("**Notes:**"));
+        assert!(output.contains("- **DNP** (`R_TERM`): Fit only for 5V input\n"));
+        assert!(output.contains("- **Note**: Values per datasheet Table 3\n"));
+    }
+
+    #[test]
+    fn test_render_example_passes_required_configs_and_ios() {
+        let param = |name: &str, type_repr: &str, has_default: bool| ParamDoc {
+            name: name.to_string(),
+            type_repr: type_repr.to_string(),
+            has_default,
+            default_repr: String::new(),
+            optional: false,
+            direction: None,
+            allowed_repr: None,
+            range_repr: None,
+            help: None,
+        };
+        let module = ModuleDoc {
+            path: "generics/Resistor.zen".to_string(),
+            file_doc: None,
+            signature: ModuleSignature {
+                configs: vec![param("value", "str", false), param("package", "str", true)],
+                ios: vec![param("P1", "Net", false), param("P2", "Net", false)],
+                annotations: vec![],
+                connectors: vec![],
+            },
+            footprint: None,
+        };
+
+        let output = render_example(&module, "@stdlib/generics/Resistor.zen");
+        assert_eq!(
+            output,
+            "Resistor = Module(\"@stdlib/generics/Resistor.zen\")\n\n\
+             Resistor(\n    name=\"Resistor\",\n    value=\"\",\n    P1=P1,\n    P2=P2,\n)\n"
+        );
     }
 
     #[test]
@@ -1,11 +1,18 @@
 //! Extract module signatures by evaluating .zen files directly.
 
-use crate::types::{ModuleSignature, ParamDoc};
-use pcb_zen_core::{lang::type_info::TypeInfo, resolution::ResolutionResult};
+use crate::types::{AnnotationDoc, ConnectorDoc, ConnectorPinDoc, ModuleSignature, ParamDoc};
+use pcb_sch::pinout::{component_pinout, connector_refdes};
+use pcb_zen_core::EvalOutput;
+use pcb_zen_core::lang::type_info::TypeInfo;
+#[cfg(feature = "native")]
+use pcb_zen_core::resolution::ResolutionResult;
+use starlark::values::list::ListRef;
+#[cfg(feature = "native")]
 use std::path::Path;
 
 /// Result of trying to get a module signature.
 /// If the file has no signature parameters, it's a library, not a module.
+#[cfg(feature = "native")]
 pub enum SignatureResult {
     /// File is a module with a signature (has config/io parameters)
     Module(ModuleSignature),
@@ -16,9 +23,7 @@ pub enum SignatureResult {
 }
 
 /// Try to get module signature, returning whether file is a module or library.
-/// A file is considered a module if:
-/// - It has io() or config() parameters in its signature, OR
-/// - It instantiates components/submodules (module_tree has more than just the root)
+#[cfg(feature = "native")]
 pub fn try_get_signature(file: &Path, resolution_result: &ResolutionResult) -> SignatureResult {
     let result = pcb_zen::eval(file, resolution_result.clone(), Default::default());
 
@@ -36,6 +41,17 @@ pub fn try_get_signature(file: &Path, resolution_result: &ResolutionResult) -> S
         ));
     };
 
+    match signature_from_output(&eval_output) {
+        Some(signature) => SignatureResult::Module(signature),
+        None => SignatureResult::Library,
+    }
+}
+
+/// Module signature of an evaluated file, or `None` if the file is a library.
+/// A file is considered a module if:
+/// - It has io() or config() parameters in its signature, OR
+/// - It instantiates components/submodules (module_tree has more than just the root)
+pub fn signature_from_output(eval_output: &EvalOutput) -> Option<ModuleSignature> {
     // A file is a library if it has no signature AND doesn't instantiate anything.
     // Check for:
     // - Submodule instances (module_tree has more than the root)
@@ -50,7 +66,7 @@ pub fn try_get_signature(file: &Path, resolution_result: &ResolutionResult) -> S
     let has_instances = has_submodules || has_components;
 
     if eval_output.signature.is_empty() && !has_instances {
-        return SignatureResult::Library;
+        return None;
     }
 
     let mut configs = Vec::new();
@@ -75,6 +91,8 @@ pub fn try_get_signature(file: &Path, resolution_result: &ResolutionResult) -> S
                     .collect::<Vec<_>>()
                     .join(", ")
             }),
+            range_repr: format_range(param.min_display.as_deref(), param.max_display.as_deref()),
+            help: param.help.clone(),
         };
 
         if param.is_config() {
@@ -84,7 +102,73 @@ pub fn try_get_signature(file: &Path, resolution_result: &ResolutionResult) -> S
         }
     }
 
-    SignatureResult::Module(ModuleSignature { configs, ios })
+    let mut annotations: Vec<AnnotationDoc> = module_tree
+        .values()
+        .next()
+        .map(|root| {
+            root.properties()
+                .iter()
+                .filter(|(key, _)| key.starts_with(pcb_sch::annotation::ATTR_ANNOTATION_PREFIX))
+                .filter_map(|(_, value)| parse_annotation(value.to_value()))
+                .collect()
+        })
+        .unwrap_or_default();
+    annotations.sort();
+
+    Some(ModuleSignature {
+        configs,
+        ios,
+        annotations,
+        connectors: connector_docs(eval_output),
+    })
+}
+
+/// Pinouts of the connectors placed by an evaluated module, empty if its
+/// schematic can't be built.
+fn connector_docs(eval_output: &EvalOutput) -> Vec<ConnectorDoc> {
+    let Ok(schematic) = eval_output.to_schematic() else {
+        return Vec::new();
+    };
+    connector_refdes(&schematic)
+        .into_iter()
+        .filter_map(|refdes| {
+            let pins = component_pinout(&schematic, &refdes)?
+                .into_iter()
+                .map(|pin| ConnectorPinDoc {
+                    pad: pin.pad,
+                    pin: pin.pin,
+                    net: pin.net,
+                    description: pin.description,
+                    voltage: pin.voltage,
+                })
+                .collect();
+            Some(ConnectorDoc { refdes, pins })
+        })
+        .collect()
+}
+
+/// Parse the `[kind, text, target]` list stored by `Annotation()`.
+fn parse_annotation(value: starlark::values::Value) -> Option<AnnotationDoc> {
+    let items: Vec<_> = ListRef::from_value(value)?.iter().collect();
+    let [kind, text, target] = items.as_slice() else {
+        return None;
+    };
+    let target = target.unpack_str()?;
+    Some(AnnotationDoc {
+        kind: kind.unpack_str()?.to_string(),
+        text: text.unpack_str()?.to_string(),
+        target: (!target.is_empty()).then(|| target.to_string()),
+    })
+}
+
+/// Format inclusive `min=`/`max=` bounds for documentation.
+fn format_range(min: Option<&str>, max: Option<&str>) -> Option<String> {
+    match (min, max) {
+        (Some(min), Some(max)) => Some(format!("{min} to {max}")),
+        (Some(min), None) => Some(format!("≥ {min}")),
+        (None, Some(max)) => Some(format!("≤ {max}")),
+        (None, None) => None,
+    }
 }
 
 /// Format a default value display string for documentation.
@@ -1,7 +1,9 @@
 //! Data types for stdlib documentation.
 
+use serde::{Deserialize, Serialize};
+
 /// Documentation for a .zen file (either a library or module).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum FileDoc {
     /// Library file with exported functions, types, constants
     Library(LibraryDoc),
@@ -19,7 +21,7 @@ impl FileDoc {
 }
 
 /// Documentation for a library file (functions, types, constants).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LibraryDoc {
     pub path: String,
     pub file_doc: Option<DocString>,
@@ -29,22 +31,26 @@ pub struct LibraryDoc {
 }
 
 /// Documentation for a module file (instantiable component).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleDoc {
     pub path: String,
     pub file_doc: Option<DocString>,
     pub signature: ModuleSignature,
+    /// File name of the module's footprint, when a `.kicad_mod` with the
+    /// module's name sits next to it (as in component packages).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub footprint: Option<String>,
 }
 
 /// A docstring with summary and description.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DocString {
     pub summary: String,
     pub description: String,
 }
 
 /// Documentation for a function.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionDoc {
     pub name: String,
     pub signature: String,
@@ -52,14 +58,14 @@ pub struct FunctionDoc {
 }
 
 /// Documentation for a type (enum, interface, etc).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypeDoc {
     pub name: String,
     pub kind: String,
 }
 
 /// Documentation for a constant.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConstDoc {
     pub name: String,
 }
@@ -67,14 +73,43 @@ pub struct ConstDoc {
 use pcb_zen_core::lang::io_direction::IoDirection;
 
 /// Module signature extracted from evaluation.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ModuleSignature {
     pub configs: Vec<ParamDoc>,
     pub ios: Vec<ParamDoc>,
+    pub annotations: Vec<AnnotationDoc>,
+    /// Pinouts of the connectors the module places.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub connectors: Vec<ConnectorDoc>,
+}
+
+/// Pinout of one connector placed by a module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectorDoc {
+    pub refdes: String,
+    pub pins: Vec<ConnectorPinDoc>,
+}
+
+/// One pad of a connector; see `pcb_sch::pinout`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectorPinDoc {
+    pub pad: String,
+    pub pin: String,
+    pub net: Option<String>,
+    pub description: Option<String>,
+    pub voltage: Option<String>,
+}
+
+/// An `Annotation()` declared by a module.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AnnotationDoc {
+    pub kind: String,
+    pub text: String,
+    pub target: Option<String>,
 }
 
 /// Documentation for a module parameter (config or io).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParamDoc {
     pub name: String,
     pub type_repr: String,
@@ -83,4 +118,7 @@ pub struct ParamDoc {
     pub optional: bool,
     pub direction: Option<IoDirection>,
     pub allowed_repr: Option<String>,
+    /// Inclusive numeric bounds, e.g. `1 to 10` or `≥ 0`.
+    pub range_repr: Option<String>,
+    pub help: Option<String>,
 }
@@ -0,0 +1,604 @@
+//! Pad, courtyard, and silkscreen geometry from KiCad footprints (`.kicad_mod`).
+//!
+//! Coordinates are footprint-local millimetres with KiCad's axis convention
+//! (Y grows downwards). Courtyard outlines are assembled from the `fp_line`,
+//! `fp_arc`, `fp_rect`, `fp_poly`, and `fp_circle` items on `F.CrtYd` /
+//! `B.CrtYd`; arcs and circles are approximated by polylines. Silkscreen
+//! items are kept one polyline per item, with their stroke width.
+
+use anyhow::{Result, anyhow, bail};
+use pcb_sexpr::{Sexpr, SexprKind, find_all_child_lists, find_child_list, parse};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Segments used to approximate a full circle.
+const CIRCLE_SEGMENTS: usize = 32;
+
+/// Endpoints closer than this (in mm) are treated as the same point when
+/// chaining courtyard segments into outlines.
+const JOIN_TOLERANCE: f64 = 1e-4;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    fn close_to(&self, other: &Point) -> bool {
+        (self.x - other.x).abs() < JOIN_TOLERANCE && (self.y - other.y).abs() < JOIN_TOLERANCE
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct Size {
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PadType {
+    Smd,
+    ThruHole,
+    NpThruHole,
+    Connect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PadShape {
+    Rect,
+    RoundRect,
+    Circle,
+    Oval,
+    Trapezoid,
+    Custom,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Pad {
+    /// Pad number as written in the file; empty for unnumbered pads such as
+    /// mounting holes. Several pads may share a number.
+    pub number: String,
+    pub pad_type: PadType,
+    pub shape: PadShape,
+    pub at: Point,
+    /// Pad rotation in degrees.
+    pub rotation: f64,
+    pub size: Size,
+    /// Drill size for through-hole pads; equal width and height for round holes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drill: Option<Size>,
+    /// Corner radius of `roundrect` pads as a fraction of the shorter side.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roundrect_rratio: Option<f64>,
+    pub layers: Vec<String>,
+}
+
+impl Pad {
+    /// Whether the pad is on `layer`, expanding KiCad's `*.Cu` / `F&B.Cu`
+    /// style wildcards.
+    pub fn is_on_layer(&self, layer: &str) -> bool {
+        let Some((side, kind)) = layer.split_once('.') else {
+            return self.layers.iter().any(|l| l == layer);
+        };
+        self.layers.iter().any(|l| {
+            l == layer
+                || l.split_once('.').is_some_and(|(l_side, l_kind)| {
+                    l_kind == kind
+                        && (l_side == "*" || (l_side == "F&B" && matches!(side, "F" | "B")))
+                })
+        })
+    }
+
+    pub fn is_through_hole(&self) -> bool {
+        matches!(self.pad_type, PadType::ThruHole | PadType::NpThruHole)
+    }
+}
+
+/// One closed (or, for malformed footprints, open) courtyard outline.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Courtyard {
+    /// `F.CrtYd` or `B.CrtYd`.
+    pub layer: String,
+    pub outline: Vec<Point>,
+}
+
+impl Courtyard {
+    /// Whether the outline ends where it started.
+    pub fn is_closed(&self) -> bool {
+        match (self.outline.first(), self.outline.last()) {
+            (Some(first), Some(last)) => self.outline.len() > 2 && first.close_to(last),
+            _ => false,
+        }
+    }
+
+    /// Axis-aligned bounding box as `(min, max)` corners.
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        let first = *self.outline.first()?;
+        Some(self.outline.iter().fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+}
+
+/// One silkscreen line, arc, rectangle, circle, or polygon as a polyline.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Graphic {
+    /// `F.SilkS` or `B.SilkS`.
+    pub layer: String,
+    /// Closed shapes end where they start.
+    pub points: Vec<Point>,
+    /// Stroke width in mm.
+    pub width: f64,
+    /// Whether a closed shape is filled.
+    pub filled: bool,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct KicadFootprint {
+    pub(super) name: String,
+    pub(super) pads: Vec<Pad>,
+    pub(super) courtyards: Vec<Courtyard>,
+    pub(super) silkscreen: Vec<Graphic>,
+    pub(super) models: Vec<String>,
+    pub(super) embedded_files: Vec<String>,
+}
+
+impl KicadFootprint {
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Self::from_str(&content)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn pads(&self) -> &[Pad] {
+        &self.pads
+    }
+
+    /// All pads with the given number.
+    pub fn pads_numbered<'a>(&'a self, number: &'a str) -> impl Iterator<Item = &'a Pad> {
+        self.pads.iter().filter(move |pad| pad.number == number)
+    }
+
+    pub fn courtyards(&self) -> &[Courtyard] {
+        &self.courtyards
+    }
+
+    /// Courtyard outlines on one layer, e.g. `F.CrtYd`.
+    pub fn courtyards_on<'a>(&'a self, layer: &'a str) -> impl Iterator<Item = &'a Courtyard> {
+        self.courtyards.iter().filter(move |c| c.layer == layer)
+    }
+
+    /// Silkscreen graphics on one layer, e.g. `F.SilkS`.
+    pub fn silkscreen_on<'a>(&'a self, layer: &'a str) -> impl Iterator<Item = &'a Graphic> {
+        self.silkscreen.iter().filter(move |g| g.layer == layer)
+    }
+
+    /// 3D model paths as written, e.g. `kicad-embed://R_0402.step`.
+    pub fn models(&self) -> &[String] {
+        &self.models
+    }
+
+    /// Names of files embedded in the footprint (`embedded_files`).
+    pub fn embedded_files(&self) -> &[String] {
+        &self.embedded_files
+    }
+}
+
+impl FromStr for KicadFootprint {
+    type Err = anyhow::Error;
+
+    fn from_str(content: &str) -> Result<Self> {
+        let sexp = parse(content)?;
+        let items = sexp
+            .as_list()
+            .ok_or_else(|| anyhow!("Invalid S-expression format"))?;
+        if !matches!(
+            items.first().and_then(Sexpr::as_sym),
+            Some("footprint" | "module")
+        ) {
+            bail!("No 'footprint' expression found");
+        }
+
+        let name = items
+            .get(1)
+            .and_then(Sexpr::as_atom)
+            .unwrap_or_default()
+            .to_string();
+        let pads = find_all_child_lists(items, "pad")
+            .into_iter()
+            .filter_map(parse_pad)
+            .collect();
+
+        let models = find_all_child_lists(items, "model")
+            .into_iter()
+            .filter_map(|model| model.get(1).and_then(atom_text))
+            .collect();
+        let embedded_files = find_child_list(items, "embedded_files")
+            .map(|files| {
+                find_all_child_lists(files, "file")
+                    .into_iter()
+                    .filter_map(|file| find_child_list(file, "name")?.get(1).and_then(atom_text))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(KicadFootprint {
+            name,
+            pads,
+            courtyards: parse_courtyards(items),
+            silkscreen: parse_silkscreen(items),
+            models,
+            embedded_files,
+        })
+    }
+}
+
+fn parse_pad(pad: &[Sexpr]) -> Option<Pad> {
+    let number = pad.get(1).and_then(atom_text)?;
+    let pad_type = match pad.get(2).and_then(Sexpr::as_sym)? {
+        "smd" => PadType::Smd,
+        "thru_hole" => PadType::ThruHole,
+        "np_thru_hole" => PadType::NpThruHole,
+        "connect" => PadType::Connect,
+        _ => return None,
+    };
+    let shape = match pad.get(3).and_then(Sexpr::as_sym)? {
+        "rect" => PadShape::Rect,
+        "roundrect" => PadShape::RoundRect,
+        "circle" => PadShape::Circle,
+        "oval" => PadShape::Oval,
+        "trapezoid" => PadShape::Trapezoid,
+        "custom" => PadShape::Custom,
+        _ => return None,
+    };
+
+    let at = find_child_list(pad, "at")?;
+    let size = find_child_list(pad, "size").and_then(|size| {
+        Some(Size {
+            width: parse_number(size.get(1))?,
+            height: parse_number(size.get(2))?,
+        })
+    })?;
+    let layers = find_child_list(pad, "layers")
+        .map(|layers| layers.iter().skip(1).filter_map(atom_text).collect())
+        .unwrap_or_default();
+
+    Some(Pad {
+        number,
+        pad_type,
+        shape,
+        at: parse_point(at)?,
+        rotation: parse_number(at.get(3)).unwrap_or(0.0),
+        size,
+        drill: find_child_list(pad, "drill").and_then(parse_drill),
+        roundrect_rratio: find_child_list(pad, "roundrect_rratio")
+            .and_then(|ratio| parse_number(ratio.get(1))),
+        layers,
+    })
+}
+
+/// `(drill 0.8)`, `(drill oval 0.6 1.2)`, optionally followed by `(offset ...)`.
+fn parse_drill(drill: &[Sexpr]) -> Option<Size> {
+    let mut values = drill.iter().skip(1).filter_map(|n| parse_number(Some(n)));
+    let width = values.next()?;
+    let height = values.next().unwrap_or(width);
+    Some(Size { width, height })
+}
+
+fn parse_courtyards(items: &[Sexpr]) -> Vec<Courtyard> {
+    let mut courtyards = Vec::new();
+    for layer in ["F.CrtYd", "B.CrtYd"] {
+        let mut segments: Vec<Vec<Point>> = Vec::new();
+        for item in items.iter().filter_map(Sexpr::as_list) {
+            if item_layer(item) != Some(layer) {
+                continue;
+            }
+            let point = |name: &str| find_child_list(item, name).and_then(parse_point);
+            match item.first().and_then(Sexpr::as_sym) {
+                Some("fp_line") => {
+                    if let (Some(start), Some(end)) = (point("start"), point("end")) {
+                        segments.push(vec![start, end]);
+                    }
+                }
+                Some("fp_arc") => {
+                    if let (Some(start), Some(mid), Some(end)) =
+                        (point("start"), point("mid"), point("end"))
+                    {
+                        segments.push(vec![start, mid, end]);
+                    }
+                }
+                Some("fp_rect") => {
+                    if let (Some(a), Some(b)) = (point("start"), point("end")) {
+                        courtyards.push(Courtyard {
+                            layer: layer.to_string(),
+                            outline: vec![a, Point::new(b.x, a.y), b, Point::new(a.x, b.y), a],
+                        });
+                    }
+                }
+                Some("fp_poly") => {
+                    let mut outline: Vec<Point> = find_child_list(item, "pts")
+                        .map(|pts| find_all_child_lists(pts, "xy"))
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter_map(parse_point)
+                        .collect();
+                    if let Some(&first) = outline.first() {
+                        outline.push(first);
+                        courtyards.push(Courtyard {
+                            layer: layer.to_string(),
+                            outline,
+                        });
+                    }
+                }
+                Some("fp_circle") => {
+                    if let (Some(center), Some(end)) = (point("center"), point("end")) {
+                        courtyards.push(Courtyard {
+                            layer: layer.to_string(),
+                            outline: circle_points(center, end),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        courtyards.extend(
+            chain_segments(segments)
+                .into_iter()
+                .map(|outline| Courtyard {
+                    layer: layer.to_string(),
+                    outline,
+                }),
+        );
+    }
+    courtyards
+}
+
+fn parse_silkscreen(items: &[Sexpr]) -> Vec<Graphic> {
+    items
+        .iter()
+        .filter_map(Sexpr::as_list)
+        .filter_map(|item| {
+            let layer = match item_layer(item)? {
+                "F.SilkS" | "F.Silkscreen" => "F.SilkS",
+                "B.SilkS" | "B.Silkscreen" => "B.SilkS",
+                _ => return None,
+            };
+            let point = |name: &str| find_child_list(item, name).and_then(parse_point);
+            let points = match item.first().and_then(Sexpr::as_sym)? {
+                "fp_line" => vec![point("start")?, point("end")?],
+                "fp_arc" => arc_points(point("start")?, point("mid")?, point("end")?),
+                "fp_rect" => {
+                    let (a, b) = (point("start")?, point("end")?);
+                    vec![a, Point::new(b.x, a.y), b, Point::new(a.x, b.y), a]
+                }
+                "fp_circle" => circle_points(point("center")?, point("end")?),
+                "fp_poly" => {
+                    let mut points: Vec<Point> = find_child_list(item, "pts")
+                        .map(|pts| find_all_child_lists(pts, "xy"))
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter_map(parse_point)
+                        .collect();
+                    points.push(*points.first()?);
+                    points
+                }
+                _ => return None,
+            };
+            // KiCad 7+ nests the width in `(stroke ...)`; older files have it
+            // directly on the item.
+            let width = find_child_list(item, "stroke")
+                .and_then(|stroke| find_child_list(stroke, "width"))
+                .or_else(|| find_child_list(item, "width"))
+                .and_then(|width| parse_number(width.get(1)))
+                .unwrap_or(0.0);
+            let filled = find_child_list(item, "fill")
+                .and_then(|fill| fill.get(1))
+                .and_then(Sexpr::as_atom)
+                .is_some_and(|fill| matches!(fill, "solid" | "yes"));
+            Some(Graphic {
+                layer: layer.to_string(),
+                points,
+                width,
+                filled,
+            })
+        })
+        .collect()
+}
+
+fn item_layer(item: &[Sexpr]) -> Option<&str> {
+    find_child_list(item, "layer")
+        .and_then(|l| l.get(1))
+        .and_then(Sexpr::as_atom)
+}
+
+/// Closed polyline around `center` through `end`.
+fn circle_points(center: Point, end: Point) -> Vec<Point> {
+    let radius = (end.x - center.x).hypot(end.y - center.y);
+    (0..=CIRCLE_SEGMENTS)
+        .map(|i| {
+            let angle = std::f64::consts::TAU * i as f64 / CIRCLE_SEGMENTS as f64;
+            Point::new(
+                center.x + radius * angle.cos(),
+                center.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+/// Polyline along the arc from `start` through `mid` to `end`, with about
+/// as many segments as the same angle of a circle.
+fn arc_points(start: Point, mid: Point, end: Point) -> Vec<Point> {
+    let (ax, ay) = (start.x, start.y);
+    let (bx, by) = (mid.x, mid.y);
+    let (cx, cy) = (end.x, end.y);
+    let d = 2.0 * (ax * (by - cy) + bx * (cy - ay) + cx * (ay - by));
+    if d.abs() < JOIN_TOLERANCE {
+        return vec![start, end];
+    }
+    let a2 = ax * ax + ay * ay;
+    let b2 = bx * bx + by * by;
+    let c2 = cx * cx + cy * cy;
+    let center = Point::new(
+        (a2 * (by - cy) + b2 * (cy - ay) + c2 * (ay - by)) / d,
+        (a2 * (cx - bx) + b2 * (ax - cx) + c2 * (bx - ax)) / d,
+    );
+    let radius = (ax - center.x).hypot(ay - center.y);
+    let angle = |p: Point| (p.y - center.y).atan2(p.x - center.x);
+
+    let tau = std::f64::consts::TAU;
+    let start_angle = angle(start);
+    let mut sweep = (angle(end) - start_angle).rem_euclid(tau);
+    if (angle(mid) - start_angle).rem_euclid(tau) > sweep {
+        sweep -= tau;
+    }
+    let segments = ((sweep.abs() / tau * CIRCLE_SEGMENTS as f64).ceil() as usize).max(2);
+    (0..=segments)
+        .map(|i| {
+            let a = start_angle + sweep * i as f64 / segments as f64;
+            Point::new(center.x + radius * a.cos(), center.y + radius * a.sin())
+        })
+        .collect()
+}
+
+/// Join polylines that share endpoints into outlines, in file order.
+fn chain_segments(mut segments: Vec<Vec<Point>>) -> Vec<Vec<Point>> {
+    let mut outlines = Vec::new();
+    while !segments.is_empty() {
+        let mut outline = segments.remove(0);
+        loop {
+            let start = outline[0];
+            let end = *outline.last().unwrap();
+            if outline.len() > 2 && start.close_to(&end) {
+                break;
+            }
+            let Some(index) = segments
+                .iter()
+                .position(|s| s[0].close_to(&end) || s.last().unwrap().close_to(&end))
+            else {
+                break;
+            };
+            let mut next = segments.remove(index);
+            if !next[0].close_to(&end) {
+                next.reverse();
+            }
+            outline.extend(next.into_iter().skip(1));
+        }
+        outlines.push(outline);
+    }
+    outlines
+}
+
+fn parse_point(list: &[Sexpr]) -> Option<Point> {
+    Some(Point::new(
+        parse_number(list.get(1))?,
+        parse_number(list.get(2))?,
+    ))
+}
+
+fn parse_number(node: Option<&Sexpr>) -> Option<f64> {
+    node.and_then(|n| n.as_float().or_else(|| n.as_int().map(|v| v as f64)))
+}
+
+/// Text of an atom; pad numbers and layer names may be bare numbers,
+/// symbols, or strings depending on the KiCad version that wrote the file.
+fn atom_text(node: &Sexpr) -> Option<String> {
+    match &node.kind {
+        SexprKind::Symbol(s) | SexprKind::String(s) => Some(s.clone()),
+        SexprKind::Int(n) => Some(n.to_string()),
+        SexprKind::F64(f) => Some(f.to_string()),
+        SexprKind::List(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pads_and_chains_courtyard_outline() {
+        let footprint = KicadFootprint::from_str(
+            r#"(footprint "Test" (layer "F.Cu")
+  (fp_line (start 1 -1) (end -1 -1) (stroke (width 0.05) (type solid)) (layer "F.CrtYd"))
+  (fp_arc (start 1 1) (mid 1.5 0) (end 1 -1) (stroke (width 0.05) (type solid)) (layer "F.CrtYd"))
+  (fp_line (start -1 -1) (end -1 1) (stroke (width 0.05) (type solid)) (layer "F.CrtYd"))
+  (fp_line (start -1 1) (end 1 1) (stroke (width 0.05) (type solid)) (layer "F.CrtYd"))
+  (fp_rect (start -2 -2) (end 2 2) (stroke (width 0.05) (type solid)) (layer "B.CrtYd"))
+  (fp_line (start -1 -1.2) (end 1 -1.2) (stroke (width 0.12) (type solid)) (layer "F.SilkS"))
+  (fp_arc (start -1 0) (mid 0 1) (end 1 0) (stroke (width 0.12) (type solid)) (layer "F.Silkscreen"))
+  (fp_poly (pts (xy 0 0) (xy 0.2 0) (xy 0.1 0.2)) (stroke (width 0)) (fill solid) (layer "B.SilkS"))
+  (pad "1" smd roundrect (at -0.5 0 90) (size 0.6 0.5) (layers "F.Cu" "F.Paste" "F.Mask") (roundrect_rratio 0.25))
+  (pad "2" thru_hole circle (at 0.5 0) (size 1 1) (drill 0.6) (layers "*.Cu" "*.Mask"))
+  (pad "" np_thru_hole circle (at 0 0.8) (size 0.5 0.5) (drill oval 0.5 0.7) (layers "F&B.Cu"))
+  (model "kicad-embed://Test.step" (offset (xyz 0 0 0)))
+  (embedded_files (file (name "Test.step") (type model) (data "abc")))
+)"#,
+        )
+        .unwrap();
+
+        assert_eq!(footprint.name(), "Test");
+        assert_eq!(footprint.models(), ["kicad-embed://Test.step"]);
+        assert_eq!(footprint.embedded_files(), ["Test.step"]);
+        let pads = footprint.pads();
+        assert_eq!(pads.len(), 3);
+        assert_eq!(pads[0].number, "1");
+        assert_eq!(pads[0].rotation, 90.0);
+        assert_eq!(pads[0].roundrect_rratio, Some(0.25));
+        assert_eq!(pads[1].roundrect_rratio, None);
+        assert!(pads[0].is_on_layer("F.Cu") && !pads[0].is_on_layer("B.Cu"));
+        assert!(pads[1].is_on_layer("B.Cu") && pads[1].is_on_layer("F.Mask"));
+        assert_eq!(
+            pads[2].drill,
+            Some(Size {
+                width: 0.5,
+                height: 0.7
+            })
+        );
+        assert!(pads[2].is_through_hole() && pads[2].is_on_layer("B.Cu"));
+
+        let front: Vec<_> = footprint.courtyards_on("F.CrtYd").collect();
+        assert_eq!(front.len(), 1);
+        assert!(front[0].is_closed());
+        assert_eq!(front[0].outline.len(), 6);
+        assert_eq!(
+            front[0].bounding_box(),
+            Some((Point::new(-1.0, -1.0), Point::new(1.5, 1.0)))
+        );
+
+        let back: Vec<_> = footprint.courtyards_on("B.CrtYd").collect();
+        assert_eq!(back.len(), 1);
+        assert!(back[0].is_closed());
+
+        let silk: Vec<_> = footprint.silkscreen_on("F.SilkS").collect();
+        assert_eq!(silk.len(), 2);
+        assert_eq!(
+            silk[0].points,
+            [Point::new(-1.0, -1.2), Point::new(1.0, -1.2)]
+        );
+        assert_eq!(silk[0].width, 0.12);
+        let arc = &silk[1].points;
+        assert!(arc.len() > 3);
+        assert!(arc[0].close_to(&Point::new(-1.0, 0.0)));
+        assert!(arc.last().unwrap().close_to(&Point::new(1.0, 0.0)));
+        assert!(arc.iter().all(|p| p.y > -JOIN_TOLERANCE));
+
+        let back_silk: Vec<_> = footprint.silkscreen_on("B.SilkS").collect();
+        assert_eq!(back_silk.len(), 1);
+        assert!(back_silk[0].filled);
+        assert_eq!(back_silk[0].points.len(), 4);
+    }
+}
@@ -0,0 +1,156 @@
+//! Footprint previews drawn without KiCad: the pads, front silkscreen, and
+//! front courtyard of a [`KicadFootprint`], rendered to SVG or PNG through
+//! `pcb-ir`.
+
+use anyhow::Result;
+use pcb_ir::dialects::artwork::{self, Document, Geometry, Layer, Object};
+use pcb_ir::dialects::{LayerRole, Side};
+use pcb_ir::geom::path::transform_cmds;
+use pcb_ir::geom::{Affine2, ContourBuf, FillRule, LineCap, Mirror, Paint, PathCmd, Polarity};
+use pcb_ir::geom::{Point as IrPoint, shapes};
+use pcb_ir::render::{RenderOptions, SizeConstraint};
+
+use super::footprint::{Graphic, KicadFootprint, Pad, PadShape, Point};
+
+/// Stroke width for courtyard outlines, which carry no width of their own
+/// that is worth drawing.
+const COURTYARD_WIDTH: f64 = 0.05;
+
+/// KiCad's default corner radius ratio for `roundrect` pads.
+const DEFAULT_ROUNDRECT_RATIO: f64 = 0.25;
+
+/// Artwork of a footprint as seen from the top: courtyard, pads (with
+/// drilled holes cleared), and silkscreen, in paint order.
+pub fn footprint_artwork(footprint: &KicadFootprint) -> Document {
+    let mut doc = Document::new();
+
+    let courtyard = doc.push_layer(Layer::new("Courtyard", LayerRole::Mechanical, Side::Top));
+    for outline in footprint.courtyards_on("F.CrtYd") {
+        let path = doc.push_path(
+            artwork::stroke_paint(COURTYARD_WIDTH, LineCap::Round),
+            [polyline(&outline.outline)],
+        );
+        doc.push_object(
+            courtyard,
+            Object::new(Polarity::Dark, Geometry::Stroke { path }),
+        );
+    }
+
+    let pads = doc.push_layer(Layer::new("Pads", LayerRole::Copper, Side::Top));
+    let fill = Paint::Fill {
+        rule: FillRule::NonZero,
+    };
+    for pad in footprint.pads() {
+        if let Some(contour) = pad_contour(pad) {
+            let path = doc.push_path(fill, [transform_cmds(contour.cmds, pad_transform(pad))]);
+            doc.push_object(pads, Object::new(Polarity::Dark, Geometry::Region { path }));
+        }
+    }
+    for pad in footprint.pads() {
+        let Some(drill) = pad.drill else {
+            continue;
+        };
+        if let Some(contour) = shapes::obround(drill.width, drill.height, true) {
+            let path = doc.push_path(fill, [transform_cmds(contour.cmds, pad_transform(pad))]);
+            doc.push_object(
+                pads,
+                Object::new(Polarity::Clear, Geometry::Region { path }),
+            );
+        }
+    }
+
+    let silkscreen = doc.push_layer(Layer::new("Silkscreen", LayerRole::Legend, Side::Top));
+    for graphic in footprint.silkscreen_on("F.SilkS") {
+        push_graphic(&mut doc, silkscreen, graphic);
+    }
+
+    doc
+}
+
+/// SVG preview of a footprint, in millimetre units.
+pub fn footprint_svg(footprint: &KicadFootprint) -> String {
+    let mask = artwork::compose_to_mask(&footprint_artwork(footprint));
+    pcb_ir::render::svg(&mask, &RenderOptions::default())
+}
+
+/// PNG preview of a footprint whose longer edge is `max_dimension_px`.
+pub fn footprint_png(footprint: &KicadFootprint, max_dimension_px: u32) -> Result<Vec<u8>> {
+    let mask = artwork::compose_to_mask(&footprint_artwork(footprint));
+    pcb_ir::render::png(
+        &mask,
+        &RenderOptions::default().with_size(SizeConstraint::MaxDimension(max_dimension_px)),
+    )
+    .map_err(anyhow::Error::msg)
+}
+
+/// Pad outline centred on the origin, before rotation. Trapezoid and custom
+/// pads are drawn as their bounding rectangle.
+fn pad_contour(pad: &Pad) -> Option<ContourBuf> {
+    let (width, height) = (pad.size.width, pad.size.height);
+    match pad.shape {
+        PadShape::Circle => shapes::circle(width),
+        PadShape::Oval => shapes::obround(width, height, true),
+        PadShape::RoundRect => shapes::rounded_rect(
+            width,
+            height,
+            pad.roundrect_rratio.unwrap_or(DEFAULT_ROUNDRECT_RATIO) * width.min(height),
+            shapes::ALL_CORNERS,
+            true,
+        ),
+        PadShape::Rect | PadShape::Trapezoid | PadShape::Custom => shapes::rect(width, height),
+    }
+}
+
+/// KiCad's Y axis points down and pcb-ir's up, so positions are flipped;
+/// KiCad's counter-clockwise pad rotation stays counter-clockwise.
+fn pad_transform(pad: &Pad) -> Affine2 {
+    Affine2::placement(ir_point(pad.at), pad.rotation, Mirror::NONE, 1.0)
+}
+
+fn push_graphic(doc: &mut Document, layer: u32, graphic: &Graphic) {
+    if graphic.filled
+        && let Some(contour) =
+            shapes::closed_polygon(graphic.points.iter().copied().map(ir_point).collect())
+    {
+        let path = doc.push_path(
+            Paint::Fill {
+                rule: FillRule::NonZero,
+            },
+            [contour],
+        );
+        doc.push_object(
+            layer,
+            Object::new(Polarity::Dark, Geometry::Region { path }),
+        );
+    }
+    if graphic.width > 0.0 {
+        let path = doc.push_path(
+            artwork::stroke_paint(graphic.width, LineCap::Round),
+            [polyline(&graphic.points)],
+        );
+        doc.push_object(
+            layer,
+            Object::new(Polarity::Dark, Geometry::Stroke { path }),
+        );
+    }
+}
+
+fn polyline(points: &[Point]) -> ContourBuf {
+    ContourBuf::new(
+        points
+            .iter()
+            .enumerate()
+            .map(|(index, &point)| {
+                if index == 0 {
+                    PathCmd::move_to(ir_point(point))
+                } else {
+                    PathCmd::line_to(ir_point(point))
+                }
+            })
+            .collect(),
+    )
+}
+
+fn ir_point(point: Point) -> IrPoint {
+    IrPoint::new(point.x, -point.y)
+}
@@ -1,3 +1,6 @@
+pub mod footprint;
+pub mod footprint_preview;
 pub mod metadata;
 pub mod symbol;
 pub mod symbol_library;
+pub mod symbol_writer;
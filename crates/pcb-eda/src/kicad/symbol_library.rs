@@ -13,7 +13,7 @@ use super::symbol::{KicadSymbol, description_from_properties, parse_symbol};
 
 pub const KICAD_SYMBOL_LIB_VERSION: &str = "20211014";
 
-fn kicad_generator_atom(generator: &str) -> String {
+pub(super) fn kicad_generator_atom(generator: &str) -> String {
     let trimmed = generator.trim();
     if trimmed.is_empty() {
         return "pcb".to_string();
@@ -0,0 +1,456 @@
+//! Programmatic construction of KiCad symbols.
+//!
+//! Build a [`SymbolDefinition`] from pins, units, and graphics (or use
+//! [`SymbolDefinition::box_symbol`] for a plain rectangular symbol) and
+//! serialize it to `.kicad_sym` text with [`symbol_library_text`].
+
+use pcb_sexpr::formatter::{KicadFileKind, format_kicad};
+use pcb_sexpr::{ListBuilder, Sexpr, kv};
+
+use super::symbol_library::{KICAD_SYMBOL_LIB_VERSION, kicad_generator_atom};
+
+/// KiCad schematic grid (50 mil). Pins are placed on this grid so wires snap to them.
+const GRID: f64 = 2.54;
+const PIN_LENGTH: f64 = 2.54;
+const FONT_SIZE: f64 = 1.27;
+const BODY_STROKE_WIDTH: f64 = 0.254;
+/// Rough pin name width per character at the default font size.
+const CHAR_WIDTH: f64 = 1.0;
+
+/// A symbol to be written to a KiCad symbol library.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolDefinition {
+    pub name: String,
+    /// Reference designator prefix, e.g. `U`.
+    pub reference: String,
+    pub value: String,
+    pub footprint: String,
+    pub datasheet: String,
+    pub description: String,
+    /// Extra properties, written hidden after the standard ones.
+    pub properties: Vec<(String, String)>,
+    pub in_bom: bool,
+    pub on_board: bool,
+    /// Graphics shared by every unit (KiCad unit 0).
+    pub common_graphics: Vec<SymbolGraphic>,
+    /// Units in order; the first one is unit 1.
+    pub units: Vec<SymbolUnit>,
+}
+
+/// One unit of a (possibly multi-unit) symbol.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolUnit {
+    pub graphics: Vec<SymbolGraphic>,
+    pub pins: Vec<SymbolPin>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SymbolGraphic {
+    Rectangle {
+        start: (f64, f64),
+        end: (f64, f64),
+        fill: GraphicFill,
+    },
+    Polyline {
+        points: Vec<(f64, f64)>,
+        fill: GraphicFill,
+    },
+    Circle {
+        center: (f64, f64),
+        radius: f64,
+        fill: GraphicFill,
+    },
+    Text {
+        text: String,
+        at: (f64, f64),
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GraphicFill {
+    #[default]
+    None,
+    Outline,
+    Background,
+}
+
+impl GraphicFill {
+    fn as_str(self) -> &'static str {
+        match self {
+            GraphicFill::None => "none",
+            GraphicFill::Outline => "outline",
+            GraphicFill::Background => "background",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolPin {
+    pub name: String,
+    pub number: String,
+    /// KiCad electrical type, e.g. `passive`, `input`, `power_in`.
+    pub electrical_type: String,
+    /// Connection point of the pin.
+    pub at: (f64, f64),
+    /// Direction from the connection point towards the body, in degrees.
+    pub rotation: f64,
+    pub length: f64,
+    pub hidden: bool,
+}
+
+/// A pin of a [`SymbolDefinition::box_symbol`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoxPin {
+    pub number: String,
+    pub name: String,
+    /// KiCad electrical type, e.g. `passive`, `input`, `power_in`.
+    pub electrical_type: String,
+}
+
+impl BoxPin {
+    pub fn new(
+        number: impl Into<String>,
+        name: impl Into<String>,
+        electrical_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            number: number.into(),
+            name: name.into(),
+            electrical_type: electrical_type.into(),
+        }
+    }
+}
+
+impl SymbolDefinition {
+    /// A single-unit symbol with the given name and no pins or graphics.
+    pub fn new(name: impl Into<String>, reference: impl Into<String>) -> Self {
+        let name = name.into();
+        Self {
+            value: name.clone(),
+            name,
+            reference: reference.into(),
+            footprint: String::new(),
+            datasheet: String::new(),
+            description: String::new(),
+            properties: Vec::new(),
+            in_bom: true,
+            on_board: true,
+            common_graphics: Vec::new(),
+            units: vec![SymbolUnit::default()],
+        }
+    }
+
+    /// A rectangular symbol with the given pins.
+    ///
+    /// The first half of the pins goes down the left edge and the rest down
+    /// the right edge, in the order given. The body is sized to fit the pin
+    /// names.
+    pub fn box_symbol(
+        name: impl Into<String>,
+        reference: impl Into<String>,
+        pins: &[BoxPin],
+    ) -> Self {
+        let (left, right) = pins.split_at(pins.len().div_ceil(2));
+        let name_width = |side: &[BoxPin]| {
+            side.iter()
+                .map(|pin| pin.name.chars().count() as f64 * CHAR_WIDTH)
+                .fold(0.0, f64::max)
+        };
+        let body_width = name_width(left) + name_width(right) + 2.0 * GRID;
+        let half_width = snap_up(body_width / 2.0).max(2.0 * GRID);
+        let rows = left.len().max(right.len()).max(1);
+        let half_height = snap_up((rows + 1) as f64 * GRID / 2.0);
+
+        let side_pins = |side: &[BoxPin], x: f64, rotation: f64| {
+            side.iter().enumerate().map(move |(row, pin)| SymbolPin {
+                name: pin.name.clone(),
+                number: pin.number.clone(),
+                electrical_type: pin.electrical_type.clone(),
+                at: (x, half_height - GRID * (row + 1) as f64),
+                rotation,
+                length: PIN_LENGTH,
+                hidden: false,
+            })
+        };
+        let mut unit_pins: Vec<SymbolPin> =
+            side_pins(left, -half_width - PIN_LENGTH, 0.0).collect();
+        unit_pins.extend(side_pins(right, half_width + PIN_LENGTH, 180.0));
+
+        let mut symbol = Self::new(name, reference);
+        symbol.common_graphics.push(SymbolGraphic::Rectangle {
+            start: (-half_width, half_height),
+            end: (half_width, -half_height),
+            fill: GraphicFill::Background,
+        });
+        symbol.units = vec![SymbolUnit {
+            graphics: Vec::new(),
+            pins: unit_pins,
+        }];
+        symbol
+    }
+
+    /// Serialize to a top-level `(symbol ...)` node.
+    pub fn to_sexpr(&self) -> Sexpr {
+        let mut l = ListBuilder::node("symbol");
+        l.push(Sexpr::string(&self.name))
+            .push(kv("in_bom", yes_no(self.in_bom)))
+            .push(kv("on_board", yes_no(self.on_board)));
+
+        let (min_y, max_y) = self.y_extent();
+        let standard = [
+            (
+                "Reference",
+                self.reference.as_str(),
+                (0.0, max_y + GRID / 2.0),
+                false,
+            ),
+            (
+                "Value",
+                self.value.as_str(),
+                (0.0, min_y - GRID / 2.0),
+                false,
+            ),
+            ("Footprint", self.footprint.as_str(), (0.0, 0.0), true),
+            ("Datasheet", self.datasheet.as_str(), (0.0, 0.0), true),
+            ("Description", self.description.as_str(), (0.0, 0.0), true),
+        ];
+        let extra = self
+            .properties
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str(), (0.0, 0.0), true));
+        for (id, (key, value, at, hidden)) in standard.into_iter().chain(extra).enumerate() {
+            l.push(property(key, value, id, at, hidden));
+        }
+
+        if !self.common_graphics.is_empty() {
+            let mut section = ListBuilder::node("symbol");
+            section.push(Sexpr::string(format!("{}_0_1", self.name)));
+            section.extend(self.common_graphics.iter().map(SymbolGraphic::to_sexpr));
+            l.push(section.build());
+        }
+        for (index, unit) in self.units.iter().enumerate() {
+            let mut section = ListBuilder::node("symbol");
+            section.push(Sexpr::string(format!("{}_{}_1", self.name, index + 1)));
+            section.extend(unit.graphics.iter().map(SymbolGraphic::to_sexpr));
+            section.extend(unit.pins.iter().map(SymbolPin::to_sexpr));
+            l.push(section.build());
+        }
+        l.build()
+    }
+
+    /// Lowest and highest y of the body and pins, for placing Reference/Value.
+    fn y_extent(&self) -> (f64, f64) {
+        let graphics = self
+            .common_graphics
+            .iter()
+            .chain(self.units.iter().flat_map(|unit| &unit.graphics))
+            .flat_map(SymbolGraphic::points);
+        let pins = self.units.iter().flat_map(|unit| &unit.pins).map(|p| p.at);
+        graphics
+            .chain(pins)
+            .map(|(_, y)| y)
+            .fold(None, |extent: Option<(f64, f64)>, y| match extent {
+                Some((min, max)) => Some((min.min(y), max.max(y))),
+                None => Some((y, y)),
+            })
+            .unwrap_or((0.0, 0.0))
+    }
+}
+
+impl SymbolGraphic {
+    fn points(&self) -> Vec<(f64, f64)> {
+        match self {
+            SymbolGraphic::Rectangle { start, end, .. } => vec![*start, *end],
+            SymbolGraphic::Polyline { points, .. } => points.clone(),
+            SymbolGraphic::Circle { center, radius, .. } => {
+                vec![(center.0, center.1 - radius), (center.0, center.1 + radius)]
+            }
+            SymbolGraphic::Text { at, .. } => vec![*at],
+        }
+    }
+
+    fn to_sexpr(&self) -> Sexpr {
+        match self {
+            SymbolGraphic::Rectangle { start, end, fill } => {
+                let mut l = ListBuilder::node("rectangle");
+                l.push(xy("start", *start))
+                    .push(xy("end", *end))
+                    .push(stroke())
+                    .push(fill_node(*fill));
+                l.build()
+            }
+            SymbolGraphic::Polyline { points, fill } => {
+                let mut pts = ListBuilder::node("pts");
+                pts.extend(points.iter().map(|p| xy("xy", *p)));
+                let mut l = ListBuilder::node("polyline");
+                l.push(pts.build()).push(stroke()).push(fill_node(*fill));
+                l.build()
+            }
+            SymbolGraphic::Circle {
+                center,
+                radius,
+                fill,
+            } => {
+                let mut l = ListBuilder::node("circle");
+                l.push(xy("center", *center))
+                    .push(kv("radius", Sexpr::float(mm(*radius))))
+                    .push(stroke())
+                    .push(fill_node(*fill));
+                l.build()
+            }
+            SymbolGraphic::Text { text, at } => {
+                let mut l = ListBuilder::node("text");
+                l.push(Sexpr::string(text))
+                    .push(at_node(*at, 0.0))
+                    .push(effects(false));
+                l.build()
+            }
+        }
+    }
+}
+
+impl SymbolPin {
+    fn to_sexpr(&self) -> Sexpr {
+        let mut l = ListBuilder::node("pin");
+        l.push(Sexpr::symbol(&self.electrical_type))
+            .push("line")
+            .push(at_node(self.at, self.rotation))
+            .push(kv("length", Sexpr::float(mm(self.length))))
+            .push_if(self.hidden, "hide");
+        for (tag, text) in [("name", &self.name), ("number", &self.number)] {
+            let mut node = ListBuilder::node(tag);
+            node.push(Sexpr::string(text)).push(effects(false));
+            l.push(node.build());
+        }
+        l.build()
+    }
+}
+
+/// Render `symbols` as a complete `.kicad_sym` library.
+pub fn symbol_library_text(symbols: &[SymbolDefinition], generator: &str) -> String {
+    let mut l = ListBuilder::node("kicad_symbol_lib");
+    l.push(kv("version", Sexpr::symbol(KICAD_SYMBOL_LIB_VERSION)))
+        .push(kv(
+            "generator",
+            Sexpr::symbol(kicad_generator_atom(generator)),
+        ))
+        .extend(symbols.iter().map(SymbolDefinition::to_sexpr));
+    format_kicad(&l.build(), KicadFileKind::Symbol)
+}
+
+fn property(key: &str, value: &str, id: usize, at: (f64, f64), hidden: bool) -> Sexpr {
+    let mut l = ListBuilder::node("property");
+    l.push(Sexpr::string(key))
+        .push(Sexpr::string(value))
+        .push(kv("id", Sexpr::int(id as i64)))
+        .push(at_node(at, 0.0))
+        .push(effects(hidden));
+    l.build()
+}
+
+fn effects(hidden: bool) -> Sexpr {
+    let font = kv(
+        "font",
+        Sexpr::list(vec![
+            Sexpr::symbol("size"),
+            Sexpr::float(FONT_SIZE),
+            Sexpr::float(FONT_SIZE),
+        ]),
+    );
+    let mut l = ListBuilder::node("effects");
+    l.push(font).push_if(hidden, "hide");
+    l.build()
+}
+
+fn stroke() -> Sexpr {
+    let mut l = ListBuilder::node("stroke");
+    l.push(kv("width", Sexpr::float(BODY_STROKE_WIDTH)))
+        .push(kv("type", "default"));
+    l.build()
+}
+
+fn fill_node(fill: GraphicFill) -> Sexpr {
+    kv("fill", kv("type", fill.as_str()))
+}
+
+fn at_node((x, y): (f64, f64), rotation: f64) -> Sexpr {
+    Sexpr::list(vec![
+        Sexpr::symbol("at"),
+        Sexpr::float(mm(x)),
+        Sexpr::float(mm(y)),
+        Sexpr::float(mm(rotation)),
+    ])
+}
+
+fn xy(tag: &str, (x, y): (f64, f64)) -> Sexpr {
+    Sexpr::list(vec![
+        Sexpr::symbol(tag),
+        Sexpr::float(mm(x)),
+        Sexpr::float(mm(y)),
+    ])
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+/// Round to 0.1 µm so grid arithmetic doesn't leak float noise into the file.
+fn mm(value: f64) -> f64 {
+    let rounded = (value * 10_000.0).round() / 10_000.0;
+    if rounded == 0.0 { 0.0 } else { rounded }
+}
+
+fn snap_up(value: f64) -> f64 {
+    // Tolerate float noise so an exact multiple of the grid isn't bumped up a step.
+    (value / GRID - 1e-9).ceil() * GRID
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SymbolLibrary;
+
+    #[test]
+    fn box_symbol_round_trips_through_parser() {
+        let mut symbol = SymbolDefinition::box_symbol(
+            "TPS1234",
+            "U",
+            &[
+                BoxPin::new("1", "VIN", "power_in"),
+                BoxPin::new("2", "EN", "input"),
+                BoxPin::new("3", "GND", "power_in"),
+                BoxPin::new("4", "VOUT", "power_out"),
+                BoxPin::new("5", "FB", "input"),
+            ],
+        );
+        symbol.footprint = "SOT-23-5".to_string();
+
+        let text = symbol_library_text(&[symbol], "pcb import");
+        let library = SymbolLibrary::from_string(&text, "kicad_sym").unwrap();
+        let parsed = library.get_symbol("TPS1234").unwrap();
+
+        assert_eq!(parsed.reference, "U");
+        assert_eq!(parsed.footprint, "SOT-23-5");
+        let pins: Vec<_> = parsed
+            .pins
+            .iter()
+            .map(|p| {
+                let at = p.at.as_ref().unwrap();
+                (p.number.as_str(), p.name.as_str(), at.x, at.y)
+            })
+            .collect();
+        // Pins 1-3 on the left, 4-5 on the right, top to bottom on the grid.
+        assert_eq!(
+            pins,
+            vec![
+                ("1", "VIN", -10.16, 2.54),
+                ("2", "EN", -10.16, 0.0),
+                ("3", "GND", -10.16, -2.54),
+                ("4", "VOUT", 10.16, 2.54),
+                ("5", "FB", 10.16, 0.0),
+            ]
+        );
+        assert!(text.contains("(rectangle"));
+        assert!(text.contains("(symbol \"TPS1234_1_1\""));
+    }
+}
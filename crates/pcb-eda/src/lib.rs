@@ -1,6 +1,9 @@
 pub mod kicad;
 
 use anyhow::Result;
+pub use kicad::footprint::{
+    Courtyard, Graphic, KicadFootprint, Pad, PadShape, PadType, Point, Size,
+};
 use kicad::symbol::KicadSymbol;
 use kicad::symbol_library::KicadSymbolLibrary;
 use pcb_sexpr::Sexpr;
@@ -102,6 +105,68 @@ pub fn is_placeholder_kicad_pin_name(name: &str) -> bool {
     usable_kicad_field_value(name).is_none()
 }
 
+/// Electrical type of a symbol pin, as written in KiCad's `(pin <type> ...)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PinElectricalType {
+    Input,
+    Output,
+    Bidirectional,
+    TriState,
+    Passive,
+    Free,
+    Unspecified,
+    PowerIn,
+    PowerOut,
+    OpenCollector,
+    OpenEmitter,
+    NoConnect,
+}
+
+impl PinElectricalType {
+    /// Parse a KiCad pin type keyword such as `power_in`.
+    pub fn parse(keyword: &str) -> Option<Self> {
+        Some(match keyword {
+            "input" => Self::Input,
+            "output" => Self::Output,
+            "bidirectional" => Self::Bidirectional,
+            "tri_state" => Self::TriState,
+            "passive" => Self::Passive,
+            "free" => Self::Free,
+            "unspecified" => Self::Unspecified,
+            "power_in" => Self::PowerIn,
+            "power_out" => Self::PowerOut,
+            "open_collector" => Self::OpenCollector,
+            "open_emitter" => Self::OpenEmitter,
+            "no_connect" => Self::NoConnect,
+            _ => return None,
+        })
+    }
+
+    /// The KiCad keyword for this type.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Input => "input",
+            Self::Output => "output",
+            Self::Bidirectional => "bidirectional",
+            Self::TriState => "tri_state",
+            Self::Passive => "passive",
+            Self::Free => "free",
+            Self::Unspecified => "unspecified",
+            Self::PowerIn => "power_in",
+            Self::PowerOut => "power_out",
+            Self::OpenCollector => "open_collector",
+            Self::OpenEmitter => "open_emitter",
+            Self::NoConnect => "no_connect",
+        }
+    }
+}
+
+impl std::fmt::Display for PinElectricalType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl Pin {
     /// KiCad uses `~` as a placeholder pin name for "unnamed" pins.
     ///
@@ -114,6 +179,20 @@ impl Pin {
             &self.name
         }
     }
+
+    /// Electrical types this pin can take: its own and those of its alternates.
+    /// Unrecognized keywords are skipped.
+    pub fn electrical_types(&self) -> BTreeSet<PinElectricalType> {
+        self.electrical_type
+            .iter()
+            .chain(
+                self.alternates
+                    .iter()
+                    .filter_map(|alt| alt.electrical_type.as_ref()),
+            )
+            .filter_map(|keyword| PinElectricalType::parse(keyword))
+            .collect()
+    }
 }
 
 impl Symbol {
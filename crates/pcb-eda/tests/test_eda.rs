@@ -2,7 +2,7 @@ mod test_utils;
 
 use test_utils::setup_symbol;
 
-use pcb_eda::{Part, Symbol, SymbolLibrary};
+use pcb_eda::{Part, PinElectricalType, Symbol, SymbolLibrary};
 use std::collections::{BTreeSet, HashMap};
 
 fn test_symbol_property(symbol_name: &str, property: impl Fn(&Symbol) -> String, expected: &str) {
@@ -214,6 +214,10 @@ fn test_pin_alternates() {
         pin.alternates[1].graphical_style.as_deref(),
         Some("inverted")
     );
+    assert_eq!(
+        pin.electrical_types().into_iter().collect::<Vec<_>>(),
+        vec![PinElectricalType::Input, PinElectricalType::Bidirectional]
+    );
 }
 
 #[test]
@@ -760,3 +764,47 @@ fn test_lan9252ti_pin_names() {
     assert_eq!(pin_map.get("64"), Some(&"VDD3TXRX2".to_string()));
     assert_eq!(pin_map.get("EPAD"), Some(&"VSS".to_string()));
 }
+
+#[test]
+fn test_sn75176bd_footprint_geometry() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/resources/kicad/SN75176BD/SOIC127P600X175-8N.kicad_mod");
+    let footprint = pcb_eda::KicadFootprint::from_file(&path).unwrap();
+
+    let numbers: Vec<_> = footprint.pads().iter().map(|p| p.number.as_str()).collect();
+    assert_eq!(numbers, ["1", "2", "3", "4", "5", "6", "7", "8"]);
+    let pad1 = footprint.pads_numbered("1").next().unwrap();
+    assert_eq!(pad1.at, pcb_eda::Point::new(-2.711, -1.905));
+    assert_eq!(pad1.size.height, 1.528);
+    assert!(pad1.is_on_layer("F.Cu"));
+
+    let courtyards: Vec<_> = footprint.courtyards_on("F.CrtYd").collect();
+    assert_eq!(courtyards.len(), 1);
+    assert!(courtyards[0].is_closed());
+    assert_eq!(
+        courtyards[0].bounding_box(),
+        Some((
+            pcb_eda::Point::new(-3.725, -2.75),
+            pcb_eda::Point::new(3.725, 2.75)
+        ))
+    );
+}
+
+#[test]
+fn test_sn75176bd_footprint_preview() {
+    use pcb_eda::kicad::footprint_preview::{footprint_artwork, footprint_png, footprint_svg};
+
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/resources/kicad/SN75176BD/SOIC127P600X175-8N.kicad_mod");
+    let footprint = pcb_eda::KicadFootprint::from_file(&path).unwrap();
+
+    let artwork = footprint_artwork(&footprint);
+    let names: Vec<_> = artwork.layers.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(names, ["Courtyard", "Pads", "Silkscreen"]);
+    assert_eq!(artwork.layers[1].objects.len(), 8);
+    artwork.validate().unwrap();
+
+    assert!(footprint_svg(&footprint).starts_with("<svg"));
+    let png = footprint_png(&footprint, 256).unwrap();
+    assert!(png.starts_with(b"\x89PNG"));
+}
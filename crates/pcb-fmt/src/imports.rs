@@ -0,0 +1,244 @@
+//! Organizing `load()` statements, the opt-in `--fix-imports` pass of
+//! `pcb fmt`.
+//!
+//! Top-level loads are gathered into one block at the position of the first
+//! load. Loads of the same target are merged, duplicate and unused symbols are
+//! dropped, and the block is sorted into groups separated by a blank line:
+//! `@stdlib`, other packages, then local paths. Symbols starting with `_` are
+//! kept, as `load.unused` ignores them too.
+//!
+//! Only whole lines are rewritten, so comments outside `load()` lines stay
+//! where they are. A file with a load that shares its lines with other code or
+//! comments is left unchanged.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use anyhow::Result;
+use starlark_syntax::codemap::Span;
+use starlark_syntax::syntax::ast::{AstExpr, ExprP, StmtP};
+use starlark_syntax::syntax::module::AstModuleFields;
+use starlark_syntax::syntax::top_level_stmts::top_level_stmts;
+use starlark_syntax::syntax::{AstModule, Dialect};
+
+/// Which block of the organized loads a target belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Group {
+    Stdlib,
+    Package,
+    Local,
+}
+
+impl Group {
+    fn of(target: &str) -> Self {
+        if target == "@stdlib" || target.starts_with("@stdlib/") {
+            return Self::Stdlib;
+        }
+        if target.starts_with('@') {
+            return Self::Package;
+        }
+        // Remote URLs start with a host name such as `github.com/`.
+        match target.split_once('/') {
+            Some((host, _)) if host.contains('.') && !host.starts_with('.') => Self::Package,
+            _ => Self::Local,
+        }
+    }
+}
+
+/// One symbol a load binds, as written in the source.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Binding {
+    local: String,
+    their: String,
+    /// Source text of the quoted symbol name.
+    their_literal: String,
+}
+
+impl Binding {
+    fn render(&self) -> String {
+        if self.local == self.their {
+            self.their_literal.clone()
+        } else {
+            format!("{} = {}", self.local, self.their_literal)
+        }
+    }
+}
+
+/// Rewrite the top-level `load()` statements of `source`; see the
+/// [module docs](crate::imports).
+pub fn fix_imports(filename: &str, source: &str) -> Result<String> {
+    let mut dialect = Dialect::Extended;
+    dialect.enable_f_strings = true;
+    let ast = AstModule::parse(filename, source.to_owned(), &dialect)
+        .map_err(|err| anyhow::anyhow!("Failed to parse {filename}: {err}"))?;
+
+    let referenced = referenced_identifiers(&ast);
+    let mut lines = Vec::new();
+    let mut targets: BTreeMap<(Group, String), (String, BTreeSet<Binding>)> = BTreeMap::new();
+    for stmt in top_level_stmts(ast.statement()) {
+        let StmtP::Load(load) = &stmt.node else {
+            continue;
+        };
+        let Some(range) = line_range(source, stmt.span) else {
+            return Ok(source.to_string());
+        };
+        lines.push(range);
+
+        let target = load.module.node.clone();
+        let (_, bindings) = targets
+            .entry((Group::of(&target), target))
+            .or_insert_with(|| (text(source, load.module.span).to_string(), BTreeSet::new()));
+        for arg in &load.args {
+            let local = &arg.local.node.ident;
+            if local.starts_with('_') || referenced.contains(local.as_str()) {
+                bindings.insert(Binding {
+                    local: local.clone(),
+                    their: arg.their.node.clone(),
+                    their_literal: text(source, arg.their.span).to_string(),
+                });
+            }
+        }
+    }
+    let Some(&(start, first_end)) = lines.first() else {
+        return Ok(source.to_string());
+    };
+
+    let mut block = String::new();
+    let mut previous_group = None;
+    for ((group, _), (target_literal, bindings)) in &targets {
+        if bindings.is_empty() {
+            continue;
+        }
+        if previous_group.is_some_and(|previous| previous != *group) {
+            block.push('\n');
+        }
+        previous_group = Some(*group);
+        let symbols: Vec<String> = bindings.iter().map(Binding::render).collect();
+        block.push_str(&format!("load({target_literal}, {})\n", symbols.join(", ")));
+    }
+
+    // Loads separated only by blank lines form the block being replaced;
+    // later ones are removed and merged into it.
+    let mut end = first_end;
+    let mut rest = lines[1..].iter().peekable();
+    while let Some(&&(next_start, next_end)) = rest.peek() {
+        if !source[end..next_start].trim().is_empty() {
+            break;
+        }
+        end = next_end;
+        rest.next();
+    }
+
+    let mut rewritten = String::with_capacity(source.len());
+    rewritten.push_str(&source[..start]);
+    rewritten.push_str(&block);
+    let mut cursor = end;
+    for &(line_start, line_end) in rest {
+        rewritten.push_str(&source[cursor..line_start]);
+        cursor = line_end;
+    }
+    rewritten.push_str(&source[cursor..]);
+    Ok(rewritten)
+}
+
+/// Byte range of the whole lines `span` covers, including the final newline,
+/// or `None` if they also hold other code or comments.
+fn line_range(source: &str, span: Span) -> Option<(usize, usize)> {
+    let begin = span.begin().get() as usize;
+    let end = begin + text(source, span).trim_end().len();
+    let start = source[..begin].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[end..]
+        .find('\n')
+        .map_or(source.len(), |i| end + i + 1);
+    let surrounding_blank = source[start..begin].trim().is_empty()
+        && source[end..line_end]
+            .trim()
+            .trim_start_matches(';')
+            .trim()
+            .is_empty();
+    surrounding_blank.then_some((start, line_end))
+}
+
+fn text(source: &str, span: Span) -> &str {
+    &source[span.begin().get() as usize..span.end().get() as usize]
+}
+
+/// Every identifier read anywhere in the module, including nested functions.
+fn referenced_identifiers(ast: &AstModule) -> HashSet<&str> {
+    fn visit<'a>(expr: &'a AstExpr, names: &mut HashSet<&'a str>) {
+        if let ExprP::Identifier(ident) = &expr.node {
+            names.insert(ident.node.ident.as_str());
+        }
+        expr.visit_expr(|child| visit(child, names));
+    }
+
+    let mut names = HashSet::new();
+    ast.statement().visit_expr(|expr| visit(expr, &mut names));
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_sorts_and_drops_unused() {
+        let source = r#"# Power stage
+load("./parts.zen", "Resistor")
+load("@stdlib/units.zen", "Voltage", "Current")
+load("github.com/acme/lib/Ldo.zen", "Ldo")
+load("./parts.zen", "Capacitor", "Resistor")
+load("@stdlib/interfaces.zen", "Power")
+
+vin = config("vin", Voltage)
+Resistor(name = "R1")
+Capacitor(name = "C1")
+
+load("./parts.zen", _Unused = "Inductor", Cap = "Capacitor")
+Ldo(name = "U1", vin = Power("VIN"), cap = Cap)
+"#;
+        let fixed = fix_imports("test.zen", source).unwrap();
+        assert_eq!(
+            fixed,
+            r#"# Power stage
+load("@stdlib/interfaces.zen", "Power")
+load("@stdlib/units.zen", "Voltage")
+
+load("github.com/acme/lib/Ldo.zen", "Ldo")
+
+load("./parts.zen", Cap = "Capacitor", "Capacitor", "Resistor", _Unused = "Inductor")
+
+vin = config("vin", Voltage)
+Resistor(name = "R1")
+Capacitor(name = "C1")
+
+Ldo(name = "U1", vin = Power("VIN"), cap = Cap)
+"#
+        );
+        assert_eq!(fix_imports("test.zen", &fixed).unwrap(), fixed);
+    }
+
+    #[test]
+    fn removes_fully_unused_loads() {
+        let source = "load(\"./a.zen\", \"A\")\nload(\"./b.zen\", \"B\")\n\nA()\n";
+        assert_eq!(
+            fix_imports("test.zen", source).unwrap(),
+            "load(\"./a.zen\", \"A\")\n\nA()\n"
+        );
+    }
+
+    #[test]
+    fn leaves_loads_sharing_lines_alone() {
+        let source = "load(\"./b.zen\", \"B\")  # keep\nload(\"./a.zen\", \"A\")\nA()\nB()\n";
+        assert_eq!(fix_imports("test.zen", source).unwrap(), source);
+    }
+
+    #[test]
+    fn groups_targets() {
+        assert_eq!(Group::of("@stdlib/units.zen"), Group::Stdlib);
+        assert_eq!(Group::of("@kicad-symbols/Device.kicad_sym"), Group::Package);
+        assert_eq!(Group::of("github.com/acme/lib"), Group::Package);
+        assert_eq!(Group::of("./utils.zen"), Group::Local);
+        assert_eq!(Group::of("../shared/utils.zen"), Group::Local);
+        assert_eq!(Group::of("components"), Group::Local);
+    }
+}
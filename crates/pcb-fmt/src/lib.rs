@@ -1,3 +1,5 @@
+pub mod imports;
+
 use std::path::Path;
 
 use anyhow::Context;
@@ -7,6 +9,7 @@ use similar::TextDiff;
 
 pub struct RuffFormatter {
     options: PyFormatOptions,
+    fix_imports: bool,
 }
 
 impl Default for RuffFormatter {
@@ -15,13 +18,27 @@ impl Default for RuffFormatter {
             options: PyFormatOptions::default()
                 .with_line_width(LineWidth::try_from(120).unwrap())
                 .with_indent_style(IndentStyle::Space),
+            fix_imports: false,
         }
     }
 }
 
 impl RuffFormatter {
+    /// Also organize `load()` statements before formatting; see [`imports`].
+    pub fn with_fix_imports(mut self, fix_imports: bool) -> Self {
+        self.fix_imports = fix_imports;
+        self
+    }
+
     /// Format source code string directly, returning the formatted code.
     pub fn format_source(&self, source: &str) -> anyhow::Result<String> {
+        let organized;
+        let source = if self.fix_imports {
+            organized = imports::fix_imports("<source>", source)?;
+            organized.as_str()
+        } else {
+            source
+        };
         format_module_source(source, self.options.clone())
             .context("Failed to format source")
             .map(|formatted| formatted.into_code())
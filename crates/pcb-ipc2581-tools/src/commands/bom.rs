@@ -101,6 +101,13 @@ pub fn execute(file: &Path, format: OutputFormat, offline: bool) -> Result<()> {
     Ok(())
 }
 
+/// Read the BOM of an IPC-2581 file, including AVL alternates.
+pub fn load_bom(file: &Path) -> Result<Bom> {
+    let content = file_utils::load_ipc_file(file)?;
+    let ipc = ipc2581::Ipc2581::parse(&content)?;
+    extract_bom_from_ipc(&IpcAccessor::new(&ipc))
+}
+
 /// Extract BOM data from IPC-2581 and convert to pcb_sch::Bom format
 fn extract_bom_from_ipc(accessor: &IpcAccessor) -> Result<Bom> {
     let ipc = accessor.ipc();
@@ -0,0 +1,162 @@
+use std::path::Path;
+
+use anyhow::Result;
+use colored::Colorize;
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::{Cell, Color, Table};
+use pcb_ir::dialects::placement::{Placement, PlacementMount, PlacementSide};
+use serde::Serialize;
+
+use super::net::{format_position, pad_columns};
+use crate::accessors::IpcAccessor;
+use crate::connectivity::{Connectivity, PinConnection};
+use crate::placement::extract_single_board_placements;
+use crate::utils::file as file_utils;
+use crate::{OutputFormat, UnitFormat};
+
+#[derive(Debug, Serialize)]
+struct ComponentReport<'a> {
+    ref_des: &'a str,
+    value: Option<&'a str>,
+    package: Option<&'a str>,
+    part: &'a str,
+    layer: &'a str,
+    side: &'static str,
+    mount: &'static str,
+    x_mm: f64,
+    y_mm: f64,
+    rotation_degrees: f64,
+    mirror: bool,
+    populate: Option<bool>,
+    pins: Vec<&'a PinConnection>,
+}
+
+pub fn execute(
+    file: &Path,
+    ref_des: &str,
+    format: OutputFormat,
+    unit_format: UnitFormat,
+) -> Result<()> {
+    let content = file_utils::load_ipc_file(file)?;
+    let ipc = ipc2581::Ipc2581::parse(&content)?;
+    let placements = extract_single_board_placements(&IpcAccessor::new(&ipc))?;
+    let Some(placement) = placements
+        .components
+        .iter()
+        .find(|component| component.designator == ref_des)
+    else {
+        anyhow::bail!("Component '{ref_des}' not found in {}", file.display());
+    };
+    let connectivity = Connectivity::extract(&ipc)?;
+    let report = build_report(placement, connectivity.component_pins(ref_des));
+
+    match format {
+        OutputFormat::Text => output_text(&report, unit_format),
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            Ok(())
+        }
+    }
+}
+
+fn build_report<'a>(placement: &'a Placement, pins: Vec<&'a PinConnection>) -> ComponentReport<'a> {
+    ComponentReport {
+        ref_des: &placement.designator,
+        value: placement.value.as_deref(),
+        package: placement.package.as_deref(),
+        part: &placement.part,
+        layer: &placement.layer_ref,
+        side: side_name(placement.side),
+        mount: mount_name(placement.mount),
+        x_mm: placement.at.x,
+        y_mm: placement.at.y,
+        rotation_degrees: placement.rotation_degrees,
+        mirror: placement.mirror,
+        populate: placement.populate,
+        pins,
+    }
+}
+
+fn output_text(report: &ComponentReport, unit_format: UnitFormat) -> Result<()> {
+    println!("{}", format!("Component {}", report.ref_des).bold());
+
+    let mut summary = Table::new();
+    summary.load_preset(UTF8_FULL_CONDENSED);
+    let mut row = |label: &str, value: String| {
+        summary.add_row(vec![Cell::new(label).fg(Color::Cyan), Cell::new(value)]);
+    };
+    if let Some(value) = report.value {
+        row("Value", value.to_string());
+    }
+    if let Some(package) = report.package {
+        row("Package", package.to_string());
+    }
+    row("Part", report.part.to_string());
+    row("Side", format!("{} ({})", report.side, report.layer));
+    row("Mount", report.mount.to_string());
+    row(
+        "Position",
+        format_position(report.x_mm, report.y_mm, unit_format),
+    );
+    row(
+        "Rotation",
+        format!(
+            "{}°{}",
+            report.rotation_degrees,
+            if report.mirror { ", mirrored" } else { "" }
+        ),
+    );
+    if report.populate == Some(false) {
+        row("Populate", "no (DNP)".to_string());
+    }
+    println!("{summary}");
+
+    if report.pins.is_empty() {
+        return Ok(());
+    }
+    println!();
+    println!("{}", "Pins".bold());
+    let mut pins = Table::new();
+    pins.load_preset(UTF8_FULL_CONDENSED);
+    pins.set_header(vec![
+        Cell::new("Pin").fg(Color::Cyan),
+        Cell::new("Net").fg(Color::Cyan),
+        Cell::new("Layer").fg(Color::Cyan),
+        Cell::new("Position").fg(Color::Cyan),
+    ]);
+    for pin in &report.pins {
+        let (layers, position) = pad_columns(&pin.pads, unit_format);
+        pins.add_row(vec![
+            Cell::new(&pin.pin),
+            Cell::new(pin.net.as_deref().unwrap_or("-")),
+            Cell::new(layers),
+            Cell::new(position),
+        ]);
+    }
+    println!("{pins}");
+    Ok(())
+}
+
+fn side_name(side: PlacementSide) -> &'static str {
+    match side {
+        PlacementSide::Top => "top",
+        PlacementSide::Bottom => "bottom",
+        PlacementSide::Internal => "internal",
+        PlacementSide::Unknown => "unknown",
+    }
+}
+
+fn mount_name(mount: PlacementMount) -> &'static str {
+    match mount {
+        PlacementMount::Smt => "smt",
+        PlacementMount::ThroughHole => "tht",
+        PlacementMount::Embedded => "embedded",
+        PlacementMount::PressFit => "press-fit",
+        PlacementMount::WireBonded => "wire-bonded",
+        PlacementMount::Glued => "glued",
+        PlacementMount::Clamped => "clamped",
+        PlacementMount::Socketed => "socketed",
+        PlacementMount::Formed => "formed",
+        PlacementMount::Other => "other",
+    }
+}
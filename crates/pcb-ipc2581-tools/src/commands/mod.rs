@@ -2,10 +2,12 @@ pub mod board_array;
 pub mod board_array_auto;
 pub mod bom;
 pub mod bom_edit;
+pub mod component;
 pub mod cpl;
 pub mod dfm;
 pub mod html_export;
 pub mod info;
+pub mod net;
 pub mod outline;
 pub mod render;
 pub mod view;
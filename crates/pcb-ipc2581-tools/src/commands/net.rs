@@ -0,0 +1,98 @@
+use std::path::Path;
+
+use anyhow::Result;
+use colored::Colorize;
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::{Cell, Color, Table};
+use serde_json::json;
+
+use crate::connectivity::{Connectivity, PadLocation, PinConnection};
+use crate::utils::{file as file_utils, units};
+use crate::{OutputFormat, UnitFormat};
+
+pub fn execute(
+    file: &Path,
+    net: &str,
+    format: OutputFormat,
+    unit_format: UnitFormat,
+) -> Result<()> {
+    let content = file_utils::load_ipc_file(file)?;
+    let ipc = ipc2581::Ipc2581::parse(&content)?;
+    let connectivity = Connectivity::extract(&ipc)?;
+    if !connectivity.has_net(net) {
+        anyhow::bail!("Net '{net}' not found in {}", file.display());
+    }
+    let pins = connectivity.net_pins(net);
+
+    match format {
+        OutputFormat::Text => output_text(net, &pins, unit_format),
+        OutputFormat::Json => {
+            let mut components: Vec<&str> = pins.iter().map(|pin| pin.component.as_str()).collect();
+            components.dedup();
+            let output = json!({
+                "net": net,
+                "components": components,
+                "pins": pins,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+            Ok(())
+        }
+    }
+}
+
+fn output_text(net: &str, pins: &[&PinConnection], unit_format: UnitFormat) -> Result<()> {
+    let mut components: Vec<&str> = pins.iter().map(|pin| pin.component.as_str()).collect();
+    components.dedup();
+    println!(
+        "{} {}",
+        format!("Net {net}").bold(),
+        format!("({} pins, {} components)", pins.len(), components.len()).dimmed()
+    );
+    if pins.is_empty() {
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED);
+    table.set_header(vec![
+        Cell::new("Component").fg(Color::Cyan),
+        Cell::new("Pin").fg(Color::Cyan),
+        Cell::new("Layer").fg(Color::Cyan),
+        Cell::new("Position").fg(Color::Cyan),
+    ]);
+    for pin in pins {
+        let (layers, position) = pad_columns(&pin.pads, unit_format);
+        table.add_row(vec![
+            Cell::new(&pin.component),
+            Cell::new(&pin.pin),
+            Cell::new(layers),
+            Cell::new(position),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// Layer and position columns for a pin's pads. Through-hole pins have one
+/// pad per layer at the same position, so distinct positions are listed once.
+pub(crate) fn pad_columns(pads: &[PadLocation], unit_format: UnitFormat) -> (String, String) {
+    let layers = pads
+        .iter()
+        .map(|pad| pad.layer.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut positions: Vec<String> = pads
+        .iter()
+        .map(|pad| format_position(pad.x_mm, pad.y_mm, unit_format))
+        .collect();
+    positions.dedup();
+    (layers, positions.join(", "))
+}
+
+pub(crate) fn format_position(x_mm: f64, y_mm: f64, unit_format: UnitFormat) -> String {
+    format!(
+        "({}, {})",
+        units::convert_mm(x_mm, unit_format),
+        units::convert_mm(y_mm, unit_format)
+    )
+}
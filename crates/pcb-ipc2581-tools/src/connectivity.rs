@@ -0,0 +1,198 @@
+//! Pin-level connectivity of a single board: the net each component pin is on
+//! and where its pads land, for net and component lookups.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use ipc2581::Ipc2581;
+use serde::Serialize;
+
+use crate::placement::single_board_step;
+
+/// One pad of a pin on a copper layer, in board coordinates (mm).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PadLocation {
+    pub layer: String,
+    pub x_mm: f64,
+    pub y_mm: f64,
+}
+
+/// A component pin with its net and pads.
+#[derive(Debug, Clone, Serialize)]
+pub struct PinConnection {
+    pub component: String,
+    pub pin: String,
+    pub net: Option<String>,
+    pub pads: Vec<PadLocation>,
+}
+
+#[derive(Debug, Default)]
+pub struct Connectivity {
+    nets: BTreeSet<String>,
+    pins: BTreeMap<(String, String), PinConnection>,
+}
+
+impl Connectivity {
+    /// Collect pins from LogicalNet PinRefs and pad locations from the
+    /// LayerFeature pads that reference them.
+    pub fn extract(ipc: &Ipc2581) -> Result<Self> {
+        let step = single_board_step(ipc)?;
+        let mut connectivity = Self::default();
+
+        for net in &step.logical_nets {
+            let name = ipc.resolve(net.name).to_string();
+            for pin_ref in &net.pin_refs {
+                let Some(component) = pin_ref.component_ref else {
+                    continue;
+                };
+                connectivity
+                    .pin_mut(ipc.resolve(component), ipc.resolve(pin_ref.pin))
+                    .net = Some(name.clone());
+            }
+            connectivity.nets.insert(name);
+        }
+
+        for layer_feature in &step.layer_features {
+            let layer = ipc.resolve(layer_feature.layer_ref);
+            for set in &layer_feature.sets {
+                for pad in set.pads() {
+                    let Some(pin_ref) = &pad.pin_ref else {
+                        continue;
+                    };
+                    let Some(component) = pin_ref.component_ref else {
+                        continue;
+                    };
+                    let pin =
+                        connectivity.pin_mut(ipc.resolve(component), ipc.resolve(pin_ref.pin));
+                    if pin.net.is_none() {
+                        pin.net = set.net.map(|net| ipc.resolve(net).to_string());
+                    }
+                    if let (Some(x_mm), Some(y_mm)) = (pad.x, pad.y) {
+                        let location = PadLocation {
+                            layer: layer.to_string(),
+                            x_mm,
+                            y_mm,
+                        };
+                        if !pin.pads.contains(&location) {
+                            pin.pads.push(location);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(connectivity)
+    }
+
+    fn pin_mut(&mut self, component: &str, pin: &str) -> &mut PinConnection {
+        self.pins
+            .entry((component.to_string(), pin.to_string()))
+            .or_insert_with(|| PinConnection {
+                component: component.to_string(),
+                pin: pin.to_string(),
+                net: None,
+                pads: Vec::new(),
+            })
+    }
+
+    /// Whether `net` is a LogicalNet of the board, even one without pins.
+    pub fn has_net(&self, net: &str) -> bool {
+        self.nets.contains(net)
+    }
+
+    /// Pins on `net`, in natural designator and pin order.
+    pub fn net_pins(&self, net: &str) -> Vec<&PinConnection> {
+        let mut pins: Vec<_> = self
+            .pins
+            .values()
+            .filter(|pin| pin.net.as_deref() == Some(net))
+            .collect();
+        pins.sort_by(|a, b| {
+            natord::compare(&a.component, &b.component)
+                .then_with(|| natord::compare(&a.pin, &b.pin))
+        });
+        pins
+    }
+
+    /// Pins of `component`, in natural pin order.
+    pub fn component_pins(&self, component: &str) -> Vec<&PinConnection> {
+        let mut pins: Vec<_> = self
+            .pins
+            .values()
+            .filter(|pin| pin.component == component)
+            .collect();
+        pins.sort_by(|a, b| natord::compare(&a.pin, &b.pin));
+        pins
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_logical_nets_with_pad_locations() {
+        let ipc = Ipc2581::parse(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<IPC-2581 revision="C" xmlns="http://webstds.ipc.org/2581">
+  <Content roleRef="owner">
+    <FunctionMode mode="ASSEMBLY"/>
+  </Content>
+  <Ecad>
+    <CadHeader units="MILLIMETER"/>
+    <CadData>
+      <Layer name="F.Cu" layerFunction="SIGNAL" side="TOP"/>
+      <Step name="board" type="BOARD">
+        <Component refDes="R1" part="10k" layerRef="F.Cu" mountType="SMT">
+          <Location x="10" y="5"/>
+        </Component>
+        <LogicalNet name="GND">
+          <PinRef componentRef="R1" pin="2"/>
+          <PinRef componentRef="U10" pin="4"/>
+          <PinRef componentRef="U2" pin="4"/>
+        </LogicalNet>
+        <LogicalNet name="NC"/>
+        <LayerFeature layerRef="F.Cu">
+          <Set net="GND">
+            <Pad>
+              <Location x="10.5" y="5"/>
+              <PinRef componentRef="R1" pin="2"/>
+            </Pad>
+          </Set>
+          <Set net="VCC">
+            <Pad>
+              <Location x="9.5" y="5"/>
+              <PinRef componentRef="R1" pin="1"/>
+            </Pad>
+          </Set>
+        </LayerFeature>
+      </Step>
+    </CadData>
+  </Ecad>
+</IPC-2581>"#,
+        )
+        .unwrap();
+        let connectivity = Connectivity::extract(&ipc).unwrap();
+
+        let gnd: Vec<_> = connectivity
+            .net_pins("GND")
+            .iter()
+            .map(|pin| format!("{}.{}", pin.component, pin.pin))
+            .collect();
+        assert_eq!(gnd, ["R1.2", "U2.4", "U10.4"]);
+        assert!(connectivity.has_net("NC"));
+        assert!(connectivity.net_pins("NC").is_empty());
+
+        let r1 = connectivity.component_pins("R1");
+        assert_eq!(r1.len(), 2);
+        assert_eq!(r1[0].net.as_deref(), Some("VCC"));
+        assert_eq!(
+            r1[1].pads,
+            [PadLocation {
+                layer: "F.Cu".to_string(),
+                x_mm: 10.5,
+                y_mm: 5.0,
+            }]
+        );
+    }
+}
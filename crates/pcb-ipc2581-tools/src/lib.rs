@@ -4,6 +4,7 @@ use ipc2581::Mode;
 pub mod accessors;
 pub mod board_array;
 pub mod commands;
+pub mod connectivity;
 pub mod geometry;
 pub mod gerber;
 pub mod layers;
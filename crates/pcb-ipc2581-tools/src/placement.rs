@@ -9,14 +9,21 @@ use pcb_ir::dialects::placement::{
 use pcb_ir::geom::Point;
 
 use crate::accessors::{CharacteristicsData, IpcAccessor};
+use crate::steps;
+
+/// The Step that carries the components of a single board: the primary Step,
+/// or the one board Step it repeats when the primary Step is a board array.
+pub fn single_board_step(ipc: &Ipc2581) -> Result<&Step> {
+    let ecad = ipc.ecad().context("IPC-2581 file has no ECAD section")?;
+    let primary_step = steps::primary_step(ipc, &ecad.cad_data.steps)
+        .context("IPC-2581 file has no primary Step")?;
+    cpl_source_step(ipc, primary_step, &ecad.cad_data.steps)
+}
 
 pub fn extract_single_board_placements(accessor: &IpcAccessor<'_>) -> Result<PlacementDocument> {
     let ipc = accessor.ipc();
     let ecad = ipc.ecad().context("IPC-2581 file has no ECAD section")?;
-    let primary_step = accessor
-        .primary_step()
-        .context("IPC-2581 file has no primary Step")?;
-    let step = cpl_source_step(ipc, primary_step, &ecad.cad_data.steps)?;
+    let step = single_board_step(ipc)?;
 
     let layer_sides = ecad
         .cad_data
@@ -28,6 +28,39 @@ pub fn append_file_revision(original_xml: &str, comment: &str) -> Result<String>
     Ok(edit::apply(original_xml, edits)?)
 }
 
+/// Set the `label` of the file's FileRevision, the board revision the
+/// file describes (e.g. `"B"`). Other attributes and children are kept.
+pub fn label_file_revision(original_xml: &str, label: &str) -> Result<String> {
+    let doc = Doc::parse(original_xml)?;
+    let root = doc.root()?;
+    let Some(revision) = doc
+        .child(root, "HistoryRecord")
+        .and_then(|record| doc.child(record, "FileRevision"))
+    else {
+        bail!("IPC-2581 file has no FileRevision to label");
+    };
+
+    let mut attrs: Vec<(String, String)> = doc
+        .attrs(revision)
+        .map(|(key, value)| match key {
+            "label" => (key.to_string(), label.to_string()),
+            _ => (key.to_string(), value.to_string()),
+        })
+        .collect();
+    if doc.attr(revision, "label").is_none() {
+        attrs.push(("label".to_string(), label.to_string()));
+    }
+
+    let mut writer = XmlWriter::new();
+    if doc.source(revision).ends_with("/>") {
+        writer.empty_element_with("FileRevision", attrs);
+    } else {
+        writer.start_element_with("FileRevision", attrs);
+    }
+    let edit = doc.replace_start_tag(revision, writer.into_string());
+    Ok(edit::apply(original_xml, vec![edit])?)
+}
+
 /// The edits behind [`append_file_revision`], for composing with other edits
 /// against the same parsed document in a single splice pass.
 pub fn file_revision_edits(doc: &Doc, comment: &str) -> Result<Vec<Edit>> {
@@ -307,6 +340,26 @@ mod tests {
         assert!(result.contains("change=\"BOM alternatives added\""));
     }
 
+    #[test]
+    fn test_label_file_revision() {
+        let original = r#"<?xml version="1.0"?>
+<IPC-2581>
+  <HistoryRecord number="1" origination="2025-10-23T16:30:12" software="KiCad EDA" lastChange="2025-10-23T16:30:12">
+    <FileRevision fileRevisionId="1" comment="Initial export" label="">
+      <SoftwarePackage name="KiCad" revision="9.0.5" vendor="KiCad EDA"/>
+    </FileRevision>
+  </HistoryRecord>
+</IPC-2581>"#;
+
+        let result = label_file_revision(original, "B").unwrap();
+
+        assert!(result.contains(
+            "<FileRevision fileRevisionId=\"1\" comment=\"Initial export\" label=\"B\">"
+        ));
+        assert!(result.contains("<SoftwarePackage name=\"KiCad\""));
+        assert!(label_file_revision("<IPC-2581/>", "B").is_err());
+    }
+
     #[test]
     fn test_existing_change_records_preserved() {
         let original = r#"<?xml version="1.0"?>
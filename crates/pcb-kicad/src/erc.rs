@@ -1,9 +1,11 @@
 use anyhow::{Context, Result};
 use log::warn;
+use pcb_sexpr::kicad as sexpr_kicad;
 use pcb_zen_core::diagnostics::{Diagnostic, Diagnostics};
 use pcb_zen_core::lang::error::CategorizedDiagnostic;
 use serde::{Deserialize, Serialize};
 use starlark::errors::EvalSeverity;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// KiCad ERC report structure matching the JSON schema
@@ -56,6 +58,57 @@ pub struct ErcPosition {
     pub y: f64,
 }
 
+/// A placed symbol that an ERC item can point at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolRef {
+    pub reference: String,
+    /// Component instance path from the symbol's `Path` property
+    pub path: Option<String>,
+}
+
+/// Placed symbols of a `.kicad_sch`, keyed by the UUID of the symbol and of
+/// each of its pins, which is what ERC items refer to.
+#[derive(Debug, Clone, Default)]
+pub struct SchematicSymbols {
+    by_uuid: HashMap<String, SymbolRef>,
+}
+
+impl SchematicSymbols {
+    /// Index the placed symbols of a schematic
+    pub fn parse(text: &str) -> Result<Self> {
+        let root = pcb_sexpr::parse(text)
+            .map_err(|e| anyhow::anyhow!("Failed to parse schematic: {e}"))?;
+        let mut by_uuid = HashMap::new();
+        for symbol in root.find_all_lists("symbol") {
+            let properties = sexpr_kicad::schematic_properties(symbol);
+            let symbol_ref = SymbolRef {
+                reference: properties.get("Reference").cloned().unwrap_or_default(),
+                path: properties.get("Path").filter(|p| !p.is_empty()).cloned(),
+            };
+            let pins = sexpr_kicad::schematic_pins(symbol).unwrap_or_default();
+            for uuid in sexpr_kicad::string_prop(symbol, "uuid")
+                .into_iter()
+                .chain(pins.into_values())
+            {
+                by_uuid.insert(uuid, symbol_ref.clone());
+            }
+        }
+        Ok(Self { by_uuid })
+    }
+
+    /// Index the placed symbols of a schematic file
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&contents)
+    }
+
+    pub fn get(&self, uuid: &str) -> Option<&SymbolRef> {
+        self.by_uuid.get(uuid)
+    }
+}
+
 impl ErcReport {
     /// Parse an ERC report from JSON string
     pub fn from_json(json: &str) -> Result<Self> {
@@ -87,12 +140,37 @@ impl ErcReport {
 impl ErcViolation {
     /// Convert an ERC violation to a diagnostic
     pub fn to_diagnostic(&self, sch_path: &str, sheet_path: &str) -> Result<Diagnostic> {
+        self.to_instance_diagnostic(sch_path, sheet_path, &[])
+    }
+
+    /// Symbols involved in this violation, in item order, without duplicates
+    pub fn symbols<'a>(&self, symbols: &'a SchematicSymbols) -> Vec<&'a SymbolRef> {
+        let mut out: Vec<&SymbolRef> = Vec::new();
+        for symbol in self.items.iter().filter_map(|item| symbols.get(&item.uuid)) {
+            if !out.contains(&symbol) {
+                out.push(symbol);
+            }
+        }
+        out
+    }
+
+    /// Convert an ERC violation to a diagnostic that names the component
+    /// `instances` it involves
+    pub fn to_instance_diagnostic(
+        &self,
+        sch_path: &str,
+        sheet_path: &str,
+        instances: &[String],
+    ) -> Result<Diagnostic> {
         let kind = format!("schematic.erc.{}", self.violation_type);
 
         let mut message = format!("[{}] {}", self.violation_type, self.description);
         if !sheet_path.is_empty() {
             message.push_str(&format!("\n  Sheet: {}", sheet_path));
         }
+        if !instances.is_empty() {
+            message.push_str(&format!("\n  Instance: {}", instances.join(", ")));
+        }
         if let Some(comment) = &self.comment
             && !comment.trim().is_empty()
         {
@@ -193,4 +271,30 @@ mod tests {
         assert!(matches!(d.severity, EvalSeverity::Warning));
         assert!(d.body.contains("Pin not connected"));
     }
+
+    #[test]
+    fn test_symbols_from_pin_uuid() {
+        let symbols = SchematicSymbols::parse(
+            r#"(kicad_sch
+                (symbol (lib_id "Device:R") (uuid "22222222-2222-2222-2222-222222222222")
+                    (property "Reference" "U1")
+                    (property "Path" "Power.Reg.U1")
+                    (pin "1" (uuid "11111111-1111-1111-1111-111111111111"))))"#,
+        )
+        .unwrap();
+        let report = ErcReport::from_json(SAMPLE_ERC_JSON).unwrap();
+        let violation = &report.sheets[0].violations[0];
+        assert_eq!(
+            violation.symbols(&symbols),
+            vec![&SymbolRef {
+                reference: "U1".to_string(),
+                path: Some("Power.Reg.U1".to_string()),
+            }]
+        );
+
+        let d = violation
+            .to_instance_diagnostic("example.kicad_sch", "/", &["Power.Reg.U1".to_string()])
+            .unwrap();
+        assert!(d.body.contains("Instance: Power.Reg.U1"));
+    }
 }
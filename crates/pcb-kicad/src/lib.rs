@@ -1,9 +1,10 @@
 pub mod drc;
 pub mod erc;
 pub mod footprint;
+pub mod project;
 
 use anyhow::{Context, Result, anyhow};
-use pcb_command_runner::CommandRunner;
+use pcb_command_runner::{CommandFailed, CommandRunner};
 use pcb_sexpr::Sexpr;
 use pcb_zen_core::Diagnostics;
 use std::collections::HashMap;
@@ -828,6 +829,7 @@ impl PythonScriptBuilder {
         }
 
         // Add log file if provided
+        let logged = self.log_file.is_some();
         if let Some(log_file) = self.log_file {
             cmd = cmd.log_file(log_file);
         }
@@ -836,8 +838,15 @@ impl PythonScriptBuilder {
         let output = cmd.run().context("Failed to execute Python script")?;
 
         if !output.success {
-            std::io::stderr().write_all(&output.raw_output)?;
-            anyhow::bail!("Python script execution failed");
+            // Logged output stays in the log; callers can read it from the error.
+            if !logged {
+                std::io::stderr().write_all(&output.raw_output)?;
+            }
+            return Err(CommandFailed {
+                message: "Python script execution failed".to_string(),
+                output,
+            }
+            .into());
         }
 
         Ok(())
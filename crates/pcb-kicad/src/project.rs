@@ -0,0 +1,346 @@
+//! Typed access to KiCad project files (`.kicad_pro`) and pcb-managed settings.
+//!
+//! A `.kicad_pro` is JSON that KiCad and users both edit. pcb owns a few
+//! settings in it (text variables and netclass patterns) and must update those
+//! without clobbering anything the user changed in KiCad. ERC/DRC rule
+//! severities can be read here but no pcb setting writes them yet, so the DRC
+//! and ERC runners still take severities from KiCad's own reports. Plot
+//! presets are stored in the `.kicad_pcb`, not here.
+//!
+//! [`KicadProject::merge_managed`] implements that "managed keys" strategy
+//! for keyed settings maps: the keys pcb writes are recorded under
+//! `pcb.managed.<section>`, so a later merge updates them, removes the ones
+//! pcb no longer sets, and leaves every other key alone. Settings stored as
+//! arrays, such as [`NETCLASS_PATTERNS`], keep their own merge logic but
+//! record their keys in the same ledger via [`ledger_keys`] and
+//! [`set_ledger_keys`].
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+
+/// `text_variables`: `${NAME}` substitutions used in title blocks and silkscreen.
+pub const TEXT_VARIABLES: &str = "text_variables";
+/// DRC rule severities, keyed by KiCad rule name (e.g. `silk_overlap`).
+pub const DRC_RULE_SEVERITIES: &str = "board.design_settings.rule_severities";
+/// ERC rule severities, keyed by KiCad rule name (e.g. `pin_not_connected`).
+pub const ERC_RULE_SEVERITIES: &str = "erc.rule_severities";
+/// Net name patterns assigned to netclasses, keyed by `pattern`.
+pub const NETCLASS_PATTERNS: &str = "net_settings.netclass_patterns";
+
+/// Where the keys pcb manages are recorded, one list per section.
+const MANAGED_LEDGER: &str = "pcb.managed";
+
+/// Severity KiCad assigns to an ERC/DRC rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleSeverity {
+    Error,
+    Warning,
+    Ignore,
+}
+
+impl RuleSeverity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RuleSeverity::Error => "error",
+            RuleSeverity::Warning => "warning",
+            RuleSeverity::Ignore => "ignore",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "error" => Some(RuleSeverity::Error),
+            "warning" => Some(RuleSeverity::Warning),
+            "ignore" => Some(RuleSeverity::Ignore),
+            _ => None,
+        }
+    }
+}
+
+/// A managed key that a merge changed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManagedChange {
+    /// Set to a new value (previously absent or different).
+    Set { key: String, value: Value },
+    /// Previously managed by pcb and no longer set, so removed.
+    Removed { key: String, value: Value },
+}
+
+/// A parsed `.kicad_pro` file.
+#[derive(Debug, Clone)]
+pub struct KicadProject {
+    path: PathBuf,
+    value: Value,
+}
+
+impl KicadProject {
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let value: Value = serde_json::from_str(&source)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        if !value.is_object() {
+            anyhow::bail!("KiCad project file root must be a JSON object");
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            value,
+        })
+    }
+
+    /// Load `path` if it exists. Boards saved without a project file have
+    /// no `.kicad_pro` next to them.
+    pub fn load_if_exists(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        Self::load(path).map(Some)
+    }
+
+    /// Write the project back to the file it was loaded from, pretty-printed.
+    pub fn save(&self) -> Result<()> {
+        let mut serialized = serde_json::to_string_pretty(&self.value)?;
+        serialized.push('\n');
+        fs::write(&self.path, serialized)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The raw project JSON.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    pub fn value_mut(&mut self) -> &mut Value {
+        &mut self.value
+    }
+
+    /// Value at a dotted path such as `board.design_settings.rules`.
+    pub fn get(&self, path: &str) -> Option<&Value> {
+        path.split('.')
+            .try_fold(&self.value, |current, segment| current.get(segment))
+    }
+
+    /// Set the value at a dotted path, creating intermediate objects.
+    pub fn set(&mut self, path: &str, value: Value) {
+        let mut current = &mut self.value;
+        let mut segments = path.split('.').peekable();
+        while let Some(segment) = segments.next() {
+            let object = ensure_object(current);
+            if segments.peek().is_none() {
+                object.insert(segment.to_string(), value);
+                return;
+            }
+            current = object
+                .entry(segment.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+        }
+    }
+
+    /// `net_settings.netclass_patterns` as `(pattern, netclass)` pairs.
+    pub fn netclass_patterns(&self) -> Vec<(String, String)> {
+        self.get(NETCLASS_PATTERNS)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                Some((
+                    entry.get("pattern")?.as_str()?.to_string(),
+                    entry.get("netclass")?.as_str()?.to_string(),
+                ))
+            })
+            .collect()
+    }
+
+    pub fn text_variable(&self, name: &str) -> Option<&str> {
+        self.get(TEXT_VARIABLES)?.get(name)?.as_str()
+    }
+
+    /// Set a text variable without recording it as managed.
+    pub fn set_text_variable(&mut self, name: &str, value: &str) {
+        self.set(
+            &format!("{TEXT_VARIABLES}.{name}"),
+            Value::String(value.to_string()),
+        );
+    }
+
+    /// Severity of a rule in [`DRC_RULE_SEVERITIES`] or [`ERC_RULE_SEVERITIES`].
+    pub fn rule_severity(&self, section: &str, rule: &str) -> Option<RuleSeverity> {
+        RuleSeverity::parse(self.get(section)?.get(rule)?.as_str()?)
+    }
+
+    /// Keys of `section` that pcb wrote on an earlier merge.
+    pub fn managed_keys(&self, section: &str) -> Vec<String> {
+        ledger_keys(&self.value, section)
+    }
+
+    /// Make pcb's keys in the object at `section` match `desired`.
+    ///
+    /// Keys in `desired` are written. Keys pcb wrote before but no longer
+    /// sets are removed. Every other key is the user's and is left alone.
+    /// Repeated merges with the same `desired` change nothing.
+    pub fn merge_managed(
+        &mut self,
+        section: &str,
+        desired: &BTreeMap<String, Value>,
+    ) -> Vec<ManagedChange> {
+        let previously_managed = self.managed_keys(section);
+        if desired.is_empty() && previously_managed.is_empty() {
+            return Vec::new();
+        }
+
+        let mut changes = Vec::new();
+        let mut entries = self
+            .get(section)
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        for key in &previously_managed {
+            if desired.contains_key(key) {
+                continue;
+            }
+            if let Some(value) = entries.remove(key) {
+                changes.push(ManagedChange::Removed {
+                    key: key.clone(),
+                    value,
+                });
+            }
+        }
+        for (key, value) in desired {
+            if entries.get(key) != Some(value) {
+                entries.insert(key.clone(), value.clone());
+                changes.push(ManagedChange::Set {
+                    key: key.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+        self.set(section, Value::Object(entries));
+        set_ledger_keys(&mut self.value, section, desired.keys().cloned().collect());
+
+        changes
+    }
+}
+
+/// Keys of `section` that pcb recorded as its own in the project JSON.
+pub fn ledger_keys(project: &Value, section: &str) -> Vec<String> {
+    MANAGED_LEDGER
+        .split('.')
+        .try_fold(project, |current, segment| current.get(segment))
+        .and_then(|ledger| ledger.get(section))
+        .and_then(Value::as_array)
+        .map(|keys| {
+            keys.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Record `keys` as pcb's keys of `section`. An empty list drops the entry.
+pub fn set_ledger_keys(project: &mut Value, section: &str, keys: Vec<String>) {
+    let ledger = MANAGED_LEDGER.split('.').fold(project, |current, segment| {
+        ensure_object(current)
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()))
+    });
+    let ledger = ensure_object(ledger);
+    if keys.is_empty() {
+        ledger.remove(section);
+    } else {
+        ledger.insert(
+            section.to_string(),
+            Value::Array(keys.into_iter().map(Value::String).collect()),
+        );
+    }
+}
+
+fn ensure_object(value: &mut Value) -> &mut Map<String, Value> {
+    if !value.is_object() {
+        *value = Value::Object(Map::new());
+    }
+    value.as_object_mut().expect("value must be object")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn project(value: Value) -> KicadProject {
+        KicadProject {
+            path: PathBuf::from("layout.kicad_pro"),
+            value,
+        }
+    }
+
+    #[test]
+    fn load_if_exists_skips_missing_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("layout.kicad_pro");
+        assert!(KicadProject::load_if_exists(&path).unwrap().is_none());
+
+        fs::write(&path, "{}").unwrap();
+        assert!(KicadProject::load_if_exists(&path).unwrap().is_some());
+    }
+
+    #[test]
+    fn merge_managed_updates_pcb_keys_and_preserves_user_keys() {
+        let mut project = project(json!({
+            "text_variables": { "PCB_NAME": "Old", "REV": "A" },
+            "erc": { "rule_severities": { "pin_not_connected": "error" } }
+        }));
+
+        let desired = BTreeMap::from([
+            ("PCB_NAME".to_string(), json!("Demo")),
+            ("PCB_VERSION".to_string(), json!("${PCB_VERSION}")),
+        ]);
+        let changes = project.merge_managed(TEXT_VARIABLES, &desired);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(project.text_variable("PCB_NAME"), Some("Demo"));
+        assert_eq!(project.text_variable("REV"), Some("A"));
+        assert!(project.merge_managed(TEXT_VARIABLES, &desired).is_empty());
+
+        let desired = BTreeMap::from([("PCB_VERSION".to_string(), json!("${PCB_VERSION}"))]);
+        let changes = project.merge_managed(TEXT_VARIABLES, &desired);
+        assert_eq!(
+            changes,
+            vec![ManagedChange::Removed {
+                key: "PCB_NAME".to_string(),
+                value: json!("Demo"),
+            }]
+        );
+        assert_eq!(project.text_variable("PCB_NAME"), None);
+        assert_eq!(project.text_variable("REV"), Some("A"));
+
+        let severities = BTreeMap::from([("lib_symbol_issues".to_string(), json!("ignore"))]);
+        project.merge_managed(ERC_RULE_SEVERITIES, &severities);
+        assert_eq!(
+            project.rule_severity(ERC_RULE_SEVERITIES, "lib_symbol_issues"),
+            Some(RuleSeverity::Ignore)
+        );
+        assert_eq!(
+            project.rule_severity(ERC_RULE_SEVERITIES, "pin_not_connected"),
+            Some(RuleSeverity::Error)
+        );
+        assert_eq!(
+            project.managed_keys(ERC_RULE_SEVERITIES),
+            vec!["lib_symbol_issues".to_string()]
+        );
+
+        project.merge_managed(ERC_RULE_SEVERITIES, &BTreeMap::new());
+        assert!(project.managed_keys(ERC_RULE_SEVERITIES).is_empty());
+        assert_eq!(
+            project.rule_severity(ERC_RULE_SEVERITIES, "lib_symbol_issues"),
+            None
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Split one schematic into several boards.
+//!
+//! A module that declares `Layout(..., board = True)` is laid out on its own
+//! `.kicad_pcb` (e.g. a flex daughter board next to the rigid main board)
+//! instead of being placed as a fragment of its parent's layout. Each board gets
+//! a schematic containing only its subtree, rebased so the board module becomes
+//! the root. Net names are kept as-is, so a net that crosses a connector between
+//! boards has the same name in every layout.
+
+use pcb_sch::{ATTR_LAYOUT_BOARD, ATTR_LAYOUT_PATH, InstanceKind, InstanceRef, Schematic};
+
+/// A board laid out from part of a schematic.
+#[derive(Debug)]
+pub struct LayoutBoard {
+    /// Instance path of the module that owns the board; empty for the main board.
+    pub module_path: Vec<String>,
+    /// Schematic restricted to this board's instances and nets, rooted at the
+    /// board module.
+    pub schematic: Schematic,
+}
+
+/// Instance paths of non-root modules that declare their own board.
+pub fn board_module_paths(schematic: &Schematic) -> Vec<Vec<String>> {
+    let mut paths: Vec<Vec<String>> = schematic
+        .instances
+        .iter()
+        .filter(|(instance_ref, instance)| {
+            instance.kind == InstanceKind::Module
+                && !instance_ref.instance_path.is_empty()
+                && instance.attributes.contains_key(ATTR_LAYOUT_PATH)
+                && instance.boolean_attr(&[ATTR_LAYOUT_BOARD]) == Some(true)
+        })
+        .map(|(instance_ref, _)| instance_ref.instance_path.clone())
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Split `schematic` into the main board followed by one board per module
+/// marked with `layout_board`. Returns `None` when there is only one board.
+pub fn split_boards(schematic: &Schematic) -> Option<Vec<LayoutBoard>> {
+    let board_paths = board_module_paths(schematic);
+    if board_paths.is_empty() {
+        return None;
+    }
+
+    let mut owners: Vec<Vec<String>> = vec![Vec::new()];
+    owners.extend(board_paths.iter().cloned());
+
+    Some(
+        owners
+            .iter()
+            .map(|owner| LayoutBoard {
+                module_path: owner.clone(),
+                schematic: board_schematic(schematic, owner, &board_paths),
+            })
+            .collect(),
+    )
+}
+
+/// The board module owning `path`: the deepest board path that prefixes it.
+fn owning_board<'a>(path: &[String], board_paths: &'a [Vec<String>]) -> Option<&'a [String]> {
+    board_paths
+        .iter()
+        .filter(|board| path.starts_with(board))
+        .max_by_key(|board| board.len())
+        .map(Vec::as_slice)
+}
+
+fn board_schematic(
+    schematic: &Schematic,
+    owner: &[String],
+    board_paths: &[Vec<String>],
+) -> Schematic {
+    let owns = |instance_ref: &InstanceRef| {
+        owning_board(&instance_ref.instance_path, board_paths).unwrap_or(&[]) == owner
+    };
+    let rebase = |instance_ref: &InstanceRef| {
+        InstanceRef::new(
+            instance_ref.module.clone(),
+            instance_ref.instance_path[owner.len()..].to_vec(),
+        )
+    };
+
+    let mut board = Schematic {
+        symbols: schematic.symbols.clone(),
+        package_roots: schematic.package_roots.clone(),
+        stamp: schematic.stamp.clone(),
+        revisions: schematic.revisions.clone(),
+        ..Default::default()
+    };
+
+    for (instance_ref, instance) in &schematic.instances {
+        if !owns(instance_ref) {
+            continue;
+        }
+        let mut instance = instance.clone();
+        instance.children = instance
+            .children
+            .iter()
+            .filter(|(_, child)| owns(child))
+            .map(|(name, child)| (name.clone(), rebase(child)))
+            .collect();
+        board.add_instance(rebase(instance_ref), instance);
+    }
+
+    board.root_ref = schematic
+        .root_ref
+        .as_ref()
+        .map(|root| InstanceRef::new(root.module.clone(), Vec::new()));
+
+    for net in schematic.nets.values() {
+        let ports: Vec<InstanceRef> = net.ports.iter().filter(|p| owns(p)).map(rebase).collect();
+        if ports.is_empty() {
+            continue;
+        }
+        let mut net = net.clone();
+        net.ports = ports;
+        board.add_net(net);
+    }
+
+    let prefix = owner.join(".");
+    board.moved_paths = schematic
+        .moved_paths
+        .iter()
+        .filter_map(|(old, new)| {
+            if owner.is_empty() {
+                return Some((old.clone(), new.clone()));
+            }
+            let old = old.strip_prefix(&prefix)?.strip_prefix('.')?;
+            let new = new.strip_prefix(&prefix)?.strip_prefix('.')?;
+            Some((old.to_string(), new.to_string()))
+        })
+        .collect();
+
+    board
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pcb_sch::{AttributeValue, Instance, ModuleRef, Net};
+
+    fn path(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn split_boards_partitions_subtrees_and_keeps_net_names() {
+        let module = ModuleRef::new("/tmp/board.zen", "<root>");
+        let iref = |parts: &[&str]| InstanceRef::new(module.clone(), path(parts));
+
+        let mut schematic = Schematic::new();
+        schematic.set_root_ref(iref(&[]));
+        schematic.add_instance(
+            iref(&[]),
+            Instance::module(module.clone())
+                .with_attribute(ATTR_LAYOUT_PATH, AttributeValue::String("main".into()))
+                .with_child("flex", iref(&["flex"])),
+        );
+        schematic.add_instance(
+            iref(&["flex"]),
+            Instance::module(module.clone())
+                .with_attribute(ATTR_LAYOUT_PATH, AttributeValue::String("flex".into()))
+                .with_attribute(ATTR_LAYOUT_BOARD, AttributeValue::Boolean(true)),
+        );
+        schematic.add_instance(iref(&["J1"]), Instance::component(module.clone()));
+        schematic.add_instance(iref(&["J1", "P1"]), Instance::port(module.clone()));
+        schematic.add_instance(iref(&["flex", "J2"]), Instance::component(module.clone()));
+        schematic.add_instance(iref(&["flex", "J2", "P1"]), Instance::port(module.clone()));
+        schematic.add_net(
+            Net::new("Net".into(), "SDA", 1)
+                .with_port(iref(&["J1", "P1"]))
+                .with_port(iref(&["flex", "J2", "P1"])),
+        );
+        schematic
+            .moved_paths
+            .insert("flex.J_OLD".into(), "flex.J2".into());
+
+        let boards = split_boards(&schematic).unwrap();
+        assert_eq!(boards.len(), 2);
+
+        let main = &boards[0];
+        assert!(main.module_path.is_empty());
+        assert!(main.schematic.instances.contains_key(&iref(&["J1"])));
+        assert!(!main.schematic.instances.contains_key(&iref(&["flex"])));
+        assert!(main.schematic.instances[&iref(&[])].children.is_empty());
+        assert_eq!(main.schematic.nets["SDA"].ports, vec![iref(&["J1", "P1"])]);
+
+        let flex = &boards[1];
+        assert_eq!(flex.module_path, path(&["flex"]));
+        assert_eq!(flex.schematic.root_ref, Some(iref(&[])));
+        assert_eq!(
+            flex.schematic
+                .root()
+                .unwrap()
+                .string_attr(&[ATTR_LAYOUT_PATH]),
+            Some("flex".to_string())
+        );
+        assert!(flex.schematic.instances.contains_key(&iref(&["J2"])));
+        assert_eq!(flex.schematic.nets["SDA"].ports, vec![iref(&["J2", "P1"])]);
+        assert_eq!(flex.schematic.moved_paths["J_OLD"], "J2");
+    }
+
+    #[test]
+    fn single_board_is_not_split() {
+        let module = ModuleRef::new("/tmp/board.zen", "<root>");
+        let root = InstanceRef::new(module.clone(), Vec::new());
+        let mut schematic = Schematic::new();
+        schematic.set_root_ref(root.clone());
+        schematic.add_instance(root, Instance::module(module));
+        assert!(split_boards(&schematic).is_none());
+    }
+}
@@ -2,12 +2,12 @@ use anyhow::{Context, Result, bail};
 use pcb_sch::kicad_netlist::try_format_footprint_with_package_roots;
 use pcb_sch::{AttributeValue, InstanceKind, Schematic};
 use pcb_sexpr::Sexpr;
-use pcb_sexpr::board::{extract_keyed_footprints, footprint_name_from_fpid};
+use pcb_sexpr::board::{
+    copper_net_item_counts, extract_keyed_footprints, footprint_name_from_fpid,
+};
 use std::collections::{BTreeMap, BTreeSet};
 use uuid::Uuid;
 
-const UUID_NAMESPACE_URL: Uuid = Uuid::from_u128(0x6ba7b811_9dad_11d1_80b4_00c04fd430c8);
-
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Port {
     pub component_path: String,
@@ -154,14 +154,16 @@ pub fn layout_effective_netlist(
 ) -> Result<(EffectiveNetlist, Vec<SemanticDiff>)> {
     let mut effective = EffectiveNetlist::default();
     let mut diagnostics = Vec::new();
-    let keyed = extract_keyed_footprints(board).map_err(anyhow::Error::msg)?;
+    // Footprints without a KiCad path were placed by hand rather than synced
+    // from the netlist, so like board-only ones they are never orphans.
+    let footprints = extract_keyed_footprints(board).map_err(anyhow::Error::msg)?;
 
     let mut kiid_to_path: BTreeMap<String, String> = BTreeMap::new();
     for path in expected.footprints.keys() {
         kiid_to_path.insert(uuid_for_path(path), path.clone());
     }
 
-    for fp in keyed {
+    for fp in footprints {
         let property_path = fp.properties.get("Path").filter(|s| !s.is_empty()).cloned();
         let component_path = if let Some(path) = property_path.clone() {
             let expected_kiid = expected_kiid_path(&path);
@@ -185,6 +187,19 @@ pub fn layout_effective_netlist(
         } else {
             let uuid = fp.path.trim_matches('/').rsplit('/').next().unwrap_or("");
             let Some(path) = kiid_to_path.get(uuid).cloned() else {
+                if !fp.attrs.iter().any(|attr| attr == "board_only") {
+                    diagnostics.push(SemanticDiff::warning(
+                        "layout.sync.orphan_footprint",
+                        format!(
+                            "Footprint {} ({}) has no counterpart in the netlist; delete it or mark it board-only",
+                            fp.properties
+                                .get("Reference")
+                                .map(String::as_str)
+                                .unwrap_or("<unknown>"),
+                            fp.fpid.as_deref().unwrap_or(""),
+                        ),
+                    ));
+                }
                 continue;
             };
             path
@@ -433,6 +448,34 @@ pub fn diff_effective_netlists(
     diffs
 }
 
+/// Layout nets that carry copper (tracks, vias, zones) but are neither a
+/// source net nor assigned to any layout pad, typically left behind when a
+/// net is removed or renamed in source.
+pub fn orphan_copper_nets(
+    board: &Sexpr,
+    expected: &EffectiveNetlist,
+    actual: &EffectiveNetlist,
+) -> Vec<SemanticDiff> {
+    let known: BTreeSet<&str> = expected
+        .port_to_net
+        .values()
+        .chain(actual.port_to_net.values())
+        .map(String::as_str)
+        .collect();
+    copper_net_item_counts(board)
+        .into_iter()
+        .filter(|(net_name, _)| !known.contains(net_name.as_str()))
+        .map(|(net_name, count)| {
+            SemanticDiff::warning(
+                "layout.sync.orphan_net",
+                format!(
+                    "Layout net `{net_name}` has {count} copper item(s) but no pads and no source net; delete or reassign them"
+                ),
+            )
+        })
+        .collect()
+}
+
 fn mark_ports(path: &str, netlist: &EffectiveNetlist, explained: &mut BTreeSet<Port>) {
     for port in netlist.port_to_net.keys() {
         if port.component_path == path {
@@ -488,7 +531,7 @@ fn comparable_fpid(fpid: &str) -> String {
 }
 
 fn uuid_for_path(path: &str) -> String {
-    Uuid::new_v5(&UUID_NAMESPACE_URL, path.as_bytes()).to_string()
+    Uuid::new_v5(&Uuid::NAMESPACE_URL, path.as_bytes()).to_string()
 }
 
 fn expected_kiid_path(path: &str) -> String {
@@ -593,6 +636,50 @@ mod tests {
         assert!(!missing.message.contains("R1:1"));
     }
 
+    #[test]
+    fn orphan_footprints_and_copper_nets_warn() {
+        let path = "R1";
+        let uuid = uuid_for_path(path);
+        let board = pcb_sexpr::parse(&format!(
+            r#"(kicad_pcb
+              (net 1 "A")
+              (net 2 "OLD")
+              (footprint "Test:FP"
+                (path "/{uuid}/{uuid}")
+                (property "Path" "{path}")
+                (property "Reference" "R1")
+                (pad "1" smd rect (net 1 "A")))
+              (footprint "Test:FP"
+                (path "/deadbeef/deadbeef")
+                (property "Reference" "R9"))
+              (footprint "Test:Logo"
+                (path "/cafef00d/cafef00d")
+                (property "Reference" "G1")
+                (attr board_only))
+              (footprint "Test:Hole"
+                (property "Reference" "H1"))
+              (segment (start 0 0) (end 1 0) (net 1))
+              (segment (start 0 0) (end 1 0) (net 2))
+              (via (at 1 0) (net 2)))"#
+        ))
+        .unwrap();
+        let expected = netlist(&[("R1", "1", "A"), ("R2", "1", "A")]);
+
+        let (actual, diagnostics) = layout_effective_netlist(&board, &expected).unwrap();
+        let orphans = orphan_copper_nets(&board, &expected, &actual);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, "layout.sync.orphan_footprint");
+        assert!(diagnostics[0].message.starts_with("Footprint R9 (Test:FP)"));
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].kind, "layout.sync.orphan_net");
+        assert!(
+            orphans[0]
+                .message
+                .starts_with("Layout net `OLD` has 2 copper item(s)")
+        );
+    }
+
     #[test]
     fn duplicate_physical_pads_with_same_net_collapse() {
         let path = "R1";
@@ -0,0 +1,273 @@
+//! Detect footprints whose embedded copy in a `.kicad_pcb` no longer matches
+//! the library `.kicad_mod` it came from, and mark them for re-import.
+//!
+//! Both sides are reduced to a fingerprint of their geometry: the board copy
+//! is de-instanced first, then pads, graphics, zones, and 3D models are kept
+//! with uuids, nets, and pin metadata stripped and numbers rounded, so
+//! placement and KiCad's re-serialization do not count as drift.
+//!
+//! Sync treats a footprint whose FPID differs from the netlist as replaced:
+//! it removes the old copy, loads the library one, and inherits the old
+//! position, rotation, and side. [`mark_for_reimport`] uses that path by
+//! rewriting the FPID of the chosen footprints to [`REIMPORT_LIBRARY`].
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use pcb_sch::kicad_netlist::try_format_footprint_with_package_roots;
+use pcb_sch::{AttributeValue, InstanceKind, Schematic};
+use pcb_sexpr::board::{
+    extract_footprints, footprint_name_from_fpid, transform_board_instance_footprint_to_standalone,
+};
+use pcb_sexpr::{PatchSet, Sexpr, SexprKind};
+use serde::Serialize;
+
+/// Library nickname written into the FPID of footprints marked for re-import.
+pub const REIMPORT_LIBRARY: &str = "pcb-reimport";
+
+/// Children of a footprint that make up its fingerprint.
+const GEOMETRY_TAGS: &[&str] = &[
+    "pad",
+    "fp_line",
+    "fp_arc",
+    "fp_circle",
+    "fp_rect",
+    "fp_poly",
+    "fp_curve",
+    "zone",
+    "model",
+];
+
+/// Lists dropped inside geometry: identity and netlist data, not shape.
+const INSTANCE_TAGS: &[&str] = &["uuid", "tstamp", "net", "pinfunction", "pintype"];
+
+/// A board footprint that differs from its library footprint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FootprintDrift {
+    /// Schematic component path, from the footprint's `Path` property.
+    pub path: String,
+    pub reference: Option<String>,
+    pub fpid: String,
+    pub library_file: PathBuf,
+    pub board_fingerprint: String,
+    pub library_fingerprint: String,
+}
+
+/// Footprints of `board_text` that drifted from their library footprint.
+///
+/// Only footprints sync manages are compared, and only when their FPID still
+/// matches the schematic; an FPID change is already re-imported by sync.
+pub fn detect_footprint_drift(
+    schematic: &Schematic,
+    board_text: &str,
+) -> Result<Vec<FootprintDrift>> {
+    let board = pcb_sexpr::parse(board_text).context("Failed to parse layout file")?;
+    let library_by_path = library_footprints(schematic);
+
+    let mut drift = Vec::new();
+    for fp in extract_footprints(&board).map_err(anyhow::Error::msg)? {
+        let Some(path) = fp.properties.get("Path") else {
+            continue;
+        };
+        let Some((fpid, library_file)) = library_by_path.get(path) else {
+            continue;
+        };
+        if fp.fpid.as_deref() != Some(fpid.as_str()) || !library_file.exists() {
+            continue;
+        }
+
+        let standalone = transform_board_instance_footprint_to_standalone(
+            &board_text[fp.span.start..fp.span.end],
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to read footprint {path}: {e}"))?;
+        let board_fingerprint = footprint_fingerprint(&standalone)
+            .with_context(|| format!("Failed to read footprint {path}"))?;
+        let library_text = std::fs::read_to_string(library_file)
+            .with_context(|| format!("Failed to read {}", library_file.display()))?;
+        let library_fingerprint = footprint_fingerprint(&library_text)
+            .with_context(|| format!("Failed to read {}", library_file.display()))?;
+
+        if board_fingerprint != library_fingerprint {
+            drift.push(FootprintDrift {
+                path: path.clone(),
+                reference: fp.properties.get("Reference").cloned(),
+                fpid: fpid.clone(),
+                library_file: library_file.clone(),
+                board_fingerprint,
+                library_fingerprint,
+            });
+        }
+    }
+    drift.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(drift)
+}
+
+/// Expected FPID and library file of each component, keyed by instance path.
+fn library_footprints(schematic: &Schematic) -> BTreeMap<String, (String, PathBuf)> {
+    schematic
+        .instances
+        .iter()
+        .filter(|(_, instance)| instance.kind == InstanceKind::Component)
+        .filter_map(|(instance_ref, instance)| {
+            let footprint = instance
+                .attributes
+                .get("footprint")
+                .and_then(AttributeValue::string)?;
+            let (fpid, _) =
+                try_format_footprint_with_package_roots(footprint, &schematic.package_roots)
+                    .ok()?;
+            let file = if footprint.starts_with(pcb_sch::PACKAGE_URI_PREFIX) {
+                schematic.resolve_package_uri(footprint).ok()?
+            } else {
+                PathBuf::from(footprint)
+            };
+            Some((instance_ref.instance_path.join("."), (fpid, file)))
+        })
+        .collect()
+}
+
+/// Fingerprint of a standalone `(footprint ...)` S-expression.
+pub fn footprint_fingerprint(footprint_text: &str) -> Result<String> {
+    let root = pcb_sexpr::parse(footprint_text)?;
+    let items = root
+        .as_list()
+        .filter(|items| items.first().and_then(Sexpr::as_sym) == Some("footprint"))
+        .context("expected (footprint ...)")?;
+
+    let mut geometry: Vec<String> = items
+        .iter()
+        .filter(|child| {
+            child
+                .as_list()
+                .and_then(|list| list.first())
+                .and_then(Sexpr::as_sym)
+                .is_some_and(|tag| GEOMETRY_TAGS.contains(&tag))
+        })
+        .filter_map(normalize)
+        .map(|node| node.to_string())
+        .collect();
+    geometry.sort();
+
+    let mut hasher = blake3::Hasher::new();
+    for node in &geometry {
+        hasher.update(node.as_bytes());
+        hasher.update(b"\n");
+    }
+    Ok(hasher.finalize().to_hex()[..16].to_string())
+}
+
+fn normalize(node: &Sexpr) -> Option<Sexpr> {
+    match &node.kind {
+        SexprKind::List(items) => {
+            let tag = items.first().and_then(Sexpr::as_sym);
+            if tag.is_some_and(|tag| INSTANCE_TAGS.contains(&tag)) {
+                return None;
+            }
+            let mut items: Vec<Sexpr> = items.iter().filter_map(normalize).collect();
+            // `(at x y 0)`, `(at x y 360)`, and `(at x y)` are the same pad.
+            if tag == Some("at")
+                && let Some(angle) = items.get(3).and_then(Sexpr::as_float)
+            {
+                let angle = angle.rem_euclid(360.0);
+                if angle == 0.0 || (angle - 360.0).abs() < 1e-4 {
+                    items.truncate(3);
+                } else {
+                    items[3] = Sexpr::float(angle);
+                }
+            }
+            Some(Sexpr::list(items))
+        }
+        SexprKind::Int(n) => Some(Sexpr::float(*n as f64)),
+        SexprKind::F64(f) => Some(Sexpr::float((f * 1e4).round() / 1e4 + 0.0)),
+        _ => Some(node.clone()),
+    }
+}
+
+/// Rewrite the FPID of the footprints at `paths` so the next sync replaces
+/// them with their library footprint. Returns the patched board text and the
+/// paths that were found.
+pub fn mark_for_reimport(
+    board_text: &str,
+    paths: &BTreeSet<String>,
+) -> Result<(String, Vec<String>)> {
+    let board = pcb_sexpr::parse(board_text).context("Failed to parse layout file")?;
+    let root_items = board.as_list().context("PCB root is not a list")?;
+
+    let mut patches = PatchSet::new();
+    let mut marked = Vec::new();
+    for item in root_items.iter().skip(1) {
+        let Some(footprint) = item.as_list() else {
+            continue;
+        };
+        if footprint.first().and_then(Sexpr::as_sym) != Some("footprint") {
+            continue;
+        }
+        let Some(path) = pcb_sexpr::kicad::schematic_properties(footprint)
+            .remove("Path")
+            .filter(|path| paths.contains(path))
+        else {
+            continue;
+        };
+        let Some(fpid) = footprint.get(1).filter(|node| node.as_str().is_some()) else {
+            continue;
+        };
+        let name = footprint_name_from_fpid(fpid.as_str().unwrap_or_default());
+        patches.replace_string(fpid.span, &format!("{REIMPORT_LIBRARY}:{name}"));
+        marked.push(path);
+    }
+
+    let mut out = Vec::new();
+    patches.write_to(board_text, &mut out)?;
+    Ok((String::from_utf8(out)?, marked))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIBRARY: &str = r#"(footprint "R_0402"
+  (version 20240108)
+  (layer "F.Cu")
+  (property "Reference" "REF**" (at 0 -1.2 0) (layer "F.SilkS"))
+  (fp_line (start -0.9 0.45) (end 0.9 0.45) (stroke (width 0.05) (type solid)) (layer "F.CrtYd") (uuid "a"))
+  (pad "1" smd roundrect (at -0.48 0) (size 0.56 0.62) (layers "F.Cu" "F.Mask" "F.Paste") (uuid "b"))
+  (pad "2" smd roundrect (at 0.48 0) (size 0.56 0.62) (layers "F.Cu" "F.Mask" "F.Paste") (uuid "c"))
+)"#;
+
+    #[test]
+    fn fingerprint_ignores_instance_data() {
+        let placed = r#"(footprint "R_0402"
+  (version 20240108)
+  (layer "F.Cu")
+  (property "Reference" "R7" (at 0 -1.2 0) (layer "F.SilkS"))
+  (pad "2" smd roundrect (at 0.480 0 0) (size 0.56 0.62) (layers "F.Cu" "F.Mask" "F.Paste") (net 3 "VCC") (uuid "x"))
+  (pad "1" smd roundrect (at -0.48 0) (size 0.56 0.62) (layers "F.Cu" "F.Mask" "F.Paste") (net 1 "GND") (pinfunction "1") (uuid "y"))
+  (fp_line (start -0.9 0.45) (end 0.9 0.45) (stroke (width 0.05) (type solid)) (layer "F.CrtYd") (uuid "z"))
+)"#;
+        assert_eq!(
+            footprint_fingerprint(LIBRARY).unwrap(),
+            footprint_fingerprint(placed).unwrap()
+        );
+
+        let resized = LIBRARY.replace("(size 0.56 0.62)", "(size 0.6 0.62)");
+        assert_ne!(
+            footprint_fingerprint(LIBRARY).unwrap(),
+            footprint_fingerprint(&resized).unwrap()
+        );
+    }
+
+    #[test]
+    fn marks_only_requested_footprints() {
+        let board = r#"(kicad_pcb
+  (footprint "lib:R_0402" (layer "F.Cu") (at 10 20 90) (property "Path" "R1"))
+  (footprint "lib:R_0402" (layer "F.Cu") (at 30 20) (property "Path" "R2"))
+)"#;
+        let (patched, marked) =
+            mark_for_reimport(board, &BTreeSet::from(["R2".to_string()])).unwrap();
+        assert_eq!(marked, ["R2"]);
+        assert!(patched.contains(r#"(footprint "lib:R_0402" (layer "F.Cu") (at 10 20 90)"#));
+        assert!(patched.contains(r#"(footprint "pcb-reimport:R_0402" (layer "F.Cu") (at 30 20)"#));
+    }
+}
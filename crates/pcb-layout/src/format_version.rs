@@ -0,0 +1,229 @@
+//! KiCad board file format detection for layout patching.
+//!
+//! Every `.kicad_pcb` starts with `(kicad_pcb (version N) ...)`, where `N` is
+//! the date the format last changed. The patchers in this crate edit the
+//! S-expression tree in place, so a file saved by a KiCad release whose format
+//! we have not checked them against may be silently corrupted. Each patch
+//! operation declares the format range it supports and refuses anything else.
+
+use anyhow::{Context, Result, bail};
+use pcb_sexpr::Sexpr;
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// First board format written by each KiCad major release.
+const KICAD_FORMATS: &[(u32, u32)] = &[
+    (20211014, 6),
+    (20221018, 7),
+    (20240108, 8),
+    (20241229, 9),
+    (20260206, 10),
+];
+
+/// Oldest format the patchers understand (KiCad 6, the first release with
+/// the current S-expression board syntax).
+const MIN_PATCHABLE_FORMAT: u32 = 20211014;
+
+/// Newest format the patchers have been checked against (KiCad 10).
+const MAX_PATCHABLE_FORMAT: u32 = 20260206;
+
+/// A layout edit applied directly to the `.kicad_pcb` file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PatchOperation {
+    /// `moved()` footprint, group, and net path renames.
+    MovedPaths,
+    /// Implicit net renames inferred from pad connectivity.
+    NetRenames,
+    /// Title block, board properties, stackup, zones, and mechanical items.
+    BoardSettings,
+}
+
+impl PatchOperation {
+    /// Inclusive range of board formats this operation can safely edit.
+    fn supported_formats(self) -> (u32, u32) {
+        match self {
+            PatchOperation::MovedPaths
+            | PatchOperation::NetRenames
+            | PatchOperation::BoardSettings => (MIN_PATCHABLE_FORMAT, MAX_PATCHABLE_FORMAT),
+        }
+    }
+}
+
+impl fmt::Display for PatchOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PatchOperation::MovedPaths => "moved() renames",
+            PatchOperation::NetRenames => "net renames",
+            PatchOperation::BoardSettings => "board settings",
+        })
+    }
+}
+
+/// Read `N` from the `(version N)` header of a parsed board.
+pub(crate) fn board_format_version(board: &Sexpr) -> Option<u32> {
+    let items = board.as_list()?;
+    if items.first().and_then(Sexpr::as_sym) != Some("kicad_pcb") {
+        return None;
+    }
+    items.iter().skip(1).find_map(version_entry)
+}
+
+/// Read the header version of a board file without parsing the whole file.
+fn read_format_version(pcb_path: &Path) -> Result<Option<u32>> {
+    let file = File::open(pcb_path)
+        .with_context(|| format!("Failed to read PCB file: {}", pcb_path.display()))?;
+    let mut version = None;
+    pcb_sexpr::walk_stream(BufReader::new(file), |node| {
+        version = version_entry(node);
+        version.is_none()
+    })
+    .with_context(|| format!("Failed to parse PCB file: {}", pcb_path.display()))?;
+    Ok(version)
+}
+
+fn version_entry(node: &Sexpr) -> Option<u32> {
+    let list = node.as_list()?;
+    if list.first().and_then(Sexpr::as_sym) != Some("version") {
+        return None;
+    }
+    u32::try_from(list.get(1)?.as_int()?).ok()
+}
+
+/// KiCad major release that writes `format`, if it is one we know about.
+fn kicad_major_for_format(format: u32) -> Option<u32> {
+    if format > MAX_PATCHABLE_FORMAT {
+        return None;
+    }
+    KICAD_FORMATS
+        .iter()
+        .rev()
+        .find(|(first, _)| format >= *first)
+        .map(|(_, major)| *major)
+}
+
+fn describe_format(format: u32) -> String {
+    match kicad_major_for_format(format) {
+        Some(major) => format!("format {format} (KiCad {major})"),
+        None if format > MAX_PATCHABLE_FORMAT => {
+            let (_, newest) = KICAD_FORMATS[KICAD_FORMATS.len() - 1];
+            format!("format {format} (newer than KiCad {newest})")
+        }
+        None => format!("format {format} (older than KiCad 6)"),
+    }
+}
+
+/// Fail unless the board file at `pcb_path` uses a format `operation` can edit.
+pub(crate) fn ensure_file_patchable(pcb_path: &Path, operation: PatchOperation) -> Result<()> {
+    check_format(pcb_path, read_format_version(pcb_path)?, operation)
+}
+
+/// Fail unless `board` uses a format that `operation` can edit.
+pub(crate) fn ensure_patchable(
+    pcb_path: &Path,
+    board: &Sexpr,
+    operation: PatchOperation,
+) -> Result<()> {
+    check_format(pcb_path, board_format_version(board), operation)
+}
+
+/// Boards without a version header are let through; the parser has already
+/// accepted them and there is nothing to compare against.
+fn check_format(pcb_path: &Path, format: Option<u32>, operation: PatchOperation) -> Result<()> {
+    let Some(format) = format else {
+        return Ok(());
+    };
+    let (min, max) = operation.supported_formats();
+    if format > max {
+        bail!(
+            "{} uses KiCad board {}, but pcb can only apply {} up to {}. \
+             Upgrade pcb to a release that supports this KiCad version; \
+             the file was left unchanged.",
+            pcb_path.display(),
+            describe_format(format),
+            operation,
+            describe_format(max),
+        );
+    }
+    if format < min {
+        bail!(
+            "{} uses KiCad board {}, but pcb can only apply {} from {}. \
+             Open the layout in KiCad {} or newer and save it to upgrade the file format; \
+             the file was left unchanged.",
+            pcb_path.display(),
+            describe_format(format),
+            operation,
+            describe_format(min),
+            kicad_major_for_format(min).unwrap_or(6),
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(version: &str) -> Sexpr {
+        pcb_sexpr::parse(&format!(
+            "(kicad_pcb (version {version}) (generator \"pcbnew\"))"
+        ))
+        .expect("parse board")
+    }
+
+    #[test]
+    fn reads_header_version() {
+        assert_eq!(board_format_version(&board("20241229")), Some(20241229));
+        assert_eq!(
+            board_format_version(&pcb_sexpr::parse("(kicad_pcb (generator \"pcbnew\"))").unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_header_version_from_file() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let pcb_path = temp.path().join("layout.kicad_pcb");
+        let mut pcb = b"(kicad_pcb\n\t(version 20260206)\n\t(generator \"pcbnew\")\n".to_vec();
+        pcb.extend_from_slice(&[0xff, 0xfe, 0xfd]);
+        std::fs::write(&pcb_path, pcb).expect("write pcb");
+
+        assert_eq!(
+            read_format_version(&pcb_path).expect("read version"),
+            Some(20260206)
+        );
+    }
+
+    #[test]
+    fn maps_formats_to_kicad_releases() {
+        assert_eq!(kicad_major_for_format(20240108), Some(8));
+        assert_eq!(kicad_major_for_format(20240501), Some(8));
+        assert_eq!(kicad_major_for_format(20260206), Some(10));
+        assert_eq!(kicad_major_for_format(20270101), None);
+    }
+
+    #[test]
+    fn refuses_formats_outside_supported_range() {
+        let path = Path::new("layout.kicad_pcb");
+        for version in ["20221018", "20241229", "20260206"] {
+            ensure_patchable(path, &board(version), PatchOperation::BoardSettings)
+                .expect("supported format");
+        }
+
+        let err = ensure_patchable(path, &board("20270301"), PatchOperation::MovedPaths)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("format 20270301 (newer than KiCad 10)"),
+            "{err}"
+        );
+        assert!(err.contains("Upgrade pcb"), "{err}");
+
+        let err = ensure_patchable(path, &board("20171130"), PatchOperation::NetRenames)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("older than KiCad 6"), "{err}");
+        assert!(err.contains("KiCad 6 or newer"), "{err}");
+    }
+}
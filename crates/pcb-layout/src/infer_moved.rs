@@ -0,0 +1,274 @@
+//! Infer moved() directives after a module hierarchy refactor.
+//!
+//! Layout footprints are keyed by component path, so renaming or regrouping
+//! modules orphans their placement. This compares the footprints in an
+//! existing `.kicad_pcb` (the previous netlist) against the freshly evaluated
+//! schematic and pairs each orphaned footprint with the new, not yet placed
+//! components that share its fingerprint: the same footprint and pad set.
+//! Candidates are ranked by how many pads keep their net, then by matching
+//! leaf name and value.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use anyhow::Result;
+use pcb_sch::Schematic;
+use pcb_sexpr::Sexpr;
+use pcb_sexpr::board::{extract_footprints, footprint_name_from_fpid};
+
+use crate::effective_netlist::{Port, source_effective_netlist};
+use crate::moved::apply_longest_prefix_match;
+
+/// An orphaned layout footprint and the components it may have moved to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InferredMove {
+    /// Component path recorded in the layout.
+    pub old: String,
+    /// New component paths with the same fingerprint, best match first.
+    pub candidates: Vec<String>,
+    /// Whether the best candidate ties with the next one.
+    pub ambiguous: bool,
+}
+
+/// Footprint identity that survives a rename.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Fingerprint {
+    footprint: String,
+    pads: BTreeSet<String>,
+}
+
+struct Component {
+    fingerprint: Fingerprint,
+    value: Option<String>,
+    /// Pad number to net name.
+    nets: BTreeMap<String, String>,
+}
+
+/// Pair footprints in `board` whose path no longer exists in `schematic` with
+/// new components that have no footprint yet. Paths already remapped by the
+/// schematic's moved() directives are skipped.
+pub fn infer_moved_paths(board: &Sexpr, schematic: &Schematic) -> Result<Vec<InferredMove>> {
+    let expected = source_effective_netlist(schematic)?;
+    let moved: HashMap<String, String> = schematic
+        .moved_paths
+        .iter()
+        .map(|(old, new)| (old.clone(), new.clone()))
+        .collect();
+
+    let mut old: BTreeMap<String, Component> = BTreeMap::new();
+    for fp in extract_footprints(board).map_err(anyhow::Error::msg)? {
+        let Some(path) = fp.properties.get("Path").filter(|path| !path.is_empty()) else {
+            continue;
+        };
+        let nets = fp
+            .pads
+            .iter()
+            .filter_map(|pad| Some((pad.number.clone(), pad.net_name.clone()?)))
+            .collect();
+        old.insert(
+            path.clone(),
+            Component {
+                fingerprint: Fingerprint {
+                    footprint: footprint_name_from_fpid(fp.fpid.as_deref().unwrap_or("")),
+                    pads: fp
+                        .pads
+                        .iter()
+                        .map(|pad| pad.number.clone())
+                        .filter(|number| !number.is_empty())
+                        .collect(),
+                },
+                value: fp.properties.get("Value").cloned(),
+                nets,
+            },
+        );
+    }
+
+    let values: HashMap<String, String> = schematic
+        .instances
+        .iter()
+        .filter_map(|(instance_ref, instance)| {
+            Some((instance_ref.instance_path.join("."), instance.value()?))
+        })
+        .collect();
+    let new: BTreeMap<&String, Component> = expected
+        .footprints
+        .iter()
+        .filter(|(path, _)| !old.contains_key(*path))
+        .map(|(path, fp)| {
+            let nets = fp
+                .pads
+                .iter()
+                .filter_map(|pad| {
+                    let port = Port {
+                        component_path: path.clone(),
+                        pad_name: pad.clone(),
+                    };
+                    Some((pad.clone(), expected.port_to_net.get(&port)?.clone()))
+                })
+                .collect();
+            let component = Component {
+                fingerprint: Fingerprint {
+                    footprint: footprint_name_from_fpid(&fp.fpid),
+                    pads: fp.pads.clone(),
+                },
+                value: values.get(path).cloned(),
+                nets,
+            };
+            (path, component)
+        })
+        .collect();
+
+    let mut inferred = Vec::new();
+    for (old_path, old_component) in &old {
+        if expected.footprints.contains_key(old_path)
+            || apply_longest_prefix_match(old_path, &moved)
+                .is_some_and(|path| expected.footprints.contains_key(&path))
+        {
+            continue;
+        }
+
+        let mut ranked: Vec<((usize, bool, bool), &String)> = new
+            .iter()
+            .filter(|(_, component)| component.fingerprint == old_component.fingerprint)
+            .map(|(path, component)| (similarity(old_path, old_component, path, component), *path))
+            .collect();
+        if ranked.is_empty() {
+            continue;
+        }
+        ranked.sort_by(|(a, a_path), (b, b_path)| b.cmp(a).then_with(|| a_path.cmp(b_path)));
+        inferred.push(InferredMove {
+            old: old_path.clone(),
+            ambiguous: ranked
+                .get(1)
+                .is_some_and(|(score, _)| *score == ranked[0].0),
+            candidates: ranked.into_iter().map(|(_, path)| path.clone()).collect(),
+        });
+    }
+    Ok(inferred)
+}
+
+/// `(pads on the same net, same leaf name, same value)`.
+fn similarity(
+    old_path: &str,
+    old: &Component,
+    new_path: &str,
+    new: &Component,
+) -> (usize, bool, bool) {
+    let same_nets = old
+        .nets
+        .iter()
+        .filter(|(pad, net)| new.nets.get(*pad) == Some(*net))
+        .count();
+    let leaf = |path: &str| path.rsplit('.').next().unwrap_or(path).to_string();
+    let same_leaf = leaf(old_path) == leaf(new_path);
+    let same_value = old.value.is_some() && old.value == new.value;
+    (same_nets, same_leaf, same_value)
+}
+
+/// Turn accepted `(old, new)` component renames into moved() directives,
+/// using one module-level directive where a whole module moved, so its
+/// group and net names follow too. A module prefix is only used when every
+/// footprint under it in `layout_paths` moves the same way.
+pub fn collapse_moves(
+    renames: &[(String, String)],
+    layout_paths: &BTreeSet<String>,
+) -> Vec<(String, String)> {
+    let mut groups: BTreeMap<(String, String), Vec<&(String, String)>> = BTreeMap::new();
+    for rename in renames {
+        groups
+            .entry(common_prefix_pair(&rename.0, &rename.1))
+            .or_default()
+            .push(rename);
+    }
+
+    let renamed: HashMap<&str, &str> = renames
+        .iter()
+        .map(|(old, new)| (old.as_str(), new.as_str()))
+        .collect();
+    let mut directives = Vec::new();
+    for ((old_prefix, new_prefix), members) in groups {
+        let covers_module = layout_paths
+            .iter()
+            .filter(|path| {
+                path.strip_prefix(old_prefix.as_str())
+                    .is_some_and(|rest| rest.starts_with('.'))
+            })
+            .all(|path| {
+                renamed
+                    .get(path.as_str())
+                    .is_some_and(|new| *new == format!("{new_prefix}{}", &path[old_prefix.len()..]))
+            });
+        if covers_module {
+            directives.push((old_prefix, new_prefix));
+        } else {
+            directives.extend(members.into_iter().cloned());
+        }
+    }
+    directives.sort();
+    directives
+}
+
+/// Strip the longest shared trailing path segments, keeping at least one
+/// segment of each path: `("Power.Reg.R1", "Supply.Reg.R1")` gives
+/// `("Power", "Supply")`.
+fn common_prefix_pair(old: &str, new: &str) -> (String, String) {
+    let old: Vec<&str> = old.split('.').collect();
+    let new: Vec<&str> = new.split('.').collect();
+    let shared = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count()
+        .min(old.len() - 1)
+        .min(new.len() - 1);
+    (
+        old[..old.len() - shared].join("."),
+        new[..new.len() - shared].join("."),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renames(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(old, new)| (old.to_string(), new.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn common_prefix_keeps_one_segment() {
+        assert_eq!(
+            common_prefix_pair("Power.Reg.R1", "Supply.Reg.R1"),
+            ("Power".to_string(), "Supply".to_string())
+        );
+        assert_eq!(
+            common_prefix_pair("R1", "R_pullup"),
+            ("R1".to_string(), "R_pullup".to_string())
+        );
+        assert_eq!(
+            common_prefix_pair("R1", "Power.R1"),
+            ("R1".to_string(), "Power.R1".to_string())
+        );
+    }
+
+    #[test]
+    fn collapses_whole_module_moves() {
+        let layout: BTreeSet<String> = ["Power.R1", "Power.C1", "Io.R1", "Io.R2"]
+            .map(String::from)
+            .into();
+        let moves = collapse_moves(
+            &renames(&[
+                ("Power.R1", "Supply.R1"),
+                ("Power.C1", "Supply.C1"),
+                ("Io.R1", "Usb.R1"),
+            ]),
+            &layout,
+        );
+
+        // `Io.R2` stays, so `Io` can't move as a whole.
+        assert_eq!(moves, renames(&[("Io.R1", "Usb.R1"), ("Power", "Supply")]));
+    }
+}
@@ -1,12 +1,12 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
+use pcb_kicad::project::{
+    KicadProject, NETCLASS_PATTERNS, TEXT_VARIABLES, ledger_keys, set_ledger_keys,
+};
 use pcb_zen_core::lang::stackup::{BoardConfig, DesignRules, NetClass};
 use serde_json::{Map, Value, json};
-use std::collections::HashMap;
-use std::fs;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
-use crate::{PCB_GIT_HASH_PLACEHOLDER, PCB_VERSION_PLACEHOLDER};
-
 const DEFAULT_COLOR: &str = "rgba(0, 0, 0, 0.000)";
 const DEFAULT_WIRE_WIDTH_MIL: i64 = 6;
 const DEFAULT_BUS_WIDTH_MIL: i64 = 12;
@@ -88,74 +88,50 @@ pub(crate) fn patch_kicad_pro(
     pro_path: &Path,
     board_config: Option<&BoardConfig>,
     assignments: &HashMap<String, String>,
-    layout_name: Option<&str>,
-) -> Result<()> {
-    let source = fs::read_to_string(pro_path)
-        .with_context(|| format!("Failed to read {}", pro_path.display()))?;
-    let mut project: Value = serde_json::from_str(&source)
-        .with_context(|| format!("Failed to parse {}", pro_path.display()))?;
-
-    patch_text_variables(&mut project, layout_name);
-    if let Some(board_config) = board_config {
-        patch_project_value(&mut project, board_config, assignments);
-    }
+    text_variables: &[(&'static str, String)],
+) -> Result<Vec<RemovedNetclassPattern>> {
+    let mut project = KicadProject::load(pro_path)?;
 
-    let mut serialized = serde_json::to_string_pretty(&project)?;
-    serialized.push('\n');
+    patch_text_variables(&mut project, text_variables);
+    let removed = board_config
+        .map(|board_config| patch_project_value(project.value_mut(), board_config, assignments))
+        .unwrap_or_default();
 
-    fs::write(pro_path, serialized)
-        .with_context(|| format!("Failed to write {}", pro_path.display()))
+    project.save()?;
+    Ok(removed)
 }
 
 pub(crate) fn extract_design_rules_from_kicad_pro(pro_path: &Path) -> Result<Option<DesignRules>> {
-    let source = fs::read_to_string(pro_path)
-        .with_context(|| format!("Failed to read {}", pro_path.display()))?;
-    let project: Value = serde_json::from_str(&source)
-        .with_context(|| format!("Failed to parse {}", pro_path.display()))?;
-    Ok(extract_design_rules_from_project_value(&project))
+    let project = KicadProject::load(pro_path)?;
+    Ok(extract_design_rules_from_project_value(project.value()))
 }
 
 fn patch_project_value(
     project: &mut Value,
     board_config: &BoardConfig,
     assignments: &HashMap<String, String>,
-) {
+) -> Vec<RemovedNetclassPattern> {
     // .kicad_pro patch policy:
     // - Constraints: set only mapped fields provided by board_config.
     // - Predefined sizes: merge/add missing Zener entries; keep existing user entries.
     // - Netclasses: upsert by class name and patch known fields only.
-    // - Netclass patterns: upsert by pattern (when assignments are present).
+    // - Netclass patterns: upsert by pattern; remove only patterns recorded as
+    //   pcb-managed that are no longer assigned.
     // - Never delete unknown keys/classes/patterns; repeated runs are idempotent.
     patch_constraints(project, board_config);
     patch_predefined_sizes(project, board_config);
     patch_netclasses(project, board_config);
-
-    if !assignments.is_empty() {
-        patch_netclass_patterns(project, assignments);
-    }
+    patch_netclass_patterns(project, assignments)
 }
 
-fn patch_text_variables(project: &mut Value, layout_name: Option<&str>) {
-    let vars = ensure_object(
-        ensure_object(project)
-            .entry("text_variables".to_string())
-            .or_insert_with(|| Value::Object(Map::new())),
-    );
-
-    if let Some(layout_name) = layout_name {
-        vars.insert(
-            "PCB_NAME".to_string(),
-            Value::String(layout_name.to_string()),
-        );
-    }
-    vars.insert(
-        "PCB_VERSION".to_string(),
-        Value::String(PCB_VERSION_PLACEHOLDER.to_string()),
-    );
-    vars.insert(
-        "PCB_GIT_HASH".to_string(),
-        Value::String(PCB_GIT_HASH_PLACEHOLDER.to_string()),
-    );
+/// Text variables are pcb-managed: `PCB_NAME` is dropped again if the
+/// board loses its layout name, and user-defined variables are kept.
+fn patch_text_variables(project: &mut KicadProject, text_variables: &[(&'static str, String)]) {
+    let vars: BTreeMap<String, Value> = text_variables
+        .iter()
+        .map(|(name, value)| (name.to_string(), Value::String(value.clone())))
+        .collect();
+    project.merge_managed(TEXT_VARIABLES, &vars);
 }
 
 fn extract_design_rules_from_project_value(project: &Value) -> Option<DesignRules> {
@@ -457,17 +433,56 @@ fn patch_netclasses(project: &mut Value, board_config: &BoardConfig) {
     );
 }
 
-fn patch_netclass_patterns(project: &mut Value, assignments: &HashMap<String, String>) {
-    let mut patterns = get_value_at_iter(project, "net_settings.netclass_patterns".split('.'))
+/// A pcb-managed netclass pattern dropped because its net no longer maps to a
+/// netclass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RemovedNetclassPattern {
+    pub pattern: String,
+    pub netclass: String,
+}
+
+fn patch_netclass_patterns(
+    project: &mut Value,
+    assignments: &HashMap<String, String>,
+) -> Vec<RemovedNetclassPattern> {
+    // Patterns written by pcb are recorded in the `pcb.managed` ledger, so
+    // later syncs can tell them apart from patterns added in KiCad.
+    let previously_managed = ledger_keys(project, NETCLASS_PATTERNS);
+    if assignments.is_empty() && previously_managed.is_empty() {
+        return Vec::new();
+    }
+
+    let mut patterns = get_value_at_iter(project, NETCLASS_PATTERNS.split('.'))
         .and_then(Value::as_array)
         .cloned()
         .unwrap_or_default();
+
+    // Drop patterns pcb wrote on a previous run whose nets are no longer
+    // assigned; anything not in the managed list belongs to the user.
+    let mut removed = Vec::new();
+    patterns.retain(|entry| {
+        let Some(pattern) = entry.get("pattern").and_then(Value::as_str) else {
+            return true;
+        };
+        if assignments.contains_key(pattern) || !previously_managed.iter().any(|p| p == pattern) {
+            return true;
+        }
+        removed.push(RemovedNetclassPattern {
+            pattern: pattern.to_string(),
+            netclass: entry
+                .get("netclass")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        });
+        false
+    });
     let mut pattern_index = index_by_string_field(&patterns, "pattern");
 
     let mut sorted: Vec<_> = assignments.iter().collect();
     sorted.sort_by_key(|(name, _)| *name);
 
-    for (pattern_name, netclass_name) in sorted {
+    for (pattern_name, netclass_name) in &sorted {
         let obj = upsert_object_by_string_field(
             &mut patterns,
             &mut pattern_index,
@@ -482,9 +497,19 @@ fn patch_netclass_patterns(project: &mut Value, assignments: &HashMap<String, St
 
     set_value_at_iter(
         project,
-        "net_settings.netclass_patterns".split('.'),
+        NETCLASS_PATTERNS.split('.'),
         Value::Array(patterns),
     );
+    set_ledger_keys(
+        project,
+        NETCLASS_PATTERNS,
+        sorted
+            .into_iter()
+            .map(|(pattern, _)| pattern.clone())
+            .collect(),
+    );
+
+    removed
 }
 
 fn set_number_if_some(obj: &mut Map<String, Value>, key: &str, value: Option<f64>) {
@@ -639,10 +664,17 @@ fn upsert_object_by_string_field<'a>(
 
 #[cfg(test)]
 mod tests {
-    use super::{extract_design_rules_from_project_value, patch_project_value};
+    use super::{
+        RemovedNetclassPattern, extract_design_rules_from_project_value, patch_kicad_pro,
+        patch_project_value,
+    };
+    use crate::board_text_variables;
+    use pcb_kicad::project::{NETCLASS_PATTERNS, ledger_keys};
     use pcb_zen_core::lang::stackup::BoardConfig;
-    use serde_json::json;
+    use serde_json::{Value, json};
     use std::collections::HashMap;
+    use std::fs;
+    use std::path::Path;
 
     #[test]
     fn patches_constraints_and_predefined_sizes() {
@@ -963,6 +995,129 @@ mod tests {
         );
     }
 
+    fn usb_config() -> BoardConfig {
+        serde_json::from_value(json!({
+            "design_rules": {
+                "netclasses": [{"name": "USB", "track_width": 0.22}]
+            }
+        }))
+        .unwrap()
+    }
+
+    fn assignments(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(pattern, class)| (pattern.to_string(), class.to_string()))
+            .collect()
+    }
+
+    fn pattern_names(project: &Value) -> Vec<&str> {
+        project["net_settings"]["netclass_patterns"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["pattern"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn merges_netclass_patterns_with_user_patterns() {
+        let mut project = json!({
+            "net_settings": {
+                "netclass_patterns": [
+                    {"pattern": "VCC*", "netclass": "Power"}
+                ]
+            }
+        });
+        let config = usb_config();
+
+        let removed = patch_project_value(
+            &mut project,
+            &config,
+            &assignments(&[("USB_DN", "USB"), ("USB_DP", "USB")]),
+        );
+        assert!(removed.is_empty());
+        assert_eq!(pattern_names(&project), ["VCC*", "USB_DN", "USB_DP"]);
+        assert_eq!(
+            ledger_keys(&project, NETCLASS_PATTERNS),
+            ["USB_DN", "USB_DP"]
+        );
+
+        // A pattern added in KiCad between syncs is kept; a managed pattern
+        // whose net is gone is removed and reported.
+        project["net_settings"]["netclass_patterns"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({"pattern": "CLK_*", "netclass": "USB"}));
+        let removed =
+            patch_project_value(&mut project, &config, &assignments(&[("USB_DP", "USB")]));
+        assert_eq!(
+            removed,
+            [RemovedNetclassPattern {
+                pattern: "USB_DN".to_string(),
+                netclass: "USB".to_string(),
+            }]
+        );
+        assert_eq!(pattern_names(&project), ["VCC*", "USB_DP", "CLK_*"]);
+
+        // Dropping every assignment clears the remaining managed patterns.
+        let removed = patch_project_value(&mut project, &config, &HashMap::new());
+        assert_eq!(removed.len(), 1);
+        assert_eq!(pattern_names(&project), ["VCC*", "CLK_*"]);
+        assert!(project["pcb"]["managed"].get(NETCLASS_PATTERNS).is_none());
+    }
+
+    #[test]
+    fn patches_real_kicad_pro_file_idempotently() {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/resources/complex/module/layout.kicad_pro");
+        let dir = tempfile::tempdir().unwrap();
+        let pro_path = dir.path().join("layout.kicad_pro");
+        fs::copy(&fixture, &pro_path).unwrap();
+
+        let config = usb_config();
+        let usb = assignments(&[("USB_DP", "USB")]);
+        patch_kicad_pro(
+            &pro_path,
+            Some(&config),
+            &usb,
+            &board_text_variables(Some("Board"), None, None),
+        )
+        .unwrap();
+        let first = fs::read_to_string(&pro_path).unwrap();
+
+        let mut project: Value = serde_json::from_str(&first).unwrap();
+        assert_eq!(pattern_names(&project), ["USB_DP"]);
+        project["net_settings"]["netclass_patterns"]
+            .as_array_mut()
+            .unwrap()
+            .insert(0, json!({"pattern": "GND", "netclass": "Default"}));
+        fs::write(&pro_path, serde_json::to_string_pretty(&project).unwrap()).unwrap();
+
+        let removed = patch_kicad_pro(
+            &pro_path,
+            Some(&config),
+            &usb,
+            &board_text_variables(Some("Board"), None, None),
+        )
+        .unwrap();
+        assert!(removed.is_empty());
+        let second: Value = serde_json::from_str(&fs::read_to_string(&pro_path).unwrap()).unwrap();
+        assert_eq!(pattern_names(&second), ["GND", "USB_DP"]);
+        assert_eq!(second["meta"], project["meta"]);
+
+        let removed = patch_kicad_pro(
+            &pro_path,
+            Some(&config),
+            &HashMap::new(),
+            &board_text_variables(None, None, None),
+        )
+        .unwrap();
+        assert_eq!(removed[0].pattern, "USB_DP");
+        let third: Value = serde_json::from_str(&fs::read_to_string(&pro_path).unwrap()).unwrap();
+        assert_eq!(pattern_names(&third), ["GND"]);
+    }
+
     #[test]
     fn extracts_design_rules_for_constraints_sizes_and_netclasses() {
         let project = json!({
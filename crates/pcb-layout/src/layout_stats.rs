@@ -0,0 +1,401 @@
+//! Physical statistics of a `.kicad_pcb`: board size, copper layer count,
+//! track length per net class, vias by type, and components per side.
+//!
+//! The board outline is the `Edge.Cuts` drawing. Lines and arcs are chained
+//! into closed loops; the largest loop, rectangle, polygon, or circle is the
+//! board and every other closed shape is a cutout. Arcs count as their two
+//! chords for area. Net classes come from the project's `netclass_patterns`,
+//! as for the routing status.
+
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use pcb_kicad::project::KicadProject;
+use pcb_sexpr::Sexpr;
+use pcb_sexpr::find_child_list;
+use serde::Serialize;
+
+use crate::routing_status::{Point, item_net, net_class_for, net_table, parse_xy};
+
+/// Endpoints closer than this (mm) are joined when chaining outline edges.
+const OUTLINE_TOLERANCE_MM: f64 = 1e-3;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LayoutStats {
+    /// Board outline, when `Edge.Cuts` has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline: Option<BoardOutline>,
+    pub copper_layers: usize,
+    /// Total track length per net class, in mm.
+    pub track_length_mm: BTreeMap<String, f64>,
+    pub vias: ViaCounts,
+    pub components: SideCounts,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BoardOutline {
+    /// Bounding box width, in mm.
+    pub width_mm: f64,
+    /// Bounding box height, in mm.
+    pub height_mm: f64,
+    /// Area inside the outline, less cutouts, in mm².
+    pub area_mm2: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ViaCounts {
+    pub through: usize,
+    /// Blind and buried vias.
+    pub blind: usize,
+    pub micro: usize,
+}
+
+impl ViaCounts {
+    pub fn total(&self) -> usize {
+        self.through + self.blind + self.micro
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SideCounts {
+    pub front: usize,
+    pub back: usize,
+}
+
+impl LayoutStats {
+    /// Total track length over all net classes, in mm.
+    pub fn total_track_length_mm(&self) -> f64 {
+        self.track_length_mm.values().sum()
+    }
+}
+
+/// Statistics of the board at `pcb_path`, with net classes from the
+/// `.kicad_pro` next to it when there is one.
+pub fn read_layout_stats(pcb_path: &Path) -> Result<LayoutStats> {
+    let text = std::fs::read_to_string(pcb_path)
+        .with_context(|| format!("Failed to read {}", pcb_path.display()))?;
+    let board = pcb_sexpr::parse(&text)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {e}", pcb_path.display()))?;
+    let netclass_patterns = KicadProject::load_if_exists(&pcb_path.with_extension("kicad_pro"))?
+        .map(|project| project.netclass_patterns())
+        .unwrap_or_default();
+    Ok(layout_stats(&board, &netclass_patterns))
+}
+
+/// Statistics of `board`, assigning tracks to net classes with the
+/// `.kicad_pro` `netclass_patterns` as `(pattern, netclass)` pairs.
+pub fn layout_stats(board: &Sexpr, netclass_patterns: &[(String, String)]) -> LayoutStats {
+    let Some(root) = board.as_list() else {
+        return LayoutStats::default();
+    };
+
+    let net_names = net_table(root);
+    let mut stats = LayoutStats {
+        copper_layers: find_child_list(root, "layers")
+            .into_iter()
+            .flat_map(|layers| layers.iter().skip(1))
+            .filter_map(Sexpr::as_list)
+            .filter(|layer| {
+                layer
+                    .get(1)
+                    .and_then(Sexpr::as_atom)
+                    .is_some_and(|name| name.ends_with(".Cu"))
+            })
+            .count(),
+        ..Default::default()
+    };
+    let mut outline = Outline::default();
+
+    for items in root.iter().skip(1).filter_map(Sexpr::as_list) {
+        let point = |name| find_child_list(items, name).and_then(parse_xy);
+        match items.first().and_then(Sexpr::as_sym) {
+            Some("footprint") => {
+                let board_only = find_child_list(items, "attr")
+                    .is_some_and(|attr| attr.iter().any(|a| a.as_sym() == Some("board_only")));
+                if board_only {
+                    continue;
+                }
+                match layer(items) {
+                    Some("F.Cu") => stats.components.front += 1,
+                    Some("B.Cu") => stats.components.back += 1,
+                    _ => {}
+                }
+            }
+            Some(kind @ ("segment" | "arc")) => {
+                let (Some(start), Some(end)) = (point("start"), point("end")) else {
+                    continue;
+                };
+                let length = match point("mid") {
+                    Some(mid) if kind == "arc" => arc_length(start, mid, end),
+                    _ => distance(start, end),
+                };
+                let net = item_net(items, &net_names).unwrap_or_default();
+                *stats
+                    .track_length_mm
+                    .entry(net_class_for(&net, netclass_patterns).to_string())
+                    .or_default() += length;
+            }
+            Some("via") => match via_type(items) {
+                Some("micro") => stats.vias.micro += 1,
+                Some("blind" | "buried" | "blind_buried") => stats.vias.blind += 1,
+                _ => stats.vias.through += 1,
+            },
+            Some(kind) if layer(items) == Some("Edge.Cuts") => match kind {
+                "gr_line" => {
+                    if let (Some(start), Some(end)) = (point("start"), point("end")) {
+                        outline.edges.push((start, end));
+                    }
+                }
+                "gr_arc" => {
+                    if let (Some(start), Some(mid), Some(end)) =
+                        (point("start"), point("mid"), point("end"))
+                    {
+                        outline.edges.extend([(start, mid), (mid, end)]);
+                        outline.extend_bounds(&arc_extremes(start, mid, end));
+                    }
+                }
+                "gr_rect" => {
+                    if let (Some(a), Some(b)) = (point("start"), point("end")) {
+                        outline.add_shape(&[a, (b.0, a.1), b, (a.0, b.1)]);
+                    }
+                }
+                "gr_poly" => {
+                    let pts: Vec<Point> = find_child_list(items, "pts")
+                        .into_iter()
+                        .flat_map(|pts| pts.iter().skip(1))
+                        .filter_map(Sexpr::as_list)
+                        .filter_map(parse_xy)
+                        .collect();
+                    if pts.len() >= 3 {
+                        outline.add_shape(&pts);
+                    }
+                }
+                "gr_circle" => {
+                    if let (Some(center), Some(edge)) = (point("center"), point("end")) {
+                        let r = distance(center, edge);
+                        outline.extend_bounds(&[
+                            (center.0 - r, center.1 - r),
+                            (center.0 + r, center.1 + r),
+                        ]);
+                        outline.areas.push(PI * r * r);
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    stats.outline = outline.finish();
+    stats
+}
+
+#[derive(Default)]
+struct Outline {
+    /// Line and arc edges still to be chained into loops.
+    edges: Vec<(Point, Point)>,
+    /// Areas of closed shapes.
+    areas: Vec<f64>,
+    bounds: Option<(Point, Point)>,
+}
+
+impl Outline {
+    fn add_shape(&mut self, polygon: &[Point]) {
+        self.extend_bounds(polygon);
+        self.areas.push(polygon_area(polygon));
+    }
+
+    fn extend_bounds(&mut self, points: &[Point]) {
+        for &(x, y) in points {
+            let (min, max) = self.bounds.get_or_insert(((x, y), (x, y)));
+            *min = (min.0.min(x), min.1.min(y));
+            *max = (max.0.max(x), max.1.max(y));
+        }
+    }
+
+    fn finish(mut self) -> Option<BoardOutline> {
+        let edges = std::mem::take(&mut self.edges);
+        for polygon in chain_loops(edges) {
+            self.add_shape(&polygon);
+        }
+        let (min, max) = self.bounds?;
+        let board = self.areas.iter().cloned().fold(0.0, f64::max);
+        let cutouts: f64 = self.areas.iter().sum::<f64>() - board;
+        Some(BoardOutline {
+            width_mm: max.0 - min.0,
+            height_mm: max.1 - min.1,
+            area_mm2: board - cutouts,
+        })
+    }
+}
+
+/// Chain edges into closed polygons. Edges that don't close a loop are
+/// dropped.
+fn chain_loops(mut edges: Vec<(Point, Point)>) -> Vec<Vec<Point>> {
+    let close = |a: Point, b: Point| distance(a, b) <= OUTLINE_TOLERANCE_MM;
+    let mut loops = Vec::new();
+    while let Some((start, mut end)) = edges.pop() {
+        let mut polygon = vec![start];
+        while !close(end, start) {
+            let Some(i) = edges
+                .iter()
+                .position(|(a, b)| close(*a, end) || close(*b, end))
+            else {
+                break;
+            };
+            let (a, b) = edges.swap_remove(i);
+            polygon.push(end);
+            end = if close(a, end) { b } else { a };
+        }
+        if close(end, start) && polygon.len() >= 3 {
+            loops.push(polygon);
+        }
+    }
+    loops
+}
+
+fn polygon_area(polygon: &[Point]) -> f64 {
+    let mut twice = 0.0;
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        twice += a.0 * b.1 - b.0 * a.1;
+    }
+    twice.abs() / 2.0
+}
+
+/// Center and radius of the circle through three points, or `None` when
+/// they are collinear.
+fn circumcircle(a: Point, b: Point, c: Point) -> Option<(Point, f64)> {
+    let d = 2.0 * (a.0 * (b.1 - c.1) + b.0 * (c.1 - a.1) + c.0 * (a.1 - b.1));
+    if d.abs() < 1e-12 {
+        return None;
+    }
+    let sq = |p: Point| p.0 * p.0 + p.1 * p.1;
+    let center = (
+        (sq(a) * (b.1 - c.1) + sq(b) * (c.1 - a.1) + sq(c) * (a.1 - b.1)) / d,
+        (sq(a) * (c.0 - b.0) + sq(b) * (a.0 - c.0) + sq(c) * (b.0 - a.0)) / d,
+    );
+    Some((center, distance(center, a)))
+}
+
+/// Length of the arc from `start` through `mid` to `end`.
+fn arc_length(start: Point, mid: Point, end: Point) -> f64 {
+    let Some((center, radius)) = circumcircle(start, mid, end) else {
+        return distance(start, mid) + distance(mid, end);
+    };
+    // `mid` halves the arc, so start→mid and mid→end each span at most π.
+    let angle = |a: Point, b: Point| {
+        let (ua, ub) = (
+            (a.0 - center.0, a.1 - center.1),
+            (b.0 - center.0, b.1 - center.1),
+        );
+        (ua.0 * ub.1 - ua.1 * ub.0)
+            .atan2(ua.0 * ub.0 + ua.1 * ub.1)
+            .abs()
+    };
+    radius * (angle(start, mid) + angle(mid, end))
+}
+
+/// Points that bound an arc: its ends and midpoint plus any axis extremes of
+/// its circle that the arc passes.
+fn arc_extremes(start: Point, mid: Point, end: Point) -> Vec<Point> {
+    let mut points = vec![start, mid, end];
+    if let Some((center, radius)) = circumcircle(start, mid, end) {
+        let chord_len = distance(start, end);
+        for extreme in [
+            (center.0 + radius, center.1),
+            (center.0 - radius, center.1),
+            (center.0, center.1 + radius),
+            (center.0, center.1 - radius),
+        ] {
+            // An extreme is on the arc when it is on the same side of the
+            // start–end chord as the midpoint.
+            let side = |p: Point| {
+                (end.0 - start.0) * (p.1 - start.1) - (end.1 - start.1) * (p.0 - start.0)
+            };
+            if chord_len > 0.0 && side(extreme) * side(mid) > 0.0 {
+                points.push(extreme);
+            }
+        }
+    }
+    points
+}
+
+fn layer(items: &[Sexpr]) -> Option<&str> {
+    find_child_list(items, "layer")?.get(1)?.as_atom()
+}
+
+/// Via type from a bare `blind`/`micro` token (KiCad ≤ 8) or a `(type ...)`
+/// child.
+fn via_type(items: &[Sexpr]) -> Option<&str> {
+    items
+        .iter()
+        .skip(1)
+        .find_map(Sexpr::as_sym)
+        .or_else(|| find_child_list(items, "type")?.get(1)?.as_atom())
+}
+
+fn distance(a: Point, b: Point) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: &str = r#"(kicad_pcb
+        (layers
+            (0 "F.Cu" signal)
+            (1 "In1.Cu" signal)
+            (2 "In2.Cu" signal)
+            (31 "B.Cu" signal)
+            (44 "Edge.Cuts" user))
+        (net 0 "")
+        (net 1 "VIN")
+        (net 2 "USB_DP")
+        (footprint "R_0402" (layer "F.Cu") (at 10 10))
+        (footprint "C_0402" (layer "F.Cu") (at 12 10))
+        (footprint "U1" (layer "B.Cu") (at 20 20))
+        (footprint "Logo" (layer "F.Cu") (attr board_only exclude_from_bom))
+        (segment (start 0 0) (end 3 4) (width 0.2) (layer "F.Cu") (net 1))
+        (arc (start 10 0) (mid 0 10) (end -10 0) (width 0.2) (layer "F.Cu") (net 2))
+        (via (at 3 4) (size 0.6) (layers "F.Cu" "B.Cu") (net 1))
+        (via blind (at 5 5) (size 0.6) (layers "F.Cu" "In1.Cu") (net 1))
+        (via micro (at 6 6) (size 0.3) (layers "F.Cu" "In1.Cu") (net 1))
+        (gr_line (start 0 0) (end 40 0) (layer "Edge.Cuts"))
+        (gr_line (start 40 0) (end 40 30) (layer "Edge.Cuts"))
+        (gr_line (start 0 30) (end 40 30) (layer "Edge.Cuts"))
+        (gr_line (start 0 0) (end 0 30) (layer "Edge.Cuts"))
+        (gr_rect (start 10 10) (end 12 12) (layer "Edge.Cuts"))
+    )"#;
+
+    #[test]
+    fn collects_board_statistics() {
+        let board = pcb_sexpr::parse(BOARD).unwrap();
+        let patterns = vec![("USB_*".to_string(), "USB".to_string())];
+        let stats = layout_stats(&board, &patterns);
+
+        assert_eq!(stats.copper_layers, 4);
+        assert_eq!(
+            stats.outline,
+            Some(BoardOutline {
+                width_mm: 40.0,
+                height_mm: 30.0,
+                area_mm2: 1196.0,
+            })
+        );
+        assert_eq!(stats.track_length_mm["Default"], 5.0);
+        assert!((stats.track_length_mm["USB"] - 10.0 * PI).abs() < 1e-9);
+        assert_eq!(
+            stats.vias,
+            ViaCounts {
+                through: 1,
+                blind: 1,
+                micro: 1,
+            }
+        );
+        assert_eq!(stats.components, SideCounts { front: 2, back: 1 });
+    }
+}
@@ -2,12 +2,13 @@ use anyhow::{Context, Result as AnyhowResult};
 use atomicwrites::{AtomicFile, OverwriteBehavior};
 use log::{debug, info};
 use pcb_sch::{ATTR_LAYOUT_PATH, AttributeValue, InstanceKind, Schematic};
+use pcb_zen_core::cancel::{CancellationToken, Cancelled};
 use pcb_zen_core::diagnostics::Diagnostic;
 use pcb_zen_core::lang::stackup::{BoardConfig, DesignRules, NetClass, Stackup, StackupError};
 use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
 use starlark::errors::EvalSeverity;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -15,21 +16,75 @@ use tempfile::TempDir;
 use thiserror::Error;
 
 use include_dir::{Dir, include_dir};
+use pcb_command_runner::{CommandFailed, LogRotation};
 use pcb_kicad::{PythonScriptBuilder, ensure_board_compatible_with_installed_kicad};
 use pcb_sch::kicad_netlist::{try_format_footprint_with_package_roots, write_fp_lib_table};
 
+mod boards;
 mod effective_netlist;
+pub mod footprint_drift;
+mod format_version;
+mod infer_moved;
 mod kicad_project_patch;
+pub mod layout_stats;
+mod mechanical;
 mod moved;
 mod repair_nets;
+pub mod return_path;
+pub mod routing_status;
+mod zones;
 use effective_netlist::{
-    DiffSeverity, diff_effective_netlists, layout_effective_netlist, source_effective_netlist,
+    DiffSeverity, diff_effective_netlists, layout_effective_netlist, orphan_copper_nets,
+    source_effective_netlist,
 };
+use format_version::PatchOperation;
+pub use infer_moved::{InferredMove, collapse_moves, infer_moved_paths};
 pub use moved::compute_moved_paths_patches;
 pub use moved::compute_net_renames_patches;
 
+/// Sync logs kept per layout directory.
+const LAYOUT_LOGS_KEPT: usize = 10;
+/// Log lines quoted in the error when sync fails.
+const LAYOUT_LOG_TAIL_LINES: usize = 20;
+
 pub const PCB_VERSION_PLACEHOLDER: &str = "v0.0.0";
 pub const PCB_GIT_HASH_PLACEHOLDER: &str = "d10d3c0";
+pub const PCB_GIT_DESCRIBE_PLACEHOLDER: &str = "v0.0.0-0-gd10d3c0";
+pub const PCB_SOURCE_HASH_PLACEHOLDER: &str = "00000000";
+
+/// Text variables pcb sets in a layout's `.kicad_pro` and `.kicad_pcb`. The
+/// release variables are placeholders until `pcb release` fills them in; the
+/// provenance variables come from the schematic's stamp when it has one, and
+/// `PCB_REVISION`/`PCB_ECO` from its declared revision.
+pub(crate) fn board_text_variables(
+    layout_name: Option<&str>,
+    stamp: Option<&pcb_sch::stamp::Stamp>,
+    revision: Option<&pcb_sch::revision::Revision>,
+) -> Vec<(&'static str, String)> {
+    let mut vars = Vec::new();
+    if let Some(layout_name) = layout_name {
+        vars.push(("PCB_NAME", layout_name.to_string()));
+    }
+    if let Some(revision) = revision {
+        vars.push(("PCB_REVISION", revision.revision.clone()));
+        if let Some(eco) = &revision.eco {
+            vars.push(("PCB_ECO", eco.clone()));
+        }
+    }
+    vars.push(("PCB_VERSION", PCB_VERSION_PLACEHOLDER.to_string()));
+    vars.push(("PCB_GIT_HASH", PCB_GIT_HASH_PLACEHOLDER.to_string()));
+    match stamp {
+        Some(stamp) => {
+            vars.push(("PCB_GIT_DESCRIBE", stamp.git_describe.clone()));
+            vars.push(("PCB_SOURCE_HASH", stamp.short_source_hash().to_string()));
+        }
+        None => {
+            vars.push(("PCB_GIT_DESCRIBE", PCB_GIT_DESCRIBE_PLACEHOLDER.to_string()));
+            vars.push(("PCB_SOURCE_HASH", PCB_SOURCE_HASH_PLACEHOLDER.to_string()));
+        }
+    }
+    vars
+}
 
 /// Extract DesignRules from a KiCad project file.
 pub fn extract_design_rules_from_kicad_pro(pro_path: &Path) -> AnyhowResult<Option<DesignRules>> {
@@ -50,6 +105,9 @@ pub struct LayoutResult {
     pub log_file: PathBuf,
     pub diagnostics_file: PathBuf,
     pub created: bool, // true if new, false if updated
+    /// Additional boards declared with `Layout(..., board = True)`, synced into
+    /// their own layout directories.
+    pub boards: Vec<LayoutResult>,
 }
 
 impl LayoutResult {
@@ -76,6 +134,9 @@ pub enum LayoutError {
 
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    Cancelled(#[from] Cancelled),
 }
 
 /// Helper struct for layout file paths
@@ -233,6 +294,12 @@ fn render_patches(source: &str, patches: &pcb_sexpr::PatchSet) -> anyhow::Result
     String::from_utf8(out).context("Patched PCB is not valid UTF-8")
 }
 
+/// Indent a formatted top-level board item one level and put it on its own
+/// line, ready to insert before the board's closing paren.
+fn indent_board_item(text: &str) -> String {
+    text.lines().map(|line| format!("\t{line}\n")).collect()
+}
+
 /// Apply moved() path renames to a PCB file
 fn apply_moved_paths(
     pcb_path: &Path,
@@ -254,6 +321,7 @@ fn apply_moved_paths(
     if renames.is_empty() {
         return Ok(());
     }
+    format_version::ensure_patchable(pcb_path, &board, PatchOperation::MovedPaths)?;
 
     apply_patches_to_file(pcb_path, &pcb_content, &patches, false)?;
 
@@ -306,6 +374,7 @@ fn repair_net_names(
     }
 
     if !result.renames.is_empty() {
+        format_version::ensure_patchable(pcb_path, &board, PatchOperation::NetRenames)?;
         let (patches, _) = moved::compute_net_renames_patches(&board, &result.renames);
         apply_patches_to_file(pcb_path, &pcb_content, &patches, false)?;
 
@@ -378,13 +447,19 @@ fn run_sync_script(paths: &LayoutPaths, lens_python_path: &Path) -> anyhow::Resu
         .arg("--diagnostics")
         .arg(paths.diagnostics.to_str().unwrap());
 
-    let log_file = fs::OpenOptions::new()
-        .create(true)
-        .write(true)
-        .truncate(true)
-        .open(&paths.log)?;
+    let log_dir = paths.log.parent().unwrap_or(Path::new("."));
+    let log_file = utils::layout_log_rotation(log_dir).create(&paths.log)?;
 
-    builder.log_file(log_file).run()
+    builder.log_file(log_file).run().map_err(|e| {
+        let Some(failed) = e.downcast_ref::<CommandFailed>() else {
+            return e;
+        };
+        anyhow::anyhow!(
+            "Layout sync failed:\n{}\n\nFull log: {}",
+            failed.output.tail(LAYOUT_LOG_TAIL_LINES),
+            paths.log.display()
+        )
+    })
 }
 
 /// Check the checked-in KiCad layout against the schematic using a semantic effective-netlist
@@ -393,6 +468,39 @@ fn run_sync_script(paths: &LayoutPaths, lens_python_path: &Path) -> anyhow::Resu
 pub fn check_layout_sync(
     schematic: &Schematic,
     diagnostics: &mut pcb_zen_core::Diagnostics,
+) -> Result<Option<LayoutResult>, LayoutError> {
+    for_each_board(schematic, |board| {
+        check_board_layout_sync(board, diagnostics)
+    })
+}
+
+/// Run `process` for every board of `schematic`. Results of additional boards
+/// are attached to the first board that has a layout.
+fn for_each_board(
+    schematic: &Schematic,
+    mut process: impl FnMut(&Schematic) -> Result<Option<LayoutResult>, LayoutError>,
+) -> Result<Option<LayoutResult>, LayoutError> {
+    let Some(boards) = boards::split_boards(schematic) else {
+        return process(schematic);
+    };
+
+    let mut results = Vec::new();
+    for board in &boards {
+        debug!("Processing board at '{}'", board.module_path.join("."));
+        if let Some(result) = process(&board.schematic)? {
+            results.push(result);
+        }
+    }
+    let mut results = results.into_iter();
+    Ok(results.next().map(|mut main| {
+        main.boards.extend(results);
+        main
+    }))
+}
+
+fn check_board_layout_sync(
+    schematic: &Schematic,
+    diagnostics: &mut pcb_zen_core::Diagnostics,
 ) -> Result<Option<LayoutResult>, LayoutError> {
     let Some(layout_dir) = utils::resolve_layout_dir(schematic)? else {
         return Ok(None);
@@ -421,6 +529,7 @@ pub fn check_layout_sync(
     let (actual, extraction_diagnostics) = layout_effective_netlist(&board, &expected)?;
     let mut semantic_diffs = extraction_diagnostics;
     semantic_diffs.extend(diff_effective_netlists(&expected, &actual));
+    semantic_diffs.extend(orphan_copper_nets(&board, &expected, &actual));
 
     for diff in semantic_diffs {
         diagnostics.diagnostics.push(Diagnostic::categorized(
@@ -443,6 +552,7 @@ pub fn check_layout_sync(
         log_file: paths.log,
         diagnostics_file: paths.diagnostics,
         created: false,
+        boards: Vec::new(),
     }))
 }
 
@@ -457,16 +567,105 @@ pub fn check_layout_sync(
 ///
 /// When `check_mode` is true:
 /// - Runs the pure semantic layout sync check without mutating layout files
+///
+/// Submodules declared with `Layout(..., board = True)` are split into their own
+/// schematics and synced into their own layout directories; their results are
+/// returned in [`LayoutResult::boards`].
 pub fn process_layout(
     schematic: &Schematic,
     use_temp_dir: bool,
     check_mode: bool,
     diagnostics: &mut pcb_zen_core::Diagnostics,
+) -> Result<Option<LayoutResult>, LayoutError> {
+    process_layout_with_cancellation(
+        schematic,
+        use_temp_dir,
+        check_mode,
+        &CancellationToken::default(),
+        diagnostics,
+    )
+}
+
+/// Like [`process_layout`], but stops between stages once `cancel` is
+/// cancelled, returning [`LayoutError::Cancelled`].
+///
+/// If a board's sync is cancelled or fails part-way, its `.kicad_pcb` and
+/// `.kicad_pro` are restored to their previous contents (or removed if they
+/// were being created), and a `--temp` layout directory is deleted.
+pub fn process_layout_with_cancellation(
+    schematic: &Schematic,
+    use_temp_dir: bool,
+    check_mode: bool,
+    cancel: &CancellationToken,
+    diagnostics: &mut pcb_zen_core::Diagnostics,
 ) -> Result<Option<LayoutResult>, LayoutError> {
     if check_mode {
         return check_layout_sync(schematic, diagnostics);
     }
+    for_each_board(schematic, |board| {
+        cancel.check("layout generation")?;
+        process_board_layout(board, use_temp_dir, cancel, diagnostics)
+    })
+}
 
+/// Original contents of the layout files a sync rewrites, restored on drop
+/// unless the sync completed.
+struct LayoutRollback {
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+    temp_layout_dir: Option<PathBuf>,
+    committed: bool,
+}
+
+impl LayoutRollback {
+    fn capture(files: &[PathBuf], temp_layout_dir: Option<PathBuf>) -> std::io::Result<Self> {
+        let files = files
+            .iter()
+            .map(|path| match fs::read(path) {
+                Ok(content) => Ok((path.clone(), Some(content))),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok((path.clone(), None)),
+                Err(e) => Err(e),
+            })
+            .collect::<std::io::Result<_>>()?;
+        Ok(Self {
+            files,
+            temp_layout_dir,
+            committed: false,
+        })
+    }
+
+    fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for LayoutRollback {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        if let Some(dir) = &self.temp_layout_dir {
+            debug!("Removing partial layout directory {}", dir.display());
+            let _ = fs::remove_dir_all(dir);
+            return;
+        }
+        for (path, original) in &self.files {
+            debug!("Restoring {}", path.display());
+            if let Some(content) = original {
+                let _ = AtomicFile::new(path, OverwriteBehavior::AllowOverwrite)
+                    .write(|f| f.write_all(content));
+            } else {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+}
+
+fn process_board_layout(
+    schematic: &Schematic,
+    use_temp_dir: bool,
+    cancel: &CancellationToken,
+    diagnostics: &mut pcb_zen_core::Diagnostics,
+) -> Result<Option<LayoutResult>, LayoutError> {
     // Resolve layout directory
     let resolved_layout_dir = if use_temp_dir {
         // Create a temporary directory and keep it (prevent cleanup on drop)
@@ -506,6 +705,11 @@ pub fn process_layout(
             layout_dir.display()
         )
     })?;
+    let rollback = LayoutRollback::capture(
+        &[paths.pcb.clone(), paths.pcb.with_extension("kicad_pro")],
+        use_temp_dir.then(|| layout_dir.clone()),
+    )
+    .with_context(|| format!("Failed to read existing layout in {}", layout_dir.display()))?;
 
     // Write netlist files
     let netlist_content = pcb_sch::kicad_netlist::to_kicad_netlist(schematic);
@@ -549,6 +753,9 @@ pub fn process_layout(
 
     // Apply moved() path renames and detect implicit net renames before sync
     if pcb_exists {
+        // The sync script and the board patchers below rewrite the whole file,
+        // so refuse formats they don't understand before touching anything.
+        format_version::ensure_file_patchable(&paths.pcb, PatchOperation::BoardSettings)?;
         apply_moved_paths(
             &paths.pcb,
             &schematic.moved_paths,
@@ -562,26 +769,50 @@ pub fn process_layout(
     let lens_python_path =
         extract_lens_module(paths.temp_dir.path()).context("Failed to extract lens module")?;
 
-    // Run the Python sync script
-    run_sync_script(&paths, &lens_python_path)?;
+    // Run the Python sync script. Ctrl-C also interrupts the child process, so
+    // a failure after cancellation is reported as the cancellation.
+    cancel.check("layout sync")?;
+    let sync_result = run_sync_script(&paths, &lens_python_path);
+    cancel.check("layout sync")?;
+    sync_result?;
 
+    cancel.check("PCB patching")?;
     let layout_name = utils::extract_layout_name(schematic);
+    let text_variables = board_text_variables(
+        layout_name.as_deref(),
+        schematic.stamp.as_ref(),
+        schematic.revision(),
+    );
     let netclass_assignments = board_config
         .as_ref()
         .map(|config| build_netclass_assignments(schematic, config.netclasses()))
         .unwrap_or_default();
-    patch_project_file(
+    let removed_patterns = patch_project_file(
         &paths.pcb.with_extension("kicad_pro"),
         board_config.as_ref(),
         &netclass_assignments,
-        layout_name.as_deref(),
+        &text_variables,
     )?;
+    for removed in removed_patterns {
+        diagnostics.diagnostics.push(Diagnostic::categorized(
+            &diagnostics_pcb_path,
+            &format!(
+                "removed netclass assignment \"{}\" → \"{}\"",
+                removed.pattern, removed.netclass
+            ),
+            "layout.netclass",
+            EvalSeverity::Advice,
+        ));
+    }
     patch_pcb_file(
         &paths.pcb,
         board_config.as_ref(),
-        layout_name.as_deref(),
+        &text_variables,
         &component_internal_connectivity_by_path(schematic),
+        &component_net_tie_groups_by_path(schematic),
+        &schematic.package_roots,
     )?;
+    rollback.commit();
 
     // Add sync diagnostics from JSON file
     if paths.diagnostics.exists() {
@@ -602,6 +833,7 @@ pub fn process_layout(
         log_file: paths.log,
         diagnostics_file: paths.diagnostics,
         created: !pcb_exists,
+        boards: Vec::new(),
     }))
 }
 
@@ -697,6 +929,11 @@ pub mod utils {
         })
     }
 
+    /// Sync logs of a layout, one timestamped file per run in `log_dir`.
+    pub fn layout_log_rotation(log_dir: &Path) -> LogRotation {
+        LogRotation::new(log_dir, "layout", LAYOUT_LOGS_KEPT)
+    }
+
     /// Get all the file paths that would be generated for a layout, with explicit PCB path.
     pub fn get_layout_paths_for_pcb(layout_dir: &Path, pcb_path: PathBuf) -> LayoutPaths {
         let temp_dir = tempfile::tempdir().expect("Failed to create temp directory for netlist");
@@ -706,7 +943,7 @@ pub mod utils {
             netlist: layout_dir.join("default.net"),
             pcb: pcb_path,
             snapshot: layout_dir.join("snapshot.layout.json"),
-            log: layout_dir.join("layout.log"),
+            log: layout_log_rotation(&layout_dir.join("logs")).next_path(),
             json_netlist,
             diagnostics,
             temp_dir,
@@ -956,17 +1193,19 @@ fn patch_project_file(
     pro_path: &Path,
     board_config: Option<&BoardConfig>,
     assignments: &HashMap<String, String>,
-    layout_name: Option<&str>,
-) -> AnyhowResult<()> {
+    text_variables: &[(&'static str, String)],
+) -> AnyhowResult<Vec<kicad_project_patch::RemovedNetclassPattern>> {
     info!("Updating project settings in {}", pro_path.display());
-    kicad_project_patch::patch_kicad_pro(pro_path, board_config, assignments, layout_name)
+    kicad_project_patch::patch_kicad_pro(pro_path, board_config, assignments, text_variables)
 }
 
 fn patch_pcb_file(
     pcb_path: &Path,
     board_config: Option<&BoardConfig>,
-    layout_name: Option<&str>,
+    text_variables: &[(&'static str, String)],
     internal_connectivity_by_path: &BTreeMap<String, pcb_sch::InternalConnectivity>,
+    net_tie_groups_by_path: &BTreeMap<String, Vec<BTreeSet<String>>>,
+    package_roots: &BTreeMap<String, PathBuf>,
 ) -> Result<(), LayoutError> {
     let pcb_content = fs::read_to_string(pcb_path).map_err(|e| {
         LayoutError::StackupPatchingError(format!("Failed to read PCB file: {}", e))
@@ -976,11 +1215,15 @@ fn patch_pcb_file(
         LayoutError::StackupPatchingError(format!("Failed to parse PCB file: {}", e))
     })?;
 
+    format_version::ensure_patchable(pcb_path, &board, PatchOperation::BoardSettings)?;
+
     let patches = build_pcb_patchset(
         &board,
         board_config,
-        layout_name,
+        text_variables,
         internal_connectivity_by_path,
+        net_tie_groups_by_path,
+        package_roots,
     )?;
     let patched = render_patches(&pcb_content, &patches).map_err(|e| {
         LayoutError::StackupPatchingError(format!(
@@ -1011,15 +1254,24 @@ fn patch_pcb_file(
 fn build_pcb_patchset(
     board: &pcb_sexpr::Sexpr,
     board_config: Option<&BoardConfig>,
-    layout_name: Option<&str>,
+    text_variables: &[(&'static str, String)],
     internal_connectivity_by_path: &BTreeMap<String, pcb_sch::InternalConnectivity>,
+    net_tie_groups_by_path: &BTreeMap<String, Vec<BTreeSet<String>>>,
+    package_roots: &BTreeMap<String, PathBuf>,
 ) -> Result<pcb_sexpr::PatchSet, LayoutError> {
-    let mut patches = build_title_block_patchset(board)?;
-    patches.extend(build_board_properties_patchset(board, layout_name)?);
+    let has_revision = text_variables
+        .iter()
+        .any(|(name, _)| *name == "PCB_REVISION");
+    let mut patches = build_title_block_patchset(board, has_revision)?;
+    patches.extend(build_board_properties_patchset(board, text_variables)?);
     patches.extend(build_footprint_internal_connectivity_patchset(
         board,
         internal_connectivity_by_path,
     )?);
+    patches.extend(build_footprint_net_tie_patchset(
+        board,
+        net_tie_groups_by_path,
+    )?);
 
     if let Some(stackup) = board_config.and_then(|config| config.stackup.as_ref()) {
         let board_thickness_iu = stackup_thickness_iu(stackup);
@@ -1034,6 +1286,15 @@ fn build_pcb_patchset(
         )?);
     }
 
+    if let Some(config) = board_config {
+        patches.extend(zones::build_zones_patchset(board, config.zones())?);
+        patches.extend(mechanical::build_mechanical_patchset(
+            board,
+            config,
+            package_roots,
+        )?);
+    }
+
     Ok(patches)
 }
 
@@ -1053,6 +1314,70 @@ fn component_internal_connectivity_by_path(
         .collect()
 }
 
+/// Net tie pad groups keyed by component path, for components marked as net ties.
+fn component_net_tie_groups_by_path(
+    schematic: &Schematic,
+) -> BTreeMap<String, Vec<BTreeSet<String>>> {
+    schematic
+        .instances
+        .iter()
+        .filter(|(_, instance)| instance.kind == InstanceKind::Component)
+        .filter_map(|(instance_ref, instance)| {
+            let groups = instance.net_tie_pad_groups();
+            (!groups.is_empty()).then(|| (instance_ref.instance_path.join("."), groups))
+        })
+        .collect()
+}
+
+/// Write `net_tie_pad_groups` on net tie footprints so KiCad's DRC allows the
+/// intentional short. Footprints that already declare the same groups are left
+/// untouched; other footprints are never modified.
+fn build_footprint_net_tie_patchset(
+    board: &pcb_sexpr::Sexpr,
+    net_tie_groups_by_path: &BTreeMap<String, Vec<BTreeSet<String>>>,
+) -> Result<pcb_sexpr::PatchSet, LayoutError> {
+    let mut patches = pcb_sexpr::PatchSet::new();
+    if net_tie_groups_by_path.is_empty() {
+        return Ok(patches);
+    }
+    let root_items = board.as_list().ok_or_else(|| {
+        LayoutError::StackupPatchingError("PCB root is not an S-expression list".to_string())
+    })?;
+
+    for item in root_items.iter().skip(1) {
+        let Some(footprint) = item.as_list() else {
+            continue;
+        };
+        if footprint.first().and_then(pcb_sexpr::Sexpr::as_sym) != Some("footprint") {
+            continue;
+        }
+        let Some(groups) = pcb_sexpr::kicad::schematic_properties(footprint)
+            .get("Path")
+            .and_then(|path| net_tie_groups_by_path.get(path))
+        else {
+            continue;
+        };
+
+        let text = format_net_tie_pad_groups(groups);
+        match direct_child_node(footprint, "net_tie_pad_groups") {
+            Some(node) if node.to_string() == text => {}
+            Some(node) => patches.replace_raw(node.span, text),
+            None => insert_footprint_child(&mut patches, footprint, &text),
+        }
+    }
+
+    Ok(patches)
+}
+
+fn format_net_tie_pad_groups(groups: &[BTreeSet<String>]) -> String {
+    let mut items = vec![pcb_sexpr::Sexpr::symbol("net_tie_pad_groups")];
+    for group in groups {
+        let pads: Vec<_> = group.iter().map(String::as_str).collect();
+        items.push(pcb_sexpr::Sexpr::string(pads.join(", ")));
+    }
+    pcb_sexpr::Sexpr::list(items).to_string()
+}
+
 fn build_footprint_internal_connectivity_patchset(
     board: &pcb_sexpr::Sexpr,
     internal_connectivity_by_path: &BTreeMap<String, pcb_sch::InternalConnectivity>,
@@ -1178,7 +1503,7 @@ fn format_jumper_pad_groups(groups: &[std::collections::BTreeSet<String>]) -> St
 
 fn build_board_properties_patchset(
     board: &pcb_sexpr::Sexpr,
-    layout_name: Option<&str>,
+    text_variables: &[(&str, String)],
 ) -> Result<pcb_sexpr::PatchSet, LayoutError> {
     let root_items = board.as_list().ok_or_else(|| {
         LayoutError::StackupPatchingError("PCB root is not an S-expression list".to_string())
@@ -1191,14 +1516,7 @@ fn build_board_properties_patchset(
 
     let mut patches = pcb_sexpr::PatchSet::new();
     let mut inserted = Vec::new();
-    for (name, value) in [
-        layout_name.map(|value| ("PCB_NAME", value)),
-        Some(("PCB_VERSION", PCB_VERSION_PLACEHOLDER)),
-        Some(("PCB_GIT_HASH", PCB_GIT_HASH_PLACEHOLDER)),
-    ]
-    .into_iter()
-    .flatten()
-    {
+    for &(name, ref value) in text_variables {
         let property = root_items.iter().find_map(|item| {
             let items = item.as_list()?;
             (items.first().and_then(|item| item.as_sym()) == Some("property")
@@ -1243,8 +1561,11 @@ fn build_board_properties_patchset(
     Ok(patches)
 }
 
+/// Title block fields pcb manages. The revision field shows the declared
+/// board revision when there is one, and the release version otherwise.
 fn build_title_block_patchset(
     board: &pcb_sexpr::Sexpr,
+    has_revision: bool,
 ) -> Result<pcb_sexpr::PatchSet, LayoutError> {
     let root_items = board.as_list().ok_or_else(|| {
         LayoutError::StackupPatchingError("PCB root is not an S-expression list".to_string())
@@ -1266,7 +1587,11 @@ fn build_title_block_patchset(
     ]);
     let rev_expr = pcb_sexpr::Sexpr::list(vec![
         pcb_sexpr::Sexpr::symbol("rev"),
-        pcb_sexpr::Sexpr::string("${PCB_VERSION}"),
+        pcb_sexpr::Sexpr::string(if has_revision {
+            "${PCB_REVISION}"
+        } else {
+            "${PCB_VERSION}"
+        }),
     ]);
 
     if let Some(title_block_idx) = pcb_sexpr::find_named_list_index(root_items, "title_block") {
@@ -1454,12 +1779,50 @@ fn stackup_thickness_iu(stackup: &Stackup) -> Option<PcbIu> {
 #[cfg(test)]
 mod tests {
     use super::{
-        PCB_GIT_HASH_PLACEHOLDER, PCB_VERSION_PLACEHOLDER, PcbIu, build_board_properties_patchset,
-        build_footprint_internal_connectivity_patchset, build_stackup_patchset,
-        build_title_block_patchset, stackup_thickness_iu,
+        LayoutRollback, PCB_GIT_HASH_PLACEHOLDER, PCB_VERSION_PLACEHOLDER, PcbIu,
+        board_text_variables, build_board_properties_patchset,
+        build_footprint_internal_connectivity_patchset, build_footprint_net_tie_patchset,
+        build_stackup_patchset, build_title_block_patchset, stackup_thickness_iu,
     };
     use pcb_zen_core::lang::stackup::{CopperRole, DielectricForm, Layer, Stackup};
     use std::collections::{BTreeMap, BTreeSet};
+    use std::fs;
+
+    #[test]
+    fn layout_rollback_restores_files_unless_committed() {
+        let dir = tempfile::tempdir().unwrap();
+        let pcb = dir.path().join("layout.kicad_pcb");
+        let pro = dir.path().join("layout.kicad_pro");
+        fs::write(&pcb, "(kicad_pcb original)").unwrap();
+
+        // An interrupted sync restores the board and removes the project file
+        // it was creating.
+        let rollback = LayoutRollback::capture(&[pcb.clone(), pro.clone()], None).unwrap();
+        fs::write(&pcb, "(kicad_pcb half synced)").unwrap();
+        fs::write(&pro, "{}").unwrap();
+        drop(rollback);
+        assert_eq!(fs::read_to_string(&pcb).unwrap(), "(kicad_pcb original)");
+        assert!(!pro.exists());
+
+        let rollback = LayoutRollback::capture(&[pcb.clone(), pro.clone()], None).unwrap();
+        fs::write(&pcb, "(kicad_pcb synced)").unwrap();
+        rollback.commit();
+        assert_eq!(fs::read_to_string(&pcb).unwrap(), "(kicad_pcb synced)");
+    }
+
+    #[test]
+    fn layout_rollback_removes_temp_layout_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let layout_dir = dir.path().join("layout");
+        fs::create_dir_all(&layout_dir).unwrap();
+        let pcb = layout_dir.join("layout.kicad_pcb");
+
+        let rollback =
+            LayoutRollback::capture(std::slice::from_ref(&pcb), Some(layout_dir.clone())).unwrap();
+        fs::write(&pcb, "(kicad_pcb half synced)").unwrap();
+        drop(rollback);
+        assert!(!layout_dir.exists());
+    }
 
     #[test]
     fn stackup_thickness_iu_rounds_like_kicad() {
@@ -1508,7 +1871,11 @@ mod tests {
 )"#;
 
         let board = pcb_sexpr::parse(input).unwrap();
-        let patches = build_board_properties_patchset(&board, Some("DemoBoard")).unwrap();
+        let patches = build_board_properties_patchset(
+            &board,
+            &board_text_variables(Some("DemoBoard"), None, None),
+        )
+        .unwrap();
         let mut out = Vec::new();
         patches.write_to(input, &mut out).unwrap();
         let out = String::from_utf8(out).unwrap();
@@ -1523,6 +1890,30 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn build_board_properties_patchset_writes_stamp() {
+        let input =
+            "(kicad_pcb\n\t(version 20240101)\n\t(property \"PCB_SOURCE_HASH\" \"00000000\")\n)";
+        let stamp = pcb_sch::stamp::Stamp {
+            git_describe: "v1.2.0-3-g1a2b3c4-dirty".to_string(),
+            source_hash: "h1:q83vEjRWeJA=".to_string(),
+        };
+
+        let board = pcb_sexpr::parse(input).unwrap();
+        let patches = build_board_properties_patchset(
+            &board,
+            &board_text_variables(None, Some(&stamp), None),
+        )
+        .unwrap();
+        let mut out = Vec::new();
+        patches.write_to(input, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.contains(r#"(property "PCB_SOURCE_HASH" "q83vEjRW")"#));
+        assert!(out.contains(r#"(property "PCB_GIT_DESCRIBE" "v1.2.0-3-g1a2b3c4-dirty")"#));
+        assert!(!out.contains("PCB_NAME"));
+    }
+
     #[test]
     fn build_footprint_internal_connectivity_patchset_applies_jumper_metadata() {
         let input = r#"(kicad_pcb
@@ -1607,6 +1998,39 @@ mod tests {
         assert!(patches.is_empty());
     }
 
+    #[test]
+    fn build_footprint_net_tie_patchset_marks_net_tie_footprints() {
+        let input = r#"(kicad_pcb
+	(footprint "Lib:NT"
+		(layer "F.Cu")
+		(property "Path" "NT1")
+		(path "/old")
+		(attr smd)
+		(pad "1" smd rect (at 0 0) (size 1 1) (layers "F.Cu"))
+		(pad "2" smd rect (at 1 0) (size 1 1) (layers "F.Cu"))
+	)
+	(footprint "Lib:R"
+		(layer "F.Cu")
+		(property "Path" "R1")
+		(path "/r")
+		(pad "1" smd rect (at 0 0) (size 1 1) (layers "F.Cu"))
+	)
+)"#;
+
+        let board = pcb_sexpr::parse(input).unwrap();
+        let groups = BTreeMap::from([(
+            "NT1".to_string(),
+            vec![BTreeSet::from(["1".to_string(), "2".to_string()])],
+        )]);
+        let patches = build_footprint_net_tie_patchset(&board, &groups).unwrap();
+        let mut out = Vec::new();
+        patches.write_to(input, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(out.matches("net_tie_pad_groups").count(), 1);
+        assert!(out.contains(r#"(net_tie_pad_groups "1, 2")"#));
+    }
+
     #[test]
     fn build_stackup_patchset_preserves_unrelated_numeric_lexemes() {
         let input = r#"(kicad_pcb
@@ -1714,7 +2138,7 @@ mod tests {
 )"#;
 
         let board = pcb_sexpr::parse(input).unwrap();
-        let patches = build_title_block_patchset(&board).unwrap();
+        let patches = build_title_block_patchset(&board, false).unwrap();
         let mut out = Vec::new();
         patches.write_to(input, &mut out).unwrap();
         let out = String::from_utf8(out).unwrap();
@@ -1737,7 +2161,7 @@ mod tests {
 )"#;
 
         let board = pcb_sexpr::parse(input).unwrap();
-        let patches = build_title_block_patchset(&board).unwrap();
+        let patches = build_title_block_patchset(&board, false).unwrap();
         let mut out = Vec::new();
         patches.write_to(input, &mut out).unwrap();
         let out = String::from_utf8(out).unwrap();
@@ -1746,4 +2170,31 @@ mod tests {
         assert!(out.contains("(title_block"));
         assert!(out.contains(r#"(title "${PCB_NAME}")"#));
     }
+
+    #[test]
+    fn title_block_shows_declared_revision() {
+        let input = r#"(kicad_pcb
+	(version 20240101)
+	(title_block (rev "A"))
+	(layers (0 "F.Cu" signal) (2 "B.Cu" signal))
+)"#;
+        let revision = pcb_sch::revision::Revision {
+            revision: "C".to_string(),
+            date: None,
+            eco: Some("ECO-0042".to_string()),
+            description: String::new(),
+        };
+        let vars = board_text_variables(None, None, Some(&revision));
+        assert!(vars.contains(&("PCB_REVISION", "C".to_string())));
+        assert!(vars.contains(&("PCB_ECO", "ECO-0042".to_string())));
+
+        let board = pcb_sexpr::parse(input).unwrap();
+        let patches = build_title_block_patchset(&board, true).unwrap();
+        let mut out = Vec::new();
+        patches.write_to(input, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let out = pcb_sexpr::formatter::prettify(&out, pcb_sexpr::formatter::FormatMode::Normal);
+
+        assert!(out.contains(r#"(rev "${PCB_REVISION}")"#));
+    }
 }
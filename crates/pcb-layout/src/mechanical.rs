@@ -0,0 +1,629 @@
+//! Mounting holes, fiducials, and logos declared by the board config.
+//!
+//! Each item becomes a standalone footprint (no netlist `Path`, so the sync
+//! leaves it alone) identified by its reference designator. A hidden
+//! `Mechanical` property records the settings the footprint was generated
+//! from; when those change, the footprint is regenerated in place and keeps
+//! its current position. Footprints whose settings match are not touched.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use pcb_sexpr::{PatchSet, Sexpr, Span};
+use pcb_zen_core::lang::stackup::{BoardConfig, FiducialConfig, HoleConfig, LogoConfig};
+use resvg::{tiny_skia, usvg};
+use uuid::Uuid;
+
+use crate::{LayoutError, indent_board_item};
+
+/// Property holding the settings a footprint was generated from.
+const MECHANICAL_PROPERTY: &str = "Mechanical";
+
+/// Size of one logo pixel in mm.
+const LOGO_PITCH_MM: f64 = 0.1;
+
+/// Upper bound on logo pixels per side, to keep boards a sane size.
+const LOGO_MAX_PIXELS: u32 = 1000;
+
+/// A footprint to keep in sync with the board config.
+struct MechanicalFootprint {
+    reference: String,
+    /// Footprint name, also used as its `Value`.
+    name: String,
+    layer: &'static str,
+    /// Canonical description of the generated footprint; a change triggers
+    /// regeneration.
+    spec: String,
+    x: f64,
+    y: f64,
+    /// Footprint body after the properties; only built when written.
+    build: Box<dyn Fn() -> Result<Vec<Sexpr>, LayoutError>>,
+}
+
+/// Build patches that create or regenerate the mounting holes, fiducials,
+/// and logos declared in the board config.
+pub(crate) fn build_mechanical_patchset(
+    board: &Sexpr,
+    config: &BoardConfig,
+    package_roots: &BTreeMap<String, PathBuf>,
+) -> Result<PatchSet, LayoutError> {
+    let mut patches = PatchSet::new();
+    let items: Vec<MechanicalFootprint> = config
+        .holes()
+        .iter()
+        .map(hole_footprint)
+        .chain(config.fiducials().iter().map(fiducial_footprint))
+        .chain(
+            config
+                .logos()
+                .iter()
+                .map(|logo| logo_footprint(logo, package_roots))
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+        .collect();
+    if items.is_empty() {
+        return Ok(patches);
+    }
+    let root_items = board.as_list().ok_or_else(|| {
+        LayoutError::StackupPatchingError("PCB root is not an S-expression list".to_string())
+    })?;
+
+    let mut new_footprints = String::new();
+    for item in &items {
+        let existing = root_items.iter().skip(1).find(|node| {
+            node.as_list().is_some_and(|list| {
+                list.first().and_then(Sexpr::as_sym) == Some("footprint")
+                    && property(list, "Reference") == Some(item.reference.as_str())
+            })
+        });
+        match existing {
+            None => {
+                let at = Sexpr::list(vec![
+                    Sexpr::symbol("at"),
+                    Sexpr::float(item.x),
+                    Sexpr::float(item.y),
+                ]);
+                new_footprints.push_str(&indent_board_item(&format_footprint(item, at)?));
+            }
+            Some(node) => {
+                let list = node.as_list().unwrap_or_default();
+                match property(list, MECHANICAL_PROPERTY) {
+                    Some(spec) if spec == item.spec => {}
+                    Some(_) => {
+                        // Keep wherever the footprint has been moved to.
+                        let at = child(list, "at").cloned().unwrap_or_else(|| {
+                            Sexpr::list(vec![
+                                Sexpr::symbol("at"),
+                                Sexpr::float(item.x),
+                                Sexpr::float(item.y),
+                            ])
+                        });
+                        let text = format_footprint(item, at)?;
+                        patches.replace_raw(node.span, text.trim_end().replace('\n', "\n\t"));
+                    }
+                    None => log::warn!(
+                        "Skipping {}: the layout already has an unrelated footprint with that reference",
+                        item.reference
+                    ),
+                }
+            }
+        }
+    }
+
+    if !new_footprints.is_empty() {
+        let at = board.span.end - 1;
+        patches.replace_raw(Span::new(at, at), new_footprints);
+    }
+    Ok(patches)
+}
+
+fn child<'a>(items: &'a [Sexpr], name: &str) -> Option<&'a Sexpr> {
+    items.iter().skip(1).find(|item| {
+        item.as_list()
+            .and_then(|list| list.first())
+            .and_then(Sexpr::as_sym)
+            == Some(name)
+    })
+}
+
+fn property<'a>(footprint: &'a [Sexpr], name: &str) -> Option<&'a str> {
+    pcb_sexpr::find_all_child_lists(&footprint[1..], "property")
+        .into_iter()
+        .find(|list| list.get(1).and_then(Sexpr::as_str) == Some(name))
+        .and_then(|list| list.get(2))
+        .and_then(Sexpr::as_str)
+}
+
+fn list(name: &str, values: Vec<Sexpr>) -> Sexpr {
+    let mut items = vec![Sexpr::symbol(name)];
+    items.extend(values);
+    Sexpr::list(items)
+}
+
+fn uuid_node(key: &str) -> Sexpr {
+    let uuid = Uuid::new_v5(&Uuid::NAMESPACE_URL, format!("mechanical:{key}").as_bytes());
+    list("uuid", vec![Sexpr::string(uuid.to_string())])
+}
+
+fn hidden_property(reference: &str, name: &str, value: &str, layer: &str) -> Sexpr {
+    list(
+        "property",
+        vec![
+            Sexpr::string(name),
+            Sexpr::string(value),
+            list("at", vec![Sexpr::int(0), Sexpr::int(0), Sexpr::int(0)]),
+            list("layer", vec![Sexpr::string(layer)]),
+            list("hide", vec![Sexpr::symbol("yes")]),
+            uuid_node(&format!("{reference}:{name}")),
+            list(
+                "effects",
+                vec![list(
+                    "font",
+                    vec![
+                        list("size", vec![Sexpr::int(1), Sexpr::int(1)]),
+                        list("thickness", vec![Sexpr::float(0.15)]),
+                    ],
+                )],
+            ),
+        ],
+    )
+}
+
+fn format_footprint(item: &MechanicalFootprint, at: Sexpr) -> Result<String, LayoutError> {
+    let (silk, fab) = if item.layer == "B.Cu" {
+        ("B.SilkS", "B.Fab")
+    } else {
+        ("F.SilkS", "F.Fab")
+    };
+    let mut items = vec![
+        Sexpr::symbol("footprint"),
+        Sexpr::string(&item.name),
+        list("layer", vec![Sexpr::string(item.layer)]),
+        uuid_node(&item.reference),
+        at,
+        hidden_property(&item.reference, "Reference", &item.reference, silk),
+        hidden_property(&item.reference, "Value", &item.name, fab),
+        hidden_property(&item.reference, MECHANICAL_PROPERTY, &item.spec, fab),
+    ];
+    items.extend((item.build)()?);
+    Ok(pcb_sexpr::formatter::format_kicad(
+        &Sexpr::list(items),
+        pcb_sexpr::formatter::KicadFileKind::Pcb,
+    ))
+}
+
+fn circle_pad(number: &str, kind: &str, diameter: f64, extra: Vec<Sexpr>) -> Sexpr {
+    let mut items = vec![
+        Sexpr::string(number),
+        Sexpr::symbol(kind),
+        Sexpr::symbol("circle"),
+        list("at", vec![Sexpr::int(0), Sexpr::int(0)]),
+        list("size", vec![Sexpr::float(diameter), Sexpr::float(diameter)]),
+    ];
+    items.extend(extra);
+    list("pad", items)
+}
+
+fn hole_footprint(hole: &HoleConfig) -> MechanicalFootprint {
+    let (drill, pad) = (hole.drill, hole.pad);
+    let spec = match pad {
+        Some(pad) => format!("hole drill={drill} pad={pad}"),
+        None => format!("hole drill={drill}"),
+    };
+    let reference = hole.reference.clone();
+    MechanicalFootprint {
+        name: format!("MountingHole_{drill}mm"),
+        layer: "F.Cu",
+        reference: hole.reference.clone(),
+        spec,
+        x: hole.x,
+        y: hole.y,
+        build: Box::new(move || {
+            let layers = list(
+                "layers",
+                vec![Sexpr::string("*.Cu"), Sexpr::string("*.Mask")],
+            );
+            let drill_node = list("drill", vec![Sexpr::float(drill)]);
+            let pad = match pad {
+                Some(pad) => circle_pad("1", "thru_hole", pad, vec![drill_node, layers]),
+                None => circle_pad("", "np_thru_hole", drill, vec![drill_node, layers]),
+            };
+            Ok(vec![
+                list(
+                    "attr",
+                    vec![
+                        Sexpr::symbol("board_only"),
+                        Sexpr::symbol("exclude_from_pos_files"),
+                        Sexpr::symbol("exclude_from_bom"),
+                    ],
+                ),
+                pad_with_uuid(pad, &reference),
+            ])
+        }),
+    }
+}
+
+fn fiducial_footprint(fiducial: &FiducialConfig) -> MechanicalFootprint {
+    let bottom = fiducial.side == "bottom";
+    let scope = if fiducial.local { "local" } else { "global" };
+    let (pad, mask) = (fiducial.pad, fiducial.mask);
+    let reference = fiducial.reference.clone();
+    MechanicalFootprint {
+        name: format!("Fiducial_{pad}mm_Mask{mask}mm"),
+        layer: if bottom { "B.Cu" } else { "F.Cu" },
+        reference: fiducial.reference.clone(),
+        spec: format!("fiducial {scope} {} pad={pad} mask={mask}", fiducial.side),
+        x: fiducial.x,
+        y: fiducial.y,
+        build: Box::new(move || {
+            let (copper, solder_mask) = if bottom {
+                ("B.Cu", "B.Mask")
+            } else {
+                ("F.Cu", "F.Mask")
+            };
+            let property = if scope == "local" {
+                "pad_prop_fiducial_loc"
+            } else {
+                "pad_prop_fiducial_glob"
+            };
+            let margin = (mask - pad) / 2.0;
+            let pad = circle_pad(
+                "",
+                "smd",
+                pad,
+                vec![
+                    list("property", vec![Sexpr::symbol(property)]),
+                    list(
+                        "layers",
+                        vec![Sexpr::string(copper), Sexpr::string(solder_mask)],
+                    ),
+                    list("solder_mask_margin", vec![Sexpr::float(margin)]),
+                    list("clearance", vec![Sexpr::float(margin)]),
+                ],
+            );
+            Ok(vec![
+                list(
+                    "attr",
+                    vec![
+                        Sexpr::symbol("smd"),
+                        Sexpr::symbol("board_only"),
+                        Sexpr::symbol("exclude_from_bom"),
+                    ],
+                ),
+                pad_with_uuid(pad, &reference),
+            ])
+        }),
+    }
+}
+
+fn logo_footprint(
+    logo: &LogoConfig,
+    package_roots: &BTreeMap<String, PathBuf>,
+) -> Result<MechanicalFootprint, LayoutError> {
+    let path = if logo.image.starts_with(pcb_sch::PACKAGE_URI_PREFIX) {
+        pcb_sch::resolve_package_uri(&logo.image, package_roots).map_err(|e| {
+            LayoutError::StackupPatchingError(format!(
+                "Failed to resolve logo image {}: {e}",
+                logo.image
+            ))
+        })?
+    } else {
+        PathBuf::from(&logo.image)
+    };
+    let data = std::fs::read(&path).map_err(|e| {
+        LayoutError::StackupPatchingError(format!(
+            "Failed to read logo image {}: {e}",
+            path.display()
+        ))
+    })?;
+    // The content hash regenerates the logo when the image changes.
+    let hash = Uuid::new_v5(&Uuid::NAMESPACE_URL, &data);
+    let (width, layer) = (logo.width, logo.layer.clone());
+    let reference = logo.reference.clone();
+    Ok(MechanicalFootprint {
+        name: "Logo".to_string(),
+        layer: if layer.starts_with("B.") {
+            "B.Cu"
+        } else {
+            "F.Cu"
+        },
+        reference: logo.reference.clone(),
+        spec: format!("logo {hash} width={width} layer={layer}"),
+        x: logo.x,
+        y: logo.y,
+        build: Box::new(move || {
+            let bitmap = rasterize(&path, &data, width)?;
+            let mut items = vec![list(
+                "attr",
+                vec![
+                    Sexpr::symbol("board_only"),
+                    Sexpr::symbol("exclude_from_pos_files"),
+                    Sexpr::symbol("exclude_from_bom"),
+                ],
+            )];
+            let (x0, y0) = (
+                -(bitmap.width as f64) * LOGO_PITCH_MM / 2.0,
+                -(bitmap.height as f64) * LOGO_PITCH_MM / 2.0,
+            );
+            for (i, rect) in bitmap.rectangles().into_iter().enumerate() {
+                let xy = |col: u32, row: u32| {
+                    list(
+                        "xy",
+                        vec![
+                            Sexpr::float(round(x0 + col as f64 * LOGO_PITCH_MM)),
+                            Sexpr::float(round(y0 + row as f64 * LOGO_PITCH_MM)),
+                        ],
+                    )
+                };
+                items.push(list(
+                    "fp_poly",
+                    vec![
+                        list(
+                            "pts",
+                            vec![
+                                xy(rect.left, rect.top),
+                                xy(rect.right, rect.top),
+                                xy(rect.right, rect.bottom),
+                                xy(rect.left, rect.bottom),
+                            ],
+                        ),
+                        list(
+                            "stroke",
+                            vec![
+                                list("width", vec![Sexpr::int(0)]),
+                                list("type", vec![Sexpr::symbol("solid")]),
+                            ],
+                        ),
+                        list("fill", vec![Sexpr::symbol("yes")]),
+                        list("layer", vec![Sexpr::string(&layer)]),
+                        uuid_node(&format!("{reference}:poly{i}")),
+                    ],
+                ));
+            }
+            Ok(items)
+        }),
+    })
+}
+
+fn pad_with_uuid(pad: Sexpr, reference: &str) -> Sexpr {
+    let mut items = pad.as_list().unwrap_or_default().to_vec();
+    items.push(uuid_node(&format!("{reference}:pad")));
+    Sexpr::list(items)
+}
+
+fn round(value: f64) -> f64 {
+    (value * 1e4).round() / 1e4
+}
+
+/// Dark, opaque pixels of a logo image sampled on the logo grid.
+struct Bitmap {
+    width: u32,
+    height: u32,
+    pixels: Vec<bool>,
+}
+
+/// Filled rectangle in grid cells, right and bottom exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rect {
+    left: u32,
+    top: u32,
+    right: u32,
+    bottom: u32,
+}
+
+impl Bitmap {
+    fn get(&self, col: u32, row: u32) -> bool {
+        self.pixels[(row * self.width + col) as usize]
+    }
+
+    /// Cover the filled pixels with rectangles: horizontal runs, merged
+    /// downwards while the run below spans the same columns.
+    fn rectangles(&self) -> Vec<Rect> {
+        let mut done = Vec::new();
+        let mut open: Vec<Rect> = Vec::new();
+        for row in 0..self.height {
+            let mut runs = Vec::new();
+            let mut col = 0;
+            while col < self.width {
+                if !self.get(col, row) {
+                    col += 1;
+                    continue;
+                }
+                let start = col;
+                while col < self.width && self.get(col, row) {
+                    col += 1;
+                }
+                runs.push((start, col));
+            }
+
+            let mut next_open = Vec::new();
+            for (left, right) in runs {
+                match open
+                    .iter()
+                    .position(|rect| rect.left == left && rect.right == right)
+                {
+                    Some(i) => {
+                        let mut rect = open.swap_remove(i);
+                        rect.bottom = row + 1;
+                        next_open.push(rect);
+                    }
+                    None => next_open.push(Rect {
+                        left,
+                        top: row,
+                        right,
+                        bottom: row + 1,
+                    }),
+                }
+            }
+            done.append(&mut open);
+            open = next_open;
+        }
+        done.append(&mut open);
+        done.sort_by_key(|rect| (rect.top, rect.left));
+        done
+    }
+}
+
+/// Rasterize an SVG or PNG image onto the logo grid for a logo `width_mm` wide.
+fn rasterize(path: &Path, data: &[u8], width_mm: f64) -> Result<Bitmap, LayoutError> {
+    let error = |message: String| {
+        LayoutError::StackupPatchingError(format!(
+            "Failed to load logo image {}: {message}",
+            path.display()
+        ))
+    };
+    let columns = ((width_mm / LOGO_PITCH_MM).round() as u32).clamp(1, LOGO_MAX_PIXELS);
+
+    let is_svg = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+    let image = if is_svg {
+        let tree = usvg::Tree::from_data(data, &usvg::Options::default())
+            .map_err(|e| error(e.to_string()))?;
+        let size = tree.size();
+        let scale = columns as f32 / size.width();
+        let rows = (size.height() * scale).round().max(1.0) as u32;
+        let mut pixmap = tiny_skia::Pixmap::new(columns, rows)
+            .ok_or_else(|| error(format!("cannot allocate a {columns}x{rows} raster")))?;
+        resvg::render(
+            &tree,
+            tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+        pixmap
+    } else {
+        tiny_skia::Pixmap::decode_png(data).map_err(|e| error(e.to_string()))?
+    };
+
+    let rows = ((columns as f64 * image.height() as f64 / image.width() as f64).round() as u32)
+        .clamp(1, LOGO_MAX_PIXELS);
+    let mut pixels = Vec::with_capacity((columns * rows) as usize);
+    for row in 0..rows {
+        for col in 0..columns {
+            // Nearest-neighbour sample at the cell center.
+            let x = ((col as f64 + 0.5) * image.width() as f64 / columns as f64) as u32;
+            let y = ((row as f64 + 0.5) * image.height() as f64 / rows as f64) as u32;
+            let pixel = image
+                .pixel(x.min(image.width() - 1), y.min(image.height() - 1))
+                .map(|pixel| pixel.demultiply());
+            pixels.push(pixel.is_some_and(|pixel| {
+                let luma = (u32::from(pixel.red()) * 299
+                    + u32::from(pixel.green()) * 587
+                    + u32::from(pixel.blue()) * 114)
+                    / 1000;
+                pixel.alpha() >= 128 && luma < 128
+            }));
+        }
+    }
+    Ok(Bitmap {
+        width: columns,
+        height: rows,
+        pixels,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: &str = "(kicad_pcb\n\t(version 20241229)\n)";
+
+    fn config(json: serde_json::Value) -> BoardConfig {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn apply(source: &str, config: &BoardConfig) -> String {
+        let board = pcb_sexpr::parse(source).unwrap();
+        let patches = build_mechanical_patchset(&board, config, &BTreeMap::new()).unwrap();
+        crate::render_patches(source, &patches).unwrap()
+    }
+
+    fn hole_config(drill: f64) -> BoardConfig {
+        config(serde_json::json!({
+            "design_rules": null,
+            "stackup": null,
+            "holes": [{"ref": "H1", "x": 3.0, "y": 3.0, "drill": drill, "pad": 6.0}],
+            "fiducials": [{"ref": "FID1", "x": 10.0, "y": 5.0, "pad": 1.0, "mask": 2.0}],
+        }))
+    }
+
+    #[test]
+    fn adds_holes_and_fiducials_once() {
+        let once = apply(BOARD, &hole_config(3.2));
+        assert!(once.contains(r#"(property "Reference" "H1""#));
+        assert!(once.contains("(drill 3.2)"));
+        assert!(once.contains("pad_prop_fiducial_glob"));
+        assert!(once.contains("(solder_mask_margin 0.5)"));
+        pcb_sexpr::parse(&once).unwrap();
+
+        assert_eq!(apply(&once, &hole_config(3.2)), once);
+    }
+
+    #[test]
+    fn regenerates_changed_hole_in_place() {
+        let once = apply(BOARD, &hole_config(3.2));
+        let moved = once.replacen("(at 3 3)", "(at 20 25)", 1);
+        let patched = apply(&moved, &hole_config(2.7));
+
+        assert!(patched.contains("(drill 2.7)"));
+        assert!(!patched.contains("(drill 3.2)"));
+        assert!(patched.contains("(at 20 25)"));
+        assert_eq!(patched.matches(r#"(property "Reference" "H1""#).count(), 1);
+    }
+
+    #[test]
+    fn merges_logo_pixels_into_rectangles() {
+        #[rustfmt::skip]
+        let pixels = [
+            true, true, false,
+            true, true, true,
+            false, false, true,
+        ];
+        let bitmap = Bitmap {
+            width: 3,
+            height: 3,
+            pixels: pixels.to_vec(),
+        };
+        assert_eq!(
+            bitmap.rectangles(),
+            vec![
+                Rect {
+                    left: 0,
+                    top: 0,
+                    right: 2,
+                    bottom: 1
+                },
+                Rect {
+                    left: 0,
+                    top: 1,
+                    right: 3,
+                    bottom: 2
+                },
+                Rect {
+                    left: 2,
+                    top: 2,
+                    right: 3,
+                    bottom: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rasterizes_svg_logo() {
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+            <rect x="0" y="0" width="10" height="10" fill="black"/>
+        </svg>"#;
+        let bitmap = rasterize(Path::new("logo.svg"), svg, 2.0).unwrap();
+        assert_eq!((bitmap.width, bitmap.height), (20, 10));
+        assert_eq!(
+            bitmap.rectangles(),
+            vec![Rect {
+                left: 0,
+                top: 0,
+                right: 10,
+                bottom: 10
+            }]
+        );
+    }
+}
@@ -18,10 +18,6 @@ use pcb_sexpr::{PatchSet, Sexpr, WalkCtx};
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
-/// UUID namespace used for generating deterministic footprint UUIDs from paths.
-/// This matches Python: uuid.NAMESPACE_URL
-const UUID_NAMESPACE_URL: Uuid = Uuid::from_u128(0x6ba7b811_9dad_11d1_80b4_00c04fd430c8);
-
 /// Compute patches for net-only renames (exact match, no prefix matching).
 ///
 /// This is used for implicit net rename detection where we only want to rename
@@ -146,7 +142,7 @@ pub fn compute_moved_paths_patches(
 /// Compute deterministic UUID from a hierarchical path.
 /// Uses UUID v5 with NAMESPACE_URL, matching Python's uuid.uuid5(uuid.NAMESPACE_URL, path).
 fn compute_uuid_from_path(path: &str) -> String {
-    Uuid::new_v5(&UUID_NAMESPACE_URL, path.as_bytes()).to_string()
+    Uuid::new_v5(&Uuid::NAMESPACE_URL, path.as_bytes()).to_string()
 }
 
 /// Apply longest-prefix matching to remap a path.
@@ -155,7 +151,10 @@ fn compute_uuid_from_path(path: &str) -> String {
 /// returns Some("Supply.R1").
 ///
 /// If no prefix matches, returns None.
-fn apply_longest_prefix_match(path: &str, moved_paths: &HashMap<String, String>) -> Option<String> {
+pub(crate) fn apply_longest_prefix_match(
+    path: &str,
+    moved_paths: &HashMap<String, String>,
+) -> Option<String> {
     let mut best_match: Option<(&str, &str)> = None;
     let mut best_len = 0;
 
@@ -0,0 +1,378 @@
+//! Return path checks for high-speed nets in a `.kicad_pcb`.
+//!
+//! Tracks of the selected net classes are sampled every
+//! [`SAMPLE_STEP_MM`]. A sample farther than the stitch distance from every
+//! ground via and through-hole ground pad lacks a nearby stitching via; a
+//! sample with no filled ground zone under it on an adjacent copper layer has
+//! a gap in its reference plane. Consecutive failing samples of a track are
+//! reported as one stretch, at its first point.
+//!
+//! Ground zones are read from their `filled_polygon`s, so the zones must be
+//! filled for the plane check to be meaningful.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use pcb_sexpr::Sexpr;
+use pcb_sexpr::{find_all_child_lists, find_child_list};
+use serde::Serialize;
+
+use crate::routing_status::{
+    Point, copper_rank, distance, item_net, net_class_for, net_table, parse_at, parse_xy, place,
+    point_in_polygon,
+};
+
+/// Distance between samples along a track, in mm.
+pub const SAMPLE_STEP_MM: f64 = 0.25;
+
+/// Default largest distance from a high-speed track to a ground via, in mm.
+pub const DEFAULT_STITCH_DISTANCE_MM: f64 = 3.0;
+
+pub struct ReturnPathOptions<'a> {
+    /// Net classes whose nets are checked.
+    pub net_classes: &'a [String],
+    /// Nets that carry the return current.
+    pub ground_nets: &'a BTreeSet<String>,
+    pub stitch_distance_mm: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnPathIssue {
+    /// No ground via within the stitch distance.
+    Stitching,
+    /// No ground plane on an adjacent copper layer.
+    ReferencePlane,
+}
+
+/// A stretch of one track that fails a check.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnPathViolation {
+    pub net: String,
+    pub net_class: String,
+    pub layer: String,
+    pub issue: ReturnPathIssue,
+    /// Start of the stretch, in board coordinates (mm).
+    pub x: f64,
+    pub y: f64,
+    pub length_mm: f64,
+    /// Largest distance to the nearest ground via along the stretch, for
+    /// stitching issues. `None` when the board has no ground vias.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub via_distance_mm: Option<f64>,
+    /// Copper layers checked for a ground plane, for reference plane issues.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reference_layers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnPathReport {
+    /// Routed nets in the selected classes.
+    pub nets_checked: usize,
+    pub violations: Vec<ReturnPathViolation>,
+}
+
+struct Track {
+    net: String,
+    layer: String,
+    start: Point,
+    end: Point,
+}
+
+#[derive(Default)]
+struct Ground {
+    /// Centers of ground vias and through-hole ground pads.
+    vias: Vec<Point>,
+    /// Filled ground zone islands by copper layer.
+    planes: BTreeMap<String, Vec<Vec<Point>>>,
+}
+
+/// Check the tracks of every net in `options.net_classes` on `board`.
+pub fn check_return_paths(
+    board: &Sexpr,
+    netclass_patterns: &[(String, String)],
+    options: &ReturnPathOptions,
+) -> ReturnPathReport {
+    let Some(root) = board.as_list() else {
+        return ReturnPathReport::default();
+    };
+
+    let net_names = net_table(root);
+    let mut tracks = Vec::new();
+    let mut ground = Ground::default();
+    for items in root.iter().skip(1).filter_map(Sexpr::as_list) {
+        let net = item_net(items, &net_names);
+        let is_ground = net
+            .as_ref()
+            .is_some_and(|net| options.ground_nets.contains(net));
+        match items.first().and_then(Sexpr::as_sym) {
+            Some(kind @ ("segment" | "arc")) => {
+                let Some(net) = net.filter(|net| {
+                    options
+                        .net_classes
+                        .iter()
+                        .any(|class| class == net_class_for(net, netclass_patterns))
+                }) else {
+                    continue;
+                };
+                let point = |name| find_child_list(items, name).and_then(parse_xy);
+                let (Some(start), Some(end), Some(layer)) =
+                    (point("start"), point("end"), layer_name(items))
+                else {
+                    continue;
+                };
+                let mid = if kind == "arc" { point("mid") } else { None };
+                let pieces = match mid {
+                    Some(mid) => vec![(start, mid), (mid, end)],
+                    None => vec![(start, end)],
+                };
+                for (start, end) in pieces {
+                    tracks.push(Track {
+                        net: net.clone(),
+                        layer: layer.to_string(),
+                        start,
+                        end,
+                    });
+                }
+            }
+            Some("via") if is_ground => {
+                if let Some((center, _)) = find_child_list(items, "at").and_then(parse_at) {
+                    ground.vias.push(center);
+                }
+            }
+            Some("footprint") => {
+                let (origin, rotation) = find_child_list(items, "at")
+                    .and_then(parse_at)
+                    .unwrap_or(((0.0, 0.0), 0.0));
+                for pad in find_all_child_lists(items, "pad") {
+                    let through_hole = pad.get(2).and_then(Sexpr::as_sym) == Some("thru_hole");
+                    let on_ground = item_net(pad, &net_names)
+                        .is_some_and(|net| options.ground_nets.contains(&net));
+                    if through_hole
+                        && on_ground
+                        && let Some((local, _)) = find_child_list(pad, "at").and_then(parse_at)
+                    {
+                        ground.vias.push(place(origin, rotation, local));
+                    }
+                }
+            }
+            Some("zone") if is_ground => {
+                for filled in find_all_child_lists(items, "filled_polygon") {
+                    let (Some(layer), Some(pts)) =
+                        (layer_name(filled), find_child_list(filled, "pts"))
+                    else {
+                        continue;
+                    };
+                    let polygon: Vec<Point> = find_all_child_lists(pts, "xy")
+                        .into_iter()
+                        .filter_map(parse_xy)
+                        .collect();
+                    if polygon.len() >= 3 {
+                        ground
+                            .planes
+                            .entry(layer.to_string())
+                            .or_default()
+                            .push(polygon);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let copper_layers = copper_layers(root);
+    let mut violations = Vec::new();
+    for track in &tracks {
+        let reference_layers = adjacent_layers(&copper_layers, &track.layer);
+        let samples = sample(track.start, track.end);
+        let via_distances: Vec<Option<f64>> = samples
+            .iter()
+            .map(|&p| {
+                ground
+                    .vias
+                    .iter()
+                    .map(|&via| distance(p, via))
+                    .min_by(f64::total_cmp)
+            })
+            .collect();
+        let over_distance: Vec<bool> = via_distances
+            .iter()
+            .map(|d| d.is_none_or(|d| d > options.stitch_distance_mm))
+            .collect();
+        let no_plane: Vec<bool> = samples
+            .iter()
+            .map(|&p| {
+                !reference_layers.iter().any(|layer| {
+                    ground.planes.get(layer).is_some_and(|polygons| {
+                        polygons.iter().any(|polygon| point_in_polygon(p, polygon))
+                    })
+                })
+            })
+            .collect();
+
+        let class = net_class_for(&track.net, netclass_patterns).to_string();
+        let mut report = |issue, range: std::ops::Range<usize>| {
+            let (x, y) = samples[range.start];
+            let via_distance_mm = match issue {
+                ReturnPathIssue::Stitching => via_distances[range.clone()]
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .max_by(f64::total_cmp),
+                ReturnPathIssue::ReferencePlane => None,
+            };
+            violations.push(ReturnPathViolation {
+                net: track.net.clone(),
+                net_class: class.clone(),
+                layer: track.layer.clone(),
+                issue,
+                x,
+                y,
+                length_mm: distance(samples[range.start], samples[range.end - 1]),
+                via_distance_mm,
+                reference_layers: match issue {
+                    ReturnPathIssue::Stitching => Vec::new(),
+                    ReturnPathIssue::ReferencePlane => reference_layers.clone(),
+                },
+            });
+        };
+        for range in runs(&over_distance) {
+            report(ReturnPathIssue::Stitching, range);
+        }
+        for range in runs(&no_plane) {
+            report(ReturnPathIssue::ReferencePlane, range);
+        }
+    }
+
+    let nets: BTreeSet<&str> = tracks.iter().map(|track| track.net.as_str()).collect();
+    ReturnPathReport {
+        nets_checked: nets.len(),
+        violations,
+    }
+}
+
+/// The item's single `(layer ...)`.
+fn layer_name(items: &[Sexpr]) -> Option<&str> {
+    find_child_list(items, "layer")?.get(1)?.as_atom()
+}
+
+/// Copper layer names of the board in stackup order.
+fn copper_layers(root: &[Sexpr]) -> Vec<String> {
+    let mut layers: Vec<(u32, String)> = find_child_list(root, "layers")
+        .into_iter()
+        .flat_map(|layers| layers.iter().skip(1))
+        .filter_map(Sexpr::as_list)
+        .filter_map(|layer| layer.get(1).and_then(Sexpr::as_atom))
+        .filter_map(|name| Some((copper_rank(name)?, name.to_string())))
+        .collect();
+    layers.sort();
+    layers.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Copper layers directly above and below `layer` in the stackup.
+fn adjacent_layers(copper_layers: &[String], layer: &str) -> Vec<String> {
+    let Some(index) = copper_layers.iter().position(|name| name == layer) else {
+        return Vec::new();
+    };
+    [index.checked_sub(1), Some(index + 1)]
+        .into_iter()
+        .flatten()
+        .filter_map(|i| copper_layers.get(i).cloned())
+        .collect()
+}
+
+/// Points along `start`..`end`, both ends included, at most
+/// [`SAMPLE_STEP_MM`] apart.
+fn sample(start: Point, end: Point) -> Vec<Point> {
+    let steps = (distance(start, end) / SAMPLE_STEP_MM).ceil().max(1.0) as usize;
+    (0..=steps)
+        .map(|i| {
+            let t = i as f64 / steps as f64;
+            (
+                start.0 + (end.0 - start.0) * t,
+                start.1 + (end.1 - start.1) * t,
+            )
+        })
+        .collect()
+}
+
+/// Index ranges of consecutive `true` values.
+fn runs(flags: &[bool]) -> Vec<std::ops::Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for (i, &flag) in flags.iter().enumerate() {
+        match (flag, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                ranges.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        ranges.push(s..flags.len());
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: &str = r#"(kicad_pcb
+        (layers (0 "F.Cu" signal) (4 "In1.Cu" power) (6 "In2.Cu" power) (2 "B.Cu" signal))
+        (net 0 "")
+        (net 1 "GND")
+        (net 2 "USB_DP")
+        (net 3 "LED")
+        (via (at 0 1) (size 0.6) (layers "F.Cu" "B.Cu") (net 1))
+        (zone (net 1) (net_name "GND") (layer "In1.Cu")
+            (filled_polygon (layer "In1.Cu") (pts (xy -1 -2) (xy 5.1 -2) (xy 5.1 2) (xy -1 2))))
+        (segment (start 0 0) (end 10 0) (width 0.2) (layer "F.Cu") (net 2))
+        (segment (start 0 0) (end 10 0) (width 0.2) (layer "F.Cu") (net 3))
+    )"#;
+
+    #[test]
+    fn reports_stitching_and_plane_gaps() {
+        let board = pcb_sexpr::parse(BOARD).unwrap();
+        let patterns = vec![("USB_*".to_string(), "USB".to_string())];
+        let ground_nets = BTreeSet::from(["GND".to_string()]);
+        let report = check_return_paths(
+            &board,
+            &patterns,
+            &ReturnPathOptions {
+                net_classes: &["USB".to_string()],
+                ground_nets: &ground_nets,
+                stitch_distance_mm: 3.0,
+            },
+        );
+
+        assert_eq!(report.nets_checked, 1);
+        assert_eq!(report.violations.len(), 2);
+
+        // The via at (0, 1) covers x <= ~2.83; the plane ends at x = 5.1.
+        let stitching = &report.violations[0];
+        assert_eq!(stitching.issue, ReturnPathIssue::Stitching);
+        assert_eq!(stitching.net, "USB_DP");
+        assert!((stitching.x - 3.0).abs() < 1e-9);
+        assert!((stitching.length_mm - 7.0).abs() < 1e-9);
+        assert!((stitching.via_distance_mm.unwrap() - 101f64.sqrt()).abs() < 1e-9);
+
+        let plane = &report.violations[1];
+        assert_eq!(plane.issue, ReturnPathIssue::ReferencePlane);
+        assert!((plane.x - 5.25).abs() < 1e-9);
+        assert_eq!(plane.reference_layers, vec!["In1.Cu".to_string()]);
+    }
+
+    #[test]
+    fn finds_adjacent_layers_in_stackup_order() {
+        let layers: Vec<String> = ["F.Cu", "In1.Cu", "In2.Cu", "B.Cu"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(adjacent_layers(&layers, "F.Cu"), vec!["In1.Cu"]);
+        assert_eq!(adjacent_layers(&layers, "In2.Cu"), vec!["In1.Cu", "B.Cu"]);
+        assert_eq!(runs(&[true, false, true, true]), vec![0..1, 2..4]);
+    }
+}
@@ -0,0 +1,677 @@
+//! Routing completion ("ratsnest") report for a `.kicad_pcb`.
+//!
+//! Every pad, track segment, arc, via, and filled zone island on a net is a
+//! node; nodes on a shared copper layer whose copper touches are connected.
+//! A net with `n` pads needs `n - 1` connections, and each extra island of
+//! pads left after the union is one unrouted connection, matching KiCad's
+//! ratsnest count.
+//!
+//! Pads keep their real outline: circles, ovals, rectangles, and rounded
+//! rectangles at the pad's orientation. Trapezoids are treated as their
+//! enclosing rectangle and custom pads as their anchor pad. Net classes come
+//! from the project's `netclass_patterns`; nets without a match are `Default`.
+
+use std::collections::BTreeMap;
+
+use pcb_sch::query::glob_match;
+use pcb_sexpr::Sexpr;
+use pcb_sexpr::board::net_name_str;
+use pcb_sexpr::{find_all_child_lists, find_child_list, number_as_f64};
+use serde::Serialize;
+
+pub const DEFAULT_NET_CLASS: &str = "Default";
+
+/// Routing state of one net with at least two pads.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetRouting {
+    pub name: String,
+    pub net_class: String,
+    pub pads: usize,
+    /// Pad-to-pad connections still shown as ratsnest lines.
+    pub unrouted_connections: usize,
+}
+
+impl NetRouting {
+    /// Connections needed to join every pad of the net.
+    pub fn connections(&self) -> usize {
+        self.pads.saturating_sub(1)
+    }
+
+    pub fn is_routed(&self) -> bool {
+        self.unrouted_connections == 0
+    }
+}
+
+/// Routing completion of all nets in one net class.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetClassRouting {
+    pub name: String,
+    pub nets: usize,
+    pub routed_nets: usize,
+    pub connections: usize,
+    pub unrouted_connections: usize,
+}
+
+impl NetClassRouting {
+    /// Share of routed connections, in percent. Classes without connections
+    /// are complete.
+    pub fn completion_percent(&self) -> f64 {
+        if self.connections == 0 {
+            return 100.0;
+        }
+        let routed = self.connections - self.unrouted_connections;
+        routed as f64 * 100.0 / self.connections as f64
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RoutingStatus {
+    /// Nets with at least two pads, sorted by name.
+    pub nets: Vec<NetRouting>,
+}
+
+impl RoutingStatus {
+    /// Per-class totals, sorted by class name.
+    pub fn net_classes(&self) -> Vec<NetClassRouting> {
+        let mut classes: BTreeMap<&str, NetClassRouting> = BTreeMap::new();
+        for net in &self.nets {
+            let class = classes
+                .entry(&net.net_class)
+                .or_insert_with(|| NetClassRouting {
+                    name: net.net_class.clone(),
+                    nets: 0,
+                    routed_nets: 0,
+                    connections: 0,
+                    unrouted_connections: 0,
+                });
+            class.nets += 1;
+            class.routed_nets += usize::from(net.is_routed());
+            class.connections += net.connections();
+            class.unrouted_connections += net.unrouted_connections;
+        }
+        classes.into_values().collect()
+    }
+
+    pub fn unrouted_nets(&self) -> impl Iterator<Item = &NetRouting> {
+        self.nets.iter().filter(|net| !net.is_routed())
+    }
+
+    pub fn total(&self) -> NetClassRouting {
+        NetClassRouting {
+            name: String::new(),
+            nets: self.nets.len(),
+            routed_nets: self.nets.iter().filter(|net| net.is_routed()).count(),
+            connections: self.nets.iter().map(NetRouting::connections).sum(),
+            unrouted_connections: self.nets.iter().map(|net| net.unrouted_connections).sum(),
+        }
+    }
+}
+
+/// Net class for `net` from `.kicad_pro` `netclass_patterns` entries, as
+/// `(pattern, netclass)` pairs. Patterns may use `*` and `?` wildcards.
+pub fn net_class_for<'a>(net: &str, patterns: &'a [(String, String)]) -> &'a str {
+    patterns
+        .iter()
+        .find(|(pattern, _)| glob_match(pattern.as_bytes(), net.as_bytes()))
+        .map(|(_, class)| class.as_str())
+        .unwrap_or(DEFAULT_NET_CLASS)
+}
+
+/// Compute the routing status of every multi-pad net on `board`.
+pub fn routing_status(board: &Sexpr, netclass_patterns: &[(String, String)]) -> RoutingStatus {
+    let mut nodes: BTreeMap<String, Vec<Node>> = BTreeMap::new();
+    for (net, node) in collect_nodes(board) {
+        nodes.entry(net).or_default().push(node);
+    }
+
+    let nets = nodes
+        .into_iter()
+        .filter_map(|(name, nodes)| {
+            let pads = nodes.iter().filter(|node| node.is_pad).count();
+            if pads < 2 {
+                return None;
+            }
+            let islands = pad_islands(&nodes);
+            Some(NetRouting {
+                net_class: net_class_for(&name, netclass_patterns).to_string(),
+                name,
+                pads,
+                unrouted_connections: islands - 1,
+            })
+        })
+        .collect();
+    RoutingStatus { nets }
+}
+
+/// Copper layers an item is on, by stackup position (`F.Cu` = 0, `In<n>.Cu`
+/// = n, `B.Cu` last).
+#[derive(Debug, Clone, PartialEq)]
+enum Copper {
+    All,
+    Range(u32, u32),
+    Layers(Vec<u32>),
+}
+
+impl Copper {
+    fn from_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut layers = Vec::new();
+        for name in names {
+            if name == "*.Cu" {
+                return Copper::All;
+            }
+            if name == "F&B.Cu" {
+                layers.extend([0, u32::MAX]);
+            } else if let Some(rank) = copper_rank(name) {
+                layers.push(rank);
+            }
+        }
+        Copper::Layers(layers)
+    }
+
+    fn overlaps(&self, other: &Copper) -> bool {
+        match (self, other) {
+            (Copper::All, other) | (other, Copper::All) => !other.is_empty(),
+            (Copper::Range(a0, a1), Copper::Range(b0, b1)) => a0 <= b1 && b0 <= a1,
+            (Copper::Range(lo, hi), Copper::Layers(layers))
+            | (Copper::Layers(layers), Copper::Range(lo, hi)) => {
+                layers.iter().any(|layer| (*lo..=*hi).contains(layer))
+            }
+            (Copper::Layers(a), Copper::Layers(b)) => a.iter().any(|layer| b.contains(layer)),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!(self, Copper::Layers(layers) if layers.is_empty())
+    }
+}
+
+pub(crate) fn copper_rank(layer: &str) -> Option<u32> {
+    match layer {
+        "F.Cu" => Some(0),
+        "B.Cu" => Some(u32::MAX),
+        _ => layer.strip_prefix("In")?.strip_suffix(".Cu")?.parse().ok(),
+    }
+}
+
+pub(crate) type Point = (f64, f64);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    /// Via: center and radius.
+    Disc(Point, f64),
+    /// Pad: the corners of its straight-edged core, grown by a corner radius.
+    /// Circles have a single-point core; ovals a line.
+    Pad(Vec<Point>, f64),
+    /// Track: endpoints and half width.
+    Track(Point, Point, f64),
+    /// Filled zone island.
+    Polygon(Vec<Point>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    shape: Shape,
+    copper: Copper,
+    is_pad: bool,
+}
+
+impl Node {
+    fn touches(&self, other: &Node) -> bool {
+        if !self.copper.overlaps(&other.copper) {
+            return false;
+        }
+        match (&self.shape, &other.shape) {
+            (Shape::Pad(a, ra), Shape::Pad(b, rb)) => polygon_distance(a, b) <= ra + rb,
+            (Shape::Pad(core, r), Shape::Disc(p, rd))
+            | (Shape::Disc(p, rd), Shape::Pad(core, r)) => {
+                polygon_segment_distance(core, *p, *p) <= r + rd
+            }
+            (Shape::Pad(core, r), Shape::Track(a, b, w))
+            | (Shape::Track(a, b, w), Shape::Pad(core, r)) => {
+                polygon_segment_distance(core, *a, *b) <= r + w
+            }
+            (Shape::Polygon(poly), Shape::Pad(core, _))
+            | (Shape::Pad(core, _), Shape::Polygon(poly)) => {
+                point_in_polygon(polygon_center(core), poly)
+            }
+            (Shape::Disc(a, ra), Shape::Disc(b, rb)) => distance(*a, *b) <= ra + rb,
+            (Shape::Disc(p, r), Shape::Track(a, b, w))
+            | (Shape::Track(a, b, w), Shape::Disc(p, r)) => {
+                point_segment_distance(*p, *a, *b) <= r + w
+            }
+            (Shape::Track(a, b, wa), Shape::Track(c, d, wb)) => {
+                segment_distance(*a, *b, *c, *d) <= wa + wb
+            }
+            (Shape::Polygon(poly), Shape::Disc(p, _))
+            | (Shape::Disc(p, _), Shape::Polygon(poly)) => point_in_polygon(*p, poly),
+            (Shape::Polygon(poly), Shape::Track(a, b, _))
+            | (Shape::Track(a, b, _), Shape::Polygon(poly)) => {
+                point_in_polygon(*a, poly) || point_in_polygon(*b, poly)
+            }
+            (Shape::Polygon(_), Shape::Polygon(_)) => false,
+        }
+    }
+}
+
+/// Number of connected groups of nodes that contain at least one pad.
+fn pad_islands(nodes: &[Node]) -> usize {
+    let mut parent: Vec<usize> = (0..nodes.len()).collect();
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    for i in 0..nodes.len() {
+        for j in i + 1..nodes.len() {
+            if nodes[i].touches(&nodes[j]) {
+                let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+                parent[a] = b;
+            }
+        }
+    }
+    let mut roots: Vec<usize> = (0..nodes.len())
+        .filter(|&i| nodes[i].is_pad)
+        .map(|i| find(&mut parent, i))
+        .collect();
+    roots.sort_unstable();
+    roots.dedup();
+    roots.len()
+}
+
+fn collect_nodes(board: &Sexpr) -> Vec<(String, Node)> {
+    let Some(root) = board.as_list() else {
+        return Vec::new();
+    };
+
+    let net_names = net_table(root);
+    let mut nodes = Vec::new();
+    for items in root.iter().skip(1).filter_map(Sexpr::as_list) {
+        match items.first().and_then(Sexpr::as_sym) {
+            Some("footprint") => {
+                let (origin, rotation) = find_child_list(items, "at")
+                    .and_then(parse_at)
+                    .unwrap_or(((0.0, 0.0), 0.0));
+                for pad in find_all_child_lists(items, "pad") {
+                    let (Some(net), Some((local, _))) = (
+                        item_net(pad, &net_names),
+                        find_child_list(pad, "at").and_then(parse_at),
+                    ) else {
+                        continue;
+                    };
+                    // Pad orientation in a board file already includes the
+                    // footprint's rotation.
+                    let pad_rotation = find_child_list(pad, "at")
+                        .and_then(|at| at.get(3))
+                        .and_then(number_as_f64)
+                        .unwrap_or(0.0);
+                    let (core, corner) =
+                        pad_outline(pad, place(origin, rotation, local), pad_rotation);
+                    nodes.push((
+                        net,
+                        Node {
+                            shape: Shape::Pad(core, corner),
+                            copper: layer_names(pad),
+                            is_pad: true,
+                        },
+                    ));
+                }
+            }
+            Some(kind @ ("segment" | "arc")) => {
+                let Some(net) = item_net(items, &net_names) else {
+                    continue;
+                };
+                let point = |name| find_child_list(items, name).and_then(parse_xy);
+                let (Some(start), Some(end)) = (point("start"), point("end")) else {
+                    continue;
+                };
+                let half_width = find_child_list(items, "width")
+                    .and_then(|width| width.get(1))
+                    .and_then(number_as_f64)
+                    .unwrap_or(0.0)
+                    / 2.0;
+                let copper = layer_names(items);
+                let mid = if kind == "arc" { point("mid") } else { None };
+                let segments = match mid {
+                    Some(mid) => vec![(start, mid), (mid, end)],
+                    None => vec![(start, end)],
+                };
+                for (a, b) in segments {
+                    nodes.push((
+                        net.clone(),
+                        Node {
+                            shape: Shape::Track(a, b, half_width),
+                            copper: copper.clone(),
+                            is_pad: false,
+                        },
+                    ));
+                }
+            }
+            Some("via") => {
+                let (Some(net), Some((center, _))) = (
+                    item_net(items, &net_names),
+                    find_child_list(items, "at").and_then(parse_at),
+                ) else {
+                    continue;
+                };
+                let radius = find_child_list(items, "size")
+                    .and_then(|size| size.get(1))
+                    .and_then(number_as_f64)
+                    .unwrap_or(0.0)
+                    / 2.0;
+                // A via connects every copper layer between its two end layers.
+                let copper = match layer_names(items) {
+                    Copper::Layers(layers) if layers.len() == 2 => {
+                        Copper::Range(layers[0].min(layers[1]), layers[0].max(layers[1]))
+                    }
+                    _ => Copper::All,
+                };
+                nodes.push((
+                    net,
+                    Node {
+                        shape: Shape::Disc(center, radius),
+                        copper,
+                        is_pad: false,
+                    },
+                ));
+            }
+            Some("zone") => {
+                let Some(net) = item_net(items, &net_names) else {
+                    continue;
+                };
+                for filled in find_all_child_lists(items, "filled_polygon") {
+                    let Some(pts) = find_child_list(filled, "pts") else {
+                        continue;
+                    };
+                    let polygon: Vec<Point> = find_all_child_lists(pts, "xy")
+                        .into_iter()
+                        .filter_map(parse_xy)
+                        .collect();
+                    if polygon.len() < 3 {
+                        continue;
+                    }
+                    nodes.push((
+                        net.clone(),
+                        Node {
+                            shape: Shape::Polygon(polygon),
+                            copper: layer_names(filled),
+                            is_pad: false,
+                        },
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+    nodes
+}
+
+/// Core corners and corner radius of a pad centered at `center`.
+fn pad_outline(pad: &[Sexpr], center: Point, rotation: f64) -> (Vec<Point>, f64) {
+    let (w, h) = find_child_list(pad, "size")
+        .and_then(parse_xy)
+        .unwrap_or((0.0, 0.0));
+    let mut shape = pad.get(3).and_then(Sexpr::as_sym).unwrap_or("circle");
+    if shape == "custom" {
+        shape = find_child_list(pad, "options")
+            .and_then(|options| find_child_list(options, "anchor"))
+            .and_then(|anchor| anchor.get(1))
+            .and_then(Sexpr::as_sym)
+            .unwrap_or("circle");
+    }
+    let short_side = w.min(h);
+    let corner = match shape {
+        "circle" => w / 2.0,
+        "oval" => short_side / 2.0,
+        "roundrect" => {
+            let ratio = find_child_list(pad, "roundrect_rratio")
+                .and_then(|ratio| ratio.get(1))
+                .and_then(number_as_f64)
+                .unwrap_or(0.25);
+            (ratio * short_side).clamp(0.0, short_side / 2.0)
+        }
+        _ => 0.0,
+    };
+    let (hx, hy) = if shape == "circle" {
+        (0.0, 0.0)
+    } else {
+        (w / 2.0 - corner, h / 2.0 - corner)
+    };
+    let core = [(-hx, -hy), (hx, -hy), (hx, hy), (-hx, hy)]
+        .into_iter()
+        .map(|corner| place(center, rotation, corner))
+        .collect();
+    (core, corner)
+}
+
+/// Net names by number. KiCad 9 items reference nets by number through the
+/// board's net table.
+pub(crate) fn net_table(root: &[Sexpr]) -> BTreeMap<i64, &str> {
+    let mut net_names = BTreeMap::new();
+    for items in root.iter().skip(1).filter_map(Sexpr::as_list) {
+        if let (Some(number), Some(name)) =
+            (items.get(1).and_then(Sexpr::as_int), net_name_str(items))
+        {
+            net_names.insert(number, name);
+        }
+    }
+    net_names
+}
+
+/// Name of the net an item's `(net ...)` refers to.
+pub(crate) fn item_net(items: &[Sexpr], net_names: &BTreeMap<i64, &str>) -> Option<String> {
+    let net = find_child_list(items, "net")?;
+    let name = net_name_str(net).or_else(|| {
+        net.get(1)
+            .and_then(Sexpr::as_int)
+            .and_then(|number| net_names.get(&number).copied())
+    })?;
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// `(name x y ...)` as a point.
+pub(crate) fn parse_xy(list: &[Sexpr]) -> Option<Point> {
+    Some((number_as_f64(list.get(1)?)?, number_as_f64(list.get(2)?)?))
+}
+
+/// `(at x y [rotation])` as a point and rotation in degrees.
+pub(crate) fn parse_at(list: &[Sexpr]) -> Option<(Point, f64)> {
+    let rotation = list.get(3).and_then(number_as_f64).unwrap_or(0.0);
+    Some((parse_xy(list)?, rotation))
+}
+
+/// Copper layers from an item's `(layer ...)` or `(layers ...)`.
+fn layer_names(items: &[Sexpr]) -> Copper {
+    let list = find_child_list(items, "layers").or_else(|| find_child_list(items, "layer"));
+    Copper::from_names(
+        list.into_iter()
+            .flat_map(|list| list.iter().skip(1))
+            .filter_map(Sexpr::as_atom),
+    )
+}
+
+/// Board position of a footprint-local point. KiCad rotates counterclockwise
+/// on screen, with y pointing down.
+pub(crate) fn place(origin: Point, rotation_deg: f64, local: Point) -> Point {
+    let (s, c) = rotation_deg.to_radians().sin_cos();
+    (
+        origin.0 + local.0 * c + local.1 * s,
+        origin.1 - local.0 * s + local.1 * c,
+    )
+}
+
+pub(crate) fn distance(a: Point, b: Point) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+fn point_segment_distance(p: Point, a: Point, b: Point) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len_sq = dx * dx + dy * dy;
+    if len_sq == 0.0 {
+        return distance(p, a);
+    }
+    let t = (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len_sq).clamp(0.0, 1.0);
+    distance(p, (a.0 + t * dx, a.1 + t * dy))
+}
+
+fn segment_distance(a: Point, b: Point, c: Point, d: Point) -> f64 {
+    let cross =
+        |o: Point, p: Point, q: Point| (p.0 - o.0) * (q.1 - o.1) - (p.1 - o.1) * (q.0 - o.0);
+    let (d1, d2) = (cross(a, b, c), cross(a, b, d));
+    let (d3, d4) = (cross(c, d, a), cross(c, d, b));
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        return 0.0;
+    }
+    point_segment_distance(a, c, d)
+        .min(point_segment_distance(b, c, d))
+        .min(point_segment_distance(c, a, b))
+        .min(point_segment_distance(d, a, b))
+}
+
+fn polygon_edges(polygon: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+fn polygon_center(polygon: &[Point]) -> Point {
+    let n = polygon.len().max(1) as f64;
+    let (x, y) = polygon
+        .iter()
+        .fold((0.0, 0.0), |(x, y), p| (x + p.0, y + p.1));
+    (x / n, y / n)
+}
+
+/// Distance from segment `a`-`b` to a convex polygon; zero when it starts inside.
+fn polygon_segment_distance(polygon: &[Point], a: Point, b: Point) -> f64 {
+    if point_in_polygon(a, polygon) {
+        return 0.0;
+    }
+    polygon_edges(polygon)
+        .map(|(c, d)| segment_distance(a, b, c, d))
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Distance between two convex polygons; zero when they overlap.
+fn polygon_distance(a: &[Point], b: &[Point]) -> f64 {
+    if a.iter().any(|p| point_in_polygon(*p, b)) || b.iter().any(|p| point_in_polygon(*p, a)) {
+        return 0.0;
+    }
+    polygon_edges(a)
+        .map(|(p, q)| polygon_segment_distance(b, p, q))
+        .fold(f64::INFINITY, f64::min)
+}
+
+pub(crate) fn point_in_polygon(p: Point, polygon: &[Point]) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len() - 1;
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[j]);
+        if (a.1 > p.1) != (b.1 > p.1) && p.0 < (b.0 - a.0) * (p.1 - a.1) / (b.1 - a.1) + a.0 {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: &str = r#"(kicad_pcb
+        (net 0 "")
+        (net 1 "VIN")
+        (net 2 "GND")
+        (net 3 "USB_DP")
+        (footprint "R_0402" (layer "F.Cu") (at 10 10)
+            (pad "1" smd rect (at -1 0) (size 0.6 0.6) (layers "F.Cu") (net 1 "VIN"))
+            (pad "2" smd rect (at 1 0) (size 0.6 0.6) (layers "F.Cu") (net 2 "GND")))
+        (footprint "R_0402" (layer "F.Cu") (at 20 10 90)
+            (pad "1" smd rect (at -1 0) (size 0.6 0.6) (layers "F.Cu") (net 1 "VIN"))
+            (pad "2" smd rect (at 1 0) (size 0.6 0.6) (layers "F.Cu") (net 2 "GND")))
+        (footprint "TP" (layer "F.Cu") (at 30 30)
+            (pad "1" thru_hole circle (at 0 0) (size 1 1) (layers "*.Cu") (net 3 "USB_DP")))
+        (segment (start 9 10) (end 15 10) (width 0.2) (layer "F.Cu") (net 1))
+        (via (at 15 10) (size 0.6) (layers "F.Cu" "B.Cu") (net 1))
+        (segment (start 15 10) (end 20 11) (width 0.2) (layer "B.Cu") (net 1))
+        (via (at 20 11) (size 0.6) (layers "F.Cu" "B.Cu") (net 1))
+        (segment (start 11 10) (end 19 9) (width 0.2) (layer "In1.Cu") (net 2))
+    )"#;
+
+    #[test]
+    fn counts_unrouted_connections_per_net_and_class() {
+        let board = pcb_sexpr::parse(BOARD).unwrap();
+        let patterns = vec![("USB_*".to_string(), "USB".to_string())];
+        let status = routing_status(&board, &patterns);
+
+        // VIN is routed through two vias; the GND track is on an inner layer
+        // the SMD pads are not on; USB_DP has a single pad.
+        assert_eq!(
+            status.nets,
+            vec![
+                NetRouting {
+                    name: "GND".to_string(),
+                    net_class: "Default".to_string(),
+                    pads: 2,
+                    unrouted_connections: 1,
+                },
+                NetRouting {
+                    name: "VIN".to_string(),
+                    net_class: "Default".to_string(),
+                    pads: 2,
+                    unrouted_connections: 0,
+                },
+            ]
+        );
+        let classes = status.net_classes();
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0].completion_percent(), 50.0);
+        assert_eq!(
+            status
+                .unrouted_nets()
+                .map(|net| net.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["GND"]
+        );
+    }
+
+    #[test]
+    fn uses_pad_outlines_instead_of_enclosing_circles() {
+        // A 4 x 0.5 mm pad rotated 90 degrees is tall and narrow: a track
+        // ending 1.5 mm above its center lands on it, one 1.5 mm to its side
+        // does not, although both are inside the pad's enclosing circle.
+        let board = |end: &str| {
+            pcb_sexpr::parse(&format!(
+                r#"(kicad_pcb
+                    (net 1 "SIG")
+                    (footprint "A" (layer "F.Cu") (at 0 0 90)
+                        (pad "1" smd roundrect (at 0 0 90) (size 4 0.5) (roundrect_rratio 0.25)
+                            (layers "F.Cu") (net 1 "SIG")))
+                    (footprint "B" (layer "F.Cu") (at 10 0)
+                        (pad "1" smd circle (at 0 0) (size 1 1) (layers "F.Cu") (net 1 "SIG")))
+                    (segment (start 10 0) (end {end}) (width 0.1) (layer "F.Cu") (net 1))
+                )"#
+            ))
+            .unwrap()
+        };
+        let unrouted = |end| routing_status(&board(end), &[]).nets[0].unrouted_connections;
+        assert_eq!(unrouted("0 -1.5"), 0);
+        assert_eq!(unrouted("1.5 0"), 1);
+    }
+
+    #[test]
+    fn matches_netclass_wildcards() {
+        let patterns = vec![
+            ("USB_*".to_string(), "USB".to_string()),
+            ("VBUS".to_string(), "Power".to_string()),
+        ];
+        assert_eq!(net_class_for("USB_DP", &patterns), "USB");
+        assert_eq!(net_class_for("VBUS", &patterns), "Power");
+        assert_eq!(net_class_for("VBUS2", &patterns), DEFAULT_NET_CLASS);
+    }
+}
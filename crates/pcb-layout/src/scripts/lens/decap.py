@@ -0,0 +1,199 @@
+"""
+Pure functions for decoupling capacitor placement.
+
+Runs before HierPlace: a newly added decoupling capacitor whose IC is already
+on the board is snapped next to the IC power pin it bypasses, instead of being
+packed with the rest of the new items around the existing content.
+
+Association (find_decoupling_pairs):
+1. A `bypass_for` property names the IC by reference designator or path.
+2. Otherwise a capacitor with one pad on a Ground net and one on another net
+   is paired with the IC on that net sharing the deepest module path with it.
+
+Placement (snap_beside_pin): the capacitor courtyard goes outside the IC
+courtyard on the edge nearest the pin, sliding along that edge until it clears
+every other courtyard.
+"""
+
+from dataclasses import dataclass
+from typing import Dict, Iterable, List, Optional, Set, Tuple
+
+from .hierplace import Rect, rects_intersect
+from .types import BoardView, EntityId, EntityPath
+
+# Clearance between the capacitor courtyard and its neighbors
+DECAP_CLEARANCE = 250_000  # 0.25mm
+
+# Slide positions tried on each side of the IC, alternating around the pin
+MAX_SLIDE_STEPS = 8
+
+# Footprints with fewer pads are passives, not ICs to decouple
+MIN_IC_PADS = 3
+
+BYPASS_FOR_FIELD = "Bypass For"
+
+
+@dataclass(frozen=True)
+class DecouplingPair:
+    """A decoupling capacitor and the IC pad it should sit next to."""
+
+    capacitor: EntityId
+    ic: EntityId
+    net: Optional[str]
+    pad: Optional[str] = None
+
+
+def _pad_sort_key(pad: str) -> Tuple[int, object]:
+    return (0, int(pad)) if pad.isdigit() else (1, pad)
+
+
+def _common_depth(a: EntityPath, b: EntityPath) -> int:
+    depth = 0
+    for left, right in zip(a.segments, b.segments):
+        if left != right:
+            break
+        depth += 1
+    return depth
+
+
+def _pads_by_footprint(board_view: BoardView) -> Dict[EntityId, Dict[str, str]]:
+    pads: Dict[EntityId, Dict[str, str]] = {}
+    for net in board_view.nets.values():
+        if net.kind == "NotConnected":
+            continue
+        for fp_id, pad in net.connections:
+            pads.setdefault(fp_id, {})[pad] = net.name
+    return pads
+
+
+def _bypass_target(board_view: BoardView, name: str) -> Optional[EntityId]:
+    for fp_id, view in board_view.footprints.items():
+        if view.reference == name or str(fp_id.path) == name:
+            return fp_id
+    return None
+
+
+def find_decoupling_pairs(
+    board_view: BoardView,
+    candidates: Iterable[EntityId],
+    placed: Set[EntityId],
+) -> List[DecouplingPair]:
+    """Pair candidate capacitors with an already placed IC, one pad per pair.
+
+    Capacitors on the same IC net are spread over its pads on that net in
+    pad order.
+    """
+    pads = _pads_by_footprint(board_view)
+    pairs: List[DecouplingPair] = []
+
+    for cap in sorted(candidates, key=lambda e: str(e.path)):
+        view = board_view.footprints.get(cap)
+        if not view:
+            continue
+        cap_nets = set(pads.get(cap, {}).values())
+        signal_nets = sorted(
+            net for net in cap_nets if board_view.nets[net].kind != "Ground"
+        )
+
+        bypass_for = view.fields.get(BYPASS_FOR_FIELD, "").strip()
+        if bypass_for:
+            ic = _bypass_target(board_view, bypass_for)
+            if not ic or ic == cap:
+                continue
+            ic_nets = set(pads.get(ic, {}).values())
+            shared = [net for net in signal_nets if net in ic_nets]
+            pairs.append(DecouplingPair(cap, ic, shared[0] if shared else None))
+            continue
+
+        if not view.reference.startswith("C") or len(cap_nets) != 2:
+            continue
+        if len(signal_nets) != 1:
+            continue
+        net = signal_nets[0]
+        ics = [
+            fp_id
+            for fp_id, _ in board_view.nets[net].connections
+            if fp_id != cap and len(pads.get(fp_id, {})) >= MIN_IC_PADS
+        ]
+        if not ics:
+            continue
+        ic = min(ics, key=lambda e: (-_common_depth(cap.path, e.path), str(e.path)))
+        pairs.append(DecouplingPair(cap, ic, net))
+
+    pairs = [pair for pair in pairs if pair.ic in placed]
+    return _assign_pads(pairs, pads)
+
+
+def _assign_pads(
+    pairs: List[DecouplingPair], pads: Dict[EntityId, Dict[str, str]]
+) -> List[DecouplingPair]:
+    used: Dict[Tuple[EntityId, Optional[str]], int] = {}
+    assigned = []
+    for pair in pairs:
+        ic_pads = sorted(
+            (pad for pad, net in pads.get(pair.ic, {}).items() if net == pair.net),
+            key=_pad_sort_key,
+        )
+        pad = None
+        if ic_pads:
+            index = used.get((pair.ic, pair.net), 0)
+            pad = ic_pads[index % len(ic_pads)]
+            used[(pair.ic, pair.net)] = index + 1
+        assigned.append(DecouplingPair(pair.capacitor, pair.ic, pair.net, pad))
+    return assigned
+
+
+def _inflate(rect: Rect, margin: int) -> Rect:
+    return (
+        rect[0] - margin,
+        rect[1] - margin,
+        rect[2] + 2 * margin,
+        rect[3] + 2 * margin,
+    )
+
+
+def snap_beside_pin(
+    ic_rect: Rect,
+    pin: Tuple[int, int],
+    size: Tuple[int, int],
+    obstacles: List[Rect],
+    clearance: int = DECAP_CLEARANCE,
+) -> Optional[Tuple[int, int]]:
+    """Top-left position for a `size` rect next to `pin`, outside `ic_rect`.
+
+    Sides of the IC are tried nearest to the pin first. Returns None if every
+    candidate collides with the IC or an obstacle.
+    """
+    left, top, width, height = ic_rect
+    right, bottom = left + width, top + height
+    pin_x, pin_y = pin
+    w, h = size
+
+    sides = sorted(
+        [
+            (pin_x - left, "left"),
+            (right - pin_x, "right"),
+            (pin_y - top, "top"),
+            (bottom - pin_y, "bottom"),
+        ]
+    )
+    blocked = [_inflate(rect, clearance) for rect in [ic_rect, *obstacles]]
+
+    for _, side in sides:
+        if side in ("left", "right"):
+            x = left - clearance - w if side == "left" else right + clearance
+            base = (x, pin_y - h // 2)
+            step = (0, h + clearance)
+        else:
+            y = top - clearance - h if side == "top" else bottom + clearance
+            base = (pin_x - w // 2, y)
+            step = (w + clearance, 0)
+
+        for i in range(MAX_SLIDE_STEPS + 1):
+            # 0, +1, -1, +2, -2, ...
+            k = (i + 1) // 2 * (1 if i % 2 else -1)
+            x, y = base[0] + k * step[0], base[1] + k * step[1]
+            if not any(rects_intersect((x, y, w, h), rect) for rect in blocked):
+                return (x, y)
+
+    return None
@@ -9,7 +9,7 @@ This module implements the consolidated apply_changeset function that handles:
 3. View updates for existing footprints
 4. Group membership rebuild
 5. Pad-to-net assignments (creates nets on-demand)
-6. HierPlace for positioning new items
+6. Decoupling capacitor snapping, then HierPlace for positioning new items
 
 Note: Renames (moved() paths) are handled in Rust preprocessing before
 the Python sync runs. FPID changes are now handled as delete + add operations
@@ -43,6 +43,7 @@ from .hierplace import (
     pad_for_depth,
     compute_cluster_bbox,
 )
+from .decap import find_decoupling_pairs, snap_beside_pin
 from .oplog import OpLog
 from typing import TYPE_CHECKING
 from dataclasses import dataclass
@@ -1392,6 +1393,98 @@ def _pack_orphans(
     return placed, positioned
 
 
+def _get_courtyard_rect(fp: Any, pcbnew: Any) -> Optional[Rect]:
+    """Courtyard bbox of a footprint on its side, falling back to its bbox."""
+    bbox = None
+    if hasattr(fp, "BuildCourtyardCaches"):
+        fp.BuildCourtyardCaches()
+        layer = pcbnew.B_CrtYd if fp.IsFlipped() else pcbnew.F_CrtYd
+        courtyard = fp.GetCourtyard(layer)
+        if courtyard.OutlineCount() > 0:
+            bbox = courtyard.BBox()
+    if bbox is None:
+        bbox = _get_item_bbox(fp, pcbnew)
+    if not bbox or bbox.GetWidth() <= 0 or bbox.GetHeight() <= 0:
+        return None
+    return (
+        int(bbox.GetLeft()),
+        int(bbox.GetTop()),
+        int(bbox.GetWidth()),
+        int(bbox.GetHeight()),
+    )
+
+
+def _snap_decoupling_caps(
+    pending: Set[EntityId],
+    board_view: BoardView,
+    fps_by_entity_id: Dict[EntityId, Any],
+    pcbnew: Any,
+    oplog: OpLog,
+) -> Set[EntityId]:
+    """Place pending decoupling capacitors next to their already placed IC.
+
+    Runs before HierPlace; returns the capacitors it placed so HierPlace
+    skips them. Capacitors without a free spot are left to HierPlace.
+    """
+    placed_ids = set(fps_by_entity_id) - pending
+    pairs = find_decoupling_pairs(board_view, pending, placed_ids)
+    if not pairs:
+        return set()
+
+    obstacles: List[Rect] = []
+    for eid in sorted(placed_ids, key=lambda e: str(e.path)):
+        rect = _get_courtyard_rect(fps_by_entity_id[eid], pcbnew)
+        if rect:
+            obstacles.append(rect)
+
+    snapped: Set[EntityId] = set()
+    for pair in pairs:
+        cap_fp = fps_by_entity_id.get(pair.capacitor)
+        ic_fp = fps_by_entity_id.get(pair.ic)
+        if not cap_fp or not ic_fp:
+            continue
+        if cap_fp.IsFlipped() != ic_fp.IsFlipped():
+            cap_fp.Flip(cap_fp.GetPosition(), True)
+
+        ic_rect = _get_courtyard_rect(ic_fp, pcbnew)
+        cap_rect = _get_courtyard_rect(cap_fp, pcbnew)
+        if not ic_rect or not cap_rect:
+            continue
+        pin = (ic_rect[0] + ic_rect[2] // 2, ic_rect[1] + ic_rect[3] // 2)
+        for pad in ic_fp.Pads():
+            if pad.GetPadName() == pair.pad:
+                pad_pos = pad.GetPosition()
+                pin = (pad_pos.x, pad_pos.y)
+                break
+
+        target = snap_beside_pin(
+            ic_rect,
+            pin,
+            (cap_rect[2], cap_rect[3]),
+            [rect for rect in obstacles if rect != ic_rect],
+        )
+        if not target:
+            continue
+
+        pos = cap_fp.GetPosition()
+        cap_fp.SetPosition(
+            pcbnew.VECTOR2I(
+                pos.x + target[0] - cap_rect[0], pos.y + target[1] - cap_rect[1]
+            )
+        )
+        obstacles.append((target[0], target[1], cap_rect[2], cap_rect[3]))
+        snapped.add(pair.capacitor)
+        oplog.place_fp_decap(
+            str(pair.capacitor.path),
+            target[0],
+            target[1],
+            str(pair.ic.path),
+            pair.pad or "",
+        )
+
+    return snapped
+
+
 def _run_hierarchical_placement(
     changeset: "SyncChangeset",
     board_view: BoardView,
@@ -1406,6 +1499,7 @@ def _run_hierarchical_placement(
 
     Rule A: Top-most fragment wins (authoritative fragments)
     Rule B: Authoritative fragments handle all descendants
+    Decoupling capacitors of already placed ICs are snapped beside them
     Rule C: Non-fragment groups use pure bottom-up HierPlace
     Rule D: Root integration with existing content
     """
@@ -1438,6 +1532,13 @@ def _run_hierarchical_placement(
     placed += frag_placed
     exclude = inherited | fragment_fps
 
+    # Decoupling capacitors of already placed ICs go next to their power pin
+    decaps = _snap_decoupling_caps(
+        newly_added - fragment_fps, board_view, fps_by_entity_id, pcbnew, oplog
+    )
+    placed += len(decaps)
+    exclude |= decaps
+
     # Rule C & D: Build tree excluding fragment descendants
     tree = _build_group_tree(changeset, plan, exclude_footprints=exclude)
     if not tree:
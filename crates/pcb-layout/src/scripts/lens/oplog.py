@@ -29,6 +29,7 @@ OpKind = Literal[
     "PLACE_FP_INHERIT",
     "PLACE_FP_FRAGMENT",
     "PLACE_FP_ORPHAN",
+    "PLACE_FP_DECAP",
     "FRAG_IGNORED",
 ]
 
@@ -265,6 +266,28 @@ class OpLog:
             )
         )
 
+    def place_fp_decap(
+        self,
+        path: str,
+        x: int,
+        y: int,
+        ic: str,
+        pad: str,
+    ) -> None:
+        """Log decoupling capacitor snapped next to an IC pad before HierPlace."""
+        self.emit(
+            OpEvent(
+                kind="PLACE_FP_DECAP",
+                fields={
+                    "path": path,
+                    "x": x,
+                    "y": y,
+                    "ic": ic,
+                    "pad": pad,
+                },
+            )
+        )
+
     def frag_ignored(
         self,
         child_path: str,
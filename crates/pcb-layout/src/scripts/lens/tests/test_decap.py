@@ -0,0 +1,99 @@
+"""
+Tests for decoupling capacitor association and placement.
+
+Tests the pure functions in decap.py without any KiCad dependencies.
+
+Run with: pytest -v test_decap.py
+"""
+
+from ..decap import DECAP_CLEARANCE, find_decoupling_pairs, snap_beside_pin
+from ..hierplace import rects_intersect
+from ..types import BoardView, EntityId, EntityPath, FootprintView, NetView
+
+
+def make_id(path: str) -> EntityId:
+    return EntityId(path=EntityPath.from_string(path), fpid="lib:fp")
+
+
+def make_view(nets: dict, footprints: dict) -> BoardView:
+    """Build a BoardView from {net: (kind, [(path, pad)])} and {path: (ref, fields)}."""
+    fps = {
+        make_id(path): FootprintView(
+            entity_id=make_id(path),
+            reference=reference,
+            value="",
+            fpid="lib:fp",
+            fields=fields,
+        )
+        for path, (reference, fields) in footprints.items()
+    }
+    net_views = {
+        name: NetView(
+            name=name,
+            connections=tuple((make_id(path), pad) for path, pad in connections),
+            kind=kind,
+        )
+        for name, (kind, connections) in nets.items()
+    }
+    return BoardView(footprints=fps, nets=net_views)
+
+
+BOARD = make_view(
+    nets={
+        "VDD": (
+            "Power",
+            [("imu.U1", "1"), ("imu.U1", "8"), ("imu.C1", "1"), ("imu.C2", "1")],
+        ),
+        "GND": ("Ground", [("imu.U1", "2"), ("imu.C1", "2"), ("imu.C2", "2")]),
+        "SDA": ("Net", [("imu.U1", "3"), ("mcu.U2", "5")]),
+        "VIO": ("Power", [("mcu.U2", "1"), ("mcu.U2", "2"), ("mcu.C3", "1")]),
+        "VIO_GND": ("Ground", [("mcu.C3", "2")]),
+    },
+    footprints={
+        "imu.U1": ("U1", {}),
+        "mcu.U2": ("U2", {}),
+        "imu.C1": ("C1", {}),
+        "imu.C2": ("C2", {}),
+        "mcu.C3": ("C3", {"Bypass For": "U1"}),
+    },
+)
+
+
+def test_inferred_pairs_spread_over_power_pads():
+    caps = {make_id("imu.C1"), make_id("imu.C2")}
+    pairs = find_decoupling_pairs(BOARD, caps, {make_id("imu.U1"), make_id("mcu.U2")})
+
+    assert [(str(p.capacitor), str(p.ic), p.net, p.pad) for p in pairs] == [
+        ("imu.C1", "imu.U1", "VDD", "1"),
+        ("imu.C2", "imu.U1", "VDD", "8"),
+    ]
+
+
+def test_bypass_for_overrides_net_inference():
+    pairs = find_decoupling_pairs(
+        BOARD, {make_id("mcu.C3")}, {make_id("imu.U1"), make_id("mcu.U2")}
+    )
+
+    assert [(str(p.ic), p.net, p.pad) for p in pairs] == [("imu.U1", None, None)]
+
+
+def test_unplaced_ic_is_left_to_hierplace():
+    caps = {make_id("imu.C1")}
+    assert find_decoupling_pairs(BOARD, caps, {make_id("mcu.U2")}) == []
+
+
+def test_snap_uses_nearest_edge_and_clears_obstacles():
+    ic = (0, 0, 4_000_000, 4_000_000)
+    size = (1_000_000, 500_000)
+
+    # Pin near the right edge: capacitor sits right of the IC, centered on it
+    pos = snap_beside_pin(ic, (3_800_000, 1_000_000), size, [])
+    assert pos == (4_000_000 + DECAP_CLEARANCE, 750_000)
+
+    # The same spot is taken: slide along the edge instead
+    taken = (pos[0], pos[1], size[0], size[1])
+    moved = snap_beside_pin(ic, (3_800_000, 1_000_000), size, [taken])
+    assert moved is not None and moved[0] == pos[0] and moved != pos
+    cand = (moved[0], moved[1], size[0], size[1])
+    assert not rects_intersect(cand, taken)
+    assert not rects_intersect(cand, ic)
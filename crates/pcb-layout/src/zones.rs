@@ -0,0 +1,390 @@
+//! Copper pours declared by the board config's `zones`.
+//!
+//! Each declared zone is matched to an existing `(zone ...)` on the same net
+//! and single copper layer, in file order, so several zones declared on one net
+//! and layer (such as a split pour) each keep their own. Matched zones keep their outline and fill, and only
+//! the declared priority, clearance, and minimum thickness are rewritten when
+//! they differ. Missing zones are added with a rectangular outline around the
+//! `Edge.Cuts` graphics; KiCad clips the fill to the board outline. Zones that
+//! are no longer declared are left alone.
+
+use pcb_sexpr::board::net_name_str;
+use pcb_sexpr::{PatchSet, Sexpr, Span, number_as_f64};
+use pcb_zen_core::lang::stackup::ZoneConfig;
+use uuid::Uuid;
+
+use crate::{LayoutError, indent_board_item};
+
+/// Build patches that create or update the zones declared in the board config.
+pub(crate) fn build_zones_patchset(
+    board: &Sexpr,
+    zones: &[ZoneConfig],
+) -> Result<PatchSet, LayoutError> {
+    let mut patches = PatchSet::new();
+    if zones.is_empty() {
+        return Ok(patches);
+    }
+    let root_items = board.as_list().ok_or_else(|| {
+        LayoutError::StackupPatchingError("PCB root is not an S-expression list".to_string())
+    })?;
+
+    // KiCad 9 boards reference nets by number through the net table; KiCad 10
+    // boards have no table and name nets directly.
+    let net_table: Vec<(i64, &str)> = root_items
+        .iter()
+        .skip(1)
+        .filter_map(Sexpr::as_list)
+        .filter(|items| items.first().and_then(Sexpr::as_sym) == Some("net"))
+        .filter_map(|items| Some((items.get(1)?.as_int()?, net_name_str(items)?)))
+        .collect();
+
+    let mut new_zones = Vec::new();
+    let mut matched = Vec::new();
+    for (index, zone) in zones.iter().enumerate() {
+        let existing = root_items
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(i, _)| !matched.contains(i))
+            .filter_map(|(i, item)| Some((i, item.as_list()?)))
+            .find(|(_, items)| is_zone_for(items, zone, &net_table));
+        if let Some((i, existing)) = existing {
+            matched.push(i);
+            update_zone(&mut patches, existing, zone);
+            continue;
+        }
+
+        let net = if net_table.is_empty() {
+            vec![Sexpr::list(vec![
+                Sexpr::symbol("net"),
+                Sexpr::string(&zone.net),
+            ])]
+        } else {
+            let Some((number, _)) = net_table.iter().find(|(_, name)| *name == zone.net) else {
+                log::warn!("Skipping zone on unknown net {}", zone.net);
+                continue;
+            };
+            vec![
+                Sexpr::list(vec![Sexpr::symbol("net"), Sexpr::int(*number)]),
+                Sexpr::list(vec![Sexpr::symbol("net_name"), Sexpr::string(&zone.net)]),
+            ]
+        };
+        let Some(outline) = board_outline_bounds(root_items) else {
+            log::warn!(
+                "Skipping zone on {}: the board has no Edge.Cuts outline",
+                zone.net
+            );
+            continue;
+        };
+        new_zones.push(format_new_zone(index, zone, net, outline));
+    }
+
+    if !new_zones.is_empty() {
+        let at = board.span.end - 1;
+        let text: String = new_zones
+            .iter()
+            .map(String::as_str)
+            .map(indent_board_item)
+            .collect();
+        patches.replace_raw(Span::new(at, at), text);
+    }
+    Ok(patches)
+}
+
+fn child<'a>(items: &'a [Sexpr], name: &str) -> Option<&'a [Sexpr]> {
+    pcb_sexpr::find_child_list(&items[1..], name)
+}
+
+fn child_node<'a>(items: &'a [Sexpr], name: &str) -> Option<&'a Sexpr> {
+    items.iter().skip(1).find(|item| {
+        item.as_list()
+            .and_then(|list| list.first())
+            .and_then(Sexpr::as_sym)
+            == Some(name)
+    })
+}
+
+fn is_zone_for(items: &[Sexpr], zone: &ZoneConfig, net_table: &[(i64, &str)]) -> bool {
+    if items.first().and_then(Sexpr::as_sym) != Some("zone") {
+        return false;
+    }
+    let net_name = child(items, "net_name")
+        .and_then(|list| list.get(1))
+        .and_then(Sexpr::as_str)
+        .or_else(|| {
+            let net = child(items, "net")?;
+            net_name_str(net).or_else(|| {
+                let number = net.get(1)?.as_int()?;
+                net_table
+                    .iter()
+                    .find(|(n, _)| *n == number)
+                    .map(|(_, name)| *name)
+            })
+        });
+    let layers: Vec<&str> = child(items, "layer")
+        .or_else(|| child(items, "layers"))
+        .map(|list| list.iter().skip(1).filter_map(Sexpr::as_atom).collect())
+        .unwrap_or_default();
+    net_name == Some(zone.net.as_str()) && layers == [zone.layer.as_str()]
+}
+
+/// Rewrite the declared settings of an existing zone that differ.
+fn update_zone(patches: &mut PatchSet, items: &[Sexpr], zone: &ZoneConfig) {
+    if let Some(priority) = zone.priority {
+        let text = format!("(priority {priority})");
+        match child_node(items, "priority") {
+            Some(node) if value(node).and_then(Sexpr::as_int) == Some(i64::from(priority)) => {}
+            Some(node) => patches.replace_raw(node.span, text),
+            // KiCad writes the priority right after the hatch settings.
+            None => {
+                let after = child_node(items, "hatch")
+                    .or_else(|| child_node(items, "layer"))
+                    .or_else(|| child_node(items, "layers"))
+                    .map_or(items[0].span.end, |node| node.span.end);
+                patches.replace_raw(Span::new(after, after), format!(" {text}"));
+            }
+        }
+    }
+
+    if let Some(clearance) = zone.clearance {
+        let text = format_list("clearance", Sexpr::float(clearance));
+        let existing = child(items, "connect_pads").and_then(|list| child_node(list, "clearance"));
+        match (child_node(items, "connect_pads"), existing) {
+            (_, Some(node)) if same_number(node, clearance) => {}
+            (_, Some(node)) => patches.replace_raw(node.span, text),
+            (Some(node), None) => {
+                let at = node.span.end - 1;
+                patches.replace_raw(Span::new(at, at), format!(" {text}"));
+            }
+            (None, None) => {
+                let at = items[0].span.end;
+                patches.replace_raw(Span::new(at, at), format!(" (connect_pads {text})"));
+            }
+        }
+    }
+
+    if let Some(min_thickness) = zone.min_thickness {
+        let text = format_list("min_thickness", Sexpr::float(min_thickness));
+        match child_node(items, "min_thickness") {
+            Some(node) if same_number(node, min_thickness) => {}
+            Some(node) => patches.replace_raw(node.span, text),
+            None => {
+                let at = items[0].span.end;
+                patches.replace_raw(Span::new(at, at), format!(" {text}"));
+            }
+        }
+    }
+}
+
+fn value(node: &Sexpr) -> Option<&Sexpr> {
+    node.as_list()?.get(1)
+}
+
+fn same_number(node: &Sexpr, expected: f64) -> bool {
+    value(node)
+        .and_then(number_as_f64)
+        .is_some_and(|actual| (actual - expected).abs() < 1e-9)
+}
+
+fn format_list(name: &str, value: Sexpr) -> String {
+    Sexpr::list(vec![Sexpr::symbol(name), value]).to_string()
+}
+
+/// `index` is the zone's position in the config; it keeps the uuids of zones
+/// on the same net and layer apart.
+fn format_new_zone(
+    index: usize,
+    zone: &ZoneConfig,
+    net: Vec<Sexpr>,
+    (min_x, min_y, max_x, max_y): (f64, f64, f64, f64),
+) -> String {
+    let uuid = Uuid::new_v5(
+        &Uuid::NAMESPACE_URL,
+        format!("zone:{index}:{}:{}", zone.net, zone.layer).as_bytes(),
+    );
+    let list = |name: &str, values: Vec<Sexpr>| {
+        let mut items = vec![Sexpr::symbol(name)];
+        items.extend(values);
+        Sexpr::list(items)
+    };
+    let xy = |x: f64, y: f64| list("xy", vec![Sexpr::float(x), Sexpr::float(y)]);
+
+    let mut items = vec![Sexpr::symbol("zone")];
+    items.extend(net);
+    items.push(list("layer", vec![Sexpr::string(&zone.layer)]));
+    items.push(list("uuid", vec![Sexpr::string(uuid.to_string())]));
+    items.push(list(
+        "hatch",
+        vec![Sexpr::symbol("edge"), Sexpr::float(0.5)],
+    ));
+    if let Some(priority) = zone.priority {
+        items.push(list("priority", vec![Sexpr::int(i64::from(priority))]));
+    }
+    let mut connect_pads = Vec::new();
+    if let Some(clearance) = zone.clearance {
+        connect_pads.push(list("clearance", vec![Sexpr::float(clearance)]));
+    }
+    items.push(list("connect_pads", connect_pads));
+    if let Some(min_thickness) = zone.min_thickness {
+        items.push(list("min_thickness", vec![Sexpr::float(min_thickness)]));
+    }
+    items.push(list("filled_areas_thickness", vec![Sexpr::symbol("no")]));
+    items.push(list(
+        "fill",
+        vec![
+            Sexpr::symbol("yes"),
+            list("thermal_gap", vec![Sexpr::float(0.5)]),
+            list("thermal_bridge_width", vec![Sexpr::float(0.5)]),
+        ],
+    ));
+    items.push(list(
+        "polygon",
+        vec![list(
+            "pts",
+            vec![
+                xy(min_x, min_y),
+                xy(max_x, min_y),
+                xy(max_x, max_y),
+                xy(min_x, max_y),
+            ],
+        )],
+    ));
+    pcb_sexpr::formatter::format_tree(
+        &Sexpr::list(items),
+        pcb_sexpr::formatter::FormatMode::Normal,
+    )
+}
+
+/// Bounding box `(min_x, min_y, max_x, max_y)` of the board's `Edge.Cuts`
+/// graphics.
+fn board_outline_bounds(root_items: &[Sexpr]) -> Option<(f64, f64, f64, f64)> {
+    let mut points = Vec::new();
+    for items in root_items.iter().skip(1).filter_map(Sexpr::as_list) {
+        let Some(kind) = items.first().and_then(Sexpr::as_sym) else {
+            continue;
+        };
+        if !kind.starts_with("gr_") {
+            continue;
+        }
+        let on_edge_cuts = child(items, "layer")
+            .and_then(|list| list.get(1))
+            .and_then(Sexpr::as_atom)
+            == Some("Edge.Cuts");
+        if !on_edge_cuts {
+            continue;
+        }
+        let point = |name: &str| {
+            let list = child(items, name)?;
+            Some((number_as_f64(list.get(1)?)?, number_as_f64(list.get(2)?)?))
+        };
+        if kind == "gr_circle" {
+            if let (Some(center), Some(end)) = (point("center"), point("end")) {
+                let r = (end.0 - center.0).hypot(end.1 - center.1);
+                points.extend([(center.0 - r, center.1 - r), (center.0 + r, center.1 + r)]);
+            }
+            continue;
+        }
+        points.extend(["start", "mid", "end"].into_iter().filter_map(point));
+        if let Some(pts) = child(items, "pts") {
+            points.extend(
+                pcb_sexpr::find_all_child_lists(pts, "xy")
+                    .into_iter()
+                    .filter_map(|xy| {
+                        Some((number_as_f64(xy.get(1)?)?, number_as_f64(xy.get(2)?)?))
+                    }),
+            );
+        }
+    }
+
+    let first = *points.first()?;
+    Some(points.iter().fold(
+        (first.0, first.1, first.0, first.1),
+        |(min_x, min_y, max_x, max_y), &(x, y)| {
+            (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(net: &str, layer: &str) -> ZoneConfig {
+        ZoneConfig {
+            net: net.to_string(),
+            layer: layer.to_string(),
+            priority: Some(1),
+            clearance: Some(0.3),
+            min_thickness: None,
+        }
+    }
+
+    fn apply(source: &str, zones: &[ZoneConfig]) -> String {
+        let board = pcb_sexpr::parse(source).unwrap();
+        let patches = build_zones_patchset(&board, zones).unwrap();
+        crate::render_patches(source, &patches).unwrap()
+    }
+
+    const BOARD: &str = r#"(kicad_pcb
+	(net 0 "")
+	(net 1 "GND")
+	(gr_rect (start 0 0) (end 50 30) (layer "Edge.Cuts"))
+)"#;
+
+    #[test]
+    fn adds_missing_zone_once() {
+        let zones = [zone("GND", "In1.Cu")];
+        let once = apply(BOARD, &zones);
+
+        let board = pcb_sexpr::parse(&once).unwrap();
+        let added = board
+            .as_list()
+            .unwrap()
+            .iter()
+            .filter_map(Sexpr::as_list)
+            .find(|items| items.first().and_then(Sexpr::as_sym) == Some("zone"))
+            .expect("zone was added");
+        assert!(is_zone_for(added, &zones[0], &[(1, "GND")]));
+        assert_eq!(child(added, "priority").unwrap()[1].as_int(), Some(1));
+        assert!(once.contains("(xy 50 30)"));
+
+        assert_eq!(apply(&once, &zones), once);
+    }
+
+    #[test]
+    fn updates_settings_of_existing_zone_in_place() {
+        let source = r#"(kicad_pcb
+	(net 1 "GND")
+	(zone (net 1) (net_name "GND") (layer "In1.Cu") (hatch edge 0.5) (priority 0)
+		(connect_pads (clearance 0.5))
+		(polygon (pts (xy 1 1) (xy 2 1) (xy 2 2)))
+	)
+)"#;
+        let patched = apply(source, &[zone("GND", "In1.Cu")]);
+        assert!(patched.contains("(priority 1)"));
+        assert!(patched.contains("(connect_pads (clearance 0.3))"));
+        assert!(patched.contains("(polygon (pts (xy 1 1) (xy 2 1) (xy 2 2)))"));
+        assert_eq!(patched.matches("(zone").count(), 1);
+    }
+
+    #[test]
+    fn keeps_split_pours_on_one_net_and_layer_apart() {
+        let zones = [zone("GND", "In1.Cu"), zone("GND", "In1.Cu")];
+        let once = apply(BOARD, &zones);
+        assert_eq!(once.matches("(zone").count(), 2);
+
+        let board = pcb_sexpr::parse(&once).unwrap();
+        let uuids: Vec<&str> = board
+            .as_list()
+            .unwrap()
+            .iter()
+            .filter_map(Sexpr::as_list)
+            .filter(|items| items.first().and_then(Sexpr::as_sym) == Some("zone"))
+            .filter_map(|items| child(items, "uuid")?.get(1)?.as_str())
+            .collect();
+        assert_eq!(uuids.len(), 2);
+        assert_ne!(uuids[0], uuids[1]);
+
+        assert_eq!(apply(&once, &zones), once);
+    }
+}
@@ -0,0 +1,101 @@
+// Module implementing Allegro/OrCAD third-party (Telesis) net-list export for `pcb_sch::Schematic`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::Schematic;
+use crate::netlist::{footprint_name, netlist_components, netlist_nets};
+
+/// Telesis lines longer than this are continued with a trailing `,`.
+const MAX_LINE_LEN: usize = 80;
+
+/// Quote a name for Telesis, which has no escape for `'`.
+fn quote(name: &str) -> String {
+    format!("'{}'", name.replace('\'', "_"))
+}
+
+/// Write `head ; item item ...`, wrapping long lines with `,` continuations.
+fn write_wrapped(out: &mut String, head: &str, items: impl IntoIterator<Item = String>) {
+    let mut line = format!("{head} ;");
+    for item in items {
+        if line.len() + 1 + item.len() > MAX_LINE_LEN {
+            writeln!(out, "{line} ,").unwrap();
+            line = String::from(" ");
+        }
+        line.push(' ');
+        line.push_str(&item);
+    }
+    writeln!(out, "{line}").unwrap();
+}
+
+/// Export the provided [`Schematic`] as an Allegro third-party netlist.
+///
+/// Components are grouped into `$PACKAGES` lines by footprint name and value
+/// (used as the device type); `$NETS` lists each net's `REFDES.PAD` nodes.
+/// Reference designators and pads are the same as in the KiCad netlist.
+pub fn to_allegro_netlist(sch: &Schematic) -> String {
+    let mut packages: BTreeMap<(String, String), Vec<&str>> = BTreeMap::new();
+    for component in netlist_components(sch) {
+        packages
+            .entry((footprint_name(&component), component.value().into_owned()))
+            .or_default()
+            .push(component.refdes);
+    }
+
+    let mut out = String::new();
+    writeln!(out, "$PACKAGES").unwrap();
+    for ((footprint, value), refs) in packages {
+        write_wrapped(
+            &mut out,
+            &format!("{} ! {}", quote(&footprint), quote(&value)),
+            refs.into_iter().map(str::to_string),
+        );
+    }
+
+    writeln!(out, "$NETS").unwrap();
+    for net in netlist_nets(sch) {
+        if net.nodes.is_empty() {
+            continue;
+        }
+        write_wrapped(
+            &mut out,
+            &quote(&net.name),
+            net.nodes
+                .iter()
+                .map(|node| format!("{}.{}", node.refdes, node.pad)),
+        );
+    }
+    writeln!(out, "$END").unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlist::test_support::divider;
+
+    #[test]
+    fn groups_packages_and_lists_nodes() {
+        assert_eq!(
+            to_allegro_netlist(&divider()),
+            "\
+$PACKAGES
+'R_0402_1005Metric' ! '10k' ; R1 R2
+$NETS
+'GND' ; R2.2
+'OUT' ; R1.2 R2.1
+'VIN' ; R1.1
+$END
+"
+        );
+    }
+
+    #[test]
+    fn wraps_long_lines() {
+        let mut out = String::new();
+        write_wrapped(&mut out, "'N'", (1..=40).map(|i| format!("R{i}.1")));
+        assert!(out.lines().count() > 1);
+        assert!(out.lines().all(|line| line.len() <= MAX_LINE_LEN + 2));
+        assert!(out.lines().rev().skip(1).all(|line| line.ends_with(" ,")));
+    }
+}
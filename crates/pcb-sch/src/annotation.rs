@@ -0,0 +1,185 @@
+//! Human-readable design annotations attached with `Annotation()`.
+//!
+//! Each call records an `annotation.<kind>:<target>:<text>` attribute on the
+//! calling module, holding `[kind, text, target]` where `target` names a child
+//! instance of that module (or is empty for the module itself). Only the value
+//! is parsed; the key just keeps repeated calls from overwriting each other. Exporters and docs use
+//! them to carry design intent alongside generated outputs.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::{AttributeValue, Instance, InstanceKind, InstanceRef, Schematic};
+
+/// Attribute key prefix for annotations on a module instance.
+pub const ATTR_ANNOTATION_PREFIX: &str = "annotation.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnotationKind {
+    /// General design note.
+    Note,
+    /// Something a reviewer or assembler must not miss.
+    Warning,
+    /// Why a part is not populated.
+    Dnp,
+}
+
+impl FromStr for AnnotationKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "note" => Ok(Self::Note),
+            "warning" => Ok(Self::Warning),
+            "dnp" => Ok(Self::Dnp),
+            other => Err(format!("unknown annotation kind '{other}'")),
+        }
+    }
+}
+
+impl fmt::Display for AnnotationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Note => "note",
+            Self::Warning => "warning",
+            Self::Dnp => "dnp",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Annotation {
+    pub kind: AnnotationKind,
+    pub text: String,
+    /// Child instance of the annotated module, e.g. `R1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+impl Annotation {
+    /// Parse the `[kind, text, target]` attribute written by `Annotation()`.
+    pub fn from_attribute(value: &AttributeValue) -> Option<Self> {
+        let AttributeValue::Array(items) = value else {
+            return None;
+        };
+        let [kind, text, target] = items.as_slice() else {
+            return None;
+        };
+        let target = target.string()?;
+        Some(Self {
+            kind: kind.string()?.parse().ok()?,
+            text: text.string()?.to_string(),
+            target: (!target.is_empty()).then(|| target.to_string()),
+        })
+    }
+}
+
+/// Annotations declared on `instance`, in a stable order.
+pub fn annotations(instance: &Instance) -> Vec<Annotation> {
+    let mut out: Vec<Annotation> = instance
+        .attributes
+        .iter()
+        .filter(|(key, _)| key.starts_with(ATTR_ANNOTATION_PREFIX))
+        .filter_map(|(_, value)| Annotation::from_attribute(value))
+        .collect();
+    out.sort_by(|a, b| (&a.target, a.kind, &a.text).cmp(&(&b.target, b.kind, &b.text)));
+    out
+}
+
+/// An annotation resolved to the instance it describes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacedAnnotation {
+    /// The annotated instance: the target child if it exists, else the module.
+    pub instance: InstanceRef,
+    pub annotation: Annotation,
+}
+
+impl Schematic {
+    /// Annotations from every module instance, ordered by instance path.
+    pub fn annotations(&self) -> Vec<PlacedAnnotation> {
+        let mut out: Vec<PlacedAnnotation> = self
+            .instances
+            .iter()
+            .filter(|(_, inst)| inst.kind == InstanceKind::Module)
+            .flat_map(|(module_ref, module)| {
+                annotations(module).into_iter().map(move |annotation| {
+                    let instance = annotation
+                        .target
+                        .as_ref()
+                        .and_then(|target| module.children.get(target))
+                        .cloned()
+                        .unwrap_or_else(|| module_ref.clone());
+                    PlacedAnnotation {
+                        instance,
+                        annotation,
+                    }
+                })
+            })
+            .collect();
+        out.sort_by(|a, b| {
+            a.instance
+                .instance_path
+                .cmp(&b.instance.instance_path)
+                .then_with(|| a.annotation.cmp(&b.annotation))
+        });
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ModuleRef;
+
+    fn annotation_attr(kind: &str, text: &str, target: &str) -> AttributeValue {
+        AttributeValue::Array(vec![
+            AttributeValue::String(kind.to_string()),
+            AttributeValue::String(text.to_string()),
+            AttributeValue::String(target.to_string()),
+        ])
+    }
+
+    #[test]
+    fn resolves_annotations_to_target_instances() {
+        let module = ModuleRef::new("board.zen", "<root>");
+        let root_ref = InstanceRef::new(module.clone(), vec![]);
+        let r1_ref = root_ref.append("R1".to_string());
+
+        let mut root = Instance::module(module.clone())
+            .with_attribute(
+                "annotation.dnp:R1:Fit for 5V",
+                annotation_attr("dnp", "Fit for 5V", "R1"),
+            )
+            .with_attribute(
+                "annotation.note::Rev B",
+                annotation_attr("note", "Rev B", ""),
+            )
+            .with_attribute(
+                "annotation.bogus::ignored",
+                annotation_attr("bogus", "ignored", ""),
+            );
+        root.add_child("R1", r1_ref.clone());
+
+        let mut schematic = Schematic::new();
+        schematic.add_instance(root_ref.clone(), root);
+        schematic.add_instance(r1_ref.clone(), Instance::component(module));
+
+        let placed = schematic.annotations();
+        assert_eq!(placed.len(), 2);
+        assert_eq!(placed[0].instance, root_ref);
+        assert_eq!(placed[0].annotation.kind, AnnotationKind::Note);
+        assert_eq!(placed[0].annotation.target, None);
+        assert_eq!(placed[1].instance, r1_ref);
+        assert_eq!(
+            placed[1].annotation,
+            Annotation {
+                kind: AnnotationKind::Dnp,
+                text: "Fit for 5V".to_string(),
+                target: Some("R1".to_string()),
+            }
+        );
+    }
+}
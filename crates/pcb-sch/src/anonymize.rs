@@ -0,0 +1,357 @@
+//! Anonymized copies of a schematic for sharing outside the team.
+//!
+//! Connectivity, reference designators, footprints, values, and pin names are
+//! kept, so netlists of the copy still describe the same board. Everything
+//! else that names or describes the design is replaced or dropped:
+//!
+//! - Module, interface, and port instances become `M<n>`, `I<n>`, and `P<n>`;
+//!   components are named by their reference designator. Names below a
+//!   component (its pins) are kept.
+//! - Source files and module names become `anon/<n>.zen` and `Module<n>`.
+//! - Nets become `N<n>`, keeping their kind.
+//! - Footprints keep only their `.pretty` library and file name, e.g.
+//!   `Resistor_SMD.pretty/R_0402_1005Metric.kicad_mod`. The workspace path or
+//!   package URL they were loaded from is dropped, as are the package roots.
+//! - Only the attributes netlists need survive: part numbers, manufacturers,
+//!   descriptions, datasheets, notes, and layout paths are dropped, as are
+//!   symbols, symbol positions, `moved()` paths, the stamp, and the revision
+//!   history.
+//!
+//! Pseudonyms are assigned in natural path order, so anonymizing the same
+//! design twice gives the same result.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use crate::capacitance::ATTR_PIN_CAPACITANCE;
+use crate::{
+    ATTR_NET_TIE_PAD_GROUPS, ATTR_PIN_TYPES, AttributeValue, Instance, InstanceKind, InstanceRef,
+    ModuleRef, Net, Schematic, Symbol,
+};
+
+/// Component attributes kept in an anonymized schematic.
+const COMPONENT_ATTRIBUTES: &[&str] = &[
+    "footprint",
+    "value",
+    "Value",
+    "Val",
+    "type",
+    "Type",
+    "package",
+    "Package",
+    "prefix",
+    "dnp",
+    "skip_bom",
+    "skip_pos",
+    ATTR_NET_TIE_PAD_GROUPS,
+];
+
+/// Pin attributes kept in an anonymized schematic.
+const PORT_ATTRIBUTES: &[&str] = &["pads", ATTR_PIN_CAPACITANCE, ATTR_PIN_TYPES];
+
+impl Schematic {
+    /// A copy of this schematic with design names and part data removed; see
+    /// the [module docs](crate::anonymize).
+    pub fn anonymized(&self) -> Schematic {
+        let names = Pseudonyms::new(self);
+
+        let instances = self
+            .instances
+            .iter()
+            .map(|(reference, instance)| {
+                (
+                    names.instance_ref(reference),
+                    anonymize_instance(instance, &names),
+                )
+            })
+            .collect();
+
+        let nets = self
+            .nets
+            .iter()
+            .map(|(key, net)| {
+                let name = names.nets[key].clone();
+                (
+                    name.clone(),
+                    Net {
+                        kind: net.kind.clone(),
+                        id: net.id,
+                        name,
+                        ports: net.ports.iter().map(|p| names.instance_ref(p)).collect(),
+                        properties: HashMap::new(),
+                    },
+                )
+            })
+            .collect();
+
+        Schematic {
+            instances,
+            nets,
+            root_ref: self.root_ref.as_ref().map(|r| names.instance_ref(r)),
+            symbols: HashMap::new(),
+            moved_paths: HashMap::new(),
+            package_roots: BTreeMap::new(),
+            stamp: None,
+            revisions: Vec::new(),
+        }
+    }
+}
+
+fn anonymize_instance(instance: &Instance, names: &Pseudonyms) -> Instance {
+    let kept: &[&str] = match instance.kind {
+        InstanceKind::Component => COMPONENT_ATTRIBUTES,
+        InstanceKind::Port | InstanceKind::Pin => PORT_ATTRIBUTES,
+        _ => &[],
+    };
+    let attributes: HashMap<Symbol, AttributeValue> = instance
+        .attributes
+        .iter()
+        .filter(|(key, _)| kept.contains(&key.as_str()))
+        .map(|(key, value)| match value {
+            AttributeValue::String(footprint) if key == "footprint" => (
+                key.clone(),
+                AttributeValue::String(anonymize_footprint(footprint)),
+            ),
+            _ => (key.clone(), value.clone()),
+        })
+        .collect();
+    let children = instance
+        .children
+        .values()
+        .map(|child| {
+            let child = names.instance_ref(child);
+            let name = child.instance_path.last().cloned().unwrap_or_default();
+            (name, child)
+        })
+        .collect();
+    Instance {
+        type_ref: names.module_ref(&instance.type_ref),
+        kind: instance.kind,
+        attributes,
+        children,
+        reference_designator: instance.reference_designator.clone(),
+        internal_connectivity: instance.internal_connectivity.clone(),
+        symbol_positions: HashMap::new(),
+    }
+}
+
+/// Strip the directories above a footprint's `.pretty` library. `lib:name`
+/// references name no location and are kept.
+fn anonymize_footprint(footprint: &str) -> String {
+    let Some(path) = footprint.strip_suffix(".kicad_mod") else {
+        return footprint.to_string();
+    };
+    let mut segments = path.rsplit(['/', '\\']);
+    let name = segments.next().unwrap_or(path);
+    match segments.next().filter(|dir| dir.ends_with(".pretty")) {
+        Some(library) => format!("{library}/{name}.kicad_mod"),
+        None => format!("{name}.kicad_mod"),
+    }
+}
+
+struct Pseudonyms {
+    modules: HashMap<ModuleRef, ModuleRef>,
+    /// New name of the last segment of each instance path.
+    segments: HashMap<Vec<Symbol>, Symbol>,
+    nets: HashMap<String, String>,
+}
+
+impl Pseudonyms {
+    fn new(sch: &Schematic) -> Self {
+        let mut module_refs: Vec<&ModuleRef> = sch
+            .instances
+            .iter()
+            .flat_map(|(reference, instance)| [&reference.module, &instance.type_ref])
+            .chain(sch.root_ref.iter().map(|r| &r.module))
+            .collect();
+        module_refs.sort_by(|a, b| {
+            (&a.source_path, &a.module_name).cmp(&(&b.source_path, &b.module_name))
+        });
+        module_refs.dedup();
+        let modules = module_refs
+            .into_iter()
+            .enumerate()
+            .map(|(i, module)| {
+                let n = i + 1;
+                (
+                    module.clone(),
+                    ModuleRef::new(PathBuf::from(format!("anon/{n}.zen")), format!("Module{n}")),
+                )
+            })
+            .collect();
+
+        let by_path: HashMap<&[Symbol], &Instance> = sch
+            .instances
+            .iter()
+            .map(|(reference, instance)| (reference.instance_path.as_slice(), instance))
+            .collect();
+        let mut paths: Vec<&Vec<Symbol>> = sch
+            .instances
+            .keys()
+            .chain(sch.nets.values().flat_map(|net| &net.ports))
+            .map(|reference| &reference.instance_path)
+            .collect();
+        paths.sort_by(|a, b| natord::compare(&a.join("."), &b.join(".")));
+        paths.dedup();
+
+        let mut counters: BTreeMap<char, usize> = BTreeMap::new();
+        let mut segments = HashMap::new();
+        for path in paths {
+            let mut under_component = false;
+            for len in 1..=path.len() {
+                let prefix = &path[..len];
+                let instance = by_path.get(prefix).copied();
+                if !segments.contains_key(prefix) {
+                    let name = match instance {
+                        _ if under_component => prefix[len - 1].clone(),
+                        Some(instance) if instance.kind == InstanceKind::Component => instance
+                            .reference_designator
+                            .clone()
+                            .unwrap_or_else(|| next(&mut counters, 'U')),
+                        Some(instance) if instance.kind == InstanceKind::Module => {
+                            next(&mut counters, 'M')
+                        }
+                        Some(instance) if instance.kind == InstanceKind::Interface => {
+                            next(&mut counters, 'I')
+                        }
+                        _ => next(&mut counters, 'P'),
+                    };
+                    segments.insert(prefix.to_vec(), name);
+                }
+                under_component |= instance.is_some_and(|i| i.kind == InstanceKind::Component);
+            }
+        }
+
+        let mut net_names: Vec<&String> = sch.nets.keys().collect();
+        net_names.sort_by(|a, b| natord::compare(a, b));
+        let nets = net_names
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| (name.clone(), format!("N{}", i + 1)))
+            .collect();
+
+        Self {
+            modules,
+            segments,
+            nets,
+        }
+    }
+
+    fn module_ref(&self, module: &ModuleRef) -> ModuleRef {
+        self.modules[module].clone()
+    }
+
+    fn instance_ref(&self, reference: &InstanceRef) -> InstanceRef {
+        InstanceRef::new(
+            self.module_ref(&reference.module),
+            (1..=reference.instance_path.len())
+                .map(|len| self.segments[&reference.instance_path[..len]].clone())
+                .collect(),
+        )
+    }
+}
+
+fn next(counters: &mut BTreeMap<char, usize>, prefix: char) -> String {
+    let n = counters.entry(prefix).or_default();
+    *n += 1;
+    format!("{prefix}{n}")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::netlist::test_support::divider;
+    use crate::netlist::{NetlistFormat, export_netlist};
+    use crate::{AttributeValue, Instance, InstanceRef};
+
+    #[test]
+    fn keeps_connectivity_and_drops_design_names() {
+        let mut sch = divider();
+        let module = sch.instances.keys().next().unwrap().module.clone();
+        let type_ref = sch.instances.values().next().unwrap().type_ref.clone();
+        for instance in sch.instances.values_mut() {
+            if instance.reference_designator.as_deref() == Some("R1") {
+                instance
+                    .attributes
+                    .insert("mpn".into(), AttributeValue::String("ACME-SECRET-1".into()));
+            }
+        }
+        sch.add_instance(
+            InstanceRef::new(module, vec!["SecretModule".into()]),
+            Instance::module(type_ref),
+        );
+
+        let anon = sch.anonymized();
+        let netlist = export_netlist(&anon, NetlistFormat::Kicad);
+        for secret in [
+            "ACME-SECRET-1",
+            "R_TOP",
+            "SecretModule",
+            "divider.zen",
+            "VIN",
+        ] {
+            assert!(!netlist.contains(secret), "{secret} leaked");
+            assert!(!anon.to_json().unwrap().contains(secret), "{secret} leaked");
+        }
+
+        // Same pads on the same nets, under new names.
+        let connections = |sch: &crate::Schematic| {
+            let mut nets: Vec<Vec<String>> = sch
+                .nets
+                .values()
+                .map(|net| {
+                    let mut ports: Vec<String> = net
+                        .ports
+                        .iter()
+                        .map(|port| port.instance_path.join("."))
+                        .collect();
+                    ports.sort();
+                    ports
+                })
+                .collect();
+            nets.sort();
+            nets
+        };
+        assert_eq!(
+            connections(&anon),
+            vec![vec!["R1.P1"], vec!["R1.P2", "R2.P1"], vec!["R2.P2"]]
+        );
+        assert!(netlist.contains("R_0402_1005Metric"));
+        assert!(anon.nets.contains_key("N1"));
+        assert_eq!(anon.to_json().unwrap(), sch.anonymized().to_json().unwrap());
+    }
+
+    #[test]
+    fn drops_footprint_locations_and_package_roots() {
+        let mut sch = divider();
+        for instance in sch.instances.values_mut() {
+            let footprint = match instance.reference_designator.as_deref() {
+                Some("R1") => {
+                    "/home/alice/acme/components/Resistor_SMD.pretty/R_0402_1005Metric.kicad_mod"
+                }
+                Some("R2") => {
+                    "package://github.com/acme/private-parts@1.0.0/RC0603/RC0603.kicad_mod"
+                }
+                _ => continue,
+            };
+            instance
+                .attributes
+                .insert("footprint".into(), AttributeValue::String(footprint.into()));
+        }
+        sch.package_roots.insert(
+            "github.com/acme/private-parts@1.0.0".into(),
+            "/home/alice/.cache/pcb/github.com/acme/private-parts/1.0.0".into(),
+        );
+
+        let anon = sch.anonymized();
+        let json = anon.to_json().unwrap();
+        let netlist = export_netlist(&anon, NetlistFormat::Kicad);
+        for secret in ["/home/alice", "acme", "package://"] {
+            assert!(!json.contains(secret), "{secret} leaked");
+            assert!(!netlist.contains(secret), "{secret} leaked");
+        }
+        assert!(anon.package_roots.is_empty());
+        assert!(json.contains("Resistor_SMD.pretty/R_0402_1005Metric.kicad_mod"));
+        assert!(netlist.contains("Resistor_SMD:R_0402_1005Metric"));
+        assert!(json.contains("\"RC0603.kicad_mod\""));
+    }
+}
@@ -0,0 +1,282 @@
+//! Assembly BOM: one line per part, as handed to the contract manufacturer.
+//!
+//! Unlike [`Bom::grouped_json`], DNP status does not split lines. Each line
+//! lists its populated designators and its DNP designators separately, the
+//! assembly notes of its components, and, for BOMs built across variants, the
+//! variants that populate it. [`write_assembly_csv`] and
+//! [`write_assembly_markdown`] render the lines.
+
+use std::collections::{BTreeSet, HashMap};
+use std::io::{self, Write};
+
+use super::variants::Variant;
+use super::{ATTR_ASSEMBLY_NOTES, Bom, BomEntry, GroupedBomEntry};
+use crate::natural_string::NaturalString;
+
+#[derive(Default)]
+struct Line {
+    designators: BTreeSet<NaturalString>,
+    populated: BTreeSet<NaturalString>,
+    /// Indices of the variants populating the line.
+    variants: BTreeSet<usize>,
+}
+
+impl Bom {
+    /// Assembly lines of this BOM, populated lines first.
+    pub fn assembly_entries(&self) -> Vec<GroupedBomEntry> {
+        assembly_entries(&[self], &[])
+    }
+}
+
+/// Assembly lines across the BOMs of several variants.
+///
+/// A designator is DNP on a line when no variant populates it with that part.
+pub fn variant_assembly_entries(boms: &[(Variant, Bom)]) -> Vec<GroupedBomEntry> {
+    let names: Vec<&str> = boms
+        .iter()
+        .map(|(variant, _)| variant.name.as_str())
+        .collect();
+    let boms: Vec<&Bom> = boms.iter().map(|(_, bom)| bom).collect();
+    assembly_entries(&boms, &names)
+}
+
+fn assembly_entries(boms: &[&Bom], variant_names: &[&str]) -> Vec<GroupedBomEntry> {
+    let mut lines: HashMap<BomEntry, Line> = HashMap::new();
+    for (index, bom) in boms.iter().enumerate() {
+        for (path, entry) in &bom.entries {
+            let designator: NaturalString = bom.designators[path].clone().into();
+            let line = lines
+                .entry(BomEntry {
+                    dnp: false,
+                    ..entry.clone()
+                })
+                .or_default();
+            line.designators.insert(designator.clone());
+            if !entry.dnp {
+                line.populated.insert(designator);
+                line.variants.insert(index);
+            }
+        }
+    }
+
+    let mut entries: Vec<GroupedBomEntry> = lines
+        .into_iter()
+        .map(|(mut entry, line)| {
+            entry.dnp = line.populated.is_empty();
+            let variants = if line.variants.is_empty() || line.variants.len() == boms.len() {
+                Vec::new()
+            } else {
+                line.variants
+                    .iter()
+                    .filter_map(|&index| variant_names.get(index))
+                    .map(|name| name.to_string())
+                    .collect()
+            };
+            GroupedBomEntry {
+                dnp_designators: line
+                    .designators
+                    .difference(&line.populated)
+                    .cloned()
+                    .collect(),
+                designators: line.designators,
+                variants,
+                entry,
+            }
+        })
+        .collect();
+    entries = Bom::consolidate_generic_entries(entries);
+    entries.sort_by(|a, b| {
+        a.entry.dnp.cmp(&b.entry.dnp).then_with(|| {
+            a.designators
+                .iter()
+                .next()
+                .cmp(&b.designators.iter().next())
+        })
+    });
+    entries
+}
+
+const HEADER: [&str; 8] = [
+    "Qty",
+    "Designators",
+    "DNP",
+    "Value",
+    "MPN",
+    "Manufacturer",
+    "Package",
+    "Description",
+];
+
+/// Cells of one line, in [`HEADER`] order followed by the optional variant
+/// column and the assembly notes.
+fn row(line: &GroupedBomEntry, with_variants: bool) -> Vec<String> {
+    let populated = line.designators.len() - line.dnp_designators.len();
+    let entry = &line.entry;
+    let mut cells = vec![
+        populated.to_string(),
+        join(line.designators.difference(&line.dnp_designators)),
+        join(line.dnp_designators.iter()),
+        entry.value.clone().unwrap_or_default(),
+        entry.mpn.clone().unwrap_or_default(),
+        entry.manufacturer.clone().unwrap_or_default(),
+        entry.package.clone().unwrap_or_default(),
+        entry.description.clone().unwrap_or_default(),
+    ];
+    if with_variants {
+        cells.push(match (entry.dnp, line.variants.is_empty()) {
+            (true, _) => String::new(),
+            (false, true) => "all".to_string(),
+            (false, false) => line.variants.join(", "),
+        });
+    }
+    cells.push(
+        entry
+            .properties
+            .get(ATTR_ASSEMBLY_NOTES)
+            .cloned()
+            .unwrap_or_default(),
+    );
+    cells
+}
+
+fn join<'a>(designators: impl Iterator<Item = &'a NaturalString>) -> String {
+    designators
+        .map(|d| d.as_ref())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn header(with_variants: bool) -> Vec<&'static str> {
+    let mut header = HEADER.to_vec();
+    if with_variants {
+        header.push("Variants");
+    }
+    header.push("Assembly Notes");
+    header
+}
+
+/// Write assembly lines as CSV. `with_variants` adds a `Variants` column.
+pub fn write_assembly_csv<W: Write>(
+    lines: &[GroupedBomEntry],
+    with_variants: bool,
+    writer: W,
+) -> io::Result<()> {
+    let mut csv = csv::Writer::from_writer(writer);
+    csv.write_record(header(with_variants))?;
+    for line in lines {
+        csv.write_record(row(line, with_variants))?;
+    }
+    csv.flush()
+}
+
+/// Write assembly lines as a Markdown table. `with_variants` adds a
+/// `Variants` column.
+pub fn write_assembly_markdown<W: Write>(
+    lines: &[GroupedBomEntry],
+    with_variants: bool,
+    mut writer: W,
+) -> io::Result<()> {
+    let header = header(with_variants);
+    writeln!(writer, "| {} |", header.join(" | "))?;
+    writeln!(writer, "|{}", "---|".repeat(header.len()))?;
+    for line in lines {
+        let cells: Vec<String> = row(line, with_variants)
+            .iter()
+            .map(|cell| cell.replace('|', "\\|").replace('\n', " "))
+            .collect();
+        writeln!(writer, "| {} |", cells.join(" | "))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use super::*;
+
+    fn bom(entries: &[(&str, &str, bool, Option<&str>)]) -> Bom {
+        let mut bom_entries = HashMap::new();
+        let mut designators = HashMap::new();
+        for (designator, mpn, dnp, notes) in entries {
+            let properties = notes
+                .map(|notes| BTreeMap::from([(ATTR_ASSEMBLY_NOTES.to_string(), notes.to_string())]))
+                .unwrap_or_default();
+            bom_entries.insert(
+                designator.to_string(),
+                BomEntry {
+                    mpn: Some(mpn.to_string()),
+                    alternatives: vec![],
+                    manufacturer: None,
+                    package: None,
+                    value: None,
+                    description: None,
+                    generic_data: None,
+                    dnp: *dnp,
+                    skip_bom: false,
+                    matcher: None,
+                    properties,
+                },
+            );
+            designators.insert(designator.to_string(), designator.to_string());
+        }
+        Bom::new(bom_entries, designators)
+    }
+
+    #[test]
+    fn dnp_designators_share_a_line() {
+        let bom = bom(&[
+            ("R1", "RC0402", false, None),
+            ("R2", "RC0402", true, None),
+            (
+                "J1",
+                "USB-C",
+                false,
+                Some("Hand solder, check | orientation"),
+            ),
+            ("TP1", "TP", true, None),
+        ]);
+        let mut csv = Vec::new();
+        write_assembly_csv(&bom.assembly_entries(), false, &mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "\
+Qty,Designators,DNP,Value,MPN,Manufacturer,Package,Description,Assembly Notes
+1,J1,,,USB-C,,,,\"Hand solder, check | orientation\"
+1,R1,R2,,RC0402,,,,
+0,,TP1,,TP,,,,
+"
+        );
+
+        let mut markdown = Vec::new();
+        write_assembly_markdown(&bom.assembly_entries()[..1], false, &mut markdown).unwrap();
+        assert!(
+            String::from_utf8(markdown).unwrap().ends_with(
+                "| 1 | J1 |  |  | USB-C |  |  |  | Hand solder, check \\| orientation |\n"
+            )
+        );
+    }
+
+    #[test]
+    fn lines_record_populating_variants() {
+        let variant = |name: &str| Variant {
+            name: name.to_string(),
+            config: BTreeMap::new(),
+        };
+        let lines = variant_assembly_entries(&[
+            (
+                variant("lite"),
+                bom(&[("U1", "MCU", false, None), ("U2", "RADIO", true, None)]),
+            ),
+            (
+                variant("pro"),
+                bom(&[("U1", "MCU", false, None), ("U2", "RADIO", false, None)]),
+            ),
+        ]);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].variants.is_empty());
+        assert_eq!(lines[1].variants, ["pro"]);
+        assert!(lines[1].dnp_designators.is_empty());
+        assert_eq!(row(&lines[1], true)[8], "pro");
+    }
+}
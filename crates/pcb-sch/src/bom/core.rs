@@ -4,8 +4,12 @@ use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 
 use crate::natural_string::NaturalString;
+use crate::values::normalize_value;
 use crate::{InstanceKind, PhysicalValue, Schematic};
 
+/// Component attribute, and BOM property, holding assembly notes.
+pub const ATTR_ASSEMBLY_NOTES: &str = "assembly_notes";
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Bom {
     pub entries: HashMap<String, BomEntry>,   // path -> BomEntry
@@ -27,6 +31,25 @@ fn trim_description(s: Option<String>) -> Option<String> {
     .filter(|s| !s.is_empty())
 }
 
+/// BOM properties recording a flagged lifecycle status (NRND, deprecated, EOL)
+/// and assembly notes.
+fn bom_properties(instance: &crate::Instance) -> BTreeMap<String, String> {
+    let mut properties = BTreeMap::new();
+    if let Some(lifecycle) = instance
+        .lifecycle()
+        .filter(|lifecycle| lifecycle.is_flagged())
+    {
+        properties.insert(
+            crate::lifecycle::ATTR_LIFECYCLE.to_string(),
+            lifecycle.to_string(),
+        );
+    }
+    if let Some(notes) = instance.assembly_notes() {
+        properties.insert(ATTR_ASSEMBLY_NOTES.to_string(), notes);
+    }
+    properties
+}
+
 /// Check if optional constraint A meets or exceeds B's requirement
 /// Returns true if A is compatible with B (A can replace B)
 fn meets_or_exceeds<T>(a: &Option<T>, b: &Option<T>, cmp: impl Fn(&T, &T) -> bool) -> bool {
@@ -53,15 +76,45 @@ fn merge_designators(target: &mut GroupedBomEntry, src: &GroupedBomEntry) {
         target.entry.description.as_deref().unwrap_or("?")
     );
     target.designators.extend(src.designators.iter().cloned());
+    target
+        .dnp_designators
+        .extend(src.dnp_designators.iter().cloned());
+    if src.variants.is_empty() {
+        target.variants.clear();
+    } else if !target.variants.is_empty() {
+        for variant in &src.variants {
+            if !target.variants.contains(variant) {
+                target.variants.push(variant.clone());
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GroupedBomEntry {
     pub designators: BTreeSet<NaturalString>,
+    /// Designators of this line that are not populated.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub dnp_designators: BTreeSet<NaturalString>,
+    /// Variants that populate this line; empty when it applies to every
+    /// variant or the BOM was built without variants.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<String>,
     #[serde(flatten)]
     pub entry: BomEntry,
 }
 
+impl GroupedBomEntry {
+    pub fn new(entry: BomEntry, designators: BTreeSet<NaturalString>) -> Self {
+        Self {
+            designators,
+            dnp_designators: BTreeSet::new(),
+            variants: Vec::new(),
+            entry,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Alternative {
     pub mpn: String,
@@ -352,13 +405,15 @@ impl Bom {
                     manufacturer: instance.manufacturer(),
                     description: trim_description(instance.description()),
                     package: instance.package(),
-                    value: instance.value(),
+                    value: instance
+                        .value()
+                        .map(|value| normalize_value(&value).into_owned()),
                     alternatives: instance.alternatives_attr(),
                     generic_data: detect_generic_component(instance),
                     dnp: instance.dnp(),
                     skip_bom: instance.skip_bom(),
                     matcher: instance.matcher(),
-                    properties: BTreeMap::new(),
+                    properties: bom_properties(instance),
                 };
                 entries.insert(path.clone(), bom_entry);
                 designators.insert(path, designator);
@@ -402,7 +457,7 @@ impl Bom {
         // Convert to vec
         let mut grouped_entries = groups
             .into_iter()
-            .map(|(entry, designators)| GroupedBomEntry { entry, designators })
+            .map(|(entry, designators)| GroupedBomEntry::new(entry, designators))
             .collect::<Vec<_>>();
 
         grouped_entries.sort_by(|a, b| {
@@ -1119,14 +1174,8 @@ mod tests {
         };
 
         let entries = vec![
-            GroupedBomEntry {
-                entry: cap_10v,
-                designators: BTreeSet::from(["C1".into(), "C2".into()]),
-            },
-            GroupedBomEntry {
-                entry: cap_no_voltage,
-                designators: BTreeSet::from(["C14".into(), "C15".into()]),
-            },
+            GroupedBomEntry::new(cap_10v, BTreeSet::from(["C1".into(), "C2".into()])),
+            GroupedBomEntry::new(cap_no_voltage, BTreeSet::from(["C14".into(), "C15".into()])),
         ];
 
         let consolidated = Bom::consolidate_generic_entries(entries);
@@ -1198,14 +1247,8 @@ mod tests {
         };
 
         let entries = vec![
-            GroupedBomEntry {
-                entry: res_100v,
-                designators: BTreeSet::from(["R1".into()]),
-            },
-            GroupedBomEntry {
-                entry: res_no_voltage,
-                designators: BTreeSet::from(["R2".into(), "R3".into()]),
-            },
+            GroupedBomEntry::new(res_100v, BTreeSet::from(["R1".into()])),
+            GroupedBomEntry::new(res_no_voltage, BTreeSet::from(["R2".into(), "R3".into()])),
         ];
 
         let consolidated = Bom::consolidate_generic_entries(entries);
@@ -1260,14 +1303,8 @@ mod tests {
         };
 
         let entries = vec![
-            GroupedBomEntry {
-                entry: cap_0402,
-                designators: BTreeSet::from(["C1".into()]),
-            },
-            GroupedBomEntry {
-                entry: cap_0603,
-                designators: BTreeSet::from(["C2".into()]),
-            },
+            GroupedBomEntry::new(cap_0402, BTreeSet::from(["C1".into()])),
+            GroupedBomEntry::new(cap_0603, BTreeSet::from(["C2".into()])),
         ];
 
         let consolidated = Bom::consolidate_generic_entries(entries);
@@ -1304,14 +1341,8 @@ mod tests {
         };
 
         let entries = vec![
-            GroupedBomEntry {
-                entry: cap_normal,
-                designators: BTreeSet::from(["C1".into()]),
-            },
-            GroupedBomEntry {
-                entry: cap_dnp,
-                designators: BTreeSet::from(["C2".into()]),
-            },
+            GroupedBomEntry::new(cap_normal, BTreeSet::from(["C1".into()])),
+            GroupedBomEntry::new(cap_dnp, BTreeSet::from(["C2".into()])),
         ];
 
         let consolidated = Bom::consolidate_generic_entries(entries);
@@ -1362,14 +1393,8 @@ mod tests {
         };
 
         let entries = vec![
-            GroupedBomEntry {
-                entry: cap_a,
-                designators: BTreeSet::from(["C1".into()]),
-            },
-            GroupedBomEntry {
-                entry: cap_b,
-                designators: BTreeSet::from(["C2".into()]),
-            },
+            GroupedBomEntry::new(cap_a, BTreeSet::from(["C1".into()])),
+            GroupedBomEntry::new(cap_b, BTreeSet::from(["C2".into()])),
         ];
 
         let consolidated = Bom::consolidate_generic_entries(entries);
@@ -1420,14 +1445,8 @@ mod tests {
         };
 
         let entries = vec![
-            GroupedBomEntry {
-                entry: cap_10v_no_mpn,
-                designators: BTreeSet::from(["C1".into()]),
-            },
-            GroupedBomEntry {
-                entry: cap_no_voltage,
-                designators: BTreeSet::from(["C2".into()]),
-            },
+            GroupedBomEntry::new(cap_10v_no_mpn, BTreeSet::from(["C1".into()])),
+            GroupedBomEntry::new(cap_no_voltage, BTreeSet::from(["C2".into()])),
         ];
 
         let consolidated = Bom::consolidate_generic_entries(entries);
@@ -1478,14 +1497,8 @@ mod tests {
         };
 
         let entries = vec![
-            GroupedBomEntry {
-                entry: cap_x7r,
-                designators: BTreeSet::from(["C1".into()]),
-            },
-            GroupedBomEntry {
-                entry: cap_no_dielec,
-                designators: BTreeSet::from(["C2".into()]),
-            },
+            GroupedBomEntry::new(cap_x7r, BTreeSet::from(["C1".into()])),
+            GroupedBomEntry::new(cap_no_dielec, BTreeSet::from(["C2".into()])),
         ];
 
         let consolidated = Bom::consolidate_generic_entries(entries);
@@ -1515,14 +1528,8 @@ mod tests {
         };
 
         let entries = vec![
-            GroupedBomEntry {
-                entry: entry_a.clone(),
-                designators: BTreeSet::from(["U1".into()]),
-            },
-            GroupedBomEntry {
-                entry: entry_a.clone(),
-                designators: BTreeSet::from(["U2".into()]),
-            },
+            GroupedBomEntry::new(entry_a.clone(), BTreeSet::from(["U1".into()])),
+            GroupedBomEntry::new(entry_a.clone(), BTreeSet::from(["U2".into()])),
         ];
 
         let consolidated = Bom::consolidate_generic_entries(entries);
@@ -1570,14 +1577,8 @@ mod tests {
         };
 
         let entries = vec![
-            GroupedBomEntry {
-                entry: res_tight,
-                designators: BTreeSet::from(["R1".into()]),
-            },
-            GroupedBomEntry {
-                entry: res_loose,
-                designators: BTreeSet::from(["R2".into(), "R3".into()]),
-            },
+            GroupedBomEntry::new(res_tight, BTreeSet::from(["R1".into()])),
+            GroupedBomEntry::new(res_loose, BTreeSet::from(["R2".into(), "R3".into()])),
         ];
 
         let consolidated = Bom::consolidate_generic_entries(entries);
@@ -1630,14 +1631,8 @@ mod tests {
         };
 
         let entries = vec![
-            GroupedBomEntry {
-                entry: cap_tight,
-                designators: BTreeSet::from(["C1".into()]),
-            },
-            GroupedBomEntry {
-                entry: cap_loose,
-                designators: BTreeSet::from(["C2".into()]),
-            },
+            GroupedBomEntry::new(cap_tight, BTreeSet::from(["C1".into()])),
+            GroupedBomEntry::new(cap_loose, BTreeSet::from(["C2".into()])),
         ];
 
         let consolidated = Bom::consolidate_generic_entries(entries);
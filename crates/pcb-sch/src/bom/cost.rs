@@ -0,0 +1,140 @@
+//! Parts cost of one board, as checked against `[board.budget]`.
+//!
+//! Identical parts are ordered together, so each BOM line is priced at its
+//! per-board quantity times the number of boards being built.
+
+use std::collections::HashMap;
+
+use super::{AvailabilitySummary, Bom, BomEntry};
+use crate::bom_table::unit_price_from_breaks;
+
+/// Parts cost of a single board.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BoardCost {
+    /// Sum of known prices of populated components, in USD.
+    pub cost: f64,
+    /// Populated components without a known price, not included in `cost`.
+    pub unpriced: usize,
+}
+
+impl Bom {
+    /// Number of distinct parts populated on the board.
+    pub fn unique_parts(&self) -> usize {
+        self.assembly_entries()
+            .iter()
+            .filter(|line| !line.entry.dnp)
+            .count()
+    }
+
+    /// Parts cost of one board when buying for `boards` boards, preferring US
+    /// offers.
+    pub fn board_cost(&self, boards: u32) -> BoardCost {
+        let mut lines: HashMap<&BomEntry, Vec<&str>> = HashMap::new();
+        for (path, entry) in &self.entries {
+            if !entry.dnp {
+                lines.entry(entry).or_default().push(path);
+            }
+        }
+
+        let mut total = BoardCost::default();
+        for paths in lines.values() {
+            let quantity = paths.len() as i32;
+            let order_quantity = quantity.saturating_mul(boards.max(1) as i32);
+            match paths
+                .iter()
+                .find_map(|path| self.unit_price_at(path, order_quantity))
+            {
+                Some(price) => total.cost += price * quantity as f64,
+                None => total.unpriced += paths.len(),
+            }
+        }
+        total
+    }
+
+    fn unit_price_at(&self, path: &str, quantity: i32) -> Option<f64> {
+        let availability = self.availability.get(path)?;
+        let price = |summary: &AvailabilitySummary| {
+            summary
+                .price_breaks
+                .as_deref()
+                .and_then(|breaks| unit_price_from_breaks(breaks, quantity))
+                .or(summary.price)
+        };
+        availability
+            .us
+            .as_ref()
+            .and_then(price)
+            .or_else(|| availability.global.as_ref().and_then(price))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::bom::Availability;
+
+    fn entry(mpn: &str, dnp: bool) -> BomEntry {
+        BomEntry {
+            mpn: Some(mpn.to_owned()),
+            alternatives: Vec::new(),
+            manufacturer: None,
+            package: None,
+            value: None,
+            description: None,
+            generic_data: None,
+            dnp,
+            skip_bom: false,
+            matcher: None,
+            properties: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn prices_lines_at_build_quantity() {
+        let mut entries = HashMap::new();
+        let mut designators = HashMap::new();
+        for (path, refdes, mpn, dnp) in [
+            ("C1", "C1", "CAP", false),
+            ("C2", "C2", "CAP", false),
+            ("U1", "U1", "MCU", false),
+            ("J1", "J1", "CONN", false),
+            ("U2", "U2", "LNA", true),
+        ] {
+            entries.insert(path.to_owned(), entry(mpn, dnp));
+            designators.insert(path.to_owned(), refdes.to_owned());
+        }
+        let mut bom = Bom::new(entries, designators);
+        bom.availability.insert(
+            "C1".to_owned(),
+            Availability {
+                us: Some(AvailabilitySummary {
+                    price_breaks: Some(vec![(1, 0.10), (100, 0.01)]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        );
+        bom.availability.insert(
+            "U1".to_owned(),
+            Availability {
+                global: Some(AvailabilitySummary {
+                    price: Some(2.0),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(bom.unique_parts(), 3);
+
+        let single = bom.board_cost(1);
+        assert!((single.cost - 2.2).abs() < 1e-9);
+        assert_eq!(single.unpriced, 1);
+
+        // Two capacitors per board reach the 100-piece break at 50 boards.
+        let batch = bom.board_cost(50);
+        assert!((batch.cost - 2.02).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,215 @@
+//! Part-level changes between two released BOMs.
+//!
+//! Releases store their BOM as `bom/design_bom.json`, one line per
+//! designator. [`diff_released_boms`] lines two of them up by designator and
+//! reports every populated part that was added, removed, or substituted, so the
+//! delta can be reviewed before a new release goes out.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::natural_string::NaturalString;
+
+/// The fields of a `design_bom.json` line that identify the fitted part.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ReleasedBomLine {
+    pub designator: String,
+    #[serde(default)]
+    pub mpn: Option<String>,
+    #[serde(default)]
+    pub manufacturer: Option<String>,
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub package: Option<String>,
+    #[serde(default)]
+    pub dnp: bool,
+    #[serde(default)]
+    pub skip_bom: bool,
+}
+
+impl ReleasedBomLine {
+    /// Human-readable part: `Manufacturer MPN` when sourced, otherwise the
+    /// value and package.
+    pub fn part(&self) -> String {
+        let fields = match &self.mpn {
+            Some(mpn) => [self.manufacturer.as_deref(), Some(mpn.as_str())],
+            None => [self.value.as_deref(), self.package.as_deref()],
+        };
+        let part = fields.into_iter().flatten().collect::<Vec<_>>().join(" ");
+        if part.is_empty() {
+            "(unspecified)".to_string()
+        } else {
+            part
+        }
+    }
+
+    fn is_fitted(&self) -> bool {
+        !self.dnp && !self.skip_bom
+    }
+}
+
+/// Parse the contents of a release's `bom/design_bom.json`.
+pub fn parse_released_bom(json: &str) -> serde_json::Result<Vec<ReleasedBomLine>> {
+    serde_json::from_str(json)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BomChangeKind {
+    Added,
+    Removed,
+    Substituted,
+}
+
+/// One designator whose fitted part changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BomChange {
+    pub designator: String,
+    pub kind: BomChangeKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+/// Changes between two released BOMs, ordered by designator.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BomDiff {
+    pub changes: Vec<BomChange>,
+}
+
+/// Compare the fitted parts of `before` and `after` by designator.
+///
+/// DNP and BOM-excluded lines count as not fitted, so populating or
+/// depopulating a footprint shows up as an addition or removal.
+pub fn diff_released_boms(before: &[ReleasedBomLine], after: &[ReleasedBomLine]) -> BomDiff {
+    let fitted = |lines: &[ReleasedBomLine]| {
+        lines
+            .iter()
+            .filter(|line| line.is_fitted())
+            .map(|line| (NaturalString::from(line.designator.clone()), line.part()))
+            .collect::<BTreeMap<_, _>>()
+    };
+    let before = fitted(before);
+    let mut after = fitted(after);
+
+    let mut changes = Vec::new();
+    for (designator, old_part) in before {
+        let (kind, new_part) = match after.remove(&designator) {
+            Some(new_part) if new_part == old_part => continue,
+            Some(new_part) => (BomChangeKind::Substituted, Some(new_part)),
+            None => (BomChangeKind::Removed, None),
+        };
+        changes.push((designator, kind, Some(old_part), new_part));
+    }
+    for (designator, new_part) in after {
+        changes.push((designator, BomChangeKind::Added, None, Some(new_part)));
+    }
+    changes.sort_by(|a, b| a.0.cmp(&b.0));
+
+    BomDiff {
+        changes: changes
+            .into_iter()
+            .map(|(designator, kind, before, after)| BomChange {
+                designator: designator.to_string(),
+                kind,
+                before,
+                after,
+            })
+            .collect(),
+    }
+}
+
+impl BomDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn count(&self, kind: BomChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+
+    /// One-line summary, e.g. `2 added, 1 removed, 0 substituted`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} added, {} removed, {} substituted",
+            self.count(BomChangeKind::Added),
+            self.count(BomChangeKind::Removed),
+            self.count(BomChangeKind::Substituted)
+        )
+    }
+
+    /// Markdown section for release notes, comparing against `baseline`.
+    pub fn to_markdown(&self, baseline: &str) -> String {
+        let mut out = format!("## BOM changes since {baseline}\n\n");
+        if self.is_empty() {
+            out.push_str("No parts added, removed, or substituted.\n");
+            return out;
+        }
+        out.push_str(&format!("{}.\n\n", self.summary()));
+        out.push_str("| Designator | Change | Before | After |\n");
+        out.push_str("|---|---|---|---|\n");
+        for change in &self.changes {
+            let kind = match change.kind {
+                BomChangeKind::Added => "added",
+                BomChangeKind::Removed => "removed",
+                BomChangeKind::Substituted => "substituted",
+            };
+            out.push_str(&format!(
+                "| {} | {kind} | {} | {} |\n",
+                change.designator,
+                change.before.as_deref().unwrap_or(""),
+                change.after.as_deref().unwrap_or("")
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_added_removed_and_substituted_parts() {
+        let before = parse_released_bom(
+            r#"[
+                {"path": "R1", "designator": "R1", "value": "10k", "package": "0402"},
+                {"path": "R2", "designator": "R2", "value": "1k", "package": "0402"},
+                {"path": "U1", "designator": "U1", "mpn": "LM358", "manufacturer": "TI"},
+                {"path": "C10", "designator": "C10", "value": "1uF", "dnp": true}
+            ]"#,
+        )
+        .unwrap();
+        let after = parse_released_bom(
+            r#"[
+                {"path": "R1", "designator": "R1", "value": "10k", "package": "0402"},
+                {"path": "U1", "designator": "U1", "mpn": "LM358B", "manufacturer": "TI"},
+                {"path": "C10", "designator": "C10", "value": "1uF"},
+                {"path": "TP1", "designator": "TP1", "skip_bom": true}
+            ]"#,
+        )
+        .unwrap();
+
+        let diff = diff_released_boms(&before, &after);
+        assert_eq!(diff.summary(), "1 added, 1 removed, 1 substituted");
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(|c| (c.designator.as_str(), c.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                ("C10", BomChangeKind::Added),
+                ("R2", BomChangeKind::Removed),
+                ("U1", BomChangeKind::Substituted),
+            ]
+        );
+        assert!(
+            diff.to_markdown("v1.0.0")
+                .contains("| U1 | substituted | TI LM358 | TI LM358B |")
+        );
+        assert!(diff_released_boms(&after, &after).is_empty());
+    }
+}
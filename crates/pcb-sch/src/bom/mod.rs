@@ -1,8 +1,17 @@
+pub mod assembly;
 pub mod availability;
 mod core;
+pub mod cost;
+pub mod diff;
+pub mod rules;
+pub mod subassembly;
+pub mod variants;
 
 // Re-export core BOM types
+pub use assembly::{variant_assembly_entries, write_assembly_csv, write_assembly_markdown};
 pub use core::*;
+pub use cost::BoardCost;
+pub use subassembly::{SubassemblyBom, TOP_LEVEL_SUBASSEMBLY};
 
 // Re-export availability types and helpers
 pub use availability::{
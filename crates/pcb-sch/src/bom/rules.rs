@@ -0,0 +1,259 @@
+//! Approved manufacturer alternates kept in a `bom-rules.json` sidecar.
+//!
+//! The sidecar sits next to a board's `.zen` file and holds
+//! [`BomMatchingRule`]s, the same format `pcb ipc2581 edit bom --rules`
+//! reads. Rules add alternates to matching BOM entries without editing the
+//! design, so that alternates approved by a contract manufacturer survive
+//! regeneration. [`imported_alternates`] builds such rules from a BOM read
+//! back from the CM (e.g. an IPC-2581 BOM section).
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::{Alternative, ApprovedSource, Bom, BomEntry, BomMatchingKey, BomMatchingRule};
+use crate::natural_string::NaturalString;
+
+/// File name of the BOM rules sidecar next to a board's `.zen` file.
+pub const BOM_RULES_FILE: &str = "bom-rules.json";
+
+/// Read BOM rules from `path`. A missing file has no rules.
+pub fn load_rules(path: &Path) -> Result<Vec<BomMatchingRule>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Write BOM rules to `path` as pretty-printed JSON.
+pub fn write_rules(path: &Path, rules: &[BomMatchingRule]) -> Result<()> {
+    let json = serde_json::to_string_pretty(rules)?;
+    std::fs::write(path, format!("{json}\n"))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Alternate described by a rule source, if it names both manufacturer and MPN.
+fn source_alternative(source: &ApprovedSource) -> Option<Alternative> {
+    Some(Alternative {
+        mpn: source.manufacturer_pn.clone()?,
+        manufacturer: source.manufacturer.clone()?,
+    })
+}
+
+/// Whether `entry` already lists `alternative` as its primary part or an alternate.
+fn has_part(entry: &BomEntry, alternative: &Alternative) -> bool {
+    entry.mpn.as_deref() == Some(alternative.mpn.as_str())
+        || entry.alternatives.contains(alternative)
+}
+
+fn rule_matches(key: &BomMatchingKey, designator: &str, entry: &BomEntry) -> bool {
+    match key {
+        BomMatchingKey::Mpn(mpn) => entry.matches_mpn(mpn),
+        BomMatchingKey::Path(designators) => designators.iter().any(|d| d == designator),
+        BomMatchingKey::Generic(generic) => entry.matches_generic(generic),
+    }
+}
+
+impl Bom {
+    /// Add the alternates of every matching rule to each entry.
+    ///
+    /// `Path` keys match reference designators, as in `pcb ipc2581 edit bom`.
+    pub fn apply_rules(&mut self, rules: &[BomMatchingRule]) {
+        for (path, entry) in &mut self.entries {
+            let designator = self.designators.get(path).map(String::as_str).unwrap_or("");
+            for rule in rules {
+                if !rule_matches(&rule.key, designator, entry) {
+                    continue;
+                }
+                for alternative in rule.sources.iter().filter_map(source_alternative) {
+                    if !has_part(entry, &alternative) {
+                        entry.alternatives.push(alternative);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Alternates that `imported` lists for a designator but `design` does not,
+/// as one `Path` rule per designator in designator order.
+///
+/// The imported primary part counts as an alternate when it differs from the
+/// design's. Designators missing from either BOM are ignored.
+pub fn imported_alternates(design: &Bom, imported: &Bom) -> Vec<BomMatchingRule> {
+    let imported_by_designator: HashMap<&str, &BomEntry> = imported
+        .entries
+        .iter()
+        .filter_map(|(path, entry)| Some((imported.designators.get(path)?.as_str(), entry)))
+        .collect();
+
+    let mut designs: Vec<(&str, &BomEntry)> = design
+        .entries
+        .iter()
+        .filter_map(|(path, entry)| Some((design.designators.get(path)?.as_str(), entry)))
+        .collect();
+    designs.sort_by_key(|(designator, _)| NaturalString::from(*designator));
+
+    designs
+        .into_iter()
+        .filter_map(|(designator, entry)| {
+            let theirs = imported_by_designator.get(designator)?;
+            let primary = theirs
+                .mpn
+                .clone()
+                .zip(theirs.manufacturer.clone())
+                .map(|(mpn, manufacturer)| Alternative { mpn, manufacturer });
+            let mut sources: Vec<ApprovedSource> = Vec::new();
+            for alternative in primary.iter().chain(&theirs.alternatives) {
+                let source = ApprovedSource {
+                    distributor: None,
+                    distributor_pn: None,
+                    manufacturer: Some(alternative.manufacturer.clone()),
+                    manufacturer_pn: Some(alternative.mpn.clone()),
+                    rank: None,
+                };
+                if !has_part(entry, alternative) && !sources.contains(&source) {
+                    sources.push(source);
+                }
+            }
+            (!sources.is_empty()).then(|| BomMatchingRule {
+                key: BomMatchingKey::Path(vec![designator.to_string()]),
+                sources,
+            })
+        })
+        .collect()
+}
+
+/// Merge `new` into `rules`, adding sources to a rule with the same key or
+/// appending the rule. Returns the number of sources added.
+pub fn merge_rules(rules: &mut Vec<BomMatchingRule>, new: Vec<BomMatchingRule>) -> usize {
+    let mut added = 0;
+    for rule in new {
+        let Some(existing) = rules.iter_mut().find(|existing| existing.key == rule.key) else {
+            added += rule.sources.len();
+            rules.push(rule);
+            continue;
+        };
+        for source in rule.sources {
+            if !existing.sources.contains(&source) {
+                existing.sources.push(source);
+                added += 1;
+            }
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn entry(mpn: &str, manufacturer: &str, alternatives: &[(&str, &str)]) -> BomEntry {
+        BomEntry {
+            mpn: Some(mpn.to_string()),
+            alternatives: alternatives
+                .iter()
+                .map(|(mpn, manufacturer)| Alternative {
+                    mpn: mpn.to_string(),
+                    manufacturer: manufacturer.to_string(),
+                })
+                .collect(),
+            manufacturer: Some(manufacturer.to_string()),
+            package: None,
+            value: None,
+            description: None,
+            generic_data: None,
+            dnp: false,
+            skip_bom: false,
+            matcher: None,
+            properties: BTreeMap::new(),
+        }
+    }
+
+    fn bom(entries: Vec<(&str, &str, BomEntry)>) -> Bom {
+        let mut paths = HashMap::new();
+        let mut designators = HashMap::new();
+        for (path, designator, entry) in entries {
+            paths.insert(path.to_string(), entry);
+            designators.insert(path.to_string(), designator.to_string());
+        }
+        Bom::new(paths, designators)
+    }
+
+    #[test]
+    fn imports_new_alternates_and_applies_them() {
+        let mut design = bom(vec![
+            (
+                "ldo.U1",
+                "U1",
+                entry("TLV755", "TI", &[("AP2112", "Diodes")]),
+            ),
+            ("R10", "R10", entry("RC0603", "Yageo", &[])),
+            ("R2", "R2", entry("ERJ-3", "Panasonic", &[])),
+        ]);
+        let imported = bom(vec![
+            (
+                "ipc::U1",
+                "U1",
+                entry(
+                    "TLV755",
+                    "TI",
+                    &[("AP2112", "Diodes"), ("MIC5504", "Microchip")],
+                ),
+            ),
+            ("ipc::R10", "R10", entry("CRCW0603", "Vishay", &[])),
+            ("ipc::R2", "R2", entry("ERJ-3", "Panasonic", &[])),
+            ("ipc::R99", "R99", entry("X", "Y", &[])),
+        ]);
+
+        let rules = imported_alternates(&design, &imported);
+        let summary: Vec<(BomMatchingKey, Vec<Option<String>>)> = rules
+            .iter()
+            .map(|rule| {
+                let mpns = rule
+                    .sources
+                    .iter()
+                    .map(|s| s.manufacturer_pn.clone())
+                    .collect();
+                (rule.key.clone(), mpns)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    BomMatchingKey::Path(vec!["R10".to_string()]),
+                    vec![Some("CRCW0603".to_string())]
+                ),
+                (
+                    BomMatchingKey::Path(vec!["U1".to_string()]),
+                    vec![Some("MIC5504".to_string())]
+                ),
+            ]
+        );
+
+        let mut sidecar = Vec::new();
+        assert_eq!(merge_rules(&mut sidecar, rules.clone()), 2);
+        assert_eq!(merge_rules(&mut sidecar, rules), 0);
+
+        design.apply_rules(&sidecar);
+        assert_eq!(
+            design.entries["ldo.U1"].alternatives,
+            vec![
+                Alternative {
+                    mpn: "AP2112".to_string(),
+                    manufacturer: "Diodes".to_string(),
+                },
+                Alternative {
+                    mpn: "MIC5504".to_string(),
+                    manufacturer: "Microchip".to_string(),
+                },
+            ]
+        );
+        assert!(imported_alternates(&design, &imported).is_empty());
+    }
+}
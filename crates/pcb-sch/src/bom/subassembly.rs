@@ -0,0 +1,204 @@
+//! BOM grouped by subassembly.
+//!
+//! A subassembly is a top-level module instance of the board, e.g.
+//! `PowerSupply` for `PowerSupply.LDO.C1`. Components instantiated directly in
+//! the root module belong to [`TOP_LEVEL_SUBASSEMBLY`], and so do single-part
+//! wrapper modules such as a stdlib `Resistor` placed in the root module
+//! (`R1.R`). Purchasing uses the per-subassembly quantities and cost to price
+//! optional assemblies.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Serialize;
+
+use super::{Bom, BomEntry, GroupedBomEntry};
+use crate::natural_string::NaturalString;
+
+/// Subassembly name for components instantiated directly in the root module.
+pub const TOP_LEVEL_SUBASSEMBLY: &str = "<root>";
+
+/// BOM lines of one subassembly.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SubassemblyBom {
+    pub name: String,
+    /// Populated (non-DNP) components.
+    pub quantity: usize,
+    /// Sum of known unit prices of populated components, preferring US offers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+    /// Populated components without a known price, not included in `cost`.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub unpriced: usize,
+    pub entries: Vec<GroupedBomEntry>,
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// Top-level module instance of a component path, if it is not in the root.
+fn top_level_instance(path: &str) -> Option<&str> {
+    path.split_once('.').map(|(top, _)| top)
+}
+
+impl Bom {
+    /// Group entries by the top-level module instance they belong to.
+    ///
+    /// Subassemblies are sorted by name with [`TOP_LEVEL_SUBASSEMBLY`] first;
+    /// entries within each are grouped and sorted like [`Bom::grouped_json`].
+    pub fn subassemblies(&self) -> Vec<SubassemblyBom> {
+        let mut paths_by_subassembly: BTreeMap<&str, Vec<&String>> = BTreeMap::new();
+        for path in self.entries.keys() {
+            let name = top_level_instance(path).unwrap_or(TOP_LEVEL_SUBASSEMBLY);
+            paths_by_subassembly.entry(name).or_default().push(path);
+        }
+        // A top-level module holding a single component directly is a part
+        // wrapper rather than a subassembly.
+        let wrappers: Vec<&str> = paths_by_subassembly
+            .iter()
+            .filter(|(name, paths)| {
+                **name != TOP_LEVEL_SUBASSEMBLY
+                    && matches!(paths.as_slice(), [path] if path.matches('.').count() == 1)
+            })
+            .map(|(name, _)| *name)
+            .collect();
+        for name in wrappers {
+            let paths = paths_by_subassembly.remove(name).unwrap_or_default();
+            paths_by_subassembly
+                .entry(TOP_LEVEL_SUBASSEMBLY)
+                .or_default()
+                .extend(paths);
+        }
+
+        let mut subassemblies: Vec<SubassemblyBom> = paths_by_subassembly
+            .into_iter()
+            .map(|(name, paths)| {
+                let mut groups = HashMap::<BomEntry, BTreeSet<NaturalString>>::new();
+                let (mut quantity, mut unpriced, mut cost) = (0, 0, None::<f64>);
+                for path in paths {
+                    let entry = &self.entries[path];
+                    groups
+                        .entry(entry.clone())
+                        .or_default()
+                        .insert(self.designators[path].clone().into());
+                    if entry.dnp {
+                        continue;
+                    }
+                    quantity += 1;
+                    match self.unit_price(path) {
+                        Some(price) => *cost.get_or_insert(0.0) += price,
+                        None => unpriced += 1,
+                    }
+                }
+
+                let mut entries: Vec<GroupedBomEntry> = groups
+                    .into_iter()
+                    .map(|(entry, designators)| GroupedBomEntry::new(entry, designators))
+                    .collect();
+                entries.sort_by(|a, b| {
+                    a.entry.dnp.cmp(&b.entry.dnp).then_with(|| {
+                        a.designators
+                            .iter()
+                            .next()
+                            .cmp(&b.designators.iter().next())
+                    })
+                });
+
+                SubassemblyBom {
+                    name: name.to_owned(),
+                    quantity,
+                    cost,
+                    unpriced,
+                    entries,
+                }
+            })
+            .collect();
+        subassemblies.sort_by_key(|s| s.name != TOP_LEVEL_SUBASSEMBLY);
+        subassemblies
+    }
+
+    pub fn subassembly_json(&self) -> String {
+        serde_json::to_string_pretty(&self.subassemblies()).unwrap()
+    }
+
+    fn unit_price(&self, path: &str) -> Option<f64> {
+        let availability = self.availability.get(path)?;
+        availability
+            .us
+            .as_ref()
+            .and_then(|summary| summary.price)
+            .or_else(|| {
+                availability
+                    .global
+                    .as_ref()
+                    .and_then(|summary| summary.price)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bom::{Availability, AvailabilitySummary};
+
+    fn entry(mpn: &str, dnp: bool) -> BomEntry {
+        BomEntry {
+            mpn: Some(mpn.to_owned()),
+            alternatives: Vec::new(),
+            manufacturer: None,
+            package: None,
+            value: None,
+            description: None,
+            generic_data: None,
+            dnp,
+            skip_bom: false,
+            matcher: None,
+            properties: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn groups_by_top_level_instance() {
+        let mut entries = HashMap::new();
+        let mut designators = HashMap::new();
+        for (path, refdes, mpn, dnp) in [
+            ("J1", "J1", "CONN", false),
+            ("R1.R", "R1", "RES", false),
+            ("PowerSupply.LDO.C1", "C1", "CAP", false),
+            ("PowerSupply.LDO.C2", "C2", "CAP", false),
+            ("PowerSupply.U1", "U1", "LDO", false),
+            ("RF.U2", "U2", "LNA", true),
+            ("RF.U3", "U3", "SAW", true),
+        ] {
+            entries.insert(path.to_owned(), entry(mpn, dnp));
+            designators.insert(path.to_owned(), refdes.to_owned());
+        }
+        let mut bom = Bom::new(entries, designators);
+        for (path, price) in [("PowerSupply.LDO.C1", 0.1), ("PowerSupply.U1", 1.5)] {
+            bom.availability.insert(
+                path.to_owned(),
+                Availability {
+                    us: Some(AvailabilitySummary {
+                        price: Some(price),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let subassemblies = bom.subassemblies();
+        let names: Vec<_> = subassemblies.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, [TOP_LEVEL_SUBASSEMBLY, "PowerSupply", "RF"]);
+        assert_eq!(subassemblies[0].quantity, 2);
+
+        let power = &subassemblies[1];
+        assert_eq!(power.quantity, 3);
+        assert_eq!(power.unpriced, 1);
+        assert!((power.cost.unwrap() - 1.6).abs() < 1e-9);
+        assert_eq!(power.entries.len(), 2);
+
+        let rf = &subassemblies[2];
+        assert_eq!((rf.quantity, rf.cost), (0, None));
+    }
+}
@@ -0,0 +1,330 @@
+//! Build variants declared with `Sweep()` and the BOM matrix across them.
+//!
+//! `Sweep(name, parameters)` in the root module records a `sweep.<name>`
+//! property holding the swept `config()` inputs. Each combination of values is
+//! a [`Variant`]: a named set of config overrides, built the same way as
+//! `--config KEY=VALUE`. [`BomMatrix`] lines up the BOMs of several variants
+//! by instance path so that per-variant differences stand out.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::{Bom, BomEntry};
+use crate::natural_string::NaturalString;
+use crate::{AttributeValue, Instance};
+
+/// Attribute key prefix for sweeps declared on the root module.
+pub const ATTR_SWEEP_PREFIX: &str = "sweep.";
+
+/// A parameter sweep over root `config()` inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sweep {
+    pub name: String,
+    /// Swept inputs in declaration order, each with its candidate values.
+    pub parameters: Vec<(String, Vec<String>)>,
+    /// Explicit variant names, one per combination.
+    pub names: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct SweepSpec {
+    parameters: Vec<(String, Vec<String>)>,
+    #[serde(default)]
+    names: Option<Vec<String>>,
+}
+
+/// A named build variant: config overrides applied on top of the base build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Variant {
+    pub name: String,
+    pub config: BTreeMap<String, String>,
+}
+
+/// Read the sweeps declared on `root`, ordered by name.
+pub fn sweeps(root: &Instance) -> Result<Vec<Sweep>> {
+    let mut sweeps = Vec::new();
+    for (key, value) in &root.attributes {
+        let Some(name) = key.strip_prefix(ATTR_SWEEP_PREFIX) else {
+            continue;
+        };
+        let AttributeValue::String(spec) = value else {
+            bail!("Sweep '{name}' has a malformed definition");
+        };
+        let spec: SweepSpec = serde_json::from_str(spec)
+            .with_context(|| format!("Sweep '{name}' has a malformed definition"))?;
+        sweeps.push(Sweep {
+            name: name.to_string(),
+            parameters: spec.parameters,
+            names: spec.names,
+        });
+    }
+    sweeps.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(sweeps)
+}
+
+impl Sweep {
+    /// Expand the sweep into one variant per combination of parameter values.
+    ///
+    /// Later parameters vary fastest. Unnamed variants are called
+    /// `<sweep>[KEY=VALUE,...]`.
+    pub fn variants(&self) -> Result<Vec<Variant>> {
+        let mut combinations = vec![BTreeMap::new()];
+        for (key, values) in &self.parameters {
+            if values.is_empty() {
+                bail!("Sweep '{}' has no values for '{key}'", self.name);
+            }
+            combinations = combinations
+                .into_iter()
+                .flat_map(|config| {
+                    values.iter().map(move |value| {
+                        let mut config = config.clone();
+                        config.insert(key.clone(), value.clone());
+                        config
+                    })
+                })
+                .collect();
+        }
+
+        if let Some(names) = &self.names
+            && names.len() != combinations.len()
+        {
+            bail!(
+                "Sweep '{}' has {} combinations but {} names",
+                self.name,
+                combinations.len(),
+                names.len()
+            );
+        }
+
+        Ok(combinations
+            .into_iter()
+            .enumerate()
+            .map(|(i, config)| {
+                let name = match &self.names {
+                    Some(names) => names[i].clone(),
+                    None => {
+                        let assignments = self
+                            .parameters
+                            .iter()
+                            .map(|(key, _)| format!("{key}={}", config[key]))
+                            .collect::<Vec<_>>()
+                            .join(",");
+                        format!("{}[{assignments}]", self.name)
+                    }
+                };
+                Variant { name, config }
+            })
+            .collect())
+    }
+}
+
+/// What a variant places at one instance path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MatrixCell {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mpn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manufacturer: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dnp: bool,
+}
+
+impl From<&BomEntry> for MatrixCell {
+    fn from(entry: &BomEntry) -> Self {
+        Self {
+            value: entry.value.clone(),
+            mpn: entry.mpn.clone(),
+            manufacturer: entry.manufacturer.clone(),
+            dnp: entry.dnp,
+        }
+    }
+}
+
+impl std::fmt::Display for MatrixCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.dnp {
+            return write!(f, "DNP");
+        }
+        let parts: Vec<&str> = [self.value.as_deref(), self.mpn.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect();
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// One instance across all variants.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MatrixRow {
+    pub path: String,
+    pub designator: String,
+    /// One cell per variant, in [`BomMatrix::variants`] order; `None` when the
+    /// variant does not contain the instance.
+    pub cells: Vec<Option<MatrixCell>>,
+    pub differs: bool,
+}
+
+/// BOMs of several variants lined up by instance path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BomMatrix {
+    pub variants: Vec<Variant>,
+    pub rows: Vec<MatrixRow>,
+}
+
+impl BomMatrix {
+    /// Build the matrix from one BOM per variant. Rows are sorted by
+    /// designator.
+    pub fn new(variants: Vec<(Variant, Bom)>) -> Self {
+        let paths: BTreeSet<&String> = variants
+            .iter()
+            .flat_map(|(_, bom)| bom.entries.keys())
+            .collect();
+
+        let mut rows: Vec<MatrixRow> = paths
+            .into_iter()
+            .map(|path| {
+                let cells: Vec<Option<MatrixCell>> = variants
+                    .iter()
+                    .map(|(_, bom)| bom.entries.get(path).map(MatrixCell::from))
+                    .collect();
+                let designator = variants
+                    .iter()
+                    .find_map(|(_, bom)| bom.designators.get(path))
+                    .cloned()
+                    .unwrap_or_default();
+                let differs = cells.windows(2).any(|pair| pair[0] != pair[1]);
+                MatrixRow {
+                    path: path.clone(),
+                    designator,
+                    cells,
+                    differs,
+                }
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            NaturalString::from(a.designator.as_str())
+                .cmp(&NaturalString::from(b.designator.as_str()))
+                .then_with(|| a.path.cmp(&b.path))
+        });
+
+        Self {
+            variants: variants.into_iter().map(|(variant, _)| variant).collect(),
+            rows,
+        }
+    }
+
+    /// Rows whose contents differ between at least two variants.
+    pub fn differing_rows(&self) -> impl Iterator<Item = &MatrixRow> {
+        self.rows.iter().filter(|row| row.differs)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn sweep(names: Option<Vec<&str>>) -> Sweep {
+        Sweep {
+            name: "divider".to_string(),
+            parameters: vec![
+                (
+                    "R_TOP".to_string(),
+                    vec!["10k".to_string(), "22k".to_string()],
+                ),
+                ("R_BOT".to_string(), vec!["4.7k".to_string()]),
+            ],
+            names: names.map(|names| names.into_iter().map(String::from).collect()),
+        }
+    }
+
+    fn bom(entries: &[(&str, &str, &str, bool)]) -> Bom {
+        let mut bom_entries = HashMap::new();
+        let mut designators = HashMap::new();
+        for (path, designator, value, dnp) in entries {
+            bom_entries.insert(
+                path.to_string(),
+                BomEntry {
+                    mpn: None,
+                    alternatives: vec![],
+                    manufacturer: None,
+                    package: None,
+                    value: Some(value.to_string()),
+                    description: None,
+                    generic_data: None,
+                    dnp: *dnp,
+                    skip_bom: false,
+                    matcher: None,
+                    properties: BTreeMap::new(),
+                },
+            );
+            designators.insert(path.to_string(), designator.to_string());
+        }
+        Bom::new(bom_entries, designators)
+    }
+
+    #[test]
+    fn expands_sweep_into_named_variants() {
+        let variants = sweep(None).variants().unwrap();
+        let names: Vec<_> = variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "divider[R_TOP=10k,R_BOT=4.7k]",
+                "divider[R_TOP=22k,R_BOT=4.7k]"
+            ]
+        );
+        assert_eq!(variants[1].config["R_TOP"], "22k");
+        assert_eq!(variants[1].config["R_BOT"], "4.7k");
+
+        let named = sweep(Some(vec!["low", "high"])).variants().unwrap();
+        assert_eq!(named[0].name, "low");
+        assert!(sweep(Some(vec!["only"])).variants().is_err());
+    }
+
+    #[test]
+    fn reads_sweeps_from_root_attributes() {
+        let root = Instance::module(crate::ModuleRef::new("board.zen", "<root>")).with_attribute(
+            "sweep.divider",
+            AttributeValue::String(
+                r#"{"parameters": [["R_TOP", ["10k", "22k"]], ["R_BOT", ["4.7k"]]], "names": null}"#
+                    .to_string(),
+            ),
+        );
+        assert_eq!(sweeps(&root).unwrap(), vec![sweep(None)]);
+    }
+
+    #[test]
+    fn matrix_flags_rows_that_differ() {
+        let variants = sweep(None).variants().unwrap();
+        let matrix = BomMatrix::new(vec![
+            (
+                variants[0].clone(),
+                bom(&[
+                    ("R1", "R1", "10k", false),
+                    ("R2", "R2", "4.7k", false),
+                    ("R10", "R10", "0", false),
+                ]),
+            ),
+            (
+                variants[1].clone(),
+                bom(&[("R1", "R1", "22k", false), ("R2", "R2", "4.7k", false)]),
+            ),
+        ]);
+
+        let designators: Vec<_> = matrix.rows.iter().map(|r| r.designator.as_str()).collect();
+        assert_eq!(designators, ["R1", "R2", "R10"]);
+        let differing: Vec<_> = matrix.differing_rows().map(|r| r.path.as_str()).collect();
+        assert_eq!(differing, ["R1", "R10"]);
+        assert_eq!(matrix.rows[0].cells[1].as_ref().unwrap().to_string(), "22k");
+        assert_eq!(matrix.rows[2].cells[1], None);
+    }
+}
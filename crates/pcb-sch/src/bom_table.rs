@@ -137,13 +137,26 @@ fn styled_status_cell(content: impl ToString, is_dnp: bool, no_match: bool, tier
     Cell::new(content).fg(color_for_status(is_dnp, no_match, tier))
 }
 
+/// Render a lifecycle status, highlighting end-of-life parts in red and others in yellow.
+fn lifecycle_cell(lifecycle: Option<&str>, is_dnp: bool) -> Cell {
+    let Some(lifecycle) = lifecycle else {
+        return Cell::new("");
+    };
+    let cell = Cell::new(lifecycle.to_ascii_uppercase());
+    match (is_dnp, lifecycle) {
+        (true, _) => cell.fg(Color::DarkGrey),
+        (false, "eol") => cell.fg(Color::Red),
+        (false, _) => cell.fg(Color::Yellow),
+    }
+}
+
 /// Check if MPN and manufacturer are both present
 fn has_complete_part_info(mpn: &str, manufacturer: &str) -> bool {
     !mpn.is_empty() && !manufacturer.is_empty()
 }
 
 /// Calculate unit price at a given quantity using price breaks
-fn unit_price_from_breaks(price_breaks: &[(i32, f64)], qty: i32) -> Option<f64> {
+pub(crate) fn unit_price_from_breaks(price_breaks: &[(i32, f64)], qty: i32) -> Option<f64> {
     if price_breaks.is_empty() {
         return None;
     }
@@ -356,6 +369,9 @@ impl Bom {
             }
         });
 
+        // Only show the lifecycle column when some line item is flagged
+        let has_lifecycle = entries.iter().any(|entry| entry.get("lifecycle").is_some());
+
         for entry in entries {
             let designators_vec: Vec<&str> = entry["designators"]
                 .as_array()
@@ -545,6 +561,8 @@ impl Bom {
                 None,
             );
             let description_cell = styled_cell(description, is_dnp, false, None);
+            let lifecycle_cell =
+                lifecycle_cell(entry.get("lifecycle").and_then(|v| v.as_str()), is_dnp);
 
             // Build row
             let mut row = vec![qty_cell];
@@ -573,6 +591,10 @@ impl Bom {
                 package_cell,
             ]);
 
+            if has_lifecycle {
+                row.push(lifecycle_cell);
+            }
+
             // Add LCSC column (from global data only, as LCSC is a global distributor)
             if has_availability {
                 let lcsc_display = global_data
@@ -609,6 +631,10 @@ impl Bom {
 
         headers.extend(vec!["Designators", "MPN", "Manufacturer", "Package"]);
 
+        if has_lifecycle {
+            headers.push("Lifecycle");
+        }
+
         if has_availability {
             headers.push("LCSC");
         }
@@ -757,6 +783,49 @@ impl Bom {
         }
         Ok(())
     }
+
+    /// Write one row per subassembly with its unique parts, quantity, and cost.
+    pub fn write_subassembly_table<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut table = Table::new();
+        table.load_preset(comfy_table::presets::UTF8_FULL_CONDENSED);
+        table.set_header(vec![
+            "Subassembly",
+            "Unique Parts",
+            "Qty",
+            "Cost",
+            "Designators",
+        ]);
+        for col_idx in 1..=3 {
+            table
+                .column_mut(col_idx)
+                .unwrap()
+                .set_cell_alignment(comfy_table::CellAlignment::Right);
+        }
+
+        for subassembly in self.subassemblies() {
+            let cost = match (subassembly.cost, subassembly.unpriced) {
+                (Some(cost), 0) => format!("${:.2}", ceil_cents(cost)),
+                (Some(cost), unpriced) => {
+                    format!("${:.2} (+{unpriced} unpriced)", ceil_cents(cost))
+                }
+                (None, _) => "-".to_string(),
+            };
+            let mut designators = subassembly
+                .entries
+                .iter()
+                .flat_map(|entry| entry.designators.iter().map(|d| d.as_ref()))
+                .collect::<Vec<_>>();
+            designators.sort_by(|a, b| natord::compare(a, b));
+            table.add_row(vec![
+                Cell::new(&subassembly.name),
+                Cell::new(subassembly.entries.len()),
+                Cell::new(subassembly.quantity),
+                Cell::new(cost),
+                Cell::new(designators.join(",")),
+            ]);
+        }
+        writeln!(writer, "{table}")
+    }
 }
 
 #[cfg(test)]
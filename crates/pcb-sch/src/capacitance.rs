@@ -0,0 +1,193 @@
+//! Capacitive loading per net, for high-speed design review.
+//!
+//! Each component pin on a signal net contributes the component's
+//! `pin_capacitance` attribute (e.g. `"10pF"`), and each pin-to-pin connection
+//! adds an estimated trace capacitance. Nets matching a budget pattern, such as
+//! `I2C_*` with the 400pF bus limit, are checked against it.
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use anyhow::{Context, Result, bail};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::Schematic;
+use crate::netlist::{netlist_components, netlist_nets};
+use crate::physical::{PhysicalUnit, PhysicalValue};
+use crate::query::glob_match;
+
+/// Component attribute with the input capacitance of each of its pins.
+pub const ATTR_PIN_CAPACITANCE: &str = "pin_capacitance";
+
+/// Trace capacitance assumed per connection when none is configured: about
+/// 25mm of trace at 0.1pF/mm.
+pub const DEFAULT_TRACE_CAPACITANCE_PF: f64 = 2.5;
+
+/// Net kinds that carry supplies rather than signals and are not reported.
+const SUPPLY_NET_KINDS: &[&str] = &["Power", "Ground", "NotConnected"];
+
+/// Capacitance budgets keyed by net name glob, in configuration order.
+#[derive(Debug, Clone, Default)]
+pub struct CapacitanceBudgets {
+    budgets: Vec<(String, f64)>,
+}
+
+impl CapacitanceBudgets {
+    /// Parse `pattern = "400pF"` pairs. The first matching pattern wins.
+    pub fn new<'a>(budgets: impl IntoIterator<Item = (&'a str, &'a str)>) -> Result<Self> {
+        let budgets = budgets
+            .into_iter()
+            .map(|(pattern, limit)| {
+                let limit = parse_picofarads(limit)
+                    .with_context(|| format!("Invalid budget for '{pattern}'"))?;
+                Ok((pattern.to_string(), limit))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { budgets })
+    }
+
+    /// Budget in pF for `net`, if any pattern matches it.
+    pub fn for_net(&self, net: &str) -> Option<f64> {
+        self.budgets
+            .iter()
+            .find(|(pattern, _)| glob_match(pattern.as_bytes(), net.as_bytes()))
+            .map(|(_, limit)| *limit)
+    }
+}
+
+/// Estimated capacitive load of one net.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetCapacitance {
+    pub net: String,
+    /// Component pins on the net.
+    pub pins: usize,
+    /// Pins whose component has no `pin_capacitance` attribute.
+    pub unknown_pins: usize,
+    /// Sum of the known pin capacitances, in pF.
+    pub pin_pf: f64,
+    /// Estimated trace capacitance, in pF.
+    pub trace_pf: f64,
+    /// Budget in pF, if the net matches one.
+    pub budget_pf: Option<f64>,
+}
+
+impl NetCapacitance {
+    pub fn total_pf(&self) -> f64 {
+        self.pin_pf + self.trace_pf
+    }
+
+    pub fn over_budget(&self) -> bool {
+        self.budget_pf
+            .is_some_and(|budget| self.total_pf() > budget)
+    }
+}
+
+/// Loading of every signal net that has a budget or at least one pin with a
+/// known capacitance, in net name order.
+///
+/// Component instances must already have reference designators; see
+/// [`Schematic::assign_reference_designators`].
+pub fn net_capacitance(
+    sch: &Schematic,
+    trace_pf_per_connection: f64,
+    budgets: &CapacitanceBudgets,
+) -> Result<Vec<NetCapacitance>> {
+    let mut pin_capacitance = HashMap::new();
+    for component in netlist_components(sch) {
+        let Some(value) = component.instance.string_attr(&[ATTR_PIN_CAPACITANCE]) else {
+            continue;
+        };
+        let pf = parse_picofarads(&value)
+            .with_context(|| format!("Invalid {ATTR_PIN_CAPACITANCE} on {}", component.refdes))?;
+        pin_capacitance.insert(component.refdes.to_string(), pf);
+    }
+
+    let mut report = Vec::new();
+    for net in netlist_nets(sch) {
+        let kind = sch.nets.get(&net.name).map(|net| net.kind.as_str());
+        if kind.is_some_and(|kind| SUPPLY_NET_KINDS.contains(&kind)) {
+            continue;
+        }
+
+        let pins: HashSet<(&str, &str)> = net
+            .nodes
+            .iter()
+            .map(|node| (node.refdes.as_str(), node.pin.as_str()))
+            .collect();
+        let known: Vec<f64> = pins
+            .iter()
+            .filter_map(|(refdes, _)| pin_capacitance.get(*refdes).copied())
+            .collect();
+        let budget_pf = budgets.for_net(&net.name);
+        if known.is_empty() && budget_pf.is_none() {
+            continue;
+        }
+
+        report.push(NetCapacitance {
+            pins: pins.len(),
+            unknown_pins: pins.len() - known.len(),
+            pin_pf: known.iter().sum(),
+            trace_pf: pins.len().saturating_sub(1) as f64 * trace_pf_per_connection,
+            budget_pf,
+            net: net.name,
+        });
+    }
+    Ok(report)
+}
+
+/// Parse a capacitance such as `"400pF"` into picofarads.
+pub fn parse_picofarads(value: &str) -> Result<f64> {
+    let physical = PhysicalValue::from_str(value.trim())
+        .map_err(|err| anyhow::anyhow!("'{value}' is not a capacitance: {err}"))?;
+    if physical.unit() != Some(PhysicalUnit::Farads) {
+        bail!("'{value}' is not a capacitance");
+    }
+    (physical.nominal * Decimal::from(1_000_000_000_000_i64))
+        .to_f64()
+        .with_context(|| format!("'{value}' is out of range"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AttributeValue;
+    use crate::netlist::test_support::divider;
+
+    #[test]
+    fn sums_pins_and_traces_against_budgets() {
+        let mut sch = divider();
+        for instance in sch.instances.values_mut() {
+            if instance.reference_designator.as_deref() == Some("R1") {
+                instance.attributes.insert(
+                    ATTR_PIN_CAPACITANCE.into(),
+                    AttributeValue::String("150pF".to_owned()),
+                );
+            }
+        }
+        sch.nets.get_mut("GND").unwrap().kind = "Ground".to_owned();
+
+        let budgets = CapacitanceBudgets::new([("OUT", "150pF"), ("*", "1nF")]).unwrap();
+        let report = net_capacitance(&sch, 2.5, &budgets).unwrap();
+
+        assert_eq!(
+            report
+                .iter()
+                .map(|net| net.net.as_str())
+                .collect::<Vec<_>>(),
+            ["OUT", "VIN"]
+        );
+        let out = &report[0];
+        assert_eq!((out.pins, out.unknown_pins), (2, 1));
+        assert_eq!(out.total_pf(), 152.5);
+        assert!(out.over_budget());
+        assert_eq!(report[1].budget_pf, Some(1000.0));
+        assert!(!report[1].over_budget());
+    }
+
+    #[test]
+    fn rejects_non_capacitance_budgets() {
+        let err = CapacitanceBudgets::new([("I2C_*", "400mA")]).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid budget for 'I2C_*'");
+    }
+}
@@ -0,0 +1,245 @@
+// Module implementing Eagle XML schematic (parts and nets) export for `pcb_sch::Schematic`.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt::Write;
+
+use crate::Schematic;
+use crate::kicad_netlist::{ComponentChildren, collect_pins_for_component};
+use crate::netlist::{footprint_name, netlist_components, netlist_nets};
+
+/// Library holding the generated package, symbol, and deviceset of each part.
+const LIBRARY: &str = "pcb";
+/// Every generated deviceset has a single gate.
+const GATE: &str = "G$1";
+/// Eagle's default 0.1" grid, in millimetres.
+const GRID: f64 = 2.54;
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Export the provided [`Schematic`] as an Eagle schematic.
+///
+/// Each component becomes a part with its own package, symbol, and
+/// deviceset (named after its reference designator) whose pins connect to
+/// the component's pads. Nets are written as `pinref`s on a single sheet;
+/// there is no graphical placement beyond a column of part instances.
+pub fn to_eagle_netlist(sch: &Schematic) -> String {
+    let components = netlist_components(sch);
+
+    let mut out = String::new();
+    writeln!(out, r#"<?xml version="1.0" encoding="utf-8"?>"#).unwrap();
+    writeln!(out, r#"<!DOCTYPE eagle SYSTEM "eagle.dtd">"#).unwrap();
+    writeln!(out, r#"<eagle version="9.6.2">"#).unwrap();
+    writeln!(out, "<drawing>").unwrap();
+    writeln!(out, "<layers>").unwrap();
+    for (number, name) in [
+        (1, "Top"),
+        (16, "Bottom"),
+        (91, "Nets"),
+        (94, "Symbols"),
+        (95, "Names"),
+        (96, "Values"),
+    ] {
+        writeln!(
+            out,
+            r#"<layer number="{number}" name="{name}" color="7" fill="1" visible="yes" active="yes"/>"#
+        )
+        .unwrap();
+    }
+    writeln!(out, "</layers>").unwrap();
+    writeln!(out, "<schematic>").unwrap();
+    if let Some(stamp) = &sch.stamp {
+        writeln!(
+            out,
+            "<description>{}</description>",
+            escape_xml(&stamp.label())
+        )
+        .unwrap();
+    }
+    writeln!(out, "<libraries>").unwrap();
+    writeln!(out, r#"<library name="{LIBRARY}">"#).unwrap();
+
+    // Pads of each pin, per component.
+    let pins_by_component: Vec<BTreeMap<String, BTreeSet<String>>> = components
+        .iter()
+        .map(|component| {
+            let mut pins: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+            if let Some(ComponentChildren { pins: children }) =
+                collect_pins_for_component(sch, &component.reference)
+            {
+                for (pad, pin) in children {
+                    pins.entry(pin).or_default().insert(pad);
+                }
+            }
+            pins
+        })
+        .collect();
+
+    writeln!(out, "<packages>").unwrap();
+    for (component, pins) in components.iter().zip(&pins_by_component) {
+        let pads: BTreeSet<&String> = pins.values().flatten().collect();
+        writeln!(out, r#"<package name="{}">"#, escape_xml(component.refdes)).unwrap();
+        writeln!(
+            out,
+            r#"<description>{}</description>"#,
+            escape_xml(&footprint_name(component))
+        )
+        .unwrap();
+        for (i, pad) in pads.into_iter().enumerate() {
+            writeln!(
+                out,
+                r#"<smd name="{}" x="{}" y="0" dx="1" dy="1" layer="1"/>"#,
+                escape_xml(pad),
+                i as f64 * GRID
+            )
+            .unwrap();
+        }
+        writeln!(out, "</package>").unwrap();
+    }
+    writeln!(out, "</packages>").unwrap();
+
+    writeln!(out, "<symbols>").unwrap();
+    for (component, pins) in components.iter().zip(&pins_by_component) {
+        writeln!(out, r#"<symbol name="{}">"#, escape_xml(component.refdes)).unwrap();
+        for (i, pin) in pins.keys().enumerate() {
+            writeln!(
+                out,
+                r#"<pin name="{}" x="0" y="{}" length="short"/>"#,
+                escape_xml(pin),
+                -(i as f64) * GRID
+            )
+            .unwrap();
+        }
+        writeln!(out, "</symbol>").unwrap();
+    }
+    writeln!(out, "</symbols>").unwrap();
+
+    writeln!(out, "<devicesets>").unwrap();
+    for (component, pins) in components.iter().zip(&pins_by_component) {
+        let name = escape_xml(component.refdes);
+        writeln!(out, r#"<deviceset name="{name}">"#).unwrap();
+        writeln!(out, "<gates>").unwrap();
+        writeln!(out, r#"<gate name="{GATE}" symbol="{name}" x="0" y="0"/>"#).unwrap();
+        writeln!(out, "</gates>").unwrap();
+        writeln!(out, "<devices>").unwrap();
+        writeln!(out, r#"<device name="" package="{name}">"#).unwrap();
+        writeln!(out, "<connects>").unwrap();
+        for (pin, pads) in pins {
+            let pads: Vec<&str> = pads.iter().map(String::as_str).collect();
+            writeln!(
+                out,
+                r#"<connect gate="{GATE}" pin="{}" pad="{}"/>"#,
+                escape_xml(pin),
+                escape_xml(&pads.join(" "))
+            )
+            .unwrap();
+        }
+        writeln!(out, "</connects>").unwrap();
+        writeln!(out, "</device>").unwrap();
+        writeln!(out, "</devices>").unwrap();
+        writeln!(out, "</deviceset>").unwrap();
+    }
+    writeln!(out, "</devicesets>").unwrap();
+    writeln!(out, "</library>").unwrap();
+    writeln!(out, "</libraries>").unwrap();
+
+    writeln!(out, "<classes>").unwrap();
+    writeln!(
+        out,
+        r#"<class number="0" name="default" width="0" drill="0"/>"#
+    )
+    .unwrap();
+    writeln!(out, "</classes>").unwrap();
+
+    writeln!(out, "<parts>").unwrap();
+    for component in &components {
+        let name = escape_xml(component.refdes);
+        writeln!(
+            out,
+            r#"<part name="{name}" library="{LIBRARY}" deviceset="{name}" device="" value="{}"/>"#,
+            escape_xml(&component.value())
+        )
+        .unwrap();
+    }
+    writeln!(out, "</parts>").unwrap();
+
+    writeln!(out, "<sheets>").unwrap();
+    writeln!(out, "<sheet>").unwrap();
+    writeln!(out, "<instances>").unwrap();
+    for (i, component) in components.iter().enumerate() {
+        writeln!(
+            out,
+            r#"<instance part="{}" gate="{GATE}" x="0" y="{}"/>"#,
+            escape_xml(component.refdes),
+            -(i as f64) * 10.0 * GRID
+        )
+        .unwrap();
+    }
+    writeln!(out, "</instances>").unwrap();
+    writeln!(out, "<nets>").unwrap();
+    for net in netlist_nets(sch) {
+        // Several pads can belong to one pin; Eagle connects pins, not pads.
+        let mut seen = HashSet::new();
+        let pinrefs: Vec<(&str, &str)> = net
+            .nodes
+            .iter()
+            .map(|node| (node.refdes.as_str(), node.pin.as_str()))
+            .filter(|pinref| seen.insert(*pinref))
+            .collect();
+        if pinrefs.is_empty() {
+            continue;
+        }
+        writeln!(out, r#"<net name="{}" class="0">"#, escape_xml(&net.name)).unwrap();
+        writeln!(out, "<segment>").unwrap();
+        for (refdes, pin) in pinrefs {
+            writeln!(
+                out,
+                r#"<pinref part="{}" gate="{GATE}" pin="{}"/>"#,
+                escape_xml(refdes),
+                escape_xml(pin)
+            )
+            .unwrap();
+        }
+        writeln!(out, "</segment>").unwrap();
+        writeln!(out, "</net>").unwrap();
+    }
+    writeln!(out, "</nets>").unwrap();
+    writeln!(out, "</sheet>").unwrap();
+    writeln!(out, "</sheets>").unwrap();
+    writeln!(out, "</schematic>").unwrap();
+    writeln!(out, "</drawing>").unwrap();
+    writeln!(out, "</eagle>").unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlist::test_support::divider;
+
+    #[test]
+    fn writes_parts_connects_and_pinrefs() {
+        let xml = to_eagle_netlist(&divider());
+
+        assert!(
+            xml.contains(r#"<part name="R1" library="pcb" deviceset="R1" device="" value="10k"/>"#)
+        );
+        assert!(xml.contains(r#"<connect gate="G$1" pin="P2" pad="2"/>"#));
+        assert!(xml.contains(r#"<description>R_0402_1005Metric</description>"#));
+        assert!(xml.contains(
+            "<net name=\"OUT\" class=\"0\">\n<segment>\n\
+             <pinref part=\"R1\" gate=\"G$1\" pin=\"P2\"/>\n\
+             <pinref part=\"R2\" gate=\"G$1\" pin=\"P1\"/>\n"
+        ));
+    }
+
+    #[test]
+    fn escapes_names() {
+        assert_eq!(escape_xml(r#"A&B<"x">"#), "A&amp;B&lt;&quot;x&quot;&gt;");
+    }
+}
@@ -0,0 +1,221 @@
+//! FMEA worksheet skeletons built from a design's failure-mode annotations.
+//!
+//! Components and modules describe how they fail with `failure_<mode>`
+//! properties holding the local effect of that mode, and the derating applied
+//! with `derating`:
+//!
+//! ```python
+//! Component(..., properties={
+//!     "failure_short": "VIN shorted to GND; input fuse opens",
+//!     "failure_open": "Loss of bulk decoupling; increased ripple",
+//!     "derating": "50% of rated voltage",
+//! })
+//! ```
+//!
+//! Every populated component gets a short and an open row, annotated or not,
+//! plus one row per other declared mode. Modules only get rows for modes they
+//! declare. System effect, severity, occurrence, detection, RPN, and mitigation
+//! are left for the reviewers to fill in.
+
+use crate::{InstanceKind, Schematic};
+
+/// Prefix of the properties that give the local effect of a failure mode,
+/// e.g. `failure_short`.
+pub const ATTR_FAILURE_PREFIX: &str = "failure_";
+
+/// Property describing the derating applied to a component or module.
+pub const ATTR_DERATING: &str = "derating";
+
+/// Failure modes every populated component is listed with.
+const COMPONENT_MODES: &[&str] = &["short", "open"];
+
+/// Worksheet columns filled in by reviewers rather than from the design.
+const REVIEW_COLUMNS: &[&str] = &[
+    "System Effect",
+    "Severity",
+    "Occurrence",
+    "Detection",
+    "RPN",
+    "Mitigation",
+];
+
+/// One failure mode of one component or module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FmeaRow {
+    /// Dot-separated instance path.
+    pub item: String,
+    /// Reference designator; `None` for modules.
+    pub reference: Option<String>,
+    /// MPN, else value, of a component.
+    pub part: Option<String>,
+    /// Mode name as written after `failure_`, e.g. `short`.
+    pub mode: String,
+    /// Declared local effect, empty when not annotated.
+    pub local_effect: String,
+    pub derating: Option<String>,
+}
+
+impl Schematic {
+    /// FMEA rows of every populated component and every annotated module, in
+    /// natural instance path order.
+    pub fn fmea_rows(&self) -> Vec<FmeaRow> {
+        let mut instances: Vec<_> = self
+            .instances
+            .iter()
+            .filter(|(_, instance)| match instance.kind {
+                InstanceKind::Component => !instance.dnp(),
+                InstanceKind::Module => instance
+                    .attributes
+                    .keys()
+                    .any(|key| key.starts_with(ATTR_FAILURE_PREFIX)),
+                _ => false,
+            })
+            .map(|(reference, instance)| (reference.instance_path.join("."), instance))
+            .collect();
+        instances.sort_by(|a, b| natord::compare(&a.0, &b.0));
+
+        let mut rows = Vec::new();
+        for (item, instance) in instances {
+            let is_component = instance.kind == InstanceKind::Component;
+            let mut modes: Vec<String> = if is_component {
+                COMPONENT_MODES
+                    .iter()
+                    .map(|mode| mode.to_string())
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            let mut declared: Vec<&str> = instance
+                .attributes
+                .keys()
+                .filter_map(|key| key.strip_prefix(ATTR_FAILURE_PREFIX))
+                .filter(|mode| !mode.is_empty() && !modes.iter().any(|m| m == mode))
+                .collect();
+            declared.sort_unstable();
+            modes.extend(declared.into_iter().map(str::to_string));
+
+            let derating = instance.string_attr(&[ATTR_DERATING]);
+            for mode in modes {
+                let effect_key = format!("{ATTR_FAILURE_PREFIX}{mode}");
+                rows.push(FmeaRow {
+                    item: item.clone(),
+                    reference: instance.reference_designator.clone(),
+                    part: is_component
+                        .then(|| instance.mpn().or_else(|| instance.value()))
+                        .flatten(),
+                    local_effect: instance
+                        .string_attr(&[effect_key.as_str()])
+                        .unwrap_or_default(),
+                    mode,
+                    derating: derating.clone(),
+                });
+            }
+        }
+        rows
+    }
+}
+
+/// Render `rows` as a CSV worksheet with empty review columns.
+pub fn fmea_csv(rows: &[FmeaRow]) -> anyhow::Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let mut header = vec![
+        "Item",
+        "Reference",
+        "Part",
+        "Failure Mode",
+        "Local Effect",
+        "Derating",
+    ];
+    header.extend(REVIEW_COLUMNS);
+    writer.write_record(&header)?;
+    for row in rows {
+        let mut record = vec![
+            row.item.clone(),
+            row.reference.clone().unwrap_or_default(),
+            row.part.clone().unwrap_or_default(),
+            mode_label(&row.mode),
+            row.local_effect.clone(),
+            row.derating.clone().unwrap_or_default(),
+        ];
+        record.extend(REVIEW_COLUMNS.iter().map(|_| String::new()));
+        writer.write_record(&record)?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+/// `short` → `Short`, `parameter_drift` → `Parameter drift`.
+fn mode_label(mode: &str) -> String {
+    let mode = mode.replace('_', " ");
+    let mut chars = mode.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlist::test_support::divider;
+    use crate::{AttributeValue, Instance, InstanceRef};
+
+    #[test]
+    fn lists_components_and_annotated_modules() {
+        let mut sch = divider();
+        let module = sch.instances.keys().next().unwrap().module.clone();
+        let type_ref = sch.instances.values().next().unwrap().type_ref.clone();
+        for instance in sch.instances.values_mut() {
+            if instance.reference_designator.as_deref() == Some("R1") {
+                for (key, value) in [
+                    ("failure_short", "VIN tied to OUT"),
+                    ("failure_parameter_drift", "OUT drifts high"),
+                    ("derating", "50% power"),
+                ] {
+                    instance.add_attribute(key, AttributeValue::String(value.into()));
+                }
+            }
+        }
+        sch.add_instance(
+            InstanceRef::new(module.clone(), vec!["Sense".into()]),
+            Instance::module(type_ref.clone())
+                .with_attribute("failure_open", AttributeValue::String("No feedback".into())),
+        );
+        sch.add_instance(
+            InstanceRef::new(module, vec!["Spare".into()]),
+            Instance::module(type_ref),
+        );
+
+        let rows = sch.fmea_rows();
+        let summary: Vec<(&str, &str, &str)> = rows
+            .iter()
+            .map(|row| {
+                (
+                    row.reference.as_deref().unwrap_or(&row.item),
+                    row.mode.as_str(),
+                    row.local_effect.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("R2", "short", ""),
+                ("R2", "open", ""),
+                ("R1", "short", "VIN tied to OUT"),
+                ("R1", "open", ""),
+                ("R1", "parameter_drift", "OUT drifts high"),
+                ("Sense", "open", "No feedback"),
+            ]
+        );
+
+        let csv = fmea_csv(&rows).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "Item,Reference,Part,Failure Mode,Local Effect,Derating,System Effect,Severity,Occurrence,Detection,RPN,Mitigation"
+            )
+        );
+        assert!(csv.contains(",Parameter drift,OUT drifts high,50% power,,,,,,\n"));
+    }
+}
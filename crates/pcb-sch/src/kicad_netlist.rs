@@ -6,28 +6,9 @@ use std::fmt::Write;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
+use crate::netlist::{netlist_components, netlist_nets, port_pads};
 use crate::{AttributeValue, InstanceKind, InstanceRef, PACKAGE_URI_PREFIX, Schematic};
 
-#[derive(Debug)]
-struct CompInfo<'a> {
-    reference: InstanceRef,
-    instance: &'a crate::Instance,
-    hier_name: String, // dot-separated instance path
-}
-
-#[derive(Debug, Clone)]
-struct Node {
-    refdes: String,
-    pad: String,
-}
-
-#[derive(Debug)]
-struct NetInfo {
-    code: u32,
-    name: String,
-    nodes: Vec<Node>,
-}
-
 #[derive(Default, Debug)]
 struct LibPartInfo {
     pins: Vec<(String, String)>, // (num, name)
@@ -65,79 +46,8 @@ pub(crate) fn format_array_as_csv(arr: &[AttributeValue]) -> String {
 /// [`Schematic::assign_reference_designators`](crate::Schematic::assign_reference_designators)
 /// before exporting.
 pub fn to_kicad_netlist(sch: &Schematic) -> String {
-    let mut components: Vec<CompInfo<'_>> = Vec::new();
-    for (inst_ref, inst) in &sch.instances {
-        if inst.kind == InstanceKind::Component {
-            let hier = inst_ref.instance_path.join(".");
-            components.push(CompInfo {
-                reference: inst_ref.clone(),
-                instance: inst,
-                hier_name: hier,
-            });
-        }
-    }
-    // Ensure deterministic output ordering.
-    // Use natural ordering so `R2` sorts before `R10`.
-    components.sort_by(|a, b| natord::compare(&a.hier_name, &b.hier_name));
-
-    //---------------------------------------------------------------------
-    // 2. Collect nets.
-    //---------------------------------------------------------------------
-
-    let mut nets: HashMap<String, NetInfo> = HashMap::new();
-
-    for (net_name, net) in &sch.nets {
-        let mut info = NetInfo {
-            code: 0,
-            name: net_name.clone(),
-            nodes: Vec::new(),
-        };
-
-        for port_ref in &net.ports {
-            // Determine the component instance that owns this port by longest-prefix match.
-            let Some(comp_ref) = sch.component_ref_for_port(port_ref) else {
-                continue; // malformed – skip
-            };
-            let refdes = sch
-                .instances
-                .get(&comp_ref)
-                .and_then(|inst| inst.reference_designator.as_deref())
-                .unwrap_or_else(|| {
-                    panic!(
-                        "component {} is missing a reference designator; call Schematic::assign_reference_designators() first",
-                        comp_ref
-                    )
-                });
-
-            // Fetch pad number from port instance attributes.
-            let pads: Vec<String> = sch
-                .instances
-                .get(port_ref)
-                .and_then(|inst| inst.attributes.get("pads"))
-                .and_then(|av| match av {
-                    AttributeValue::Array(arr) => Some(arr),
-                    _ => None,
-                })
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|av| match av {
-                            AttributeValue::String(s) => Some(s.clone()),
-                            _ => None,
-                        })
-                        .collect()
-                })
-                .unwrap_or_default();
-
-            for pad in pads {
-                info.nodes.push(Node {
-                    refdes: refdes.to_owned(),
-                    pad,
-                });
-            }
-        }
-
-        nets.insert(info.name.clone(), info);
-    }
+    let components = netlist_components(sch);
+    let nets = netlist_nets(sch);
 
     //---------------------------------------------------------------------
     // 3. Emit S-expression.
@@ -148,46 +58,35 @@ pub fn to_kicad_netlist(sch: &Schematic) -> String {
     writeln!(out, "  (design").unwrap();
     writeln!(out, "    (source \"unknown\")").unwrap();
     writeln!(out, "    (date \"\")").unwrap();
-    writeln!(out, "    (tool \"pcb\"))").unwrap();
+    write!(out, "    (tool \"pcb\")").unwrap();
+    if let Some(stamp) = &sch.stamp {
+        for (name, value) in [
+            ("PCB_GIT_DESCRIBE", &stamp.git_describe),
+            ("PCB_SOURCE_HASH", &stamp.source_hash),
+        ] {
+            write!(
+                out,
+                "\n    (textvar (name \"{name}\") \"{}\")",
+                escape_kicad_string(value)
+            )
+            .unwrap();
+        }
+    }
+    writeln!(out, ")").unwrap();
 
     //---------------- components ----------------
     writeln!(out, "  (components").unwrap();
     for comp in &components {
-        let refdes = comp.instance.reference_designator.as_deref().unwrap_or_else(|| {
-            panic!(
-                "component {} is missing a reference designator; call Schematic::assign_reference_designators() first",
-                comp.reference
-            )
-        });
-        let value_field = comp
-            .instance
-            .attributes
-            .get("mpn")
-            .or_else(|| comp.instance.attributes.get("Value"))
-            .or_else(|| comp.instance.attributes.get("Val"))
-            .or_else(|| comp.instance.attributes.get("type"))
-            .and_then(|av| match av {
-                AttributeValue::String(s) => Some(s.as_str()),
-                _ => None,
-            })
-            .unwrap_or("?");
-        let fp_attr = comp
-            .instance
-            .attributes
-            .get("footprint")
-            .and_then(|av| match av {
-                AttributeValue::String(s) => Some(s.as_str()),
-                _ => None,
-            })
-            .unwrap_or("UNKNOWN:UNKNOWN");
+        let refdes = comp.refdes;
+        let value_field = comp.value();
         let (fp_string, _lib_info) =
-            format_footprint_with_package_roots(fp_attr, &sch.package_roots);
+            format_footprint_with_package_roots(comp.footprint(), &sch.package_roots);
 
         writeln!(out, "    (comp (ref \"{}\")", escape_kicad_string(refdes)).unwrap();
         writeln!(
             out,
             "      (value \"{}\")",
-            escape_kicad_string(value_field)
+            escape_kicad_string(&value_field)
         )
         .unwrap();
         writeln!(
@@ -199,7 +98,7 @@ pub fn to_kicad_netlist(sch: &Schematic) -> String {
         writeln!(
             out,
             "      (libsource (lib \"lib\") (part \"{}\") (description \"unknown\"))",
-            escape_kicad_string(value_field)
+            escape_kicad_string(&value_field)
         )
         .unwrap();
         // Deterministic UUID from hierarchical name.
@@ -348,33 +247,15 @@ pub fn to_kicad_netlist(sch: &Schematic) -> String {
     // 5. Nets section.
     //---------------------------------------------------------------------
     writeln!(out, "  (nets").unwrap();
-    let mut net_vec: Vec<_> = nets.into_iter().collect();
-    net_vec.sort_by(|a, b| a.0.cmp(&b.0));
-    for (code, (_name, info)) in (1_u32..).zip(net_vec.iter_mut()) {
-        info.code = code;
-    }
-    for (_name, info) in net_vec {
-        // Sort nodes for deterministic ordering.
-        let mut sorted_nodes = info.nodes.clone();
-        sorted_nodes.sort_by(|a, b| {
-            let ord = natord::compare(&a.refdes, &b.refdes);
-            if ord == std::cmp::Ordering::Equal {
-                a.pad.cmp(&b.pad)
-            } else {
-                ord
-            }
-        });
-        let mut seen_nodes = HashSet::new();
-        sorted_nodes.retain(|node| seen_nodes.insert((node.refdes.clone(), node.pad.clone())));
-
+    for (code, net) in (1_u32..).zip(nets) {
         writeln!(
             out,
             "    (net (code \"{}\") (name \"{}\")",
-            info.code,
-            escape_kicad_string(&info.name)
+            code,
+            escape_kicad_string(&net.name)
         )
         .unwrap();
-        for node in sorted_nodes {
+        for node in net.nodes {
             writeln!(
                 out,
                 "      (node (ref \"{}\") (pin \"{}\") (pintype \"stereo\"))",
@@ -392,11 +273,11 @@ pub fn to_kicad_netlist(sch: &Schematic) -> String {
 }
 
 // Helper returning all pins (pad, name) for a given component reference.
-struct ComponentChildren {
-    pins: Vec<(String, String)>,
+pub(crate) struct ComponentChildren {
+    pub(crate) pins: Vec<(String, String)>,
 }
 
-fn collect_pins_for_component(
+pub(crate) fn collect_pins_for_component(
     sch: &Schematic,
     comp_ref: &InstanceRef,
 ) -> Option<ComponentChildren> {
@@ -404,20 +285,15 @@ fn collect_pins_for_component(
     let mut pins = Vec::new();
     for child_ref in comp_inst.children.values() {
         let child_inst = sch.instances.get(child_ref)?;
-        if child_inst.kind == InstanceKind::Port
-            && let Some(AttributeValue::Array(pads)) = child_inst.attributes.get("pads")
-        {
-            for pad in pads {
-                if let AttributeValue::String(pad) = pad {
-                    let pin_name = sch
-                        .component_ref_and_pin_for_port(child_ref)
-                        .and_then(|(owner_ref, pin_name)| {
-                            (owner_ref == *comp_ref).then_some(pin_name)
-                        })
-                        .unwrap_or_else(|| pad.clone());
-                    pins.push((pad.clone(), pin_name));
-                }
-            }
+        if child_inst.kind != InstanceKind::Port {
+            continue;
+        }
+        for pad in port_pads(child_inst) {
+            let pin_name = sch
+                .component_ref_and_pin_for_port(child_ref)
+                .and_then(|(owner_ref, pin_name)| (owner_ref == *comp_ref).then_some(pin_name))
+                .unwrap_or_else(|| pad.clone());
+            pins.push((pad, pin_name));
         }
     }
     Some(ComponentChildren { pins })
@@ -1032,6 +908,20 @@ mod tests {
         assert!(netlist.contains("(node (ref \"D1\") (pin \"2\") (pintype \"stereo\"))"));
     }
 
+    #[test]
+    fn stamp_is_written_as_design_textvars() {
+        let mut schematic = Schematic::new();
+        schematic.stamp = Some(crate::stamp::Stamp {
+            git_describe: "v1.2.0-3-g1a2b3c4".to_owned(),
+            source_hash: "h1:q83vEjRWeJA=".to_owned(),
+        });
+
+        let netlist = to_kicad_netlist(&schematic);
+        assert!(netlist.contains(
+            "    (tool \"pcb\")\n    (textvar (name \"PCB_GIT_DESCRIBE\") \"v1.2.0-3-g1a2b3c4\")\n    (textvar (name \"PCB_SOURCE_HASH\") \"h1:q83vEjRWeJA=\"))\n"
+        ));
+    }
+
     #[test]
     fn emits_component_internal_connectivity() {
         let module_ref = crate::ModuleRef::from_path(Path::new("/tmp/test.zen"), "<root>");
@@ -0,0 +1,607 @@
+//! KiCad schematic (`.kicad_sch`) import for round-trip verification.
+//!
+//! [`KicadSchematic::parse`] reads placed symbols, wires, junctions, and labels
+//! from a single sheet, and [`KicadSchematic::nets`] derives connectivity from
+//! their geometry the way eeschema does:
+//!
+//! * wire endpoints, pins, labels, and junctions touching the same point
+//!   connect, as does any of them lying on a wire,
+//! * labels with the same name connect,
+//! * power symbols connect every net named by their value.
+//!
+//! [`compare_connectivity`] then checks the result against a [`Schematic`],
+//! so a generate → re-import → compare harness catches generator regressions.
+//! Hierarchical sheets are not followed.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+use anyhow::{Context, Result, bail};
+use pcb_sexpr::Sexpr;
+use pcb_sexpr::kicad::{child_list, schematic_properties};
+
+use crate::Schematic;
+use crate::netlist::netlist_nets;
+
+/// Coordinates are stored in units of 0.1 µm so points compare exactly.
+const UNITS_PER_MM: f64 = 10_000.0;
+
+/// A point on the sheet, in 0.1 µm units with Y pointing down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    fn from_mm(x: f64, y: f64) -> Self {
+        Self {
+            x: (x * UNITS_PER_MM).round() as i64,
+            y: (y * UNITS_PER_MM).round() as i64,
+        }
+    }
+}
+
+/// A placed symbol with the sheet position of each pin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchematicSymbol {
+    pub lib_id: String,
+    pub reference: String,
+    pub value: String,
+    pub footprint: Option<String>,
+    /// Power symbols name a global net by their value and have no pads.
+    pub power: bool,
+    /// `(pin number, position)`.
+    pub pins: Vec<(String, Point)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelKind {
+    Local,
+    Global,
+    Hierarchical,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub kind: LabelKind,
+    pub name: String,
+    pub at: Point,
+}
+
+/// A `(refdes, pin number)` connection.
+pub type PinRef = (String, String);
+
+/// A net recovered from sheet geometry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedNet {
+    /// Lowest label or power symbol name on the net, if any.
+    pub name: Option<String>,
+    pub pins: BTreeSet<PinRef>,
+}
+
+/// One sheet of a KiCad schematic.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KicadSchematic {
+    pub symbols: Vec<SchematicSymbol>,
+    pub wires: Vec<(Point, Point)>,
+    pub junctions: Vec<Point>,
+    pub labels: Vec<Label>,
+}
+
+/// Pins of a library symbol, per unit (0 for pins shared by all units).
+#[derive(Default)]
+struct LibSymbol {
+    power: bool,
+    pins: Vec<(u32, String, f64, f64)>,
+}
+
+impl KicadSchematic {
+    pub fn parse(text: &str) -> Result<Self> {
+        let root = pcb_sexpr::parse(text).context("Failed to parse KiCad schematic")?;
+        let items = root
+            .as_list()
+            .filter(|items| items.first().and_then(Sexpr::as_sym) == Some("kicad_sch"))
+            .context("Not a KiCad schematic: expected (kicad_sch ...)")?;
+
+        let lib_symbols: HashMap<&str, LibSymbol> = child_list(items, "lib_symbols")
+            .map(|libs| libs.iter().skip(1).filter_map(lib_symbol).collect())
+            .unwrap_or_default();
+
+        let mut sheet = Self::default();
+        for node in items.iter().skip(1) {
+            let Some(list) = node.as_list() else {
+                continue;
+            };
+            match list.first().and_then(Sexpr::as_sym) {
+                Some("symbol") => sheet.symbols.push(placed_symbol(list, &lib_symbols)?),
+                Some("wire") => {
+                    let points = xy_points(list);
+                    if let [start, end] = points[..] {
+                        sheet.wires.push((start, end));
+                    }
+                }
+                Some("junction") => {
+                    if let Some((at, _)) = at(list) {
+                        sheet.junctions.push(at);
+                    }
+                }
+                Some(tag @ ("label" | "global_label" | "hierarchical_label")) => {
+                    let kind = match tag {
+                        "label" => LabelKind::Local,
+                        "global_label" => LabelKind::Global,
+                        _ => LabelKind::Hierarchical,
+                    };
+                    if let (Some(name), Some((at, _))) =
+                        (list.get(1).and_then(Sexpr::as_str), at(list))
+                    {
+                        sheet.labels.push(Label {
+                            kind,
+                            name: name.to_string(),
+                            at,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(sheet)
+    }
+
+    /// Nets with at least one non-power pin, sorted by their pins.
+    pub fn nets(&self) -> Vec<ImportedNet> {
+        let mut points = PointSets::default();
+        for &(start, end) in &self.wires {
+            points.union(start, end);
+        }
+
+        let anchors: Vec<Point> = self
+            .symbols
+            .iter()
+            .flat_map(|symbol| symbol.pins.iter().map(|(_, at)| *at))
+            .chain(self.labels.iter().map(|label| label.at))
+            .chain(self.junctions.iter().copied())
+            .chain(self.wires.iter().flat_map(|&(start, end)| [start, end]))
+            .collect();
+        for &point in &anchors {
+            points.find(point);
+            for &(start, end) in &self.wires {
+                if on_segment(point, start, end) {
+                    points.union(point, start);
+                }
+            }
+        }
+
+        // Same-named labels and power symbols share a net.
+        let mut names: BTreeMap<String, Vec<Point>> = BTreeMap::new();
+        for label in &self.labels {
+            names.entry(label.name.clone()).or_default().push(label.at);
+        }
+        for symbol in self.symbols.iter().filter(|symbol| symbol.power) {
+            names
+                .entry(symbol.value.clone())
+                .or_default()
+                .extend(symbol.pins.iter().map(|(_, at)| *at));
+        }
+        for named in names.values() {
+            for pair in named.windows(2) {
+                points.union(pair[0], pair[1]);
+            }
+        }
+
+        let mut nets: BTreeMap<Point, ImportedNet> = BTreeMap::new();
+        for symbol in self.symbols.iter().filter(|symbol| !symbol.power) {
+            for (number, at) in &symbol.pins {
+                nets.entry(points.find(*at))
+                    .or_insert_with(|| ImportedNet {
+                        name: None,
+                        pins: BTreeSet::new(),
+                    })
+                    .pins
+                    .insert((symbol.reference.clone(), number.clone()));
+            }
+        }
+        for (name, named) in &names {
+            let Some(&at) = named.first() else {
+                continue;
+            };
+            if let Some(net) = nets.get_mut(&points.find(at))
+                && net.name.is_none()
+            {
+                net.name = Some(name.clone());
+            }
+        }
+
+        let mut nets: Vec<ImportedNet> = nets.into_values().collect();
+        nets.sort_by(|a, b| a.pins.cmp(&b.pins));
+        nets
+    }
+}
+
+/// A group of pins connected on one side of the comparison only.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectivityMismatch {
+    /// Connected in the KiCad schematic but not in the design.
+    OnlyInKicad(BTreeSet<PinRef>),
+    /// Connected in the design but not in the KiCad schematic.
+    OnlyInSchematic { net: String, pins: BTreeSet<PinRef> },
+}
+
+impl fmt::Display for ConnectivityMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pins = |pins: &BTreeSet<PinRef>| {
+            pins.iter()
+                .map(|(refdes, pin)| format!("{refdes}.{pin}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match self {
+            Self::OnlyInKicad(connected) => {
+                write!(f, "only the KiCad schematic connects {}", pins(connected))
+            }
+            Self::OnlyInSchematic {
+                net,
+                pins: connected,
+            } => {
+                write!(
+                    f,
+                    "only the design connects {} (net {net})",
+                    pins(connected)
+                )
+            }
+        }
+    }
+}
+
+/// Compare the connectivity of `imported` against `schematic`, which must
+/// have reference designators assigned. Nets with fewer than two pins are
+/// ignored on both sides, since an unconnected pin has no counterpart in the
+/// sheet geometry.
+pub fn compare_connectivity(
+    imported: &KicadSchematic,
+    schematic: &Schematic,
+) -> Vec<ConnectivityMismatch> {
+    let kicad: BTreeSet<BTreeSet<PinRef>> = imported
+        .nets()
+        .into_iter()
+        .map(|net| net.pins)
+        .filter(|pins| pins.len() > 1)
+        .collect();
+
+    let mut mismatches = Vec::new();
+    let mut design = BTreeSet::new();
+    for net in netlist_nets(schematic) {
+        let pins: BTreeSet<PinRef> = net
+            .nodes
+            .into_iter()
+            .map(|node| (node.refdes, node.pad))
+            .collect();
+        if pins.len() < 2 {
+            continue;
+        }
+        if !kicad.contains(&pins) {
+            mismatches.push(ConnectivityMismatch::OnlyInSchematic {
+                net: net.name,
+                pins: pins.clone(),
+            });
+        }
+        design.insert(pins);
+    }
+    mismatches.extend(
+        kicad
+            .difference(&design)
+            .cloned()
+            .map(ConnectivityMismatch::OnlyInKicad),
+    );
+    mismatches
+}
+
+/// Union-find over sheet points.
+#[derive(Default)]
+struct PointSets {
+    parent: HashMap<Point, Point>,
+}
+
+impl PointSets {
+    fn find(&mut self, point: Point) -> Point {
+        let parent = *self.parent.entry(point).or_insert(point);
+        if parent == point {
+            return point;
+        }
+        let root = self.find(parent);
+        self.parent.insert(point, root);
+        root
+    }
+
+    fn union(&mut self, a: Point, b: Point) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parent.insert(a.max(b), a.min(b));
+        }
+    }
+}
+
+/// Whether `point` lies on the segment from `start` to `end`.
+fn on_segment(point: Point, start: Point, end: Point) -> bool {
+    let cross = (end.x - start.x) as i128 * (point.y - start.y) as i128
+        - (end.y - start.y) as i128 * (point.x - start.x) as i128;
+    cross == 0
+        && point.x >= start.x.min(end.x)
+        && point.x <= start.x.max(end.x)
+        && point.y >= start.y.min(end.y)
+        && point.y <= start.y.max(end.y)
+}
+
+fn number(node: &Sexpr) -> Option<f64> {
+    node.as_float().or_else(|| node.as_int().map(|n| n as f64))
+}
+
+/// `(at x y [angle])` of a list, as a point and angle in degrees.
+fn at(list: &[Sexpr]) -> Option<(Point, f64)> {
+    let at = child_list(list, "at")?;
+    let x = number(at.get(1)?)?;
+    let y = number(at.get(2)?)?;
+    let angle = at.get(3).and_then(number).unwrap_or(0.0);
+    Some((Point::from_mm(x, y), angle))
+}
+
+/// Points of `(pts (xy x y) ...)`.
+fn xy_points(list: &[Sexpr]) -> Vec<Point> {
+    let Some(pts) = child_list(list, "pts") else {
+        return Vec::new();
+    };
+    pts.iter()
+        .skip(1)
+        .filter_map(|node| {
+            let xy = node.as_list()?;
+            (xy.first()?.as_sym() == Some("xy"))
+                .then(|| Some(Point::from_mm(number(xy.get(1)?)?, number(xy.get(2)?)?)))
+                .flatten()
+        })
+        .collect()
+}
+
+/// `(symbol "Lib:Name" ...)` under `lib_symbols`, with pins from its
+/// `(symbol "Name_<unit>_<style>" ...)` units.
+fn lib_symbol(node: &Sexpr) -> Option<(&str, LibSymbol)> {
+    let list = node.as_list()?;
+    if list.first()?.as_sym() != Some("symbol") {
+        return None;
+    }
+    let name = list.get(1)?.as_str()?;
+    let mut symbol = LibSymbol {
+        power: child_list(list, "power").is_some(),
+        pins: Vec::new(),
+    };
+    collect_pins(list, 0, &mut symbol.pins);
+    for unit in list.iter().skip(2).filter_map(Sexpr::as_list) {
+        if unit.first().and_then(Sexpr::as_sym) != Some("symbol") {
+            continue;
+        }
+        let unit_number = unit
+            .get(1)
+            .and_then(Sexpr::as_str)
+            .and_then(|unit_name| unit_name.rsplitn(3, '_').nth(1))
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(0);
+        collect_pins(unit, unit_number, &mut symbol.pins);
+    }
+    Some((name, symbol))
+}
+
+fn collect_pins(list: &[Sexpr], unit: u32, out: &mut Vec<(u32, String, f64, f64)>) {
+    for pin in list.iter().skip(1).filter_map(Sexpr::as_list) {
+        if pin.first().and_then(Sexpr::as_sym) != Some("pin") {
+            continue;
+        }
+        let Some(number) = child_list(pin, "number")
+            .and_then(|number| number.get(1))
+            .and_then(Sexpr::as_str)
+        else {
+            continue;
+        };
+        let Some(at) = child_list(pin, "at") else {
+            continue;
+        };
+        if let (Some(x), Some(y)) = (
+            at.get(1).and_then(self::number),
+            at.get(2).and_then(self::number),
+        ) {
+            out.push((unit, number.to_string(), x, y));
+        }
+    }
+}
+
+fn placed_symbol(
+    list: &[Sexpr],
+    lib_symbols: &HashMap<&str, LibSymbol>,
+) -> Result<SchematicSymbol> {
+    let lib_id = child_list(list, "lib_id")
+        .and_then(|lib_id| lib_id.get(1))
+        .and_then(Sexpr::as_str)
+        .context("Placed symbol has no lib_id")?;
+    let lib_name = child_list(list, "lib_name")
+        .and_then(|lib_name| lib_name.get(1))
+        .and_then(Sexpr::as_str)
+        .unwrap_or(lib_id);
+    let Some(lib) = lib_symbols.get(lib_name) else {
+        bail!("Placed symbol {lib_id} is missing from lib_symbols");
+    };
+    let (origin, angle) =
+        at(list).with_context(|| format!("Placed symbol {lib_id} has no position"))?;
+    let mirror = child_list(list, "mirror").and_then(|mirror| mirror.get(1)?.as_sym());
+    let unit: u32 = child_list(list, "unit")
+        .and_then(|unit| unit.get(1))
+        .and_then(Sexpr::as_int)
+        .map_or(1, |unit| unit as u32);
+
+    // Library coordinates have Y up; rotate counter-clockwise on the sheet,
+    // then mirror.
+    let (sin, cos) = angle.to_radians().sin_cos();
+    let pins = lib
+        .pins
+        .iter()
+        .filter(|(pin_unit, ..)| *pin_unit == 0 || *pin_unit == unit)
+        .map(|(_, number, x, y)| {
+            let (x, y) = (*x, -*y);
+            let (mut dx, mut dy) = (x * cos + y * sin, -x * sin + y * cos);
+            match mirror {
+                Some("x") => dy = -dy,
+                Some("y") => dx = -dx,
+                _ => {}
+            }
+            let at = Point {
+                x: origin.x + (dx * UNITS_PER_MM).round() as i64,
+                y: origin.y + (dy * UNITS_PER_MM).round() as i64,
+            };
+            (number.clone(), at)
+        })
+        .collect();
+
+    let properties = schematic_properties(list);
+    Ok(SchematicSymbol {
+        lib_id: lib_id.to_string(),
+        reference: properties.get("Reference").cloned().unwrap_or_default(),
+        value: properties.get("Value").cloned().unwrap_or_default(),
+        footprint: properties
+            .get("Footprint")
+            .filter(|fp| !fp.is_empty())
+            .cloned(),
+        power: lib.power,
+        pins,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AttributeValue, Instance, InstanceRef, ModuleRef, Net};
+    use std::path::Path;
+
+    const SHEET: &str = r##"(kicad_sch
+  (version 20250114)
+  (lib_symbols
+    (symbol "Device:R"
+      (symbol "R_1_1"
+        (pin passive line (at 0 3.81 270) (length 1.27) (name "~") (number "1"))
+        (pin passive line (at 0 -3.81 90) (length 1.27) (name "~") (number "2"))))
+    (symbol "power:GND" (power)
+      (symbol "GND_1_1"
+        (pin power_in line (at 0 0 270) (length 0) (name "GND") (number "1")))))
+  (symbol (lib_id "Device:R") (at 100 100 0) (unit 1)
+    (property "Reference" "R1") (property "Value" "10k"))
+  (symbol (lib_id "Device:R") (at 120 100 90) (unit 1)
+    (property "Reference" "R2") (property "Value" "10k"))
+  (symbol (lib_id "Device:R") (at 140 100 0) (unit 1)
+    (property "Reference" "R3") (property "Value" "1k"))
+  (symbol (lib_id "power:GND") (at 123.81 100 0) (unit 1)
+    (property "Reference" "#PWR01") (property "Value" "GND"))
+  (symbol (lib_id "power:GND") (at 140 103.81 0) (unit 1)
+    (property "Reference" "#PWR02") (property "Value" "GND"))
+  (wire (pts (xy 100 103.81) (xy 110 103.81)))
+  (wire (pts (xy 110 103.81) (xy 110 100)))
+  (wire (pts (xy 110 100) (xy 116.19 100)))
+  (label "VOUT" (at 110 102 90))
+  (global_label "VIN" (at 100 96.19 0))
+)"##;
+
+    fn pins(pins: &[(&str, &str)]) -> BTreeSet<PinRef> {
+        pins.iter()
+            .map(|(refdes, pin)| (refdes.to_string(), pin.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn derives_nets_from_geometry() {
+        let sheet = KicadSchematic::parse(SHEET).unwrap();
+        assert_eq!(sheet.symbols.len(), 5);
+        assert_eq!(
+            sheet.symbols[1].pins,
+            vec![
+                ("1".to_string(), Point::from_mm(116.19, 100.0)),
+                ("2".to_string(), Point::from_mm(123.81, 100.0)),
+            ]
+        );
+
+        let nets = sheet.nets();
+        let named: Vec<(Option<&str>, BTreeSet<PinRef>)> = nets
+            .iter()
+            .map(|net| (net.name.as_deref(), net.pins.clone()))
+            .collect();
+        assert_eq!(
+            named,
+            vec![
+                (Some("VIN"), pins(&[("R1", "1")])),
+                (Some("VOUT"), pins(&[("R1", "2"), ("R2", "1")])),
+                (Some("GND"), pins(&[("R2", "2"), ("R3", "2")])),
+                (None, pins(&[("R3", "1")])),
+            ]
+        );
+    }
+
+    fn design(nets: &[(&str, &[(&str, &str)])]) -> Schematic {
+        let module = ModuleRef::from_path(Path::new("/tmp/board.zen"), "<root>");
+        let mut schematic = Schematic::new();
+        for refdes in ["R1", "R2", "R3"] {
+            let mut component = Instance::component(module.clone());
+            component.reference_designator = Some(refdes.to_string());
+            schematic.add_instance(
+                InstanceRef::new(module.clone(), vec![refdes.into()]),
+                component,
+            );
+        }
+        for (id, (name, connected)) in nets.iter().enumerate() {
+            let mut ports = Vec::new();
+            for (refdes, pad) in *connected {
+                let port_ref =
+                    InstanceRef::new(module.clone(), vec![refdes.to_string(), format!("P{pad}")]);
+                let mut port = Instance::port(module.clone());
+                port.attributes.insert(
+                    "pads".into(),
+                    AttributeValue::Array(vec![AttributeValue::String(pad.to_string())]),
+                );
+                schematic.add_instance(port_ref.clone(), port);
+                ports.push(port_ref);
+            }
+            schematic.add_net(Net {
+                kind: "Net".to_string(),
+                id: id as u64,
+                name: name.to_string(),
+                ports,
+                properties: HashMap::new(),
+            });
+        }
+        schematic
+    }
+
+    #[test]
+    fn compares_connectivity_with_design() {
+        let sheet = KicadSchematic::parse(SHEET).unwrap();
+        let matching = design(&[
+            ("VOUT", &[("R1", "2"), ("R2", "1")]),
+            ("GND", &[("R2", "2"), ("R3", "2")]),
+            ("VIN", &[("R1", "1")]),
+        ]);
+        assert_eq!(compare_connectivity(&sheet, &matching), vec![]);
+
+        let broken = design(&[
+            ("VOUT", &[("R1", "2"), ("R2", "1")]),
+            ("GND", &[("R1", "1"), ("R2", "2"), ("R3", "2")]),
+        ]);
+        let mismatches = compare_connectivity(&sheet, &broken);
+        assert_eq!(
+            mismatches,
+            vec![
+                ConnectivityMismatch::OnlyInSchematic {
+                    net: "GND".to_string(),
+                    pins: pins(&[("R1", "1"), ("R2", "2"), ("R3", "2")]),
+                },
+                ConnectivityMismatch::OnlyInKicad(pins(&[("R2", "2"), ("R3", "2")])),
+            ]
+        );
+        assert_eq!(
+            mismatches[0].to_string(),
+            "only the design connects R1.1, R2.2, R3.2 (net GND)"
+        );
+    }
+}
@@ -11,14 +11,30 @@
 //!   stable [`netlist::InstanceRef`].
 //! * `nets` – all electrical nets keyed by their deduplicated name.
 
+pub mod allegro_netlist;
+pub mod annotation;
+pub mod anonymize;
 pub mod bom;
 #[cfg(feature = "table")]
 mod bom_table;
+pub mod capacitance;
+pub mod eagle_netlist;
+pub mod fmea;
 pub mod hierarchical_layout;
 pub mod kicad_netlist;
+pub mod kicad_schematic;
+pub mod lifecycle;
 pub mod natural_string;
+pub mod net_stats;
+pub mod netlist;
 pub mod physical;
+pub mod pinout;
 pub mod position;
+pub mod query;
+pub mod refdes_policy;
+pub mod revision;
+pub mod stamp;
+pub mod values;
 
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::hash::{Hash, Hasher};
@@ -28,20 +44,50 @@ use serde::{Deserialize, Serialize};
 
 use crate::physical::PhysicalValue;
 use crate::position::Position;
+use crate::refdes_policy::{RefdesAssignment, RefdesConflict, RefdesPolicy, RefdesRange};
 
 /// Helper type alias – we map the original Atopile `Symbol` to a plain
 /// UTF-8 `String`.
 pub type Symbol = String;
 
+/// Serialize a `HashMap` with its entries in key order, so serialized
+/// schematics are byte-identical between runs regardless of hash seeds.
+fn serialize_sorted<S, K, V>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    K: Clone + Into<String>,
+    V: Serialize,
+{
+    map.iter()
+        .map(|(key, value)| (key.clone().into(), value))
+        .collect::<BTreeMap<String, &V>>()
+        .serialize(serializer)
+}
+
 /// Attribute key that stores the path to the KiCad PCB layout associated with
 /// a module or instance. Used with `AttributeValue::String`.
 pub const ATTR_LAYOUT_PATH: &str = "layout_path";
 
+/// Attribute key marking a module with a `layout_path` as a separate board
+/// (e.g. a flex daughter board) rather than a fragment of its parent's layout.
+/// Used with `AttributeValue::Boolean`.
+pub const ATTR_LAYOUT_BOARD: &str = "layout_board";
+
 /// Attribute key that stores a list of layout hint expressions (e.g. placement
 /// constraints). Used with `AttributeValue::Array` where each element is an
 /// `AttributeValue::String`.
 pub const ATTR_LAYOUT_HINTS: &str = "layout_hints";
 
+/// Attribute key marking a component as a net tie. Holds the pads that are
+/// intentionally shorted, in KiCad's `"1, 2"` form, either as a single
+/// `AttributeValue::String` or an `AttributeValue::Array` of groups.
+pub const ATTR_NET_TIE_PAD_GROUPS: &str = "net_tie_pad_groups";
+
+/// Port attribute listing the KiCad electrical types (`input`, `power_in`,
+/// ...) of the symbol pins behind the port, including pin alternates. Used
+/// with `AttributeValue::Array` of `AttributeValue::String`, sorted.
+pub const ATTR_PIN_TYPES: &str = "pin_types";
+
 /// URI prefix for stable, machine-independent package references.
 pub const PACKAGE_URI_PREFIX: &str = "package://";
 
@@ -547,6 +593,7 @@ pub struct Net {
     pub id: u64,
     pub name: String,
     pub ports: Vec<InstanceRef>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub properties: HashMap<Symbol, AttributeValue>,
 }
 
@@ -554,12 +601,18 @@ pub struct Net {
 pub struct Instance {
     pub type_ref: ModuleRef,
     pub kind: InstanceKind,
+    #[serde(serialize_with = "serialize_sorted")]
     pub attributes: HashMap<Symbol, AttributeValue>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub children: HashMap<Symbol, InstanceRef>,
     pub reference_designator: Option<String>,
     #[serde(default, skip_serializing_if = "InternalConnectivity::is_empty")]
     pub internal_connectivity: InternalConnectivity,
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
     pub symbol_positions: HashMap<String, Position>,
 }
 
@@ -746,24 +799,71 @@ impl Instance {
     pub fn matcher(&self) -> Option<String> {
         self.string_attr(&["Matcher", "matcher"])
     }
+
+    /// Free-form instructions for the assembler, e.g. "Install after reflow".
+    pub fn assembly_notes(&self) -> Option<String> {
+        self.string_attr(&[crate::bom::ATTR_ASSEMBLY_NOTES, "Assembly Notes"])
+            .map(|notes| notes.trim().to_string())
+            .filter(|notes| !notes.is_empty())
+    }
+
+    /// Lifecycle status from the `lifecycle` attribute, if present and valid.
+    pub fn lifecycle(&self) -> Option<crate::lifecycle::Lifecycle> {
+        self.string_attr(&[crate::lifecycle::ATTR_LIFECYCLE, "Lifecycle"])
+            .and_then(|value| value.parse().ok())
+    }
+
+    /// Pad groups this component intentionally shorts, if it is a net tie.
+    ///
+    /// Groups with fewer than two pads are dropped.
+    pub fn net_tie_pad_groups(&self) -> Vec<BTreeSet<String>> {
+        let groups: Vec<&str> = match self.attributes.get(ATTR_NET_TIE_PAD_GROUPS) {
+            Some(AttributeValue::String(group)) => vec![group.as_str()],
+            Some(AttributeValue::Array(groups)) => {
+                groups.iter().filter_map(AttributeValue::string).collect()
+            }
+            _ => Vec::new(),
+        };
+
+        groups
+            .into_iter()
+            .map(|group| {
+                group
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|pad| !pad.is_empty())
+                    .map(str::to_string)
+                    .collect::<BTreeSet<_>>()
+            })
+            .filter(|group| group.len() > 1)
+            .collect()
+    }
+
+    pub fn is_net_tie(&self) -> bool {
+        !self.net_tie_pad_groups().is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 /// Complete schematic description (instances + nets).
 pub struct Schematic {
     /// Every instance in the design, keyed by its fully-qualified reference.
+    #[serde(serialize_with = "serialize_sorted")]
     pub instances: HashMap<InstanceRef, Instance>,
 
     /// Electrical nets, keyed by their **unique** name.
+    #[serde(serialize_with = "serialize_sorted")]
     pub nets: HashMap<String, Net>,
 
     /// Root module reference.
     pub root_ref: Option<InstanceRef>,
 
     /// Symbol library - maps symbol paths to their s-expression content
+    #[serde(serialize_with = "serialize_sorted")]
     pub symbols: HashMap<String, String>,
 
     /// Path remapping rules for moved() directives (old_path -> new_path)
+    #[serde(serialize_with = "serialize_sorted")]
     pub moved_paths: HashMap<String, String>,
 
     /// Package roots for resolving package:// URIs.
@@ -771,6 +871,14 @@ pub struct Schematic {
     /// to absolute filesystem path.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub package_roots: BTreeMap<String, PathBuf>,
+
+    /// Provenance to write into artifacts generated from this schematic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stamp: Option<stamp::Stamp>,
+
+    /// Revision history of the board, oldest first (`[[board.revisions]]`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revisions: Vec<revision::Revision>,
 }
 
 impl Schematic {
@@ -779,6 +887,11 @@ impl Schematic {
         Self::default()
     }
 
+    /// The board's current revision: the last entry of [`Schematic::revisions`].
+    pub fn revision(&self) -> Option<&revision::Revision> {
+        self.revisions.last()
+    }
+
     /// Serialize the schematic to canonical (deterministic) JSON string.
     /// Uses RFC 8785 canonical JSON format with sorted keys.
     pub fn to_json(&self) -> anyhow::Result<String> {
@@ -870,6 +983,19 @@ impl Schematic {
     ///
     /// Returns a map from InstanceRef to the assigned reference designator.
     pub fn assign_reference_designators(&mut self) -> HashMap<InstanceRef, String> {
+        self.assign_reference_designators_with_policy(&RefdesPolicy::default())
+            .refdes
+    }
+
+    /// Assign reference designators, numbering new ones according to `policy`.
+    ///
+    /// Pre-assigned designators are kept even when they violate the policy, and
+    /// reported in [`RefdesAssignment::conflicts`] along with ranges that ran out
+    /// of numbers. Path hints that violate the policy are ignored.
+    pub fn assign_reference_designators_with_policy(
+        &mut self,
+        policy: &RefdesPolicy,
+    ) -> RefdesAssignment {
         struct ComponentForRefdes<'a> {
             hier: String,
             inst_ref: InstanceRef,
@@ -906,6 +1032,12 @@ impl Schematic {
             .iter()
             .map(|component| get_component_prefix(component.inst))
             .collect();
+        let ranges: Vec<RefdesRange<'_>> = components
+            .iter()
+            .zip(&prefixes)
+            .map(|(component, prefix)| policy.range(prefix, &component.hier))
+            .collect();
+        let mut conflicts = Vec::new();
 
         let mut used_numbers_by_prefix: HashMap<String, std::collections::HashSet<u32>> =
             HashMap::new();
@@ -944,6 +1076,14 @@ impl Schematic {
                 .or_default()
                 .insert(number);
             assigned_numbers[i] = Some(number);
+            if !ranges[i].permits(number) {
+                conflicts.push(RefdesConflict {
+                    instance: components[i].inst_ref.clone(),
+                    refdes: format!("{}{number}", prefixes[i]),
+                    message: "kept from the existing design, but outside the numbering policy"
+                        .to_owned(),
+                });
+            }
         }
 
         // Opportunistically assign hints for components that didn't have a fixed refdes.
@@ -953,6 +1093,7 @@ impl Schematic {
             .map(|(i, component)| {
                 assigned_numbers[i].is_none().then_some(())?;
                 refdes_alloc::extract_hint_number(&component.inst_ref.instance_path, &prefixes[i])
+                    .filter(|number| ranges[i].permits(*number))
             })
             .collect();
 
@@ -997,28 +1138,50 @@ impl Schematic {
 
         let mut ref_map: HashMap<InstanceRef, String> = HashMap::new();
 
-        let mut next_number_by_prefix: HashMap<String, u32> = HashMap::new();
+        // Keyed by prefix and range start, so each module region counts on its own.
+        let mut next_number_by_range: HashMap<(String, u32), u32> = HashMap::new();
 
         for (i, component) in components.into_iter().enumerate() {
             let prefix = prefixes[i].clone();
-            let number = assigned_numbers[i].unwrap_or_else(|| {
-                let used = used_numbers_by_prefix.entry(prefix.clone()).or_default();
-                let next = next_number_by_prefix.entry(prefix.clone()).or_insert(1);
-                while used.contains(next) {
+            let range = ranges[i];
+            let number = match assigned_numbers[i] {
+                Some(number) => number,
+                None => {
+                    let used = used_numbers_by_prefix.entry(prefix.clone()).or_default();
+                    let next = next_number_by_range
+                        .entry((prefix.clone(), range.start))
+                        .or_insert(range.start);
+                    while used.contains(next) || range.skips(*next) {
+                        *next += 1;
+                    }
+                    let number = *next;
+                    used.insert(number);
                     *next += 1;
+                    if let Some(end) = range.end
+                        && number > end
+                    {
+                        conflicts.push(RefdesConflict {
+                            instance: component.inst_ref.clone(),
+                            refdes: format!("{prefix}{number}"),
+                            message: format!(
+                                "assigned past the end of the {prefix}{}-{prefix}{end} range",
+                                range.start
+                            ),
+                        });
+                    }
+                    number
                 }
-                let number = *next;
-                used.insert(number);
-                *next += 1;
-                number
-            });
+            };
 
             let refdes = format!("{prefix}{number}");
             component.inst.reference_designator = Some(refdes.clone());
             ref_map.insert(component.inst_ref, refdes);
         }
 
-        ref_map
+        RefdesAssignment {
+            refdes: ref_map,
+            conflicts,
+        }
     }
 
     pub fn bom(&self) -> bom::Bom {
@@ -1169,6 +1332,31 @@ mod tests {
         assert_eq!(h1.finish(), h2.finish());
     }
 
+    #[test]
+    fn serialization_does_not_depend_on_insertion_order() {
+        let module = ModuleRef::from_path(Path::new("/tmp/board.zen"), "<root>");
+        let build = |names: &[&str]| {
+            let mut sch = Schematic::new();
+            for name in names {
+                let mut instance = Instance::component(module.clone());
+                for key in names {
+                    instance.add_attribute(*key, AttributeValue::String(key.to_string()));
+                }
+                sch.add_instance(
+                    InstanceRef::new(module.clone(), vec![name.to_string()]),
+                    instance,
+                );
+                sch.symbols.insert(name.to_string(), String::new());
+            }
+            serde_json::to_string(&sch).unwrap()
+        };
+
+        let names = ["R1", "C7", "U3", "J2", "D9", "Q4", "L5", "Y6"];
+        let mut reversed = names;
+        reversed.reverse();
+        assert_eq!(build(&names), build(&reversed));
+    }
+
     #[test]
     fn package_uri_supports_workspace_and_versioned_coordinates() {
         let mut roots = BTreeMap::new();
@@ -1576,6 +1764,117 @@ mod tests {
         assert_ne!(a, b);
     }
 
+    fn policy_component(schematic: &mut Schematic, path: &[&str], prefix: &str) -> InstanceRef {
+        let mod_ref = ModuleRef::from_path(Path::new("/test.pmod"), "TestModule");
+        let inst_ref = InstanceRef::new(
+            mod_ref.clone(),
+            path.iter().map(|segment| segment.to_string()).collect(),
+        );
+        let inst = Instance::component(mod_ref).with_attribute("prefix", prefix.to_string());
+        schematic.add_instance(inst_ref.clone(), inst);
+        inst_ref
+    }
+
+    #[test]
+    fn assign_refdes_follows_policy_ranges_and_module_offsets() {
+        let mut schematic = Schematic::new();
+        let j = ["j1", "j2", "j3"].map(|name| policy_component(&mut schematic, &[name], "J"));
+        let tp = ["tp1", "tp2"].map(|name| policy_component(&mut schematic, &[name], "TP"));
+        let r = ["r1", "r2"].map(|name| policy_component(&mut schematic, &["power", name], "R"));
+
+        let policy = RefdesPolicy::new(
+            [
+                (
+                    "J".to_string(),
+                    refdes_policy::PrefixRule {
+                        start: Some(100),
+                        end: Some(101),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "TP".to_string(),
+                    refdes_policy::PrefixRule {
+                        start: Some(500),
+                        skip: [501].into(),
+                        ..Default::default()
+                    },
+                ),
+            ],
+            [("power".to_string(), 1000)],
+        )
+        .unwrap();
+        let assignment = schematic.assign_reference_designators_with_policy(&policy);
+
+        let refdes = |inst_ref: &InstanceRef| assignment.refdes[inst_ref].as_str();
+        assert_eq!(j.each_ref().map(refdes), ["J100", "J101", "J102"]);
+        assert_eq!(tp.each_ref().map(refdes), ["TP500", "TP502"]);
+        assert_eq!(r.each_ref().map(refdes), ["R1001", "R1002"]);
+
+        assert_eq!(assignment.conflicts.len(), 1);
+        assert_eq!(assignment.conflicts[0].refdes, "J102");
+        assert_eq!(assignment.conflicts[0].instance, j[2]);
+    }
+
+    #[test]
+    fn assign_refdes_policy_keeps_existing_designators() {
+        let mut schematic = Schematic::new();
+        let kept = policy_component(&mut schematic, &["a"], "R");
+        schematic
+            .instances
+            .get_mut(&kept)
+            .unwrap()
+            .reference_designator = Some("R13".to_string());
+        let hinted = policy_component(&mut schematic, &["R2", "b"], "R");
+
+        let policy = RefdesPolicy::new(
+            [(
+                "R".to_string(),
+                refdes_policy::PrefixRule {
+                    start: Some(10),
+                    skip: [13].into(),
+                    ..Default::default()
+                },
+            )],
+            [],
+        )
+        .unwrap();
+        let assignment = schematic.assign_reference_designators_with_policy(&policy);
+
+        assert_eq!(assignment.refdes[&kept], "R13");
+        assert_eq!(assignment.refdes[&hinted], "R10");
+        assert_eq!(assignment.conflicts.len(), 1);
+        assert_eq!(assignment.conflicts[0].instance, kept);
+    }
+
+    #[test]
+    fn refdes_policy_rejects_inverted_ranges() {
+        let rule = refdes_policy::PrefixRule {
+            start: Some(10),
+            end: Some(5),
+            ..Default::default()
+        };
+        let err = RefdesPolicy::new([("J".to_string(), rule)], []).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Refdes range for 'J' ends before it starts"
+        );
+    }
+
+    #[test]
+    fn net_tie_pad_groups_parse_kicad_form() {
+        let mod_ref = ModuleRef::from_path(Path::new("/test.zen"), "NetTie");
+        let tie = Instance::component(mod_ref.clone())
+            .with_attribute(ATTR_NET_TIE_PAD_GROUPS, "1, 2".to_string());
+        let expected: BTreeSet<String> = ["1", "2"].into_iter().map(String::from).collect();
+        assert_eq!(tie.net_tie_pad_groups(), vec![expected]);
+        assert!(tie.is_net_tie());
+
+        let plain =
+            Instance::component(mod_ref).with_attribute(ATTR_NET_TIE_PAD_GROUPS, "1".to_string());
+        assert!(!plain.is_net_tie());
+    }
+
     #[test]
     fn internal_connectivity_normalizes_groups() {
         let connectivity = InternalConnectivity::new(
@@ -0,0 +1,86 @@
+//! Component lifecycle status.
+//!
+//! Packages mark parts that should not be used in new designs with a
+//! `lifecycle` property, e.g. `Component(..., properties={"lifecycle": "nrnd"})`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Attribute key that stores a component's lifecycle status.
+pub const ATTR_LIFECYCLE: &str = "lifecycle";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lifecycle {
+    /// In production and recommended for new designs.
+    Active,
+    /// Not recommended for new designs.
+    Nrnd,
+    /// Superseded by another part in the same package.
+    Deprecated,
+    /// End of life; no longer manufactured.
+    Eol,
+}
+
+impl Lifecycle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Lifecycle::Active => "active",
+            Lifecycle::Nrnd => "nrnd",
+            Lifecycle::Deprecated => "deprecated",
+            Lifecycle::Eol => "eol",
+        }
+    }
+
+    /// Human-readable description used in diagnostics and BOM output.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Lifecycle::Active => "active",
+            Lifecycle::Nrnd => "not recommended for new designs",
+            Lifecycle::Deprecated => "deprecated",
+            Lifecycle::Eol => "end of life",
+        }
+    }
+
+    /// Whether a design using this part should be flagged.
+    pub fn is_flagged(&self) -> bool {
+        !matches!(self, Lifecycle::Active)
+    }
+}
+
+impl fmt::Display for Lifecycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Lifecycle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "active" => Ok(Lifecycle::Active),
+            "nrnd" => Ok(Lifecycle::Nrnd),
+            "deprecated" => Ok(Lifecycle::Deprecated),
+            "eol" | "obsolete" => Ok(Lifecycle::Eol),
+            other => Err(format!(
+                "unknown lifecycle '{other}' (expected active, nrnd, deprecated, or eol)"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_case_insensitively_with_obsolete_alias() {
+        assert_eq!("NRND".parse::<Lifecycle>(), Ok(Lifecycle::Nrnd));
+        assert_eq!(" Obsolete ".parse::<Lifecycle>(), Ok(Lifecycle::Eol));
+        assert!("retired".parse::<Lifecycle>().is_err());
+        assert!(!Lifecycle::Active.is_flagged());
+    }
+}
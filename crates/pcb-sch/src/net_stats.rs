@@ -0,0 +1,156 @@
+//! Connectivity statistics of each net, stored as net properties so tools
+//! reading a schematic don't have to walk its ports again.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{AttributeValue, InstanceRef, Net, Schematic, Symbol};
+
+/// Net property: number of component pins on the net.
+pub const PROP_PIN_COUNT: &str = "pin_count";
+
+/// Net property: number of distinct components the net connects.
+pub const PROP_COMPONENT_COUNT: &str = "component_count";
+
+/// Net property: most module boundaries crossed between two components on
+/// the net. Components in the same module are 0 apart, a parent and child
+/// module 1, and sibling modules 2.
+pub const PROP_HIERARCHY_SPAN: &str = "hierarchy_span";
+
+/// Connectivity statistics of one net.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetStats {
+    pub pin_count: usize,
+    pub component_count: usize,
+    pub hierarchy_span: usize,
+}
+
+impl Schematic {
+    /// Statistics of `net`. Ports that belong to no component are skipped.
+    pub fn net_stats(&self, net: &Net) -> NetStats {
+        let owners: Vec<InstanceRef> = net
+            .ports
+            .iter()
+            .filter_map(|port| self.component_ref_for_port(port))
+            .collect();
+        let components: HashSet<&InstanceRef> = owners.iter().collect();
+        let modules: Vec<&[Symbol]> = components
+            .iter()
+            .map(|component| {
+                let path = &component.instance_path;
+                &path[..path.len().saturating_sub(1)]
+            })
+            .collect();
+        NetStats {
+            pin_count: owners.len(),
+            component_count: components.len(),
+            hierarchy_span: hierarchy_span(&modules),
+        }
+    }
+
+    /// Store [`Schematic::net_stats`] of every net as its [`PROP_PIN_COUNT`],
+    /// [`PROP_COMPONENT_COUNT`], and [`PROP_HIERARCHY_SPAN`] properties,
+    /// replacing earlier values.
+    pub fn annotate_net_stats(&mut self) {
+        let stats: BTreeMap<String, NetStats> = self
+            .nets
+            .iter()
+            .map(|(name, net)| (name.clone(), self.net_stats(net)))
+            .collect();
+        for (name, stats) in stats {
+            let net = self.nets.get_mut(&name).expect("net exists");
+            for (key, value) in [
+                (PROP_PIN_COUNT, stats.pin_count),
+                (PROP_COMPONENT_COUNT, stats.component_count),
+                (PROP_HIERARCHY_SPAN, stats.hierarchy_span),
+            ] {
+                net.properties
+                    .insert(key.to_string(), AttributeValue::Json(value.into()));
+            }
+        }
+    }
+}
+
+/// Longest distance in the module tree between two of `modules`, each given
+/// as its instance path.
+fn hierarchy_span(modules: &[&[Symbol]]) -> usize {
+    if modules.is_empty() {
+        0
+    } else {
+        height_and_span(modules).1
+    }
+}
+
+/// Height of the deepest of `modules` below the current node, and the
+/// longest distance between two of them. Paths are relative to the node.
+fn height_and_span(modules: &[&[Symbol]]) -> (usize, usize) {
+    let mut children: BTreeMap<&Symbol, Vec<&[Symbol]>> = BTreeMap::new();
+    let mut heights = Vec::new();
+    for module in modules {
+        match module.split_first() {
+            Some((head, tail)) => children.entry(head).or_default().push(tail),
+            None if heights.is_empty() => heights.push(0),
+            None => {}
+        }
+    }
+
+    let mut span = 0;
+    for tails in children.values() {
+        let (height, child_span) = height_and_span(tails);
+        heights.push(height + 1);
+        span = span.max(child_span);
+    }
+    heights.sort_unstable_by(|a, b| b.cmp(a));
+    if let [first, second, ..] = heights[..] {
+        span = span.max(first + second);
+    }
+    (heights.first().copied().unwrap_or(0), span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlist::test_support::divider;
+
+    #[test]
+    fn counts_pins_and_components() {
+        let mut sch = divider();
+        sch.annotate_net_stats();
+
+        let property = |key: &str| match &sch.nets["OUT"].properties[key] {
+            AttributeValue::Json(value) => value.as_u64(),
+            _ => None,
+        };
+        assert_eq!(property(PROP_PIN_COUNT), Some(2));
+        assert_eq!(property(PROP_COMPONENT_COUNT), Some(2));
+        assert_eq!(property(PROP_HIERARCHY_SPAN), Some(0));
+        assert_eq!(
+            sch.net_stats(&sch.nets["VIN"]),
+            NetStats {
+                pin_count: 1,
+                component_count: 1,
+                hierarchy_span: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn span_counts_module_boundaries() {
+        let path = |p: &str| -> Vec<Symbol> {
+            p.split('.')
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect()
+        };
+        let span = |paths: &[&str]| {
+            let paths: Vec<Vec<Symbol>> = paths.iter().map(|p| path(p)).collect();
+            let refs: Vec<&[Symbol]> = paths.iter().map(Vec::as_slice).collect();
+            hierarchy_span(&refs)
+        };
+        assert_eq!(span(&[]), 0);
+        assert_eq!(span(&[""]), 0);
+        assert_eq!(span(&["", "power"]), 1);
+        assert_eq!(span(&["power.ldo", "mcu"]), 3);
+        assert_eq!(span(&["power.ldo", "power.buck", "power"]), 2);
+        assert_eq!(span(&["a.b.c", "a.b.d.e", "a"]), 3);
+    }
+}
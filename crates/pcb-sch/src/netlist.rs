@@ -0,0 +1,246 @@
+//! Netlist export in the formats partners import, and the component/net
+//! collection they share.
+//!
+//! Every exporter sees the same reference designators, values, footprints,
+//! and `(refdes, pad)` connections, so netlists of one design agree across
+//! formats.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use crate::values::normalize_value;
+use crate::{AttributeValue, Instance, InstanceKind, InstanceRef, Schematic};
+
+/// Netlist formats `pcb` can write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NetlistFormat {
+    /// KiCad S-expression netlist (`.net`).
+    Kicad,
+    /// Allegro/OrCAD third-party (Telesis) netlist (`.tel`).
+    Allegro,
+    /// Eagle XML schematic with parts and nets (`.sch`).
+    Eagle,
+}
+
+impl NetlistFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            NetlistFormat::Kicad => "net",
+            NetlistFormat::Allegro => "tel",
+            NetlistFormat::Eagle => "sch",
+        }
+    }
+}
+
+/// Export `sch` as a netlist in `format`.
+///
+/// Component instances must already have reference designators; see
+/// [`Schematic::assign_reference_designators`].
+pub fn export_netlist(sch: &Schematic, format: NetlistFormat) -> String {
+    match format {
+        NetlistFormat::Kicad => crate::kicad_netlist::to_kicad_netlist(sch),
+        NetlistFormat::Allegro => crate::allegro_netlist::to_allegro_netlist(sch),
+        NetlistFormat::Eagle => crate::eagle_netlist::to_eagle_netlist(sch),
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct NetlistComponent<'a> {
+    pub(crate) reference: InstanceRef,
+    pub(crate) instance: &'a Instance,
+    /// Dot-separated instance path.
+    pub(crate) hier_name: String,
+    pub(crate) refdes: &'a str,
+}
+
+impl<'a> NetlistComponent<'a> {
+    /// Part value: the MPN, else a `Value`/`Val`/`type` attribute, else `?`.
+    /// A `Value`/`Val` is normalized, e.g. `4k7` becomes `4.7k`.
+    pub(crate) fn value(&self) -> Cow<'a, str> {
+        ["mpn", "Value", "Val", "type"]
+            .iter()
+            .find_map(|key| {
+                let value = self.instance.attributes.get(*key)?.string()?;
+                Some(match *key {
+                    "Value" | "Val" => normalize_value(value),
+                    _ => Cow::Borrowed(value),
+                })
+            })
+            .unwrap_or(Cow::Borrowed("?"))
+    }
+
+    /// Raw `footprint` attribute (a path or `lib:name`), or `UNKNOWN:UNKNOWN`.
+    pub(crate) fn footprint(&self) -> &'a str {
+        self.instance
+            .attributes
+            .get("footprint")
+            .and_then(AttributeValue::string)
+            .unwrap_or("UNKNOWN:UNKNOWN")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct NetlistNode {
+    pub(crate) refdes: String,
+    pub(crate) pad: String,
+    /// Name of the component pin the pad belongs to.
+    pub(crate) pin: String,
+}
+
+#[derive(Debug)]
+pub(crate) struct NetlistNet {
+    pub(crate) name: String,
+    pub(crate) nodes: Vec<NetlistNode>,
+}
+
+fn refdes<'a>(instance: &'a Instance, reference: &InstanceRef) -> &'a str {
+    instance.reference_designator.as_deref().unwrap_or_else(|| {
+        panic!(
+            "component {reference} is missing a reference designator; call Schematic::assign_reference_designators() first"
+        )
+    })
+}
+
+/// Component instances in natural hierarchical-name order, so `R2` sorts
+/// before `R10`.
+pub(crate) fn netlist_components(sch: &Schematic) -> Vec<NetlistComponent<'_>> {
+    let mut components: Vec<NetlistComponent<'_>> = sch
+        .instances
+        .iter()
+        .filter(|(_, instance)| instance.kind == InstanceKind::Component)
+        .map(|(reference, instance)| NetlistComponent {
+            reference: reference.clone(),
+            instance,
+            hier_name: reference.instance_path.join("."),
+            refdes: refdes(instance, reference),
+        })
+        .collect();
+    components.sort_by(|a, b| natord::compare(&a.hier_name, &b.hier_name));
+    components
+}
+
+/// Pads assigned to a port instance.
+pub(crate) fn port_pads(port: &Instance) -> Vec<String> {
+    match port.attributes.get("pads") {
+        Some(AttributeValue::Array(pads)) => pads
+            .iter()
+            .filter_map(|pad| pad.string().map(str::to_owned))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Nets sorted by name, each with its `(refdes, pad)` nodes in natural
+/// refdes order and without duplicates.
+pub(crate) fn netlist_nets(sch: &Schematic) -> Vec<NetlistNet> {
+    let mut nets: Vec<NetlistNet> = sch
+        .nets
+        .iter()
+        .map(|(name, net)| {
+            let mut nodes = Vec::new();
+            for port_ref in &net.ports {
+                // Determine the component instance that owns this port by longest-prefix match.
+                let Some(comp_ref) = sch.component_ref_for_port(port_ref) else {
+                    continue; // malformed – skip
+                };
+                let Some(component) = sch.instances.get(&comp_ref) else {
+                    continue;
+                };
+                let refdes = refdes(component, &comp_ref);
+                let pin = sch
+                    .component_ref_and_pin_for_port(port_ref)
+                    .map(|(_, pin)| pin);
+                let pads = sch
+                    .instances
+                    .get(port_ref)
+                    .map(port_pads)
+                    .unwrap_or_default();
+                for pad in pads {
+                    nodes.push(NetlistNode {
+                        refdes: refdes.to_owned(),
+                        pin: pin.clone().unwrap_or_else(|| pad.clone()),
+                        pad,
+                    });
+                }
+            }
+            nodes.sort_by(|a, b| {
+                natord::compare(&a.refdes, &b.refdes).then_with(|| a.pad.cmp(&b.pad))
+            });
+            let mut seen = HashSet::new();
+            nodes.retain(|node| seen.insert((node.refdes.clone(), node.pad.clone())));
+            NetlistNet {
+                name: name.clone(),
+                nodes,
+            }
+        })
+        .collect();
+    nets.sort_by(|a, b| a.name.cmp(&b.name));
+    nets
+}
+
+/// Footprint name without its library, e.g. `R_0402_1005Metric` for both
+/// `.../R_0402_1005Metric.kicad_mod` and `Resistor_SMD:R_0402_1005Metric`.
+pub(crate) fn footprint_name(component: &NetlistComponent<'_>) -> String {
+    let footprint = component.footprint();
+    let name = match footprint.strip_suffix(".kicad_mod") {
+        Some(path) => path.rsplit(['/', '\\']).next().unwrap_or(path),
+        None => footprint.rsplit(':').next().unwrap_or(footprint),
+    };
+    name.to_string()
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    use crate::{AttributeValue, Instance, InstanceRef, ModuleRef, Net, Schematic};
+
+    /// R1 and R2 in series between `VIN` and `GND`, with an `OUT` midpoint.
+    pub(crate) fn divider() -> Schematic {
+        let module_ref = ModuleRef::from_path(Path::new("/tmp/divider.zen"), "<root>");
+        let mut schematic = Schematic::new();
+        let mut port_refs = HashMap::new();
+        for (name, refdes) in [("R_TOP", "R1"), ("R_BOT", "R2")] {
+            let comp_ref = InstanceRef::new(module_ref.clone(), vec![name.into()]);
+            let mut component = Instance::component(module_ref.clone());
+            component.reference_designator = Some(refdes.to_owned());
+            component.attributes.insert(
+                "footprint".into(),
+                AttributeValue::String("Resistor_SMD:R_0402_1005Metric".to_owned()),
+            );
+            component
+                .attributes
+                .insert("Value".into(), AttributeValue::String("10k".to_owned()));
+            for (pin, pad) in [("P1", "1"), ("P2", "2")] {
+                let port_ref = InstanceRef::new(module_ref.clone(), vec![name.into(), pin.into()]);
+                let mut port = Instance::port(module_ref.clone());
+                port.attributes.insert(
+                    "pads".into(),
+                    AttributeValue::Array(vec![AttributeValue::String(pad.to_owned())]),
+                );
+                component.add_child(pin, port_ref.clone());
+                schematic.add_instance(port_ref.clone(), port);
+                port_refs.insert((refdes, pin), port_ref);
+            }
+            schematic.add_instance(comp_ref, component);
+        }
+        for (id, (name, ports)) in [
+            ("VIN", vec![("R1", "P1")]),
+            ("OUT", vec![("R1", "P2"), ("R2", "P1")]),
+            ("GND", vec![("R2", "P2")]),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            schematic.add_net(Net {
+                kind: "Net".to_owned(),
+                id: id as u64,
+                name: name.to_owned(),
+                ports: ports.iter().map(|key| port_refs[key].clone()).collect(),
+                properties: HashMap::new(),
+            });
+        }
+        schematic
+    }
+}
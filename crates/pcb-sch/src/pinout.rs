@@ -0,0 +1,440 @@
+//! Pin-to-net assignments of a single component, exported for firmware and
+//! RTL teams and as connector pinout documentation.
+//!
+//! The CSV, Markdown, and SVG outputs list every pad of the component with
+//! its signal description and voltage domain. The description comes from a
+//! `pinout_<pin>` (or `pinout_<pad>`) property of the component, and the
+//! voltage domain from the `voltage` of the connected net:
+//!
+//! ```python
+//! Component(..., properties={
+//!     "pinout_SDA": "I2C data to the sensor board",
+//! })
+//! ```
+//!
+//! The Xilinx XDC and Quartus QSF outputs are constraint stubs: one pin
+//! location per signal net, with the net name turned into an HDL port name.
+//! Power and ground nets, and nets on more than one pad of the component, are
+//! listed as comments instead.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::kicad_netlist::collect_pins_for_component;
+use crate::netlist::{netlist_components, netlist_nets};
+use crate::{Instance, Schematic};
+
+/// Prefix of the component properties describing the signal on a pin, e.g.
+/// `pinout_SDA`.
+pub const ATTR_PINOUT_PREFIX: &str = "pinout_";
+
+/// Pinout formats `pcb` can write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PinoutFormat {
+    /// `pad,pin,net,description,voltage` rows.
+    Csv,
+    /// A Markdown table headed by the reference designator.
+    Markdown,
+    /// A labeled drawing of the pins.
+    Svg,
+    /// Xilinx Vivado constraints (`.xdc`).
+    Xdc,
+    /// Intel Quartus settings (`.qsf`).
+    Qsf,
+}
+
+impl PinoutFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            PinoutFormat::Csv => "csv",
+            PinoutFormat::Markdown => "md",
+            PinoutFormat::Svg => "svg",
+            PinoutFormat::Xdc => "xdc",
+            PinoutFormat::Qsf => "qsf",
+        }
+    }
+}
+
+/// One pad of a component and the net it connects to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinAssignment {
+    pub pad: String,
+    /// Name of the component pin the pad belongs to.
+    pub pin: String,
+    /// `None` for unconnected pads.
+    pub net: Option<String>,
+    /// Kind of the connected net, e.g. `Net`, `Power`, or `Ground`.
+    pub net_kind: Option<String>,
+    /// Signal description from the component's `pinout_<pin>` property.
+    pub description: Option<String>,
+    /// Voltage domain of the connected net, e.g. `3.3V`.
+    pub voltage: Option<String>,
+}
+
+/// Pads of the component `refdes` in natural pad order, or `None` if no
+/// component has that reference designator.
+///
+/// Component instances must already have reference designators; see
+/// [`Schematic::assign_reference_designators`].
+pub fn component_pinout(sch: &Schematic, refdes: &str) -> Option<Vec<PinAssignment>> {
+    let component = netlist_components(sch)
+        .into_iter()
+        .find(|component| component.refdes == refdes)?;
+
+    let mut nets_by_pad = HashMap::new();
+    for net in netlist_nets(sch) {
+        let schematic_net = sch.nets.get(&net.name);
+        let kind = schematic_net.map(|net| net.kind.clone());
+        let voltage = schematic_net.and_then(|net| {
+            net.properties
+                .get("voltage")
+                .and_then(|voltage| voltage.string())
+                .map(str::to_owned)
+                .or_else(|| (net.kind == "Ground").then(|| "0V".to_owned()))
+        });
+        for node in net.nodes.into_iter().filter(|node| node.refdes == refdes) {
+            nets_by_pad.insert(node.pad, (net.name.clone(), kind.clone(), voltage.clone()));
+        }
+    }
+
+    let mut pins = collect_pins_for_component(sch, &component.reference)?.pins;
+    pins.sort_by(|a, b| natord::compare(&a.0, &b.0));
+    pins.dedup_by(|a, b| a.0 == b.0);
+    Some(
+        pins.into_iter()
+            .map(|(pad, pin)| {
+                let (net, net_kind, voltage) = match nets_by_pad.remove(&pad) {
+                    Some((net, kind, voltage)) => (Some(net), kind, voltage),
+                    None => (None, None, None),
+                };
+                PinAssignment {
+                    description: pin_description(component.instance, &pin, &pad),
+                    pad,
+                    pin,
+                    net,
+                    net_kind,
+                    voltage,
+                }
+            })
+            .collect(),
+    )
+}
+
+fn pin_description(component: &Instance, pin: &str, pad: &str) -> Option<String> {
+    component.string_attr(&[
+        format!("{ATTR_PINOUT_PREFIX}{pin}").as_str(),
+        format!("{ATTR_PINOUT_PREFIX}{pad}").as_str(),
+    ])
+}
+
+/// Reference designators of the connectors of `sch` in natural order:
+/// components of type `connector`, or whose designator is `J` and a number.
+pub fn connector_refdes(sch: &Schematic) -> Vec<String> {
+    let mut refdes: Vec<String> = netlist_components(sch)
+        .into_iter()
+        .filter(|component| {
+            component.instance.component_type().as_deref() == Some("connector")
+                || component
+                    .refdes
+                    .strip_prefix('J')
+                    .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+        })
+        .map(|component| component.refdes.to_owned())
+        .collect();
+    refdes.sort_by(|a, b| natord::compare(a, b));
+    refdes
+}
+
+/// Render `pins` of the component `refdes` in `format`.
+pub fn export_pinout(refdes: &str, pins: &[PinAssignment], format: PinoutFormat) -> String {
+    match format {
+        PinoutFormat::Csv => to_csv(pins),
+        PinoutFormat::Markdown => to_markdown(refdes, pins),
+        PinoutFormat::Svg => to_svg(refdes, pins),
+        PinoutFormat::Xdc => to_constraints(refdes, pins, |pad, port| {
+            format!("set_property PACKAGE_PIN {pad} [get_ports {{{port}}}]")
+        }),
+        PinoutFormat::Qsf => to_constraints(refdes, pins, |pad, port| {
+            format!("set_location_assignment PIN_{pad} -to {port}")
+        }),
+    }
+}
+
+fn to_csv(pins: &[PinAssignment]) -> String {
+    let mut out = String::from("pad,pin,net,description,voltage\n");
+    for pin in pins {
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            csv_field(&pin.pad),
+            csv_field(&pin.pin),
+            csv_field(pin.net.as_deref().unwrap_or("")),
+            csv_field(pin.description.as_deref().unwrap_or("")),
+            csv_field(pin.voltage.as_deref().unwrap_or(""))
+        )
+        .unwrap();
+    }
+    out
+}
+
+fn to_markdown(refdes: &str, pins: &[PinAssignment]) -> String {
+    let cell = |value: Option<&str>| value.unwrap_or("").replace('|', "\\|");
+    let mut out = format!("## {refdes}\n\n");
+    out.push_str("| Pad | Pin | Net | Signal | Voltage |\n");
+    out.push_str("|-----|-----|-----|--------|---------|\n");
+    for pin in pins {
+        writeln!(
+            out,
+            "| {} | {} | {} | {} | {} |",
+            cell(Some(&pin.pad)),
+            cell(Some(&pin.pin)),
+            cell(pin.net.as_deref()),
+            cell(pin.description.as_deref()),
+            cell(pin.voltage.as_deref())
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// Pads as a column of numbered boxes, each labeled with its net, voltage,
+/// and description.
+fn to_svg(refdes: &str, pins: &[PinAssignment]) -> String {
+    const ROW: usize = 24;
+    const TOP: usize = 40;
+    let labels: Vec<String> = pins
+        .iter()
+        .map(|pin| {
+            let mut label = pin.net.clone().unwrap_or_else(|| "(unconnected)".into());
+            if let Some(voltage) = &pin.voltage {
+                label.push_str(&format!(" [{voltage}]"));
+            }
+            if let Some(description) = &pin.description {
+                label.push_str(&format!(" \u{2014} {description}"));
+            }
+            label
+        })
+        .collect();
+    let width = 150 + 7 * labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let height = TOP + ROW * pins.len() + 16;
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" font-family="monospace" font-size="12">"#
+    )
+    .unwrap();
+    writeln!(
+        out,
+        r#"<text x="16" y="24" font-size="16" font-weight="bold">{}</text>"#,
+        xml_escape(refdes)
+    )
+    .unwrap();
+    writeln!(
+        out,
+        r#"<rect x="16" y="{TOP}" width="80" height="{}" fill="#f4f4f4" stroke="black"/>"#,
+        ROW * pins.len()
+    )
+    .unwrap();
+    for (i, (pin, label)) in pins.iter().zip(&labels).enumerate() {
+        let y = TOP + ROW * i + ROW / 2;
+        let color = match pin.net_kind.as_deref() {
+            Some("Power") => "#c00000",
+            Some("Ground") => "#404040",
+            _ if pin.net.is_none() => "#a0a0a0",
+            _ => "#0050a0",
+        };
+        writeln!(
+            out,
+            r#"<text x="24" y="{}">{}</text>"#,
+            y + 4,
+            xml_escape(&pin.pad)
+        )
+        .unwrap();
+        writeln!(
+            out,
+            r#"<text x="90" y="{}" text-anchor="end">{}</text>"#,
+            y + 4,
+            xml_escape(&pin.pin)
+        )
+        .unwrap();
+        writeln!(
+            out,
+            r#"<line x1="96" y1="{y}" x2="126" y2="{y}" stroke="{color}" stroke-width="2"/>"#
+        )
+        .unwrap();
+        writeln!(
+            out,
+            r#"<text x="132" y="{}" fill="{color}">{}</text>"#,
+            y + 4,
+            xml_escape(label)
+        )
+        .unwrap();
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+fn to_constraints(
+    refdes: &str,
+    pins: &[PinAssignment],
+    location: impl Fn(&str, &str) -> String,
+) -> String {
+    let mut pads_per_net: HashMap<&str, usize> = HashMap::new();
+    for net in pins.iter().filter_map(|pin| pin.net.as_deref()) {
+        *pads_per_net.entry(net).or_default() += 1;
+    }
+
+    let mut out = String::new();
+    writeln!(out, "# Pin constraints for {refdes}, generated by pcb").unwrap();
+    let mut skipped = Vec::new();
+    for pin in pins {
+        let Some(net) = pin.net.as_deref() else {
+            continue;
+        };
+        let supply = matches!(pin.net_kind.as_deref(), Some("Power" | "Ground"));
+        if supply || pads_per_net[net] > 1 {
+            skipped.push(pin);
+            continue;
+        }
+        writeln!(out, "{}", location(&pin.pad, &hdl_port_name(net))).unwrap();
+    }
+    if !skipped.is_empty() {
+        writeln!(out).unwrap();
+        writeln!(out, "# Not constrained (supply or shared nets):").unwrap();
+        for pin in skipped {
+            writeln!(
+                out,
+                "#   {} ({}): {}",
+                pin.pad,
+                pin.pin,
+                pin.net.as_deref().unwrap_or_default()
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+/// HDL port name for a net: characters other than ASCII letters, digits,
+/// and `_` become `_`, and a leading digit gets a `n_` prefix.
+fn hdl_port_name(net: &str) -> String {
+    let name: String = net
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("n_{name}")
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AttributeValue;
+    use crate::netlist::test_support::divider;
+
+    #[test]
+    fn exports_component_pinout() {
+        let sch = divider();
+        let pins = component_pinout(&sch, "R1").unwrap();
+        assert_eq!(
+            export_pinout("R1", &pins, PinoutFormat::Csv),
+            "pad,pin,net,description,voltage\n1,P1,VIN,,\n2,P2,OUT,,\n"
+        );
+        assert_eq!(
+            export_pinout("R1", &pins, PinoutFormat::Xdc),
+            "\
+# Pin constraints for R1, generated by pcb
+set_property PACKAGE_PIN 1 [get_ports {VIN}]
+set_property PACKAGE_PIN 2 [get_ports {OUT}]
+"
+        );
+        assert_eq!(
+            export_pinout("R1", &pins, PinoutFormat::Qsf),
+            "\
+# Pin constraints for R1, generated by pcb
+set_location_assignment PIN_1 -to VIN
+set_location_assignment PIN_2 -to OUT
+"
+        );
+        assert!(component_pinout(&sch, "U1").is_none());
+    }
+
+    #[test]
+    fn constraints_skip_supply_nets() {
+        let mut sch = divider();
+        sch.nets.get_mut("GND").unwrap().kind = "Ground".to_owned();
+        let pins = component_pinout(&sch, "R2").unwrap();
+        assert_eq!(
+            export_pinout("R2", &pins, PinoutFormat::Qsf),
+            "\
+# Pin constraints for R2, generated by pcb
+set_location_assignment PIN_1 -to OUT
+
+# Not constrained (supply or shared nets):
+#   2 (P2): GND
+"
+        );
+    }
+
+    #[test]
+    fn hdl_port_names_are_identifiers() {
+        assert_eq!(hdl_port_name("SPI.MOSI"), "SPI_MOSI");
+        assert_eq!(hdl_port_name("3V3_EN"), "n_3V3_EN");
+    }
+
+    #[test]
+    fn documents_connector_pins() {
+        let mut sch = divider();
+        sch.nets
+            .get_mut("VIN")
+            .unwrap()
+            .properties
+            .insert("voltage".into(), AttributeValue::String("5V".to_owned()));
+        sch.nets.get_mut("GND").unwrap().kind = "Ground".to_owned();
+        for instance in sch.instances.values_mut() {
+            if instance.reference_designator.as_deref() == Some("R2") {
+                instance.reference_designator = Some("J1".to_owned());
+                instance.add_attribute(
+                    "pinout_P1",
+                    AttributeValue::String("Divided output | to ADC".into()),
+                );
+            }
+        }
+        assert_eq!(connector_refdes(&sch), ["J1"]);
+
+        let pins = component_pinout(&sch, "J1").unwrap();
+        assert_eq!(
+            export_pinout("J1", &pins, PinoutFormat::Markdown),
+            "\
+## J1
+
+| Pad | Pin | Net | Signal | Voltage |
+|-----|-----|-----|--------|---------|
+| 1 | P1 | OUT | Divided output \\| to ADC |  |
+| 2 | P2 | GND |  | 0V |
+"
+        );
+        let pins = component_pinout(&sch, "R1").unwrap();
+        assert_eq!(pins[0].voltage.as_deref(), Some("5V"));
+        assert!(export_pinout("R1", &pins, PinoutFormat::Svg).contains(">VIN [5V]</text>"));
+    }
+}
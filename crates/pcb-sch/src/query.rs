@@ -0,0 +1,517 @@
+//! Selector language for querying a [`Schematic`].
+//!
+//! ```text
+//! components[mpn~="STM32*"].footprint
+//! nets[kind=power].name
+//! modules[layout_path].path
+//! ```
+//!
+//! A query names a collection (`components`, `modules`, or `nets`), optionally
+//! filters it with `[...]` blocks, and optionally projects a single field with
+//! `.field`. A block holds comma-separated conditions that must all hold:
+//! `key` (present), `key=value`, `key!=value`, or `key~=glob` (`*` and `?`
+//! wildcards). Comparisons ignore ASCII case, and a condition on an array
+//! matches if any element matches. Values are bare words or double-quoted
+//! strings.
+//!
+//! Instances expose `path`, `reference`, `kind`, and their attributes. Nets
+//! expose `name`, `kind`, `id`, `ports`, and their properties, including the
+//! [statistics](crate::net_stats) of an annotated schematic.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::{AttributeValue, InstanceKind, Schematic};
+
+/// A record produced by a query: field name to JSON value.
+pub type Record = BTreeMap<String, Value>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Collection {
+    Components,
+    Modules,
+    Nets,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// `key`: the field is present
+    Exists,
+    /// `key=value`
+    Eq,
+    /// `key!=value`
+    Ne,
+    /// `key~=glob`
+    Glob,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    pub key: String,
+    pub op: Op,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub collection: Collection,
+    pub conditions: Vec<Condition>,
+    /// Field projected from each matching record, if any
+    pub field: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid query at column {}: {message}", .offset + 1)]
+pub struct QueryError {
+    pub offset: usize,
+    pub message: String,
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Parser { input, pos: 0 }.parse_query()
+    }
+}
+
+impl Query {
+    /// Evaluate the query. Returns the projected field of each matching record
+    /// (skipping records without it), or the whole records when no field is
+    /// selected. Results are ordered by instance path or net name.
+    pub fn evaluate(&self, schematic: &Schematic) -> Vec<Value> {
+        let records = match self.collection {
+            Collection::Components => instance_records(schematic, InstanceKind::Component),
+            Collection::Modules => instance_records(schematic, InstanceKind::Module),
+            Collection::Nets => net_records(schematic),
+        };
+
+        records
+            .into_iter()
+            .filter(|record| self.conditions.iter().all(|c| c.matches(record)))
+            .filter_map(|mut record| match &self.field {
+                Some(field) => record.remove(field),
+                None => Some(Value::Object(record.into_iter().collect())),
+            })
+            .collect()
+    }
+}
+
+impl Condition {
+    fn matches(&self, record: &Record) -> bool {
+        let Some(value) = record.get(&self.key) else {
+            return self.op == Op::Ne;
+        };
+        let texts = value_texts(value);
+        let expected = self.value.to_ascii_lowercase();
+        match self.op {
+            Op::Exists => true,
+            Op::Eq => texts.iter().any(|text| *text == expected),
+            Op::Ne => texts.iter().all(|text| *text != expected),
+            Op::Glob => texts
+                .iter()
+                .any(|text| glob_match(expected.as_bytes(), text.as_bytes())),
+        }
+    }
+}
+
+/// Lowercased string forms of a value; arrays contribute each element.
+fn value_texts(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) => items.iter().flat_map(value_texts).collect(),
+        Value::String(s) => vec![s.to_ascii_lowercase()],
+        Value::Null => Vec::new(),
+        other => vec![other.to_string().to_ascii_lowercase()],
+    }
+}
+
+/// Match `text` against a pattern where `*` matches any run of bytes and `?`
+/// matches one byte. Comparison is byte-wise; lowercase both for a
+/// case-insensitive match.
+pub fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+fn attribute_json(value: &AttributeValue) -> Value {
+    match value {
+        AttributeValue::String(s) | AttributeValue::Port(s) => Value::String(s.clone()),
+        AttributeValue::Number(n) => serde_json::Number::from_f64(*n)
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        AttributeValue::Boolean(b) => Value::Bool(*b),
+        AttributeValue::Array(items) => Value::Array(items.iter().map(attribute_json).collect()),
+        AttributeValue::Json(v) => v.clone(),
+    }
+}
+
+fn instance_records(schematic: &Schematic, kind: InstanceKind) -> Vec<Record> {
+    let mut records: Vec<Record> = schematic
+        .instances
+        .iter()
+        .filter(|(_, instance)| instance.kind == kind)
+        .map(|(instance_ref, instance)| {
+            let mut record: Record = instance
+                .attributes
+                .iter()
+                .map(|(key, value)| (key.clone(), attribute_json(value)))
+                .collect();
+            record.insert(
+                "path".into(),
+                Value::String(instance_ref.instance_path.join(".")),
+            );
+            record.insert(
+                "kind".into(),
+                Value::String(format!("{:?}", instance.kind).to_ascii_lowercase()),
+            );
+            if let Some(reference) = &instance.reference_designator {
+                record.insert("reference".into(), Value::String(reference.clone()));
+            }
+            record
+        })
+        .collect();
+    records.sort_by(|a, b| a["path"].as_str().cmp(&b["path"].as_str()));
+    records
+}
+
+fn net_records(schematic: &Schematic) -> Vec<Record> {
+    let mut records: Vec<Record> = schematic
+        .nets
+        .values()
+        .map(|net| {
+            let mut record: Record = net
+                .properties
+                .iter()
+                .map(|(key, value)| (key.clone(), attribute_json(value)))
+                .collect();
+            record.insert("name".into(), Value::String(net.name.clone()));
+            record.insert("kind".into(), Value::String(net.kind.clone()));
+            record.insert("id".into(), Value::from(net.id));
+            let mut ports: Vec<String> = net
+                .ports
+                .iter()
+                .map(|port| port.instance_path.join("."))
+                .collect();
+            ports.sort();
+            record.insert(
+                "ports".into(),
+                Value::Array(ports.into_iter().map(Value::String).collect()),
+            );
+            record
+        })
+        .collect();
+    records.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+    records
+}
+
+/// Render query results as CSV. Projected values become a single column named
+/// after the field; records become one column per key, identity keys first.
+pub fn to_csv(results: &[Value], field: Option<&str>) -> anyhow::Result<String> {
+    const LEADING: &[&str] = &["path", "name", "reference", "kind"];
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    match field {
+        Some(field) => {
+            writer.write_record([field])?;
+            for value in results {
+                writer.write_record([csv_cell(value)])?;
+            }
+        }
+        None => {
+            let mut columns: Vec<String> = results
+                .iter()
+                .filter_map(Value::as_object)
+                .flat_map(|record| record.keys().cloned())
+                .collect::<std::collections::BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            columns.sort_by_key(|column| {
+                LEADING
+                    .iter()
+                    .position(|leading| leading == column)
+                    .unwrap_or(LEADING.len())
+            });
+            writer.write_record(&columns)?;
+            for record in results.iter().filter_map(Value::as_object) {
+                writer.write_record(
+                    columns
+                        .iter()
+                        .map(|column| record.get(column).map(csv_cell).unwrap_or_default()),
+                )?;
+            }
+        }
+    }
+    let bytes = writer.into_inner().map_err(|err| err.into_error())?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        Value::Array(items) => items.iter().map(csv_cell).collect::<Vec<_>>().join(";"),
+        other => other.to_string(),
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, QueryError> {
+        Err(QueryError {
+            offset: self.pos,
+            message: message.into(),
+        })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.input[self.pos..].starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume characters until one of `stops` (or whitespace) is reached.
+    fn take_until(&mut self, stops: &[char]) -> &'a str {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || stops.contains(&c) {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+        &self.input[start..self.pos]
+    }
+
+    fn parse_query(mut self) -> Result<Query, QueryError> {
+        self.skip_whitespace();
+        let collection = match self.take_until(&['[', '.']) {
+            "components" => Collection::Components,
+            "modules" => Collection::Modules,
+            "nets" => Collection::Nets,
+            "" => return self.error("expected `components`, `modules`, or `nets`"),
+            other => {
+                self.pos -= other.len();
+                return self.error(format!(
+                    "unknown collection `{other}`; expected `components`, `modules`, or `nets`"
+                ));
+            }
+        };
+
+        let mut conditions = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if !self.eat("[") {
+                break;
+            }
+            loop {
+                self.skip_whitespace();
+                conditions.push(self.parse_condition()?);
+                self.skip_whitespace();
+                if self.eat("]") {
+                    break;
+                }
+                if !self.eat(",") {
+                    return self.error("expected `,` or `]`");
+                }
+            }
+        }
+
+        let field = if self.eat(".") {
+            let field = self.input[self.pos..].trim();
+            if field.is_empty() {
+                return self.error("expected a field name after `.`");
+            }
+            self.pos = self.input.len();
+            Some(field.to_string())
+        } else {
+            None
+        };
+
+        self.skip_whitespace();
+        if self.pos < self.input.len() {
+            return self.error("unexpected trailing input");
+        }
+
+        Ok(Query {
+            collection,
+            conditions,
+            field,
+        })
+    }
+
+    fn parse_condition(&mut self) -> Result<Condition, QueryError> {
+        let key = self.take_until(&['=', '!', '~', ',', ']']).to_string();
+        if key.is_empty() {
+            return self.error("expected a field name");
+        }
+        self.skip_whitespace();
+        let op = if self.eat("!=") {
+            Op::Ne
+        } else if self.eat("~=") {
+            Op::Glob
+        } else if self.eat("=") {
+            Op::Eq
+        } else {
+            return Ok(Condition {
+                key,
+                op: Op::Exists,
+                value: String::new(),
+            });
+        };
+        self.skip_whitespace();
+        let value = self.parse_value()?;
+        Ok(Condition { key, op, value })
+    }
+
+    fn parse_value(&mut self) -> Result<String, QueryError> {
+        if !self.eat("\"") {
+            let value = self.take_until(&[',', ']']);
+            if value.is_empty() {
+                return self.error("expected a value");
+            }
+            return Ok(value.to_string());
+        }
+
+        let mut value = String::new();
+        let mut chars = self.input[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(value);
+                }
+                '\\' => match chars.next() {
+                    Some((_, escaped)) => value.push(escaped),
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+        self.pos = self.input.len();
+        self.error("unterminated string")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Instance, InstanceRef, ModuleRef, Net};
+
+    fn schematic() -> Schematic {
+        let module = ModuleRef::new("/tmp/board.zen", "<root>");
+        let iref = |path: &[&str]| {
+            InstanceRef::new(module.clone(), path.iter().map(|s| s.to_string()).collect())
+        };
+
+        let mut schematic = Schematic::new();
+        schematic.add_instance(
+            iref(&["mcu", "U1"]),
+            Instance::component(module.clone())
+                .with_attribute("mpn", "STM32G431CBU6".to_string())
+                .with_attribute("footprint", "QFN-48".to_string())
+                .with_reference_designator("U1"),
+        );
+        schematic.add_instance(
+            iref(&["R1"]),
+            Instance::component(module.clone())
+                .with_attribute("mpn", "RC0603FR-0710KL".to_string())
+                .with_attribute("footprint", "R_0603".to_string())
+                .with_reference_designator("R1"),
+        );
+        schematic.add_instance(iref(&["mcu"]), Instance::module(module.clone()));
+        schematic.add_net(
+            Net::new("Power".to_string(), "VCC_3V3", 1).with_port(iref(&["mcu", "U1", "VDD"])),
+        );
+        schematic.add_net(Net::new("Net".to_string(), "SDA", 2).with_port(iref(&["R1", "P1"])));
+        schematic
+    }
+
+    fn run(query: &str) -> Vec<Value> {
+        query.parse::<Query>().unwrap().evaluate(&schematic())
+    }
+
+    #[test]
+    fn filters_and_projects_fields() {
+        assert_eq!(run(r#"components[mpn~="stm32*"].footprint"#), ["QFN-48"]);
+        assert_eq!(run("nets[kind=power].name"), ["VCC_3V3"]);
+        assert_eq!(run("nets[ports~=R1.*].name"), ["SDA"]);
+        assert_eq!(run("components.reference"), ["R1", "U1"]);
+        assert_eq!(
+            run("components[footprint!=R_0603, reference].path"),
+            ["mcu.U1"]
+        );
+        assert_eq!(run("modules.path"), ["mcu"]);
+    }
+
+    #[test]
+    fn records_render_as_csv() {
+        let query: Query = "components[reference=R1]".parse().unwrap();
+        let csv = to_csv(&query.evaluate(&schematic()), None).unwrap();
+        assert_eq!(
+            csv,
+            "path,reference,kind,footprint,mpn\nR1,R1,component,R_0603,RC0603FR-0710KL\n"
+        );
+    }
+
+    #[test]
+    fn reports_parse_errors_with_position() {
+        let err = "parts[mpn=x]".parse::<Query>().unwrap_err();
+        assert_eq!(err.offset, 0);
+        let err = "components[mpn=\"x".parse::<Query>().unwrap_err();
+        assert_eq!(err.message, "unterminated string");
+        assert!("components[mpn=x".parse::<Query>().is_err());
+        assert!("components.".parse::<Query>().is_err());
+    }
+
+    #[test]
+    fn skips_multibyte_whitespace() {
+        assert_eq!(run("\u{a0}components.reference"), ["R1", "U1"]);
+    }
+
+    #[test]
+    fn glob_matches_wildcards() {
+        assert!(glob_match(b"a*c", b"abbbc"));
+        assert!(glob_match(b"a?c", b"abc"));
+        assert!(glob_match(b"*", b""));
+        assert!(!glob_match(b"a*d", b"abc"));
+    }
+}
@@ -0,0 +1,133 @@
+//! Organization-specific reference designator numbering.
+//!
+//! A [`RefdesPolicy`] constrains the numbers handed out by
+//! [`Schematic::assign_reference_designators_with_policy`](crate::Schematic::assign_reference_designators_with_policy):
+//!
+//! * per-prefix ranges, e.g. test points from `TP500`,
+//! * per-prefix skip lists, e.g. never `R13`,
+//! * per-module offsets, e.g. everything under `power` numbered from 100.
+//!
+//! Module offsets shift a prefix's whole range, so with `TP` starting at 500
+//! and `power` offset by 1000, test points in `power` start at `TP1500`. Skip
+//! lists hold final numbers and are not shifted.
+//!
+//! Designators that are already assigned or hinted by the instance path keep
+//! their numbers so existing layouts stay stable; preserved designators that
+//! violate the policy are reported as conflicts instead of renumbered.
+
+use std::collections::{BTreeSet, HashMap};
+
+use anyhow::{Result, bail};
+
+use crate::InstanceRef;
+
+/// Number range and skip list for one prefix.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrefixRule {
+    /// First number to assign. Defaults to 1.
+    pub start: Option<u32>,
+    /// Last number in the range. Numbers past it are still assigned, but
+    /// reported as conflicts.
+    pub end: Option<u32>,
+    /// Numbers that are never auto-assigned.
+    pub skip: BTreeSet<u32>,
+}
+
+/// Numbering rules applied when assigning reference designators.
+#[derive(Debug, Clone, Default)]
+pub struct RefdesPolicy {
+    prefixes: HashMap<String, PrefixRule>,
+    /// `(instance path, offset)`, longest path first.
+    modules: Vec<(String, u32)>,
+}
+
+/// The numbers a component may be assigned.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RefdesRange<'a> {
+    pub(crate) start: u32,
+    pub(crate) end: Option<u32>,
+    skip: Option<&'a BTreeSet<u32>>,
+}
+
+impl RefdesRange<'_> {
+    pub(crate) fn skips(&self, number: u32) -> bool {
+        self.skip.is_some_and(|skip| skip.contains(&number))
+    }
+
+    pub(crate) fn permits(&self, number: u32) -> bool {
+        number >= self.start && self.end.is_none_or(|end| number <= end) && !self.skips(number)
+    }
+}
+
+impl RefdesPolicy {
+    /// Build a policy from per-prefix rules and per-module offsets. Module
+    /// paths are dotted instance paths such as `power` or `io.usb`.
+    pub fn new(
+        prefixes: impl IntoIterator<Item = (String, PrefixRule)>,
+        modules: impl IntoIterator<Item = (String, u32)>,
+    ) -> Result<Self> {
+        let prefixes: HashMap<String, PrefixRule> = prefixes.into_iter().collect();
+        for (prefix, rule) in &prefixes {
+            if rule.start == Some(0) {
+                bail!("Refdes range for '{prefix}' must start at 1 or above");
+            }
+            if let Some(end) = rule.end
+                && end < rule.start.unwrap_or(1)
+            {
+                bail!("Refdes range for '{prefix}' ends before it starts");
+            }
+        }
+
+        let mut modules: Vec<(String, u32)> = modules.into_iter().collect();
+        if let Some((path, _)) = modules.iter().find(|(path, _)| path.is_empty()) {
+            bail!("Refdes module offset '{path}' must name a module instance path");
+        }
+        modules.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+
+        Ok(Self { prefixes, modules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prefixes.is_empty() && self.modules.is_empty()
+    }
+
+    /// Offset of the innermost configured module containing `hier`.
+    fn module_offset(&self, hier: &str) -> u32 {
+        self.modules
+            .iter()
+            .find(|(path, _)| {
+                hier.strip_prefix(path.as_str())
+                    .is_some_and(|rest| rest.starts_with('.'))
+            })
+            .map_or(0, |(_, offset)| *offset)
+    }
+
+    /// Range for a component with `prefix` at dotted instance path `hier`.
+    pub(crate) fn range(&self, prefix: &str, hier: &str) -> RefdesRange<'_> {
+        let rule = self.prefixes.get(prefix);
+        let offset = self.module_offset(hier);
+        RefdesRange {
+            start: offset.saturating_add(rule.and_then(|r| r.start).unwrap_or(1)),
+            end: rule
+                .and_then(|r| r.end)
+                .map(|end| offset.saturating_add(end)),
+            skip: rule.map(|r| &r.skip),
+        }
+    }
+}
+
+/// A designator that does not satisfy the numbering policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefdesConflict {
+    pub instance: InstanceRef,
+    pub refdes: String,
+    pub message: String,
+}
+
+/// Result of [`Schematic::assign_reference_designators_with_policy`](crate::Schematic::assign_reference_designators_with_policy).
+#[derive(Debug, Clone, Default)]
+pub struct RefdesAssignment {
+    pub refdes: HashMap<InstanceRef, String>,
+    /// Policy violations, in instance path order.
+    pub conflicts: Vec<RefdesConflict>,
+}
@@ -0,0 +1,123 @@
+//! Board revision history, declared as `[[board.revisions]]` in a board's
+//! `pcb.toml` and carried into generated artifacts.
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+/// One entry of a board's revision history. The last entry is the board's
+/// current revision.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Revision {
+    /// Revision identifier, e.g. `"B"` or `"1.2"`.
+    pub revision: String,
+
+    /// Date the revision was released, e.g. `"2026-03-14"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+
+    /// Engineering change order the revision implements, e.g. `"ECO-0042"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eco: Option<String>,
+
+    /// What changed in this revision.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+}
+
+impl Revision {
+    /// One-line form for file headers, e.g. `Rev B (ECO-0042)`.
+    pub fn label(&self) -> String {
+        match &self.eco {
+            Some(eco) => format!("Rev {} ({eco})", self.revision),
+            None => format!("Rev {}", self.revision),
+        }
+    }
+}
+
+/// How a revision identifier counts up.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum RevisionKey {
+    /// `1`, `1.2`, `2.0.1`: compared component by component.
+    Numeric(Vec<u64>),
+    /// `A`, `B`, ..., `Z`, `AA`: compared by length, then alphabetically.
+    Letters(usize, String),
+}
+
+impl RevisionKey {
+    fn parse(revision: &str) -> Result<Self> {
+        let revision = revision.trim();
+        if !revision.is_empty() && revision.chars().all(|c| c.is_ascii_alphabetic()) {
+            let letters = revision.to_ascii_uppercase();
+            return Ok(Self::Letters(letters.len(), letters));
+        }
+        revision
+            .split('.')
+            .map(|part| part.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::Numeric)
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "revision {revision:?} must be letters (e.g. \"B\") or dotted numbers (e.g. \"1.2\")"
+                )
+            })
+    }
+}
+
+/// Check that `revisions` count strictly upwards in one numbering style.
+pub fn check_revision_order(revisions: &[Revision]) -> Result<()> {
+    let keys = revisions
+        .iter()
+        .map(|r| RevisionKey::parse(&r.revision))
+        .collect::<Result<Vec<_>>>()?;
+    for (i, pair) in keys.windows(2).enumerate() {
+        let (previous, current) = (&revisions[i].revision, &revisions[i + 1].revision);
+        match pair {
+            [RevisionKey::Numeric(_), RevisionKey::Numeric(_)]
+            | [RevisionKey::Letters(..), RevisionKey::Letters(..)] => {
+                if pair[1] <= pair[0] {
+                    bail!("revision {current:?} does not come after {previous:?}");
+                }
+            }
+            _ => bail!("revision {current:?} is numbered differently from {previous:?}"),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revisions(ids: &[&str]) -> Vec<Revision> {
+        ids.iter()
+            .map(|id| Revision {
+                revision: id.to_string(),
+                date: None,
+                eco: None,
+                description: String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn revisions_must_increase() {
+        assert!(check_revision_order(&revisions(&["A", "B", "Z", "AA"])).is_ok());
+        assert!(check_revision_order(&revisions(&["1", "1.1", "1.10", "2"])).is_ok());
+        assert!(check_revision_order(&revisions(&[])).is_ok());
+
+        assert!(check_revision_order(&revisions(&["B", "A"])).is_err());
+        assert!(check_revision_order(&revisions(&["1.2", "1.2"])).is_err());
+        assert!(check_revision_order(&revisions(&["1.10", "1.9"])).is_err());
+        assert!(check_revision_order(&revisions(&["A", "2"])).is_err());
+        assert!(check_revision_order(&revisions(&["rev-1"])).is_err());
+    }
+
+    #[test]
+    fn label_includes_eco() {
+        let mut revision = revisions(&["B"]).remove(0);
+        assert_eq!(revision.label(), "Rev B");
+        revision.eco = Some("ECO-0042".to_string());
+        assert_eq!(revision.label(), "Rev B (ECO-0042)");
+    }
+}
@@ -0,0 +1,48 @@
+//! Provenance stamp written into generated artifacts so a physical board can
+//! be traced back to the sources it was made from.
+
+use serde::{Deserialize, Serialize};
+
+/// Git revision and source hash of a build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stamp {
+    /// `git describe --tags --always --dirty` of the workspace.
+    pub git_describe: String,
+    /// Content hash of the workspace's Zen sources and manifests
+    /// (`h1:<base64 blake3>`).
+    pub source_hash: String,
+}
+
+impl Stamp {
+    /// Length of [`Stamp::short_source_hash`], short enough for silkscreen.
+    pub const SHORT_HASH_LEN: usize = 8;
+
+    /// Leading characters of the source hash digest.
+    pub fn short_source_hash(&self) -> &str {
+        let digest = self
+            .source_hash
+            .strip_prefix("h1:")
+            .unwrap_or(&self.source_hash);
+        &digest[..Self::SHORT_HASH_LEN.min(digest.len())]
+    }
+
+    /// One-line form for file headers, e.g. `v1.2.0-3-g1a2b3c4 src:h1:...`.
+    pub fn label(&self) -> String {
+        format!("{} src:{}", self.git_describe, self.source_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_source_hash_skips_prefix() {
+        let stamp = Stamp {
+            git_describe: "v1.2.0-3-g1a2b3c4".to_string(),
+            source_hash: "h1:q83vEjRWeJA=".to_string(),
+        };
+        assert_eq!(stamp.short_source_hash(), "q83vEjRW");
+        assert_eq!(stamp.label(), "v1.2.0-3-g1a2b3c4 src:h1:q83vEjRWeJA=");
+    }
+}
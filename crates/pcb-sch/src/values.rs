@@ -0,0 +1,396 @@
+//! Component value normalization and validation.
+//!
+//! Passive values are written many ways (`10k`, `10K`, `4k7`, `0.1uF`). BOM
+//! and netlist exports rewrite the leading value of a `value` string to the
+//! canonical [`PhysicalValue`] form (`10k`, `4.7k`, `100nF`), and schematic
+//! conversion warns about resistor, capacitor, and inductor values that do not
+//! parse, fall outside the E-series selected in `[workspace.values]`, or
+//! disagree with the resistance encoded in the part's MPN.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{Result, bail};
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::physical::PhysicalValue;
+use crate::{Instance, PhysicalUnit};
+
+/// Relative difference under which two values are considered equal.
+const VALUE_EPSILON: f64 = 1e-3;
+
+/// IEC 60063 preferred number series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ESeries {
+    E3,
+    E6,
+    E12,
+    E24,
+    E48,
+    E96,
+    E192,
+}
+
+impl ESeries {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ESeries::E3 => "E3",
+            ESeries::E6 => "E6",
+            ESeries::E12 => "E12",
+            ESeries::E24 => "E24",
+            ESeries::E48 => "E48",
+            ESeries::E96 => "E96",
+            ESeries::E192 => "E192",
+        }
+    }
+
+    /// Values of one decade, from 1.0 up to (excluding) 10.
+    pub fn mantissas(&self) -> Vec<f64> {
+        const E24: [f64; 24] = [
+            1.0, 1.1, 1.2, 1.3, 1.5, 1.6, 1.8, 2.0, 2.2, 2.4, 2.7, 3.0, 3.3, 3.6, 3.9, 4.3, 4.7,
+            5.1, 5.6, 6.2, 6.8, 7.5, 8.2, 9.1,
+        ];
+        let steps = match self {
+            ESeries::E3 | ESeries::E6 | ESeries::E12 | ESeries::E24 => {
+                let stride = 24 / self.steps();
+                return E24.iter().step_by(stride).copied().collect();
+            }
+            ESeries::E48 | ESeries::E96 | ESeries::E192 => self.steps(),
+        };
+        // The tolerance series follow 10^(i/n) rounded to three digits,
+        // except for E192's 9.20.
+        (0..steps)
+            .map(|i| {
+                let value = (10f64.powf(i as f64 / steps as f64) * 100.0).round() / 100.0;
+                if steps == 192 && value == 9.19 {
+                    9.20
+                } else {
+                    value
+                }
+            })
+            .collect()
+    }
+
+    fn steps(&self) -> usize {
+        match self {
+            ESeries::E3 => 3,
+            ESeries::E6 => 6,
+            ESeries::E12 => 12,
+            ESeries::E24 => 24,
+            ESeries::E48 => 48,
+            ESeries::E96 => 96,
+            ESeries::E192 => 192,
+        }
+    }
+
+    /// Whether `value` is a member of this series in any decade.
+    pub fn contains(&self, value: f64) -> bool {
+        if value <= 0.0 || !value.is_finite() {
+            return false;
+        }
+        let mantissa = value / 10f64.powf(value.log10().floor());
+        self.mantissas()
+            .into_iter()
+            .chain([10.0])
+            .any(|preferred| approx_eq(mantissa, preferred))
+    }
+}
+
+impl fmt::Display for ESeries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ESeries {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "E3" => Ok(ESeries::E3),
+            "E6" => Ok(ESeries::E6),
+            "E12" => Ok(ESeries::E12),
+            "E24" => Ok(ESeries::E24),
+            "E48" => Ok(ESeries::E48),
+            "E96" => Ok(ESeries::E96),
+            "E192" => Ok(ESeries::E192),
+            other => Err(format!(
+                "unknown E-series '{other}' (expected E3, E6, E12, E24, E48, E96, or E192)"
+            )),
+        }
+    }
+}
+
+/// A problem with a component's value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueIssue {
+    /// The value of a resistor, capacitor, or inductor does not parse.
+    Invalid { value: String },
+    /// The value is in none of the selected series.
+    OffSeries { value: String, series: Vec<ESeries> },
+    /// The value disagrees with the resistance encoded in the MPN.
+    MpnMismatch {
+        value: String,
+        mpn: String,
+        mpn_value: String,
+    },
+}
+
+impl ValueIssue {
+    /// Diagnostic kind, for `[lints]` and `-S`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ValueIssue::Invalid { .. } => "component.value.invalid",
+            ValueIssue::OffSeries { .. } => "component.value.e_series",
+            ValueIssue::MpnMismatch { .. } => "component.value.mpn_mismatch",
+        }
+    }
+}
+
+impl fmt::Display for ValueIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueIssue::Invalid { value } => write!(f, "value '{value}' is not a valid value"),
+            ValueIssue::OffSeries { value, series } => {
+                let series: Vec<&str> = series.iter().map(ESeries::as_str).collect();
+                write!(f, "value {value} is not in {}", series.join(" or "))
+            }
+            ValueIssue::MpnMismatch {
+                value,
+                mpn,
+                mpn_value,
+            } => write!(
+                f,
+                "value {value} does not match {mpn_value} encoded in {mpn}"
+            ),
+        }
+    }
+}
+
+/// Value checks applied during schematic conversion.
+#[derive(Debug, Clone, Default)]
+pub struct ValuePolicy {
+    /// Series a value must belong to (any of them); empty skips the check.
+    e_series: Vec<ESeries>,
+}
+
+impl ValuePolicy {
+    /// Build a policy from E-series names such as `"E24"`.
+    pub fn new<'a>(e_series: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        let mut series = Vec::new();
+        for name in e_series {
+            match name.parse::<ESeries>() {
+                Ok(parsed) => series.push(parsed),
+                Err(err) => bail!("{err}"),
+            }
+        }
+        series.sort();
+        series.dedup();
+        Ok(Self { e_series: series })
+    }
+
+    /// Problems with the value of `instance`. Only resistors, capacitors,
+    /// and inductors are checked.
+    pub fn check(&self, instance: &Instance) -> Vec<ValueIssue> {
+        let Some((unit, keys)) = passive_kind(instance) else {
+            return Vec::new();
+        };
+        let value = match instance.physical_attr(keys) {
+            Some(value) => value,
+            None => {
+                let Some(text) = instance.value() else {
+                    return Vec::new();
+                };
+                match leading_value(&text).filter(|value| value.unit() == Some(unit)) {
+                    Some(value) => value,
+                    None => return vec![ValueIssue::Invalid { value: text }],
+                }
+            }
+        };
+        let Some(nominal) = value.nominal.to_f64() else {
+            return Vec::new();
+        };
+        let display = point_value(nominal, unit);
+
+        let mut issues = Vec::new();
+        if !self.e_series.is_empty()
+            && nominal > 0.0
+            && !self.e_series.iter().any(|series| series.contains(nominal))
+        {
+            issues.push(ValueIssue::OffSeries {
+                value: display.clone(),
+                series: self.e_series.clone(),
+            });
+        }
+        if unit == PhysicalUnit::Ohms
+            && let Some(mpn) = instance.mpn()
+            && let Some(encoded) = mpn_resistance(&mpn)
+            && !approx_eq(encoded, nominal)
+        {
+            issues.push(ValueIssue::MpnMismatch {
+                value: display,
+                mpn_value: point_value(encoded, unit),
+                mpn,
+            });
+        }
+        issues
+    }
+}
+
+/// Rewrite the leading resistance, capacitance, or inductance of `value` in
+/// canonical form, e.g. `"0.1uF 16V X7R"` to `"100nF 16V X7R"`. Anything else
+/// is returned unchanged.
+pub fn normalize_value(value: &str) -> Cow<'_, str> {
+    let trimmed = value.trim_start();
+    let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+    let token = &trimmed[..end];
+    if !token.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        || !token.contains(|c: char| c.is_alphabetic())
+    {
+        return Cow::Borrowed(value);
+    }
+    let Ok(parsed) = token.parse::<PhysicalValue>() else {
+        return Cow::Borrowed(value);
+    };
+    if !matches!(
+        parsed.unit(),
+        Some(PhysicalUnit::Ohms | PhysicalUnit::Farads | PhysicalUnit::Henries)
+    ) {
+        return Cow::Borrowed(value);
+    }
+    let canonical = parsed.to_string();
+    if canonical == token {
+        return Cow::Borrowed(value);
+    }
+    Cow::Owned(format!("{canonical}{}", &trimmed[end..]))
+}
+
+/// Resistance encoded in a chip resistor MPN, for series whose part numbers
+/// carry an IEC 60062 value code: Panasonic ERJ (`ERJ-3EKF1002V`), Yageo RC
+/// (`RC0603FR-0710KL`), and Vishay CRCW (`CRCW060310K0FKEA`).
+pub fn mpn_resistance(mpn: &str) -> Option<f64> {
+    let mpn = mpn.trim().to_ascii_uppercase();
+    let code = if mpn.starts_with("ERJ-") {
+        // Value code between the tolerance letter and the packaging suffix.
+        let body = mpn.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        let start = body
+            .rfind(|c: char| !(c.is_ascii_digit() || c == 'R'))
+            .map_or(0, |i| i + 1);
+        &body[start..]
+    } else if mpn.starts_with("RC") && mpn[2..].starts_with(|c: char| c.is_ascii_digit()) {
+        // Two packaging digits, the value code, then `L`.
+        let (_, tail) = mpn.split_once('-')?;
+        tail.get(2..)?.strip_suffix('L')?
+    } else if let Some(rest) = mpn.strip_prefix("CRCW") {
+        // Four size digits, the value code, then the tolerance letter.
+        let rest = rest.get(4..)?;
+        let end = rest
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, 'R' | 'K' | 'M')))
+            .unwrap_or(rest.len());
+        &rest[..end]
+    } else {
+        return None;
+    };
+    decode_resistance_code(code)
+}
+
+/// Decode an IEC 60062 resistance code: `4K7`, `10R0`, `R047`, or three or
+/// four digits where the last is the power of ten (`103`, `1002`).
+fn decode_resistance_code(code: &str) -> Option<f64> {
+    if let Some(pos) = code.find(['R', 'K', 'M']) {
+        let multiplier = match &code[pos..pos + 1] {
+            "R" => 1.0,
+            "K" => 1e3,
+            _ => 1e6,
+        };
+        let (whole, fraction) = (&code[..pos], &code[pos + 1..]);
+        if !(whole.chars().chain(fraction.chars())).all(|c| c.is_ascii_digit())
+            || whole.len() + fraction.len() == 0
+        {
+            return None;
+        }
+        let number: f64 = format!("0{whole}.{fraction}0").parse().ok()?;
+        return Some(number * multiplier);
+    }
+    if !matches!(code.len(), 3 | 4) || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (digits, exponent) = code.split_at(code.len() - 1);
+    let digits: f64 = digits.parse().ok()?;
+    let exponent: i32 = exponent.parse().ok()?;
+    Some(digits * 10f64.powi(exponent))
+}
+
+/// The expected unit and typed attribute keys of a passive component.
+fn passive_kind(instance: &Instance) -> Option<(PhysicalUnit, &'static [&'static str])> {
+    match instance.component_type()?.as_str() {
+        "resistor" => Some((PhysicalUnit::Ohms, &["Resistance", "resistance"])),
+        "capacitor" => Some((PhysicalUnit::Farads, &["Capacitance", "capacitance"])),
+        "inductor" => Some((PhysicalUnit::Henries, &["Inductance", "inductance"])),
+        _ => None,
+    }
+}
+
+/// Parse the leading value of a `value` string, with the tolerance that
+/// follows it, e.g. `10k` from `"10k 1% 50V"`.
+fn leading_value(text: &str) -> Option<PhysicalValue> {
+    let mut tokens = text.split_whitespace();
+    let value = tokens.next()?;
+    match tokens.next().filter(|token| token.ends_with('%')) {
+        Some(tolerance) => format!("{value} {tolerance}").parse().ok(),
+        None => value.parse().ok(),
+    }
+}
+
+fn point_value(value: f64, unit: PhysicalUnit) -> String {
+    PhysicalValue::new(value, 0.0, unit).to_string()
+}
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() <= VALUE_EPSILON * a.abs().max(b.abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_leading_passive_value() {
+        assert_eq!(normalize_value("4k7"), "4.7k");
+        assert_eq!(normalize_value("0.1uF 16V X7R"), "100nF 16V X7R");
+        assert_eq!(normalize_value("10k 1%"), "10k 1%");
+        assert!(matches!(normalize_value("10k 1%"), Cow::Borrowed(_)));
+        assert_eq!(normalize_value("3.3V"), "3.3V");
+        assert_eq!(normalize_value("USB 2.0"), "USB 2.0");
+        assert_eq!(normalize_value("1N4148"), "1N4148");
+    }
+
+    #[test]
+    fn e_series_membership() {
+        assert_eq!(
+            ESeries::E96.mantissas()[..6],
+            [1.00, 1.02, 1.05, 1.07, 1.10, 1.13]
+        );
+        assert_eq!(ESeries::E12.mantissas().len(), 12);
+        assert!(ESeries::E192.mantissas().contains(&9.20));
+
+        assert!(ESeries::E24.contains(4700.0));
+        assert!(ESeries::E24.contains(100e-9));
+        assert!(!ESeries::E24.contains(4750.0));
+        assert!(ESeries::E96.contains(4750.0));
+        assert!(ESeries::E96.contains(10_000.0));
+        assert!(!ESeries::E96.contains(4700.0));
+    }
+
+    #[test]
+    fn decodes_resistor_mpns() {
+        assert_eq!(mpn_resistance("ERJ-3EKF1002V"), Some(10_000.0));
+        assert_eq!(mpn_resistance("ERJ-3GEYJ472V"), Some(4700.0));
+        assert_eq!(mpn_resistance("ERJ-2RKF10R0X"), Some(10.0));
+        assert_eq!(mpn_resistance("RC0603FR-074K7L"), Some(4700.0));
+        assert_eq!(mpn_resistance("RC0402JR-070RL"), Some(0.0));
+        assert_eq!(mpn_resistance("CRCW060310K0FKEA"), Some(10_000.0));
+        assert_eq!(mpn_resistance("GRM188R71C104KA01D"), None);
+    }
+}
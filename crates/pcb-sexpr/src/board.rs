@@ -630,6 +630,16 @@ fn min_fp_text(kind: &str, value: &str, layer: &str) -> Sexpr {
 
 /// Extract keyed footprints (those with a `(path "/...")`), ignoring unkeyed footprints.
 pub fn extract_keyed_footprints(root: &Sexpr) -> Result<Vec<FootprintInfo>, String> {
+    extract_footprint_infos(root, true)
+}
+
+/// Extract every footprint, including ones without a KiCad `(path ...)`
+/// (e.g. placed by hand). Such footprints have an empty `path`.
+pub fn extract_footprints(root: &Sexpr) -> Result<Vec<FootprintInfo>, String> {
+    extract_footprint_infos(root, false)
+}
+
+fn extract_footprint_infos(root: &Sexpr, keyed_only: bool) -> Result<Vec<FootprintInfo>, String> {
     let root_list = root
         .as_list()
         .ok_or_else(|| "KiCad PCB root is not a list".to_string())?;
@@ -692,8 +702,10 @@ pub fn extract_keyed_footprints(root: &Sexpr) -> Result<Vec<FootprintInfo>, Stri
             }
         }
 
-        let Some(path) = path else {
-            continue;
+        let path = match path {
+            Some(path) => path,
+            None if keyed_only => continue,
+            None => String::new(),
         };
 
         let fpid = items.get(1).and_then(Sexpr::as_str).map(|s| s.to_string());
@@ -717,6 +729,57 @@ pub fn extract_keyed_footprints(root: &Sexpr) -> Result<Vec<FootprintInfo>, Stri
     Ok(out)
 }
 
+/// Count board-level copper items (tracks, arcs, vias, zones) per net name.
+///
+/// KiCad 9 items reference nets by number, resolved through the board's net
+/// table; KiCad 10 items name the net directly. Items on no net are skipped.
+pub fn copper_net_item_counts(root: &Sexpr) -> BTreeMap<String, usize> {
+    let Some(root_list) = root.as_list() else {
+        return BTreeMap::new();
+    };
+
+    let mut net_names: BTreeMap<i64, &str> = BTreeMap::new();
+    for node in root_list.iter().skip(1) {
+        let Some(items) = node.as_list() else {
+            continue;
+        };
+        if let (Some(number), Some(name)) =
+            (items.get(1).and_then(Sexpr::as_int), net_name_str(items))
+        {
+            net_names.insert(number, name);
+        }
+    }
+
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for node in root_list.iter().skip(1) {
+        let Some(items) = node.as_list() else {
+            continue;
+        };
+        if !matches!(
+            items.first().and_then(Sexpr::as_sym),
+            Some("segment" | "arc" | "via" | "zone")
+        ) {
+            continue;
+        }
+        let Some(net) = items
+            .iter()
+            .filter_map(Sexpr::as_list)
+            .find(|list| list.first().and_then(Sexpr::as_sym) == Some("net"))
+        else {
+            continue;
+        };
+        let name = net_name_str(net).or_else(|| {
+            net.get(1)
+                .and_then(Sexpr::as_int)
+                .and_then(|number| net_names.get(&number).copied())
+        });
+        if let Some(name) = name.filter(|name| !name.is_empty()) {
+            *counts.entry(name.to_string()).or_default() += 1;
+        }
+    }
+    counts
+}
+
 fn parse_at_list(list: &[Sexpr]) -> Option<FootprintAt> {
     let x = crate::number_as_f64(list.get(1)?)?;
     let y = crate::number_as_f64(list.get(2)?)?;
@@ -1262,6 +1325,41 @@ mod tests {
         assert!(!fp.span.is_empty());
     }
 
+    #[test]
+    fn test_extract_footprints_includes_unkeyed() {
+        let input = r#"(kicad_pcb
+            (footprint "R" (property "Reference" "R1") (path "/abc-123"))
+            (footprint "MountingHole" (property "Reference" "H1") (attr board_only))
+        )"#;
+
+        let board = parse(input).unwrap();
+        let fps = extract_footprints(&board).unwrap();
+        assert_eq!(fps.len(), 2);
+        assert_eq!(fps[1].path, "");
+        assert_eq!(fps[1].attrs, vec!["board_only".to_string()]);
+    }
+
+    #[test]
+    fn test_copper_net_item_counts() {
+        let input = r#"(kicad_pcb
+            (net 0 "")
+            (net 1 "GND")
+            (net 2 "OLD")
+            (segment (start 0 0) (end 1 0) (net 1))
+            (segment (start 0 0) (end 1 0) (net 0))
+            (via (at 0 0) (net 2))
+            (arc (start 0 0) (mid 1 1) (end 2 0) (net "GND"))
+            (zone (net 1) (net_name "GND"))
+        )"#;
+
+        let board = parse(input).unwrap();
+        let counts = copper_net_item_counts(&board);
+        assert_eq!(
+            counts,
+            BTreeMap::from([("GND".to_string(), 3), ("OLD".to_string(), 1)])
+        );
+    }
+
     #[test]
     fn test_extract_keyed_footprints_kicad10_net_syntax() {
         let input = r#"(kicad_pcb
@@ -3,6 +3,8 @@
 //! This module contains:
 //! - A KiCad-style text prettifier (`prettify`) that mirrors KiCad's `Prettify()`
 //! - A tree entrypoint (`format_tree`) that always formats via `prettify`
+//! - A save-style entrypoint (`format_kicad`) for full-tree rewrites that also
+//!   matches KiCad 8/9 quoting and number formatting per file type
 
 use crate::{Sexpr, SexprKind};
 
@@ -30,6 +32,73 @@ impl FormatMode {
     }
 }
 
+/// KiCad file types, which differ in internal units and in which values are
+/// written as quoted strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KicadFileKind {
+    /// `.kicad_pcb` boards and `.kicad_mod` footprints (1nm units).
+    Pcb,
+    /// `.kicad_sch` schematics (100nm units).
+    Schematic,
+    /// `.kicad_sym` symbol libraries (100nm units).
+    Symbol,
+}
+
+/// Which arguments of a list KiCad writes as quoted strings.
+#[derive(Clone, Copy)]
+enum QuotedArgs {
+    /// The first `n` arguments after the head.
+    Leading(usize),
+    /// Every argument.
+    All,
+    /// A `name value` pair, but not a lone flag such as a pad's
+    /// `(property pad_prop_heatsink)`.
+    NameValue,
+}
+
+impl KicadFileKind {
+    /// File kind for a path's extension, if it is a KiCad S-expression file.
+    pub fn from_path(path: &std::path::Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "kicad_pcb" | "kicad_mod" => Some(Self::Pcb),
+            "kicad_sch" => Some(Self::Schematic),
+            "kicad_sym" => Some(Self::Symbol),
+            _ => None,
+        }
+    }
+
+    /// Decimal places of one internal unit, in millimetres.
+    fn decimals(self) -> usize {
+        match self {
+            Self::Pcb => 6,
+            Self::Schematic | Self::Symbol => 4,
+        }
+    }
+
+    fn quoted_args(self, head: &str) -> Option<QuotedArgs> {
+        use QuotedArgs::{All, Leading, NameValue};
+
+        let common = match head {
+            "generator" | "generator_version" | "uuid" | "paper" => Some(Leading(1)),
+            "property" => Some(NameValue),
+            _ => None,
+        };
+        common.or(match (self, head) {
+            (Self::Pcb, "layer" | "footprint" | "pad" | "model" | "descr" | "tags") => {
+                Some(Leading(1))
+            }
+            (Self::Pcb, "layers") => Some(All),
+            (
+                Self::Schematic,
+                "lib_id" | "lib_name" | "reference" | "path" | "project" | "label" | "global_label"
+                | "hierarchical_label" | "text",
+            ) => Some(Leading(1)),
+            (Self::Symbol, "symbol" | "extends" | "name" | "number") => Some(Leading(1)),
+            _ => None,
+        })
+    }
+}
+
 /// Pretty-print raw S-expression text using KiCad's `Prettify()` logic.
 ///
 /// This intentionally mirrors KiCad's character-stream formatter behavior:
@@ -177,12 +246,79 @@ pub fn format_tree(sexpr: &Sexpr, mode: FormatMode) -> String {
     prettify(&raw, mode)
 }
 
+/// Format an S-expression tree the way KiCad 8/9 saves a file of `kind`.
+///
+/// On top of [`format_tree`], symbol atoms in positions KiCad always quotes
+/// (e.g. `layer`, `uuid`, `property` names) become strings, floats that were
+/// not parsed from text are rounded to the file's internal unit, and strings
+/// are escaped like KiCad's `OUTPUTFORMATTER::Quotes`. Atoms parsed from a
+/// KiCad-saved file keep their exact text, so such files round-trip unchanged.
+pub fn format_kicad(sexpr: &Sexpr, kind: KicadFileKind) -> String {
+    let mut raw = String::new();
+    write_kicad(sexpr, kind, false, &mut raw);
+    prettify(&raw, FormatMode::Normal)
+}
+
 fn serialize_compact(sexpr: &Sexpr) -> String {
     let mut out = String::new();
     write_compact(sexpr, &mut out);
     out
 }
 
+fn write_kicad(sexpr: &Sexpr, kind: KicadFileKind, quote: bool, out: &mut String) {
+    match &sexpr.kind {
+        SexprKind::Symbol(s) if quote => write_kicad_string(s, out),
+        SexprKind::String(s) => write_kicad_string(s, out),
+        SexprKind::F64(f) if sexpr.raw_atom.is_none() => {
+            out.push_str(&format_kicad_float(*f, kind.decimals()));
+        }
+        SexprKind::List(items) => {
+            let quoted = match items.first().map(|head| &head.kind) {
+                Some(SexprKind::Symbol(head)) => kind.quoted_args(head),
+                _ => None,
+            };
+            out.push('(');
+            for (idx, item) in items.iter().enumerate() {
+                if idx > 0 {
+                    out.push(' ');
+                }
+                let quote = match quoted {
+                    Some(QuotedArgs::Leading(n)) => (1..=n).contains(&idx),
+                    Some(QuotedArgs::All) => idx > 0,
+                    Some(QuotedArgs::NameValue) => {
+                        (1..=2).contains(&idx) && items.get(2).is_some_and(|value| !value.is_list())
+                    }
+                    None => false,
+                };
+                write_kicad(item, kind, quote, out);
+            }
+            out.push(')');
+        }
+        _ => write_compact(sexpr, out),
+    }
+}
+
+/// Quote like KiCad, which escapes quotes, backslashes and line breaks only.
+fn write_kicad_string(s: &str, out: &mut String) {
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            _ => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+/// Round to `decimals` places and drop trailing zeros, never printing `-0`.
+fn format_kicad_float(value: f64, decimals: usize) -> String {
+    let s = trim_float(format!("{value:.decimals$}"));
+    if s == "-0" { "0".to_string() } else { s }
+}
+
 fn write_compact(sexpr: &Sexpr, out: &mut String) {
     match &sexpr.kind {
         SexprKind::Symbol(s) => out.push_str(s),
@@ -311,7 +447,7 @@ fn trim_float(mut s: String) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{FormatMode, format_tree, prettify};
+    use super::{FormatMode, KicadFileKind, format_kicad, format_tree, prettify};
     use crate::{Sexpr, parse};
 
     #[test]
@@ -393,4 +529,49 @@ mod tests {
         assert!(out.contains("(dashed_line_gap_ratio 3.000000)"));
         assert!(out.contains("(hpglpendiameter 15.000000)"));
     }
+
+    #[test]
+    fn format_kicad_roundtrips_kicad_saved_schematic() {
+        let saved = "(kicad_sch\n\t(version 20250114)\n\t(generator \"eeschema\")\n\t(generator_version \"9.0\")\n\t(uuid \"7cb90b3f-e87a-4fd9-8f47-56f0aef646ac\")\n\t(paper \"A4\")\n\t(lib_symbols)\n\t(text \"Note: see \\\"A\\\"\"\n\t\t(exclude_from_sim no)\n\t\t(at 101.6 50.8 0)\n\t)\n)\n";
+
+        let parsed = parse(saved).unwrap();
+        assert_eq!(format_kicad(&parsed, KicadFileKind::Schematic), saved);
+    }
+
+    #[test]
+    fn format_kicad_quotes_and_rounds_built_nodes() {
+        let footprint = Sexpr::list(vec![
+            Sexpr::symbol("footprint"),
+            Sexpr::symbol("R_0402"),
+            Sexpr::list(vec![Sexpr::symbol("layer"), Sexpr::symbol("F.Cu")]),
+            Sexpr::list(vec![
+                Sexpr::symbol("at"),
+                Sexpr::float(0.1 + 0.2),
+                Sexpr::float(-0.0000001),
+            ]),
+            Sexpr::list(vec![
+                Sexpr::symbol("property"),
+                Sexpr::symbol("Reference"),
+                Sexpr::string("R1\tx"),
+                Sexpr::list(vec![Sexpr::symbol("hide"), Sexpr::symbol("yes")]),
+            ]),
+        ]);
+
+        assert_eq!(
+            format_kicad(&footprint, KicadFileKind::Pcb),
+            "(footprint \"R_0402\"\n\t(layer \"F.Cu\")\n\t(at 0.3 0)\n\t(property \"Reference\" \"R1\tx\"\n\t\t(hide yes)\n\t)\n)\n"
+        );
+
+        let pin = Sexpr::list(vec![Sexpr::symbol("length"), Sexpr::float(2.540_000_01)]);
+        assert_eq!(format_kicad(&pin, KicadFileKind::Symbol), "(length 2.54)\n");
+
+        let pad_flag = Sexpr::list(vec![
+            Sexpr::symbol("property"),
+            Sexpr::symbol("pad_prop_heatsink"),
+        ]);
+        assert_eq!(
+            format_kicad(&pad_flag, KicadFileKind::Pcb),
+            "(property pad_prop_heatsink)\n"
+        );
+    }
 }
@@ -8,11 +8,13 @@
 //!
 //! - [`Sexpr::walk`] - Depth-first traversal with ancestor context
 //! - [`Sexpr::walk_strings`] - Walk only string literals
+//! - [`Sexpr::select`] - Find lists matching a CSS-like [`select::Selector`]
 //! - [`PatchSet`] - Collect patches and write directly to any `std::io::Write`
 
 pub mod board;
 pub mod formatter;
 pub mod kicad;
+pub mod select;
 
 use std::fmt;
 use std::io::BufRead;
@@ -77,7 +79,7 @@ pub fn set_or_insert_named_list(
 /// Coerce a number atom into f64.
 ///
 /// KiCad S-exprs sometimes encode whole numbers as ints and sometimes as floats.
-pub(crate) fn number_as_f64(node: &Sexpr) -> Option<f64> {
+pub fn number_as_f64(node: &Sexpr) -> Option<f64> {
     node.as_float().or_else(|| node.as_int().map(|v| v as f64))
 }
 
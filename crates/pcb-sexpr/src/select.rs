@@ -0,0 +1,441 @@
+//! CSS-like selectors over S-expression trees.
+//!
+//! A selector is a sequence of steps separated by combinators:
+//!
+//! - `footprint` matches lists whose tag (first symbol) is `footprint`; `*`
+//!   matches any list.
+//! - `a b` matches `b` anywhere below `a`; `a > b` matches `b` directly inside `a`.
+//! - `[key]` requires a child list `(key ...)`.
+//! - `[key=value]` requires a child list `(key value ...)`. A numeric key
+//!   compares the positional argument at that index (1 is the first item after
+//!   the tag), and `name` also compares the first positional argument, so
+//!   `property[name=Reference]` matches `(property "Reference" "R1" ...)`.
+//!
+//! Values may be bare or double-quoted. Matches carry the node (and therefore
+//! its [`Span`](crate::Span)) plus its ancestors, ready for building a
+//! [`PatchSet`](crate::PatchSet).
+//!
+//! ```
+//! use pcb_sexpr::parse;
+//!
+//! let board = parse(r#"(kicad_pcb (footprint "R" (property "Reference" "R1")))"#).unwrap();
+//! let matches = board.select("footprint > property[name=Reference]").unwrap();
+//! assert_eq!(matches[0].node.as_list().unwrap()[2].as_str(), Some("R1"));
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Sexpr, SexprKind};
+
+/// A node matched by a [`Selector`].
+#[derive(Debug, Clone)]
+pub struct Match<'a> {
+    /// The matched list node.
+    pub node: &'a Sexpr,
+    /// Ancestors from root to parent of the node (root first).
+    pub ancestors: Vec<&'a Sexpr>,
+}
+
+impl<'a> Match<'a> {
+    /// Get the parent node (last ancestor).
+    pub fn parent(&self) -> Option<&'a Sexpr> {
+        self.ancestors.last().copied()
+    }
+
+    /// Find the nearest ancestor list with the given tag.
+    pub fn closest(&self, tag: &str) -> Option<&'a Sexpr> {
+        self.ancestors
+            .iter()
+            .rev()
+            .copied()
+            .find(|node| list_tag(node) == Some(tag))
+    }
+}
+
+/// A parsed selector; see [`crate::select`] for the syntax.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Step {
+    /// Relation to the previous step; ignored for the first step.
+    combinator: Combinator,
+    /// Required tag, or `None` for `*`.
+    tag: Option<String>,
+    predicates: Vec<Predicate>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Predicate {
+    key: String,
+    value: Option<String>,
+}
+
+/// Errors that can occur while parsing a selector
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectorError {
+    /// Byte offset into the selector
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid selector at offset {}: {}",
+            self.position, self.message
+        )
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Selector::parse(input)
+    }
+}
+
+impl Selector {
+    /// Parse a selector such as `footprint > property[name=Reference]`.
+    pub fn parse(input: &str) -> Result<Self, SelectorError> {
+        SelectorParser { input, pos: 0 }.parse()
+    }
+
+    /// Return every list under (and including) `root` matched by this
+    /// selector, in document order.
+    pub fn select<'a>(&self, root: &'a Sexpr) -> Vec<Match<'a>> {
+        fn visit<'a>(
+            selector: &Selector,
+            node: &'a Sexpr,
+            ancestors: &mut Vec<&'a Sexpr>,
+            out: &mut Vec<Match<'a>>,
+        ) {
+            let Some(children) = node.as_list() else {
+                return;
+            };
+            if matches_at(&selector.steps, node, ancestors) {
+                out.push(Match {
+                    node,
+                    ancestors: ancestors.clone(),
+                });
+            }
+            ancestors.push(node);
+            for child in children {
+                visit(selector, child, ancestors, out);
+            }
+            ancestors.pop();
+        }
+
+        let mut out = Vec::new();
+        visit(self, root, &mut Vec::new(), &mut out);
+        out
+    }
+}
+
+impl Sexpr {
+    /// Parse `selector` and return all matching lists in this tree.
+    pub fn select(&self, selector: &str) -> Result<Vec<Match<'_>>, SelectorError> {
+        Ok(Selector::parse(selector)?.select(self))
+    }
+
+    /// Parse `selector` and return the first matching list in this tree.
+    pub fn select_first(&self, selector: &str) -> Result<Option<Match<'_>>, SelectorError> {
+        Ok(Selector::parse(selector)?.select(self).into_iter().next())
+    }
+}
+
+/// Check `node` against the last step, then the earlier steps against its
+/// ancestors, backtracking over descendant combinators.
+fn matches_at(steps: &[Step], node: &Sexpr, ancestors: &[&Sexpr]) -> bool {
+    let Some((step, earlier)) = steps.split_last() else {
+        return true;
+    };
+    if !step.matches(node) {
+        return false;
+    }
+    if earlier.is_empty() {
+        return true;
+    }
+    match step.combinator {
+        Combinator::Child => ancestors
+            .split_last()
+            .is_some_and(|(parent, rest)| matches_at(earlier, parent, rest)),
+        Combinator::Descendant => (0..ancestors.len())
+            .rev()
+            .any(|i| matches_at(earlier, ancestors[i], &ancestors[..i])),
+    }
+}
+
+impl Step {
+    fn matches(&self, node: &Sexpr) -> bool {
+        let Some(items) = node.as_list() else {
+            return false;
+        };
+        if let Some(tag) = &self.tag
+            && list_tag(node) != Some(tag.as_str())
+        {
+            return false;
+        }
+        self.predicates
+            .iter()
+            .all(|predicate| predicate.matches(items))
+    }
+}
+
+impl Predicate {
+    fn matches(&self, items: &[Sexpr]) -> bool {
+        let child_arg = items
+            .iter()
+            .skip(1)
+            .filter_map(Sexpr::as_list)
+            .filter(|child| child.first().and_then(Sexpr::as_sym) == Some(self.key.as_str()))
+            .map(|child| child.get(1));
+        let positional = match self.key.parse::<usize>() {
+            Ok(index) => Some(items.get(index)),
+            Err(_) if self.key == "name" => Some(items.get(1)),
+            Err(_) => None,
+        };
+
+        let Some(expected) = &self.value else {
+            return positional.flatten().is_some() || child_arg.count() > 0;
+        };
+        child_arg
+            .chain(positional)
+            .flatten()
+            .any(|arg| atom_text(arg).is_some_and(|text| text == expected.as_str()))
+    }
+}
+
+fn list_tag(node: &Sexpr) -> Option<&str> {
+    node.as_list()?.first()?.as_sym()
+}
+
+fn atom_text(node: &Sexpr) -> Option<Cow<'_, str>> {
+    match &node.kind {
+        SexprKind::Symbol(s) | SexprKind::String(s) => Some(Cow::Borrowed(s)),
+        SexprKind::Int(n) => Some(
+            node.raw_atom
+                .as_deref()
+                .map_or_else(|| Cow::Owned(n.to_string()), Cow::Borrowed),
+        ),
+        SexprKind::F64(f) => Some(
+            node.raw_atom
+                .as_deref()
+                .map_or_else(|| Cow::Owned(f.to_string()), Cow::Borrowed),
+        ),
+        SexprKind::List(_) => None,
+    }
+}
+
+struct SelectorParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> SelectorParser<'a> {
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, SelectorError> {
+        Err(SelectorError {
+            position: self.pos,
+            message: message.into(),
+        })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    /// Skip whitespace, returning whether any was skipped.
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+        self.pos > start
+    }
+
+    fn take_while(&mut self, keep: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+        while let Some(c) = self.peek().filter(|&c| keep(c)) {
+            self.pos += c.len_utf8();
+        }
+        &self.input[start..self.pos]
+    }
+
+    fn parse(mut self) -> Result<Selector, SelectorError> {
+        let mut steps = Vec::new();
+        self.skip_whitespace();
+        let mut combinator = Combinator::Descendant;
+        loop {
+            steps.push(self.parse_step(combinator)?);
+            let spaced = self.skip_whitespace();
+            match self.peek() {
+                None => break,
+                Some('>') => {
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    combinator = Combinator::Child;
+                }
+                Some(_) if spaced => combinator = Combinator::Descendant,
+                Some(c) => return self.error(format!("unexpected '{c}'")),
+            }
+        }
+        Ok(Selector { steps })
+    }
+
+    fn parse_step(&mut self, combinator: Combinator) -> Result<Step, SelectorError> {
+        let tag = match self.take_while(|c| !c.is_whitespace() && !matches!(c, '>' | '[' | ']')) {
+            "" => return self.error("expected a tag or '*'"),
+            "*" => None,
+            tag => Some(tag.to_string()),
+        };
+
+        let mut predicates = Vec::new();
+        while self.peek() == Some('[') {
+            self.pos += 1;
+            self.skip_whitespace();
+            let key = self.take_while(|c| !c.is_whitespace() && !matches!(c, '=' | ']'));
+            if key.is_empty() {
+                return self.error("expected a key");
+            }
+            self.skip_whitespace();
+            let value = if self.peek() == Some('=') {
+                self.pos += 1;
+                self.skip_whitespace();
+                Some(self.parse_value()?)
+            } else {
+                None
+            };
+            self.skip_whitespace();
+            if self.peek() != Some(']') {
+                return self.error("expected ']'");
+            }
+            self.pos += 1;
+            predicates.push(Predicate {
+                key: key.to_string(),
+                value,
+            });
+        }
+
+        Ok(Step {
+            combinator,
+            tag,
+            predicates,
+        })
+    }
+
+    fn parse_value(&mut self) -> Result<String, SelectorError> {
+        if self.peek() != Some('"') {
+            let value = self.take_while(|c| !c.is_whitespace() && c != ']');
+            if value.is_empty() {
+                return self.error("expected a value");
+            }
+            return Ok(value.to_string());
+        }
+
+        self.pos += 1;
+        let mut value = String::new();
+        let mut chars = self.input[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(value);
+                }
+                '\\' => match chars.next() {
+                    Some((_, escaped)) => value.push(escaped),
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+        self.pos = self.input.len();
+        self.error("unterminated string")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse;
+
+    const BOARD: &str = r#"(kicad_pcb
+  (net 1 "GND")
+  (footprint "Resistor_SMD:R_0603" (layer "F.Cu")
+    (property "Reference" "R1")
+    (property "Value" "10k")
+    (pad "1" smd rect (net 1 "GND")))
+  (footprint "Capacitor_SMD:C_0402" (layer "B.Cu")
+    (property "Reference" "C1")
+    (pad "2" smd rect (net 2 "VCC"))))"#;
+
+    fn texts(selector: &str) -> Vec<String> {
+        let board = parse(BOARD).unwrap();
+        board
+            .select(selector)
+            .unwrap()
+            .iter()
+            .map(|m| BOARD[m.node.span.start..m.node.span.end].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn selects_by_tag_and_positional_name() {
+        assert_eq!(
+            texts("footprint > property[name=Reference]"),
+            [
+                r#"(property "Reference" "R1")"#,
+                r#"(property "Reference" "C1")"#
+            ]
+        );
+        assert_eq!(
+            texts("footprint[layer=B.Cu] pad"),
+            [r#"(pad "2" smd rect (net 2 "VCC"))"#]
+        );
+        assert_eq!(texts("pad > net[2=\"GND\"]"), [r#"(net 1 "GND")"#]);
+    }
+
+    #[test]
+    fn child_combinator_is_stricter_than_descendant() {
+        assert_eq!(texts("kicad_pcb net").len(), 3);
+        assert_eq!(texts("kicad_pcb > net"), [r#"(net 1 "GND")"#]);
+        assert_eq!(texts("* > pad[1=2]").len(), 1);
+        assert_eq!(texts("footprint[property]").len(), 2);
+    }
+
+    #[test]
+    fn match_exposes_ancestors() {
+        let board = parse(BOARD).unwrap();
+        let found = board.select_first("pad net[name=2]").unwrap().unwrap();
+        let footprint = found.closest("footprint").unwrap();
+        assert_eq!(
+            footprint.as_list().unwrap()[1].as_str(),
+            Some("Capacitor_SMD:C_0402")
+        );
+        assert_eq!(
+            found.parent().unwrap().as_list().unwrap()[1].as_str(),
+            Some("2")
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_selectors() {
+        assert!(parse("(a)").unwrap().select("").is_err());
+        assert!(parse("(a)").unwrap().select("a[b=").is_err());
+        let err = parse("(a)").unwrap().select("a[b=\"c]").unwrap_err();
+        assert_eq!(err.message, "unterminated string");
+        assert!(parse("(a)").unwrap().select("a >").is_err());
+    }
+}
@@ -1,5 +1,7 @@
 pub mod ngspice;
+pub mod op;
 pub use ngspice::{SimulationResult, check_ngspice_installed, run_ngspice_captured};
+pub use op::{OperatingPoint, annotate_schematic, parse_operating_point};
 
 use anyhow::Result;
 use itertools::Itertools;
@@ -0,0 +1,164 @@
+//! DC operating point results from an ngspice `.op` analysis.
+//!
+//! In batch mode ngspice prints a `Node Voltage` table followed by a
+//! `Source Current` table. Node names come back lowercased, so they are
+//! matched case-insensitively against the schematic's net names.
+
+use std::collections::{BTreeMap, HashMap};
+
+use pcb_sch::{AttributeValue, Schematic};
+
+/// Net property holding the DC operating point voltage, e.g. `"1.65V"`.
+pub const PROP_OP_VOLTAGE: &str = "op_voltage";
+
+/// Node voltages and source currents of a DC operating point.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OperatingPoint {
+    /// Node voltages in volts, keyed by ngspice node name.
+    pub voltages: BTreeMap<String, f64>,
+    /// Branch currents in amps, keyed by source name (e.g. `v1`).
+    pub currents: BTreeMap<String, f64>,
+}
+
+#[derive(Clone, Copy)]
+enum Table {
+    Voltages,
+    Currents,
+}
+
+/// Parse the operating point tables from ngspice batch output. Returns `None`
+/// if the output has no `.op` results.
+pub fn parse_operating_point(output: &str) -> Option<OperatingPoint> {
+    let mut op = OperatingPoint::default();
+    let mut found = false;
+    let mut table = None;
+
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            ["Node", "Voltage"] => {
+                table = Some(Table::Voltages);
+                found = true;
+            }
+            ["Source", "Current"] => table = Some(Table::Currents),
+            [] => {}
+            [first, ..] if first.starts_with("--") => {}
+            [name, value] if table.is_some() => {
+                let Ok(value) = value.parse::<f64>() else {
+                    table = None;
+                    continue;
+                };
+                match table {
+                    Some(Table::Voltages) => {
+                        op.voltages.insert(node_name(name), value);
+                    }
+                    Some(Table::Currents) => {
+                        let source = name.strip_suffix("#branch").unwrap_or(*name);
+                        op.currents.insert(source.to_string(), value);
+                    }
+                    None => {}
+                }
+            }
+            _ => table = None,
+        }
+    }
+
+    found.then_some(op)
+}
+
+/// Strip the `v(...)` wrapper some ngspice versions print around node names.
+fn node_name(name: &str) -> String {
+    name.strip_prefix("v(")
+        .and_then(|name| name.strip_suffix(')'))
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// Record each node voltage as the [`PROP_OP_VOLTAGE`] property of the
+/// matching net. Returns the number of nets annotated.
+pub fn annotate_schematic(schematic: &mut Schematic, op: &OperatingPoint) -> usize {
+    let by_node: HashMap<String, String> = schematic
+        .nets
+        .keys()
+        .map(|name| (name.to_lowercase(), name.clone()))
+        .collect();
+
+    let mut annotated = 0;
+    for (node, volts) in &op.voltages {
+        let Some(net) = by_node
+            .get(node)
+            .and_then(|name| schematic.nets.get_mut(name))
+        else {
+            continue;
+        };
+        net.properties.insert(
+            PROP_OP_VOLTAGE.to_string(),
+            AttributeValue::String(format_volts(*volts)),
+        );
+        annotated += 1;
+    }
+    annotated
+}
+
+/// Format a voltage with four significant digits, in mV below one volt.
+pub fn format_volts(volts: f64) -> String {
+    let (value, unit) = if volts != 0.0 && volts.abs() < 1.0 {
+        (volts * 1e3, "mV")
+    } else {
+        (volts, "V")
+    };
+    let digits = 3usize.saturating_sub(value.abs().log10().floor().max(0.0) as usize);
+    let text = format!("{value:.digits$}");
+    let text = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &text
+    };
+    format!("{text}{unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "\
+Circuit: divider
+
+No. of Data Rows : 1
+\tNode                                  Voltage
+\t----                                  -------
+\t----\t-------
+\tvin                              5.000000e+00
+\tout                              1.650000e+00
+
+\tSource\tCurrent
+\t------\t-------
+
+\tv1#branch                        -3.30000e-04
+
+ Resistor: Simple linear resistor
+     device                   r1
+";
+
+    #[test]
+    fn parses_voltages_and_currents() {
+        let op = parse_operating_point(OUTPUT).unwrap();
+        assert_eq!(op.voltages.get("vin"), Some(&5.0));
+        assert_eq!(op.voltages.get("out"), Some(&1.65));
+        assert_eq!(op.voltages.len(), 2);
+        assert_eq!(op.currents.get("v1"), Some(&-3.3e-4));
+        assert_eq!(op.currents.len(), 1);
+
+        assert_eq!(parse_operating_point("Circuit: divider\n"), None);
+    }
+
+    #[test]
+    fn formats_volts() {
+        assert_eq!(format_volts(5.0), "5V");
+        assert_eq!(format_volts(1.65), "1.65V");
+        assert_eq!(format_volts(0.33), "330mV");
+        assert_eq!(format_volts(-0.0123456), "-12.35mV");
+        assert_eq!(format_volts(12.3456), "12.35V");
+        assert_eq!(format_volts(0.0), "0V");
+    }
+}
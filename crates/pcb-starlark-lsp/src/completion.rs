@@ -155,9 +155,35 @@ impl<T: LspContext> Backend<T> {
         document_uri: &LspUrl,
         workspace_root: Option<&Path>,
     ) -> Vec<CompletionItem> {
-        self.context
-            .resolve_load(load_path, document_uri, workspace_root)
-            .and_then(|url| self.get_ast_or_load_from_disk(&url))
+        let url = self
+            .context
+            .resolve_load(load_path, document_uri, workspace_root);
+        if let Ok(url) = &url
+            && let Some(exports) = self.context.get_indexed_exports(url)
+        {
+            return exports
+                .into_iter()
+                .filter(|(name, _)| !previously_loaded.contains(name))
+                .map(|(name, meta)| CompletionItem {
+                    text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                        range: current_span.into(),
+                        new_text: name.clone(),
+                    })),
+                    label: name,
+                    kind: meta.kind,
+                    detail: meta.detail,
+                    documentation: meta.documentation.map(|docs| {
+                        Documentation::MarkupContent(MarkupContent {
+                            kind: MarkupKind::Markdown,
+                            value: docs,
+                        })
+                    }),
+                    ..Default::default()
+                })
+                .collect();
+        }
+
+        url.and_then(|url| self.get_ast_or_load_from_disk(&url))
             .into_iter()
             .flatten()
             .flat_map(|ast| {
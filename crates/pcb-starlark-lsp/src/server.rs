@@ -401,6 +401,13 @@ pub trait LspContext {
         None
     }
 
+    /// Return the symbols `uri` exports with their completion metadata when
+    /// the context keeps a prebuilt index of them. `None` makes the server
+    /// parse the file instead.
+    fn get_indexed_exports(&self, _uri: &LspUrl) -> Option<Vec<(String, CompletionMeta)>> {
+        None
+    }
+
     /// Get valid completion options if possible, based on the kind of string
     /// completion expected (e.g. any string literal, versus the path argument in
     /// a load statement).
@@ -512,6 +512,12 @@ impl Sandbox {
             .replace_all(&result, r#""describe": "<GIT_HASH>""#)
             .to_string();
 
+        // Sanitize the release stamp's git describe (e.g., "git_describe": "v1.0.0-2-g6111ea3")
+        let stamp_describe_pattern = Regex::new(r#""git_describe":\s*"[^"]+""#).unwrap();
+        result = stamp_describe_pattern
+            .replace_all(&result, r#""git_describe": "<GIT_DESCRIBE>""#)
+            .to_string();
+
         // Sanitize git commit hashes in release output (e.g., "Release c634658-dirty staged successfully")
         let release_hash_pattern = Regex::new(r"Release [a-f0-9]{7,8}(-dirty)? staged").unwrap();
         result = release_hash_pattern
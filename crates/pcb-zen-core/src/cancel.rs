@@ -0,0 +1,86 @@
+//! Cooperative cancellation for long-running pipelines.
+//!
+//! A [`CancellationToken`] is cheap to clone and shared between the code that
+//! requests cancellation (e.g. a Ctrl-C handler) and the pipelines that poll
+//! it. Pipelines call [`CancellationToken::check`] at stage boundaries and
+//! clean up any partial output before propagating the resulting [`Cancelled`]
+//! error.
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Shared flag that signals a pipeline to stop at its next checkpoint.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation. Every clone of this token observes it.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Return [`Cancelled`] for `stage` if cancellation has been requested.
+    pub fn check(&self, stage: impl Into<String>) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled {
+                stage: stage.into(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CancellationToken")
+            .field(&self.is_cancelled())
+            .finish()
+    }
+}
+
+/// Error returned when a pipeline stops because its token was cancelled.
+///
+/// `stage` names where the pipeline stopped, e.g. `"evaluation of board.zen"`
+/// or `"layout sync"`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("cancelled during {stage}")]
+pub struct Cancelled {
+    pub stage: String,
+}
+
+impl Cancelled {
+    /// Find a [`Cancelled`] anywhere in an error's source chain.
+    pub fn find(err: &anyhow::Error) -> Option<&Cancelled> {
+        err.chain()
+            .find_map(|cause| cause.downcast_ref::<Cancelled>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_is_shared_between_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(token.check("eval").is_ok());
+
+        clone.cancel();
+        let err = token.check("layout sync").unwrap_err();
+        assert_eq!(err.to_string(), "cancelled during layout sync");
+
+        let wrapped = anyhow::Error::new(err).context("Failed to generate layout");
+        assert_eq!(Cancelled::find(&wrapped).unwrap().stage, "layout sync");
+    }
+}
@@ -6,6 +6,7 @@ use ariadne::{Label, Report, ReportKind, Source};
 use serde::{Deserialize, Serialize};
 
 use crate::FileProvider;
+use crate::workspace::package_url_covers;
 
 /// Top-level pcb.toml configuration.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -37,6 +38,39 @@ pub struct PcbToml {
     /// Access control configuration section
     #[serde(skip_serializing_if = "Option::is_none")]
     pub access: Option<AccessConfig>,
+
+    /// Diagnostic severity overrides keyed by diagnostic kind.
+    ///
+    /// Keys match hierarchically, so `"component.lifecycle" = "deny"` also applies to
+    /// `component.lifecycle.eol`. The most specific key wins.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lints: BTreeMap<String, LintLevel>,
+
+    /// Names exported when this directory is loaded as a whole.
+    ///
+    /// Maps each exported name to a `.zen` file relative to the manifest. Ignored
+    /// when the directory contains an `exports.zen`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub exports: BTreeMap<String, String>,
+
+    /// Upstream provenance of vendored assets such as datasheets and 3D models.
+    ///
+    /// Written by `pcb search` when a component is added and checked by
+    /// `pcb verify --assets`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provenance: Vec<AssetProvenance>,
+}
+
+/// Severity level for a `[lints]` entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// Suppress matching diagnostics.
+    Allow,
+    /// Report matching diagnostics as warnings.
+    Warn,
+    /// Report matching diagnostics as errors.
+    Deny,
 }
 
 /// Dependency tables stored under `[dependencies]` and `[dependencies.indirect]`.
@@ -277,6 +311,34 @@ pub struct WorkspaceConfig {
     #[serde(default, skip_serializing_if = "BomConfig::is_default")]
     pub bom: BomConfig,
 
+    /// Package publishing configuration.
+    #[serde(default, skip_serializing_if = "PublishConfig::is_default")]
+    pub publish: PublishConfig,
+
+    /// Restrictions on what evaluated packages may load.
+    #[serde(default, skip_serializing_if = "SandboxConfig::is_default")]
+    pub sandbox: SandboxConfig,
+
+    /// Net capacitance budgets for `pcb build --analyze`.
+    #[serde(default, skip_serializing_if = "CapacitanceConfig::is_default")]
+    pub capacitance: CapacitanceConfig,
+
+    /// Reference designator numbering policy.
+    #[serde(default, skip_serializing_if = "RefdesConfig::is_default")]
+    pub refdes: RefdesConfig,
+
+    /// Component value checks.
+    #[serde(default, skip_serializing_if = "ValuesConfig::is_default")]
+    pub values: ValuesConfig,
+
+    /// Net naming policy.
+    #[serde(
+        rename = "net-names",
+        default,
+        skip_serializing_if = "NetNamesConfig::is_default"
+    )]
+    pub net_names: NetNamesConfig,
+
     /// Default board name to use
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_board: Option<String>,
@@ -295,6 +357,12 @@ pub struct WorkspaceConfig {
     /// Example: ["modules/deprecated/*", "boards/test-*"]
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub exclude: Vec<String>,
+
+    /// Net names shared by every board in the workspace, mapped to their net type.
+    /// A registered name is one system-wide net; other names are board-local.
+    /// Example: { GND = "Ground", VBUS = "Power", CAN_H = "Net" }
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub nets: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -310,6 +378,261 @@ impl BomConfig {
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PublishConfig {
+    /// Size budget for a package's canonical archive, e.g. "5MiB" or "500KB".
+    #[serde(rename = "max-package-size", skip_serializing_if = "Option::is_none")]
+    pub max_package_size: Option<String>,
+}
+
+impl PublishConfig {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// `max-package-size` in bytes.
+    pub fn max_package_size_bytes(&self) -> Result<Option<u64>> {
+        self.max_package_size
+            .as_deref()
+            .map(|size| {
+                parse_byte_size(size).with_context(|| {
+                    format!("Invalid [workspace.publish] max-package-size '{size}'")
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Net loading analysis (`[workspace.capacitance]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CapacitanceConfig {
+    /// Estimated trace capacitance per pin-to-pin connection, e.g. "2.5pF".
+    #[serde(
+        rename = "trace-per-connection",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub trace_per_connection: Option<String>,
+
+    /// Capacitance budgets keyed by net name glob.
+    /// Example: { "I2C_*" = "400pF" }
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub budgets: BTreeMap<String, String>,
+}
+
+impl CapacitanceConfig {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Reference designator numbering (`[workspace.refdes]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RefdesConfig {
+    /// Number ranges keyed by prefix.
+    /// Example: { TP = { start = 500 }, J = { start = 100, end = 199 } }
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub prefixes: BTreeMap<String, RefdesPrefixConfig>,
+
+    /// Numbering offsets keyed by module instance path.
+    /// Example: { power = 100, "io.usb" = 200 }
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub modules: BTreeMap<String, u32>,
+}
+
+/// Number range for one prefix in `[workspace.refdes.prefixes]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RefdesPrefixConfig {
+    /// First number to assign (default 1).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<u32>,
+
+    /// Last number in the range; designators past it are reported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<u32>,
+
+    /// Numbers never assigned automatically.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skip: Vec<u32>,
+}
+
+impl RefdesConfig {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Numbering policy for `Schematic::assign_reference_designators_with_policy`.
+    pub fn policy(&self) -> Result<pcb_sch::refdes_policy::RefdesPolicy> {
+        pcb_sch::refdes_policy::RefdesPolicy::new(
+            self.prefixes.iter().map(|(prefix, rule)| {
+                (
+                    prefix.clone(),
+                    pcb_sch::refdes_policy::PrefixRule {
+                        start: rule.start,
+                        end: rule.end,
+                        skip: rule.skip.iter().copied().collect(),
+                    },
+                )
+            }),
+            self.modules
+                .iter()
+                .map(|(path, offset)| (path.clone(), *offset)),
+        )
+        .context("Invalid [workspace.refdes] policy")
+    }
+}
+
+/// Component value checks (`[workspace.values]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValuesConfig {
+    /// Preferred number series that resistor, capacitor, and inductor values
+    /// must belong to; a value in any of them passes.
+    /// Example: ["E24", "E96"]
+    #[serde(rename = "e-series", default, skip_serializing_if = "Vec::is_empty")]
+    pub e_series: Vec<String>,
+}
+
+impl ValuesConfig {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Checks for `ModuleConverter` to apply to component values.
+    pub fn policy(&self) -> Result<pcb_sch::values::ValuePolicy> {
+        pcb_sch::values::ValuePolicy::new(self.e_series.iter().map(String::as_str))
+            .context("Invalid [workspace.values] e-series")
+    }
+}
+
+/// Net naming policy (`[workspace.net-names]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetNamesConfig {
+    /// Names no net may use.
+    /// Example: ["GND"] to require AGND or DGND instead
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reserved: Vec<String>,
+
+    /// Names allowed for each net type; nets of a listed type must use one.
+    /// Example: { Ground = ["AGND", "DGND"] }
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub allowed: BTreeMap<String, Vec<String>>,
+}
+
+impl NetNamesConfig {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Evaluation sandbox (`[workspace.sandbox]`).
+///
+/// When enabled, `load()` and file references may only reach the workspace,
+/// the package caches, and the stdlib, and remote dependencies must come from
+/// an allowed host. Individual packages can be granted more.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SandboxConfig {
+    /// Enforce the sandbox during evaluation.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub enabled: bool,
+
+    /// Package URL prefixes remote dependencies may come from. Empty allows any.
+    /// Example: ["github.com/diodeinc", "gitlab.com/acme"]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hosts: Vec<String>,
+
+    /// Capabilities granted to packages, keyed by package URL prefix.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub packages: BTreeMap<String, SandboxCapabilities>,
+}
+
+/// What a package may do beyond the sandbox defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SandboxCapabilities {
+    /// Load files outside the workspace, package caches, and stdlib.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub filesystem: bool,
+
+    /// Depend on packages from hosts outside `hosts`.
+    #[serde(
+        default,
+        rename = "any-host",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub any_host: bool,
+}
+
+impl SandboxConfig {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether remote packages under `url` may be used.
+    pub fn allows_host(&self, url: &str) -> bool {
+        self.hosts.is_empty()
+            || self
+                .hosts
+                .iter()
+                .any(|host| package_url_covers(host.trim_end_matches('/'), url))
+    }
+
+    /// Capabilities of the package at `package_url`, from the longest matching
+    /// `[workspace.sandbox.packages]` key.
+    pub fn capabilities(&self, package_url: &str) -> SandboxCapabilities {
+        self.packages
+            .iter()
+            .filter(|(prefix, _)| package_url_covers(prefix.trim_end_matches('/'), package_url))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, capabilities)| *capabilities)
+            .unwrap_or_default()
+    }
+
+    /// Reject a remote dependency of the package `requirer` from a host
+    /// outside `hosts`, unless `requirer` was granted `any-host`. Checked
+    /// while resolving dependencies, before anything is fetched.
+    pub fn check_dependency(&self, requirer: &str, dep_url: &str) -> Result<()> {
+        if !self.enabled || self.allows_host(dep_url) || self.capabilities(requirer).any_host {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Sandbox violation: {requirer} depends on '{dep_url}', which is not under an allowed host\n  \
+            Add its host to [workspace.sandbox] hosts, or grant `any-host = true` to {requirer} under [workspace.sandbox.packages]"
+        )
+    }
+}
+
+/// Parse a byte size such as `"1024"`, `"500KB"`, or `"5 MiB"`.
+///
+/// Decimal units (KB, MB, GB) are powers of 1000; binary units (KiB, MiB,
+/// GiB) are powers of 1024.
+pub fn parse_byte_size(s: &str) -> Result<u64> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number
+        .parse()
+        .with_context(|| format!("expected a number, got '{number}'"))?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        other => anyhow::bail!("unknown size unit '{other}'"),
+    };
+    Ok((number * multiplier as f64).round() as u64)
+}
+
 /// Access control configuration.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AccessConfig {
@@ -331,6 +654,95 @@ pub struct Board {
     /// Optional description of the board
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub description: String,
+
+    /// Design limits checked by `pcb build` (`[board.budget]`).
+    #[serde(default, skip_serializing_if = "BudgetConfig::is_default")]
+    pub budget: BudgetConfig,
+
+    /// Revision history, oldest first (`[[board.revisions]]`). The last entry
+    /// is the board's current revision.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revisions: Vec<pcb_sch::revision::Revision>,
+    /// Build flags modules read as `target.<name>` (`[board.target]`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub target: BTreeMap<String, String>,
+}
+
+/// Design budgets (`[board.budget]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BudgetConfig {
+    /// Most distinct populated BOM lines.
+    #[serde(rename = "max-unique-parts", skip_serializing_if = "Option::is_none")]
+    pub max_unique_parts: Option<usize>,
+
+    /// Most a single board's parts may cost, in USD, e.g. "$12.50".
+    #[serde(rename = "max-bom-cost", skip_serializing_if = "Option::is_none")]
+    pub max_bom_cost: Option<String>,
+
+    /// Number of boards the BOM cost is priced at (default 1).
+    #[serde(rename = "bom-quantity", skip_serializing_if = "Option::is_none")]
+    pub bom_quantity: Option<u32>,
+
+    /// Largest board outline area, e.g. "2500mm2" or "25cm2".
+    #[serde(rename = "max-area", skip_serializing_if = "Option::is_none")]
+    pub max_area: Option<String>,
+}
+
+impl BudgetConfig {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// `max-bom-cost` in USD.
+    pub fn max_bom_cost_usd(&self) -> Result<Option<f64>> {
+        self.max_bom_cost
+            .as_deref()
+            .map(|cost| {
+                cost.trim()
+                    .trim_start_matches('$')
+                    .trim()
+                    .parse::<f64>()
+                    .with_context(|| format!("Invalid [board.budget] max-bom-cost '{cost}'"))
+            })
+            .transpose()
+    }
+
+    /// `max-area` in mm².
+    pub fn max_area_mm2(&self) -> Result<Option<f64>> {
+        self.max_area
+            .as_deref()
+            .map(|area| {
+                parse_area_mm2(area)
+                    .with_context(|| format!("Invalid [board.budget] max-area '{area}'"))
+            })
+            .transpose()
+    }
+
+    /// `bom-quantity`, defaulting to a single board.
+    pub fn bom_quantity(&self) -> u32 {
+        self.bom_quantity.unwrap_or(1).max(1)
+    }
+}
+
+/// Parse an area such as "2500mm2", "25 cm²", or "4in2" into mm². A bare
+/// number is taken as mm².
+pub fn parse_area_mm2(s: &str) -> Result<f64> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number
+        .parse()
+        .with_context(|| format!("expected a number, got '{number}'"))?;
+    let multiplier = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "mm2" | "mm²" | "mm^2" => 1.0,
+        "cm2" | "cm²" | "cm^2" => 100.0,
+        "in2" | "in²" | "in^2" => 645.16,
+        other => anyhow::bail!("unknown area unit '{other}'"),
+    };
+    Ok(number * multiplier)
 }
 
 /// Board configuration (used for compatibility with external crates expecting BoardConfig name)
@@ -415,6 +827,32 @@ pub struct ManifestPart {
     pub datasheet: Option<String>,
 }
 
+/// Where a vendored asset came from.
+///
+/// ```toml
+/// [[provenance]]
+/// path = "docs/TPS54331.pdf"
+/// url = "https://www.ti.com/lit/ds/symlink/tps54331.pdf"
+/// sha256 = "9f2c..."
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetProvenance {
+    /// Path of the asset relative to the package directory.
+    pub path: String,
+    /// URL the asset was downloaded from.
+    pub url: String,
+    /// Hex-encoded SHA256 of the downloaded bytes.
+    pub sha256: String,
+    /// The asset was embedded into another file (e.g. a STEP model inside a
+    /// footprint) and no longer exists at `path`.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub embedded: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
 /// Extract inline pcb.toml manifest from .zen file content
 ///
 /// Looks for a comment block in the leading comments like:
@@ -550,6 +988,128 @@ description = "A test board"
         assert_eq!(board.description, "A test board");
     }
 
+    #[test]
+    fn test_parse_board_budget() {
+        let content = r#"
+[board]
+name = "TestBoard"
+
+[board.budget]
+max-unique-parts = 40
+max-bom-cost = "$12.50"
+bom-quantity = 100
+max-area = "25cm2"
+"#;
+
+        let budget = PcbToml::parse(content).unwrap().board.unwrap().budget;
+        assert_eq!(budget.max_unique_parts, Some(40));
+        assert_eq!(budget.max_bom_cost_usd().unwrap(), Some(12.5));
+        assert_eq!(budget.bom_quantity(), 100);
+        assert_eq!(budget.max_area_mm2().unwrap(), Some(2500.0));
+
+        assert_eq!(parse_area_mm2("300").unwrap(), 300.0);
+        assert_eq!(parse_area_mm2("1 in²").unwrap(), 645.16);
+        assert!(parse_area_mm2("3 acres").is_err());
+        assert!(PcbToml::parse("[board]\nname = \"B\"\n[board.budget]\nmax-cost = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_board_revisions() {
+        let content = r#"
+[board]
+name = "TestBoard"
+
+[[board.revisions]]
+revision = "A"
+date = "2026-01-12"
+description = "Initial release"
+
+[[board.revisions]]
+revision = "B"
+eco = "ECO-0042"
+description = "Swap U3 for the automotive grade part"
+"#;
+
+        let revisions = PcbToml::parse(content).unwrap().board.unwrap().revisions;
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].date.as_deref(), Some("2026-01-12"));
+        assert_eq!(revisions[1].label(), "Rev B (ECO-0042)");
+        assert!(
+            PcbToml::parse("[board]\nname = \"B\"\n[[board.revisions]]\nrev = \"A\"\n").is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_sandbox() {
+        let config = PcbToml::parse(
+            r#"
+[workspace.sandbox]
+enabled = true
+hosts = ["github.com/diodeinc"]
+
+[workspace.sandbox.packages."github.com/acme/tools"]
+filesystem = true
+"#,
+        )
+        .unwrap();
+
+        let sandbox = config.workspace.unwrap().sandbox;
+        assert!(sandbox.enabled);
+        assert!(sandbox.allows_host("github.com/diodeinc/stdlib"));
+        assert!(!sandbox.allows_host("github.com/diodeincorporated/x"));
+        assert!(sandbox.capabilities("github.com/acme/tools/sub").filesystem);
+        assert!(!sandbox.capabilities("github.com/acme/toolsx").filesystem);
+
+        sandbox
+            .check_dependency("github.com/acme/board", "github.com/diodeinc/registry")
+            .unwrap();
+        let err = sandbox
+            .check_dependency("github.com/acme/board", "gitlab.com/other/parts")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with(
+                "Sandbox violation: github.com/acme/board depends on 'gitlab.com/other/parts'"
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_parse_refdes_policy() {
+        let config = PcbToml::parse(
+            r#"
+[workspace.refdes.prefixes]
+J = { start = 100, end = 199 }
+TP = { start = 500, skip = [666] }
+
+[workspace.refdes.modules]
+power = 1000
+"#,
+        )
+        .unwrap();
+
+        let refdes = config.workspace.unwrap().refdes;
+        assert_eq!(refdes.prefixes["J"].end, Some(199));
+        assert_eq!(refdes.prefixes["TP"].skip, vec![666]);
+        assert_eq!(refdes.modules["power"], 1000);
+        assert!(refdes.policy().is_ok());
+
+        let inverted = RefdesConfig {
+            prefixes: [(
+                "J".to_string(),
+                RefdesPrefixConfig {
+                    start: Some(200),
+                    end: Some(100),
+                    skip: Vec::new(),
+                },
+            )]
+            .into(),
+            ..Default::default()
+        };
+        assert!(inverted.policy().is_err());
+    }
+
     #[test]
     fn test_parse_rejects_legacy_module_section() {
         let err = PcbToml::parse(
@@ -799,6 +1359,27 @@ strict = true
         assert!(workspace.bom.strict);
     }
 
+    #[test]
+    fn test_parse_workspace_publish_config() {
+        let content = r#"
+[workspace]
+pcb-version = "0.4"
+
+[workspace.publish]
+max-package-size = "5MiB"
+"#;
+
+        let config = PcbToml::parse(content).unwrap();
+        let publish = config.workspace.unwrap().publish;
+        assert_eq!(publish.max_package_size_bytes().unwrap(), Some(5 << 20));
+
+        assert_eq!(parse_byte_size("1024").unwrap(), 1024);
+        assert_eq!(parse_byte_size("1.5 KB").unwrap(), 1500);
+        assert_eq!(parse_byte_size("2gib").unwrap(), 2 << 30);
+        assert!(parse_byte_size("5 parsecs").is_err());
+        assert!(parse_byte_size("MiB").is_err());
+    }
+
     #[test]
     fn test_parse_v2_patch_branch() {
         let content = r#"
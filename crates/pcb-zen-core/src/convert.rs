@@ -4,6 +4,7 @@ use crate::lang::io_direction::IoDirection;
 use crate::lang::module::{ModulePath, find_moved_span};
 use crate::lang::net::net_kind_requires_name;
 use crate::lang::part::PartValue;
+use crate::lang::pin_erc::signal_pin_type_candidates;
 use crate::lang::symbol::SymbolValue;
 use crate::lang::type_info::TypeInfo;
 use crate::moved::{
@@ -16,7 +17,11 @@ use crate::{
 use itertools::Itertools;
 use pcb_sch::physical::PhysicalValue;
 use pcb_sch::position::{MirrorAxis, Position};
-use pcb_sch::{AttributeValue, Instance, InstanceKind, InstanceRef, ModuleRef, Net, Schematic};
+use pcb_sch::refdes_policy::{RefdesConflict, RefdesPolicy};
+use pcb_sch::values::ValuePolicy;
+use pcb_sch::{
+    ATTR_PIN_TYPES, AttributeValue, Instance, InstanceKind, InstanceRef, ModuleRef, Net, Schematic,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map as JsonMap, Number as JsonNumber, Value as JsonValue};
 use starlark::values::list::ListRef;
@@ -45,7 +50,7 @@ fn net_info_requires_name(kind: Option<&str>) -> bool {
 /// Convert a [`FrozenModuleValue`] to a [`Schematic`].
 pub(crate) struct ModuleConverter {
     schematic: Schematic,
-    net_to_info: HashMap<NetId, NetInfo>,
+    net_to_info: BTreeMap<NetId, NetInfo>,
     // Mapping <ref to component instance> -> <spice model>
     comp_models: Vec<(InstanceRef, FrozenSpiceModelValue)>,
     // Mapping <module instance ref> -> <module value> for position processing
@@ -54,6 +59,8 @@ pub(crate) struct ModuleConverter {
     // the child's scoped name maps to the parent's canonical name.
     // Format: scoped_child_name -> canonical_name
     net_name_aliases: HashMap<String, String>,
+    refdes_policy: RefdesPolicy,
+    value_policy: ValuePolicy,
 }
 
 /// Module signature information to be serialized as JSON
@@ -80,6 +87,10 @@ struct ParameterInfo {
     allowed_values: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     allowed_display: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_value: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_value: Option<serde_json::Value>,
 }
 
 fn serialize_signature_value(value: FrozenValue) -> Option<JsonValue> {
@@ -215,13 +226,27 @@ impl ModuleConverter {
     pub(crate) fn new() -> Self {
         Self {
             schematic: Schematic::new(),
-            net_to_info: HashMap::new(),
+            net_to_info: BTreeMap::new(),
             comp_models: Vec::new(),
             module_instances: Vec::new(),
             net_name_aliases: HashMap::new(),
+            refdes_policy: RefdesPolicy::default(),
+            value_policy: ValuePolicy::default(),
         }
     }
 
+    /// Number new reference designators according to `policy`.
+    pub(crate) fn with_refdes_policy(mut self, policy: RefdesPolicy) -> Self {
+        self.refdes_policy = policy;
+        self
+    }
+
+    /// Check component values according to `policy`.
+    pub(crate) fn with_value_policy(mut self, policy: ValuePolicy) -> Self {
+        self.value_policy = policy;
+        self
+    }
+
     fn net_info_mut(&mut self, id: NetId) -> &mut NetInfo {
         self.net_to_info.entry(id).or_default()
     }
@@ -339,15 +364,20 @@ impl ModuleConverter {
             comp_inst.add_attribute(crate::attrs::MODEL_ARGS, AttributeValue::String(arg_str));
         }
 
-        self.schematic.assign_reference_designators();
+        let refdes = self
+            .schematic
+            .assign_reference_designators_with_policy(&self.refdes_policy);
 
         // Validate moved directives, collect warnings, and filter out problematic ones
         let (mut diagnostics, mut filtered_moved_paths) =
             self.validate_and_filter_moved_directives();
+        self.diagnose_refdes_conflicts(&refdes.conflicts, &mut diagnostics);
 
         // These diagnostics are purely schematic/netlist semantics (not layout-specific),
         // so emit them during schematic conversion rather than in layout sync.
         self.diagnose_missing_bom_part_components(&mut diagnostics);
+        self.diagnose_component_lifecycle(&mut diagnostics);
+        self.diagnose_component_values(&mut diagnostics);
         self.diagnose_unused_module_io(&module_tree, &mut diagnostics);
         self.diagnose_not_connected_multi_port(root_module.source_path(), &mut diagnostics);
 
@@ -465,6 +495,98 @@ impl ModuleConverter {
         }
     }
 
+    /// Warn about reference designators that break the numbering policy.
+    fn diagnose_refdes_conflicts(
+        &self,
+        conflicts: &[RefdesConflict],
+        diagnostics: &mut Diagnostics,
+    ) {
+        for conflict in conflicts {
+            let Some(instance) = self.schematic.instances.get(&conflict.instance) else {
+                continue;
+            };
+            let body = format!(
+                "Reference designator {} on '{}' is {}.",
+                conflict.refdes,
+                conflict.instance.instance_path.join("."),
+                conflict.message
+            );
+            diagnostics.push(Diagnostic::categorized(
+                &instance.type_ref.source_path.to_string_lossy(),
+                &body,
+                "refdes.policy",
+                EvalSeverity::Warning,
+            ));
+        }
+    }
+
+    /// Warn about components whose package marks them NRND, deprecated, or EOL.
+    ///
+    /// Severity can be raised or lowered per status through `[lints]` in `pcb.toml`,
+    /// e.g. `"component.lifecycle.eol" = "deny"`.
+    fn diagnose_component_lifecycle(&self, diagnostics: &mut Diagnostics) {
+        let mut flagged: Vec<_> = self
+            .schematic
+            .instances
+            .values()
+            .filter(|instance| instance.kind == InstanceKind::Component && !instance.dnp())
+            .filter_map(|instance| {
+                let lifecycle = instance.lifecycle().filter(|l| l.is_flagged())?;
+                Some((instance, lifecycle))
+            })
+            .collect();
+        flagged.sort_by(|(a, _), (b, _)| a.reference_designator.cmp(&b.reference_designator));
+
+        for (instance, lifecycle) in flagged {
+            let name = instance
+                .reference_designator
+                .as_deref()
+                .unwrap_or(instance.type_ref.module_name.as_ref());
+            let part = instance
+                .mpn()
+                .map(|mpn| format!(" ({mpn})"))
+                .unwrap_or_default();
+            let body = format!(
+                "Component '{name}'{part} is marked {}: {}.",
+                lifecycle.as_str().to_ascii_uppercase(),
+                lifecycle.description()
+            );
+            diagnostics.push(Diagnostic::categorized(
+                &instance.type_ref.source_path.to_string_lossy(),
+                &body,
+                &format!("component.lifecycle.{lifecycle}"),
+                EvalSeverity::Warning,
+            ));
+        }
+    }
+
+    /// Warn about passive values that do not parse, fall outside the
+    /// `[workspace.values]` E-series, or disagree with the part's MPN.
+    fn diagnose_component_values(&self, diagnostics: &mut Diagnostics) {
+        let mut instances: Vec<_> = self
+            .schematic
+            .instances
+            .values()
+            .filter(|instance| instance.kind == InstanceKind::Component && !instance.dnp())
+            .collect();
+        instances.sort_by(|a, b| a.reference_designator.cmp(&b.reference_designator));
+
+        for instance in instances {
+            let name = instance
+                .reference_designator
+                .as_deref()
+                .unwrap_or(instance.type_ref.module_name.as_ref());
+            for issue in self.value_policy.check(instance) {
+                diagnostics.push(Diagnostic::categorized(
+                    &instance.type_ref.source_path.to_string_lossy(),
+                    &format!("Component '{name}': {issue}."),
+                    issue.kind(),
+                    EvalSeverity::Warning,
+                ));
+            }
+        }
+    }
+
     fn is_house_bom_match_eligible(instance: &Instance) -> bool {
         match instance.component_type().as_deref() {
             Some(
@@ -598,6 +720,8 @@ impl ModuleConverter {
                 default_value: param.default_value.and_then(serialize_signature_value),
                 allowed_values: serialize_signature_values(param.allowed_values.as_ref()),
                 allowed_display: param.allowed_display(),
+                min_value: param.min_value.and_then(serialize_signature_value),
+                max_value: param.max_value.and_then(serialize_signature_value),
             });
         }
 
@@ -852,6 +976,16 @@ impl ModuleConverter {
                     ),
                 );
 
+                let pin_types = signal_pin_type_candidates(symbol_value, signal_name);
+                if !pin_types.is_empty() {
+                    pin_inst.add_attribute(
+                        ATTR_PIN_TYPES,
+                        AttributeValue::Array(
+                            pin_types.into_iter().map(AttributeValue::String).collect(),
+                        ),
+                    );
+                }
+
                 self.schematic.add_instance(pin_inst_ref.clone(), pin_inst);
                 comp_inst.add_child(signal_name.clone(), pin_inst_ref.clone());
 
@@ -1165,7 +1299,7 @@ fn collect_net_ids_into(value: Value, net_ids: &mut HashSet<NetId>) {
 
 /// Propagate impedance from DiffPair interfaces to P/N nets
 fn propagate_diffpair_impedance(
-    net_info: &mut HashMap<NetId, NetInfo>,
+    net_info: &mut BTreeMap<NetId, NetInfo>,
     tree: &BTreeMap<ModulePath, FrozenModuleValue>,
 ) {
     for module in tree.values() {
@@ -1178,7 +1312,7 @@ fn propagate_diffpair_impedance(
 }
 
 /// Propagate impedance from DiffPair interfaces to their P/N nets
-fn propagate_from_value(value: Value, net_info: &mut HashMap<NetId, NetInfo>) {
+fn propagate_from_value(value: Value, net_info: &mut BTreeMap<NetId, NetInfo>) {
     let Some(interface) = value.downcast_ref::<FrozenInterfaceValue>() else {
         return;
     };
@@ -1,15 +1,12 @@
 use std::collections::{BTreeSet, HashMap};
 
-use pcb_sch::Schematic;
+use pcb_sch::{ATTR_PIN_TYPES, Schematic};
 use starlark::codemap::ResolvedSpan;
 use starlark::errors::EvalSeverity;
 use starlark::values::ValueLike;
 
-use crate::lang::pin_erc::{
-    pin_no_connect_body, pin_types_are_only_no_connect, signal_pin_type_candidates,
-};
-use crate::lang::symbol::SymbolValue;
-use crate::{Diagnostic, Diagnostics, EvalOutput, FrozenNetValue, ModulePath};
+use crate::lang::pin_erc::{pin_no_connect_body, pin_types_are_only_no_connect};
+use crate::{Diagnostic, Diagnostics, EvalOutput, FrozenNetValue};
 
 #[derive(Clone)]
 struct NetMetadata {
@@ -18,12 +15,6 @@ struct NetMetadata {
     span: Option<ResolvedSpan>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-struct ComponentSignalKey {
-    component_path: String,
-    signal_name: String,
-}
-
 #[derive(Clone)]
 struct NetPinAttachment {
     component_name: String,
@@ -38,8 +29,16 @@ struct ErcNet<'a> {
     pin_attachments: Vec<NetPinAttachment>,
 }
 
+/// A net tie component and the nets attached to each of its shorted pad groups.
+struct ErcNetTie {
+    component_name: String,
+    source_path: String,
+    groups: Vec<BTreeSet<String>>,
+}
+
 struct SchematicErcContext<'a> {
     nets: Vec<ErcNet<'a>>,
+    net_ties: Vec<ErcNetTie>,
 }
 
 trait SchematicErcPass {
@@ -48,47 +47,12 @@ trait SchematicErcPass {
 
 struct PinNoConnectPass;
 
-fn component_path(module_path: &ModulePath, component_name: &str) -> String {
-    if module_path.is_root() {
-        component_name.to_string()
-    } else {
-        format!("{module_path}.{component_name}")
-    }
-}
-
-fn signal_names(symbol: &SymbolValue) -> BTreeSet<&str> {
-    symbol
-        .pad_to_signal
-        .values()
-        .map(|value| value.as_str())
-        .collect()
-}
-
 impl<'a> SchematicErcContext<'a> {
     fn build(eval_output: &EvalOutput, schematic: &'a Schematic) -> Self {
-        let mut pin_types_by_component_signal: HashMap<ComponentSignalKey, Vec<String>> =
-            HashMap::new();
         let mut net_metadata: HashMap<u64, NetMetadata> = HashMap::new();
 
-        for (module_path, module) in eval_output.module_tree() {
+        for module in eval_output.module_tree().into_values() {
             for component in module.components() {
-                let component_path = component_path(&module_path, component.name());
-
-                if let Some(symbol) = component.symbol().downcast_ref::<SymbolValue>() {
-                    for signal_name in signal_names(symbol) {
-                        let candidates = signal_pin_type_candidates(symbol, signal_name);
-                        if !candidates.is_empty() {
-                            pin_types_by_component_signal.insert(
-                                ComponentSignalKey {
-                                    component_path: component_path.clone(),
-                                    signal_name: signal_name.to_string(),
-                                },
-                                candidates,
-                            );
-                        }
-                    }
-                }
-
                 for net_value in component.connections().values() {
                     if let Some(net) = net_value.downcast_ref::<FrozenNetValue>() {
                         net_metadata.entry(net.id()).or_insert_with(|| NetMetadata {
@@ -112,13 +76,14 @@ impl<'a> SchematicErcContext<'a> {
                     continue;
                 };
 
-                let component_path = component_ref.instance_path.join(".");
-                let Some(pin_types) = pin_types_by_component_signal.get(&ComponentSignalKey {
-                    component_path: component_path.clone(),
-                    signal_name: signal_name.to_string(),
-                }) else {
+                let pin_types = schematic
+                    .instances
+                    .get(port_ref)
+                    .map(|port| port.string_list_attr(&[ATTR_PIN_TYPES]))
+                    .unwrap_or_default();
+                if pin_types.is_empty() {
                     continue;
-                };
+                }
 
                 let component_name = component_ref
                     .instance_path
@@ -130,7 +95,7 @@ impl<'a> SchematicErcContext<'a> {
                 pin_attachments.push(NetPinAttachment {
                     component_name,
                     signal_name: signal_name.to_string(),
-                    pin_types: pin_types.clone(),
+                    pin_types,
                 });
             }
 
@@ -141,7 +106,60 @@ impl<'a> SchematicErcContext<'a> {
             });
         }
 
-        Self { nets }
+        let net_ties = Self::build_net_ties(schematic);
+        Self { nets, net_ties }
+    }
+
+    fn build_net_ties(schematic: &Schematic) -> Vec<ErcNetTie> {
+        let mut net_by_pad: HashMap<(pcb_sch::InstanceRef, String), &str> = HashMap::new();
+        for (net_name, net) in &schematic.nets {
+            for port_ref in &net.ports {
+                let Some(component_ref) = schematic.component_ref_for_port(port_ref) else {
+                    continue;
+                };
+                let Some(pcb_sch::AttributeValue::Array(pads)) = schematic
+                    .instances
+                    .get(port_ref)
+                    .and_then(|port| port.attributes.get(crate::attrs::PADS))
+                else {
+                    continue;
+                };
+                for pad in pads.iter().filter_map(pcb_sch::AttributeValue::string) {
+                    net_by_pad.insert((component_ref.clone(), pad.to_string()), net_name.as_str());
+                }
+            }
+        }
+
+        let mut net_ties: Vec<ErcNetTie> = schematic
+            .instances
+            .iter()
+            .filter(|(_, instance)| instance.kind == pcb_sch::InstanceKind::Component)
+            .filter_map(|(instance_ref, instance)| {
+                let pad_groups = instance.net_tie_pad_groups();
+                if pad_groups.is_empty() {
+                    return None;
+                }
+                let groups = pad_groups
+                    .iter()
+                    .map(|pads| {
+                        pads.iter()
+                            .filter_map(|pad| net_by_pad.get(&(instance_ref.clone(), pad.clone())))
+                            .map(|net| net.to_string())
+                            .collect()
+                    })
+                    .collect();
+                Some(ErcNetTie {
+                    component_name: instance
+                        .reference_designator
+                        .clone()
+                        .unwrap_or_else(|| instance_ref.instance_path.join(".")),
+                    source_path: instance.type_ref.source_path.to_string_lossy().into_owned(),
+                    groups,
+                })
+            })
+            .collect();
+        net_ties.sort_by(|a, b| a.component_name.cmp(&b.component_name));
+        net_ties
     }
 }
 
@@ -184,10 +202,37 @@ impl SchematicErcPass for PinNoConnectPass {
     }
 }
 
+/// Net ties intentionally short distinct nets. A tie whose pads all land on the
+/// same net has no effect and usually means the nets were merged by name.
+struct NetTiePass;
+
+impl SchematicErcPass for NetTiePass {
+    fn run(&self, ctx: &SchematicErcContext<'_>, diagnostics: &mut Diagnostics) {
+        for tie in &ctx.net_ties {
+            for nets in &tie.groups {
+                if nets.len() != 1 {
+                    continue;
+                }
+                let net = nets.iter().next().map(String::as_str).unwrap_or_default();
+                let body = format!(
+                    "Net tie '{}' connects net '{net}' to itself; a net tie should join two different nets",
+                    tie.component_name
+                );
+                diagnostics.diagnostics.push(Diagnostic::categorized(
+                    &tie.source_path,
+                    &body,
+                    "net_tie.same_net",
+                    EvalSeverity::Warning,
+                ));
+            }
+        }
+    }
+}
+
 pub fn run_schematic_erc(eval_output: &EvalOutput, schematic: &Schematic) -> Diagnostics {
     let ctx = SchematicErcContext::build(eval_output, schematic);
     let mut diagnostics = Diagnostics::default();
-    let passes: [&dyn SchematicErcPass; 1] = [&PinNoConnectPass];
+    let passes: [&dyn SchematicErcPass; 2] = [&PinNoConnectPass, &NetTiePass];
 
     for pass in passes {
         pass.run(&ctx, &mut diagnostics);
@@ -286,4 +286,20 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_metadata_tracks_contents() {
+        let mut provider = InMemoryFileProvider::empty();
+        provider.add_file("/a.zen", "x = 1".to_string());
+        let before = provider.metadata(Path::new("/a.zen")).unwrap();
+        assert_eq!(before.len, 5);
+        assert_eq!(before, provider.metadata(Path::new("/a.zen")).unwrap());
+
+        provider.add_file("/a.zen", "x = 2".to_string());
+        assert_ne!(before, provider.metadata(Path::new("/a.zen")).unwrap());
+        assert!(matches!(
+            provider.metadata(Path::new("/missing.zen")),
+            Err(FileProviderError::NotFound(_))
+        ));
+    }
 }
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use starlark::collections::SmallMap;
 use starlark::environment::GlobalsBuilder;
 use starlark::errors::EvalSeverity;
 use starlark::eval::Evaluator;
@@ -7,9 +8,12 @@ use starlark::starlark_module;
 use starlark::values::Value;
 
 use crate::Diagnostic;
-use crate::lang::error::CategorizedDiagnostic;
+use crate::lang::error::{CategorizedDiagnostic, CheckFailure};
 use crate::lang::evaluator_ext::EvaluatorExt;
 
+/// Diagnostic kind of failed `check()` calls.
+const CHECK_KIND: &str = "assert.check";
+
 /// Helper to create source_error from kind string
 fn make_source_error(msg: &str, kind: Option<String>) -> Option<Arc<anyhow::Error>> {
     kind.and_then(|k| {
@@ -25,9 +29,9 @@ fn make_diagnostic(
     msg: String,
     severity: EvalSeverity,
     suppressed: bool,
-    kind: Option<String>,
+    source_error: Option<Arc<anyhow::Error>>,
 ) -> Diagnostic {
-    // For warn() and error(), convert the call stack into a diagnostic chain.
+    // For warn(), error(), and check(), convert the call stack into a diagnostic chain.
     // This shows the full trace of where the warning originated, not just the
     // warn() call itself.
     let call_stack = eval.call_stack();
@@ -67,7 +71,7 @@ fn make_diagnostic(
             body: msg.clone(),
             call_stack: None,
             child: None,
-            source_error: make_source_error(&msg, kind),
+            source_error,
             related: Vec::new(),
             suppressed,
         };
@@ -79,7 +83,6 @@ fn make_diagnostic(
     // We iterate in reverse so we build from innermost outward.
     let mut current: Option<Diagnostic> = None;
     let innermost_idx = frames_to_use.len() - 1;
-    let source_error = make_source_error(&msg, kind);
 
     for (i, (frame_name, loc)) in frames_to_use.iter().enumerate().rev() {
         let is_innermost = i == innermost_idx;
@@ -121,7 +124,14 @@ pub(crate) fn assert_globals(builder: &mut GlobalsBuilder) {
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> anyhow::Result<Value<'v>> {
         if suppress.unwrap_or(false) {
-            eval.add_diagnostic(make_diagnostic(eval, msg, EvalSeverity::Error, true, kind));
+            let source_error = make_source_error(&msg, kind);
+            eval.add_diagnostic(make_diagnostic(
+                eval,
+                msg,
+                EvalSeverity::Error,
+                true,
+                source_error,
+            ));
             Ok(Value::new_none())
         } else {
             Err(anyhow::anyhow!(msg))
@@ -135,25 +145,48 @@ pub(crate) fn assert_globals(builder: &mut GlobalsBuilder) {
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> anyhow::Result<Value<'v>> {
         let suppressed = suppress.unwrap_or(false);
+        let source_error = make_source_error(&msg, kind);
         eval.add_diagnostic(make_diagnostic(
             eval,
             msg,
             EvalSeverity::Warning,
             suppressed,
-            kind,
+            source_error,
         ));
         Ok(Value::new_none())
     }
 
-    /// Check that a condition holds. If `cond` is false, raise an error with `msg`.
+    /// Check that a condition holds. If `cond` is false, fail with a diagnostic
+    /// carrying `msg`, the current instance path, and the `context` values.
     fn check<'v>(
         #[starlark(require = pos)] cond: bool,
         #[starlark(require = pos)] msg: String,
-    ) -> anyhow::Result<Value<'v>> {
+        #[starlark(kwargs)] context: SmallMap<String, Value<'v>>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> starlark::Result<Value<'v>> {
+        if let Some(mut module) = eval.module_value_mut() {
+            module.add_check(msg.clone());
+        }
         if cond {
-            Ok(Value::new_none())
-        } else {
-            Err(anyhow::anyhow!(msg))
+            return Ok(Value::new_none());
         }
+
+        let instance_path = eval
+            .module_value()
+            .map(|module| module.path().clone())
+            .unwrap_or_default();
+        let failure = CheckFailure {
+            message: msg,
+            instance_path: instance_path.to_string(),
+            context: context
+                .into_iter()
+                .map(|(name, value)| (name, value.to_str()))
+                .collect(),
+        };
+        let body = failure.to_string();
+        let kind = CategorizedDiagnostic::new(body.clone(), CHECK_KIND.to_string())
+            .expect("valid diagnostic kind");
+        let source_error = Arc::new(anyhow::Error::new(kind).context(failure));
+        Err(make_diagnostic(eval, body, EvalSeverity::Error, false, Some(source_error)).into())
     }
 }
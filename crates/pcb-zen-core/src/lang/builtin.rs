@@ -11,7 +11,8 @@ use starlark::{
     eval::{Arguments, Evaluator},
     starlark_module, starlark_simple_value,
     values::{
-        Freeze, StarlarkValue, Value,
+        Freeze, StarlarkValue, Value, ValueLike,
+        float::StarlarkFloat,
         list::UnpackList,
         none::{NoneOr, NoneType},
         starlark_value,
@@ -22,9 +23,13 @@ use starlark::{
 use crate::{
     attrs,
     lang::{
-        evaluator_ext::EvaluatorExt, net::*, param_decl::invoke_builtin_io, part::PartValue,
+        evaluator_ext::EvaluatorExt, file::stable_path_string, net::*,
+        param_decl::invoke_builtin_io, part::PartValue, path::normalize_datasheet,
         stackup::BoardConfig,
     },
+    parametric_footprint::{
+        GENERATED_FOOTPRINTS_DIR, ParametricFootprint, write_generated_footprint,
+    },
 };
 
 #[derive(Clone, Copy, Debug, ProvidesStaticType, Freeze, Allocative, Serialize)]
@@ -100,6 +105,11 @@ fn builtin_methods(methods: &mut MethodsBuilder) {
         Ok(PhysicalValueType::new(PhysicalUnitDims::TEMP))
     }
 
+    #[starlark(attribute)]
+    fn stdlib_version(#[allow(unused_variables)] this: &Builtin) -> starlark::Result<&'static str> {
+        Ok(crate::stdlib::STDLIB_VERSION)
+    }
+
     fn add_board_config<'v>(
         #[allow(unused_variables)] this: &Builtin,
         name: String,
@@ -198,6 +208,7 @@ fn builtin_methods(methods: &mut MethodsBuilder) {
             String,
         >,
         #[starlark(require = named, default = NoneOr::None)] datasheet: NoneOr<String>,
+        eval: &mut Evaluator<'_, '_, '_>,
     ) -> starlark::Result<PartValue> {
         if mpn.trim().is_empty() {
             return Err(Error::new_other(anyhow::anyhow!(
@@ -217,7 +228,7 @@ fn builtin_methods(methods: &mut MethodsBuilder) {
                         "`datasheet` must be a non-empty string when provided"
                     )));
                 }
-                Some(datasheet)
+                normalize_datasheet(&datasheet, eval.eval_context())
             }
         };
         Ok(PartValue::new(
@@ -304,6 +315,37 @@ fn builtin_methods(methods: &mut MethodsBuilder) {
         Ok(NoneType)
     }
 
+    /// Generate a parametric footprint (e.g. `pin_header`) into the workspace's
+    /// `.pcb/footprints` directory and return its path.
+    fn generate_footprint<'v>(
+        #[allow(unused_variables)] this: &Builtin,
+        #[starlark(require = pos)] kind: String,
+        #[starlark(kwargs)] kwargs: SmallMap<String, Value<'v>>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> starlark::Result<Value<'v>> {
+        let mut params = std::collections::BTreeMap::new();
+        for (key, value) in kwargs.iter() {
+            params.insert(key.clone(), footprint_param(key, *value)?);
+        }
+        let footprint = ParametricFootprint::from_params(&kind, &params)
+            .map_err(Error::new_other)?
+            .generate();
+
+        let eval_ctx = eval
+            .eval_context()
+            .ok_or_else(|| Error::new_other(anyhow::anyhow!("No eval context available")))?;
+        let resolution = eval_ctx.resolution();
+        let dir = resolution
+            .workspace_info
+            .root
+            .join(GENERATED_FOOTPRINTS_DIR);
+        let path = write_generated_footprint(eval_ctx.file_provider(), &dir, &footprint)
+            .map_err(Error::new_other)?;
+
+        let stable = stable_path_string(resolution, &path);
+        Ok(eval.heap().alloc_str(&stable).to_value())
+    }
+
     fn current_module_path<'v>(
         #[allow(unused_variables)] this: &Builtin,
         eval: &mut Evaluator<'v, '_, '_>,
@@ -416,3 +458,24 @@ fn builtin_methods(methods: &mut MethodsBuilder) {
         Ok(NoneType)
     }
 }
+
+/// A footprint parameter: a number of millimeters (or a count), or a string
+/// such as `"2.54mm"`.
+fn footprint_param(key: &str, value: Value) -> starlark::Result<f64> {
+    if let Some(int) = value.unpack_i32() {
+        return Ok(f64::from(int));
+    }
+    if let Some(float) = value.downcast_ref::<StarlarkFloat>() {
+        return Ok(float.0);
+    }
+    if let Some(text) = value.unpack_str() {
+        let number = text.trim().strip_suffix("mm").unwrap_or(text).trim();
+        if let Ok(parsed) = number.parse::<f64>() {
+            return Ok(parsed);
+        }
+    }
+    Err(Error::new_other(anyhow::anyhow!(
+        "Footprint parameter '{key}' must be a number or a string like \"2.54mm\", got {}",
+        value.to_repr()
+    )))
+}
@@ -33,7 +33,7 @@ use crate::{
 
 use super::net::{ConnectionIntent, FrozenNetValue, NetValue, generate_net_id};
 use super::part::PartValue;
-use super::path::normalize_path_to_package_uri;
+use super::path::{normalize_datasheet, normalize_path_to_package_uri};
 use super::symbol::{SymbolType, SymbolValue, symbol_pins_from_pad_map};
 use super::validation::validate_identifier_name;
 
@@ -1352,7 +1352,7 @@ impl<'v> StarlarkValue<'v> for ComponentValue<'v> {
                 let datasheet = value.unpack_str().ok_or_else(|| {
                     starlark::Error::new_other(anyhow!("`datasheet` must be a string"))
                 })?;
-                data.component_datasheet = Some(datasheet.to_owned());
+                data.component_datasheet = normalize_datasheet(datasheet, None);
                 data.datasheet = resolve_component_datasheet(
                     data.part.as_ref(),
                     data.component_datasheet.as_deref(),
@@ -2101,17 +2101,13 @@ where
             // Skip empty strings and "~" (KiCad's placeholder for no datasheet) - prefer None over empty
             let component_datasheet = datasheet_val
                 .and_then(|v| v.unpack_str())
-                .and_then(pcb_eda::usable_kicad_field_value)
-                .map(ToOwned::to_owned)
+                .and_then(|v| normalize_datasheet(v, Some(ctx)))
                 .or_else(|| {
                     properties_map
                         .get("datasheet")
                         .and_then(|v| v.unpack_str())
-                        .and_then(pcb_eda::usable_kicad_field_value)
-                        .map(ToOwned::to_owned)
+                        .and_then(|v| normalize_datasheet(v, Some(ctx)))
                 });
-            let component_datasheet = component_datasheet
-                .map(|datasheet| normalize_path_to_package_uri(&datasheet, Some(ctx)));
             let symbol_datasheet = resolve_symbol_datasheet(&final_symbol, ctx)?;
             let final_datasheet = resolve_component_datasheet(
                 final_part.as_ref(),
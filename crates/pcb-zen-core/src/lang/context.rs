@@ -1,6 +1,6 @@
 #![allow(clippy::needless_lifetimes)]
 
-use std::{cell::RefCell, fmt::Display};
+use std::{cell::RefCell, collections::BTreeMap, fmt::Display};
 
 use allocative::Allocative;
 use serde::Serialize;
@@ -28,6 +28,8 @@ pub(crate) struct PendingChild<'v> {
     pub(crate) properties: Option<SmallMap<String, Value<'v>>>,
     pub(crate) component_modifiers: Vec<Value<'v>>,
     pub(crate) provided_names: Vec<String>,
+    #[trace(unsafe_ignore)]
+    pub(crate) forwarded_inputs: BTreeMap<String, String>,
     pub(crate) call_site_path: String,
     pub(crate) call_site_span: ResolvedSpan,
     pub(crate) call_stack: CallStack,
@@ -44,6 +46,7 @@ impl<'v> Freeze for PendingChild<'v> {
             properties: self.properties.map(|m| m.freeze(freezer)).transpose()?,
             component_modifiers: self.component_modifiers.freeze(freezer)?,
             provided_names: self.provided_names,
+            forwarded_inputs: self.forwarded_inputs,
             call_site_path: self.call_site_path,
             call_site_span: self.call_site_span,
             call_stack: self.call_stack,
@@ -59,6 +62,7 @@ pub(crate) struct FrozenPendingChild {
     pub(crate) properties: Option<SmallMap<String, FrozenValue>>,
     pub(crate) component_modifiers: Vec<FrozenValue>,
     pub(crate) provided_names: Vec<String>,
+    pub(crate) forwarded_inputs: BTreeMap<String, String>,
     pub(crate) call_site_path: String,
     pub(crate) call_site_span: ResolvedSpan,
     pub(crate) call_stack: CallStack,
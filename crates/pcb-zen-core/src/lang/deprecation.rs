@@ -0,0 +1,120 @@
+//! `deprecated()` and the `deprecated=` parameter option: let library authors
+//! retire functions, modules, and parameters with a warning wherever they are
+//! still used.
+//!
+//! Module and parameter deprecations are reported at the `Module()` call in
+//! the parent, so they are passed through unchanged instead of being wrapped
+//! as a warning from inside the module (see [`is_call_site_deprecation`]).
+
+use std::path::Path;
+
+use starlark::environment::GlobalsBuilder;
+use starlark::errors::EvalSeverity;
+use starlark::eval::Evaluator;
+use starlark::starlark_module;
+use starlark::values::none::NoneType;
+
+use crate::Diagnostic;
+use crate::lang::error::CategorizedDiagnostic;
+use crate::lang::evaluator_ext::EvaluatorExt;
+
+pub const DEPRECATED_FUNCTION: &str = "deprecated.function";
+pub const DEPRECATED_MODULE: &str = "deprecated.module";
+pub const DEPRECATED_PARAMETER: &str = "deprecated.parameter";
+
+fn deprecation_message(subject: &str, message: &str, replacement: Option<&str>) -> String {
+    match replacement {
+        Some(replacement) => {
+            format!("{subject} is deprecated: {message} (use `{replacement}` instead)")
+        }
+        None => format!("{subject} is deprecated: {message}"),
+    }
+}
+
+/// Whether `diagnostic` is a module or parameter deprecation, which is
+/// already located at the `Module()` call that uses it.
+pub(crate) fn is_call_site_deprecation(diagnostic: &Diagnostic) -> bool {
+    diagnostic
+        .downcast_error_ref::<CategorizedDiagnostic>()
+        .is_some_and(|categorized| {
+            categorized.kind == DEPRECATED_MODULE || categorized.kind == DEPRECATED_PARAMETER
+        })
+}
+
+/// Warn at the parent's `Module()` call that the module being evaluated, or
+/// its parameter `parameter`, is deprecated. Does nothing for the root module.
+fn warn_at_instantiation(
+    eval: &Evaluator<'_, '_, '_>,
+    parameter: Option<&str>,
+    message: &str,
+    replacement: Option<&str>,
+) {
+    let Some(diagnostic) = eval.module_value().and_then(|module| {
+        let site = module.instantiation()?;
+        let module_name = Path::new(module.source_path())
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let (subject, kind) = match parameter {
+            Some(parameter) => (
+                format!("Parameter `{parameter}` of `{module_name}`"),
+                DEPRECATED_PARAMETER,
+            ),
+            None => (format!("`{module_name}`"), DEPRECATED_MODULE),
+        };
+        Some(
+            Diagnostic::categorized(
+                &site.path,
+                &deprecation_message(&subject, message, replacement),
+                kind,
+                EvalSeverity::Warning,
+            )
+            .with_span(Some(site.span)),
+        )
+    }) else {
+        return;
+    };
+    eval.add_diagnostic(diagnostic);
+}
+
+/// Warn that parameter `name` is deprecated. Called only when the parent
+/// passed a value for it.
+pub(crate) fn warn_deprecated_parameter(eval: &Evaluator<'_, '_, '_>, name: &str, message: &str) {
+    warn_at_instantiation(eval, Some(name), message, None);
+}
+
+#[starlark_module]
+pub(crate) fn deprecation_globals(builder: &mut GlobalsBuilder) {
+    /// Mark the enclosing function as deprecated, or the whole module when
+    /// called at the top level of a module file. Each call of the function or
+    /// instantiation of the module then warns at the call site.
+    fn deprecated<'v>(
+        #[starlark(require = pos)] message: String,
+        #[starlark(require = named)] replacement: Option<String>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> starlark::Result<NoneType> {
+        // Frames are outermost-first and the last one is this call, so the one
+        // before it is the deprecated function, located where it was called.
+        let call_stack = eval.call_stack();
+        let Some([function, _]) = call_stack.frames.last_chunk::<2>() else {
+            warn_at_instantiation(eval, None, &message, replacement.as_deref());
+            return Ok(NoneType);
+        };
+        if let Some(location) = &function.location {
+            eval.add_diagnostic(
+                Diagnostic::categorized(
+                    location.file.filename(),
+                    &deprecation_message(
+                        &format!("`{}`", function.name),
+                        &message,
+                        replacement.as_deref(),
+                    ),
+                    DEPRECATED_FUNCTION,
+                    EvalSeverity::Warning,
+                )
+                .with_span(Some(location.resolve_span())),
+            );
+        }
+        Ok(NoneType)
+    }
+}
@@ -37,6 +37,34 @@ pub struct BenchTestResult {
     pub passed: bool,
 }
 
+/// A failed `check()` call, with where it happened and the values passed as
+/// context.
+#[derive(Debug, Error, Clone)]
+pub struct CheckFailure {
+    /// The message passed to `check()`
+    pub message: String,
+
+    /// Dot-separated instance path of the module that called `check()`, empty
+    /// at the root
+    pub instance_path: String,
+
+    /// `name = value` pairs passed as keyword arguments, in call order
+    pub context: Vec<(String, String)>,
+}
+
+impl std::fmt::Display for CheckFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if !self.instance_path.is_empty() {
+            write!(f, "\n  in {}", self.instance_path)?;
+        }
+        for (name, value) in &self.context {
+            write!(f, "\n  {name} = {value}")?;
+        }
+        Ok(())
+    }
+}
+
 /// Structured diagnostic with a categorization kind for filtering and classification.
 ///
 /// This allows downstream tooling to filter, suppress, or categorize diagnostics
@@ -30,26 +30,34 @@ use rayon::prelude::*;
 
 use tracing::{info_span, instrument};
 
+use crate::cancel::CancellationToken;
+use crate::config::{SandboxCapabilities, SandboxConfig};
 use crate::lang::assert::assert_globals;
 use crate::lang::{
     binding,
     builtin::builtin_globals,
     component::component_globals,
+    deprecation::{deprecation_globals, is_call_site_deprecation},
     r#enum::EnumValue,
     style_lint::{ast_style_lints, is_ast_style_diagnostic},
     type_info::{ParameterInfo, TypeInfo},
+    unused_lint::{ast_unused_lints, is_ast_unused_diagnostic},
 };
 use crate::lang::{
     electrical_check::FrozenElectricalCheck,
     evaluator_ext::EvaluatorExt,
     file::file_globals,
     footprint::{FootprintCacheKey, footprint_cache_key, validate_footprints},
-    module::{FrozenModuleValue, ModulePath},
+    module::{FrozenModuleValue, InstantiationSite, ModulePath},
+    profile::Profiler,
+    secret::{SecretInputs, redact_schematic},
+    stdlib_version::stdlib_version_globals,
+    target::{TARGET_GLOBAL, TargetFlags},
 };
 use crate::load_spec::LoadSpec;
 use crate::resolution::{PackageScopeKey, PackageUrlResolution, ResolutionResult};
-use crate::{Diagnostic, Diagnostics, WithDiagnostics};
-use crate::{FileProvider, ResolveContext};
+use crate::{DependencySource, FileMetadata, FileProvider, ResolveContext, ResolveStep};
+use crate::{Diagnostic, DiagnosticReference, Diagnostics, WithDiagnostics};
 use crate::{convert::ModuleConverter, lang::context::FrozenPendingChild};
 
 pub use super::evaluator_ext::EvalContextRef;
@@ -267,6 +275,9 @@ pub(crate) type SpiceCacheKey = (PathBuf, String);
 pub(crate) struct ParsedSource {
     pub(crate) contents: String,
     pub(crate) ast: Arc<AstModule>,
+    /// File metadata when it was read; a cached entry is reused only while
+    /// the file still reports the same metadata.
+    pub(crate) metadata: Option<FileMetadata>,
 }
 
 /// Concurrent map for session-scoped caches of values derived from files.
@@ -328,9 +339,38 @@ impl EvalOutput {
 
     /// Convert to schematic with diagnostics
     pub fn to_schematic_with_diagnostics(&self) -> crate::WithDiagnostics<pcb_sch::Schematic> {
-        let converter = ModuleConverter::new();
+        let workspace_info = &self.config.resolution.workspace_info;
+        let workspace_config = workspace_info.workspace_config();
+        let (refdes_policy, policy_error) = match workspace_config.refdes.policy() {
+            Ok(policy) => (policy, None),
+            Err(err) => (Default::default(), Some(err)),
+        };
+        let (value_policy, value_policy_error) = match workspace_config.values.policy() {
+            Ok(policy) => (policy, None),
+            Err(err) => (Default::default(), Some(err)),
+        };
+        let converter = ModuleConverter::new()
+            .with_refdes_policy(refdes_policy)
+            .with_value_policy(value_policy);
         let module_tree = self.module_tree();
         let mut result = converter.build(module_tree);
+        let pcb_toml = workspace_info.root.join("pcb.toml");
+        if let Some(err) = policy_error {
+            result.diagnostics.push(Diagnostic::categorized(
+                &pcb_toml.to_string_lossy(),
+                &format!("{err:#}"),
+                "refdes.policy",
+                EvalSeverity::Error,
+            ));
+        }
+        if let Some(err) = value_policy_error {
+            result.diagnostics.push(Diagnostic::categorized(
+                &pcb_toml.to_string_lossy(),
+                &format!("{err:#}"),
+                "component.value",
+                EvalSeverity::Error,
+            ));
+        }
         if let Some(ref mut schematic) = result.output {
             schematic.package_roots = self.config.resolution.package_roots();
 
@@ -360,10 +400,18 @@ impl EvalOutput {
                     }
                 }
             }
+
+            let leaks = redact_schematic(schematic, &self.config.secret_inputs);
+            result.diagnostics.diagnostics.extend(leaks);
         }
         result
     }
 
+    /// Secret root inputs this output was evaluated with.
+    pub fn secret_inputs(&self) -> &SecretInputs {
+        &self.config.secret_inputs
+    }
+
     /// Convert to schematic (error if conversion fails)
     pub fn to_schematic(&self) -> anyhow::Result<pcb_sch::Schematic> {
         let result = self.to_schematic_with_diagnostics();
@@ -452,6 +500,22 @@ pub struct EvalSession {
     module_deps: Arc<RwLock<HashMap<PathBuf, HashSet<PathBuf>>>>,
     /// Tree of all frozen child modules indexed by fully qualified path.
     module_tree: Arc<RwLock<BTreeMap<ModulePath, FrozenModule>>>,
+    /// Profiler run on every evaluation, if profiling is enabled.
+    profiler: Option<Arc<Profiler>>,
+    /// Checked before every module evaluation so a cancelled build stops at
+    /// the next module boundary.
+    cancellation: CancellationToken,
+}
+
+/// A `load()` (or `Module()`) in one file of a load chain.
+#[derive(Debug, Clone)]
+pub(crate) struct LoadChainLink {
+    /// File containing the load.
+    pub(crate) path: PathBuf,
+    /// Load path as written, e.g. `"./Ldo.zen"`.
+    pub(crate) load_path: String,
+    /// Span of the load, when the caller knows it.
+    pub(crate) span: Option<ResolvedSpan>,
 }
 
 /// Configuration for creating an EvalContext. Send + Sync safe for passing across threads.
@@ -471,9 +535,9 @@ pub struct EvalContextConfig {
     /// The fully qualified path of the module we are evaluating (e.g., "root", "root.child")
     pub(crate) module_path: ModulePath,
 
-    /// Per-context load chain for cycle detection. Contains canonical paths of all files
-    /// in the current load chain (ancestors). Thread-local to each evaluation path.
-    pub(crate) load_chain: HashSet<PathBuf>,
+    /// Per-context load chain for cycle detection: the `load()` in each ancestor
+    /// file that led to this one, outermost first. Thread-local to each evaluation path.
+    pub(crate) load_chain: Vec<LoadChainLink>,
 
     /// The absolute path to the module we are evaluating.
     pub(crate) source_path: Option<PathBuf>,
@@ -500,6 +564,12 @@ pub struct EvalContextConfig {
     /// Defaults to `true`. Set to `false` for stdlib modules (circular dep avoidance)
     /// and test harnesses that don't need the prelude.
     pub(crate) inject_prelude: bool,
+
+    /// Secret root inputs, redacted from the schematic after conversion.
+    pub(crate) secret_inputs: Arc<SecretInputs>,
+
+    /// Build flags every module reads through the `target` global.
+    pub(crate) target_flags: Arc<BTreeMap<String, String>>,
 }
 
 impl EvalContextConfig {
@@ -526,7 +596,7 @@ impl EvalContextConfig {
             file_provider,
             resolution,
             module_path: ModulePath::root(),
-            load_chain: HashSet::new(),
+            load_chain: Vec::new(),
             source_path: None,
             active_root_package: None,
             contents: None,
@@ -534,6 +604,8 @@ impl EvalContextConfig {
             build_circuit: false,
             eager: true,
             inject_prelude: true,
+            secret_inputs: Arc::default(),
+            target_flags: Arc::default(),
         }
     }
 
@@ -586,11 +658,21 @@ impl EvalContextConfig {
     }
 
     /// Create a child config for loading a module at the given path.
-    /// Adds the current source to the load chain for cycle detection.
-    pub fn child_for_load(&self, child_module_path: ModulePath, target_path: PathBuf) -> Self {
+    /// Adds the load in the current source to the load chain for cycle detection.
+    pub(crate) fn child_for_load(
+        &self,
+        child_module_path: ModulePath,
+        target_path: PathBuf,
+        load_path: &str,
+        load_span: Option<ResolvedSpan>,
+    ) -> Self {
         let mut child_load_chain = self.load_chain.clone();
         if let Some(ref source) = self.source_path {
-            child_load_chain.insert(source.clone());
+            child_load_chain.push(LoadChainLink {
+                path: source.clone(),
+                load_path: load_path.to_string(),
+                span: load_span,
+            });
         }
 
         Self {
@@ -606,13 +688,16 @@ impl EvalContextConfig {
             build_circuit: false,
             eager: self.eager,
             inject_prelude: self.inject_prelude,
+            secret_inputs: self.secret_inputs.clone(),
+            target_flags: self.target_flags.clone(),
         }
         .set_source_path(target_path)
     }
 
     /// Check if loading the given path would create a cycle.
     pub fn would_create_cycle(&self, path: &Path) -> bool {
-        self.load_chain.contains(path)
+        self.source_path.as_deref() == Some(path)
+            || self.load_chain.iter().any(|link| link.path == path)
     }
 
     /// Create a child config for a pending child module instantiation.
@@ -626,7 +711,7 @@ impl EvalContextConfig {
             file_provider: self.file_provider.clone(),
             resolution: self.resolution.clone(),
             module_path: child_module_path,
-            load_chain: HashSet::new(),
+            load_chain: Vec::new(),
             source_path: None,
             active_root_package: self.active_root_package.clone(),
             contents: None,
@@ -634,6 +719,8 @@ impl EvalContextConfig {
             build_circuit: false,
             eager: self.eager,
             inject_prelude: self.inject_prelude,
+            secret_inputs: self.secret_inputs.clone(),
+            target_flags: self.target_flags.clone(),
         }
     }
 
@@ -663,16 +750,54 @@ impl EvalContextConfig {
         &self,
         load_spec: &LoadSpec,
         current_file: &Path,
+    ) -> Result<PathBuf, anyhow::Error> {
+        self.resolve_spec_inner(load_spec, current_file, None)
+    }
+
+    /// Resolve a load path string like [`Self::resolve_path`], also returning
+    /// every step taken, including those before a failure.
+    pub fn trace_resolve_path(
+        &self,
+        path: &str,
+        current_file: &Path,
+    ) -> (Result<PathBuf, anyhow::Error>, Vec<ResolveStep>) {
+        let mut trace = Vec::new();
+        let result = LoadSpec::parse(path)
+            .ok_or_else(|| anyhow::anyhow!("Invalid load path spec: {}", path))
+            .and_then(|load_spec| {
+                self.resolve_spec_inner(&load_spec, current_file, Some(&mut trace))
+            });
+        (result, trace)
+    }
+
+    fn resolve_spec_inner(
+        &self,
+        load_spec: &LoadSpec,
+        current_file: &Path,
+        mut trace: Option<&mut Vec<ResolveStep>>,
     ) -> Result<PathBuf, anyhow::Error> {
         if let LoadSpec::PackageUri { uri, .. } = load_spec {
             let abs = self.resolution.resolve_package_uri(uri)?;
-            return self.resolve_spec(&LoadSpec::local_path(abs), current_file);
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.push(ResolveStep::PackageUri {
+                    uri: uri.clone(),
+                    path: abs.clone(),
+                });
+            }
+            return self.resolve_spec_inner(&LoadSpec::local_path(abs), current_file, trace);
         }
 
         let current_file = self.file_provider.canonicalize(current_file)?;
         let mut context =
             ResolveContext::new(self.file_provider(), current_file, load_spec.clone());
-        self.resolve(&mut context)
+        if trace.is_some() {
+            context.trace = Some(Vec::new());
+        }
+        let result = self.resolve(&mut context);
+        if let (Some(trace), Some(steps)) = (trace, context.trace) {
+            trace.extend(steps);
+        }
+        result
     }
 
     fn current_package_scope(
@@ -700,11 +825,18 @@ impl EvalContextConfig {
     /// Remote resolution: longest prefix match against package's declared deps.
     fn try_resolve_workspace(
         &self,
-        context: &ResolveContext,
+        context: &mut ResolveContext,
         scope: &crate::resolution::ResolvedPackageScope<'_>,
     ) -> Result<PathBuf, anyhow::Error> {
         let full_url = if let LoadSpec::Stdlib { path } = context.latest_spec() {
             let stdlib_root = self.resolution.workspace_info.workspace_stdlib_dir();
+            let path = path.clone();
+            context.record(|| ResolveStep::Dependency {
+                url: crate::STDLIB_MODULE_PATH.to_string(),
+                dep_url: crate::STDLIB_MODULE_PATH.to_string(),
+                root: stdlib_root.clone(),
+                source: DependencySource::Stdlib,
+            });
             return Ok(if path.as_os_str().is_empty() {
                 stdlib_root
             } else {
@@ -752,6 +884,15 @@ impl EvalContextConfig {
             ),
         };
 
+        let source = self.dependency_source(root_path);
+        context.record(|| ResolveStep::Dependency {
+            url: full_url.clone(),
+            dep_url: matched_dep.to_string(),
+            root: root_path.to_path_buf(),
+            source,
+        });
+        self.check_sandbox_host(context, matched_dep, source)?;
+
         let relative_path = full_url
             .strip_prefix(matched_dep)
             .and_then(|s| s.strip_prefix('/'))
@@ -814,6 +955,9 @@ impl EvalContextConfig {
 
         let canonical_resolved = context.file_provider.canonicalize(&resolved_path)?;
         let canonical_root = context.file_provider.canonicalize(&package_root)?;
+        context.record(|| ResolveStep::Relative {
+            path: canonical_resolved.clone(),
+        });
 
         // The load crosses a package boundary when the target is owned by a
         // different package than the current file — judged first by the frozen
@@ -855,6 +999,9 @@ impl EvalContextConfig {
                 path.to_string_lossy().replace('\\', "/")
             ))?;
 
+            context.record(|| ResolveStep::PackageBoundary {
+                url: target_url.clone(),
+            });
             let new_spec = LoadSpec::Package {
                 package: target_url,
                 path: PathBuf::new(),
@@ -891,10 +1038,15 @@ impl EvalContextConfig {
         if let LoadSpec::Package { package, path, .. } = context.latest_spec() {
             let expanded_url = self.expand_alias(context, package)?;
             let expanded_spec = LoadSpec::Package {
-                package: expanded_url,
+                package: expanded_url.clone(),
                 path: path.clone(),
             };
             if &expanded_spec != context.latest_spec() {
+                let alias = package.clone();
+                context.record(|| ResolveStep::Alias {
+                    alias,
+                    url: expanded_url,
+                });
                 context.push_spec(expanded_spec)?;
             }
         }
@@ -904,7 +1056,111 @@ impl EvalContextConfig {
             _ => self.resolve_url(context)?,
         };
 
-        self.finish_resolve(context, resolved_path)
+        let resolved_path = self.finish_resolve(context, resolved_path)?;
+        self.check_sandbox_path(context, &resolved_path)?;
+        context.record(|| ResolveStep::Resolved {
+            path: resolved_path.clone(),
+        });
+        Ok(resolved_path)
+    }
+
+    /// `[workspace.sandbox]`, when enabled.
+    fn sandbox(&self) -> Option<&SandboxConfig> {
+        self.resolution.workspace_info.sandbox()
+    }
+
+    /// Sandbox capabilities of the package that owns `file`.
+    fn sandbox_capabilities(&self, sandbox: &SandboxConfig, file: &Path) -> SandboxCapabilities {
+        self.package_scope_for_file(file)
+            .and_then(|scope| scope.package_url().map(|url| sandbox.capabilities(url)))
+            .unwrap_or_default()
+    }
+
+    /// Reject a load that leaves the workspace, the package caches, and the stdlib.
+    fn check_sandbox_path(&self, context: &ResolveContext, path: &Path) -> anyhow::Result<()> {
+        let Some(sandbox) = self.sandbox() else {
+            return Ok(());
+        };
+        let workspace_info = &self.resolution.workspace_info;
+        let roots = [
+            workspace_info.root.clone(),
+            workspace_info.workspace_cache_dir(),
+            workspace_info.cache_dir.clone(),
+            workspace_info.workspace_stdlib_dir(),
+        ];
+        let inside = roots
+            .iter()
+            .filter(|root| !root.as_os_str().is_empty())
+            .any(|root| {
+                path.starts_with(root)
+                    || self
+                        .file_provider
+                        .canonicalize(root)
+                        .is_ok_and(|root| path.starts_with(root))
+            });
+        if inside
+            || self
+                .sandbox_capabilities(sandbox, &context.current_file)
+                .filesystem
+        {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Sandbox violation: {} loads {}, which is outside the workspace and package caches\n  \
+            Grant `filesystem = true` to its package under [workspace.sandbox.packages] in pcb.toml",
+            context.current_file.display(),
+            path.display()
+        )
+    }
+
+    /// Reject a remote dependency from a host outside `[workspace.sandbox].hosts`.
+    fn check_sandbox_host(
+        &self,
+        context: &ResolveContext,
+        dep_url: &str,
+        source: DependencySource,
+    ) -> anyhow::Result<()> {
+        let Some(sandbox) = self.sandbox() else {
+            return Ok(());
+        };
+        let remote = matches!(source, DependencySource::Cache | DependencySource::Vendored);
+        if !remote
+            || sandbox.allows_host(dep_url)
+            || self
+                .sandbox_capabilities(sandbox, &context.current_file)
+                .any_host
+        {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Sandbox violation: {} depends on '{dep_url}', which is not under an allowed host\n  \
+            Add its host to [workspace.sandbox] hosts, or grant `any-host = true` to the loading package under [workspace.sandbox.packages]",
+            context.current_file.display()
+        )
+    }
+
+    /// Classify where a dependency root's files come from.
+    fn dependency_source(&self, root: &Path) -> DependencySource {
+        let workspace_info = &self.resolution.workspace_info;
+        let under = |dir: PathBuf| {
+            root.starts_with(&dir)
+                || self
+                    .file_provider
+                    .canonicalize(&dir)
+                    .is_ok_and(|dir| root.starts_with(dir))
+        };
+        if under(workspace_info.workspace_stdlib_dir()) {
+            DependencySource::Stdlib
+        } else if under(workspace_info.root.join("vendor")) {
+            DependencySource::Vendored
+        } else if under(workspace_info.workspace_cache_dir())
+            || (!workspace_info.cache_dir.as_os_str().is_empty()
+                && under(workspace_info.cache_dir.clone()))
+        {
+            DependencySource::Cache
+        } else {
+            DependencySource::Workspace
+        }
     }
 }
 
@@ -920,11 +1176,29 @@ impl Default for EvalSession {
             symbol_meta: Arc::new(RwLock::new(HashMap::new())),
             module_deps: Arc::new(RwLock::new(HashMap::new())),
             module_tree: Arc::new(RwLock::new(BTreeMap::new())),
+            profiler: None,
+            cancellation: CancellationToken::default(),
         }
     }
 }
 
 impl EvalSession {
+    /// Profile every evaluation in this session with `profiler`.
+    pub fn with_profiler(mut self, profiler: Arc<Profiler>) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
+    pub(crate) fn profiler(&self) -> Option<&Arc<Profiler>> {
+        self.profiler.as_ref()
+    }
+
+    /// Stop evaluations in this session once `token` is cancelled.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
     /// Reset per-root evaluation state while preserving reusable caches such as
     /// loaded modules and canonicalized file contents.
     ///
@@ -966,8 +1240,15 @@ impl EvalSession {
     /// Drop everything derived from source files: loaded modules, parsed
     /// sources, and footprint/symbol/spice values.
     pub fn clear_load_cache(&self) {
-        self.load_cache.clear();
+        self.clear_evaluated_cache();
         self.source_cache.clear();
+    }
+
+    /// Like [`Self::clear_load_cache`], but keep parsed sources. They are
+    /// revalidated against file metadata on use, so unchanged files are not
+    /// re-read or re-parsed.
+    fn clear_evaluated_cache(&self) {
+        self.load_cache.clear();
         self.footprint_cache.clear();
         self.symbol_cache.clear();
         self.spice_cache.clear();
@@ -1073,6 +1354,7 @@ impl EvalSession {
             pending_inputs: SmallMap::new(),
             pending_properties: SmallMap::new(),
             pending_parent_component_modifiers: Vec::new(),
+            pending_instantiation: None,
             json_inputs: SmallMap::new(),
         }
     }
@@ -1092,6 +1374,7 @@ pub struct EvalContext {
     pending_inputs: SmallMap<String, FrozenValue>,
     pending_properties: SmallMap<String, FrozenValue>,
     pending_parent_component_modifiers: Vec<FrozenValue>,
+    pending_instantiation: Option<InstantiationSite>,
     json_inputs: SmallMap<String, serde_json::Value>,
 }
 
@@ -1236,6 +1519,8 @@ impl EvalContext {
             build_circuit: false,
             eager: self.config.eager,
             inject_prelude: self.config.inject_prelude,
+            secret_inputs: self.config.secret_inputs.clone(),
+            target_flags: self.config.target_flags.clone(),
         };
         self.session.create_context(child_config)
     }
@@ -1266,11 +1551,13 @@ impl EvalContext {
                     LibraryExtension::Json,
                 ])
                 .with(builtin_globals)
+                .with(deprecation_globals)
                 .with(component_globals)
                 .with(module_globals)
                 .with(interface_globals)
                 .with(assert_globals)
                 .with(file_globals)
+                .with(stdlib_version_globals)
                 .with(model_globals)
                 .with(test_bench_globals)
                 .build()
@@ -1373,6 +1660,9 @@ impl EvalContext {
                 .map(|value| value.to_value())
                 .collect();
             module_value.set_parent_component_modifiers(parent_modifiers);
+            if let Some(site) = &self.pending_instantiation {
+                module_value.set_instantiation(site.clone());
+            }
         }
 
         for (name, value) in self.pending_properties.iter() {
@@ -1436,6 +1726,21 @@ impl EvalContext {
         self.json_inputs.extend(json_inputs);
     }
 
+    /// Set sensitive root inputs. They are passed to config() like JSON inputs
+    /// and redacted from the schematic if their values leak into attributes.
+    pub fn set_secret_inputs(&mut self, secrets: SecretInputs) {
+        for (name, value) in secrets.values() {
+            self.json_inputs.insert(name.clone(), value.clone());
+        }
+        self.config.secret_inputs = Arc::new(secrets);
+    }
+
+    /// Set the build flags every module of this evaluation reads through the
+    /// `target` global.
+    pub fn set_target_flags(&mut self, flags: BTreeMap<String, String>) {
+        self.config.target_flags = Arc::new(flags);
+    }
+
     /// Parse Starlark source with this context's dialect, using the recursive
     /// descent parser (same AST as the default LALRPOP parser, roughly half
     /// the cost).
@@ -1471,10 +1776,18 @@ impl EvalContext {
         if let Some(contents) = &self.config.contents {
             let contents = contents.clone();
             let ast = parse(contents.clone())?;
-            return Ok(ParsedSource { contents, ast });
+            return Ok(ParsedSource {
+                contents,
+                ast,
+                metadata: None,
+            });
         }
 
-        if let Some(source) = self.session.source_cache.get(&source_path.to_path_buf()) {
+        let metadata = self.file_provider().metadata(source_path).ok();
+        if let Some(source) = self.session.source_cache.get(&source_path.to_path_buf())
+            && metadata.is_some()
+            && source.metadata == metadata
+        {
             return Ok(source);
         }
 
@@ -1485,6 +1798,7 @@ impl EvalContext {
         let source = ParsedSource {
             ast: parse(contents.clone())?,
             contents,
+            metadata,
         };
         self.session
             .source_cache
@@ -1505,11 +1819,18 @@ impl EvalContext {
     )]
     pub fn eval(mut self) -> WithDiagnostics<EvalOutput> {
         // Make sure a source path is set.
-        if self.config.source_path.is_none() {
+        let Some(source_path) = self.config.source_path.as_deref() else {
             return anyhow::anyhow!("source_path not set on Context before eval()").into();
+        };
+        if let Err(cancelled) = self
+            .session
+            .cancellation
+            .check(format!("evaluation of {}", source_path.display()))
+        {
+            return anyhow::Error::new(cancelled).into();
         }
 
-        let ParsedSource { contents, ast } = match self.parsed_source() {
+        let ParsedSource { contents, ast, .. } = match self.parsed_source() {
             Ok(source) => source,
             Err(failure) => return *failure,
         };
@@ -1525,8 +1846,14 @@ impl EvalContext {
         }
 
         Module::with_temp_heap(|module| {
-            // Make prelude symbols available before user code runs.
+            // Make prelude symbols and build flags available before user code runs.
             self.inject_prelude(&module);
+            module.set(
+                TARGET_GLOBAL,
+                module
+                    .heap()
+                    .alloc(TargetFlags::new(&self.config.target_flags)),
+            );
 
             // Attach a `ContextValue` so user code can access evaluation context,
             // then seed any inputs/properties that were collected before the
@@ -1543,14 +1870,24 @@ impl EvalContext {
                 eval.set_loader(&self);
                 eval.set_print_handler(&print_handler);
                 eval.extra_mut = Some(&mut eval_context_ref);
+                if let Some(profiler) = &self.session.profiler {
+                    profiler.start(&mut eval);
+                }
 
                 let globals = Self::build_globals();
 
                 // We are only interested in whether evaluation succeeded, not in the
                 // value of the final expression, so map the result to `()`.
                 let _span = info_span!("starlark_eval").entered();
-                eval.eval_module(AstModule::clone(&ast), &globals)
-                    .and_then(|_| Self::apply_component_modifiers(&mut eval))
+                let result = eval
+                    .eval_module(AstModule::clone(&ast), &globals)
+                    .and_then(|_| Self::apply_component_modifiers(&mut eval));
+                if let (Some(profiler), Some(path)) =
+                    (&self.session.profiler, self.config.source_path.as_deref())
+                {
+                    profiler.finish(&mut eval, path);
+                }
+                result
             };
 
             // Collect print output after evaluation
@@ -1616,6 +1953,8 @@ impl EvalContext {
                                 default_display,
                                 allowed_values,
                                 allowed_display,
+                                min_display: param.min_display(),
+                                max_display: param.max_display(),
                                 help: param.help.clone(),
                                 direction: param.direction,
                             }
@@ -1672,6 +2011,14 @@ impl EvalContext {
 
                     if !diagnostics.iter().any(Diagnostic::is_error) {
                         diagnostics.extend(ast_style_lints(&ast));
+                        let is_stdlib = self
+                            .config
+                            .source_path
+                            .as_deref()
+                            .is_some_and(|path| is_stdlib_source_path(path, &self.config));
+                        if !is_stdlib {
+                            diagnostics.extend(ast_unused_lints(&ast));
+                        }
                     }
 
                     let output = EvalOutput {
@@ -1739,7 +2086,7 @@ impl EvalContext {
         path: PathBuf,
         contents: String,
     ) -> WithDiagnostics<EvalOutput> {
-        self.session.clear_load_cache();
+        self.session.clear_evaluated_cache();
         self.session.prepare_for_root_eval();
         self.session.clear_symbol_maps(&path);
 
@@ -1953,6 +2300,77 @@ impl EvalContext {
         Some(codemap.file_span(span).resolve_span())
     }
 
+    /// Span of the `load()` of `load_path` in `file`, from its cached parse.
+    fn load_statement_span(&self, file: &Path, load_path: &str) -> Option<ResolvedSpan> {
+        let source = self.session.source_cache.get(&file.to_path_buf())?;
+        let load = source
+            .ast
+            .loads()
+            .into_iter()
+            .find(|load| load.module_id == load_path)?;
+        Some(load.span.resolve_span())
+    }
+
+    /// Diagnostic for a `load()` of `target` (written `load_path`) that closes a
+    /// cycle, listing every file in the cycle (`a.zen → b.zen → a.zen`) with the
+    /// load statement that continues it.
+    fn load_cycle_diagnostic(
+        &self,
+        target: &Path,
+        load_path: &str,
+        span: Option<ResolvedSpan>,
+    ) -> Diagnostic {
+        let current_file = self.config.source_path.clone().unwrap_or_default();
+        let start = self
+            .config
+            .load_chain
+            .iter()
+            .position(|link| link.path == target)
+            .unwrap_or(self.config.load_chain.len());
+        let mut links = self.config.load_chain[start..].to_vec();
+        links.push(LoadChainLink {
+            path: current_file.clone(),
+            load_path: load_path.to_string(),
+            span: span.or_else(|| self.resolve_load_span(load_path)),
+        });
+
+        let workspace_root = &self.config.resolution.workspace_info.root;
+        let display = |path: &Path| {
+            path.strip_prefix(workspace_root)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        };
+        let cycle: Vec<String> = links
+            .iter()
+            .map(|link| display(&link.path))
+            .chain(std::iter::once(display(target)))
+            .collect();
+
+        let current = links.pop().expect("cycle includes the current load");
+        let mut diagnostic = Diagnostic::categorized(
+            &current_file.to_string_lossy(),
+            &format!("Cyclic load: {}", cycle.join(" → ")),
+            "load.cycle",
+            EvalSeverity::Error,
+        )
+        .with_span(current.span);
+        for link in links {
+            let Some(span) = link
+                .span
+                .or_else(|| self.load_statement_span(&link.path, &link.load_path))
+            else {
+                continue;
+            };
+            diagnostic = diagnostic.with_related(DiagnosticReference {
+                path: link.path.to_string_lossy().into_owned(),
+                span,
+                message: format!("`{}` is loaded here", link.load_path),
+            });
+        }
+        diagnostic
+    }
+
     /// Get the source path of the current module being evaluated
     pub fn get_source_path(&self) -> Option<&Path> {
         self.config.source_path.as_deref()
@@ -2032,15 +2450,33 @@ impl EvalContext {
             )));
         };
 
+        self.session
+            .cancellation
+            .check(format!("load of {path}"))
+            .map_err(|cancelled| starlark::Error::new_other(anyhow::Error::new(cancelled)))?;
+
         // Resolve the load path to an absolute path
         let canonical_path = load_config.resolve_path(path, current_file)?;
 
+        // Directory loads evaluate the directory's export manifest, synthesizing
+        // one from `[exports]` or the contained `.zen` files when absent.
+        let mut exports_source = None;
+        let canonical_path = if load_config.file_provider.is_directory(&canonical_path) {
+            exports_source = crate::lang::exports::directory_exports_source(
+                load_config.file_provider.as_ref(),
+                &canonical_path,
+            )
+            .map_err(starlark::Error::new_other)?;
+            crate::lang::exports::exports_path(&canonical_path)
+        } else {
+            canonical_path
+        };
+
         // Check for cyclic imports using per-context load chain (thread-safe)
-        if self.config.load_chain.contains(&canonical_path) {
-            return Err(starlark::Error::new_other(anyhow!(
-                "cyclic load detected while loading `{}`",
-                canonical_path.display()
-            )));
+        if self.config.would_create_cycle(&canonical_path) {
+            return Err(self
+                .load_cycle_diagnostic(&canonical_path, path, span)
+                .into());
         }
 
         let source_path = self
@@ -2063,12 +2499,6 @@ impl EvalContext {
             return Ok(cached.output);
         }
 
-        if load_config.file_provider.is_directory(&canonical_path) {
-            return Err(starlark::Error::new_other(anyhow::anyhow!(
-                "Directory load syntax is no longer supported"
-            )));
-        }
-
         // Build child config for the nested load
         let name = canonical_path
             .file_stem()
@@ -2079,9 +2509,12 @@ impl EvalContext {
         let mut child_path = self.config.module_path.clone();
         child_path.push(&name);
 
-        let child_config = self
-            .config
-            .child_for_load(child_path, canonical_path.clone());
+        let mut child_config =
+            self.config
+                .child_for_load(child_path, canonical_path.clone(), path, span);
+        if let Some(source) = exports_source {
+            child_config = child_config.set_source_contents(source);
+        }
 
         let result = self.session.create_context(child_config).eval();
 
@@ -2178,6 +2611,11 @@ impl EvalContext {
         }
         self.set_inputs_from_frozen_values(pending.inputs.clone());
         self.set_parent_component_modifiers_from_frozen_values(pending.component_modifiers);
+        self.pending_instantiation = Some(InstantiationSite {
+            path: pending.call_site_path.clone(),
+            span: pending.call_site_span,
+            forwarded_inputs: pending.forwarded_inputs,
+        });
 
         let child_result = self.eval();
 
@@ -2187,7 +2625,10 @@ impl EvalContext {
             .diagnostics
             .iter()
             .map(|child_diag| {
-                if is_ast_style_diagnostic(child_diag) {
+                if is_ast_style_diagnostic(child_diag)
+                    || is_ast_unused_diagnostic(child_diag)
+                    || is_call_site_deprecation(child_diag)
+                {
                     return child_diag.clone();
                 }
 
@@ -2290,4 +2731,95 @@ mod tests {
 
         assert!(context.session.footprint_cache.get(&key).is_none());
     }
+
+    fn sandboxed_config() -> EvalContextConfig {
+        let workspace = crate::config::WorkspaceConfig {
+            sandbox: SandboxConfig {
+                enabled: true,
+                hosts: vec!["github.com/diodeinc".to_string()],
+                packages: BTreeMap::new(),
+            },
+            ..Default::default()
+        };
+        let workspace_info = crate::workspace::WorkspaceInfo {
+            root: PathBuf::from("/ws"),
+            cache_dir: PathBuf::from("/home/user/.pcb/cache"),
+            config: Some(crate::config::PcbToml {
+                workspace: Some(workspace),
+                ..Default::default()
+            }),
+            packages: BTreeMap::new(),
+            errors: vec![],
+        };
+        EvalContextConfig::new(
+            Arc::new(InMemoryFileProvider::empty()),
+            Arc::new(ResolutionResult::frozen(
+                workspace_info,
+                BTreeMap::new(),
+                HashMap::new(),
+            )),
+        )
+    }
+
+    fn resolve_context(file_provider: &dyn FileProvider) -> ResolveContext<'_> {
+        ResolveContext::new(
+            file_provider,
+            PathBuf::from("/ws/boards/Main.zen"),
+            LoadSpec::Path {
+                path: PathBuf::from("x.zen"),
+                allow_not_exist: false,
+            },
+        )
+    }
+
+    #[test]
+    fn sandbox_rejects_loads_outside_workspace_and_caches() {
+        let config = sandboxed_config();
+        let context = resolve_context(config.file_provider());
+
+        for allowed in [
+            "/ws/modules/Led.zen",
+            "/home/user/.pcb/cache/github.com/diodeinc/stdlib/1.0.0/units.zen",
+        ] {
+            config
+                .check_sandbox_path(&context, Path::new(allowed))
+                .unwrap();
+        }
+        let err = config
+            .check_sandbox_path(&context, Path::new("/etc/secrets.zen"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Sandbox violation"), "{err}");
+        assert!(err.contains("/etc/secrets.zen"), "{err}");
+    }
+
+    #[test]
+    fn sandbox_rejects_remote_dependencies_from_other_hosts() {
+        let config = sandboxed_config();
+        let context = resolve_context(config.file_provider());
+
+        config
+            .check_sandbox_host(
+                &context,
+                "github.com/diodeinc/registry",
+                DependencySource::Cache,
+            )
+            .unwrap();
+        // Workspace packages are not remote, whatever their URL.
+        config
+            .check_sandbox_host(
+                &context,
+                "github.com/acme/boards",
+                DependencySource::Workspace,
+            )
+            .unwrap();
+        for source in [DependencySource::Cache, DependencySource::Vendored] {
+            let err = config
+                .check_sandbox_host(&context, "github.com/acme/parts", source)
+                .unwrap_err()
+                .to_string();
+            assert!(err.starts_with("Sandbox violation"), "{err}");
+            assert!(err.contains("'github.com/acme/parts'"), "{err}");
+        }
+    }
 }
@@ -0,0 +1,117 @@
+//! Provenance of io()/config() values for `pcb build --explain`.
+//!
+//! Each module instance records the `Module()` call that created it and which
+//! arguments were the parent's own io()/config() values passed straight
+//! through. [`explain_parameter`] follows those links from an instance
+//! parameter up the hierarchy until it reaches the value's origin.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Result, bail};
+use starlark::codemap::ResolvedSpan;
+use starlark::values::ValueLike;
+
+use crate::lang::module::{FrozenModuleValue, ModulePath};
+
+/// Where a parameter got its value at one level of the hierarchy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueOrigin {
+    /// Passed by the parent in the `Module()` call at this location.
+    Passed {
+        path: String,
+        span: ResolvedSpan,
+        /// Parent parameter the value was forwarded from, if any.
+        forwarded_from: Option<String>,
+    },
+    /// Provided to the root module, e.g. with `pcb build --config`.
+    Override,
+    /// Not provided; the declaration's default (or synthesized value) was used.
+    Default {
+        path: String,
+        span: Option<ResolvedSpan>,
+    },
+}
+
+/// One level of a parameter's provenance chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterTraceStep {
+    /// Module instance holding the parameter.
+    pub module: ModulePath,
+    /// Parameter name in that module.
+    pub param: String,
+    /// Repr of the value the module saw.
+    pub value: String,
+    pub origin: ValueOrigin,
+}
+
+/// Trace `target` (`instance.path.param`, or just `param` for the root
+/// module) from the instance that uses it up to where the value originated.
+pub fn explain_parameter(
+    module_tree: &BTreeMap<ModulePath, FrozenModuleValue>,
+    target: &str,
+) -> Result<Vec<ParameterTraceStep>> {
+    let mut segments: Vec<String> = target.split('.').map(str::to_string).collect();
+    let param = segments.pop().filter(|param| !param.is_empty());
+    let Some(mut param) = param else {
+        bail!("Expected INSTANCE.PATH.PARAM, got `{target}`");
+    };
+    let mut path = ModulePath { segments };
+
+    let mut steps = Vec::new();
+    loop {
+        let Some(module) = module_tree.get(&path) else {
+            bail!("No module instance `{path}` in this design");
+        };
+        let Some(metadata) = module.signature().iter().find(|p| p.name == param) else {
+            let names: Vec<&str> = module.signature().iter().map(|p| p.name.as_str()).collect();
+            bail!(
+                "Module `{}` has no io() or config() named `{param}` (available: {})",
+                path.name(),
+                if names.is_empty() {
+                    "none".to_string()
+                } else {
+                    names.join(", ")
+                }
+            );
+        };
+
+        let value = metadata
+            .actual_value
+            .map(|value| value.to_value().to_repr())
+            .unwrap_or_else(|| "None".to_string());
+        let origin = if !module.inputs().contains_key(&param) {
+            ValueOrigin::Default {
+                path: module.source_path().to_string(),
+                span: metadata.declaration_span,
+            }
+        } else if let Some(site) = module.instantiation() {
+            ValueOrigin::Passed {
+                path: site.path.clone(),
+                span: site.span,
+                forwarded_from: site.forwarded_inputs.get(&param).cloned(),
+            }
+        } else {
+            ValueOrigin::Override
+        };
+
+        let next = match &origin {
+            ValueOrigin::Passed {
+                forwarded_from: Some(parent_param),
+                ..
+            } => path.parent().map(|parent| (parent, parent_param.clone())),
+            _ => None,
+        };
+        steps.push(ParameterTraceStep {
+            module: path,
+            param,
+            value,
+            origin,
+        });
+
+        let Some((parent, parent_param)) = next else {
+            return Ok(steps);
+        };
+        path = parent;
+        param = parent_param;
+    }
+}
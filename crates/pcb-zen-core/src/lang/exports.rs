@@ -0,0 +1,168 @@
+//! Export manifests for directory loads.
+//!
+//! `load("./components", "Resistor", "Capacitor")` loads the export manifest of a
+//! directory instead of a single file. The manifest is resolved in this order:
+//!
+//! 1. `exports.zen` inside the directory, evaluated as a regular module.
+//! 2. An `[exports]` table in the directory's `pcb.toml`, mapping exported names to
+//!    `.zen` files relative to the directory.
+//! 3. Every `.zen` file directly inside the directory, exported under its file stem.
+//!
+//! Cases 2 and 3 are expanded into a synthesized `exports.zen` source so that the
+//! evaluator, caching, and LSP all treat a directory load like any other module.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::FileProvider;
+use crate::config::PcbToml;
+
+/// File name of a directory's export manifest.
+pub const EXPORTS_FILE: &str = "exports.zen";
+
+/// Path of the export manifest for `dir`, whether or not it exists on disk.
+pub fn exports_path(dir: &Path) -> PathBuf {
+    dir.join(EXPORTS_FILE)
+}
+
+/// Compute the exported names of `dir`, mapped to paths relative to `dir`.
+///
+/// Returns `None` when the directory has an explicit `exports.zen`, which is
+/// evaluated as written.
+pub fn directory_exports(
+    file_provider: &dyn FileProvider,
+    dir: &Path,
+) -> Result<Option<BTreeMap<String, String>>> {
+    if file_provider.exists(&exports_path(dir)) {
+        return Ok(None);
+    }
+
+    let manifest = dir.join("pcb.toml");
+    if file_provider.exists(&manifest) {
+        let config = PcbToml::from_file(file_provider, &manifest)?;
+        if !config.exports.is_empty() {
+            for (name, target) in &config.exports {
+                if !is_identifier(name) {
+                    anyhow::bail!(
+                        "invalid export name `{name}` in {}: must be a valid identifier",
+                        manifest.display()
+                    );
+                }
+                if !file_provider.exists(&dir.join(target)) {
+                    anyhow::bail!(
+                        "export `{name}` in {} points to missing file `{target}`",
+                        manifest.display()
+                    );
+                }
+            }
+            return Ok(Some(config.exports));
+        }
+    }
+
+    let entries = file_provider
+        .list_directory(dir)
+        .with_context(|| format!("failed to list {}", dir.display()))?;
+    let exports = entries
+        .iter()
+        .filter(|path| path.extension().is_some_and(|ext| ext == "zen"))
+        .filter(|path| !file_provider.is_directory(path))
+        .filter_map(|path| {
+            let file_name = path.file_name()?.to_str()?;
+            let stem = path.file_stem()?.to_str()?;
+            is_identifier(stem).then(|| (stem.to_string(), file_name.to_string()))
+        })
+        .collect();
+    Ok(Some(exports))
+}
+
+/// Render exports as Zener source, one `Module()` binding per name in sorted order.
+pub fn render_exports(exports: &BTreeMap<String, String>) -> String {
+    let mut source = String::from("# Generated export manifest\n");
+    for (name, target) in exports {
+        let target = target.strip_prefix("./").unwrap_or(target);
+        source.push_str(&format!("{name} = Module(\"./{target}\")\n"));
+    }
+    source
+}
+
+/// Synthesized `exports.zen` source for `dir`, or `None` if it has an explicit one.
+pub fn directory_exports_source(
+    file_provider: &dyn FileProvider,
+    dir: &Path,
+) -> Result<Option<String>> {
+    Ok(directory_exports(file_provider, dir)?.map(|exports| render_exports(&exports)))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemoryFileProvider;
+    use std::collections::HashMap;
+
+    fn provider(files: &[(&str, &str)]) -> InMemoryFileProvider {
+        InMemoryFileProvider::new(
+            files
+                .iter()
+                .map(|(path, contents)| (path.to_string(), contents.to_string()))
+                .collect::<HashMap<_, _>>(),
+        )
+    }
+
+    #[test]
+    fn globs_zen_files_in_sorted_order() {
+        let files = provider(&[
+            ("/lib/Resistor.zen", ""),
+            ("/lib/Capacitor.zen", ""),
+            ("/lib/not-an-ident.zen", ""),
+            ("/lib/README.md", ""),
+        ]);
+        let source = directory_exports_source(&files, Path::new("/lib"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            source,
+            "# Generated export manifest\n\
+             Capacitor = Module(\"./Capacitor.zen\")\n\
+             Resistor = Module(\"./Resistor.zen\")\n"
+        );
+    }
+
+    #[test]
+    fn pcb_toml_exports_take_precedence_over_glob() {
+        let files = provider(&[
+            (
+                "/lib/pcb.toml",
+                "[exports]\nR = \"passives/Resistor.zen\"\n",
+            ),
+            ("/lib/passives/Resistor.zen", ""),
+            ("/lib/Other.zen", ""),
+        ]);
+        let exports = directory_exports(&files, Path::new("/lib"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            exports.into_iter().collect::<Vec<_>>(),
+            vec![("R".to_string(), "passives/Resistor.zen".to_string())]
+        );
+    }
+
+    #[test]
+    fn explicit_exports_file_is_used_as_written() {
+        let files = provider(&[("/lib/exports.zen", ""), ("/lib/Resistor.zen", "")]);
+        assert!(
+            directory_exports(&files, Path::new("/lib"))
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -7,7 +7,10 @@ use crate::lang::evaluator_ext::EvaluatorExt;
 use crate::load_spec::LoadSpec;
 use crate::resolution::ResolutionResult;
 
-fn stable_path_string(resolution: &ResolutionResult, resolved_path: &std::path::Path) -> String {
+pub(crate) fn stable_path_string(
+    resolution: &ResolutionResult,
+    resolved_path: &std::path::Path,
+) -> String {
     if let Some(uri) = resolution.format_package_uri(resolved_path) {
         return uri;
     }
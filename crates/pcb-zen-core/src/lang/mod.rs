@@ -2,10 +2,13 @@ pub(crate) mod binding;
 pub mod builtin;
 pub mod component;
 pub mod context;
+pub mod deprecation;
 pub mod electrical_check;
 pub mod r#enum;
 pub mod eval;
 pub(crate) mod evaluator_ext;
+pub mod explain;
+pub mod exports;
 pub(crate) mod footprint;
 pub(crate) mod interface;
 pub mod io_direction;
@@ -15,9 +18,13 @@ pub(crate) mod param_decl;
 pub mod part;
 pub(crate) mod path;
 pub(crate) mod pin_erc;
+pub mod profile;
+pub mod secret;
 pub mod spice_model;
 pub mod stackup;
+pub(crate) mod stdlib_version;
 pub mod symbol;
+pub mod target;
 pub mod test_bench;
 pub(crate) mod type_conversion;
 pub mod type_info;
@@ -34,6 +41,7 @@ pub mod error;
 // Naming convention checks
 pub mod naming;
 pub(crate) mod style_lint;
+pub(crate) mod unused_lint;
 
 // Validation utilities
 pub(crate) mod validation;
@@ -288,6 +288,10 @@ pub struct ParameterMetadataGen<V: ValueLifetimeless> {
     pub default_value: Option<V>,
     /// Finite set of allowed values if provided
     pub allowed_values: Option<Vec<V>>,
+    /// Inclusive lower bound for numeric config values if provided
+    pub min_value: Option<V>,
+    /// Inclusive upper bound for numeric config values if provided
+    pub max_value: Option<V>,
     /// Whether this is a config parameter (vs io parameter)
     pub is_config: bool,
     /// Help text describing the parameter
@@ -328,6 +332,7 @@ impl<'v, V: ValueLike<'v>> ParameterMetadataGen<V> {
         optional: bool,
         default_value: Option<V>,
         allowed_values: Option<Vec<V>>,
+        (min_value, max_value): (Option<V>, Option<V>),
         is_config: bool,
         help: Option<String>,
         direction: Option<IoDirection>,
@@ -340,6 +345,8 @@ impl<'v, V: ValueLike<'v>> ParameterMetadataGen<V> {
             optional,
             default_value,
             allowed_values,
+            min_value,
+            max_value,
             is_config,
             help,
             direction,
@@ -363,6 +370,18 @@ impl<'v, V: ValueLike<'v>> ParameterMetadataGen<V> {
                 .collect()
         })
     }
+
+    pub fn min_display(&self) -> Option<String> {
+        self.min_value
+            .as_ref()
+            .map(|value| value.to_value().to_repr())
+    }
+
+    pub fn max_display(&self) -> Option<String> {
+        self.max_value
+            .as_ref()
+            .map(|value| value.to_value().to_repr())
+    }
 }
 
 fn is_io_wrapper_path(path: &str) -> bool {
@@ -384,6 +403,7 @@ pub(crate) struct ParameterMetadataInput<'v> {
     pub(crate) optional: bool,
     pub(crate) default: Option<Value<'v>>,
     pub(crate) allowed_values: Option<Vec<Value<'v>>>,
+    pub(crate) range: ConfigRange<'v>,
     pub(crate) is_config: bool,
     pub(crate) help: Option<String>,
     pub(crate) direction: Option<IoDirection>,
@@ -416,6 +436,7 @@ pub(crate) fn record_parameter_metadata<'v>(
             metadata.optional,
             metadata.default,
             metadata.allowed_values,
+            (metadata.range.min, metadata.range.max),
             metadata.is_config,
             metadata.help.clone(),
             metadata.direction,
@@ -467,6 +488,17 @@ pub(crate) fn io_declaration_site(eval: &Evaluator<'_, '_, '_>) -> DeclarationSi
     }
 }
 
+/// Where a module was instantiated by its parent.
+#[derive(Debug, Clone, Default)]
+pub struct InstantiationSite {
+    /// Source file containing the `Module()` call.
+    pub path: String,
+    /// Span of the `Module()` call.
+    pub span: ResolvedSpan,
+    /// Parent io()/config() values passed straight through. Map of `input name → parent parameter name`.
+    pub forwarded_inputs: BTreeMap<String, String>,
+}
+
 #[derive(Clone, Coerce, Trace, ProvidesStaticType, NoSerialize, Allocative, Freeze)]
 #[repr(C)]
 pub struct ModuleValueGen<V: ValueLifetimeless> {
@@ -483,6 +515,8 @@ pub struct ModuleValueGen<V: ValueLifetimeless> {
     positions: PositionMap,
     /// Path movement directives from moved() calls. Map of `old path → (new path, auto_generated)`.
     moved_directives: SmallMap<String, (String, bool)>,
+    /// Messages of the check() calls that ran in this module, in call order.
+    checks: Vec<String>,
     /// Local values (components, electrical checks, testbenches). Child modules are in module_tree.
     children: Vec<V>,
     /// Component modifier functions registered via builtin.add_component_modifier().
@@ -495,6 +529,11 @@ pub struct ModuleValueGen<V: ValueLifetimeless> {
     /// parent.parent_component_modifiers(), creating the full ancestor chain.
     /// Applied AFTER the module's own modifiers.
     parent_component_modifiers: Vec<V>,
+    /// Call site in the parent module, or `None` for the root module.
+    #[freeze(identity)]
+    #[trace(unsafe_ignore)]
+    #[allocative(skip)]
+    instantiation: Option<InstantiationSite>,
 }
 
 starlark_complex_value!(pub ModuleValue);
@@ -600,9 +639,11 @@ impl<'v, V: ValueLike<'v>> ModuleValueGen<V> {
             net_name_to_id: SmallMap::new(),
             positions,
             moved_directives: SmallMap::new(),
+            checks: Vec::new(),
             children: Vec::new(),
             component_modifiers: Vec::new(),
             parent_component_modifiers: Vec::new(),
+            instantiation: None,
         }
     }
 
@@ -622,6 +663,15 @@ impl<'v, V: ValueLike<'v>> ModuleValueGen<V> {
         &self.inputs
     }
 
+    /// Where the parent instantiated this module, or `None` for the root module.
+    pub fn instantiation(&self) -> Option<&InstantiationSite> {
+        self.instantiation.as_ref()
+    }
+
+    pub(crate) fn set_instantiation(&mut self, site: InstantiationSite) {
+        self.instantiation = Some(site);
+    }
+
     /// Return a reference to the custom property map attached to this Module.
     pub fn properties(&self) -> &SmallMap<String, V> {
         &self.properties
@@ -680,6 +730,7 @@ impl<'v, V: ValueLike<'v>> ModuleValueGen<V> {
         optional: bool,
         default_value: Option<V>,
         allowed_values: Option<Vec<V>>,
+        range: (Option<V>, Option<V>),
         is_config: bool,
         help: Option<String>,
         direction: Option<IoDirection>,
@@ -695,6 +746,7 @@ impl<'v, V: ValueLike<'v>> ModuleValueGen<V> {
                 optional,
                 default_value,
                 allowed_values,
+                range,
                 is_config,
                 help,
                 direction,
@@ -887,6 +939,16 @@ impl<'v, V: ValueLike<'v>> ModuleValueGen<V> {
         &self.moved_directives
     }
 
+    /// Record that a check() call ran in this module.
+    pub(crate) fn add_check(&mut self, message: String) {
+        self.checks.push(message);
+    }
+
+    /// Messages of the check() calls that ran in this module.
+    pub fn checks(&self) -> &[String] {
+        &self.checks
+    }
+
     /// Extract all net names from a value recursively.
     /// This handles Net types directly and recursively extracts nets from Interface types.
     pub fn extract_nets_from_value(value: starlark::values::Value<'_>) -> HashSet<String> {
@@ -1159,6 +1221,20 @@ where
 
         // Collect parent modifiers (parent's own + parent's ancestors)
         let combined_modifiers = parent_module.collect_all_component_modifiers_as_values();
+
+        // Note which arguments are the parent's own io()/config() values so that
+        // `pcb build --explain` can follow a value up the hierarchy.
+        let forwarded_inputs: BTreeMap<String, String> = parent_values
+            .iter()
+            .filter_map(|(arg_name, value)| {
+                parent_module.signature().iter().find_map(|param| {
+                    param
+                        .actual_value
+                        .filter(|actual| actual.ptr_eq(*value))
+                        .map(|_| (arg_name.clone(), param.name.clone()))
+                })
+            })
+            .collect();
         drop(parent_module);
 
         context.enqueue_child(PendingChild {
@@ -1168,6 +1244,7 @@ where
             properties: properties_override,
             component_modifiers: combined_modifiers,
             provided_names,
+            forwarded_inputs,
             call_site_path,
             call_site_span,
             call_stack,
@@ -1497,11 +1574,103 @@ pub(crate) fn validate_allowed_config_value<'v>(
     );
 }
 
+/// Inclusive `min=`/`max=` bounds of a numeric `config()` parameter.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ConfigRange<'v> {
+    pub(crate) min: Option<Value<'v>>,
+    pub(crate) max: Option<Value<'v>>,
+}
+
+impl<'v> ConfigRange<'v> {
+    fn is_empty(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+}
+
+fn config_range_type_supported<'v>(typ: Value<'v>) -> bool {
+    typ.downcast_ref::<PhysicalValueType>().is_some()
+        || matches!(typ.to_string().as_str(), "int" | "Int" | "float" | "Float")
+}
+
+fn compare_values<'v>(a: Value<'v>, b: Value<'v>) -> anyhow::Result<std::cmp::Ordering> {
+    a.compare(b).map_err(starlark::Error::into_anyhow)
+}
+
+pub(crate) fn normalize_config_range<'v>(
+    name: &str,
+    typ: Value<'v>,
+    min: Option<Value<'v>>,
+    max: Option<Value<'v>>,
+    eval: &mut Evaluator<'v, '_, '_>,
+) -> anyhow::Result<ConfigRange<'v>> {
+    if min.is_none() && max.is_none() {
+        return Ok(ConfigRange::default());
+    }
+
+    if !config_range_type_supported(typ) {
+        anyhow::bail!(
+            "config `{name}` uses unsupported type for `min`/`max`: expected int, float, or physical value type"
+        );
+    }
+
+    let min = min
+        .map(|value| validate_or_convert(name, value, typ, eval))
+        .transpose()?;
+    let max = max
+        .map(|value| validate_or_convert(name, value, typ, eval))
+        .transpose()?;
+    if let (Some(min), Some(max)) = (min, max)
+        && compare_values(min, max)? == std::cmp::Ordering::Greater
+    {
+        anyhow::bail!(
+            "config `{name}` has `min` {} greater than `max` {}",
+            min.to_repr(),
+            max.to_repr()
+        );
+    }
+
+    Ok(ConfigRange { min, max })
+}
+
+pub(crate) fn validate_config_range<'v>(
+    name: &str,
+    value: Value<'v>,
+    range: &ConfigRange<'v>,
+) -> anyhow::Result<()> {
+    if range.is_empty() {
+        return Ok(());
+    }
+
+    let below = match range.min {
+        Some(min) => compare_values(value, min)? == std::cmp::Ordering::Less,
+        None => false,
+    };
+    let above = match range.max {
+        Some(max) => compare_values(value, max)? == std::cmp::Ordering::Greater,
+        None => false,
+    };
+    if !below && !above {
+        return Ok(());
+    }
+
+    let expected = match (range.min, range.max) {
+        (Some(min), Some(max)) => format!("between {} and {}", min.to_repr(), max.to_repr()),
+        (Some(min), None) => format!("at least {}", min.to_repr()),
+        (None, Some(max)) => format!("at most {}", max.to_repr()),
+        (None, None) => unreachable!(),
+    };
+    anyhow::bail!(
+        "invalid value for config `{name}`: got {}; expected {expected}",
+        value.to_repr()
+    );
+}
+
 pub(crate) fn normalize_config_default<'v>(
     name: &str,
     default: Option<Value<'v>>,
     typ: Value<'v>,
     allowed_values: Option<&[Value<'v>]>,
+    range: &ConfigRange<'v>,
     eval: &mut Evaluator<'v, '_, '_>,
 ) -> anyhow::Result<Option<Value<'v>>> {
     let Some(default) = default else {
@@ -1510,6 +1679,7 @@ pub(crate) fn normalize_config_default<'v>(
 
     let default = validate_or_convert(name, default, typ, eval)?;
     validate_allowed_config_value(name, default, allowed_values)?;
+    validate_config_range(name, default, range)?;
     Ok(Some(default))
 }
 
@@ -1811,7 +1981,12 @@ where
         // Get the parent context from the evaluator's ContextValue if available
         let parent_context = eval.eval_context().expect("expected eval context");
         let span = eval.call_stack_top_location().unwrap().resolve_span();
-        let output = parent_context.resolve_and_eval_module(&path, Some(span))?;
+        let output = match parent_context.session().profiler() {
+            Some(profiler) => profiler.nested(eval, || {
+                parent_context.resolve_and_eval_module(&path, Some(span))
+            })?,
+            None => parent_context.resolve_and_eval_module(&path, Some(span))?,
+        };
         let mut params: Vec<String> = vec!["name".to_string(), "properties".to_string()];
         let mut param_types: SmallMap<String, String> = SmallMap::new();
 
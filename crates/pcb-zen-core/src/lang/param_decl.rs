@@ -18,20 +18,23 @@ use crate::lang::{
 };
 
 use super::context::ContextValue;
+use super::deprecation::warn_deprecated_parameter;
 use super::interface::{
     FrozenInterfaceValue, InstancePrefix, InterfaceValue, instantiate_interface,
     unregister_template_owned_nets,
 };
 use super::module::{
-    DeclarationSite, MissingInputError, ParameterMetadataInput, current_declaration_site,
-    default_for_type, io_declaration_site, io_generated_default, normalize_allowed_values,
-    normalize_config_default, record_parameter_metadata, run_checks, validate_allowed_config_value,
+    ConfigRange, DeclarationSite, MissingInputError, ParameterMetadataInput,
+    current_declaration_site, default_for_type, io_declaration_site, io_generated_default,
+    normalize_allowed_values, normalize_config_default, normalize_config_range,
+    record_parameter_metadata, run_checks, validate_allowed_config_value, validate_config_range,
     validate_or_convert,
 };
 use super::net::{
     FrozenNetType, FrozenNetValue, NetInstantiateIntent, NetInstantiateOptions, NetType,
     NetTypeGen, NetValue,
 };
+use super::type_conversion::convert_container_input;
 
 #[derive(Debug, Clone, Trace, Allocative)]
 struct DeclArgs<'v> {
@@ -39,9 +42,13 @@ struct DeclArgs<'v> {
     checks: Option<Value<'v>>,
     default: Option<Value<'v>>,
     allowed: Option<Value<'v>>,
+    min: Option<Value<'v>>,
+    max: Option<Value<'v>>,
     optional: Option<bool>,
     help: Option<String>,
     direction: Option<IoDirection>,
+    /// Deprecation message, reported when a parent passes this parameter.
+    deprecated: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -90,6 +97,10 @@ impl ParamKind {
         matches!(self, ParamKind::Config)
     }
 
+    fn allows_range(self) -> bool {
+        matches!(self, ParamKind::Config)
+    }
+
     fn allows_direction(self) -> bool {
         matches!(self, ParamKind::Io)
     }
@@ -231,17 +242,23 @@ fn parse_decl_args<'v>(
     let mut default = None;
     let mut checks = None;
     let mut allowed = None;
+    let mut min = None;
+    let mut max = None;
     let mut optional = None;
     let mut help = None;
     let mut direction = None;
+    let mut deprecated = None;
 
     for (arg_name, value) in args.names_map()? {
         match arg_name.as_str() {
             "checks" => checks = none_if_none(value),
             "default" => default = none_if_none(value),
             "allowed" if kind.allows_allowed() => allowed = none_if_none(value),
+            "min" if kind.allows_range() => min = none_if_none(value),
+            "max" if kind.allows_range() => max = none_if_none(value),
             "optional" => optional = Some(unpack_bool_arg(value, function, "optional")?),
             "help" => help = unpack_optional_string_arg(value, function, "help")?,
+            "deprecated" => deprecated = unpack_optional_string_arg(value, function, "deprecated")?,
             "direction" if kind.allows_direction() => {
                 direction = IoDirection::parse_optional(
                     unpack_optional_string_arg(value, function, "direction")?.as_deref(),
@@ -296,9 +313,12 @@ fn parse_decl_args<'v>(
             checks: checks.or(positional_checks),
             default,
             allowed,
+            min,
+            max,
             optional,
             help,
             direction,
+            deprecated,
         },
     ))
 }
@@ -317,6 +337,12 @@ fn warn_deprecated_io_default(
     eval.add_diagnostic(diag);
 }
 
+fn warn_if_deprecated(name: &str, args: &DeclArgs<'_>, eval: &Evaluator<'_, '_, '_>) {
+    if let Some(message) = &args.deprecated {
+        warn_deprecated_parameter(eval, name, message);
+    }
+}
+
 fn note_missing_input(name: &str, eval: &mut Evaluator<'_, '_, '_>) {
     if let Some(ctx) = eval.context_value() {
         ctx.add_missing_input(name.to_owned());
@@ -393,17 +419,23 @@ fn resolve_config<'v>(
     };
     let allowed_values = normalize_allowed_values(name, args.typ, args.allowed, eval)
         .map_err(starlark::Error::from)?;
+    let range = normalize_config_range(name, args.typ, args.min, args.max, eval)
+        .map_err(starlark::Error::from)?;
     let default_value = normalize_config_default(
         name,
         args.default,
         args.typ,
         allowed_values.as_deref(),
+        &range,
         eval,
     )
     .map_err(starlark::Error::from)?;
     let is_optional = args.optional.unwrap_or(default_value.is_some());
 
     let value = if let Some(provided) = eval.request_input(name)? {
+        warn_if_deprecated(name, args, eval);
+        let provided = convert_container_input(name, provided, args.typ, eval)
+            .map_err(starlark::Error::from)?;
         convert_value(eval, provided)?
     } else if is_optional {
         default_value.unwrap_or_else(Value::new_none)
@@ -421,6 +453,8 @@ fn resolve_config<'v>(
             .copied()
         {
             first_allowed
+        } else if let Some(min) = range.min {
+            min
         } else {
             let generated = default_for_type(eval, args.typ)?;
             convert_value(eval, generated)?
@@ -430,6 +464,7 @@ fn resolve_config<'v>(
     if !value.is_none() {
         validate_allowed_config_value(name, value, allowed_values.as_deref())
             .map_err(starlark::Error::from)?;
+        validate_config_range(name, value, &range).map_err(starlark::Error::from)?;
     }
 
     finish_resolution(
@@ -440,6 +475,7 @@ fn resolve_config<'v>(
             optional: is_optional,
             default: default_value,
             allowed_values,
+            range,
             is_config: true,
             help: args.help.clone(),
             direction: None,
@@ -492,6 +528,7 @@ fn resolve_io<'v>(
     };
 
     let (value, metadata_default) = if let Some(provided) = eval.request_input(name)? {
+        warn_if_deprecated(name, args, eval);
         let converted = validate_or_convert(name, provided, normalized.typ, eval)?;
         let converted = register_provided_io_net(name, converted, normalized.typ, eval)?;
         for failure in run_implicit_checks(name, &normalized.implicit_checks, converted) {
@@ -520,6 +557,7 @@ fn resolve_io<'v>(
             optional: is_optional,
             default: metadata_default,
             allowed_values: None,
+            range: ConfigRange::default(),
             is_config: false,
             help: args.help.clone(),
             direction: args.direction,
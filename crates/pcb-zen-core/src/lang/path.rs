@@ -37,6 +37,20 @@ pub(crate) fn normalize_path_to_package_uri(path: &str, ctx: Option<&EvalContext
     path.to_owned()
 }
 
+/// Normalize a datasheet attribute value.
+///
+/// Blank values and KiCad's `~` placeholder become `None`, `file://` prefixes
+/// are dropped, http(s) URLs are kept verbatim, and everything else goes
+/// through [`normalize_path_to_package_uri`].
+pub(crate) fn normalize_datasheet(value: &str, ctx: Option<&EvalContext>) -> Option<String> {
+    let value = pcb_eda::usable_kicad_field_value(value.trim())?;
+    if value.starts_with("http://") || value.starts_with("https://") {
+        return Some(value.to_owned());
+    }
+    let path = value.strip_prefix("file://").unwrap_or(value);
+    Some(normalize_path_to_package_uri(path, ctx))
+}
+
 /// Resolve a non-URI path relative to `base_dir` and format it as `package://...`
 /// when possible.
 pub(crate) fn format_relative_path_as_package_uri(
@@ -0,0 +1,313 @@
+//! Starlark profiling across every module evaluation in a session, for
+//! `pcb build --eval-profile`.
+//!
+//! Each evaluation runs its own Starlark profiler. The resulting folded
+//! stacks are prefixed with the evaluated file as the root frame and
+//! concatenated, which flamegraph tools (`flamegraph.pl`, `inferno`) sum
+//! into one graph.
+//!
+//! `Module()` evaluates its target inline, so the parent's time profile also
+//! counts the child's evaluation under the `Module()` call. That time is
+//! subtracted from the parent frame, leaving it in the child's own stacks.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread::ThreadId;
+
+use starlark::eval::{Evaluator, ProfileMode};
+
+/// What a [`Profiler`] measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileKind {
+    /// Time spent in each function.
+    Time,
+    /// Bytes allocated by each function.
+    Alloc,
+}
+
+impl ProfileKind {
+    fn mode(self) -> ProfileMode {
+        match self {
+            ProfileKind::Time => ProfileMode::TimeFlame,
+            ProfileKind::Alloc => ProfileMode::HeapFlameAllocated,
+        }
+    }
+}
+
+/// Collects folded stacks from every evaluation in an
+/// [`EvalSession`](crate::lang::eval::EvalSession).
+#[derive(Debug)]
+pub struct Profiler {
+    kind: ProfileKind,
+    /// Prefix stripped from file frames.
+    root: PathBuf,
+    folded: Mutex<Vec<String>>,
+    /// Evaluations in progress on each thread, innermost last.
+    active: Mutex<HashMap<ThreadId, Vec<ActiveEval>>>,
+}
+
+/// Bookkeeping for an evaluation that has started but not finished.
+#[derive(Debug, Default)]
+struct ActiveEval {
+    /// Leaf frame of the call currently evaluating a nested module.
+    nested_call: Option<String>,
+    /// Leaf frame in the parent that this evaluation runs under.
+    parent_call: Option<String>,
+    /// Weight of finished nested evaluations, by the leaf frame that ran them.
+    nested_weight: HashMap<String, u64>,
+}
+
+/// Share of the total profile weight attributed to a function or file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileEntry {
+    pub name: String,
+    pub weight: u64,
+}
+
+/// Heaviest functions (by self weight) and files (by total weight).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProfileSummary {
+    pub total: u64,
+    pub functions: Vec<ProfileEntry>,
+    pub files: Vec<ProfileEntry>,
+}
+
+impl Profiler {
+    /// Create a profiler whose file frames are shown relative to `root`.
+    pub fn new(kind: ProfileKind, root: PathBuf) -> Self {
+        Self {
+            kind,
+            root,
+            folded: Mutex::new(Vec::new()),
+            active: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn kind(&self) -> ProfileKind {
+        self.kind
+    }
+
+    pub(crate) fn start(&self, eval: &mut Evaluator<'_, '_, '_>) {
+        if let Err(err) = eval.enable_profile(&self.kind.mode()) {
+            log::debug!("Failed to enable Starlark profiler: {err}");
+        }
+        let mut active = self.active.lock().unwrap();
+        let stack = active.entry(std::thread::current().id()).or_default();
+        let parent_call = stack
+            .last_mut()
+            .and_then(|parent| parent.nested_call.take());
+        stack.push(ActiveEval {
+            parent_call,
+            ..Default::default()
+        });
+    }
+
+    /// Run `nested`, which may evaluate another module, from the call on top
+    /// of `eval`'s stack, so the time it takes is charged to that module only.
+    pub(crate) fn nested<T>(&self, eval: &Evaluator<'_, '_, '_>, nested: impl FnOnce() -> T) -> T {
+        let call = eval
+            .call_stack()
+            .frames
+            .last()
+            .map(|frame| frame.name.clone());
+        self.with_current(|current| current.nested_call = call);
+        let result = nested();
+        self.with_current(|current| current.nested_call = None);
+        result
+    }
+
+    fn with_current(&self, f: impl FnOnce(&mut ActiveEval)) {
+        let mut active = self.active.lock().unwrap();
+        if let Some(current) = active
+            .get_mut(&std::thread::current().id())
+            .and_then(|stack| stack.last_mut())
+        {
+            f(current);
+        }
+    }
+
+    /// Record the profile of an evaluation of `source_path`.
+    pub(crate) fn finish(&self, eval: &mut Evaluator<'_, '_, '_>, source_path: &Path) {
+        let current = {
+            let mut active = self.active.lock().unwrap();
+            let stack = active.entry(std::thread::current().id()).or_default();
+            stack.pop().unwrap_or_default()
+        };
+        let folded = match eval.gen_profile().and_then(|data| data.r#gen()) {
+            Ok(folded) => folded,
+            Err(err) => {
+                log::debug!("Failed to collect Starlark profile: {err}");
+                return;
+            }
+        };
+
+        // Nested evaluations allocate on their own heaps, so only time
+        // profiles count them twice.
+        if self.kind == ProfileKind::Time
+            && let Some(call) = current.parent_call
+        {
+            let weight = total_weight(&folded);
+            self.with_current(|parent| *parent.nested_weight.entry(call).or_default() += weight);
+        }
+        let folded = subtract_nested(&folded, current.nested_weight);
+
+        let file = source_path
+            .strip_prefix(&self.root)
+            .unwrap_or(source_path)
+            .display()
+            .to_string()
+            .replace(';', "_");
+        let mut lines = self.folded.lock().unwrap();
+        for line in folded.lines().filter(|line| !line.trim().is_empty()) {
+            lines.push(format!("{file};{line}"));
+        }
+    }
+
+    /// All recorded stacks in folded format, one `frame;frame;... weight` per line.
+    pub fn folded(&self) -> String {
+        let lines = self.folded.lock().unwrap();
+        let mut out = lines.join("\n");
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The `limit` heaviest functions and files.
+    pub fn summary(&self, limit: usize) -> ProfileSummary {
+        summarize(&self.folded(), limit)
+    }
+}
+
+/// Split a folded line into its stack and weight.
+fn parse_line(line: &str) -> Option<(&str, u64)> {
+    let (stack, weight) = line.rsplit_once(' ')?;
+    Some((stack, weight.trim().parse().ok()?))
+}
+
+fn total_weight(folded: &str) -> u64 {
+    folded.lines().filter_map(parse_line).map(|(_, w)| w).sum()
+}
+
+/// Take `nested` weight off the stacks ending in the frame that ran it,
+/// heaviest stack first, dropping stacks left with no weight.
+fn subtract_nested(folded: &str, mut nested: HashMap<String, u64>) -> String {
+    if nested.is_empty() {
+        return folded.to_string();
+    }
+    let mut lines: Vec<(String, u64)> = folded
+        .lines()
+        .filter_map(parse_line)
+        .map(|(stack, weight)| (stack.to_string(), weight))
+        .collect();
+    let mut order: Vec<usize> = (0..lines.len()).collect();
+    order.sort_by(|&a, &b| lines[b].1.cmp(&lines[a].1));
+    for index in order {
+        let (stack, weight) = &mut lines[index];
+        let leaf = stack.rsplit(';').next().unwrap_or(stack);
+        if let Some(remaining) = nested.get_mut(leaf) {
+            let taken = (*remaining).min(*weight);
+            *weight -= taken;
+            *remaining -= taken;
+        }
+    }
+    lines
+        .into_iter()
+        .filter(|(_, weight)| *weight > 0)
+        .map(|(stack, weight)| format!("{stack} {weight}\n"))
+        .collect()
+}
+
+fn summarize(folded: &str, limit: usize) -> ProfileSummary {
+    let mut total = 0;
+    let mut functions: HashMap<&str, u64> = HashMap::new();
+    let mut files: HashMap<&str, u64> = HashMap::new();
+    for (stack, weight) in folded.lines().filter_map(parse_line) {
+        let mut frames = stack.split(';');
+        let Some(file) = frames.next() else {
+            continue;
+        };
+        total += weight;
+        *files.entry(file).or_default() += weight;
+        if let Some(function) = frames.next_back() {
+            *functions.entry(function).or_default() += weight;
+        }
+    }
+
+    let top = |map: HashMap<&str, u64>| {
+        let mut entries: Vec<ProfileEntry> = map
+            .into_iter()
+            .map(|(name, weight)| ProfileEntry {
+                name: name.to_string(),
+                weight,
+            })
+            .collect();
+        entries.sort_by(|a, b| b.weight.cmp(&a.weight).then_with(|| a.name.cmp(&b.name)));
+        entries.truncate(limit);
+        entries
+    };
+    ProfileSummary {
+        total,
+        functions: top(functions),
+        files: top(files),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subtracts_nested_module_time_from_the_calling_frame() {
+        let folded = "\
+build;Module 120
+build;Module 15
+build;Resistor 30
+";
+        let nested = HashMap::from([("Module".to_string(), 125)]);
+        assert_eq!(
+            subtract_nested(folded, nested),
+            "build;Module 10\nbuild;Resistor 30\n"
+        );
+        assert_eq!(total_weight(folded), 165);
+    }
+
+    #[test]
+    fn summarizes_self_weight_by_function_and_total_by_file() {
+        let folded = "\
+board.zen;build;Resistor 30
+board.zen;build 10
+lib/ldo.zen;Ldo;Resistor 50
+lib/ldo.zen;Ldo;Capacitor 20
+";
+        let summary = summarize(folded, 2);
+        assert_eq!(summary.total, 110);
+        assert_eq!(
+            summary.functions,
+            vec![
+                ProfileEntry {
+                    name: "Resistor".to_string(),
+                    weight: 80,
+                },
+                ProfileEntry {
+                    name: "Capacitor".to_string(),
+                    weight: 20,
+                },
+            ]
+        );
+        assert_eq!(
+            summary.files,
+            vec![
+                ProfileEntry {
+                    name: "lib/ldo.zen".to_string(),
+                    weight: 70,
+                },
+                ProfileEntry {
+                    name: "board.zen".to_string(),
+                    weight: 40,
+                },
+            ]
+        );
+    }
+}
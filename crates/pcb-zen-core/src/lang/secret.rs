@@ -0,0 +1,184 @@
+//! Sensitive root config() inputs (provisioning keys, license IDs, ...).
+//!
+//! Secret inputs reach the root module like any other config() value, but
+//! their values must never end up in build artifacts. After schematic
+//! conversion, [`redact_schematic`] replaces every attribute value that
+//! contains a secret and reports each one as a `config.secret_leak` error.
+//! Values recorded in module signatures are redacted without a diagnostic,
+//! since that is where the inputs themselves are stored.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use pcb_sch::{AttributeValue, Schematic};
+use starlark::errors::EvalSeverity;
+
+use crate::Diagnostic;
+
+/// Replacement text for redacted values.
+pub const REDACTED: &str = "<redacted>";
+
+/// Secret values shorter than this only match an attribute exactly, so that a
+/// short value like `1` doesn't flag every attribute containing that digit.
+const MIN_SUBSTRING_MATCH_LEN: usize = 4;
+
+/// Secret root inputs by config() name. `Debug` never prints the values.
+#[derive(Clone, Default, PartialEq)]
+pub struct SecretInputs {
+    values: BTreeMap<String, serde_json::Value>,
+}
+
+impl fmt::Debug for SecretInputs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.values.keys().map(|name| (name, REDACTED)))
+            .finish()
+    }
+}
+
+impl SecretInputs {
+    pub fn insert(&mut self, name: impl Into<String>, value: serde_json::Value) {
+        self.values.insert(name.into(), value);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = (&String, &serde_json::Value)> {
+        self.values.iter()
+    }
+
+    /// Name of the first secret whose value appears in `text`.
+    pub fn leaked_in(&self, text: &str) -> Option<&str> {
+        self.values.iter().find_map(|(name, value)| {
+            let secret = match value {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Null => return None,
+                other => other.to_string(),
+            };
+            let leaked = if secret.len() < MIN_SUBSTRING_MATCH_LEN {
+                text == secret
+            } else {
+                text.contains(&secret)
+            };
+            leaked.then_some(name.as_str())
+        })
+    }
+
+    /// `text`, or [`REDACTED`] if it contains a secret.
+    pub fn redact(&self, text: &str) -> String {
+        if self.leaked_in(text).is_some() {
+            REDACTED.to_string()
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Redact attributes of `schematic` that contain secret values, returning a
+/// `config.secret_leak` error for each.
+pub fn redact_schematic(schematic: &mut Schematic, secrets: &SecretInputs) -> Vec<Diagnostic> {
+    if secrets.is_empty() {
+        return Vec::new();
+    }
+
+    let mut diagnostics = Vec::new();
+    for (instance_ref, instance) in schematic.instances.iter_mut() {
+        for (attribute, value) in instance.attributes.iter_mut() {
+            let Some(name) = redact_attribute(value, secrets) else {
+                continue;
+            };
+            if attribute == crate::attrs::SIGNATURE {
+                continue;
+            }
+            let instance_path = instance_ref.instance_path.join(".");
+            let body = format!(
+                "Secret input `{name}` leaks into attribute `{attribute}` of `{instance_path}`; \
+                 the value was redacted"
+            );
+            diagnostics.push(Diagnostic::categorized(
+                &instance.type_ref.source_path.display().to_string(),
+                &body,
+                "config.secret_leak",
+                EvalSeverity::Error,
+            ));
+        }
+    }
+    diagnostics
+}
+
+/// Replace `value` (or the offending array element or JSON leaf) with
+/// [`REDACTED`] if it contains a secret, returning the secret's name.
+fn redact_attribute(value: &mut AttributeValue, secrets: &SecretInputs) -> Option<String> {
+    let text = match value {
+        AttributeValue::String(s) | AttributeValue::Port(s) => s.clone(),
+        AttributeValue::Number(n) => n.to_string(),
+        AttributeValue::Boolean(_) => return None,
+        AttributeValue::Json(json) => return redact_json(json, secrets),
+        AttributeValue::Array(items) => {
+            let mut leaked = None;
+            for item in items {
+                leaked = redact_attribute(item, secrets).or(leaked);
+            }
+            return leaked;
+        }
+    };
+    let name = secrets.leaked_in(&text)?.to_string();
+    *value = AttributeValue::String(REDACTED.to_string());
+    Some(name)
+}
+
+fn redact_json(value: &mut serde_json::Value, secrets: &SecretInputs) -> Option<String> {
+    let text = match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::Null | serde_json::Value::Bool(_) => return None,
+        serde_json::Value::Array(items) => {
+            let mut leaked = None;
+            for item in items {
+                leaked = redact_json(item, secrets).or(leaked);
+            }
+            return leaked;
+        }
+        serde_json::Value::Object(entries) => {
+            let mut leaked = None;
+            for item in entries.values_mut() {
+                leaked = redact_json(item, secrets).or(leaked);
+            }
+            return leaked;
+        }
+    };
+    let name = secrets.leaked_in(&text)?.to_string();
+    *value = serde_json::Value::String(REDACTED.to_string());
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn matches_long_secrets_as_substrings_and_short_ones_exactly() {
+        let mut secrets = SecretInputs::default();
+        secrets.insert("provisioning_key", json!("K3Y-abc"));
+        secrets.insert("license_id", json!(7));
+
+        assert_eq!(
+            secrets.leaked_in("MPN K3Y-abc rev"),
+            Some("provisioning_key")
+        );
+        assert_eq!(secrets.leaked_in("7"), Some("license_id"));
+        assert_eq!(secrets.leaked_in("0.7uF"), None);
+        assert_eq!(secrets.redact("K3Y-abc"), REDACTED);
+        assert_eq!(
+            format!("{secrets:?}"),
+            r#"{"license_id": "<redacted>", "provisioning_key": "<redacted>"}"#
+        );
+    }
+}
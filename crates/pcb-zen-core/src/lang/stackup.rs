@@ -310,10 +310,80 @@ pub struct DesignRules {
     pub netclasses: Vec<NetClass>,
 }
 
+/// Copper pour the layout should contain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZoneConfig {
+    pub net: String,
+    pub layer: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clearance: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_thickness: Option<f64>,
+}
+
+/// Mounting hole the layout should contain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoleConfig {
+    #[serde(rename = "ref")]
+    pub reference: String,
+    pub x: f64,
+    pub y: f64,
+    pub drill: f64,
+    /// Plated pad diameter; `None` for an unplated hole
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pad: Option<f64>,
+}
+
+/// Fiducial the layout should contain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FiducialConfig {
+    #[serde(rename = "ref")]
+    pub reference: String,
+    pub x: f64,
+    pub y: f64,
+    pub pad: f64,
+    pub mask: f64,
+    #[serde(default)]
+    pub local: bool,
+    #[serde(default = "default_fiducial_side")]
+    pub side: String,
+}
+
+fn default_fiducial_side() -> String {
+    "top".to_string()
+}
+
+/// Image logo the layout should contain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogoConfig {
+    #[serde(rename = "ref")]
+    pub reference: String,
+    pub image: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    #[serde(default = "default_logo_layer")]
+    pub layer: String,
+}
+
+fn default_logo_layer() -> String {
+    "F.SilkS".to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BoardConfig {
     pub design_rules: Option<DesignRules>,
     pub stackup: Option<Stackup>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zones: Option<Vec<ZoneConfig>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holes: Option<Vec<HoleConfig>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fiducials: Option<Vec<FiducialConfig>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logos: Option<Vec<LogoConfig>>,
     #[serde(default = "default_num_user_layers")]
     pub num_user_layers: usize,
 }
@@ -331,6 +401,26 @@ impl BoardConfig {
         Ok(board_config)
     }
 
+    /// Get all copper zones declared in this board config
+    pub fn zones(&self) -> &[ZoneConfig] {
+        self.zones.as_deref().unwrap_or(&[])
+    }
+
+    /// Get all mounting holes declared in this board config
+    pub fn holes(&self) -> &[HoleConfig] {
+        self.holes.as_deref().unwrap_or(&[])
+    }
+
+    /// Get all fiducials declared in this board config
+    pub fn fiducials(&self) -> &[FiducialConfig] {
+        self.fiducials.as_deref().unwrap_or(&[])
+    }
+
+    /// Get all logos declared in this board config
+    pub fn logos(&self) -> &[LogoConfig] {
+        self.logos.as_deref().unwrap_or(&[])
+    }
+
     /// Get all netclasses defined in this board config
     pub fn netclasses(&self) -> &[NetClass] {
         self.design_rules
@@ -0,0 +1,47 @@
+//! `requires_stdlib()`: declare the stdlib versions a file works with.
+//!
+//! The stdlib ships with the toolchain, so a mismatch means the board needs a
+//! different `pcb` release rather than a dependency update.
+
+use anyhow::Context;
+use semver::{Version, VersionReq};
+use starlark::environment::GlobalsBuilder;
+use starlark::starlark_module;
+use starlark::values::none::NoneType;
+
+use crate::stdlib::STDLIB_VERSION;
+
+/// Fail unless `version` satisfies the semver `requirement`, e.g. `">=0.4, <1"`.
+fn check_stdlib_requirement(requirement: &str, version: &str) -> anyhow::Result<()> {
+    let req = VersionReq::parse(requirement)
+        .with_context(|| format!("Invalid stdlib requirement {requirement:?}"))?;
+    let version = Version::parse(version).context("Invalid stdlib version")?;
+    anyhow::ensure!(
+        req.matches(&version),
+        "This file requires stdlib {requirement}, but the installed pcb toolchain ships stdlib \
+         {version}. Install a matching pcb release or update [workspace].pcb-version in pcb.toml."
+    );
+    Ok(())
+}
+
+#[starlark_module]
+pub(crate) fn stdlib_version_globals(builder: &mut GlobalsBuilder) {
+    /// Fail evaluation unless the bundled stdlib satisfies `requirement`.
+    fn requires_stdlib(#[starlark(require = pos)] requirement: &str) -> anyhow::Result<NoneType> {
+        check_stdlib_requirement(requirement, STDLIB_VERSION)?;
+        Ok(NoneType)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_requirement_against_version() {
+        check_stdlib_requirement(">=0.4, <1", "0.4.12").unwrap();
+        let err = check_stdlib_requirement(">=1.4, <2", "0.4.12").unwrap_err();
+        assert!(err.to_string().contains("requires stdlib >=1.4, <2"));
+        assert!(check_stdlib_requirement("1.x.y", "0.4.12").is_err());
+    }
+}
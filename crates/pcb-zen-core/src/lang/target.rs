@@ -0,0 +1,82 @@
+//! Build flags for design variants, read through the `target` global.
+//!
+//! Flags are string key/value pairs set in `[board.target]` of a board's
+//! `pcb.toml` or with `pcb build --target KEY=VALUE`. Every module of the
+//! build, including those loaded from dependencies, sees the same flags, so a
+//! shared design tree can adapt to a variant without threading config()
+//! parameters through each level:
+//!
+//! ```python
+//! if target.variant == "lite":
+//!     ...
+//! ```
+//!
+//! Reading a flag that isn't set gives `None`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use allocative::Allocative;
+use starlark::{
+    any::ProvidesStaticType,
+    collections::SmallMap,
+    starlark_simple_value,
+    values::{Freeze, Heap, NoSerialize, StarlarkValue, Trace, Value, starlark_value},
+};
+
+/// Name of the global holding the build flags.
+pub const TARGET_GLOBAL: &str = "target";
+
+#[derive(Clone, Debug, Default, Trace, ProvidesStaticType, NoSerialize, Allocative, Freeze)]
+pub struct TargetFlags {
+    flags: SmallMap<String, String>,
+}
+
+impl TargetFlags {
+    pub fn new(flags: &BTreeMap<String, String>) -> Self {
+        Self {
+            flags: flags
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for TargetFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "target(")?;
+        for (i, (key, value)) in self.flags.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{key} = {value:?}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+starlark_simple_value!(TargetFlags);
+
+#[starlark_value(type = "target")]
+impl<'v> StarlarkValue<'v> for TargetFlags
+where
+    Self: ProvidesStaticType<'v>,
+{
+    fn get_attr(&self, attr: &str, heap: Heap<'v>) -> Option<Value<'v>> {
+        Some(
+            self.flags
+                .get(attr)
+                .map(|value| heap.alloc_str(value).to_value())
+                .unwrap_or_else(Value::new_none),
+        )
+    }
+
+    fn has_attr(&self, attr: &str, _heap: Heap<'v>) -> bool {
+        self.flags.contains_key(attr)
+    }
+
+    fn dir_attr(&self) -> Vec<String> {
+        self.flags.keys().cloned().collect()
+    }
+}
@@ -204,6 +204,7 @@ pub fn test_bench_globals(builder: &mut GlobalsBuilder) {
                 properties: None,
                 component_modifiers: combined_modifiers.clone(),
                 provided_names: Vec::new(),
+                forwarded_inputs: Default::default(),
                 call_site_path: source_path.clone(),
                 call_site_span: call_span.unwrap_or_default(),
                 call_stack: call_stack.clone(),
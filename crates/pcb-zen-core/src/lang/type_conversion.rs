@@ -1,12 +1,15 @@
 use pcb_sch::PhysicalUnit;
 use pcb_sch::physical::{PhysicalUnitDims, PhysicalValue, PhysicalValueType};
 use starlark::eval::Evaluator;
+use starlark::values::dict::{AllocDict, DictRef};
+use starlark::values::list::ListRef;
 use starlark::values::{Value, ValueLike, float::StarlarkFloat, typing::TypeCompiled};
 
 use crate::lang::r#enum::{EnumType, EnumValue};
 use crate::lang::net::{
     FrozenNetType, FrozenNetValue, NetType, NetValue, compatible_net_type_view,
 };
+use crate::lang::type_info::TypeInfo;
 
 fn has_type_name<'v>(typ: Value<'v>, names: &[&str]) -> bool {
     names.contains(&typ.get_type()) || names.contains(&typ.to_string().as_str())
@@ -218,3 +221,119 @@ pub(crate) fn try_implicit_type_conversion<'v>(
 
     Ok(None)
 }
+
+/// Convert the elements of a list or dict input to a parameterized container
+/// type such as `list[int]` or `dict[str, Voltage]`.
+///
+/// JSON inputs arrive as plain lists, dicts, strings, and numbers, so each
+/// element gets the scalar conversions a top-level input would. A mismatch is
+/// reported against the path of the offending element, e.g. `rails[1]` or
+/// `limits["vin"]`. Other types are returned unchanged.
+pub(crate) fn convert_container_input<'v>(
+    name: &str,
+    value: Value<'v>,
+    typ: Value<'v>,
+    eval: &mut Evaluator<'v, '_, '_>,
+) -> anyhow::Result<Value<'v>> {
+    // Bare `list` and `dict` place no constraint on their elements.
+    if !typ.to_string().contains('[') {
+        return Ok(value);
+    }
+    let info = TypeInfo::from_value(typ);
+    if !matches!(info, TypeInfo::List { .. } | TypeInfo::Dict { .. }) {
+        return Ok(value);
+    }
+    convert_to_type_info(name, value, &info, eval)
+}
+
+fn convert_to_type_info<'v>(
+    path: &str,
+    value: Value<'v>,
+    info: &TypeInfo,
+    eval: &mut Evaluator<'v, '_, '_>,
+) -> anyhow::Result<Value<'v>> {
+    let converted = match info {
+        TypeInfo::List { element } => match ListRef::from_value(value) {
+            Some(list) => {
+                let items: Vec<Value<'v>> = list.iter().collect();
+                let mut converted = Vec::with_capacity(items.len());
+                for (i, item) in items.into_iter().enumerate() {
+                    converted.push(convert_to_type_info(
+                        &format!("{path}[{i}]"),
+                        item,
+                        element,
+                        eval,
+                    )?);
+                }
+                Some(eval.heap().alloc(converted))
+            }
+            None => None,
+        },
+        TypeInfo::Dict { key, value: item } => match DictRef::from_value(value) {
+            Some(dict) => {
+                let entries: Vec<(Value<'v>, Value<'v>)> = dict.iter().collect();
+                drop(dict);
+                let mut converted = Vec::with_capacity(entries.len());
+                for (k, v) in entries {
+                    let entry_path = format!("{path}[{}]", k.to_repr());
+                    converted.push((
+                        convert_to_type_info(&entry_path, k, key, eval)?,
+                        convert_to_type_info(&entry_path, v, item, eval)?,
+                    ));
+                }
+                Some(eval.heap().alloc(AllocDict(converted)))
+            }
+            None => None,
+        },
+        TypeInfo::String => value.unpack_str().map(|_| value),
+        TypeInfo::Int => value.unpack_i32().map(|_| value).or_else(|| {
+            value
+                .unpack_str()
+                .and_then(|raw| raw.parse::<i32>().ok())
+                .map(|parsed| eval.heap().alloc(parsed))
+        }),
+        TypeInfo::Float => {
+            if value.downcast_ref::<StarlarkFloat>().is_some() {
+                Some(value)
+            } else if let Some(i) = value.unpack_i32() {
+                Some(eval.heap().alloc(StarlarkFloat(i as f64)))
+            } else {
+                value
+                    .unpack_str()
+                    .and_then(|raw| raw.parse::<f64>().ok())
+                    .map(|parsed| eval.heap().alloc(StarlarkFloat(parsed)))
+            }
+        }
+        TypeInfo::Bool => value.unpack_bool().map(Value::new_bool).or_else(|| {
+            value.unpack_str().and_then(|raw| {
+                if raw.eq_ignore_ascii_case("true") {
+                    Some(Value::new_bool(true))
+                } else if raw.eq_ignore_ascii_case("false") {
+                    Some(Value::new_bool(false))
+                } else {
+                    None
+                }
+            })
+        }),
+        TypeInfo::Unknown { type_name } => match physical_unit_from_type_name(type_name) {
+            Some(unit)
+                if value
+                    .downcast_ref::<PhysicalValue>()
+                    .is_some_and(|v| v.unit == unit) =>
+            {
+                Some(value)
+            }
+            Some(unit) => try_physical_conversion_for_unit(value, unit, eval)?,
+            // Leave other element types to the final type check.
+            None => Some(value),
+        },
+        TypeInfo::Net | TypeInfo::Enum { .. } | TypeInfo::Interface { .. } => Some(value),
+    };
+
+    converted.ok_or_else(|| {
+        anyhow::anyhow!(
+            "Input '{path}' has wrong type: expected {info}, got {}",
+            value.to_repr()
+        )
+    })
+}
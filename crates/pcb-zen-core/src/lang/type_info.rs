@@ -100,73 +100,98 @@ impl TypeInfo {
 
         // Check if it's a TypeType (like str, int, float constructors)
         if TypeType::unpack_value_opt(value).is_some() {
-            // Use the string representation to determine the type
-            let type_str = value.to_string();
-            match type_str.as_str() {
-                "str" => return TypeInfo::String,
-                "int" => return TypeInfo::Int,
-                "float" => return TypeInfo::Float,
-                "bool" => return TypeInfo::Bool,
-                "list" => {
-                    return TypeInfo::List {
-                        element: Box::new(TypeInfo::Unknown {
-                            type_name: "any".to_string(),
-                        }),
-                    };
-                }
-                "dict" => {
-                    return TypeInfo::Dict {
-                        key: Box::new(TypeInfo::String),
-                        value: Box::new(TypeInfo::Unknown {
-                            type_name: "any".to_string(),
-                        }),
-                    };
-                }
-                _ => {
-                    // Unknown type constructor
-                    return TypeInfo::Unknown {
-                        type_name: type_str,
-                    };
-                }
-            }
+            return Self::from_type_repr(&value.to_string());
         }
 
         // Check for built-in types by examining the type
         match type_name {
-            "type" => {
-                // This is a type constructor like str, int, etc.
-                let type_str = value.to_string();
-                match type_str.as_str() {
-                    "str" => TypeInfo::String,
-                    "int" => TypeInfo::Int,
-                    "float" => TypeInfo::Float,
-                    "bool" => TypeInfo::Bool,
-                    "list" => TypeInfo::List {
-                        element: Box::new(TypeInfo::Unknown {
-                            type_name: "any".to_string(),
-                        }),
-                    },
-                    "dict" => TypeInfo::Dict {
-                        key: Box::new(TypeInfo::String),
-                        value: Box::new(TypeInfo::Unknown {
-                            type_name: "any".to_string(),
-                        }),
-                    },
-                    _ => TypeInfo::Unknown {
-                        type_name: type_str,
-                    },
-                }
-            }
-            _ => {
-                // For any other type, return Unknown with the type name
-                TypeInfo::Unknown {
-                    type_name: type_name.to_string(),
-                }
+            // This is a type constructor like str, int, etc.
+            "type" => Self::from_type_repr(&value.to_string()),
+            // For any other type, return Unknown with the type name
+            _ => TypeInfo::Unknown {
+                type_name: type_name.to_string(),
+            },
+        }
+    }
+
+    /// Parse the display form of a Starlark type, e.g. `list[dict[str, int]]`.
+    fn from_type_repr(repr: &str) -> Self {
+        let any = || {
+            Box::new(TypeInfo::Unknown {
+                type_name: "any".to_string(),
+            })
+        };
+        let repr = repr.trim();
+        match repr {
+            "str" => return TypeInfo::String,
+            "int" => return TypeInfo::Int,
+            "float" => return TypeInfo::Float,
+            "bool" => return TypeInfo::Bool,
+            "list" => return TypeInfo::List { element: any() },
+            "dict" => {
+                return TypeInfo::Dict {
+                    key: Box::new(TypeInfo::String),
+                    value: any(),
+                };
             }
+            _ => {}
+        }
+
+        if let Some(element) = repr
+            .strip_prefix("list[")
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            return TypeInfo::List {
+                element: Box::new(Self::from_type_repr(element)),
+            };
+        }
+        if let Some((key, value)) = repr
+            .strip_prefix("dict[")
+            .and_then(|rest| rest.strip_suffix(']'))
+            .and_then(split_top_level_comma)
+        {
+            return TypeInfo::Dict {
+                key: Box::new(Self::from_type_repr(key)),
+                value: Box::new(Self::from_type_repr(value)),
+            };
+        }
+
+        TypeInfo::Unknown {
+            type_name: repr.to_string(),
         }
     }
 }
 
+impl std::fmt::Display for TypeInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeInfo::String => write!(f, "str"),
+            TypeInfo::Int => write!(f, "int"),
+            TypeInfo::Float => write!(f, "float"),
+            TypeInfo::Bool => write!(f, "bool"),
+            TypeInfo::List { element } => write!(f, "list[{element}]"),
+            TypeInfo::Dict { key, value } => write!(f, "dict[{key}, {value}]"),
+            TypeInfo::Net => write!(f, "Net"),
+            TypeInfo::Enum { name, .. } | TypeInfo::Interface { name, .. } => write!(f, "{name}"),
+            TypeInfo::Unknown { type_name } => write!(f, "{type_name}"),
+        }
+    }
+}
+
+/// Split `key, value` at the comma outside any brackets.
+fn split_top_level_comma(repr: &str) -> Option<(&str, &str)> {
+    let mut depth = 0usize;
+    for (i, c) in repr.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => return Some((&repr[..i], &repr[i + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
 /// Parameter information with structured type
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ParameterInfo {
@@ -183,6 +208,12 @@ pub struct ParameterInfo {
     pub allowed_values: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allowed_display: Option<Vec<String>>,
+    /// Inclusive `min=` bound of a numeric config, as displayed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_display: Option<String>,
+    /// Inclusive `max=` bound of a numeric config, as displayed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_display: Option<String>,
     pub help: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub direction: Option<IoDirection>,
@@ -202,6 +233,12 @@ impl std::fmt::Debug for ParameterInfo {
         if self.allowed_display.is_some() {
             debug.field("allowed_display", &self.allowed_display);
         }
+        if self.min_display.is_some() {
+            debug.field("min_display", &self.min_display);
+        }
+        if self.max_display.is_some() {
+            debug.field("max_display", &self.max_display);
+        }
         debug.field("help", &self.help);
         if let Some(direction) = &self.direction {
             debug.field("direction", direction);
@@ -0,0 +1,186 @@
+//! Lints for names a module declares but never uses.
+//!
+//! - `load.unused`: a symbol imported with `load()` is never referenced.
+//! - `module.config.unused`: a `config()` parameter is assigned to a name that
+//!   is never referenced, so setting it has no effect.
+//!
+//! Like the style lints these are reported as advice, which editors show but
+//! builds do not; raise them with `[lints]` (e.g. `"load.unused" = "warn"`).
+//! Unconnected `io()` parameters are reported separately as `module.io.unused`
+//! once the netlist is known. Names starting with `_` are exempt.
+
+use std::collections::HashSet;
+
+use starlark::codemap::CodeMap;
+use starlark::errors::EvalSeverity;
+use starlark::syntax::AstModule;
+use starlark::syntax::ast::{AssignTargetP, AstExpr, ExprP, LoadArgP, StmtP};
+use starlark_syntax::syntax::{module::AstModuleFields, top_level_stmts::top_level_stmts};
+
+use crate::Diagnostic;
+
+/// Diagnostic category for `load()` symbols that are never referenced.
+pub const UNUSED_LOAD: &str = "load.unused";
+
+/// Diagnostic category for `config()` parameters that are never referenced.
+pub const UNUSED_CONFIG: &str = "module.config.unused";
+
+pub(crate) fn ast_unused_lints(ast: &AstModule) -> Vec<Diagnostic> {
+    let path = ast.codemap().filename();
+    let referenced = referenced_identifiers(ast);
+    let top_level = top_level_stmts(ast.statement());
+    let mut diagnostics = Vec::new();
+
+    for stmt in &top_level {
+        match &stmt.node {
+            StmtP::Load(load) => {
+                let unused: Vec<&LoadArgP<_>> = load
+                    .args
+                    .iter()
+                    .filter(|arg| is_unused(&arg.local.node.ident, &referenced))
+                    .collect();
+                let remove_statement = unused.len() == load.args.len();
+                for arg in unused {
+                    let name = &arg.local.node.ident;
+                    let fix = if remove_statement {
+                        "remove the load() statement".to_string()
+                    } else {
+                        format!("remove '{name}' from the load() statement")
+                    };
+                    diagnostics.push(unused_diagnostic(
+                        format!(
+                            "'{name}' is loaded from \"{}\" but never used; {fix}",
+                            load.module.node
+                        ),
+                        UNUSED_LOAD,
+                        ast.codemap(),
+                        arg.local.span,
+                        path,
+                    ));
+                }
+            }
+            StmtP::Assign(assign) => {
+                let AssignTargetP::Identifier(ident) = &assign.lhs.node else {
+                    continue;
+                };
+                if is_config_call(&assign.rhs) && is_unused(&ident.node.ident, &referenced) {
+                    diagnostics.push(unused_diagnostic(
+                        format!(
+                            "config() parameter '{}' is never used; remove it or use its value",
+                            ident.node.ident
+                        ),
+                        UNUSED_CONFIG,
+                        ast.codemap(),
+                        ident.span,
+                        path,
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    diagnostics
+}
+
+pub(crate) fn is_ast_unused_diagnostic(diagnostic: &Diagnostic) -> bool {
+    diagnostic
+        .downcast_error_ref::<crate::lang::error::CategorizedDiagnostic>()
+        .is_some_and(|categorized| {
+            categorized.kind == UNUSED_LOAD || categorized.kind == UNUSED_CONFIG
+        })
+}
+
+fn is_unused(name: &str, referenced: &HashSet<&str>) -> bool {
+    !name.starts_with('_') && !referenced.contains(name)
+}
+
+/// Every identifier read anywhere in the module, including nested functions.
+fn referenced_identifiers(ast: &AstModule) -> HashSet<&str> {
+    fn visit<'a>(expr: &'a AstExpr, names: &mut HashSet<&'a str>) {
+        if let ExprP::Identifier(ident) = &expr.node {
+            names.insert(ident.node.ident.as_str());
+        }
+        expr.visit_expr(|child| visit(child, names));
+    }
+
+    let mut names = HashSet::new();
+    ast.statement().visit_expr(|expr| visit(expr, &mut names));
+    names
+}
+
+fn is_config_call(expr: &AstExpr) -> bool {
+    matches!(
+        &expr.node,
+        ExprP::Call(fun, _) if matches!(&fun.node, ExprP::Identifier(ident) if ident.node.ident == "config")
+    )
+}
+
+fn unused_diagnostic(
+    message: String,
+    kind: &str,
+    codemap: &CodeMap,
+    span: starlark::codemap::Span,
+    path: &str,
+) -> Diagnostic {
+    Diagnostic::categorized(path, &message, kind, EvalSeverity::Advice)
+        .with_span(Some(codemap.file_span(span).resolve_span()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starlark::syntax::Dialect;
+
+    fn lint(source: &str) -> Vec<(String, String)> {
+        let ast = AstModule::parse("test.zen", source.to_owned(), &Dialect::Extended).unwrap();
+        ast_unused_lints(&ast)
+            .into_iter()
+            .map(|diag| {
+                let kind = diag
+                    .downcast_error_ref::<crate::lang::error::CategorizedDiagnostic>()
+                    .unwrap()
+                    .kind
+                    .clone();
+                (kind, diag.body)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reports_unused_loads_and_config() {
+        let diagnostics = lint(
+            r#"load("parts.zen", "Resistor", "Capacitor")
+load("nets.zen", "Power")
+load("util.zen", _helper = "helper")
+
+gain = config("gain", int, default = 1)
+enable = config("enable", bool, default = False)
+_spare = config("spare", int, default = 0)
+
+def make(n = gain):
+    return Resistor(n)
+
+make()
+"#,
+        );
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    UNUSED_LOAD.to_string(),
+                    "'Capacitor' is loaded from \"parts.zen\" but never used; remove 'Capacitor' from the load() statement".to_string()
+                ),
+                (
+                    UNUSED_LOAD.to_string(),
+                    "'Power' is loaded from \"nets.zen\" but never used; remove the load() statement".to_string()
+                ),
+                (
+                    UNUSED_CONFIG.to_string(),
+                    "config() parameter 'enable' is never used; remove it or use its value"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -6,6 +6,7 @@ use std::{
 
 use semver::Version;
 
+pub mod cancel;
 pub mod config;
 pub mod convert;
 pub mod diagnostics;
@@ -15,6 +16,9 @@ pub mod graph;
 pub mod lang;
 pub mod load_spec;
 mod moved;
+pub mod net_names;
+pub mod net_registry;
+pub mod parametric_footprint;
 pub mod passes;
 pub mod resolution;
 pub mod stdlib;
@@ -133,8 +137,8 @@ pub use lang::error::SuppressedDiagnostics;
 pub use lang::eval::{EvalContext, EvalContextConfig, EvalOutput};
 pub use load_spec::LoadSpec;
 pub use passes::{
-    AggregatePass, CommentSuppressPass, FilterHiddenPass, JsonExportPass, LspFilterPass,
-    PromotePass, SortPass, StylePromotePass, SuppressPass,
+    AggregatePass, CommentSuppressPass, FilterHiddenPass, JsonExportPass, LintLevelPass,
+    LspFilterPass, PromotePass, SortPass, StylePromotePass, SuppressPass,
 };
 
 // Re-export file provider types
@@ -158,6 +162,33 @@ pub struct DirEntry {
     pub is_symlink: bool,
 }
 
+/// Size and change stamp of a file, for detecting changes without re-reading
+/// it. Two snapshots of an unchanged file compare equal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileMetadata {
+    /// Size in bytes.
+    pub len: u64,
+    /// Last modification time, when the provider tracks one.
+    pub modified: Option<std::time::SystemTime>,
+    /// Hash of the contents, for providers without modification times (in
+    /// memory, WASM bundles, editor buffers). Only stable within one process.
+    pub content_hash: Option<u64>,
+}
+
+impl FileMetadata {
+    /// Metadata of in-memory `contents`, stamped with a content hash.
+    pub fn from_contents(contents: &str) -> Self {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+        let mut hasher = DefaultHasher::new();
+        contents.hash(&mut hasher);
+        Self {
+            len: contents.len() as u64,
+            modified: None,
+            content_hash: Some(hasher.finish()),
+        }
+    }
+}
+
 pub trait FileProvider: Send + Sync {
     /// Read the contents of a file at the given path
     fn read_file(&self, path: &std::path::Path) -> Result<String, FileProviderError>;
@@ -207,6 +238,20 @@ pub trait FileProvider: Send + Sync {
     fn cache_dir(&self) -> std::path::PathBuf {
         std::path::PathBuf::new()
     }
+
+    /// Size and change stamp of a file. The default implementation reads
+    /// the file and hashes its contents, which suits providers that hold
+    /// files in memory; providers backed by a filesystem should return the
+    /// modification time instead.
+    fn metadata(&self, path: &std::path::Path) -> Result<FileMetadata, FileProviderError> {
+        Ok(FileMetadata::from_contents(&self.read_file(path)?))
+    }
+
+    /// Write `contents` to `path`, creating parent directories as needed.
+    /// Providers are read-only unless they override this.
+    fn write_file(&self, path: &std::path::Path, _contents: &str) -> Result<(), FileProviderError> {
+        Err(FileProviderError::PermissionDenied(path.to_path_buf()))
+    }
 }
 
 /// Blanket implementation of FileProvider for Arc<T> where T: FileProvider
@@ -251,6 +296,14 @@ impl<T: FileProvider + ?Sized> FileProvider for Arc<T> {
     fn cache_dir(&self) -> std::path::PathBuf {
         (**self).cache_dir()
     }
+
+    fn metadata(&self, path: &std::path::Path) -> Result<FileMetadata, FileProviderError> {
+        (**self).metadata(path)
+    }
+
+    fn write_file(&self, path: &std::path::Path, contents: &str) -> Result<(), FileProviderError> {
+        (**self).write_file(path, contents)
+    }
 }
 
 #[derive(Debug, Clone, thiserror::Error)]
@@ -431,6 +484,48 @@ impl FileProvider for DefaultFileProvider {
             .expect("Cannot determine home directory")
             .join(".pcb/cache")
     }
+
+    fn metadata(&self, path: &std::path::Path) -> Result<FileMetadata, FileProviderError> {
+        let metadata = std::fs::metadata(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => FileProviderError::NotFound(path.to_path_buf()),
+            std::io::ErrorKind::PermissionDenied => {
+                FileProviderError::PermissionDenied(path.to_path_buf())
+            }
+            _ => FileProviderError::IoError(e.to_string()),
+        })?;
+        Ok(FileMetadata {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+            content_hash: None,
+        })
+    }
+
+    fn write_file(&self, path: &std::path::Path, contents: &str) -> Result<(), FileProviderError> {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let io_error = |e: std::io::Error| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => {
+                FileProviderError::PermissionDenied(path.to_path_buf())
+            }
+            _ => FileProviderError::IoError(e.to_string()),
+        };
+        let dir = path.parent().unwrap_or(std::path::Path::new("."));
+        std::fs::create_dir_all(dir).map_err(io_error)?;
+        // Evaluations run in parallel: write to a unique temp file and rename
+        // so readers never see a partial file.
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let temp = dir.join(format!(
+            ".{file_name}.{}.{}.tmp",
+            std::process::id(),
+            TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::write(&temp, contents).map_err(io_error)?;
+        std::fs::rename(&temp, path).map_err(|e| {
+            let _ = std::fs::remove_file(&temp);
+            io_error(e)
+        })
+    }
 }
 
 /// Information about a package alias including its target and source
@@ -453,6 +548,9 @@ pub(crate) struct ResolveContext<'a> {
     // Resolution history - specs get pushed as they're resolved further
     // Index 0 = original spec, later indices = progressively resolved specs
     pub spec_history: Vec<LoadSpec>,
+
+    // Steps taken so far, only recorded when tracing was requested
+    pub trace: Option<Vec<ResolveStep>>,
 }
 
 impl<'a> ResolveContext<'a> {
@@ -466,6 +564,14 @@ impl<'a> ResolveContext<'a> {
             file_provider,
             current_file,
             spec_history: vec![load_spec],
+            trace: None,
+        }
+    }
+
+    /// Record a resolution step if this context is being traced
+    pub fn record(&mut self, step: impl FnOnce() -> ResolveStep) {
+        if let Some(trace) = &mut self.trace {
+            trace.push(step());
         }
     }
 
@@ -497,6 +603,78 @@ impl<'a> ResolveContext<'a> {
     }
 }
 
+/// Where a dependency's files are read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencySource {
+    /// A package in the current workspace
+    Workspace,
+    /// A copy checked into the workspace `vendor/` directory
+    Vendored,
+    /// A remote package served from the package cache
+    Cache,
+    /// The toolchain stdlib, or its `[patch]` override
+    Stdlib,
+}
+
+impl std::fmt::Display for DependencySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DependencySource::Workspace => "workspace package",
+            DependencySource::Vendored => "vendor hit",
+            DependencySource::Cache => "vendor miss, served from cache",
+            DependencySource::Stdlib => "stdlib",
+        })
+    }
+}
+
+/// One step of resolving a load path, in the order it was taken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveStep {
+    /// A `package://` URI mapped to a path on disk
+    PackageUri { uri: String, path: PathBuf },
+    /// An `@alias` expanded to the URL it stands for
+    Alias { alias: String, url: String },
+    /// A relative path joined onto the loading file's directory
+    Relative { path: PathBuf },
+    /// A relative path that leaves its package, rewritten as a package URL
+    PackageBoundary { url: String },
+    /// A URL matched against the loading package's declared dependencies
+    Dependency {
+        url: String,
+        dep_url: String,
+        root: PathBuf,
+        source: DependencySource,
+    },
+    /// The final path on disk
+    Resolved { path: PathBuf },
+}
+
+impl std::fmt::Display for ResolveStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResolveStep::PackageUri { uri, path } => {
+                write!(f, "package URI {uri} -> {}", path.display())
+            }
+            ResolveStep::Alias { alias, url } => write!(f, "alias @{alias} -> {url}"),
+            ResolveStep::Relative { path } => write!(f, "relative path -> {}", path.display()),
+            ResolveStep::PackageBoundary { url } => {
+                write!(f, "leaves the current package -> {url}")
+            }
+            ResolveStep::Dependency {
+                url,
+                dep_url,
+                root,
+                source,
+            } => write!(
+                f,
+                "{url} matches dependency {dep_url} ({source}) at {}",
+                root.display()
+            ),
+            ResolveStep::Resolved { path } => write!(f, "resolved -> {}", path.display()),
+        }
+    }
+}
+
 /// File extension constants and utilities
 pub mod file_extensions {
     use std::ffi::OsStr;
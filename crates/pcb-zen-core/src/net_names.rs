@@ -0,0 +1,272 @@
+//! Net name checks run on each board before its netlist reaches layout.
+//!
+//! KiCad accepts almost any net name in a netlist but unescapes `{slash}`-style
+//! tokens and trims whitespace while importing it, so some names fail or merge
+//! during layout sync. Those problems are reported at build time instead:
+//!
+//! - `net.name.invalid`: names with control characters or leading/trailing
+//!   whitespace.
+//! - `net.name.collision`: distinct nets KiCad would import under one name.
+//! - `net.name.case`: distinct nets whose names differ only in case, which
+//!   case-insensitive tools such as Allegro merge.
+//! - `net.name.reserved`: names ruled out by `[workspace.net-names]`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use pcb_sch::{Net, Schematic};
+use starlark::errors::EvalSeverity;
+
+use crate::Diagnostic;
+use crate::config::NetNamesConfig;
+
+/// KiCad escape tokens and the characters they stand for.
+const KICAD_ESCAPES: &[(&str, &str)] = &[
+    ("{slash}", "/"),
+    ("{backslash}", "\\"),
+    ("{dblquote}", "\""),
+    ("{quote}", "'"),
+    ("{lt}", "<"),
+    ("{gt}", ">"),
+    ("{bar}", "|"),
+    ("{colon}", ":"),
+    ("{comma}", ","),
+    ("{space}", " "),
+    ("{tab}", "\t"),
+    ("{return}", "\n"),
+    ("{dollar}", "$"),
+    ("{brace}", "{"),
+];
+
+/// Flag net names of `schematic` that KiCad rejects or merges, or that
+/// `config` rules out.
+pub fn check_net_names(
+    config: &NetNamesConfig,
+    schematic: &Schematic,
+    source_path: &str,
+) -> Vec<Diagnostic> {
+    let mut nets: Vec<&Net> = schematic
+        .nets
+        .values()
+        .filter(|net| net.kind != "NotConnected" && !net.name.is_empty())
+        .collect();
+    nets.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let diagnostic = |message: String, kind: &str, severity| {
+        Diagnostic::categorized(source_path, &message, kind, severity)
+    };
+    let mut diagnostics = Vec::new();
+
+    for net in &nets {
+        if let Some(reason) = invalid_reason(&net.name) {
+            diagnostics.push(diagnostic(
+                format!("Net name {:?} {reason}", net.name),
+                "net.name.invalid",
+                EvalSeverity::Error,
+            ));
+        }
+    }
+
+    let mut by_kicad_name: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for net in &nets {
+        by_kicad_name
+            .entry(kicad_name(&net.name))
+            .or_default()
+            .push(&net.name);
+    }
+    let mut by_folded_name: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for (key, names) in &by_kicad_name {
+        if names.len() > 1 {
+            diagnostics.push(diagnostic(
+                format!("Nets {} become one net in KiCad", quoted(names)),
+                "net.name.collision",
+                EvalSeverity::Error,
+            ));
+        }
+        by_folded_name
+            .entry(key.to_uppercase())
+            .or_default()
+            .extend(names);
+    }
+    for names in by_folded_name.values().filter(|names| {
+        names
+            .iter()
+            .map(|name| kicad_name(name))
+            .collect::<BTreeSet<_>>()
+            .len()
+            > 1
+    }) {
+        diagnostics.push(diagnostic(
+            format!(
+                "Nets {} differ only in case; case-insensitive tools merge them",
+                quoted(names)
+            ),
+            "net.name.case",
+            EvalSeverity::Warning,
+        ));
+    }
+
+    for net in &nets {
+        let local = local_name(&net.name);
+        if config.reserved.iter().any(|reserved| reserved == local) {
+            diagnostics.push(diagnostic(
+                format!(
+                    "Net '{}' uses '{local}', which [workspace.net-names] reserves",
+                    net.name
+                ),
+                "net.name.reserved",
+                EvalSeverity::Warning,
+            ));
+        } else if let Some(allowed) = config.allowed.get(&net.kind)
+            && !allowed.iter().any(|name| name == local)
+        {
+            diagnostics.push(diagnostic(
+                format!(
+                    "{} net '{}' must be named one of {} per [workspace.net-names]",
+                    net.kind,
+                    net.name,
+                    allowed.join(", ")
+                ),
+                "net.name.reserved",
+                EvalSeverity::Warning,
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+fn invalid_reason(name: &str) -> Option<&'static str> {
+    if name.chars().any(char::is_control) {
+        Some("contains a control character")
+    } else if name.trim() != name {
+        Some("has leading or trailing whitespace, which KiCad strips")
+    } else {
+        None
+    }
+}
+
+/// Name KiCad gives a net after importing it from a netlist.
+fn kicad_name(name: &str) -> String {
+    let mut unescaped = String::with_capacity(name.len());
+    let mut rest = name;
+    while let Some(start) = rest.find('{') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match KICAD_ESCAPES
+            .iter()
+            .find(|(token, _)| rest.starts_with(token))
+        {
+            Some((token, text)) => {
+                unescaped.push_str(text);
+                rest = &rest[token.len()..];
+            }
+            None => {
+                unescaped.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped.trim().to_string()
+}
+
+fn quoted(names: &[&str]) -> String {
+    names
+        .iter()
+        .map(|name| format!("'{name}'"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Name of a net within the module declaring it.
+fn local_name(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schematic(nets: &[(&str, &str)]) -> Schematic {
+        let mut sch = Schematic::new();
+        for (id, (name, kind)) in nets.iter().enumerate() {
+            sch.add_net(Net::new(kind.to_string(), *name, id as u64));
+        }
+        sch
+    }
+
+    fn bodies(config: &NetNamesConfig, nets: &[(&str, &str)]) -> Vec<(String, String)> {
+        check_net_names(config, &schematic(nets), "board.zen")
+            .into_iter()
+            .map(|diag| {
+                let kind = diag
+                    .downcast_error_ref::<crate::lang::error::CategorizedDiagnostic>()
+                    .map(|c| c.kind.clone())
+                    .unwrap_or_default();
+                (kind, diag.body)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flags_names_kicad_rejects_or_merges() {
+        let diagnostics = bodies(
+            &NetNamesConfig::default(),
+            &[
+                ("SDA/SCL", "Net"),
+                ("SDA{slash}SCL", "Net"),
+                ("EN ", "Net"),
+                ("Vbus", "Power"),
+                ("VBUS", "Power"),
+                ("GND", "Ground"),
+            ],
+        );
+        assert_eq!(
+            diagnostics,
+            [
+                (
+                    "net.name.invalid".to_string(),
+                    "Net name \"EN \" has leading or trailing whitespace, which KiCad strips"
+                        .to_string()
+                ),
+                (
+                    "net.name.collision".to_string(),
+                    "Nets 'SDA/SCL', 'SDA{slash}SCL' become one net in KiCad".to_string()
+                ),
+                (
+                    "net.name.case".to_string(),
+                    "Nets 'VBUS', 'Vbus' differ only in case; case-insensitive tools merge them"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn applies_workspace_policy() {
+        let config = NetNamesConfig {
+            reserved: vec!["GND".to_string()],
+            allowed: BTreeMap::from([(
+                "Ground".to_string(),
+                vec!["AGND".to_string(), "DGND".to_string()],
+            )]),
+        };
+        let diagnostics = bodies(
+            &config,
+            &[
+                ("GND", "Ground"),
+                ("AGND", "Ground"),
+                ("power.PGND", "Ground"),
+                ("power.DGND", "Ground"),
+            ],
+        );
+        let messages: Vec<&str> = diagnostics.iter().map(|(_, body)| body.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Net 'GND' uses 'GND', which [workspace.net-names] reserves",
+                "Ground net 'power.PGND' must be named one of AGND, DGND per [workspace.net-names]",
+            ]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Workspace-wide registry of shared net names (`[workspace.nets]`).
+//!
+//! Boards of one system are built separately, so a top-level net name such as
+//! `VBUS` means the same wire on every board only by convention. The registry
+//! makes that convention explicit: each registered name is one system net with
+//! a fixed net type, and every other top-level name is local to its board.
+//!
+//! Two checks enforce it: [`check_registered_nets`] runs on each board and
+//! flags registered nets declared with another type, and
+//! [`check_net_name_collisions`] runs across the boards of one build and flags
+//! unregistered names used on more than one board.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use pcb_sch::Schematic;
+use starlark::errors::EvalSeverity;
+
+use crate::Diagnostic;
+
+/// Top-level net names of a board: named at the root module and connected.
+pub fn board_net_names(schematic: &Schematic) -> BTreeSet<String> {
+    schematic
+        .nets
+        .values()
+        .filter(|net| net.kind != "NotConnected" && !net.name.is_empty())
+        .filter(|net| !net.name.contains('.'))
+        .map(|net| net.name.clone())
+        .collect()
+}
+
+/// Flag nets of `schematic` that use a registered name with another net type.
+pub fn check_registered_nets(
+    registry: &BTreeMap<String, String>,
+    schematic: &Schematic,
+    source_path: &str,
+) -> Vec<Diagnostic> {
+    let mut nets: Vec<_> = schematic
+        .nets
+        .values()
+        .filter_map(|net| Some((net, registry.get(&net.name)?)))
+        .filter(|(net, kind)| &net.kind != *kind)
+        .collect();
+    nets.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+    nets.into_iter()
+        .map(|(net, kind)| {
+            Diagnostic::categorized(
+                source_path,
+                &format!(
+                    "Net '{}' is registered in [workspace.nets] as {kind} but declared as {}",
+                    net.name, net.kind
+                ),
+                "net.registry.type",
+                EvalSeverity::Warning,
+            )
+        })
+        .collect()
+}
+
+/// Top-level net names of one board, for [`check_net_name_collisions`].
+pub struct BoardNets {
+    pub board: String,
+    pub source_path: String,
+    pub nets: BTreeSet<String>,
+}
+
+/// Flag unregistered top-level net names shared by more than one board.
+pub fn check_net_name_collisions(
+    registry: &BTreeMap<String, String>,
+    boards: &[BoardNets],
+) -> Vec<Diagnostic> {
+    let mut boards_by_net: BTreeMap<&str, Vec<&BoardNets>> = BTreeMap::new();
+    for board in boards {
+        for name in board
+            .nets
+            .iter()
+            .filter(|name| !registry.contains_key(*name))
+        {
+            boards_by_net.entry(name.as_str()).or_default().push(board);
+        }
+    }
+
+    boards_by_net
+        .into_iter()
+        .filter(|(_, boards)| boards.len() > 1)
+        .flat_map(|(name, boards)| {
+            boards.iter().map(move |board| {
+                let others: Vec<&str> = boards
+                    .iter()
+                    .filter(|other| other.board != board.board)
+                    .map(|other| other.board.as_str())
+                    .collect();
+                Diagnostic::categorized(
+                    &board.source_path,
+                    &format!(
+                        "Net '{name}' is also defined by board {}; register it in [workspace.nets] if it is the same net, or rename it",
+                        others.join(", ")
+                    ),
+                    "net.registry.collision",
+                    EvalSeverity::Warning,
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(board: &str, nets: &[&str]) -> BoardNets {
+        BoardNets {
+            board: board.to_string(),
+            source_path: format!("{board}.zen"),
+            nets: nets.iter().map(|net| net.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn collisions_skip_registered_names() {
+        let registry = BTreeMap::from([("VBUS".to_string(), "Power".to_string())]);
+        let diagnostics = check_net_name_collisions(
+            &registry,
+            &[
+                board("main", &["VBUS", "SDA", "EN"]),
+                board("flex", &["VBUS", "SDA"]),
+            ],
+        );
+        let bodies: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.path.as_str(), d.body.as_str()))
+            .collect();
+        assert_eq!(
+            bodies,
+            [
+                (
+                    "main.zen",
+                    "Net 'SDA' is also defined by board flex; register it in [workspace.nets] if it is the same net, or rename it"
+                ),
+                (
+                    "flex.zen",
+                    "Net 'SDA' is also defined by board main; register it in [workspace.nets] if it is the same net, or rename it"
+                ),
+            ]
+        );
+    }
+}
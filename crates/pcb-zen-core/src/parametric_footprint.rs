@@ -0,0 +1,445 @@
+//! Parametric footprints generated at evaluation time.
+//!
+//! `builtin.generate_footprint(kind, ...)` renders a `.kicad_mod` for simple,
+//! regular footprints such as pin headers, so each pin count does not need its
+//! own library entry. The output depends only on the parameters: there are no
+//! uuids, timestamps, or tool versions in it, and the file name spells out the
+//! variant, so repeated builds write the same file at the same path.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+
+use crate::FileProvider;
+
+/// Directory, relative to the workspace root, that generated footprints are
+/// written to.
+pub const GENERATED_FOOTPRINTS_DIR: &str = ".pcb/footprints";
+
+const SILK_WIDTH: f64 = 0.12;
+const SILK_OFFSET: f64 = 0.11;
+const FAB_WIDTH: f64 = 0.1;
+const COURTYARD_WIDTH: f64 = 0.05;
+const COURTYARD_MARGIN: f64 = 0.5;
+
+/// A footprint kind with its resolved parameters. Lengths are in millimeters.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParametricFootprint {
+    /// Through-hole pin header, pin 1 at the origin, odd/even numbering
+    /// across the two rows.
+    PinHeader {
+        pins: u32,
+        rows: u32,
+        pitch: f64,
+        drill: f64,
+        pad: f64,
+    },
+    /// A single row of rectangular SMD pads, pin 1 at the origin.
+    PadRow {
+        pins: u32,
+        pitch: f64,
+        pad_width: f64,
+        pad_height: f64,
+    },
+}
+
+/// The rendered footprint: its name and `.kicad_mod` source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedFootprint {
+    pub name: String,
+    pub source: String,
+}
+
+impl ParametricFootprint {
+    /// Build a footprint of `kind` from its parameters. Unknown parameters
+    /// and out-of-range values are errors.
+    pub fn from_params(kind: &str, params: &BTreeMap<String, f64>) -> Result<Self> {
+        let allowed: &[&str] = match kind {
+            "pin_header" => &["pins", "rows", "pitch", "drill", "pad"],
+            "pad_row" => &["pins", "pitch", "pad_width", "pad_height"],
+            _ => bail!("Unknown footprint kind '{kind}'. Expected 'pin_header' or 'pad_row'"),
+        };
+        if let Some(unknown) = params.keys().find(|key| !allowed.contains(&key.as_str())) {
+            bail!(
+                "Unknown parameter '{unknown}' for '{kind}'. Expected one of: {}",
+                allowed.join(", ")
+            );
+        }
+        let length = |key: &str, default: f64| -> Result<f64> {
+            let value = params.get(key).copied().unwrap_or(default);
+            if !(value > 0.0 && value < 100.0) {
+                bail!("'{key}' must be between 0 and 100 mm, got {value}");
+            }
+            Ok(round(value, 0.001))
+        };
+
+        let pins = count(params, "pins", None, 1..=100)?;
+        match kind {
+            "pin_header" => {
+                let rows = count(params, "rows", Some(1), 1..=2)?;
+                let pitch = length("pitch", 2.54)?;
+                let drill = length("drill", round(pitch * 0.4, 0.05))?;
+                let pad = length("pad", round(pitch * 0.67, 0.05))?;
+                if drill >= pad {
+                    bail!("'drill' ({drill} mm) must be smaller than 'pad' ({pad} mm)");
+                }
+                if pad > pitch {
+                    bail!("'pad' ({pad} mm) must not exceed 'pitch' ({pitch} mm)");
+                }
+                Ok(Self::PinHeader {
+                    pins,
+                    rows,
+                    pitch,
+                    drill,
+                    pad,
+                })
+            }
+            _ => {
+                let pitch = length("pitch", 2.54)?;
+                let pad_width = length("pad_width", round(pitch * 0.5, 0.05))?;
+                let pad_height = length("pad_height", 2.0)?;
+                if pad_width >= pitch {
+                    bail!("'pad_width' ({pad_width} mm) must be smaller than 'pitch' ({pitch} mm)");
+                }
+                Ok(Self::PadRow {
+                    pins,
+                    pitch,
+                    pad_width,
+                    pad_height,
+                })
+            }
+        }
+    }
+
+    /// Footprint name, in the style of the KiCad library. Parameters that
+    /// differ from their pitch-derived default are appended.
+    pub fn name(&self) -> String {
+        match *self {
+            Self::PinHeader {
+                pins,
+                rows,
+                pitch,
+                drill,
+                pad,
+            } => {
+                let mut name = format!("PinHeader_{rows}x{pins:02}_P{}mm_Vertical", mm(pitch));
+                if drill != round(pitch * 0.4, 0.05) || pad != round(pitch * 0.67, 0.05) {
+                    let _ = write!(name, "_D{}mm_Pad{}mm", mm(drill), mm(pad));
+                }
+                name
+            }
+            Self::PadRow {
+                pins,
+                pitch,
+                pad_width,
+                pad_height,
+            } => format!(
+                "PadRow_1x{pins:02}_P{}mm_Pad{}x{}mm_SMD",
+                mm(pitch),
+                mm(pad_width),
+                mm(pad_height)
+            ),
+        }
+    }
+
+    /// Render the `.kicad_mod` source.
+    pub fn generate(&self) -> GeneratedFootprint {
+        let name = self.name();
+        let mut out = String::new();
+        let _ = writeln!(out, "(footprint \"{name}\"");
+        out.push_str("\t(version 20240108)\n\t(generator \"pcb\")\n\t(layer \"F.Cu\")\n");
+
+        let (pads, body, attr, descr) = match *self {
+            Self::PinHeader {
+                pins,
+                rows,
+                pitch,
+                drill,
+                pad,
+            } => {
+                let pads: Vec<_> = (0..pins * rows)
+                    .map(|i| {
+                        let (column, row) = if rows == 1 { (0, i) } else { (i % 2, i / 2) };
+                        let shape = if i == 0 { "rect" } else { "oval" };
+                        format!(
+                            "(pad \"{}\" thru_hole {shape} (at {} {}) (size {} {}) (drill {}) (layers \"*.Cu\" \"*.Mask\"))",
+                            i + 1,
+                            mm(column as f64 * pitch),
+                            mm(row as f64 * pitch),
+                            mm(pad),
+                            mm(pad),
+                            mm(drill)
+                        )
+                    })
+                    .collect();
+                let half = pitch / 2.0;
+                let body = Rect {
+                    left: -half,
+                    top: -half,
+                    right: (rows - 1) as f64 * pitch + half,
+                    bottom: (pins - 1) as f64 * pitch + half,
+                };
+                let descr = format!(
+                    "Through hole pin header, {rows}x{pins:02}, {} mm pitch, generated",
+                    mm(pitch)
+                );
+                (pads, body, "through_hole", descr)
+            }
+            Self::PadRow {
+                pins,
+                pitch,
+                pad_width,
+                pad_height,
+            } => {
+                let pads: Vec<_> = (0..pins)
+                    .map(|i| {
+                        format!(
+                            "(pad \"{}\" smd rect (at {} 0) (size {} {}) (layers \"F.Cu\" \"F.Paste\" \"F.Mask\"))",
+                            i + 1,
+                            mm(i as f64 * pitch),
+                            mm(pad_width),
+                            mm(pad_height)
+                        )
+                    })
+                    .collect();
+                let body = Rect {
+                    left: -pad_width / 2.0,
+                    top: -pad_height / 2.0,
+                    right: (pins - 1) as f64 * pitch + pad_width / 2.0,
+                    bottom: pad_height / 2.0,
+                };
+                let descr = format!(
+                    "SMD pad row, 1x{pins:02}, {} mm pitch, {}x{} mm pads, generated",
+                    mm(pitch),
+                    mm(pad_width),
+                    mm(pad_height)
+                );
+                (pads, body, "smd", descr)
+            }
+        };
+
+        let _ = writeln!(out, "\t(descr \"{descr}\")");
+        let text = "(effects (font (size 1 1) (thickness 0.15)))";
+        let _ = writeln!(
+            out,
+            "\t(property \"Reference\" \"REF**\" (at {} {} 0) (layer \"F.SilkS\") {text})",
+            mm(body.center_x()),
+            mm(body.top - 1.5)
+        );
+        let _ = writeln!(
+            out,
+            "\t(property \"Value\" \"{name}\" (at {} {} 0) (layer \"F.Fab\") {text})",
+            mm(body.center_x()),
+            mm(body.bottom + 1.5)
+        );
+        let _ = writeln!(out, "\t(attr {attr})");
+        let courtyard = body.grow(COURTYARD_MARGIN).snap_out(0.01);
+        for (rect, width, layer) in [
+            (body.grow(SILK_OFFSET), SILK_WIDTH, "F.SilkS"),
+            (body, FAB_WIDTH, "F.Fab"),
+            (courtyard, COURTYARD_WIDTH, "F.CrtYd"),
+        ] {
+            let _ = writeln!(
+                out,
+                "\t(fp_rect (start {} {}) (end {} {}) (stroke (width {}) (type solid)) (fill none) (layer \"{layer}\"))",
+                mm(rect.left),
+                mm(rect.top),
+                mm(rect.right),
+                mm(rect.bottom),
+                mm(width)
+            );
+        }
+        for pad in pads {
+            let _ = writeln!(out, "\t{pad}");
+        }
+        out.push_str(")\n");
+
+        GeneratedFootprint { name, source: out }
+    }
+}
+
+/// Write `footprint` to `<dir>/<name>.kicad_mod` through `files` and return
+/// the path. An existing file with the same contents is left untouched.
+pub fn write_generated_footprint(
+    files: &dyn FileProvider,
+    dir: &Path,
+    footprint: &GeneratedFootprint,
+) -> Result<PathBuf> {
+    let path = dir.join(format!("{}.kicad_mod", footprint.name));
+    if files
+        .read_file(&path)
+        .is_ok_and(|existing| existing == footprint.source)
+    {
+        return Ok(path);
+    }
+    files
+        .write_file(&path, &footprint.source)
+        .with_context(|| format!("Failed to write generated footprint {}", path.display()))?;
+    Ok(path)
+}
+
+fn count(
+    params: &BTreeMap<String, f64>,
+    key: &str,
+    default: Option<u32>,
+    range: std::ops::RangeInclusive<u32>,
+) -> Result<u32> {
+    let Some(value) = params.get(key).copied().or(default.map(f64::from)) else {
+        bail!("Missing required parameter '{key}'");
+    };
+    if value.fract() != 0.0 || value < f64::from(*range.start()) || value > f64::from(*range.end())
+    {
+        bail!(
+            "'{key}' must be a whole number from {} to {}, got {value}",
+            range.start(),
+            range.end()
+        );
+    }
+    Ok(value as u32)
+}
+
+fn round(value: f64, step: f64) -> f64 {
+    ((value / step).round() * step * 1e6).round() / 1e6
+}
+
+/// Format millimeters with at most four decimals and no trailing zeros.
+fn mm(value: f64) -> String {
+    let text = format!("{value:.4}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    left: f64,
+    top: f64,
+    right: f64,
+    bottom: f64,
+}
+
+impl Rect {
+    fn grow(self, margin: f64) -> Self {
+        Self {
+            left: self.left - margin,
+            top: self.top - margin,
+            right: self.right + margin,
+            bottom: self.bottom + margin,
+        }
+    }
+
+    fn snap_out(self, grid: f64) -> Self {
+        Self {
+            left: (self.left / grid).floor() * grid,
+            top: (self.top / grid).floor() * grid,
+            right: (self.right / grid).ceil() * grid,
+            bottom: (self.bottom / grid).ceil() * grid,
+        }
+    }
+
+    fn center_x(self) -> f64 {
+        (self.left + self.right) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, f64)]) -> BTreeMap<String, f64> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), *value))
+            .collect()
+    }
+
+    #[test]
+    fn pin_header_numbering_and_name() {
+        let footprint = ParametricFootprint::from_params(
+            "pin_header",
+            &params(&[("pins", 3.0), ("rows", 2.0)]),
+        )
+        .unwrap()
+        .generate();
+
+        assert_eq!(footprint.name, "PinHeader_2x03_P2.54mm_Vertical");
+        assert!(footprint.source.contains(
+            r#"(pad "1" thru_hole rect (at 0 0) (size 1.7 1.7) (drill 1) (layers "*.Cu" "*.Mask"))"#
+        ));
+        assert!(
+            footprint
+                .source
+                .contains(r#"(pad "4" thru_hole oval (at 2.54 2.54)"#)
+        );
+        assert!(
+            footprint
+                .source
+                .contains(r#"(pad "6" thru_hole oval (at 2.54 5.08)"#)
+        );
+        assert!(pcb_sexpr::kicad::footprint::validate_footprint_source(&footprint.source).is_ok());
+
+        let again = ParametricFootprint::from_params(
+            "pin_header",
+            &params(&[("pins", 3.0), ("rows", 2.0)]),
+        )
+        .unwrap()
+        .generate();
+        assert_eq!(footprint, again);
+    }
+
+    #[test]
+    fn overridden_parameters_are_named() {
+        let footprint = ParametricFootprint::from_params(
+            "pin_header",
+            &params(&[("pins", 4.0), ("drill", 1.1)]),
+        )
+        .unwrap();
+        assert_eq!(
+            footprint.name(),
+            "PinHeader_1x04_P2.54mm_Vertical_D1.1mm_Pad1.7mm"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_parameters() {
+        let err =
+            ParametricFootprint::from_params("pin_header", &params(&[("pins", 2.5)])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "'pins' must be a whole number from 1 to 100, got 2.5"
+        );
+        let err =
+            ParametricFootprint::from_params("pad_row", &params(&[("pins", 4.0), ("rows", 2.0)]))
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown parameter 'rows' for 'pad_row'. Expected one of: pins, pitch, pad_width, pad_height"
+        );
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn writes_through_the_file_provider() {
+        let footprint = ParametricFootprint::from_params("pin_header", &params(&[("pins", 3.0)]))
+            .unwrap()
+            .generate();
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join(GENERATED_FOOTPRINTS_DIR);
+
+        let files = crate::DefaultFileProvider::new();
+        let path = write_generated_footprint(&files, &dir, &footprint).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), footprint.source);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        // Read-only providers (in-memory, WASM) refuse the write.
+        let err =
+            write_generated_footprint(&crate::InMemoryFileProvider::empty(), &dir, &footprint)
+                .unwrap_err();
+        assert!(format!("{err:#}").contains("Permission denied"), "{err:#}");
+    }
+}
@@ -1,7 +1,8 @@
+use crate::config::LintLevel;
 use crate::lang::error::CategorizedDiagnostic;
 use crate::{Diagnostic, Diagnostics, DiagnosticsPass, SuppressedDiagnostics};
 use starlark::errors::EvalSeverity;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 /// A pass that filters out hidden diagnostics (containing "<hidden>")
@@ -342,6 +343,52 @@ impl DiagnosticsPass for PromotePass {
     }
 }
 
+/// A pass that applies `[lints]` severity overrides from `pcb.toml`.
+///
+/// Keys match hierarchically like [`SuppressPass`]; when several keys match, the
+/// longest (most specific) one decides the level.
+pub struct LintLevelPass {
+    levels: BTreeMap<String, LintLevel>,
+}
+
+impl LintLevelPass {
+    pub fn new(levels: BTreeMap<String, LintLevel>) -> Self {
+        Self { levels }
+    }
+
+    fn level_for(&self, kind: &str) -> Option<LintLevel> {
+        self.levels
+            .iter()
+            .filter(|(key, _)| kind == key.as_str() || kind.starts_with(&format!("{key}.")))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, level)| *level)
+    }
+}
+
+impl DiagnosticsPass for LintLevelPass {
+    fn apply(&self, diagnostics: &mut Diagnostics) {
+        if self.levels.is_empty() {
+            return;
+        }
+
+        for diag in &mut diagnostics.diagnostics {
+            let Some(level) = diag
+                .innermost()
+                .downcast_error_ref::<CategorizedDiagnostic>()
+                .and_then(|c| self.level_for(&c.kind))
+            else {
+                continue;
+            };
+
+            match level {
+                LintLevel::Allow => diag.suppressed = true,
+                LintLevel::Warn => diag.severity = EvalSeverity::Warning,
+                LintLevel::Deny => diag.severity = EvalSeverity::Error,
+            }
+        }
+    }
+}
+
 /// A pass that promotes all style-related diagnostics from Advice to Warning severity.
 ///
 /// This is specifically for LSP use where we want style hints to be more visible.
@@ -472,6 +519,40 @@ mod tests {
         assert!(diagnostics.diagnostics[0].suppressed);
     }
 
+    #[test]
+    fn test_lint_level_pass_prefers_most_specific_key() {
+        let lifecycle = |kind: &str| {
+            Diagnostic::new("Lifecycle", EvalSeverity::Warning, Path::new("test.zen"))
+                .with_source_error(Some(
+                    crate::lang::error::CategorizedDiagnostic::new(
+                        "Lifecycle".to_string(),
+                        kind.to_string(),
+                    )
+                    .unwrap(),
+                ))
+        };
+
+        let mut diagnostics = Diagnostics {
+            diagnostics: vec![
+                lifecycle("component.lifecycle.eol"),
+                lifecycle("component.lifecycle.nrnd"),
+            ],
+        };
+
+        let pass = LintLevelPass::new(BTreeMap::from([
+            ("component.lifecycle".to_string(), LintLevel::Allow),
+            ("component.lifecycle.eol".to_string(), LintLevel::Deny),
+        ]));
+        pass.apply(&mut diagnostics);
+
+        assert!(matches!(
+            diagnostics.diagnostics[0].severity,
+            EvalSeverity::Error
+        ));
+        assert!(!diagnostics.diagnostics[0].suppressed);
+        assert!(diagnostics.diagnostics[1].suppressed);
+    }
+
     #[test]
     fn test_aggregate_pass_deduplicates_identical_advice() {
         let diag = Diagnostic::new(
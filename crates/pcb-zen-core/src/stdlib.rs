@@ -21,6 +21,10 @@ static EXCLUDED_PATHS: LazyLock<GlobSet> = LazyLock::new(|| {
         .expect("valid stdlib exclude globset configuration")
 });
 
+/// Version of the bundled stdlib. The stdlib ships with the toolchain, so this
+/// is the toolchain version.
+pub const STDLIB_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 pub fn include_path(path: &Path) -> bool {
     !EXCLUDED_PATHS.is_match(path)
 }
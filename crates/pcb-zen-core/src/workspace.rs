@@ -10,8 +10,8 @@ use std::path::{Path, PathBuf};
 
 use crate::FileProvider;
 use crate::config::{
-    PcbToml, WorkspaceConfig, find_workspace_root, parse_pcb_version, pcb_version_from_cargo,
-    pcb_version_is_older,
+    PcbToml, SandboxConfig, WorkspaceConfig, find_workspace_root, parse_pcb_version,
+    pcb_version_from_cargo, pcb_version_is_older,
 };
 
 fn is_default<T: Default + PartialEq>(value: &T) -> bool {
@@ -174,12 +174,31 @@ impl WorkspaceInfo {
             .map(|path| self.root.join(path))
     }
 
+    /// `[patch]` entries at workspace root that point at local directories,
+    /// as `(package URL, path as written in pcb.toml)`.
+    pub fn path_patches(&self) -> Vec<(&str, &str)> {
+        let Some(root_cfg) = self.config.as_ref() else {
+            return Vec::new();
+        };
+        root_cfg
+            .patch
+            .iter()
+            .filter_map(|(url, patch)| Some((url.as_str(), patch.path.as_deref()?)))
+            .collect()
+    }
+
     /// Workspace-local toolchain stdlib materialization path.
     pub fn workspace_stdlib_dir(&self) -> PathBuf {
         self.stdlib_patch_path()
             .unwrap_or_else(|| crate::workspace_stdlib_root(&self.root))
     }
 
+    /// `[workspace.sandbox]`, when enabled.
+    pub fn sandbox(&self) -> Option<&SandboxConfig> {
+        let workspace = self.config.as_ref()?.workspace.as_ref()?;
+        Some(&workspace.sandbox).filter(|sandbox| sandbox.enabled)
+    }
+
     /// Get workspace config section (with defaults if not present)
     pub fn workspace_config(&self) -> WorkspaceConfig {
         self.config
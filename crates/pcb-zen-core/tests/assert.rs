@@ -46,3 +46,60 @@ snapshot_eval!(warn_function_continues_execution, {
         x = 42
     "#
 });
+
+// A failed `check()` in a child module carries the instance path and context values.
+#[test]
+fn check_failure_reports_instance_path_and_context() {
+    use common::eval_zen;
+    use pcb_zen_core::diagnostics::diagnostic_kind;
+    use pcb_zen_core::lang::error::CheckFailure;
+
+    let result = eval_zen(vec![
+        (
+            "Ldo.zen".to_string(),
+            r#"
+vout = config("vout", float, default = 3.3)
+vin = config("vin", float, default = 3.0)
+check(vout < vin, "vout must be below vin", vout = vout, vin = vin)
+"#
+            .to_string(),
+        ),
+        (
+            "top.zen".to_string(),
+            r#"
+Ldo = Module("Ldo.zen")
+Ldo(name = "ldo")
+"#
+            .to_string(),
+        ),
+    ]);
+
+    let failure = result
+        .diagnostics
+        .iter()
+        .find_map(|diag| {
+            let innermost = diag.innermost();
+            innermost
+                .downcast_error_ref::<CheckFailure>()
+                .map(|failure| (diag, innermost, failure))
+        })
+        .expect("expected a check failure");
+    let (diag, innermost, failure) = failure;
+
+    assert_eq!(failure.message, "vout must be below vin");
+    assert_eq!(failure.instance_path, "ldo");
+    assert_eq!(
+        failure.context,
+        vec![
+            ("vout".to_string(), "3.3".to_string()),
+            ("vin".to_string(), "3.0".to_string()),
+        ]
+    );
+    assert_eq!(
+        innermost.body,
+        "vout must be below vin\n  in ldo\n  vout = 3.3\n  vin = 3.0"
+    );
+    assert!(innermost.path.ends_with("Ldo.zen"));
+    assert!(innermost.span.is_some());
+    assert_eq!(diagnostic_kind(diag).as_deref(), Some("assert.check"));
+}
@@ -10,7 +10,7 @@ use common::InMemoryFileProvider;
 use pcb_zen_core::config::DependencyTable;
 use pcb_zen_core::resolution::ResolutionResult;
 use pcb_zen_core::workspace::{WorkspaceInfo, WorkspacePackage};
-use pcb_zen_core::{EvalContext, FileProvider};
+use pcb_zen_core::{DependencySource, EvalContext, FileProvider, ResolveStep};
 use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -278,3 +278,38 @@ fn cross_package_relative_load_undeclared_dependency_missing_from_frozen_resolut
         errors
     );
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn cross_package_relative_load_trace() {
+    let deps = BTreeMap::from([(
+        "github.com/myorg/project/modules/Led".to_string(),
+        pcb_zen_core::config::DependencySpec::Version("0.1.0".to_string()),
+    )]);
+
+    let (file_provider, resolution, main_path) =
+        setup_cross_package_workspace(Some("github.com/myorg/project"), deps, true);
+
+    let config = pcb_zen_core::EvalContextConfig::new(file_provider, Arc::new(resolution))
+        .set_source_path(main_path.clone());
+    let (result, trace) = config.trace_resolve_path("../../modules/Led/Led.zen", &main_path);
+
+    let led = PathBuf::from("/workspace/modules/Led/Led.zen");
+    assert_eq!(result.unwrap(), led);
+    assert_eq!(
+        trace,
+        vec![
+            ResolveStep::Relative { path: led.clone() },
+            ResolveStep::PackageBoundary {
+                url: "github.com/myorg/project/modules/Led/Led.zen".to_string(),
+            },
+            ResolveStep::Dependency {
+                url: "github.com/myorg/project/modules/Led/Led.zen".to_string(),
+                dep_url: "github.com/myorg/project/modules/Led".to_string(),
+                root: PathBuf::from("/workspace/modules/Led"),
+                source: DependencySource::Workspace,
+            },
+            ResolveStep::Resolved { path: led },
+        ]
+    );
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::eval_zen;
+use pcb_zen_core::diagnostics::diagnostic_kind;
+use starlark::errors::EvalSeverity;
+
+/// `(kind, body, path, line)` of each deprecation warning, at its primary location.
+fn deprecations(files: Vec<(&str, &str)>) -> Vec<(String, String, String, u32)> {
+    let result = eval_zen(
+        files
+            .into_iter()
+            .map(|(path, content)| (path.to_string(), content.to_string()))
+            .collect(),
+    );
+    assert!(result.is_success(), "eval failed: {:?}", result.diagnostics);
+    result
+        .diagnostics
+        .iter()
+        .filter(|diag| matches!(diag.severity, EvalSeverity::Warning))
+        .filter_map(|diag| {
+            let kind = diagnostic_kind(diag).filter(|kind| kind.starts_with("deprecated."))?;
+            let line = diag.span.map(|span| span.begin.line as u32)?;
+            Some((kind, diag.innermost().body.clone(), diag.path.clone(), line))
+        })
+        .collect()
+}
+
+#[test]
+fn deprecated_function_warns_at_call_site() {
+    let warnings = deprecations(vec![(
+        "test.zen",
+        r#"
+def old_divider(ratio):
+    deprecated("ratio is ambiguous", replacement = "divider")
+    return ratio
+
+def divider(ratio):
+    return ratio
+
+old_divider(0.5)
+divider(0.5)
+"#,
+    )]);
+
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    let (kind, body, path, _) = &warnings[0];
+    assert_eq!(kind, "deprecated.function");
+    assert_eq!(
+        body,
+        "`old_divider` is deprecated: ratio is ambiguous (use `divider` instead)"
+    );
+    assert!(path.ends_with("test.zen"));
+}
+
+#[test]
+fn deprecated_module_warns_at_each_instantiation() {
+    let warnings = deprecations(vec![
+        (
+            "VoltageDivider.zen",
+            r#"
+deprecated("use VoltageDivider v2")
+"#,
+        ),
+        (
+            "top.zen",
+            r#"
+VoltageDivider = Module("VoltageDivider.zen")
+VoltageDivider(name = "a")
+VoltageDivider(name = "b")
+"#,
+        ),
+    ]);
+
+    assert_eq!(warnings.len(), 2, "{warnings:?}");
+    for (kind, body, path, _) in &warnings {
+        assert_eq!(kind, "deprecated.module");
+        assert_eq!(
+            body,
+            "`VoltageDivider` is deprecated: use VoltageDivider v2"
+        );
+        assert!(path.ends_with("top.zen"));
+    }
+    assert_ne!(warnings[0].3, warnings[1].3);
+}
+
+#[test]
+fn deprecated_parameter_warns_only_when_passed() {
+    let warnings = deprecations(vec![
+        (
+            "Ldo.zen",
+            r#"
+vout = config("vout", float, default = 3.3)
+trim = config("trim", float, default = 0.0, deprecated = "set vout instead")
+"#,
+        ),
+        (
+            "top.zen",
+            r#"
+Ldo = Module("Ldo.zen")
+Ldo(name = "a", vout = 1.8)
+Ldo(name = "b", trim = 0.1)
+"#,
+        ),
+    ]);
+
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    let (kind, body, path, _) = &warnings[0];
+    assert_eq!(kind, "deprecated.parameter");
+    assert_eq!(
+        body,
+        "Parameter `trim` of `Ldo` is deprecated: set vout instead"
+    );
+    assert!(path.ends_with("top.zen"));
+}
@@ -0,0 +1,55 @@
+#[macro_use]
+mod common;
+
+use pcb_sch::Schematic;
+
+const BOARD: &str = r#"
+vcc = Net("VCC")
+gnd = Net("GND")
+sda = Net("SDA")
+scl = Net("SCL")
+
+for i in range(1, 9):
+    Component(
+        name = "R" + str(i),
+        footprint = "TEST:0402",
+        pin_defs = {"A": "1", "B": "2"},
+        pins = {"A": vcc if i % 2 else sda, "B": gnd if i % 3 else scl},
+        prefix = "R",
+        properties = {"tolerance": "1%", "index": i, "note": "pull " + str(i)},
+    )
+
+Component(
+    name = "U1",
+    footprint = "TEST:SOIC8",
+    pin_defs = {"VCC": "1", "GND": "2", "SDA": "3", "SCL": "4"},
+    pins = {"VCC": vcc, "GND": gnd, "SDA": sda, "SCL": scl},
+    prefix = "U",
+)
+"#;
+
+fn build() -> Schematic {
+    let result = common::eval_zen(vec![("main.zen".to_string(), BOARD.to_string())]);
+    assert!(result.is_success(), "eval failed: {:?}", result.diagnostics);
+    let sch_result = result.output.unwrap().to_schematic_with_diagnostics();
+    sch_result
+        .output
+        .unwrap_or_else(|| panic!("schematic conversion failed: {:?}", sch_result.diagnostics))
+}
+
+#[test]
+fn repeated_builds_serialize_identically() {
+    let first = build();
+    for _ in 0..4 {
+        let again = build();
+        assert_eq!(first.to_json().unwrap(), again.to_json().unwrap());
+        assert_eq!(
+            serde_json::to_string(&first).unwrap(),
+            serde_json::to_string(&again).unwrap()
+        );
+        assert_eq!(
+            pcb_sch::kicad_netlist::to_kicad_netlist(&first),
+            pcb_sch::kicad_netlist::to_kicad_netlist(&again)
+        );
+    }
+}
@@ -2127,3 +2127,286 @@ snapshot_eval!(config_allowed_unsupported_type, {
         )
     "#
 });
+
+#[test]
+fn config_range_bounds() {
+    let eval = |gain: &str| {
+        eval_zen(vec![
+            (
+                "Module.zen".to_string(),
+                r#"
+                    gain = config("gain", int, min = 1, max = 10, default = 2)
+                    supply = config("supply", Voltage, max = "5V", default = "3.3V")
+                "#
+                .to_string(),
+            ),
+            (
+                "top.zen".to_string(),
+                format!(
+                    r#"
+                    Mod = Module("Module.zen")
+
+                    Mod(name = "U1", gain = {gain})
+                    "#
+                ),
+            ),
+        ])
+    };
+
+    let result = eval("10");
+    assert!(
+        result.is_success(),
+        "expected eval success, got diagnostics: {:?}",
+        result.diagnostics
+    );
+    let output = result.output.expect("expected eval output");
+    let module_tree = output.module_tree();
+    let child_module = module_tree
+        .values()
+        .find(|module| module.path().name() == "U1")
+        .expect("expected instantiated child module");
+    let gain = child_module
+        .signature()
+        .iter()
+        .find(|param| param.name == "gain")
+        .expect("expected gain parameter in child signature");
+    assert_eq!(gain.min_display().as_deref(), Some("1"));
+    assert_eq!(gain.max_display().as_deref(), Some("10"));
+
+    let result = eval("11");
+    assert!(!result.is_success());
+    let mentions_range = |diag: &pcb_zen_core::Diagnostic| {
+        std::iter::successors(Some(diag), |diag| diag.child.as_deref()).any(|diag| {
+            diag.body
+                .contains("invalid value for config `gain`: got 11; expected between 1 and 10")
+        })
+    };
+    assert!(
+        result.diagnostics.iter().any(mentions_range),
+        "expected range error, got diagnostics: {:?}",
+        result.diagnostics
+    );
+}
+
+#[test]
+fn explain_follows_forwarded_config_values() {
+    use pcb_zen_core::lang::explain::{ValueOrigin, explain_parameter};
+
+    let result = eval_zen(vec![
+        (
+            "Ldo.zen".to_string(),
+            r#"
+                vout = config("vout", str, default = "1.8V")
+                trim = config("trim", int, default = 0)
+            "#
+            .to_string(),
+        ),
+        (
+            "Power.zen".to_string(),
+            r#"
+                vout = config("vout", str, default = "1.8V")
+
+                Ldo = Module("Ldo.zen")
+                Ldo(name = "ldo", vout = vout)
+            "#
+            .to_string(),
+        ),
+        (
+            "top.zen".to_string(),
+            r#"
+                Power = Module("Power.zen")
+
+                Power(name = "power", vout = "3.3V")
+            "#
+            .to_string(),
+        ),
+    ]);
+    assert!(
+        result.is_success(),
+        "expected eval success, got diagnostics: {:?}",
+        result.diagnostics
+    );
+    let module_tree = result.output.expect("expected eval output").module_tree();
+
+    let steps = explain_parameter(&module_tree, "power.ldo.vout").unwrap();
+    let chain: Vec<(String, &str, &str)> = steps
+        .iter()
+        .map(|step| {
+            (
+                step.module.to_string(),
+                step.param.as_str(),
+                step.value.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        chain,
+        vec![
+            ("power.ldo".to_string(), "vout", "\"3.3V\""),
+            ("power".to_string(), "vout", "\"3.3V\""),
+        ]
+    );
+    assert!(matches!(
+        &steps[0].origin,
+        ValueOrigin::Passed { path, forwarded_from: Some(from), .. }
+            if path.ends_with("Power.zen") && from == "vout"
+    ));
+    assert!(matches!(
+        &steps[1].origin,
+        ValueOrigin::Passed { path, forwarded_from: None, .. } if path.ends_with("top.zen")
+    ));
+
+    let steps = explain_parameter(&module_tree, "power.ldo.trim").unwrap();
+    assert_eq!(steps.len(), 1);
+    assert!(
+        matches!(&steps[0].origin, ValueOrigin::Default { path, .. } if path.ends_with("Ldo.zen"))
+    );
+
+    assert!(explain_parameter(&module_tree, "power.ldo.missing").is_err());
+    assert!(explain_parameter(&module_tree, "nowhere.vout").is_err());
+}
+
+#[test]
+fn secret_inputs_leaking_into_attributes_are_redacted() {
+    use pcb_zen_core::lang::secret::{REDACTED, SecretInputs};
+
+    let mut files = stdlib_test_files();
+    files.insert(
+        "test.zen".to_string(),
+        r#"
+            key = config("provisioning_key", str)
+
+            Component(
+                name = "U1",
+                footprint = File("@kicad-footprints/Resistor_SMD.pretty/R_0402_1005Metric.kicad_mod"),
+                pin_defs = {"A": "1", "B": "2"},
+                pins = {"A": Net("A"), "B": Net("B")},
+                properties = {"Serial": "SN-" + key},
+            )
+        "#
+        .to_string(),
+    );
+
+    let mut secrets = SecretInputs::default();
+    secrets.insert("provisioning_key", serde_json::json!("K3Y-abc123"));
+    let file_provider: Arc<dyn pcb_zen_core::FileProvider> =
+        Arc::new(InMemoryFileProvider::new(files));
+    let mut ctx = pcb_zen_core::EvalContext::new(file_provider, test_resolution())
+        .set_source_path(PathBuf::from("test.zen"));
+    ctx.set_secret_inputs(secrets);
+    let eval_result = ctx.eval();
+    assert!(
+        !eval_result.diagnostics.has_errors(),
+        "eval produced unexpected errors: {:?}",
+        eval_result.diagnostics
+    );
+
+    let sch_result = eval_result
+        .output
+        .expect("expected eval output")
+        .to_schematic_with_diagnostics();
+    let leaks: Vec<&String> = sch_result
+        .diagnostics
+        .iter()
+        .filter(|diag| {
+            diag.downcast_error_ref::<CategorizedDiagnostic>()
+                .is_some_and(|c| c.kind == "config.secret_leak")
+        })
+        .map(|diag| &diag.body)
+        .collect();
+    assert_eq!(leaks.len(), 1, "got: {:?}", sch_result.diagnostics);
+    assert!(leaks[0].contains("`provisioning_key`") && leaks[0].contains("`Serial`"));
+
+    let schematic = sch_result.output.expect("expected schematic");
+    let json = schematic.to_json().unwrap();
+    assert!(!json.contains("K3Y-abc123"));
+    assert!(json.contains(REDACTED));
+}
+
+#[test]
+fn target_flags_reach_every_module() {
+    let mut files = stdlib_test_files();
+    files.insert(
+        "child.zen".to_string(),
+        r#"
+            if target.variant != "lite":
+                fail("child saw variant " + str(target.variant))
+            if target.region != None:
+                fail("unset flags should read as None")
+        "#
+        .to_string(),
+    );
+    files.insert(
+        "test.zen".to_string(),
+        r#"
+            Child = Module("child.zen")
+            Child(name = "C")
+        "#
+        .to_string(),
+    );
+
+    let eval = |flags: &[(&str, &str)]| {
+        let file_provider: Arc<dyn pcb_zen_core::FileProvider> =
+            Arc::new(InMemoryFileProvider::new(files.clone()));
+        let mut ctx = pcb_zen_core::EvalContext::new(file_provider, test_resolution())
+            .set_source_path(PathBuf::from("test.zen"));
+        ctx.set_target_flags(
+            flags
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        );
+        ctx.eval().diagnostics
+    };
+
+    let diagnostics = eval(&[("variant", "lite")]);
+    assert!(!diagnostics.has_errors(), "got: {diagnostics:?}");
+    assert!(eval(&[("variant", "full")]).has_errors());
+    assert!(eval(&[]).has_errors());
+}
+
+#[test]
+fn json_inputs_convert_nested_elements() {
+    let mut files = stdlib_test_files();
+    files.insert(
+        "test.zen".to_string(),
+        r#"
+            pins = config(list[int])
+            rails = config(dict[str, Voltage])
+            if pins != [1, 2]:
+                fail("pins were " + str(pins))
+            if rails["vin"] != Voltage("5V"):
+                fail("rails were " + str(rails))
+        "#
+        .to_string(),
+    );
+
+    let eval = |inputs: serde_json::Value| {
+        let file_provider: Arc<dyn pcb_zen_core::FileProvider> =
+            Arc::new(InMemoryFileProvider::new(files.clone()));
+        let mut ctx = pcb_zen_core::EvalContext::new(file_provider, test_resolution())
+            .set_source_path(PathBuf::from("test.zen"));
+        let serde_json::Value::Object(inputs) = inputs else {
+            unreachable!()
+        };
+        ctx.set_json_inputs(inputs.into_iter().collect());
+        ctx.eval().diagnostics
+    };
+
+    let diagnostics = eval(serde_json::json!({
+        "pins": ["1", 2],
+        "rails": {"vin": "5V"},
+    }));
+    assert!(!diagnostics.has_errors(), "got: {diagnostics:?}");
+
+    let diagnostics = eval(serde_json::json!({
+        "pins": [1, "two"],
+        "rails": {"vin": "5V"},
+    }));
+    assert!(
+        diagnostics.iter().any(|diag| diag
+            .body
+            .contains("Input 'pins[1]' has wrong type: expected int, got \"two\"")),
+        "got: {diagnostics:?}"
+    );
+}
@@ -100,3 +100,74 @@ fn stdlib_units_primary_type_does_not_warn_on_constructor() {
         "did not expect deprecation warning, got: {warning_bodies:?}"
     );
 }
+
+#[test]
+fn directory_load_exports_zen_files() {
+    let result = eval_zen(vec![
+        (
+            "components/Resistor.zen".to_string(),
+            "Kind = \"resistor\"\n".to_string(),
+        ),
+        (
+            "components/Capacitor.zen".to_string(),
+            "Kind = \"capacitor\"\n".to_string(),
+        ),
+        (
+            "test.zen".to_string(),
+            r#"
+                load("./components", "Resistor", "Capacitor")
+
+                check(Resistor.Kind == "resistor", "Resistor should be exported")
+                check(Capacitor.Kind == "capacitor", "Capacitor should be exported")
+            "#
+            .to_string(),
+        ),
+    ]);
+
+    assert!(
+        result.is_success(),
+        "unexpected diagnostics: {:?}",
+        result.diagnostics
+    );
+}
+
+#[test]
+fn cyclic_load_reports_full_cycle_with_load_spans() {
+    let result = eval_zen(vec![
+        (
+            "b.zen".to_string(),
+            "load(\"c.zen\", \"C\")\nB = 1\n".to_string(),
+        ),
+        (
+            "c.zen".to_string(),
+            "load(\"a.zen\", \"A\")\nC = 1\n".to_string(),
+        ),
+        (
+            "a.zen".to_string(),
+            "load(\"b.zen\", \"B\")\nA = 1\n".to_string(),
+        ),
+    ]);
+    assert!(!result.is_success());
+
+    let cycle = result
+        .diagnostics
+        .iter()
+        .flat_map(|diag| std::iter::successors(Some(diag), |diag| diag.child.as_deref()))
+        .find(|diag| diag.body.starts_with("Cyclic load"))
+        .expect("expected a cyclic load diagnostic");
+    assert_eq!(cycle.body, "Cyclic load: b.zen → c.zen → a.zen → b.zen");
+    assert!(cycle.path.ends_with("a.zen"));
+    assert!(cycle.span.is_some());
+    let related: Vec<(&str, &str)> = cycle
+        .related
+        .iter()
+        .map(|reference| (reference.path.as_str(), reference.message.as_str()))
+        .collect();
+    assert_eq!(
+        related,
+        vec![
+            ("/b.zen", "`c.zen` is loaded here"),
+            ("/c.zen", "`a.zen` is loaded here"),
+        ]
+    );
+}
@@ -6,7 +6,7 @@ use pcb_zen_core::resolution::{
 };
 use pcb_zen_core::workspace::WorkspaceInfo;
 use pcb_zen_core::workspace::get_workspace_info;
-use pcb_zen_core::{EvalContext, FileProvider, FileProviderError};
+use pcb_zen_core::{EvalContext, FileMetadata, FileProvider, FileProviderError};
 use ruzstd::decoding::StreamingDecoder;
 use semver::Version;
 use serde::{Deserialize, Serialize};
@@ -52,6 +52,20 @@ impl MemoryFiles {
             })
     }
 
+    /// Project files can be replaced by `override_file` (e.g. an unsaved
+    /// editor buffer), possibly with contents of the same size, so the stamp
+    /// carries a content hash.
+    fn metadata(
+        &self,
+        normalized: &str,
+        original_path: &Path,
+    ) -> Result<FileMetadata, FileProviderError> {
+        self.files
+            .get(normalized)
+            .map(|bytes| FileMetadata::from_contents(&String::from_utf8_lossy(bytes)))
+            .ok_or_else(|| FileProviderError::NotFound(original_path.to_path_buf()))
+    }
+
     fn contains_or_dir(&self, normalized: &str) -> bool {
         self.files.contains_key(normalized) || self.is_dir(normalized)
     }
@@ -120,6 +134,13 @@ impl BundleFileProvider {
         normalized.join("/")
     }
 
+    /// Replace (or add) a project file, e.g. the unsaved buffer of an editor.
+    fn override_file(&mut self, path: &Path, contents: &str) {
+        self.project
+            .files
+            .insert(Self::normalize(path), contents.as_bytes().to_vec());
+    }
+
     fn stdlib_rel<'a>(&'a self, normalized: &'a str) -> Option<&'a str> {
         normalized
             .strip_prefix(&self.stdlib_root)
@@ -197,6 +218,22 @@ impl FileProvider for BundleFileProvider {
         self.project.read_utf8(&normalized, path)
     }
 
+    fn metadata(&self, path: &Path) -> Result<FileMetadata, FileProviderError> {
+        let normalized = Self::normalize(path);
+
+        if normalized == self.stdlib_root {
+            return Err(FileProviderError::NotFound(path.to_path_buf()));
+        }
+        if let Some(rel) = self.stdlib_rel(&normalized) {
+            if pcb_zen_core::stdlib::include_path(Path::new(rel)) {
+                return self.stdlib.metadata(rel, path);
+            }
+            return Err(FileProviderError::NotFound(path.to_path_buf()));
+        }
+
+        self.project.metadata(&normalized, path)
+    }
+
     fn exists(&self, path: &Path) -> bool {
         let normalized = Self::normalize(path);
         if normalized == self.stdlib_root {
@@ -565,6 +602,27 @@ mod tests {
         assert!(matches!(err, FileProviderError::NotFound(_)));
     }
 
+    #[test]
+    fn override_file_replaces_bundle_source() {
+        let mut provider = provider(empty_zip_bytes());
+        provider.override_file(Path::new("/boards/demo/demo.zen"), "print('edited')");
+        assert_eq!(
+            provider
+                .read_file(Path::new("boards/demo/demo.zen"))
+                .expect("read source"),
+            "print('edited')"
+        );
+    }
+
+    #[test]
+    fn format_source_uses_pcb_fmt_settings() {
+        assert_eq!(
+            format_source_impl("x=[1,2]\n").expect("format"),
+            "x = [1, 2]\n"
+        );
+        assert!(format_source_impl("def (:\n").is_err());
+    }
+
     #[test]
     fn missing_stdlib_archive_errors() {
         let err = match BundleFileProvider::new(empty_zip_bytes(), Vec::new()) {
@@ -790,26 +848,7 @@ pub fn evaluate_impl(
     inputs_json: &str,
 ) -> Result<EvaluationResult, String> {
     let file_provider = Arc::new(BundleFileProvider::new(bundle_bytes, stdlib_tar_zst_bytes)?);
-
-    let main_file = if main_file.is_empty() {
-        file_provider.detect_main_file().ok_or_else(|| {
-            "Could not auto-detect main file. Expected exactly one board directory \
-             in boards/ with exactly one .zen file. Please specify the main file explicitly."
-                .to_string()
-        })?
-    } else {
-        main_file.to_string()
-    };
-
-    let requested_main_path = PathBuf::from(&main_file);
-    let main_path = Path::new("/").join(requested_main_path);
-    let main_path = file_provider
-        .canonicalize(&main_path)
-        .map_err(|e| format!("Failed to canonicalize main file path: {e}"))?;
-    let workspace_root = find_workspace_root(file_provider.as_ref(), &main_path)
-        .map_err(|e| format!("Failed to find workspace root: {e}"))?;
-    let resolution = resolve_packages(file_provider.clone(), &workspace_root, &main_path)
-        .map_err(|e| format!("Failed to resolve dependencies: {e}"))?;
+    let (main_path, resolution) = resolve_main_file(&file_provider, main_file)?;
 
     let inputs: HashMap<String, serde_json::Value> =
         serde_json::from_str(inputs_json).map_err(|e| format!("Failed to parse inputs: {e}"))?;
@@ -839,6 +878,34 @@ pub fn evaluate_impl(
     })
 }
 
+/// Canonical path of `main_file` (auto-detected when empty) and the package
+/// resolution of its workspace.
+fn resolve_main_file(
+    file_provider: &Arc<BundleFileProvider>,
+    main_file: &str,
+) -> Result<(PathBuf, pcb_zen_core::resolution::ResolutionResult), String> {
+    let main_file = if main_file.is_empty() {
+        file_provider.detect_main_file().ok_or_else(|| {
+            "Could not auto-detect main file. Expected exactly one board directory \
+             in boards/ with exactly one .zen file. Please specify the main file explicitly."
+                .to_string()
+        })?
+    } else {
+        main_file.to_string()
+    };
+
+    let requested_main_path = PathBuf::from(&main_file);
+    let main_path = Path::new("/").join(requested_main_path);
+    let main_path = file_provider
+        .canonicalize(&main_path)
+        .map_err(|e| format!("Failed to canonicalize main file path: {e}"))?;
+    let workspace_root = find_workspace_root(file_provider.as_ref(), &main_path)
+        .map_err(|e| format!("Failed to find workspace root: {e}"))?;
+    let resolution = resolve_packages(file_provider.clone(), &workspace_root, &main_path)
+        .map_err(|e| format!("Failed to resolve dependencies: {e}"))?;
+    Ok((main_path, resolution))
+}
+
 /// Evaluate a Zener module from an in-memory source bundle (WASM binding).
 #[wasm_bindgen]
 pub fn evaluate(
@@ -856,6 +923,79 @@ pub fn evaluate(
         .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {e}")))
 }
 
+/// Format `.zen` source with the same settings as `pcb fmt`.
+pub fn format_source_impl(source: &str) -> Result<String, String> {
+    pcb_fmt::RuffFormatter::default()
+        .format_source(source)
+        .map_err(|e| format!("{e:#}"))
+}
+
+/// Format `.zen` source with the same settings as `pcb fmt` (WASM binding).
+#[wasm_bindgen]
+pub fn format_source(source: &str) -> Result<String, JsValue> {
+    format_source_impl(source).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Render markdown documentation for one file of a source bundle.
+///
+/// `source` replaces the bundle's copy of `file`, so unsaved editor contents
+/// can be previewed. `markdown` is `None` when evaluation fails.
+pub fn docs_preview_impl(
+    bundle_bytes: Vec<u8>,
+    stdlib_tar_zst_bytes: Vec<u8>,
+    file: &str,
+    source: &str,
+) -> Result<DocsPreview, String> {
+    let mut file_provider = BundleFileProvider::new(bundle_bytes, stdlib_tar_zst_bytes)?;
+    file_provider.override_file(Path::new(file), source);
+    let file_provider = Arc::new(file_provider);
+    let (main_path, resolution) = resolve_main_file(&file_provider, file)?;
+
+    let result = EvalContext::new(file_provider.clone(), resolution)
+        .set_source_path(main_path)
+        .eval();
+    let markdown = match &result.output {
+        Some(output) => {
+            let doc = pcb_docgen::document_source(file, source, output)
+                .map_err(|e| format!("Failed to document {file}: {e:#}"))?;
+            Some(pcb_docgen::render_docs(&[doc], None, None))
+        }
+        None => None,
+    };
+
+    Ok(DocsPreview {
+        markdown,
+        diagnostics: result
+            .diagnostics
+            .into_iter()
+            .map(|d| diagnostic_to_json(&d))
+            .collect(),
+    })
+}
+
+/// Render markdown documentation for one file of a source bundle (WASM binding).
+#[wasm_bindgen]
+pub fn docs_preview(
+    bundle_bytes: Vec<u8>,
+    stdlib_tar_zst_bytes: Vec<u8>,
+    file: &str,
+    source: &str,
+) -> Result<JsValue, JsValue> {
+    let preview = docs_preview_impl(bundle_bytes, stdlib_tar_zst_bytes, file, source)
+        .map_err(|e| JsValue::from_str(&e))?;
+
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+    preview
+        .serialize(&serializer)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {e}")))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DocsPreview {
+    pub markdown: Option<String>,
+    pub diagnostics: Vec<DiagnosticInfo>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct DiagnosticInfo {
     pub level: String,
@@ -0,0 +1,242 @@
+//! Source rewrites for migrating `.zen` files between language versions.
+//!
+//! Each [`Codemod`] finds matching call sites in the parsed AST and edits only
+//! the spans it touches, so comments and formatting elsewhere are preserved.
+//! Codemods must be idempotent: once applied, the pattern they match no longer
+//! occurs. [`apply_codemods`] enforces this by re-running them on the result.
+
+use anyhow::{Result, bail};
+use starlark::codemap::Span;
+use starlark::syntax::{AstModule, Dialect};
+use starlark_syntax::syntax::ast::{ArgumentP, AstExpr, ExprP};
+
+use crate::ast_utils::{SourceEdit, apply_edits};
+
+/// A single mechanical rewrite of call sites.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codemod {
+    /// Rename calls to a builtin or function: `from(...)` → `to(...)`.
+    /// Attribute calls such as `mod.from(...)` are renamed too.
+    RenameFunction {
+        from: &'static str,
+        to: &'static str,
+    },
+    /// Rename a keyword argument in calls to `function`.
+    RenameKeyword {
+        function: &'static str,
+        from: &'static str,
+        to: &'static str,
+    },
+    /// Pass the positional argument at `index` by keyword `name` in calls to
+    /// `function`. Only applies when it is the last positional argument, so
+    /// the call stays valid.
+    PositionalToKeyword {
+        function: &'static str,
+        index: usize,
+        name: &'static str,
+    },
+}
+
+/// Apply `codemods` in order and return the rewritten source.
+///
+/// Fails if the source does not parse, or if running any codemod again on the
+/// result would change it further.
+pub fn apply_codemods(filename: &str, source: &str, codemods: &[Codemod]) -> Result<String> {
+    let mut current = source.to_string();
+    for codemod in codemods {
+        current = apply_codemod(filename, &current, codemod)?;
+    }
+    for codemod in codemods {
+        if apply_codemod(filename, &current, codemod)? != current {
+            bail!("{filename}: codemod {codemod:?} is not idempotent");
+        }
+    }
+    Ok(current)
+}
+
+fn apply_codemod(filename: &str, source: &str, codemod: &Codemod) -> Result<String> {
+    let mut dialect = Dialect::Extended;
+    dialect.enable_f_strings = true;
+    let ast = AstModule::parse(filename, source.to_owned(), &dialect)
+        .map_err(|err| anyhow::anyhow!("Failed to parse {filename}: {err}"))?;
+
+    let lines = SourceLines::new(source);
+    let mut edits = Vec::new();
+    ast.statement()
+        .visit_expr(|expr| collect_edits(codemod, &lines, expr, &mut edits));
+    if edits.is_empty() {
+        return Ok(source.to_string());
+    }
+
+    // Splitting on '\n' alone keeps any '\r', so line endings round-trip.
+    let mut rewritten: Vec<String> = source.split('\n').map(str::to_string).collect();
+    apply_edits(&mut rewritten, edits);
+    Ok(rewritten.join("\n"))
+}
+
+/// Byte offsets where each line of a source text starts, for turning AST
+/// spans into the line/column positions of a [`SourceEdit`].
+struct SourceLines(Vec<usize>);
+
+impl SourceLines {
+    fn new(source: &str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self(starts)
+    }
+
+    /// Line index and byte column of `offset`.
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let line = self.0.partition_point(|&start| start <= offset) - 1;
+        (line, offset - self.0[line])
+    }
+
+    fn edit(&self, start: usize, end: usize, replacement: &str) -> SourceEdit {
+        let (start_line, start_col) = self.position(start);
+        let (end_line, end_col) = self.position(end);
+        (
+            start_line,
+            start_col,
+            end_line,
+            end_col,
+            replacement.to_string(),
+        )
+    }
+
+    fn replace(&self, span: Span, replacement: &str) -> SourceEdit {
+        self.edit(
+            span.begin().get() as usize,
+            span.end().get() as usize,
+            replacement,
+        )
+    }
+}
+
+fn collect_edits(
+    codemod: &Codemod,
+    lines: &SourceLines,
+    expr: &AstExpr,
+    edits: &mut Vec<SourceEdit>,
+) {
+    if let ExprP::Call(fun, args) = &expr.node
+        && let Some((callee, callee_span)) = callee_name(fun)
+    {
+        match *codemod {
+            Codemod::RenameFunction { from, to } if callee == from => {
+                edits.push(lines.replace(callee_span, to));
+            }
+            Codemod::RenameKeyword { function, from, to } if callee == function => {
+                for arg in &args.args {
+                    if let ArgumentP::Named(name, _) = &arg.node
+                        && name.node == from
+                    {
+                        edits.push(lines.replace(name.span, to));
+                    }
+                }
+            }
+            Codemod::PositionalToKeyword {
+                function,
+                index,
+                name,
+            } if callee == function => {
+                let positional: Vec<&AstExpr> = args
+                    .args
+                    .iter()
+                    .filter_map(|arg| match &arg.node {
+                        ArgumentP::Positional(expr) => Some(expr),
+                        _ => None,
+                    })
+                    .collect();
+                let already_named = args
+                    .args
+                    .iter()
+                    .any(|arg| matches!(&arg.node, ArgumentP::Named(n, _) if n.node == name));
+                if positional.len() == index + 1 && !already_named {
+                    let start = positional[index].span.begin().get() as usize;
+                    edits.push(lines.edit(start, start, &format!("{name}=")));
+                }
+            }
+            _ => {}
+        }
+    }
+    expr.visit_expr(|child| collect_edits(codemod, lines, child, edits));
+}
+
+/// Name of a called function and the span to rename: the identifier for
+/// `f(...)`, or the attribute for `module.f(...)`.
+fn callee_name(fun: &AstExpr) -> Option<(&str, Span)> {
+    match &fun.node {
+        ExprP::Identifier(ident) => Some((ident.node.ident.as_str(), ident.span)),
+        ExprP::Dot(_, attr) => Some((attr.node.as_str(), attr.span)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODEMODS: &[Codemod] = &[
+        Codemod::RenameFunction {
+            from: "OldNet",
+            to: "Net",
+        },
+        Codemod::RenameKeyword {
+            function: "Component",
+            from: "footprint_path",
+            to: "footprint",
+        },
+        Codemod::PositionalToKeyword {
+            function: "Net",
+            index: 0,
+            name: "name",
+        },
+    ];
+
+    #[test]
+    fn rewrites_call_sites_and_preserves_formatting() {
+        let source = r#"# power nets
+vcc = OldNet("VCC")  # keep me
+gnd = std.OldNet(
+    "GND",
+)
+Component(name="R1", footprint_path="R_0603.kicad_mod", pins={"1": vcc})
+OldNetwork = "not a call"
+"#;
+        let rewritten = apply_codemods("test.zen", source, CODEMODS).unwrap();
+        assert_eq!(
+            rewritten,
+            r#"# power nets
+vcc = Net(name="VCC")  # keep me
+gnd = std.Net(
+    name="GND",
+)
+Component(name="R1", footprint="R_0603.kicad_mod", pins={"1": vcc})
+OldNetwork = "not a call"
+"#
+        );
+        assert_eq!(
+            apply_codemods("test.zen", &rewritten, CODEMODS).unwrap(),
+            rewritten
+        );
+    }
+
+    #[test]
+    fn positional_to_keyword_skips_ambiguous_calls() {
+        let source = "Net(\"A\", \"B\")\nNet(\"C\", name=\"D\")\n";
+        assert_eq!(
+            apply_codemods("test.zen", source, CODEMODS).unwrap(),
+            source
+        );
+    }
+
+    #[test]
+    fn rejects_non_idempotent_codemods() {
+        let swap = [
+            Codemod::RenameFunction { from: "A", to: "B" },
+            Codemod::RenameFunction { from: "B", to: "A" },
+        ];
+        assert!(apply_codemods("test.zen", "A()\n", &swap).is_err());
+    }
+}
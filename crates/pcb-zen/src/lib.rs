@@ -1,11 +1,14 @@
 pub mod archive;
 pub mod ast_utils;
 pub mod cache_index;
+pub mod codemod;
 pub mod diagnostics;
 pub mod git;
 pub mod import_scanner;
+pub mod lock_drift;
 pub mod lsp;
 pub mod package_resolver;
+pub mod provenance;
 pub mod resolve;
 pub mod suppression;
 pub mod tags;
@@ -16,10 +19,12 @@ use std::path::Path;
 use std::sync::Arc;
 
 use pcb_sch::Schematic;
+use pcb_zen_core::lang::secret::SecretInputs;
 use pcb_zen_core::resolution::ResolutionResult;
 use pcb_zen_core::{DefaultFileProvider, EvalContext, EvalOutput};
 use serde_json::Value as JsonValue;
 use starlark::collections::SmallMap;
+use workspace::WorkspaceInfoExt;
 
 pub use package_resolver::resolve_workspace_dependencies;
 pub use pcb_zen_core::file_extensions;
@@ -33,14 +38,34 @@ pub fn eval(
     file: &Path,
     resolution_result: ResolutionResult,
     inputs: SmallMap<String, JsonValue>,
+) -> WithDiagnostics<EvalOutput> {
+    eval_with_secrets(file, resolution_result, inputs, SecretInputs::default())
+}
+
+/// [`eval`] with sensitive root inputs, which are redacted from the schematic.
+///
+/// When `file` is a board's entry point, its `[board.target]` flags are set.
+pub fn eval_with_secrets(
+    file: &Path,
+    resolution_result: ResolutionResult,
+    inputs: SmallMap<String, JsonValue>,
+    secrets: SecretInputs,
 ) -> WithDiagnostics<EvalOutput> {
     let abs_path = file
         .canonicalize()
         .expect("failed to canonicalise input path");
 
+    let target_flags = resolution_result
+        .workspace_info
+        .board_config_for_zen(&abs_path)
+        .map(|board| board.target)
+        .unwrap_or_default();
+
     let file_provider = Arc::new(DefaultFileProvider::new());
     let mut ctx = EvalContext::new(file_provider, resolution_result).set_source_path(abs_path);
     ctx.set_json_inputs(inputs);
+    ctx.set_secret_inputs(secrets);
+    ctx.set_target_flags(target_flags);
     ctx.eval()
 }
 
@@ -79,16 +104,8 @@ pub fn lsp_with_eager(eager: bool) -> anyhow::Result<()> {
     pcb_starlark_lsp::server::stdio_server(ctx)
 }
 
-/// Start the LSP server with `eager` and a custom request handler.
-pub fn lsp_with_custom_request_handler<F>(eager: bool, handler: F) -> anyhow::Result<()>
-where
-    F: Fn(&str, &serde_json::Value) -> anyhow::Result<Option<serde_json::Value>>
-        + Send
-        + Sync
-        + 'static,
-{
-    let ctx = lsp::LspEvalContext::default()
-        .set_eager(eager)
-        .with_custom_request_handler(handler);
+/// Start the LSP server with a preconfigured context, e.g. one with a custom
+/// request handler.
+pub fn lsp_with_context(ctx: lsp::LspEvalContext) -> anyhow::Result<()> {
     pcb_starlark_lsp::server::stdio_server(ctx)
 }
@@ -0,0 +1,202 @@
+//! Dependency state a build used that the repository doesn't record.
+//!
+//! Builds take dependency versions from the `[dependencies]` tables of the
+//! workspace's `pcb.toml` files and package sources from `vendor/` before the
+//! shared cache. A build is only reproducible from a checkout when both are
+//! committed, so anything else is reported:
+//!
+//! - a `pcb.toml` whose dependency tables differ from the committed ones, e.g.
+//!   after `pcb sync` moved a branch dependency to a newer commit;
+//! - uncommitted changes under `vendor/`;
+//! - resolved packages that `[workspace].vendor` covers but that are missing
+//!   from `vendor/`, so they were loaded from the cache.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use pcb_zen_core::config::PcbToml;
+use pcb_zen_core::resolution::ResolutionResult;
+
+use crate::git;
+use crate::resolve::plan_vendor_package_roots;
+use crate::workspace::WorkspaceInfo;
+
+/// One way a build differs from the committed dependency state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockDrift {
+    /// The dependency tables of `manifest` differ from the committed ones.
+    UncommittedDependencies { manifest: PathBuf },
+    /// `vendor_dir` has uncommitted changes.
+    UncommittedVendor { vendor_dir: PathBuf },
+    /// A vendored package was loaded from the cache.
+    NotVendored {
+        vendor_dir: PathBuf,
+        module_path: String,
+        version: String,
+    },
+}
+
+impl LockDrift {
+    /// File or directory the drift is in.
+    pub fn path(&self) -> &Path {
+        match self {
+            LockDrift::UncommittedDependencies { manifest } => manifest,
+            LockDrift::UncommittedVendor { vendor_dir }
+            | LockDrift::NotVendored { vendor_dir, .. } => vendor_dir,
+        }
+    }
+}
+
+impl fmt::Display for LockDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockDrift::UncommittedDependencies { .. } => write!(
+                f,
+                "Dependencies in pcb.toml differ from the committed ones; commit them so other checkouts build the same versions"
+            ),
+            LockDrift::UncommittedVendor { .. } => write!(
+                f,
+                "vendor/ has uncommitted changes; this build may not match other checkouts"
+            ),
+            LockDrift::NotVendored {
+                module_path,
+                version,
+                ..
+            } => write!(
+                f,
+                "{module_path}@{version} is not in vendor/ and was loaded from the cache; run `pcb vendor`"
+            ),
+        }
+    }
+}
+
+/// Everything `resolution` used that the repository containing its workspace
+/// doesn't record. Empty outside a git repository with at least one commit.
+pub fn lock_drift(resolution: &ResolutionResult) -> Vec<LockDrift> {
+    let workspace = &resolution.workspace_info;
+    let vendor_dir = workspace.root.join("vendor");
+    let mut drift = Vec::new();
+
+    if git::rev_parse_head(&workspace.root).is_some() {
+        for manifest in manifest_paths(workspace) {
+            if dependencies_changed(&manifest) {
+                drift.push(LockDrift::UncommittedDependencies { manifest });
+            }
+        }
+        if vendor_dir.exists() && git::has_uncommitted_changes_in_path(&workspace.root, &vendor_dir)
+        {
+            drift.push(LockDrift::UncommittedVendor {
+                vendor_dir: vendor_dir.clone(),
+            });
+        }
+    }
+
+    let package_roots: BTreeSet<(String, String)> = resolution
+        .remote_package_versions()
+        .into_iter()
+        .flat_map(|(path, versions)| {
+            versions
+                .into_iter()
+                .map(move |version| (path.clone(), version))
+        })
+        .collect();
+    let Ok(plan) = plan_vendor_package_roots(workspace, &package_roots, &[], None, false) else {
+        return drift;
+    };
+    for copy in plan.copies {
+        let Ok(rel) = copy.dst.strip_prefix(&plan.vendor_dir) else {
+            continue;
+        };
+        let (Some(module_path), Some(version)) = (rel.parent(), rel.file_name()) else {
+            continue;
+        };
+        drift.push(LockDrift::NotVendored {
+            vendor_dir: plan.vendor_dir.clone(),
+            module_path: module_path.to_string_lossy().replace('\\', "/"),
+            version: version.to_string_lossy().into_owned(),
+        });
+    }
+
+    drift
+}
+
+/// `pcb.toml` of the workspace root and of each workspace package.
+fn manifest_paths(workspace: &WorkspaceInfo) -> BTreeSet<PathBuf> {
+    std::iter::once(workspace.root.join("pcb.toml"))
+        .chain(
+            workspace
+                .packages
+                .values()
+                .map(|package| workspace.root.join(&package.rel_path).join("pcb.toml")),
+        )
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Whether the dependency tables of `manifest` differ from those at `HEAD`.
+/// An untracked manifest has changed if it declares any dependencies.
+fn dependencies_changed(manifest: &Path) -> bool {
+    let Some(dir) = manifest.parent() else {
+        return false;
+    };
+    let Some(current) = std::fs::read_to_string(manifest)
+        .ok()
+        .and_then(|content| PcbToml::parse(&content).ok())
+    else {
+        return false;
+    };
+    match git::run_output_opt(dir, &["show", "HEAD:./pcb.toml"]) {
+        Some(committed) => PcbToml::parse(&committed)
+            .is_ok_and(|committed| committed.dependencies != current.dependencies),
+        None => !current.dependencies.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn only_dependency_edits_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("pcb.toml");
+        std::fs::write(
+            &manifest,
+            "[dependencies]\n\"github.com/acme/lib\" = \"0.3.1\"\n",
+        )
+        .unwrap();
+
+        git(dir.path(), &["init", "-q"]);
+        assert!(dependencies_changed(&manifest));
+        git(dir.path(), &["add", "pcb.toml"]);
+        git(dir.path(), &["commit", "-q", "-m", "init"]);
+        assert!(!dependencies_changed(&manifest));
+
+        std::fs::write(
+            &manifest,
+            "# Main board\n[dependencies]\n\"github.com/acme/lib\" = \"0.3.1\"\n",
+        )
+        .unwrap();
+        assert!(!dependencies_changed(&manifest));
+
+        std::fs::write(
+            &manifest,
+            "[dependencies]\n\"github.com/acme/lib\" = \"0.3.2\"\n",
+        )
+        .unwrap();
+        assert!(dependencies_changed(&manifest));
+    }
+}
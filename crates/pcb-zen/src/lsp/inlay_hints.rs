@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use lsp_types::{InlayHint, InlayHintKind, InlayHintLabel, Position, Range};
+use serde::Deserialize;
+use starlark::codemap::{CodeMap, ResolvedPos};
+use starlark::syntax::AstModule;
+use starlark::syntax::ast::*;
+use starlark_syntax::syntax::module::AstModuleFields;
+
+use pcb_starlark_lsp::server::{LspContext, LspUrl};
+
+use crate::lsp::signature::function_params;
+
+/// Which inlay hints to show, from `initializationOptions.inlayHints`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InlayHintSettings {
+    /// Parameter names before positional arguments.
+    pub parameter_names: bool,
+    /// Evaluated physical values after computed top-level assignments.
+    pub values: bool,
+}
+
+impl Default for InlayHintSettings {
+    fn default() -> Self {
+        Self {
+            parameter_names: true,
+            values: true,
+        }
+    }
+}
+
+impl InlayHintSettings {
+    pub fn from_initialize_params(params: &lsp_types::InitializeParams) -> Self {
+        params
+            .initialization_options
+            .as_ref()
+            .and_then(|options| options.get("inlayHints"))
+            .and_then(|hints| serde_json::from_value(hints.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Inlay hints for the part of `ast` inside `range`.
+///
+/// `values` maps top-level names to their formatted physical value from the
+/// last evaluation of the file.
+pub fn inlay_hints<T: LspContext>(
+    ast: &AstModule,
+    range: Range,
+    settings: &InlayHintSettings,
+    values: &HashMap<String, String>,
+    ctx: &T,
+    current_uri: &LspUrl,
+) -> Vec<InlayHint> {
+    let mut hints = Vec::new();
+    if settings.parameter_names {
+        parameter_name_hints(ast, range, ctx, current_uri, &mut hints);
+    }
+    if settings.values {
+        value_hints(ast, range, values, &mut hints);
+    }
+    hints.sort_by_key(|hint| (hint.position.line, hint.position.character));
+    hints
+}
+
+fn parameter_name_hints<T: LspContext>(
+    ast: &AstModule,
+    range: Range,
+    ctx: &T,
+    current_uri: &LspUrl,
+    hints: &mut Vec<InlayHint>,
+) {
+    let mut calls = Vec::new();
+    ast.statement()
+        .visit_expr(|expr| collect_calls(expr, ast.codemap(), range, &mut calls));
+
+    let mut params_by_name: HashMap<String, Vec<String>> = HashMap::new();
+    let mut loaded = None;
+    for (function_name, args) in calls {
+        let params = params_by_name
+            .entry(function_name.clone())
+            .or_insert_with(|| {
+                function_params(ast, &function_name, ctx, current_uri, &mut loaded)
+                    .into_iter()
+                    .filter(|param| is_identifier(param))
+                    .collect()
+            });
+
+        for ((pos, arg_text), param) in args.into_iter().zip(params.iter()) {
+            // `f(vout)` for parameter `vout` needs no hint.
+            if arg_text.as_deref() == Some(param.as_str()) {
+                continue;
+            }
+            hints.push(InlayHint {
+                position: to_lsp(pos),
+                label: InlayHintLabel::String(format!("{param}:")),
+                kind: Some(InlayHintKind::PARAMETER),
+                text_edits: None,
+                tooltip: None,
+                padding_left: None,
+                padding_right: Some(true),
+                data: None,
+            });
+        }
+    }
+}
+
+/// Start of each positional argument and, for a bare identifier, its name.
+type PositionalArgs = Vec<(ResolvedPos, Option<String>)>;
+
+/// Collect `(function name, positional args)` for identifier calls with at
+/// least two positional arguments.
+fn collect_calls<P: AstPayload>(
+    expr: &AstExprP<P>,
+    codemap: &CodeMap,
+    range: Range,
+    out: &mut Vec<(String, PositionalArgs)>,
+) {
+    let span = codemap.resolve_span(expr.span);
+    if to_lsp(span.end) < range.start || to_lsp(span.begin) > range.end {
+        return;
+    }
+
+    if let ExprP::Call(target, args) = &expr.node
+        && let ExprP::Identifier(ident) = &target.node
+    {
+        // Positional arguments come first; stop at the first named or `*` one.
+        let positional: Vec<_> = args
+            .args
+            .iter()
+            .map_while(|arg| match &arg.node {
+                ArgumentP::Positional(value) => Some(value),
+                _ => None,
+            })
+            .map(|value| {
+                let text = match &value.node {
+                    ExprP::Identifier(ident) => Some(ident.node.ident.clone()),
+                    _ => None,
+                };
+                (codemap.resolve_span(value.span).begin, text)
+            })
+            .collect();
+        // A lone argument is usually obvious from the function name.
+        if positional.len() > 1 {
+            out.push((ident.node.ident.clone(), positional));
+        }
+    }
+    expr.visit_expr(|child| collect_calls(child, codemap, range, out));
+}
+
+/// `= <value>` after each computed top-level assignment whose value is known.
+fn value_hints(
+    ast: &AstModule,
+    range: Range,
+    values: &HashMap<String, String>,
+    hints: &mut Vec<InlayHint>,
+) {
+    let statements = match &ast.statement().node {
+        StmtP::Statements(statements) => statements.as_slice(),
+        _ => std::slice::from_ref(ast.statement()),
+    };
+
+    let mut assignments: HashMap<&str, Vec<&AstExpr>> = HashMap::new();
+    for stmt in statements {
+        if let StmtP::Assign(AssignP { lhs, rhs, .. }) = &stmt.node
+            && let AssignTargetP::Identifier(ident) = &lhs.node
+        {
+            assignments.entry(&ident.node.ident).or_default().push(rhs);
+        }
+    }
+
+    for (name, rhs) in assignments {
+        // A reassigned name only has its final value.
+        let [rhs] = rhs.as_slice() else {
+            continue;
+        };
+        let Some(value) = values.get(name) else {
+            continue;
+        };
+        if is_constant(rhs) {
+            continue;
+        }
+        let end = to_lsp(ast.codemap().resolve_span(rhs.span).end);
+        if end < range.start || end > range.end {
+            continue;
+        }
+        hints.push(InlayHint {
+            position: end,
+            label: InlayHintLabel::String(format!("= {value}")),
+            kind: None,
+            text_edits: None,
+            tooltip: None,
+            padding_left: Some(true),
+            padding_right: None,
+            data: None,
+        });
+    }
+}
+
+/// A literal, or a call such as `Voltage("3.3V")` whose arguments are all
+/// literals, which already shows its value.
+fn is_constant(expr: &AstExpr) -> bool {
+    match &expr.node {
+        ExprP::Literal(_) => true,
+        ExprP::Minus(inner) => matches!(inner.node, ExprP::Literal(_)),
+        ExprP::Call(_, args) => args.args.iter().all(|arg| is_constant(arg.node.expr())),
+        _ => false,
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn to_lsp(pos: ResolvedPos) -> Position {
+    Position {
+        line: pos.line as u32,
+        character: pos.column as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> AstModule {
+        AstModule::parse(
+            "test.zen",
+            source.to_string(),
+            &starlark::syntax::Dialect::Extended,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn skips_constant_assignments() {
+        let ast = parse("a = Voltage(\"3.3V\")\nb = a * 2\nc = -1\nc = 2\n");
+        let values = HashMap::from([
+            ("a".to_string(), "3.3V".to_string()),
+            ("b".to_string(), "6.6V".to_string()),
+            ("c".to_string(), "2".to_string()),
+        ]);
+        let range = Range::new(Position::new(0, 0), Position::new(10, 0));
+        let mut hints = Vec::new();
+        value_hints(&ast, range, &values, &mut hints);
+
+        assert_eq!(hints.len(), 1);
+        assert_eq!(hints[0].position, Position::new(1, 9));
+        assert!(matches!(&hints[0].label, InlayHintLabel::String(label) if label == "= 6.6V"));
+    }
+
+    #[test]
+    fn collects_positional_calls() {
+        let ast = parse("divider(vin, 10, ratio = 2)\nNet(\"VCC\")\n");
+        let range = Range::new(Position::new(0, 0), Position::new(10, 0));
+        let mut calls = Vec::new();
+        ast.statement()
+            .visit_expr(|expr| collect_calls(expr, ast.codemap(), range, &mut calls));
+
+        assert_eq!(calls.len(), 1);
+        let (name, args) = &calls[0];
+        assert_eq!(name, "divider");
+        assert_eq!(args.len(), 2);
+        assert_eq!(args[0].1.as_deref(), Some("vin"));
+        assert_eq!(args[1].1, None);
+    }
+}
@@ -1,10 +1,11 @@
+pub mod inlay_hints;
 pub mod signature;
 
 use log::{debug, info};
 use lsp_server::ResponseError;
 use lsp_types::{
-    Hover, HoverContents, MarkupContent, MarkupKind, ServerCapabilities, SignatureHelpOptions, Url,
-    WorkDoneProgressOptions, request::Request,
+    Hover, HoverContents, MarkupContent, MarkupKind, OneOf, ServerCapabilities,
+    SignatureHelpOptions, Url, WorkDoneProgressOptions, request::Request,
 };
 use pcb_sch::position::{Position, edit_position_comments, symbol_id_to_comment_key};
 use pcb_starlark_lsp::server::{
@@ -12,10 +13,12 @@ use pcb_starlark_lsp::server::{
 };
 use pcb_zen_core::config::find_workspace_root;
 use pcb_zen_core::file_extensions::is_kicad_symbol_file;
+use pcb_zen_core::lang::exports;
 use pcb_zen_core::lang::symbol::invalidate_symbol_library;
 use pcb_zen_core::lang::type_info::ParameterInfo;
 use pcb_zen_core::{
-    DefaultFileProvider, EvalContext, EvalContextConfig, FileProvider, FileProviderError,
+    DefaultFileProvider, EvalContext, EvalContextConfig, FileMetadata, FileProvider,
+    FileProviderError,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -64,12 +67,22 @@ pub struct LspEvalContext {
     /// Per-file cache of the schematic computed right after evaluation, before
     /// the shared session module tree can be contaminated by other files.
     last_schematics: Arc<RwLock<HashMap<PathBuf, pcb_sch::Schematic>>>,
+    /// Per-file formatted physical values of top-level names, for inlay hints.
+    last_values: Arc<RwLock<HashMap<PathBuf, HashMap<String, String>>>>,
+    /// Metadata of watched files as of their last change notification, so
+    /// repeated notifications for the same write are ignored.
+    watched_metadata: RwLock<HashMap<PathBuf, FileMetadata>>,
     custom_request_handler: Option<Arc<CustomRequestHandler>>,
+    exported_symbols: Option<Arc<ExportedSymbolsProvider>>,
 }
 
 type CustomRequestHandler =
     dyn Fn(&str, &JsonValue) -> anyhow::Result<Option<JsonValue>> + Send + Sync;
 
+/// Prebuilt exports of a file, such as a docs index of a remote package.
+type ExportedSymbolsProvider =
+    dyn Fn(&Path) -> Option<Vec<(String, pcb_zen_core::SymbolInfo)>> + Send + Sync;
+
 #[derive(Default)]
 struct NetlistSubscription {
     inputs: HashMap<String, JsonValue>,
@@ -141,6 +154,18 @@ impl FileProvider for OverlayFileProvider {
     fn cache_dir(&self) -> std::path::PathBuf {
         self.base.cache_dir()
     }
+
+    fn metadata(&self, path: &Path) -> Result<FileMetadata, FileProviderError> {
+        if let Some(contents) = self.lookup(path) {
+            return Ok(FileMetadata::from_contents(&contents));
+        }
+
+        self.base.metadata(path)
+    }
+
+    fn write_file(&self, path: &Path, contents: &str) -> Result<(), FileProviderError> {
+        self.base.write_file(path, contents)
+    }
 }
 
 /// Create a load resolver rooted at `workspace_root` with optional dependency resolution.
@@ -192,7 +217,10 @@ impl Default for LspEvalContext {
             open_files,
             netlist_subscriptions: Arc::new(RwLock::new(HashMap::new())),
             last_schematics: Arc::new(RwLock::new(HashMap::new())),
+            last_values: Arc::new(RwLock::new(HashMap::new())),
+            watched_metadata: RwLock::new(HashMap::new()),
             custom_request_handler: None,
+            exported_symbols: None,
         }
     }
 }
@@ -221,6 +249,20 @@ impl LspEvalContext {
         self
     }
 
+    /// Answer export completions for the files `provider` knows from its
+    /// index instead of parsing them.
+    pub fn with_exported_symbols<F>(mut self, provider: F) -> Self
+    where
+        F: Fn(&Path) -> Option<Vec<(String, pcb_zen_core::SymbolInfo)>> + Send + Sync + 'static,
+    {
+        self.exported_symbols = Some(Arc::new(provider));
+        self
+    }
+
+    fn indexed_exports(&self, path: &Path) -> Option<Vec<(String, pcb_zen_core::SymbolInfo)>> {
+        self.exported_symbols.as_ref()?(path)
+    }
+
     fn open_file_contents(&self, path: &Path) -> Option<String> {
         if let Some(contents) = self.open_files.read().unwrap().get(path) {
             return Some(contents.clone());
@@ -252,6 +294,19 @@ impl LspEvalContext {
         }
     }
 
+    /// Record the current metadata of a watched file. Returns false when it
+    /// matches the last notification, i.e. the file did not actually change.
+    fn watched_metadata_changed(&self, path: &Path) -> bool {
+        let mut watched = self.watched_metadata.write().unwrap();
+        match self.file_provider.metadata(path) {
+            Ok(metadata) => watched.insert(path.to_path_buf(), metadata.clone()) != Some(metadata),
+            Err(_) => {
+                watched.remove(path);
+                true
+            }
+        }
+    }
+
     fn maybe_invalidate_symbol_library(&self, path: &Path) {
         if is_kicad_symbol_file(path.extension()) {
             invalidate_symbol_library(path, self.file_provider.as_ref());
@@ -343,6 +398,61 @@ impl LspEvalContext {
         self.last_schematics.write().unwrap().remove(&key);
     }
 
+    fn set_last_values(&self, path: &Path, output: &pcb_zen_core::EvalOutput) {
+        let values = output
+            .star_module
+            .names()
+            .filter_map(|name| {
+                let value = output.star_module.get_option(name.as_str()).ok()??;
+                let physical = value
+                    .value()
+                    .downcast_ref::<pcb_sch::physical::PhysicalValue>()?
+                    .to_string();
+                Some((name.as_str().to_string(), physical))
+            })
+            .collect();
+        let key = self.normalize_path(path);
+        self.last_values.write().unwrap().insert(key, values);
+    }
+
+    /// Inlay hints for `uri` within `range`, as enabled by the client's
+    /// `inlayHints` initialization options. Unparseable files get none.
+    fn inlay_hints(
+        &self,
+        uri: &LspUrl,
+        range: lsp_types::Range,
+        initialize_params: &lsp_types::InitializeParams,
+    ) -> Vec<lsp_types::InlayHint> {
+        let settings = inlay_hints::InlayHintSettings::from_initialize_params(initialize_params);
+        let Ok(Some(contents)) = self.get_load_contents(uri) else {
+            return Vec::new();
+        };
+        let mut dialect = starlark::syntax::Dialect::Extended;
+        dialect.enable_f_strings = true;
+        let Ok(ast) = starlark::syntax::AstModule::parse(
+            uri.path().to_string_lossy().as_ref(),
+            contents,
+            &dialect,
+        ) else {
+            return Vec::new();
+        };
+        let values = match uri {
+            LspUrl::File(path) => self.get_last_values(path),
+            _ => HashMap::new(),
+        };
+        inlay_hints::inlay_hints(&ast, range, &settings, &values, self, uri)
+    }
+
+    fn get_last_values(&self, path: &Path) -> HashMap<String, String> {
+        let key = self.normalize_path(path);
+        self.last_values
+            .read()
+            .unwrap()
+            .get(&key)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     fn maybe_update_symbol_watch_paths_from_response(
         &self,
         source_path: &Path,
@@ -514,7 +624,8 @@ impl LspEvalContext {
 
     fn diagnostic_to_lsp(&self, diag: &pcb_zen_core::Diagnostic) -> lsp_types::Diagnostic {
         use lsp_types::{
-            DiagnosticRelatedInformation, DiagnosticSeverity, Location, Position, Range,
+            DiagnosticRelatedInformation, DiagnosticSeverity, DiagnosticTag, Location, Position,
+            Range,
         };
 
         let to_location = |path: &str, span: &starlark::codemap::ResolvedSpan| Location {
@@ -622,7 +733,10 @@ impl LspEvalContext {
             } else {
                 Some(related)
             },
-            tags: None,
+            // Lets editors strike through uses of deprecated APIs.
+            tags: pcb_zen_core::diagnostics::diagnostic_kind(diag)
+                .filter(|kind| kind.starts_with("deprecated."))
+                .map(|_| vec![DiagnosticTag::DEPRECATED]),
             data: Self::diagnostic_target_uri(&diag.path).map(|uri| json!({ "targetUri": uri })),
         }
     }
@@ -638,6 +752,7 @@ impl LspContext for LspEvalContext {
                     work_done_progress: None,
                 },
             }),
+            inlay_hint_provider: Some(OneOf::Left(true)),
             ..ServerCapabilities::default()
         }
     }
@@ -658,6 +773,10 @@ impl LspContext for LspEvalContext {
             self.netlist_subscriptions.write().unwrap().remove(&key);
             self.inner.invalidate_file(path);
             self.clear_last_schematic(path);
+            self.last_values
+                .write()
+                .unwrap()
+                .remove(&self.normalize_path(path));
             self.maybe_invalidate_symbol_library(path);
             self.maybe_invalidate_resolution_cache(path);
         }
@@ -672,6 +791,9 @@ impl LspContext for LspEvalContext {
     fn watched_file_changed(&self, uri: &LspUrl) -> bool {
         match uri {
             LspUrl::File(path) => {
+                if !self.watched_metadata_changed(path) {
+                    return false;
+                }
                 let mut should_revalidate = false;
 
                 if is_kicad_symbol_file(path.extension()) {
@@ -819,6 +941,7 @@ impl LspContext for LspEvalContext {
                 result.diagnostics.apply_passes(&passes);
 
                 if let Some(parsed) = result.output.as_ref() {
+                    self.set_last_values(path, parsed);
                     // Cache the schematic now, while the session module tree
                     // still reflects only this file's evaluation.
                     if let Ok(sch) = parsed.to_schematic() {
@@ -863,6 +986,9 @@ impl LspContext for LspEvalContext {
             LspUrl::File(current_path) => {
                 let config = self.config_for(current_path);
                 let resolved = config.resolve_path(path, current_path)?;
+                if self.file_provider.is_directory(&resolved) {
+                    return Ok(LspUrl::File(exports::exports_path(&resolved)));
+                }
                 Ok(LspUrl::File(resolved))
             }
             _ => Err(anyhow::anyhow!("Cannot resolve load from non-file URL")),
@@ -924,9 +1050,19 @@ impl LspContext for LspEvalContext {
                 }
 
                 if self.file_provider.exists(path) {
-                    Ok(Some(self.file_provider.read_file(path)?))
-                } else {
-                    Ok(None)
+                    return Ok(Some(self.file_provider.read_file(path)?));
+                }
+
+                // Directory loads resolve to a synthesized export manifest.
+                match path.parent() {
+                    Some(dir)
+                        if path.file_name()
+                            == Some(std::ffi::OsStr::new(exports::EXPORTS_FILE))
+                            && self.file_provider.is_directory(dir) =>
+                    {
+                        exports::directory_exports_source(self.file_provider.as_ref(), dir)
+                    }
+                    _ => Ok(None),
                 }
             }
             LspUrl::Starlark(_) => {
@@ -978,6 +1114,16 @@ impl LspContext for LspEvalContext {
                     });
                 }
 
+                // Then the index of the file that defines it
+                if let Some(target) = self.inner.get_url_for_global_symbol(path, symbol)
+                    && let Some(info) = self
+                        .indexed_exports(&target)
+                        .and_then(|exports| exports.into_iter().find(|(name, _)| name == symbol))
+                        .map(|(_, info)| info)
+                {
+                    return Some(completion_meta(info));
+                }
+
                 // Fallback to builtin docs
                 if let Ok(parsed_url) = Url::parse(&format!("starlark:/{symbol}.zen"))
                     && let Ok(lsp_url) = LspUrl::try_from(parsed_url)
@@ -996,6 +1142,18 @@ impl LspContext for LspEvalContext {
         }
     }
 
+    fn get_indexed_exports(&self, uri: &LspUrl) -> Option<Vec<(String, CompletionMeta)>> {
+        let LspUrl::File(path) = uri else {
+            return None;
+        };
+        Some(
+            self.indexed_exports(path)?
+                .into_iter()
+                .map(|(name, info)| (name, completion_meta(info)))
+                .collect(),
+        )
+    }
+
     fn is_eager(&self) -> bool {
         self.inner.is_eager()
     }
@@ -1042,9 +1200,38 @@ impl LspContext for LspEvalContext {
     fn handle_custom_request(
         &self,
         req: &server::Request,
-        _initialize_params: &lsp_types::InitializeParams,
+        initialize_params: &lsp_types::InitializeParams,
     ) -> Option<Response> {
         debug!("Received custom request: method={}", req.method);
+        if req.method == lsp_types::request::InlayHintRequest::METHOD {
+            let result = serde_json::from_value::<lsp_types::InlayHintParams>(req.params.clone())
+                .map_err(|e| format!("Failed to parse params: {e}"))
+                .and_then(|params| {
+                    let uri: LspUrl = params
+                        .text_document
+                        .uri
+                        .try_into()
+                        .map_err(|e| format!("Invalid URI: {e}"))?;
+                    Ok(self.inlay_hints(&uri, params.range, initialize_params))
+                });
+            return Some(match result {
+                Ok(hints) => Response {
+                    id: req.id.clone(),
+                    result: Some(serde_json::to_value(hints).unwrap()),
+                    error: None,
+                },
+                Err(message) => Response {
+                    id: req.id.clone(),
+                    result: None,
+                    error: Some(ResponseError {
+                        code: INVALID_PARAMS,
+                        message,
+                        data: None,
+                    }),
+                },
+            });
+        }
+
         // Handle signature help requests
         if req.method == "textDocument/signatureHelp" {
             match serde_json::from_value::<lsp_types::SignatureHelpParams>(req.params.clone()) {
@@ -1400,6 +1587,24 @@ impl LspEvalContext {
     }
 }
 
+fn completion_meta(info: pcb_zen_core::SymbolInfo) -> CompletionMeta {
+    use lsp_types::CompletionItemKind;
+    let kind = match info.kind {
+        pcb_zen_core::SymbolKind::Function => CompletionItemKind::FUNCTION,
+        pcb_zen_core::SymbolKind::Module | pcb_zen_core::SymbolKind::Component => {
+            CompletionItemKind::MODULE
+        }
+        pcb_zen_core::SymbolKind::Class => CompletionItemKind::CLASS,
+        pcb_zen_core::SymbolKind::Variable => CompletionItemKind::CONSTANT,
+        pcb_zen_core::SymbolKind::Interface => CompletionItemKind::INTERFACE,
+    };
+    CompletionMeta {
+        kind: Some(kind),
+        detail: Some(info.type_name),
+        documentation: info.documentation,
+    }
+}
+
 fn position_edit_result_to_response(
     id: lsp_server::RequestId,
     result: Result<PcbPositionEditResponse, ResponseError>,
@@ -188,33 +188,19 @@ pub fn load_symbols_info<T: LspContext>(
     (param_map, url_map)
 }
 
-/// Produce an LSP [`SignatureHelp`] value for the given AST and cursor position.
-///
-/// The implementation first searches for a `def` statement *within the current file*.
-/// If no matching definition is found, it consults the surrounding [`Context`] to
-/// resolve the symbol – this allows it to discover functions that were imported via
-/// `load()` as well as built-in Starlark globals.
-pub fn signature_help<T: LspContext>(
+/// Parameter names of `function_name` as seen from `current_uri`: a `def` in
+/// the current file, then a global symbol, then a `load()`ed definition.
+/// `loaded` caches the `load()` lookup so callers resolving many functions
+/// parse each loaded module once.
+pub(crate) fn function_params<T: LspContext>(
     ast: &AstModule,
-    line: u32,
-    character: u32,
+    function_name: &str,
     ctx: &T,
     current_uri: &LspUrl,
-) -> SignatureHelp {
-    let calls = calls_at_position(ast, line, character).unwrap_or_default();
-    if calls.is_empty() {
-        return SignatureHelp {
-            signatures: vec![],
-            active_signature: None,
-            active_parameter: None,
-        };
-    }
-
-    // Pick the innermost call (last in vector).
-    let call = calls.last().unwrap();
-
+    loaded: &mut Option<HashMap<String, Vec<String>>>,
+) -> Vec<String> {
     // Attempt to find parameter list for function.
-    let mut params = find_def_params(ast.statement(), &call.function_name).unwrap_or_default();
+    let mut params = find_def_params(ast.statement(), function_name).unwrap_or_default();
 
     // ------------------------------------------------------------------
     // Fast path: if the Context already knows the parameter list (e.g. for a
@@ -227,9 +213,7 @@ pub fn signature_help<T: LspContext>(
     // ------------------------------------------------------------------
     if params.is_empty() {
         // Resolve the target URL for the symbol (if any).
-        if let Ok(Some(target_url)) =
-            ctx.get_url_for_global_symbol(current_uri, &call.function_name)
-        {
+        if let Ok(Some(target_url)) = ctx.get_url_for_global_symbol(current_uri, function_name) {
             match &target_url {
                 // User-defined Starlark file – parse its AST and look for `def`.
                 LspUrl::File(path) => {
@@ -242,17 +226,17 @@ pub fn signature_help<T: LspContext>(
                             contents,
                             &dialect,
                         ) {
-                            params = find_def_params(target_ast.statement(), &call.function_name)
+                            params = find_def_params(target_ast.statement(), function_name)
                                 .unwrap_or_default();
                         }
                     }
                 }
                 // Built-in global – check if we have documentation
                 LspUrl::Starlark(_) => {
-                    if let Some(meta) = ctx.get_completion_meta(current_uri, &call.function_name)
+                    if let Some(meta) = ctx.get_completion_meta(current_uri, function_name)
                         && let Some(doc) = meta.documentation
                     {
-                        params = parse_params_from_builtin_doc(&call.function_name, &doc);
+                        params = parse_params_from_builtin_doc(function_name, &doc);
                     }
                 }
                 _ => {}
@@ -266,12 +250,43 @@ pub fn signature_help<T: LspContext>(
     // discover the function definition.
     // ------------------------------------------------------------------
     if params.is_empty() {
-        let (load_param_map, _url_map) = load_symbols_info(ast, ctx, current_uri);
-        if let Some(p) = load_param_map.get(&call.function_name) {
+        let load_param_map =
+            loaded.get_or_insert_with(|| load_symbols_info(ast, ctx, current_uri).0);
+        if let Some(p) = load_param_map.get(function_name) {
             params = p.clone();
         }
     }
 
+    params
+}
+
+/// Produce an LSP [`SignatureHelp`] value for the given AST and cursor position.
+///
+/// The implementation first searches for a `def` statement *within the current file*.
+/// If no matching definition is found, it consults the surrounding [`Context`] to
+/// resolve the symbol – this allows it to discover functions that were imported via
+/// `load()` as well as built-in Starlark globals.
+pub fn signature_help<T: LspContext>(
+    ast: &AstModule,
+    line: u32,
+    character: u32,
+    ctx: &T,
+    current_uri: &LspUrl,
+) -> SignatureHelp {
+    let calls = calls_at_position(ast, line, character).unwrap_or_default();
+    if calls.is_empty() {
+        return SignatureHelp {
+            signatures: vec![],
+            active_signature: None,
+            active_parameter: None,
+        };
+    }
+
+    // Pick the innermost call (last in vector).
+    let call = calls.last().unwrap();
+
+    let params = function_params(ast, &call.function_name, ctx, current_uri, &mut None);
+
     let label = format!("{}({})", call.function_name, params.join(", "));
 
     let parameters: Vec<ParameterInformation> = params
@@ -1,4 +1,7 @@
 use std::collections::BTreeSet;
+use std::io::IsTerminal;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::cache_index::CacheIndex;
 use anyhow::{Context, Result};
@@ -6,6 +9,26 @@ use semver::Version;
 
 use super::ResolvedDepId;
 
+/// Upper bound on the default number of concurrent package fetches.
+const MAX_DEFAULT_FETCH_JOBS: usize = 8;
+
+/// Configured fetch concurrency; 0 means "pick a default".
+static FETCH_JOBS: AtomicUsize = AtomicUsize::new(0);
+
+/// Set how many packages are fetched concurrently (`pcb --jobs N`).
+pub fn set_fetch_jobs(jobs: usize) {
+    FETCH_JOBS.store(jobs, Ordering::Relaxed);
+}
+
+fn fetch_jobs() -> usize {
+    match FETCH_JOBS.load(Ordering::Relaxed) {
+        0 => std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(MAX_DEFAULT_FETCH_JOBS),
+        jobs => jobs,
+    }
+}
+
 pub(crate) fn materialize_selected<'a>(
     workspace: &crate::WorkspaceInfo,
     selected_remote: impl IntoIterator<Item = (&'a ResolvedDepId, &'a Version)>,
@@ -13,42 +36,81 @@ pub(crate) fn materialize_selected<'a>(
     cache_index: &CacheIndex,
 ) -> Result<BTreeSet<(String, String)>> {
     let mut package_roots = BTreeSet::new();
+    let mut missing = Vec::new();
 
     for (dep_id, version) in selected_remote {
         package_roots.insert((dep_id.path.clone(), version.to_string()));
-        ensure_remote_package_materialized(workspace, &dep_id.path, version, offline, cache_index)?;
+        if is_materialized(workspace, &dep_id.path, version) {
+            continue;
+        }
+        if offline {
+            anyhow::bail!(
+                "{}@{} is not cached. Run `pcb build` once online to fetch it.",
+                dep_id.path,
+                version
+            );
+        }
+        missing.push((dep_id.path.as_str(), version));
     }
 
+    fetch_packages(&missing, cache_index)?;
     Ok(package_roots)
 }
 
-fn ensure_remote_package_materialized(
-    workspace: &crate::WorkspaceInfo,
-    module_path: &str,
-    version: &Version,
-    offline: bool,
-    cache_index: &CacheIndex,
-) -> Result<()> {
-    let version_str = version.to_string();
+fn is_materialized(workspace: &crate::WorkspaceInfo, module_path: &str, version: &Version) -> bool {
     let manifest_rel = std::path::Path::new(module_path)
-        .join(&version_str)
+        .join(version.to_string())
         .join("pcb.toml");
-    if workspace.root.join("vendor").join(&manifest_rel).exists()
+    workspace.root.join("vendor").join(&manifest_rel).exists()
         || workspace.cache_dir.join(&manifest_rel).exists()
-    {
+}
+
+/// Fetch `packages` into the cache, up to [`fetch_jobs`] at a time.
+///
+/// Every package is attempted; the first failure in input order is returned.
+fn fetch_packages(packages: &[(&str, &Version)], cache_index: &CacheIndex) -> Result<()> {
+    if packages.is_empty() {
         return Ok(());
     }
 
-    if offline {
-        anyhow::bail!(
-            "{}@{} is not cached. Run `pcb build` once online to fetch it.",
-            module_path,
-            version_str
-        );
-    }
+    let progress = pcb_ui::ProgressBar::builder(packages.len() as u64)
+        .message("Fetching packages")
+        .hidden(packages.len() == 1 || !std::io::stderr().is_terminal())
+        .start();
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<()>>>> =
+        Mutex::new(packages.iter().map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..fetch_jobs().min(packages.len()) {
+            scope.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some((module_path, version)) = packages.get(i) else {
+                        break;
+                    };
+                    progress.set_message(format!("Fetching {module_path}@{version}"));
+                    let result = crate::resolve::ensure_package_manifest_in_cache(
+                        module_path,
+                        version,
+                        cache_index,
+                    )
+                    .map(|_| ())
+                    .with_context(|| format!("Failed to materialize {}@{}", module_path, version));
+                    results.lock().unwrap()[i] = Some(result);
+                    progress.inc(1);
+                }
+            });
+        }
+    });
+    progress.finish();
 
-    crate::resolve::ensure_package_manifest_in_cache(module_path, version, cache_index)
-        .with_context(|| format!("Failed to materialize {}@{}", module_path, version))?;
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect::<Result<Vec<()>>>()?;
     Ok(())
 }
 
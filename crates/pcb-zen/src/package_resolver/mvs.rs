@@ -250,6 +250,7 @@ impl PackageResolver {
         let imported_workspace_floors = self.import_workspace_floors(&scanned)?;
 
         self.run_remote_mvs(
+            package_url,
             &scanned,
             &current_config.dependencies.direct,
             &imported_workspace_floors,
@@ -427,6 +428,7 @@ impl PackageResolver {
 
     fn run_remote_mvs(
         &mut self,
+        package_url: &str,
         scanned: &ScannedDirectDeps,
         existing_direct: &BTreeMap<String, DependencySpec>,
         imported_workspace_floors: &BTreeMap<ResolvedDepId, Version>,
@@ -434,6 +436,9 @@ impl PackageResolver {
         let mut selected = BTreeMap::<ResolvedDepId, Version>::new();
         let mut queue = VecDeque::<ResolvedDepId>::new();
 
+        for dep_path in scanned.remote.keys() {
+            self.check_sandbox(package_url, dep_path)?;
+        }
         let direct_remote_ids = self.seed_specs(
             &scanned.remote,
             "direct dependency",
@@ -456,6 +461,7 @@ impl PackageResolver {
                 if is_stdlib_module_path(&dep_path) {
                     continue;
                 }
+                self.check_sandbox(&dep_id.path, &dep_path)?;
                 let dep_version = self
                     .spec_resolver
                     .resolve_spec(&dep_path, &dep_spec)
@@ -470,6 +476,7 @@ impl PackageResolver {
                 );
             }
             for (transitive_id, dep_version) in loaded.indirect {
+                self.check_sandbox(&dep_id.path, &transitive_id.path)?;
                 enqueue_floor_version(&mut selected, transitive_id, dep_version, &mut queue);
             }
         }
@@ -487,6 +494,14 @@ impl PackageResolver {
         })
     }
 
+    /// Enforce `[workspace.sandbox]` hosts before `dep_url` is fetched.
+    fn check_sandbox(&self, requirer: &str, dep_url: &str) -> Result<()> {
+        match self.workspace.sandbox() {
+            Some(sandbox) => sandbox.check_dependency(requirer, dep_url),
+            None => Ok(()),
+        }
+    }
+
     fn seed_specs(
         &mut self,
         deps: &BTreeMap<String, DependencySpec>,
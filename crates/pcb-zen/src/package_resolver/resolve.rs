@@ -231,6 +231,9 @@ impl FrozenResolutionBuilder {
     }
 
     fn materialize_selected_remote(&mut self) -> Result<()> {
+        // Packages from hosts outside `[workspace.sandbox]` are not prefetched;
+        // the walk fetches them only for packages granted `any-host`.
+        let sandbox = self.workspace.sandbox();
         let pending: BTreeMap<_, _> = self
             .selected_remote
             .iter()
@@ -238,6 +241,7 @@ impl FrozenResolutionBuilder {
                 !self
                     .materialized_remote
                     .contains(&((*dep_id).clone(), (*version).clone()))
+                    && sandbox.is_none_or(|sandbox| sandbox.allows_host(&dep_id.path))
             })
             .map(|(dep_id, version)| (dep_id.clone(), version.clone()))
             .collect();
@@ -261,11 +265,12 @@ impl FrozenResolutionBuilder {
         node: PackageNode,
         queue: &mut VecDeque<PackageNode>,
     ) -> Result<()> {
-        let (identity, package_root, direct_deps, parts) = match node {
+        let (identity, package_url, package_root, direct_deps, parts) = match node {
             PackageNode::Workspace(package_url) => {
                 let (package_root, config) = self.workspace_manifest(&package_url)?;
                 (
-                    FrozenPackageIdentity::Workspace(package_url),
+                    FrozenPackageIdentity::Workspace(package_url.clone()),
+                    package_url,
                     package_root,
                     config.dependencies.direct,
                     config.parts,
@@ -277,8 +282,10 @@ impl FrozenResolutionBuilder {
                     .manifest_loader
                     .load(&self.cache_index, &dep_id.path, &version)
                     .with_context(|| format!("Failed to load {}@{}", dep_id.path, version))?;
+                let package_url = dep_id.path.clone();
                 (
                     FrozenPackageIdentity::Remote { dep_id, version },
+                    package_url,
                     package_root,
                     manifest.direct,
                     manifest.parts,
@@ -286,7 +293,7 @@ impl FrozenResolutionBuilder {
             }
         };
 
-        let deps = self.resolve_direct_deps(&package_root, &direct_deps, queue)?;
+        let deps = self.resolve_direct_deps(&package_url, &package_root, &direct_deps, queue)?;
         self.packages.insert(
             canonicalize(&package_root),
             FrozenPackage {
@@ -300,6 +307,7 @@ impl FrozenResolutionBuilder {
 
     fn resolve_direct_deps(
         &mut self,
+        package_url: &str,
         package_root: &Path,
         direct_deps: &BTreeMap<String, DependencySpec>,
         queue: &mut VecDeque<PackageNode>,
@@ -322,6 +330,9 @@ impl FrozenResolutionBuilder {
                 continue;
             }
 
+            if let Some(sandbox) = self.workspace.sandbox() {
+                sandbox.check_dependency(package_url, dep_url)?;
+            }
             let requested_version = exact_spec_version(dep_url, spec)?;
             let dep_id = ResolvedDepId::for_version(dep_url.clone(), &requested_version);
             let selected_version = self.selected_remote.get(&dep_id).cloned().ok_or_else(|| {
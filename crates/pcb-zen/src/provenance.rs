@@ -0,0 +1,150 @@
+//! Provenance records for vendored assets (datasheets, 3D models).
+//!
+//! Each package's `pcb.toml` lists `[[provenance]]` entries with the upstream URL
+//! and SHA256 of every asset downloaded into it. [`verify_package_assets`] checks
+//! the files on disk against those records.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use pcb_zen_core::config::{AssetProvenance, PcbToml};
+use sha2::{Digest, Sha256};
+
+/// Hex-encoded SHA256 of `bytes`.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Add or replace provenance entries in the `pcb.toml` of `package_dir`.
+///
+/// Entries are keyed by `path` and kept sorted so repeated downloads produce
+/// stable manifests. A missing `pcb.toml` is created.
+pub fn record_assets(
+    package_dir: &Path,
+    entries: impl IntoIterator<Item = AssetProvenance>,
+) -> Result<()> {
+    let toml_path = package_dir.join("pcb.toml");
+    let mut config = if toml_path.exists() {
+        PcbToml::from_path(&toml_path)?
+    } else {
+        PcbToml::default()
+    };
+
+    for entry in entries {
+        config
+            .provenance
+            .retain(|existing| existing.path != entry.path);
+        config.provenance.push(entry);
+    }
+    config.provenance.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut rendered = toml::to_string_pretty(&config)?;
+    if !rendered.is_empty() && !rendered.ends_with('\n') {
+        rendered.push('\n');
+    }
+    fs::write(&toml_path, rendered)
+        .with_context(|| format!("Failed to write {}", toml_path.display()))
+}
+
+/// Result of checking one vendored asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetStatus {
+    /// File contents match the recorded hash.
+    Verified,
+    /// File contents differ from the recorded hash.
+    Modified { actual: String },
+    /// File no longer exists at the recorded path.
+    Missing,
+    /// Asset was embedded into another file and cannot be checked on disk.
+    Embedded,
+}
+
+impl AssetStatus {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, AssetStatus::Modified { .. } | AssetStatus::Missing)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AssetCheck {
+    /// Absolute path of the asset.
+    pub path: PathBuf,
+    pub provenance: AssetProvenance,
+    pub status: AssetStatus,
+}
+
+/// Check every provenance entry of a package against the files on disk.
+pub fn verify_package_assets(package_dir: &Path, config: &PcbToml) -> Result<Vec<AssetCheck>> {
+    config
+        .provenance
+        .iter()
+        .map(|entry| {
+            let path = package_dir.join(&entry.path);
+            let status = if entry.embedded {
+                AssetStatus::Embedded
+            } else if !path.exists() {
+                AssetStatus::Missing
+            } else {
+                let bytes = fs::read(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                let actual = sha256_hex(&bytes);
+                if actual.eq_ignore_ascii_case(&entry.sha256) {
+                    AssetStatus::Verified
+                } else {
+                    AssetStatus::Modified { actual }
+                }
+            };
+            Ok(AssetCheck {
+                path,
+                provenance: entry.clone(),
+                status,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, bytes: &[u8]) -> AssetProvenance {
+        AssetProvenance {
+            path: path.to_string(),
+            url: format!("https://example.com/{path}"),
+            sha256: sha256_hex(bytes),
+            embedded: false,
+        }
+    }
+
+    #[test]
+    fn record_then_verify_detects_changes() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::create_dir_all(dir.path().join("docs"))?;
+        fs::write(dir.path().join("docs/part.pdf"), b"%PDF original")?;
+        fs::write(dir.path().join("model.step"), b"ISO-10303-21")?;
+
+        record_assets(
+            dir.path(),
+            [
+                entry("model.step", b"ISO-10303-21"),
+                entry("docs/part.pdf", b"%PDF original"),
+                entry("gone.pdf", b"%PDF"),
+            ],
+        )?;
+        fs::write(dir.path().join("model.step"), b"ISO-10303-21 edited")?;
+
+        let config = PcbToml::from_path(&dir.path().join("pcb.toml"))?;
+        let paths: Vec<_> = config.provenance.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(paths, ["docs/part.pdf", "gone.pdf", "model.step"]);
+
+        let statuses: Vec<_> = verify_package_assets(dir.path(), &config)?
+            .into_iter()
+            .map(|check| check.status)
+            .collect();
+        assert_eq!(statuses[0], AssetStatus::Verified);
+        assert_eq!(statuses[1], AssetStatus::Missing);
+        assert!(matches!(statuses[2], AssetStatus::Modified { .. }));
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
 use globset::{Glob, GlobSetBuilder};
+use pcb_zen_core::cancel::CancellationToken;
 use pcb_zen_core::config::{ManifestPart, split_repo_and_subpath};
 use pcb_zen_core::resolution::{FrozenResolutionMap, ResolutionResult, build_package_roots};
 use semver::Version;
@@ -11,9 +12,7 @@ use tracing::instrument;
 use crate::cache_index::{CacheIndex, cache_base, ensure_source_repo, source_repo_dir};
 use crate::git;
 use crate::workspace::WorkspaceInfo;
-use pcb_canonical::{
-    CanonicalTarOptions, compute_content_hash_from_dir, compute_manifest_hash, copy_canonical_files,
-};
+use pcb_canonical::{CanonicalTarOptions, PackageHashes, copy_canonical_files};
 
 /// Result of vendoring operation
 pub struct VendorResult {
@@ -42,17 +41,30 @@ impl VendorPlan {
     }
 
     pub fn apply(&self) -> Result<VendorResult> {
+        self.apply_with_cancellation(&CancellationToken::default())
+    }
+
+    /// Apply the plan, stopping between packages once `cancel` is cancelled.
+    /// A package whose copy fails part-way is removed rather than left
+    /// half-vendored.
+    pub fn apply_with_cancellation(&self, cancel: &CancellationToken) -> Result<VendorResult> {
         for copy in &self.copies {
-            copy_canonical_files(
+            cancel.check(format!("vendoring {}", copy.dst.display()))?;
+            let copied = copy_canonical_files(
                 &copy.src,
                 &copy.dst,
                 Some(CanonicalTarOptions {
                     exclude_nested_packages: true,
                 }),
-            )?;
+            );
+            if let Err(e) = copied {
+                let _ = fs::remove_dir_all(&copy.dst);
+                return Err(e);
+            }
         }
 
         for root in &self.prunes {
+            cancel.check(format!("pruning {}", root.display()))?;
             log::debug!("Pruning stale vendor path: {}", root.display());
             fs::remove_dir_all(root)?;
             remove_empty_ancestors_until(&self.vendor_dir, root)?;
@@ -86,6 +98,7 @@ pub fn vendor_deps(
     additional_patterns: &[String],
     target_vendor_dir: Option<&Path>,
     prune: bool,
+    cancel: &CancellationToken,
 ) -> Result<VendorResult> {
     let package_roots: BTreeSet<_> = resolution
         .remote_package_versions()
@@ -102,6 +115,7 @@ pub fn vendor_deps(
         additional_patterns,
         target_vendor_dir,
         prune,
+        cancel,
     )
 }
 
@@ -112,6 +126,7 @@ pub fn vendor_package_roots(
     additional_patterns: &[String],
     target_vendor_dir: Option<&Path>,
     prune: bool,
+    cancel: &CancellationToken,
 ) -> Result<VendorResult> {
     plan_vendor_package_roots(
         workspace_info,
@@ -120,7 +135,7 @@ pub fn vendor_package_roots(
         target_vendor_dir,
         prune,
     )?
-    .apply()
+    .apply_with_cancellation(cancel)
 }
 
 #[instrument(name = "plan_vendor_package_roots", skip_all)]
@@ -361,12 +376,9 @@ pub fn ensure_package_manifest_in_cache(
 
     ensure_sparse_checkout(&checkout_dir, module_path, &version_str)?;
 
-    let content_hash = compute_content_hash_from_dir(&checkout_dir)?;
-    let manifest_content = std::fs::read_to_string(&pcb_toml_path)?;
-    let manifest_hash = compute_manifest_hash(&manifest_content);
-
-    verify_tag_hashes(module_path, version, &content_hash, &manifest_hash)?;
-    index.set_package(module_path, &version_str, &content_hash, &manifest_hash)?;
+    let hashes = PackageHashes::compute(&checkout_dir)?;
+    verify_tag_hashes(module_path, version, &hashes)?;
+    index.set_package(module_path, &version_str, &hashes.content, &hashes.manifest)?;
 
     Ok(pcb_toml_path)
 }
@@ -421,12 +433,7 @@ pub fn build_frozen_symbol_parts(
 }
 
 /// Verify computed hashes match the expected hashes from the git tag annotation
-fn verify_tag_hashes(
-    module_path: &str,
-    version: &Version,
-    content_hash: &str,
-    manifest_hash: &str,
-) -> Result<()> {
+fn verify_tag_hashes(module_path: &str, version: &Version, hashes: &PackageHashes) -> Result<()> {
     let (repo_url, subpath) = split_repo_and_subpath(module_path);
     let source_dir = source_repo_dir(repo_url)?;
     let tag_name = if subpath.is_empty() {
@@ -441,7 +448,7 @@ fn verify_tag_hashes(
         return Ok(());
     };
 
-    let Some((expected_content, expected_manifest)) = parse_hashes_from_tag_body(&tag_body) else {
+    let Some(expected) = PackageHashes::from_tag_annotation(&tag_body) else {
         return Ok(());
     };
 
@@ -470,15 +477,15 @@ fn verify_tag_hashes(
 
     check_hash(
         "Content",
-        content_hash,
-        &expected_content,
+        &hashes.content,
+        &expected.content,
         module_path,
         version,
     )?;
     check_hash(
         "Manifest",
-        manifest_hash,
-        &expected_manifest,
+        &hashes.manifest,
+        &expected.manifest,
         module_path,
         version,
     )?;
@@ -486,26 +493,6 @@ fn verify_tag_hashes(
     Ok(())
 }
 
-/// Parse content and manifest hashes from tag annotation body
-fn parse_hashes_from_tag_body(body: &str) -> Option<(String, String)> {
-    let mut content_hash = None;
-    let mut manifest_hash = None;
-
-    for line in body.lines() {
-        let line = line.trim();
-        if let Some(hash_start) = line.find(" h1:") {
-            let hash = line[hash_start + 1..].to_string();
-            if line[..hash_start].ends_with("/pcb.toml") {
-                manifest_hash = Some(hash);
-            } else {
-                content_hash = Some(hash);
-            }
-        }
-    }
-
-    content_hash.zip(manifest_hash)
-}
-
 /// Populate a cache directory with exclusive locking.
 ///
 /// Only one process fetches; others wait for the lock and then see the completed result.
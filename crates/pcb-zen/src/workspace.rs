@@ -4,13 +4,13 @@
 //! running full dependency resolution. Used by `pcb info` and other commands
 //! that need workspace metadata.
 
-use anyhow::Result;
-use std::collections::{HashMap, HashSet};
+use anyhow::{Result, bail};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::thread;
 use tracing::{info_span, instrument};
 
-use pcb_zen_core::config::PcbToml;
+use pcb_zen_core::config::{Board, PatchSpec, PcbToml};
 use pcb_zen_core::{DefaultFileProvider, FileProvider};
 use semver::Version;
 
@@ -38,6 +38,7 @@ pub trait WorkspaceInfoExt {
     fn reload(&mut self) -> Result<()>;
     fn board_name_for_zen(&self, zen_path: &Path) -> Option<String>;
     fn board_info_for_zen(&self, zen_path: &Path) -> Option<BoardInfo>;
+    fn board_config_for_zen(&self, zen_path: &Path) -> Option<Board>;
     fn package_url_for_zen(&self, zen_path: &Path) -> Option<String>;
 }
 
@@ -52,7 +53,7 @@ impl WorkspaceInfoExt for WorkspaceInfo {
             let pkg_toml_path = pkg.dir(&self.root).join("pcb.toml");
             pkg.config = PcbToml::from_file(&file_provider, &pkg_toml_path)?;
         }
-        Ok(())
+        resolve_patch_aliases(self)
     }
 
     fn board_name_for_zen(&self, zen_path: &Path) -> Option<String> {
@@ -70,6 +71,15 @@ impl WorkspaceInfoExt for WorkspaceInfo {
             .find(|b| b.absolute_zen_path(&self.root) == canon)
     }
 
+    fn board_config_for_zen(&self, zen_path: &Path) -> Option<Board> {
+        let canon = zen_path.canonicalize().ok()?;
+        self.packages.values().find_map(|pkg| {
+            let board = pkg.config.board.as_ref()?;
+            let zen = board.path.as_ref()?;
+            (pkg.dir(&self.root).join(zen) == canon).then(|| board.clone())
+        })
+    }
+
     fn package_url_for_zen(&self, zen_path: &Path) -> Option<String> {
         let file_provider = DefaultFileProvider::new();
         self.package_url_for_path(&file_provider, zen_path)
@@ -229,15 +239,115 @@ pub fn get_workspace_info<F: FileProvider>(
         pcb_zen_core::workspace::get_workspace_info(file_provider, start_path)?
     };
 
+    resolve_patch_aliases(&mut info)?;
+
     // Add path-patched forks as workspace packages.
     {
         let _span = info_span!("add_path_patched_forks").entered();
-        add_path_patched_forks(file_provider, &mut info)?;
+        add_path_patched_forks(file_provider, &mut info, &BTreeMap::new())?;
     }
 
     Ok(info)
 }
 
+/// Rewrite root `[patch]` keys written as `@alias` names (`"@stdlib"`, or
+/// the last URL segment of a workspace dependency) to the package URLs they
+/// name, the same way `--override-package` targets are resolved.
+fn resolve_patch_aliases(info: &mut WorkspaceInfo) -> Result<()> {
+    let Some(root_cfg) = info.config.as_ref() else {
+        return Ok(());
+    };
+    let aliases = root_cfg
+        .patch
+        .keys()
+        .filter(|key| key.starts_with('@'))
+        .map(|alias| Ok((alias.clone(), override_package_url(info, alias)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let root_cfg = info.config.as_mut().expect("root config checked above");
+    for (alias, url) in aliases {
+        let patch = root_cfg.patch.remove(&alias).expect("alias key exists");
+        if root_cfg.patch.insert(url.clone(), patch).is_some() {
+            bail!("[patch] has entries for both {alias} and {url}");
+        }
+    }
+    Ok(())
+}
+
+/// Replace packages with local directories for this invocation only, as if the
+/// root pcb.toml had `[patch]` path entries for them. Unlike `[patch]`, the
+/// directories may live outside the workspace.
+///
+/// Targets are `@alias` names (`@stdlib`, or the last URL segment of any
+/// workspace dependency) or full package URLs. Returns the applied overrides
+/// keyed by package URL.
+pub fn apply_package_overrides<F: FileProvider>(
+    file_provider: &F,
+    info: &mut WorkspaceInfo,
+    overrides: &[(String, PathBuf)],
+) -> Result<BTreeMap<String, PathBuf>> {
+    let mut applied = BTreeMap::new();
+    for (target, path) in overrides {
+        let url = override_package_url(info, target)?;
+        if !file_provider.is_directory(path) {
+            bail!(
+                "Override for {target}: {} is not a directory",
+                path.display()
+            );
+        }
+        if !pcb_zen_core::is_stdlib_module_path(&url)
+            && !file_provider.exists(&path.join("pcb.toml"))
+        {
+            bail!("Override for {target}: {} has no pcb.toml", path.display());
+        }
+        if applied.insert(url.clone(), path.clone()).is_some() {
+            bail!("Package {url} is overridden more than once");
+        }
+    }
+
+    let root_cfg = info.config.get_or_insert_with(PcbToml::default);
+    for (url, path) in &applied {
+        root_cfg.patch.insert(
+            url.clone(),
+            PatchSpec {
+                path: Some(path.to_string_lossy().into_owned()),
+                branch: None,
+                rev: None,
+            },
+        );
+    }
+    add_path_patched_forks(file_provider, info, &applied)?;
+
+    Ok(applied)
+}
+
+/// Package URL for an `--override-package` target.
+fn override_package_url(info: &WorkspaceInfo, target: &str) -> Result<String> {
+    let Some(alias) = target.strip_prefix('@') else {
+        return Ok(target.to_string());
+    };
+    if pcb_zen_core::is_stdlib_module_path(alias) {
+        return Ok(alias.to_string());
+    }
+
+    let urls: HashSet<String> = info
+        .manifests()
+        .filter_map(|config| config.auto_generated_aliases().remove(alias))
+        .collect();
+    match urls.len() {
+        1 => Ok(urls.into_iter().next().unwrap()),
+        0 => bail!("Unknown package alias {target}; no workspace dependency ends in '{alias}'"),
+        _ => {
+            let mut urls: Vec<_> = urls.into_iter().collect();
+            urls.sort();
+            bail!(
+                "Package alias {target} is ambiguous ({}); use the full package URL",
+                urls.join(", ")
+            )
+        }
+    }
+}
+
 /// Populate package `version` from the newest package tags merged into HEAD.
 /// Cheap subset of [`enrich_git_metadata`] for commands that only need
 /// versions (e.g. the workspace pins `pcb sync` writes back).
@@ -297,9 +407,13 @@ pub fn enrich_git_metadata(info: &mut WorkspaceInfo) {
 ///
 /// This allows forks to be treated like regular workspace packages for dependency
 /// resolution, without requiring special handling in resolve.rs.
+///
+/// Packages in `overrides` may live outside the workspace root and replace
+/// already discovered packages.
 fn add_path_patched_forks<F: FileProvider>(
     file_provider: &F,
     info: &mut WorkspaceInfo,
+    overrides: &BTreeMap<String, PathBuf>,
 ) -> Result<()> {
     let Some(root_cfg) = info.config.as_ref() else {
         return Ok(());
@@ -314,9 +428,10 @@ fn add_path_patched_forks<F: FileProvider>(
         };
 
         let abs = info.root.join(rel_path);
+        let overridden = overrides.contains_key(url);
 
         // Only support forks that live under the workspace root
-        if !abs.starts_with(&info.root) {
+        if !abs.starts_with(&info.root) && !overridden {
             continue;
         }
 
@@ -326,7 +441,7 @@ fn add_path_patched_forks<F: FileProvider>(
         }
 
         // Skip if already discovered.
-        if info.packages.contains_key(url) {
+        if info.packages.contains_key(url) && !overridden {
             continue;
         }
 
@@ -6,8 +6,8 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use pcb_canonical::{
-    compute_content_hash_from_dir, compute_content_hash_from_memory_files, compute_manifest_hash,
-    list_canonical_tar_entries,
+    PackageHashes, canonical_path, compute_content_hash_from_dir,
+    compute_content_hash_from_memory_files, compute_manifest_hash, list_canonical_tar_entries,
 };
 
 /// Test helper for creating isolated directories with files.
@@ -379,3 +379,115 @@ fn single_file_hashing() {
         "test.txt: {hash1}\nother.txt: {hash2}\nsame_content_a.txt: {hash_a}\nsame_content_b.txt: {hash_b}"
     ));
 }
+
+/// Hash of `a.txt`, `b.txt`, and `sub/c.txt` from [`deterministic_hashing`],
+/// spelled out so the stability tests below don't depend on snapshots.
+const ABC_HASH: &str = "h1:o/UjctMrJSSYnETR11PrBq3JQPz3qfrNXHJTGyp9AOk=";
+
+#[test]
+fn memory_and_directory_hashes_agree() {
+    let dir = CanonicalTestDir::new();
+    dir.add_file("a.txt", "content a");
+    dir.add_file("b.txt", "content b");
+    dir.add_file("sub/c.txt", "content c");
+    assert_eq!(compute_content_hash_from_dir(dir.root()).unwrap(), ABC_HASH);
+
+    // Insertion order and separator spelling don't matter.
+    let memory = compute_content_hash_from_memory_files([
+        (Path::new("sub\\c.txt"), b"content c".as_slice()),
+        (Path::new("b.txt"), b"content b".as_slice()),
+        (Path::new("a.txt"), b"content a".as_slice()),
+    ])
+    .unwrap();
+    assert_eq!(memory, ABC_HASH);
+}
+
+#[test]
+fn unicode_paths_hash_as_nfc() {
+    let nfc = "caf\u{e9}.zen";
+    let nfd = "cafe\u{301}.zen";
+    assert_eq!(canonical_path(Path::new(nfd)).unwrap(), nfc);
+
+    let hash = |name: &str| {
+        compute_content_hash_from_memory_files([(Path::new(name), b"x = 1\n".as_slice())]).unwrap()
+    };
+    assert_eq!(hash(nfc), hash(nfd));
+
+    let dir = CanonicalTestDir::new();
+    dir.add_file(nfd, "x = 1\n");
+    assert_eq!(
+        compute_content_hash_from_dir(dir.root()).unwrap(),
+        hash(nfc)
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn permissions_and_timestamps_do_not_change_hash() {
+    use std::os::unix::fs::PermissionsExt;
+    use std::time::{Duration, SystemTime};
+
+    let dir = CanonicalTestDir::new();
+    dir.add_file("a.txt", "content a");
+    dir.add_file("b.txt", "content b");
+    dir.add_file("sub/c.txt", "content c");
+
+    let script = dir.root().join("sub/c.txt");
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&script)
+        .unwrap()
+        .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+        .unwrap();
+
+    assert_eq!(compute_content_hash_from_dir(dir.root()).unwrap(), ABC_HASH);
+}
+
+#[test]
+fn line_endings_are_hashed_verbatim() {
+    let hash = |contents: &[u8]| {
+        compute_content_hash_from_memory_files([(Path::new("main.zen"), contents)]).unwrap()
+    };
+    assert_ne!(hash(b"x = 1\ny = 2\n"), hash(b"x = 1\r\ny = 2\r\n"));
+    assert_ne!(
+        compute_manifest_hash("[dependencies]\n"),
+        compute_manifest_hash("[dependencies]\r\n")
+    );
+}
+
+#[test]
+fn package_hashes_round_trip_through_tag_annotation() {
+    let dir = CanonicalTestDir::new();
+    dir.add_file("pcb.toml", "[dependencies]\n");
+    dir.add_file("main.zen", "x = 1\n");
+    dir.add_file("pcb.sum", "github.com/acme/dep v1.0.0 h1:old\n");
+
+    let hashes = PackageHashes::compute(dir.root()).unwrap();
+    assert_eq!(hashes.manifest, compute_manifest_hash("[dependencies]\n"));
+    assert_eq!(
+        hashes.content,
+        compute_content_hash_from_memory_files([
+            (Path::new("pcb.toml"), b"[dependencies]\n".as_slice()),
+            (Path::new("main.zen"), b"x = 1\n".as_slice()),
+        ])
+        .unwrap()
+    );
+
+    let annotation = hashes.tag_annotation("github.com/acme/lib", "1.2.0");
+    assert_eq!(
+        annotation,
+        format!(
+            "github.com/acme/lib v1.2.0 {}\ngithub.com/acme/lib v1.2.0/pcb.toml {}",
+            hashes.content, hashes.manifest
+        )
+    );
+    assert_eq!(
+        PackageHashes::from_tag_annotation(&annotation),
+        Some(hashes)
+    );
+    assert_eq!(
+        PackageHashes::from_tag_annotation("github.com/acme/lib v1.2.0 h1:abc"),
+        None
+    );
+}
@@ -0,0 +1,461 @@
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use ignore::WalkBuilder;
+use pcb_eda::{KicadFootprint, PadType};
+use pcb_ui::prelude::*;
+use pcb_zen_core::lang::symbol::SymbolValue;
+use pcb_zen_core::resolution::ResolutionResult;
+use serde::Serialize;
+use starlark::values::ValueLike;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use crate::info::OutputFormat;
+
+/// Library files the report tracks for references.
+const LIBRARY_EXTENSIONS: &[&str] = &["kicad_sym", "kicad_mod", "step", "stp", "wrl"];
+
+/// 3D model paths embedded in the footprint itself.
+const EMBED_PREFIX: &str = "kicad-embed://";
+
+#[derive(Args)]
+pub struct AnalyzeArgs {
+    #[command(subcommand)]
+    command: AnalyzeCommand,
+}
+
+#[derive(Subcommand)]
+enum AnalyzeCommand {
+    /// Report components with missing, mismatched, or unreferenced library files
+    Library {
+        /// Package directory to scan (defaults to current directory)
+        #[arg(value_name = "PATH", value_hint = clap::ValueHint::DirPath)]
+        path: Option<PathBuf>,
+
+        /// Output format
+        #[arg(short = 'f', long, value_enum, default_value = "human")]
+        format: OutputFormat,
+
+        /// Disable network access (offline mode) - only use vendored dependencies
+        #[arg(long)]
+        offline: bool,
+    },
+}
+
+pub fn execute(args: AnalyzeArgs) -> Result<()> {
+    match args.command {
+        AnalyzeCommand::Library {
+            path,
+            format,
+            offline,
+        } => analyze_library(path, format, offline),
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+struct LibraryReport {
+    components: Vec<ComponentReport>,
+    /// `.zen` files that failed to evaluate, usually because a file they
+    /// reference is missing.
+    failed: Vec<FailedFile>,
+    /// Symbol, footprint, and model files no component references.
+    unreferenced: Vec<String>,
+}
+
+impl LibraryReport {
+    fn issue_count(&self) -> usize {
+        self.components
+            .iter()
+            .map(|component| component.issues.len())
+            .sum::<usize>()
+            + self.failed.len()
+            + self.unreferenced.len()
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct FailedFile {
+    file: String,
+    error: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ComponentReport {
+    file: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symbol: Option<String>,
+    footprint: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    models: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    issues: Vec<LibraryIssue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum LibraryIssue {
+    MissingFootprint {
+        path: String,
+    },
+    InvalidFootprint {
+        path: String,
+        error: String,
+    },
+    NoModel,
+    MissingModel {
+        model: String,
+    },
+    PadMismatch {
+        symbol_pads: usize,
+        footprint_pads: usize,
+        /// Pads the symbol maps that the footprint lacks.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        missing: Vec<String>,
+        /// Footprint pads no symbol pin maps to.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        unmapped: Vec<String>,
+    },
+}
+
+impl std::fmt::Display for LibraryIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LibraryIssue::MissingFootprint { path } => write!(f, "footprint {path} is missing"),
+            LibraryIssue::InvalidFootprint { path, error } => {
+                write!(f, "footprint {path} could not be parsed: {error}")
+            }
+            LibraryIssue::NoModel => write!(f, "footprint has no 3D model"),
+            LibraryIssue::MissingModel { model } => write!(f, "3D model {model} is missing"),
+            LibraryIssue::PadMismatch {
+                symbol_pads,
+                footprint_pads,
+                missing,
+                unmapped,
+            } => {
+                write!(
+                    f,
+                    "symbol maps {symbol_pads} pads but footprint has {footprint_pads}"
+                )?;
+                if !missing.is_empty() {
+                    write!(f, "; not on footprint: {}", missing.join(", "))?;
+                }
+                if !unmapped.is_empty() {
+                    write!(f, "; unmapped: {}", unmapped.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn analyze_library(path: Option<PathBuf>, format: OutputFormat, offline: bool) -> Result<()> {
+    let root = path
+        .unwrap_or_else(|| std::env::current_dir().unwrap())
+        .canonicalize()
+        .context("Failed to resolve library path")?;
+    let resolution = crate::resolve::resolve(Some(&root), offline)?;
+    let zen_files = crate::file_walker::collect_zen_files(&[&root])?;
+
+    let spinner = Spinner::builder("Analyzing library").start();
+    let mut report = LibraryReport::default();
+    let mut referenced = BTreeSet::new();
+    let mut components = BTreeMap::new();
+    for zen_path in &zen_files {
+        spinner.set_message(format!("Analyzing {}", display_path(&root, zen_path)));
+        let eval_result = pcb_zen::eval(zen_path, resolution.clone(), Default::default());
+        let Some(output) = eval_result
+            .output
+            .filter(|_| !eval_result.diagnostics.has_errors())
+        else {
+            let error = eval_result
+                .diagnostics
+                .errors()
+                .first()
+                .map(|diagnostic| diagnostic.body.clone())
+                .unwrap_or_else(|| "evaluation failed".to_string());
+            report.failed.push(FailedFile {
+                file: display_path(&root, zen_path),
+                error,
+            });
+            continue;
+        };
+
+        for module in output.module_tree().values() {
+            for component in module.components() {
+                let source_path = Path::new(component.source_path());
+                if !source_path.starts_with(&root) {
+                    continue;
+                }
+                let key = (source_path.to_path_buf(), component.name().to_string());
+                if components.contains_key(&key) {
+                    continue;
+                }
+                let symbol = component.symbol().downcast_ref::<SymbolValue>();
+                let symbol_path = symbol
+                    .and_then(SymbolValue::source_uri)
+                    .and_then(|uri| resolution.resolve_package_uri(uri).ok());
+                let symbol_pads: BTreeSet<String> = symbol
+                    .map(|symbol| symbol.pad_to_signal().keys().cloned().collect())
+                    .unwrap_or_default();
+
+                let mut entry = ComponentReport {
+                    file: display_path(&root, source_path),
+                    name: component.name().to_string(),
+                    symbol: symbol_path.as_ref().map(|path| display_path(&root, path)),
+                    footprint: component.footprint().to_string(),
+                    models: Vec::new(),
+                    issues: Vec::new(),
+                };
+                referenced.extend(symbol_path);
+                if let Some(footprint_path) =
+                    footprint_file(component.footprint(), source_path, &resolution)
+                {
+                    entry.footprint = display_path(&root, &footprint_path);
+                    check_footprint(&footprint_path, &symbol_pads, &mut entry, &mut referenced);
+                }
+                components.insert(key, entry);
+            }
+        }
+    }
+    spinner.finish();
+
+    report.components = components.into_values().collect();
+    report.unreferenced = unreferenced_files(&root, &referenced)?;
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Human => print_report(&report),
+    }
+
+    let issues = report.issue_count();
+    if issues > 0 {
+        anyhow::bail!("Found {issues} library issue(s)");
+    }
+    Ok(())
+}
+
+/// Resolve a file-backed footprint the way footprint validation does.
+/// Library references like `Resistor_SMD:R_0402` return `None`.
+fn footprint_file(
+    footprint: &str,
+    source_path: &Path,
+    resolution: &ResolutionResult,
+) -> Option<PathBuf> {
+    if !footprint.ends_with(".kicad_mod") {
+        return None;
+    }
+    if footprint.starts_with(pcb_sch::PACKAGE_URI_PREFIX) {
+        return resolution.resolve_package_uri(footprint).ok();
+    }
+    let path = PathBuf::from(footprint);
+    if path.is_absolute() {
+        return Some(path);
+    }
+    source_path.parent().map(|parent| parent.join(path))
+}
+
+fn check_footprint(
+    path: &Path,
+    symbol_pads: &BTreeSet<String>,
+    entry: &mut ComponentReport,
+    referenced: &mut BTreeSet<PathBuf>,
+) {
+    if !path.exists() {
+        entry.issues.push(LibraryIssue::MissingFootprint {
+            path: entry.footprint.clone(),
+        });
+        return;
+    }
+    referenced.insert(path.to_path_buf());
+    let footprint = match KicadFootprint::from_file(path) {
+        Ok(footprint) => footprint,
+        Err(err) => {
+            entry.issues.push(LibraryIssue::InvalidFootprint {
+                path: entry.footprint.clone(),
+                error: err.to_string(),
+            });
+            return;
+        }
+    };
+
+    entry.models = footprint.models().to_vec();
+    if footprint.models().is_empty() {
+        entry.issues.push(LibraryIssue::NoModel);
+    }
+    for model in footprint.models() {
+        match model_file(model, path) {
+            ModelRef::Embedded(name) => {
+                if !footprint.embedded_files().iter().any(|file| file == name) {
+                    entry.issues.push(LibraryIssue::MissingModel {
+                        model: model.clone(),
+                    });
+                }
+            }
+            ModelRef::File(model_path) => {
+                if model_path.exists() {
+                    referenced.insert(model_path);
+                } else {
+                    entry.issues.push(LibraryIssue::MissingModel {
+                        model: model.clone(),
+                    });
+                }
+            }
+            ModelRef::External => {}
+        }
+    }
+
+    if !symbol_pads.is_empty()
+        && let Some(issue) = pad_mismatch(symbol_pads, &footprint)
+    {
+        entry.issues.push(issue);
+    }
+}
+
+enum ModelRef<'a> {
+    /// Embedded in the footprint under this name.
+    Embedded(&'a str),
+    /// A file next to (or relative to) the footprint.
+    File(PathBuf),
+    /// Resolved by KiCad through a path variable such as `${KICAD9_3DMODEL_DIR}`.
+    External,
+}
+
+fn model_file<'a>(model: &'a str, footprint_path: &Path) -> ModelRef<'a> {
+    if let Some(name) = model.strip_prefix(EMBED_PREFIX) {
+        return ModelRef::Embedded(name);
+    }
+    if model.contains("${") {
+        return ModelRef::External;
+    }
+    let path = PathBuf::from(model);
+    if path.is_absolute() {
+        return ModelRef::File(path);
+    }
+    match footprint_path.parent() {
+        Some(parent) => ModelRef::File(parent.join(path)),
+        None => ModelRef::File(path),
+    }
+}
+
+/// Compare the pads a symbol maps with the footprint's numbered, electrical pads.
+fn pad_mismatch(
+    symbol_pads: &BTreeSet<String>,
+    footprint: &KicadFootprint,
+) -> Option<LibraryIssue> {
+    let footprint_pads: BTreeSet<String> = footprint
+        .pads()
+        .iter()
+        .filter(|pad| !pad.number.is_empty() && pad.pad_type != PadType::NpThruHole)
+        .map(|pad| pad.number.clone())
+        .collect();
+    if &footprint_pads == symbol_pads {
+        return None;
+    }
+    Some(LibraryIssue::PadMismatch {
+        symbol_pads: symbol_pads.len(),
+        footprint_pads: footprint_pads.len(),
+        missing: symbol_pads.difference(&footprint_pads).cloned().collect(),
+        unmapped: footprint_pads.difference(symbol_pads).cloned().collect(),
+    })
+}
+
+fn unreferenced_files(root: &Path, referenced: &BTreeSet<PathBuf>) -> Result<Vec<String>> {
+    let referenced: BTreeSet<PathBuf> = referenced
+        .iter()
+        .map(|path| path.canonicalize().unwrap_or_else(|_| path.clone()))
+        .collect();
+
+    let mut unreferenced = Vec::new();
+    let walker = WalkBuilder::new(root)
+        .hidden(true)
+        .git_ignore(true)
+        .filter_entry(pcb_zen::ast_utils::skip_vendor)
+        .build();
+    for entry in walker {
+        let path = entry?.into_path();
+        let is_library_file = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| LIBRARY_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+        if !is_library_file || !path.is_file() {
+            continue;
+        }
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if !referenced.contains(&canonical) {
+            unreferenced.push(display_path(root, &path));
+        }
+    }
+    unreferenced.sort();
+    Ok(unreferenced)
+}
+
+fn print_report(report: &LibraryReport) {
+    for component in &report.components {
+        let label = format!("{} ({})", component.name, component.file);
+        if component.issues.is_empty() {
+            println!("{} {}", "✓".green(), label);
+            continue;
+        }
+        println!("{} {}", "✗".red(), label.bold());
+        for issue in &component.issues {
+            println!("    {issue}");
+        }
+    }
+    for failed in &report.failed {
+        println!("{} {}", "✗".red(), failed.file.bold());
+        println!("    failed to evaluate: {}", failed.error);
+    }
+    if !report.unreferenced.is_empty() {
+        println!("{}", "Unreferenced library files:".yellow());
+        for file in &report.unreferenced {
+            println!("    {file}");
+        }
+    }
+
+    let issues = report.issue_count();
+    if issues == 0 {
+        println!(
+            "{} {}",
+            "✓".green().bold(),
+            format!("{} components, no issues", report.components.len()).bold()
+        );
+    }
+}
+
+fn display_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn pad_mismatch_ignores_mounting_holes_and_reports_differences() {
+        let footprint = KicadFootprint::from_str(
+            r#"(footprint "SOT-23"
+  (pad "1" smd rect (at -1 0.9) (size 0.6 0.7) (layers "F.Cu"))
+  (pad "2" smd rect (at 1 0.9) (size 0.6 0.7) (layers "F.Cu"))
+  (pad "3" smd rect (at 0 -0.9) (size 0.6 0.7) (layers "F.Cu"))
+  (pad "" np_thru_hole circle (at 0 0) (size 1 1) (drill 1) (layers "F&B.Cu"))
+)"#,
+        )
+        .unwrap();
+
+        let pads = |pads: &[&str]| pads.iter().map(|pad| pad.to_string()).collect();
+        assert_eq!(pad_mismatch(&pads(&["1", "2", "3"]), &footprint), None);
+        assert_eq!(
+            pad_mismatch(&pads(&["1", "2", "4"]), &footprint),
+            Some(LibraryIssue::PadMismatch {
+                symbol_pads: 3,
+                footprint_pads: 3,
+                missing: vec!["4".to_string()],
+                unmapped: vec!["3".to_string()],
+            })
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Read-only HTTP server behind `pcb open --serve`.
+//!
+//! Artifacts are built once before the server starts and held in memory, so
+//! requests never touch the workspace. Only `GET` and `HEAD` are answered.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+/// Largest request line plus headers read from a client.
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+/// Connections handled at once; further clients get a 503.
+const MAX_CONNECTIONS: usize = 32;
+/// How long a client may take to send its request or receive the response.
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// One file served at `/{name}`.
+pub struct Artifact {
+    pub name: &'static str,
+    pub content_type: &'static str,
+    pub description: &'static str,
+    pub body: Vec<u8>,
+}
+
+/// Serve `artifacts` of the board `title` at `addr` until interrupted.
+pub fn serve(addr: SocketAddr, title: &str, mut artifacts: Vec<Artifact>) -> Result<()> {
+    artifacts.insert(
+        0,
+        Artifact {
+            name: "index.html",
+            content_type: "text/html; charset=utf-8",
+            description: "This page",
+            body: index_html(title, &artifacts).into_bytes(),
+        },
+    );
+    let listener =
+        TcpListener::bind(addr).with_context(|| format!("Failed to listen on {addr}"))?;
+    let local = listener.local_addr()?;
+    eprintln!("Serving {title} at http://{local}/ (Ctrl+C to stop)");
+    for artifact in &artifacts[1..] {
+        eprintln!("  http://{local}/{}", artifact.name);
+    }
+
+    let artifacts = Arc::new(artifacts);
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        if let Err(e) = stream
+            .set_read_timeout(Some(IO_TIMEOUT))
+            .and_then(|()| stream.set_write_timeout(Some(IO_TIMEOUT)))
+        {
+            log::debug!("artifact server: {e}");
+            continue;
+        }
+        if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            active.fetch_sub(1, Ordering::SeqCst);
+            let _ = respond(&stream, "GET", &BUSY);
+            continue;
+        }
+        let artifacts = artifacts.clone();
+        let active = active.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = handle(&stream, &artifacts) {
+                log::debug!("artifact server: {e}");
+            }
+            active.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+    Ok(())
+}
+
+fn handle(stream: &TcpStream, artifacts: &[Artifact]) -> std::io::Result<()> {
+    let Some((method, target)) = read_request(BufReader::new(stream.take(MAX_REQUEST_BYTES)))?
+    else {
+        return respond(stream, "GET", &TOO_LARGE);
+    };
+    respond(stream, &method, &route(artifacts, &method, &target))
+}
+
+/// Method and target of the request in `reader`, or `None` when the request
+/// line and headers do not end within the reader.
+fn read_request(mut reader: impl BufRead) -> std::io::Result<Option<(String, String)>> {
+    let mut request_line = String::new();
+    if !read_full_line(&mut reader, &mut request_line)? {
+        return Ok(None);
+    }
+    // Drain the headers; requests carry nothing else the server uses.
+    let mut header = String::new();
+    loop {
+        header.clear();
+        if !read_full_line(&mut reader, &mut header)? {
+            return Ok(None);
+        }
+        if header.trim_end().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default().to_string();
+    Ok(Some((method, target)))
+}
+
+/// Read one line into `line`, returning whether it ended with a newline.
+fn read_full_line(reader: &mut impl BufRead, line: &mut String) -> std::io::Result<bool> {
+    reader.read_line(line)?;
+    Ok(line.ends_with('\n'))
+}
+
+fn respond(mut stream: &TcpStream, method: &str, response: &Response<'_>) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len(),
+        if response.status.starts_with("405") {
+            "Allow: GET, HEAD\r\n"
+        } else {
+            ""
+        },
+    )?;
+    if method != "HEAD" {
+        stream.write_all(response.body)?;
+    }
+    stream.flush()
+}
+
+struct Response<'a> {
+    status: &'static str,
+    content_type: &'static str,
+    body: &'a [u8],
+}
+
+const TOO_LARGE: Response<'static> = Response {
+    status: "431 Request Header Fields Too Large",
+    content_type: "text/plain; charset=utf-8",
+    body: b"Request too large\n",
+};
+
+const BUSY: Response<'static> = Response {
+    status: "503 Service Unavailable",
+    content_type: "text/plain; charset=utf-8",
+    body: b"Too many connections\n",
+};
+
+fn route<'a>(artifacts: &'a [Artifact], method: &str, target: &str) -> Response<'a> {
+    if method != "GET" && method != "HEAD" {
+        return Response {
+            status: "405 Method Not Allowed",
+            content_type: "text/plain; charset=utf-8",
+            body: b"Method not allowed\n",
+        };
+    }
+    let path = target.split(['?', '#']).next().unwrap_or_default();
+    let name = match path.trim_start_matches('/') {
+        "" => "index.html",
+        name => name,
+    };
+    match artifacts.iter().find(|artifact| artifact.name == name) {
+        Some(artifact) => Response {
+            status: "200 OK",
+            content_type: artifact.content_type,
+            body: &artifact.body,
+        },
+        None => Response {
+            status: "404 Not Found",
+            content_type: "text/plain; charset=utf-8",
+            body: b"Not found\n",
+        },
+    }
+}
+
+fn index_html(title: &str, artifacts: &[Artifact]) -> String {
+    let title = html_escape(title);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n<ul>\n"
+    );
+    for artifact in artifacts {
+        html.push_str(&format!(
+            "<li><a href=\"{0}\">{0}</a>: {1}</li>\n",
+            artifact.name, artifact.description
+        ));
+    }
+    html.push_str("</ul>\n");
+    if artifacts
+        .iter()
+        .any(|artifact| artifact.name == "layout.svg")
+    {
+        html.push_str("<img src=\"layout.svg\" alt=\"Layout\" style=\"max-width: 100%\">\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artifacts() -> Vec<Artifact> {
+        ["index.html", "netlist.json"]
+            .into_iter()
+            .map(|name| Artifact {
+                name,
+                content_type: "text/plain",
+                description: "",
+                body: name.as_bytes().to_vec(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn routes_read_only_requests() {
+        let artifacts = artifacts();
+        let get = |method, target| {
+            let response = route(&artifacts, method, target);
+            (
+                response.status,
+                String::from_utf8_lossy(response.body).into_owned(),
+            )
+        };
+        assert_eq!(get("GET", "/"), ("200 OK", "index.html".to_string()));
+        assert_eq!(
+            get("HEAD", "/netlist.json?v=1"),
+            ("200 OK", "netlist.json".to_string())
+        );
+        assert_eq!(get("GET", "/../pcb.toml").0, "404 Not Found");
+        assert_eq!(get("POST", "/netlist.json").0, "405 Method Not Allowed");
+    }
+
+    #[test]
+    fn rejects_requests_past_the_size_limit() {
+        let read = |request: String| {
+            read_request(BufReader::new(request.as_bytes().take(MAX_REQUEST_BYTES))).unwrap()
+        };
+        assert_eq!(
+            read("GET /bom.json HTTP/1.1\r\nHost: x\r\n\r\n".to_string()),
+            Some(("GET".to_string(), "/bom.json".to_string()))
+        );
+        let long_header = format!(
+            "GET / HTTP/1.1\r\nX-Pad: {}\r\n\r\n",
+            "a".repeat(MAX_REQUEST_BYTES as usize)
+        );
+        assert_eq!(read(long_header), None);
+        let endless_headers = "GET / HTTP/1.1\r\n".to_string() + &"X: y\r\n".repeat(4096);
+        assert_eq!(read(endless_headers), None);
+    }
+}
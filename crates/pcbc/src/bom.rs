@@ -6,9 +6,21 @@ use crate::config_input::{CONFIG_ARG_HELP, parse_config_overrides};
 use crate::release::discover_layout_from_output;
 use anyhow::{Context, Result};
 use clap::{Args, ValueEnum};
+use inquire::Confirm;
 use pcb_layout::utils;
-use pcb_sch::bom::{Bom, parse_kicad_csv_bom};
+use pcb_sch::bom::rules::{self, BOM_RULES_FILE};
+use pcb_sch::bom::variants::{BomMatrix, Variant, sweeps};
+use pcb_sch::bom::{
+    Bom, BomMatchingRule, parse_kicad_csv_bom, variant_assembly_entries, write_assembly_csv,
+    write_assembly_markdown,
+};
+use pcb_sch::revision::Revision;
+use pcb_sch::stamp::Stamp;
 use pcb_ui::prelude::*;
+use pcb_zen::workspace::WorkspaceInfoExt;
+use pcb_zen_core::resolution::ResolutionResult;
+use serde_json::Value as JsonValue;
+use starlark::collections::SmallMap;
 
 /// Generate BOM with KiCad fallback if design BOM is empty
 pub fn generate_bom_with_fallback(design_bom: Bom, layout_path: Option<&Path>) -> Result<Bom> {
@@ -49,11 +61,26 @@ pub fn generate_bom_with_fallback(design_bom: Bom, layout_path: Option<&Path>) -
     Ok(design_bom)
 }
 
+/// Path of the `bom-rules.json` sidecar for a board file.
+pub fn bom_rules_path(zen_file: &Path) -> PathBuf {
+    zen_file.with_file_name(BOM_RULES_FILE)
+}
+
+/// Add alternates from the board's `bom-rules.json` sidecar, if present.
+pub fn apply_bom_rules(bom: &mut Bom, zen_file: &Path) -> Result<()> {
+    bom.apply_rules(&rules::load_rules(&bom_rules_path(zen_file))?);
+    Ok(())
+}
+
 #[derive(ValueEnum, Debug, Clone, Default)]
 pub enum BomFormat {
     #[default]
     Table,
     Json,
+    /// Assembly BOM: one line per part with DNP designators and assembly notes
+    Csv,
+    /// Assembly BOM as a Markdown table
+    Markdown,
 }
 
 impl std::fmt::Display for BomFormat {
@@ -61,6 +88,8 @@ impl std::fmt::Display for BomFormat {
         match self {
             BomFormat::Table => write!(f, "table"),
             BomFormat::Json => write!(f, "json"),
+            BomFormat::Csv => write!(f, "csv"),
+            BomFormat::Markdown => write!(f, "markdown"),
         }
     }
 }
@@ -82,6 +111,31 @@ pub struct BomArgs {
     /// Disable network access (offline mode) - only use vendored dependencies
     #[arg(long = "offline")]
     pub offline: bool,
+
+    /// Build every variant declared with Sweep() and output a BOM matrix
+    #[arg(long = "variants")]
+    pub variants: bool,
+
+    /// Group the BOM by top-level module instance with per-subassembly quantity and cost
+    #[arg(long = "by-subassembly", conflicts_with = "variants")]
+    pub by_subassembly: bool,
+
+    /// Merge approved alternates from an IPC-2581 BOM into bom-rules.json
+    #[arg(
+        long = "import-ipc2581",
+        value_name = "IPC_FILE",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with = "variants"
+    )]
+    pub import_ipc2581: Option<PathBuf>,
+
+    /// Write imported alternates without asking for confirmation
+    #[arg(long = "yes", short = 'y', requires = "import_ipc2581")]
+    pub yes: bool,
+
+    /// Start the BOM with the board revision and the workspace's git describe and source hash
+    #[arg(long = "stamp", conflicts_with = "import_ipc2581")]
+    pub stamp: bool,
 }
 
 pub fn execute(args: BomArgs) -> Result<()> {
@@ -95,6 +149,18 @@ pub fn execute(args: BomArgs) -> Result<()> {
         .workspace_config()
         .bom
         .strict;
+    let stamp = args
+        .stamp
+        .then(|| -> Result<BomStamp> {
+            let workspace = &resolution_result.workspace_info;
+            Ok(BomStamp {
+                stamp: crate::stamp::workspace_stamp(workspace)?,
+                revision: workspace
+                    .board_config_for_zen(&args.file)
+                    .and_then(|board| board.revisions.last().cloned()),
+            })
+        })
+        .transpose()?;
 
     let file_name = args.file.file_name().unwrap().to_string_lossy();
 
@@ -102,7 +168,7 @@ pub fn execute(args: BomArgs) -> Result<()> {
     let spinner = Spinner::builder(format!("{file_name}: Building")).start();
 
     // Evaluate the design
-    let eval_result = pcb_zen::eval(&args.file, resolution_result, config_inputs);
+    let eval_result = pcb_zen::eval(&args.file, resolution_result.clone(), config_inputs.clone());
     let layout_path = eval_result
         .output
         .as_ref()
@@ -121,7 +187,41 @@ pub fn execute(args: BomArgs) -> Result<()> {
         .to_schematic()
         .context("Failed to convert to schematic")?;
 
+    if args.variants {
+        let boms = build_variant_boms(
+            &args.file,
+            &schematic,
+            resolution_result,
+            &config_inputs,
+            &spinner,
+        )?;
+        spinner.finish();
+        let mut writer = io::stdout().lock();
+        write_stamp_header(&mut writer, stamp.as_ref(), args.format)?;
+        match args.format {
+            BomFormat::Csv => write_assembly_csv(&variant_assembly_entries(&boms), true, writer)?,
+            BomFormat::Markdown => {
+                write_assembly_markdown(&variant_assembly_entries(&boms), true, writer)?
+            }
+            BomFormat::Json => writeln!(
+                writer,
+                "{}",
+                stamped_json(BomMatrix::new(boms).to_json(), stamp.as_ref())
+            )?,
+            BomFormat::Table => write_variant_matrix(&BomMatrix::new(boms), writer)?,
+        }
+        return Ok(());
+    }
+
     let mut bom = generate_bom_with_fallback(schematic.bom(), layout_path.as_deref())?;
+    apply_bom_rules(&mut bom, &args.file)?;
+
+    if let Some(ipc_file) = &args.import_ipc2581 {
+        spinner.set_message(format!("{file_name}: Reading {}", ipc_file.display()));
+        let imported = pcb_ipc2581_tools::commands::bom::load_bom(ipc_file)?;
+        spinner.finish();
+        return import_alternates(&args.file, &bom, &imported, args.yes);
+    }
 
     // Filter out components marked as skip_bom
     bom = bom.filter_excluded();
@@ -149,10 +249,198 @@ pub fn execute(args: BomArgs) -> Result<()> {
     spinner.finish();
 
     let mut writer = io::stdout().lock();
-    match args.format {
-        BomFormat::Json => write!(writer, "{}", bom.ungrouped_json())?,
-        BomFormat::Table => bom.write_table(writer)?,
+    write_stamp_header(&mut writer, stamp.as_ref(), args.format)?;
+    match (args.format, args.by_subassembly) {
+        (BomFormat::Json, false) => write!(
+            writer,
+            "{}",
+            stamped_json(bom.ungrouped_json(), stamp.as_ref())
+        )?,
+        (BomFormat::Table, false) => bom.write_table(writer)?,
+        (BomFormat::Json, true) => write!(
+            writer,
+            "{}",
+            stamped_json(bom.subassembly_json(), stamp.as_ref())
+        )?,
+        (BomFormat::Table, true) => bom.write_subassembly_table(writer)?,
+        (BomFormat::Csv, false) => write_assembly_csv(&bom.assembly_entries(), false, writer)?,
+        (BomFormat::Markdown, false) => {
+            write_assembly_markdown(&bom.assembly_entries(), false, writer)?
+        }
+        (BomFormat::Csv | BomFormat::Markdown, true) => {
+            anyhow::bail!("--by-subassembly supports only the table and json formats")
+        }
+    };
+
+    Ok(())
+}
+
+/// Provenance at the top of a `--stamp`ed BOM.
+struct BomStamp {
+    stamp: Stamp,
+    /// The board's current revision, when `pcb.toml` declares one.
+    revision: Option<Revision>,
+}
+
+impl BomStamp {
+    fn label(&self) -> String {
+        match &self.revision {
+            Some(revision) => format!("{}, {}", revision.label(), self.stamp.label()),
+            None => self.stamp.label(),
+        }
+    }
+}
+
+/// Write `stamp` as a header line in the comment syntax of `format`. JSON
+/// BOMs carry it in a wrapper object instead; see [`stamped_json`].
+fn write_stamp_header(
+    writer: &mut impl Write,
+    stamp: Option<&BomStamp>,
+    format: BomFormat,
+) -> Result<()> {
+    let Some(stamp) = stamp else {
+        return Ok(());
     };
+    match format {
+        BomFormat::Csv => writeln!(writer, "# {}", stamp.label())?,
+        BomFormat::Markdown => writeln!(writer, "<!-- {} -->\n", stamp.label())?,
+        BomFormat::Table => writeln!(writer, "Source: {}", stamp.label())?,
+        BomFormat::Json => {}
+    }
+    Ok(())
+}
+
+/// Wrap a JSON BOM as `{"stamp": ..., "revision": ..., "bom": ...}` when
+/// stamping; `revision` is present only when the board declares one.
+fn stamped_json(json: String, stamp: Option<&BomStamp>) -> String {
+    let Some(stamp) = stamp else {
+        return json;
+    };
+    let bom: JsonValue = serde_json::from_str(&json).expect("BOM JSON is valid");
+    let mut wrapper = serde_json::json!({ "stamp": stamp.stamp, "bom": bom });
+    if let Some(revision) = &stamp.revision {
+        wrapper["revision"] = serde_json::json!(revision);
+    }
+    serde_json::to_string_pretty(&wrapper).expect("BOM JSON serializes")
+}
+
+/// Preview alternates the CM approved that the design lacks, then merge them
+/// into the board's `bom-rules.json`.
+fn import_alternates(zen_file: &Path, design: &Bom, imported: &Bom, yes: bool) -> Result<()> {
+    let new_rules = rules::imported_alternates(design, imported);
+    if new_rules.is_empty() {
+        println!("No new approved alternates");
+        return Ok(());
+    }
+
+    print_rule_preview(&new_rules);
+    let rules_path = bom_rules_path(zen_file);
+    let count: usize = new_rules.iter().map(|rule| rule.sources.len()).sum();
+    if !yes {
+        let prompt = format!("Add {count} alternate(s) to {}?", rules_path.display());
+        if !Confirm::new(&prompt)
+            .with_default(true)
+            .prompt()
+            .unwrap_or(false)
+        {
+            println!("{}", "Import cancelled".yellow());
+            return Ok(());
+        }
+    }
+
+    let mut sidecar = rules::load_rules(&rules_path)?;
+    let added = rules::merge_rules(&mut sidecar, new_rules);
+    rules::write_rules(&rules_path, &sidecar)?;
+    println!(
+        "{} Added {added} alternate(s) to {}",
+        "✓".green(),
+        rules_path.display()
+    );
+    Ok(())
+}
 
+fn print_rule_preview(rules: &[BomMatchingRule]) {
+    for rule in rules {
+        let pcb_sch::bom::BomMatchingKey::Path(designators) = &rule.key else {
+            continue;
+        };
+        println!("{}", designators.join(", ").bold());
+        for source in &rule.sources {
+            println!(
+                "  {} {} {}",
+                "+".green(),
+                source.manufacturer.as_deref().unwrap_or("?"),
+                source.manufacturer_pn.as_deref().unwrap_or("?")
+            );
+        }
+    }
+}
+
+/// Evaluate each variant declared with `Sweep()` on the root module and
+/// return their BOMs. Variant config overrides take precedence over `--config`.
+fn build_variant_boms(
+    file: &Path,
+    schematic: &pcb_sch::Schematic,
+    resolution_result: ResolutionResult,
+    config_inputs: &SmallMap<String, JsonValue>,
+    spinner: &Spinner,
+) -> Result<Vec<(Variant, Bom)>> {
+    let file_name = file.file_name().unwrap().to_string_lossy();
+    let root = schematic
+        .root()
+        .context("Design has no root module - cannot read sweeps")?;
+    let mut variants = Vec::new();
+    for sweep in sweeps(root)? {
+        variants.extend(sweep.variants()?);
+    }
+    if variants.is_empty() {
+        anyhow::bail!("{file_name} declares no Sweep() - no variants to build");
+    }
+
+    let mut boms = Vec::with_capacity(variants.len());
+    for variant in variants {
+        spinner.set_message(format!("{file_name}: Building {}", variant.name));
+        let mut inputs = config_inputs.clone();
+        for (key, value) in &variant.config {
+            inputs.insert(key.clone(), JsonValue::String(value.clone()));
+        }
+        let output = pcb_zen::eval(file, resolution_result.clone(), inputs)
+            .output_result()
+            .map_err(|mut diagnostics| {
+                diagnostics.apply_passes(&create_diagnostics_passes(&[], &[]));
+                anyhow::anyhow!("Failed to build variant {}", variant.name)
+            })?;
+        let bom = output
+            .to_schematic()
+            .context("Failed to convert to schematic")?
+            .bom()
+            .filter_excluded();
+        boms.push((variant, bom));
+    }
+
+    Ok(boms)
+}
+
+fn write_variant_matrix(matrix: &BomMatrix, mut writer: impl Write) -> Result<()> {
+    let mut table = comfy_table::Table::new();
+    table.load_preset(comfy_table::presets::UTF8_FULL_CONDENSED);
+    let mut header = vec!["Designator".to_string()];
+    header.extend(matrix.variants.iter().map(|v| v.name.clone()));
+    table.set_header(header);
+    for row in matrix.differing_rows() {
+        let mut cells = vec![row.designator.clone()];
+        cells.extend(row.cells.iter().map(|cell| match cell {
+            Some(cell) => cell.to_string(),
+            None => "-".to_string(),
+        }));
+        table.add_row(cells);
+    }
+    writeln!(writer, "{table}")?;
+    let identical = matrix.rows.len() - matrix.differing_rows().count();
+    writeln!(
+        writer,
+        "{} variants, {identical} components identical across all variants",
+        matrix.variants.len()
+    )?;
     Ok(())
 }
@@ -1,28 +1,58 @@
 use anyhow::{Context, Result};
-use clap::Args;
+use clap::{Args, ValueEnum};
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::{Cell, Color, Table};
 use log::debug;
-use pcb_sch::Schematic;
+use pcb_layout::layout_stats::read_layout_stats;
+use pcb_sch::capacitance::{
+    CapacitanceBudgets, DEFAULT_TRACE_CAPACITANCE_PF, net_capacitance, parse_picofarads,
+};
+use pcb_sch::netlist::{NetlistFormat, export_netlist};
+use pcb_sch::{AttributeValue, Schematic};
 use pcb_ui::prelude::*;
-use pcb_zen::workspace::WorkspaceInfoExt;
-use pcb_zen_core::config::find_workspace_root;
+use pcb_zen::lock_drift::LockDrift;
+use pcb_zen::workspace::{WorkspaceInfo, WorkspaceInfoExt};
+use pcb_zen_core::cancel::CancellationToken;
+use pcb_zen_core::config::{
+    BudgetConfig, CapacitanceConfig, LintLevel, NetNamesConfig, find_workspace_root,
+};
+use pcb_zen_core::lang::explain::{ParameterTraceStep, ValueOrigin, explain_parameter};
+use pcb_zen_core::lang::profile::{ProfileKind, Profiler};
+use pcb_zen_core::lang::secret::SecretInputs;
+use pcb_zen_core::net_names::check_net_names;
+use pcb_zen_core::net_registry::{
+    BoardNets, board_net_names, check_net_name_collisions, check_registered_nets,
+};
 use pcb_zen_core::resolution::ResolutionResult;
 use pcb_zen_core::{
-    DefaultFileProvider, Diagnostics, EvalContext, EvalContextConfig, FileProvider,
+    DefaultFileProvider, Diagnostics, EvalContext, EvalContextConfig, EvalOutput, FileProvider,
 };
 use serde_json::Value as JsonValue;
+use starlark::codemap::ResolvedSpan;
 use starlark::collections::SmallMap;
-use std::collections::BTreeMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::{info_span, instrument};
 
-use crate::config_input::{CONFIG_ARG_HELP, parse_config_overrides};
+use crate::config_input::{
+    CONFIG_ARG_HELP, INPUTS_FILE_ARG_HELP, SECRET_INPUTS_FILE_ARG_HELP, TARGET_ARG_HELP,
+    collect_config_inputs, load_secret_inputs, parse_target_flags,
+};
 use crate::file_walker;
 
 pub(crate) struct BuildEvalState {
     session: pcb_zen_core::lang::eval::EvalSession,
     file_provider: Arc<DefaultFileProvider>,
     resolution: Arc<ResolutionResult>,
+    secrets: SecretInputs,
+    /// `--target` flags, overriding each board's `[board.target]`.
+    target_flags: BTreeMap<String, String>,
+    /// `[workspace.nets]` from the workspace `pcb.toml`.
+    net_registry: BTreeMap<String, String>,
+    /// `[workspace.net-names]` from the workspace `pcb.toml`.
+    net_names: NetNamesConfig,
 }
 
 pub(crate) struct BuildResult {
@@ -34,16 +64,72 @@ pub(crate) struct BuildResult {
 }
 
 impl BuildEvalState {
+    pub(crate) fn with_profiler(mut self, profiler: Arc<Profiler>) -> Self {
+        self.session = self.session.with_profiler(profiler);
+        self
+    }
+
+    pub(crate) fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.session = self.session.with_cancellation(token);
+        self
+    }
+
     pub(crate) fn new(mut resolution: ResolutionResult) -> Self {
         let file_provider = Arc::new(DefaultFileProvider::new());
         resolution.canonicalize_keys(file_provider.as_ref());
+        let workspace_config = resolution.workspace_info.workspace_config();
         Self {
             session: pcb_zen_core::lang::eval::EvalSession::default(),
             file_provider,
             resolution: Arc::new(resolution),
+            secrets: SecretInputs::default(),
+            target_flags: BTreeMap::new(),
+            net_registry: workspace_config.nets,
+            net_names: workspace_config.net_names,
         }
     }
 
+    pub(crate) fn with_secret_inputs(mut self, secrets: SecretInputs) -> Self {
+        self.secrets = secrets;
+        self
+    }
+
+    pub(crate) fn with_target_flags(mut self, flags: BTreeMap<String, String>) -> Self {
+        self.target_flags = flags;
+        self
+    }
+
+    /// Board name of `zen_path` when it is the entry point of a workspace board.
+    fn board_name_for_zen(&self, zen_path: &Path) -> Option<String> {
+        self.resolution.workspace_info.board_name_for_zen(zen_path)
+    }
+
+    /// Dependency state this build used that the repository doesn't record.
+    fn lock_drift(&self) -> Vec<LockDrift> {
+        pcb_zen::lock_drift::lock_drift(&self.resolution)
+    }
+
+    /// `[board.budget]` of the board whose entry point is `zen_path`.
+    fn board_budget_for_zen(&self, zen_path: &Path) -> Option<BudgetConfig> {
+        self.resolution
+            .workspace_info
+            .board_config_for_zen(zen_path)
+            .map(|board| board.budget)
+    }
+
+    /// Build flags of `zen_path`: its board's `[board.target]`, overridden by
+    /// `--target`.
+    fn target_flags_for_zen(&self, zen_path: &Path) -> BTreeMap<String, String> {
+        let mut flags = self
+            .resolution
+            .workspace_info
+            .board_config_for_zen(zen_path)
+            .map(|board| board.target)
+            .unwrap_or_default();
+        flags.extend(self.target_flags.clone());
+        flags
+    }
+
     fn eval(
         &self,
         zen_path: &Path,
@@ -62,6 +148,8 @@ impl BuildEvalState {
         .set_source_path(source_path);
 
         ctx.set_json_inputs(inputs);
+        ctx.set_secret_inputs(self.secrets.clone());
+        ctx.set_target_flags(self.target_flags_for_zen(zen_path));
         ctx.eval()
     }
 
@@ -80,10 +168,14 @@ impl BuildEvalState {
         debug!("Compiling Zener file: {}", zen_path.display());
         let spinner = Spinner::builder(format!("{file_name}: Building")).start();
 
+        let input_names: Vec<String> = inputs.keys().cloned().collect();
         let eval_result = self.eval(zen_path, inputs);
         let mut diagnostics = eval_result.diagnostics;
 
         let output = if let Some(eval_output) = eval_result.output {
+            if let Some(diag) = unknown_inputs_diagnostic(zen_path, &input_names, &eval_output) {
+                diagnostics.diagnostics.push(diag);
+            }
             let _span = info_span!("electrical_checks").entered();
             for (check, defining_module) in eval_output.collect_electrical_checks() {
                 diagnostics
@@ -101,13 +193,41 @@ impl BuildEvalState {
             diagnostics
                 .diagnostics
                 .extend(schematic_result.diagnostics.diagnostics);
-            if let Some(ref schematic) = schematic_result.output {
+            let mut schematic = schematic_result.output;
+            if let Some(schematic) = &mut schematic {
+                if let Some(board) = self
+                    .resolution
+                    .workspace_info
+                    .board_config_for_zen(zen_path)
+                {
+                    if let Err(e) = pcb_sch::revision::check_revision_order(&board.revisions) {
+                        diagnostics
+                            .diagnostics
+                            .push(pcb_zen_core::Diagnostic::categorized(
+                                &zen_path.to_string_lossy(),
+                                &format!("Invalid [[board.revisions]] for {}: {e}", board.name),
+                                "board.revision",
+                                starlark::errors::EvalSeverity::Error,
+                            ));
+                    }
+                    schematic.revisions = board.revisions;
+                }
                 let erc_diagnostics = pcb_zen_core::run_schematic_erc(eval_output, schematic);
                 for diag in erc_diagnostics.diagnostics {
                     diagnostics.push_unique(diag);
                 }
+                diagnostics.diagnostics.extend(check_registered_nets(
+                    &self.net_registry,
+                    schematic,
+                    &zen_path.to_string_lossy(),
+                ));
+                diagnostics.diagnostics.extend(check_net_names(
+                    &self.net_names,
+                    schematic,
+                    &zen_path.to_string_lossy(),
+                ));
             }
-            schematic_result.output
+            schematic
         });
 
         if diagnostics.diagnostics.is_empty() && schematic.is_none() {
@@ -177,9 +297,19 @@ fn execute_electrical_check(
 pub fn create_diagnostics_passes(
     suppress: &[String],
     promote: &[String],
+) -> Vec<Box<dyn pcb_zen_core::DiagnosticsPass>> {
+    create_diagnostics_passes_with_lints(suppress, promote, &BTreeMap::new())
+}
+
+/// Like [`create_diagnostics_passes`], also applying `[lints]` levels from `pcb.toml`.
+pub fn create_diagnostics_passes_with_lints(
+    suppress: &[String],
+    promote: &[String],
+    lints: &BTreeMap<String, LintLevel>,
 ) -> Vec<Box<dyn pcb_zen_core::DiagnosticsPass>> {
     let mut passes: Vec<Box<dyn pcb_zen_core::DiagnosticsPass>> = vec![
         Box::new(pcb_zen_core::FilterHiddenPass),
+        Box::new(pcb_zen_core::LintLevelPass::new(lints.clone())),
         Box::new(pcb_zen_core::SuppressPass::new(suppress.to_vec())),
         Box::new(pcb_zen_core::CommentSuppressPass::new()),
     ];
@@ -204,13 +334,27 @@ pub struct BuildArgs {
     #[arg(value_name = "PATH", value_hint = clap::ValueHint::AnyPath)]
     pub paths: Vec<PathBuf>,
 
-    #[arg(long = "config", value_name = "KEY=VALUE", help = CONFIG_ARG_HELP)]
+    #[arg(long = "config", visible_alias = "input", value_name = "KEY=VALUE", help = CONFIG_ARG_HELP)]
     pub config: Vec<String>,
 
+    #[arg(long = "inputs-file", value_name = "PATH", help = INPUTS_FILE_ARG_HELP, value_hint = clap::ValueHint::FilePath)]
+    pub inputs_file: Option<PathBuf>,
+
+    #[arg(long = "secret-inputs-file", value_name = "PATH", help = SECRET_INPUTS_FILE_ARG_HELP, value_hint = clap::ValueHint::FilePath)]
+    pub secret_inputs_file: Option<PathBuf>,
+
+    #[arg(long = "target", value_name = "KEY=VALUE", help = TARGET_ARG_HELP)]
+    pub target: Vec<String>,
+
     /// Print JSON netlist to stdout (undocumented)
     #[arg(long = "netlist", hide = true)]
     pub netlist: bool,
 
+    /// Write a netlist next to each .zen file (repeatable): 'netlist-kicad' (.net),
+    /// 'netlist-allegro' (.tel), or 'netlist-eagle' (.sch)
+    #[arg(long = "emit", value_name = "FORMAT")]
+    pub emit: Vec<BuildEmit>,
+
     /// Write build diagnostics as JSON to PATH, or '-' for stdout
     #[arg(long = "diagnostics", value_name = "PATH", value_hint = clap::ValueHint::AnyPath)]
     pub diagnostics: Option<PathBuf>,
@@ -219,6 +363,11 @@ pub struct BuildArgs {
     #[arg(long = "offline")]
     pub offline: bool,
 
+    /// Build against a local copy of a package without editing pcb.toml, e.g.
+    /// '@stdlib=../stdlib' (repeatable). Overridden builds are flagged with warnings.
+    #[arg(long = "override-package", value_name = "TARGET=PATH")]
+    pub override_package: Vec<String>,
+
     /// Set lint level to deny (treat as error). Use 'warnings' for all warnings,
     /// or specific lint names like 'unstable-refs'
     #[arg(short = 'D', long = "deny", value_name = "LINT")]
@@ -235,6 +384,504 @@ pub struct BuildArgs {
     /// Supports hierarchical matching (e.g., 'style' matches 'style.naming.io')
     #[arg(short = 'W', long = "warn", value_name = "KIND")]
     pub warn: Vec<String>,
+
+    /// Trace where an io()/config() value came from, e.g. 'power.ldo.vout'.
+    /// Follows values forwarded from parent parameters up to their origin.
+    #[arg(long = "explain", value_name = "INSTANCE.PATH.PARAM")]
+    pub explain: Option<String>,
+
+    /// Profile Starlark evaluation of every module ('time' or 'alloc', default 'time').
+    /// Prints the hottest functions and files and writes folded stacks for
+    /// flamegraph tools to pcb-profile-KIND.folded.
+    #[arg(
+        long = "eval-profile",
+        value_name = "KIND",
+        num_args = 0..=1,
+        default_missing_value = "time"
+    )]
+    pub eval_profile: Option<BuildProfile>,
+
+    /// Print per-net capacitive loading and warn about nets over their
+    /// `[workspace.capacitance]` budget
+    #[arg(long = "analyze")]
+    pub analyze: bool,
+
+    /// Run KiCad's ERC on each board's layout schematic and report violations
+    /// against the component instances involved
+    #[arg(long = "kicad-erc")]
+    pub kicad_erc: bool,
+
+    /// Write the workspace's git describe and source hash into the header of
+    /// --emit netlists and the --netlist JSON
+    #[arg(long = "stamp")]
+    pub stamp: bool,
+
+    /// Replace module, instance, and net names and drop part numbers and notes
+    /// in --emit netlists (written as NAME.anon.EXT) and the --netlist JSON,
+    /// keeping connectivity, reference designators, and footprints
+    #[arg(long = "anonymize", conflicts_with = "stamp")]
+    pub anonymize: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BuildEmit {
+    NetlistKicad,
+    NetlistAllegro,
+    NetlistEagle,
+}
+
+impl BuildEmit {
+    fn netlist_format(self) -> NetlistFormat {
+        match self {
+            Self::NetlistKicad => NetlistFormat::Kicad,
+            Self::NetlistAllegro => NetlistFormat::Allegro,
+            Self::NetlistEagle => NetlistFormat::Eagle,
+        }
+    }
+}
+
+/// Error for `--config`/`--inputs-file` names the root module does not declare
+/// with config(), so a typo does not silently build the default configuration.
+fn unknown_inputs_diagnostic(
+    zen_path: &Path,
+    input_names: &[String],
+    output: &EvalOutput,
+) -> Option<pcb_zen_core::Diagnostic> {
+    let declared: HashSet<&str> = output
+        .signature
+        .iter()
+        .map(|param| param.name.as_str())
+        .collect();
+    let unknown: Vec<&str> = input_names
+        .iter()
+        .map(String::as_str)
+        .filter(|name| !declared.contains(name))
+        .collect();
+    if unknown.is_empty() {
+        return None;
+    }
+
+    let mut declared: Vec<&str> = declared.into_iter().collect();
+    declared.sort_unstable();
+    let declared = if declared.is_empty() {
+        "none".to_string()
+    } else {
+        declared.join(", ")
+    };
+    Some(pcb_zen_core::Diagnostic::new(
+        format!(
+            "Unknown input(s) for {}: {} (declared: {declared})",
+            zen_path.file_name().unwrap_or_default().to_string_lossy(),
+            unknown.join(", ")
+        ),
+        starlark::errors::EvalSeverity::Error,
+        zen_path,
+    ))
+}
+
+/// Directory emitted netlists go to: the board's layout directory, or
+/// `build/` next to `zen_path` for modules without a layout.
+fn emit_dir(zen_path: &Path, schematic: &Schematic) -> Result<PathBuf> {
+    let layout_dir = pcb_layout::utils::resolve_layout_dir(schematic)?;
+    Ok(layout_dir.unwrap_or_else(|| {
+        zen_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join("build")
+    }))
+}
+
+/// Write `export` as a netlist in `format` to `output_dir`, named after
+/// `zen_path`.
+fn emit_netlist(
+    zen_path: &Path,
+    output_dir: &Path,
+    export: &Schematic,
+    format: NetlistFormat,
+    anonymized: bool,
+) -> Result<PathBuf> {
+    let stem = zen_path.file_stem().unwrap_or_default().to_string_lossy();
+    let file_name = if anonymized {
+        format!("{stem}.anon.{}", format.extension())
+    } else {
+        format!("{stem}.{}", format.extension())
+    };
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create {}", output_dir.display()))?;
+    let output_path = output_dir.join(file_name);
+    std::fs::write(&output_path, export_netlist(export, format))
+        .with_context(|| format!("Failed to write {}", output_path.display()))?;
+    Ok(output_path)
+}
+
+/// `--analyze` settings from `[workspace.capacitance]`.
+struct NetLoading {
+    trace_pf_per_connection: f64,
+    budgets: CapacitanceBudgets,
+}
+
+impl NetLoading {
+    fn from_config(config: &CapacitanceConfig) -> Result<Self> {
+        let trace_pf_per_connection = match &config.trace_per_connection {
+            Some(value) => parse_picofarads(value)
+                .context("Invalid [workspace.capacitance] trace-per-connection")?,
+            None => DEFAULT_TRACE_CAPACITANCE_PF,
+        };
+        let budgets = CapacitanceBudgets::new(
+            config
+                .budgets
+                .iter()
+                .map(|(pattern, limit)| (pattern.as_str(), limit.as_str())),
+        )
+        .context("Invalid [workspace.capacitance] budgets")?;
+        Ok(Self {
+            trace_pf_per_connection,
+            budgets,
+        })
+    }
+
+    /// Print the loading table for `schematic` and return a warning for each
+    /// net over its budget.
+    fn analyze(
+        &self,
+        zen_path: &Path,
+        schematic: &Schematic,
+    ) -> Result<Vec<pcb_zen_core::Diagnostic>> {
+        let nets = net_capacitance(schematic, self.trace_pf_per_connection, &self.budgets)?;
+        if nets.is_empty() {
+            eprintln!(
+                "  {}",
+                "No nets with pin_capacitance or a capacitance budget".dimmed()
+            );
+            return Ok(Vec::new());
+        }
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL_CONDENSED);
+        table.set_header(
+            [
+                "Net",
+                "Pins",
+                "Pins (pF)",
+                "Trace (pF)",
+                "Total (pF)",
+                "Budget (pF)",
+            ]
+            .map(|title| Cell::new(title).fg(Color::Cyan)),
+        );
+        for net in &nets {
+            let pins = if net.unknown_pins > 0 {
+                format!("{} ({} unknown)", net.pins, net.unknown_pins)
+            } else {
+                net.pins.to_string()
+            };
+            let total = Cell::new(format!("{:.1}", net.total_pf()));
+            table.add_row(vec![
+                Cell::new(&net.net),
+                Cell::new(pins),
+                Cell::new(format!("{:.1}", net.pin_pf)),
+                Cell::new(format!("{:.1}", net.trace_pf)),
+                if net.over_budget() {
+                    total.fg(Color::Red)
+                } else {
+                    total
+                },
+                Cell::new(
+                    net.budget_pf
+                        .map_or_else(|| "-".to_string(), |budget| format!("{budget:.1}")),
+                ),
+            ]);
+        }
+        println!("{table}");
+
+        Ok(nets
+            .iter()
+            .filter(|net| net.over_budget())
+            .map(|net| {
+                pcb_zen_core::Diagnostic::categorized(
+                    &zen_path.to_string_lossy(),
+                    &format!(
+                        "Net '{}' loads {:.1}pF, over its {:.1}pF budget",
+                        net.net,
+                        net.total_pf(),
+                        net.budget_pf.unwrap_or_default()
+                    ),
+                    "analysis.capacitance_budget",
+                    starlark::errors::EvalSeverity::Warning,
+                )
+            })
+            .collect())
+    }
+}
+
+/// Print each `[board.budget]` figure against its limit and return a warning
+/// for each limit exceeded. BOM cost needs availability data and board area
+/// needs a layout; either is skipped with a note when unavailable.
+fn budget_diagnostics(
+    zen_path: &Path,
+    schematic: &Schematic,
+    budget: &BudgetConfig,
+    strict: bool,
+    offline: bool,
+) -> Result<Vec<pcb_zen_core::Diagnostic>> {
+    let max_bom_cost = budget.max_bom_cost_usd()?;
+    let max_area = budget.max_area_mm2()?;
+    let mut rows: Vec<(String, String, String, bool)> = Vec::new();
+    let mut diagnostics = Vec::new();
+    let mut warn = |message: String, kind: &str| {
+        diagnostics.push(pcb_zen_core::Diagnostic::categorized(
+            &zen_path.to_string_lossy(),
+            &message,
+            kind,
+            starlark::errors::EvalSeverity::Warning,
+        ));
+    };
+
+    let mut bom = schematic.bom();
+    crate::bom::apply_bom_rules(&mut bom, zen_path)?;
+    let mut bom = bom.filter_excluded();
+
+    if let Some(limit) = budget.max_unique_parts {
+        let parts = bom.unique_parts();
+        if parts > limit {
+            warn(
+                format!("Board uses {parts} unique parts, over its budget of {limit}"),
+                "budget.unique_parts",
+            );
+        }
+        rows.push((
+            "Unique parts".to_string(),
+            parts.to_string(),
+            limit.to_string(),
+            parts > limit,
+        ));
+    }
+
+    if let Some(limit) = max_bom_cost {
+        let quantity = budget.bom_quantity();
+        let label = format!("BOM cost @ {quantity}");
+        if fetch_availability(zen_path, &mut bom, strict, offline) {
+            let total = bom.board_cost(quantity);
+            let mut current = format!("${:.2}", total.cost);
+            if total.unpriced > 0 {
+                current.push_str(&format!(" ({} unpriced)", total.unpriced));
+            }
+            if total.cost > limit {
+                warn(
+                    format!(
+                        "Board parts cost ${:.2} at quantity {quantity}, over its ${limit:.2} budget",
+                        total.cost
+                    ),
+                    "budget.bom_cost",
+                );
+            }
+            rows.push((label, current, format!("${limit:.2}"), total.cost > limit));
+        } else {
+            rows.push((label, "-".to_string(), format!("${limit:.2}"), false));
+            eprintln!(
+                "  {}",
+                "No availability data; skipping BOM cost budget".dimmed()
+            );
+        }
+    }
+
+    if let Some(limit) = max_area {
+        let area = board_area_mm2(schematic)?;
+        match area {
+            Some(area) => {
+                if area > limit {
+                    warn(
+                        format!("Board area is {area:.0}mm², over its {limit:.0}mm² budget"),
+                        "budget.board_area",
+                    );
+                }
+                rows.push((
+                    "Board area (mm²)".to_string(),
+                    format!("{area:.0}"),
+                    format!("{limit:.0}"),
+                    area > limit,
+                ));
+            }
+            None => {
+                rows.push((
+                    "Board area (mm²)".to_string(),
+                    "-".to_string(),
+                    format!("{limit:.0}"),
+                    false,
+                ));
+                eprintln!(
+                    "  {}",
+                    "No board outline; skipping board area budget".dimmed()
+                );
+            }
+        }
+    }
+
+    if !rows.is_empty() {
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL_CONDENSED);
+        table.set_header(
+            ["Budget", "Current", "Limit"].map(|title| Cell::new(title).fg(Color::Cyan)),
+        );
+        for (name, current, limit, over) in rows {
+            let current = Cell::new(current);
+            table.add_row(vec![
+                Cell::new(name),
+                if over {
+                    current.fg(Color::Red)
+                } else {
+                    current
+                },
+                Cell::new(limit),
+            ]);
+        }
+        println!("{table}");
+    }
+
+    Ok(diagnostics)
+}
+
+/// Populate `bom` with pricing from the BOM service, returning whether any
+/// was fetched.
+fn fetch_availability(
+    zen_path: &Path,
+    bom: &mut pcb_sch::bom::Bom,
+    strict: bool,
+    offline: bool,
+) -> bool {
+    if offline {
+        return false;
+    }
+    let ctx = pcb_diode_api::WorkspaceContext::from_path(zen_path);
+    let Ok(token) = pcb_diode_api::auth::get_api_token_with_context(&ctx) else {
+        debug!("Not authenticated, skipping availability fetch");
+        return false;
+    };
+    if let Err(e) = pcb_diode_api::fetch_and_populate_availability_with_context(
+        &ctx,
+        token.as_deref(),
+        bom,
+        strict,
+    ) {
+        log::warn!("Failed to fetch availability data: {}", e);
+        return false;
+    }
+    !bom.availability.is_empty()
+}
+
+/// Area inside the outline of the board's layout, if it has one.
+fn board_area_mm2(schematic: &Schematic) -> Result<Option<f64>> {
+    let Some(layout_dir) = pcb_layout::utils::resolve_layout_dir(schematic)? else {
+        return Ok(None);
+    };
+    let Some(kicad_files) = pcb_layout::utils::discover_kicad_files(&layout_dir)? else {
+        return Ok(None);
+    };
+    let pcb_path = kicad_files.kicad_pcb();
+    if !pcb_path.exists() {
+        return Ok(None);
+    }
+    Ok(read_layout_stats(&pcb_path)?
+        .outline
+        .map(|outline| outline.area_mm2))
+}
+
+/// Run KiCad's ERC on the `.kicad_sch` in the board's layout directory. Each
+/// violation names the component instances it involves, found through the
+/// symbol's `Path` property or reference designator, and is reported against
+/// the source file that instantiates the first of them.
+fn kicad_erc_diagnostics(schematic: &Schematic) -> Result<Vec<pcb_zen_core::Diagnostic>> {
+    let Some(layout_dir) = pcb_layout::utils::resolve_layout_dir(schematic)? else {
+        eprintln!("  {}", "No layout; skipping KiCad ERC".dimmed());
+        return Ok(Vec::new());
+    };
+    let sch_path = pcb_layout::utils::discover_kicad_files(&layout_dir)?
+        .map(|files| files.kicad_sch())
+        .filter(|path| path.exists());
+    let Some(sch_path) = sch_path else {
+        eprintln!("  {}", "No KiCad schematic; skipping KiCad ERC".dimmed());
+        return Ok(Vec::new());
+    };
+
+    let report = pcb_kicad::run_erc_report(&sch_path, Some(&layout_dir))
+        .with_context(|| format!("Failed to run KiCad ERC on {}", sch_path.display()))?;
+    let symbols = pcb_kicad::erc::SchematicSymbols::from_file(&sch_path)?;
+
+    let by_path: HashMap<String, &pcb_sch::Instance> = schematic
+        .instances
+        .iter()
+        .map(|(instance_ref, instance)| (instance_ref.instance_path.join("."), instance))
+        .collect();
+    let by_refdes: HashMap<&str, String> = schematic
+        .instances
+        .iter()
+        .filter_map(|(instance_ref, instance)| {
+            Some((
+                instance.reference_designator.as_deref()?,
+                instance_ref.instance_path.join("."),
+            ))
+        })
+        .collect();
+    let root_source = schematic
+        .root_ref
+        .as_ref()
+        .map(|root| root.module.source_path.clone());
+
+    let sch_display = sch_path.to_string_lossy();
+    let mut diagnostics = Vec::new();
+    for sheet in &report.sheets {
+        for violation in &sheet.violations {
+            let instances: Vec<String> = violation
+                .symbols(&symbols)
+                .into_iter()
+                .filter_map(|symbol| {
+                    symbol
+                        .path
+                        .clone()
+                        .or_else(|| by_refdes.get(symbol.reference.as_str()).cloned())
+                })
+                .collect();
+            let mut diagnostic =
+                violation.to_instance_diagnostic(&sch_display, &sheet.path, &instances)?;
+            // The instantiating file is the parent module's, or the board's
+            // own file for top-level components.
+            let source = instances
+                .first()
+                .and_then(|path| match path.rsplit_once('.') {
+                    Some((parent, _)) => by_path
+                        .get(parent)
+                        .map(|parent| parent.type_ref.source_path.clone()),
+                    None => root_source.clone(),
+                });
+            if let Some(source) = source {
+                diagnostic.path = source.to_string_lossy().into_owned();
+            }
+            diagnostics.push(diagnostic);
+        }
+    }
+    Ok(diagnostics)
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum BuildProfile {
+    Time,
+    Alloc,
+}
+
+impl BuildProfile {
+    fn kind(self) -> ProfileKind {
+        match self {
+            Self::Time => ProfileKind::Time,
+            Self::Alloc => ProfileKind::Alloc,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Time => "time",
+            Self::Alloc => "alloc",
+        }
+    }
 }
 
 enum BuildInput {
@@ -269,15 +916,16 @@ impl BuildInput {
     }
 }
 
-fn select_build_input(paths: &[PathBuf], has_config: bool) -> Result<BuildInput> {
-    if has_config {
+/// `single_file_flag` names a flag that needs exactly one `.zen` file target.
+fn select_build_input(paths: &[PathBuf], single_file_flag: Option<&str>) -> Result<BuildInput> {
+    if let Some(flag) = single_file_flag {
         if paths.len() != 1 {
-            anyhow::bail!("--config requires a single .zen file target");
+            anyhow::bail!("{flag} requires a single .zen file target");
         }
 
         let path = &paths[0];
         if path.is_dir() {
-            anyhow::bail!("--config requires a single .zen file target");
+            anyhow::bail!("{flag} requires a single .zen file target");
         }
 
         file_walker::require_zen_file(path)?;
@@ -358,30 +1006,6 @@ pub fn print_build_success(file_name: &str, schematic: &Schematic) {
     );
 }
 
-#[instrument(name = "build_file", skip_all, fields(file = %zen_path.file_name().unwrap().to_string_lossy()))]
-pub fn build(
-    zen_path: &Path,
-    inputs: SmallMap<String, JsonValue>,
-    passes: Vec<Box<dyn pcb_zen_core::DiagnosticsPass>>,
-    deny_warnings: bool,
-    has_errors: &mut bool,
-    has_warnings: &mut bool,
-    resolution: ResolutionResult,
-) -> Option<Schematic> {
-    let eval_state = BuildEvalState::new(resolution);
-
-    eval_state
-        .build(
-            zen_path,
-            inputs,
-            passes,
-            deny_warnings,
-            has_errors,
-            has_warnings,
-        )
-        .schematic
-}
-
 fn workspace_relative_path(path: &Path, workspace_root: &Path) -> String {
     path.strip_prefix(workspace_root)
         .unwrap_or(path)
@@ -389,6 +1013,84 @@ fn workspace_relative_path(path: &Path, workspace_root: &Path) -> String {
         .to_string()
 }
 
+/// Note each root `[patch]` path entry the build resolves through, so local
+/// development copies are never used silently. `--override-package` entries
+/// are reported as warnings instead; see [`package_override_diagnostics`].
+fn print_path_patches(workspace: &WorkspaceInfo, overrides: &BTreeMap<String, PathBuf>) {
+    for (url, path) in workspace.path_patches() {
+        if overrides.contains_key(url) {
+            continue;
+        }
+        let name = if pcb_zen_core::is_stdlib_module_path(url) {
+            "@stdlib"
+        } else {
+            url
+        };
+        eprintln!(
+            "{} Using patched {} from {path}",
+            pcb_ui::icons::warning(),
+            name.bold()
+        );
+    }
+}
+
+/// Root schematic attribute listing the `--override-package` replacements a
+/// board was built with, as `url=path` strings.
+const ATTR_PACKAGE_OVERRIDES: &str = "package_overrides";
+
+/// Warnings recording that `zen_path` was built against local package copies,
+/// so such builds can't pass for ones reproducible from pcb.toml.
+fn package_override_diagnostics(
+    zen_path: &Path,
+    overrides: &BTreeMap<String, PathBuf>,
+) -> Diagnostics {
+    let mut diagnostics = Diagnostics::default();
+    for (url, path) in overrides {
+        diagnostics.push(pcb_zen_core::Diagnostic::categorized(
+            &zen_path.to_string_lossy(),
+            &format!(
+                "Package {url} is overridden by {}; this build does not match pcb.toml",
+                path.display()
+            ),
+            "build.package_override",
+            starlark::errors::EvalSeverity::Warning,
+        ));
+    }
+    diagnostics
+}
+
+/// Warnings for dependency state the build used but the repository doesn't
+/// record, so drift between checkouts shows up before it breaks someone else.
+fn lock_drift_diagnostics(drift: &[LockDrift]) -> Vec<pcb_zen_core::Diagnostic> {
+    drift
+        .iter()
+        .map(|drift| {
+            pcb_zen_core::Diagnostic::categorized(
+                &drift.path().to_string_lossy(),
+                &drift.to_string(),
+                "build.lock_drift",
+                starlark::errors::EvalSeverity::Warning,
+            )
+        })
+        .collect()
+}
+
+/// Record `overrides` on the root instance of `schematic`.
+fn record_package_overrides(schematic: &mut Schematic, overrides: &BTreeMap<String, PathBuf>) {
+    let Some(root) = schematic
+        .root_ref
+        .clone()
+        .and_then(|root_ref| schematic.instances.get_mut(&root_ref))
+    else {
+        return;
+    };
+    let entries = overrides
+        .iter()
+        .map(|(url, path)| AttributeValue::String(format!("{url}={}", path.display())))
+        .collect();
+    root.add_attribute(ATTR_PACKAGE_OVERRIDES, AttributeValue::Array(entries));
+}
+
 fn diagnostics_report_for_file(
     source_file: String,
     diagnostics: &Diagnostics,
@@ -414,6 +1116,85 @@ fn write_diagnostics_report(
         .with_context(|| format!("Failed to write diagnostics to {}", output_path.display()))
 }
 
+fn format_location(path: &str, span: Option<ResolvedSpan>, workspace_root: &Path) -> String {
+    let path = workspace_relative_path(Path::new(path), workspace_root);
+    match span {
+        Some(span) => format!("{path}:{}", span.begin.line + 1),
+        None => path,
+    }
+}
+
+/// Print the provenance chain from `pcb build --explain`, innermost instance first.
+fn print_parameter_trace(
+    steps: &[ParameterTraceStep],
+    secrets: &SecretInputs,
+    workspace_root: &Path,
+) {
+    for step in steps {
+        let name = if step.module.is_root() {
+            step.param.clone()
+        } else {
+            format!("{}.{}", step.module, step.param)
+        };
+        println!("{} = {}", name.bold(), secrets.redact(&step.value));
+        let origin = match &step.origin {
+            ValueOrigin::Passed {
+                path,
+                span,
+                forwarded_from,
+            } => {
+                let parent = step
+                    .module
+                    .parent()
+                    .filter(|parent| !parent.is_root())
+                    .map(|parent| format!("`{parent}`"))
+                    .unwrap_or_else(|| "the root module".to_string());
+                let forwarded = forwarded_from
+                    .as_ref()
+                    .map(|param| format!(" (its `{param}`)"))
+                    .unwrap_or_default();
+                format!(
+                    "passed by {parent}{forwarded} at {}",
+                    format_location(path, Some(*span), workspace_root)
+                )
+            }
+            ValueOrigin::Override if secrets.names().any(|secret| secret == step.param) => {
+                "secret input".to_string()
+            }
+            ValueOrigin::Override => "set with --config".to_string(),
+            ValueOrigin::Default { path, span } => format!(
+                "default, declared at {}",
+                format_location(path, *span, workspace_root)
+            ),
+        };
+        println!("  {} {}", "←".dimmed(), origin);
+    }
+}
+
+/// Write the folded stacks and print the hottest functions and files.
+fn write_profile(profile: BuildProfile, profiler: &Profiler) -> Result<()> {
+    let output_path = PathBuf::from(format!("pcb-profile-{}.folded", profile.as_str()));
+    std::fs::write(&output_path, profiler.folded())
+        .with_context(|| format!("Failed to write profile to {}", output_path.display()))?;
+
+    let summary = profiler.summary(10);
+    let percent = |weight: u64| 100.0 * weight as f64 / summary.total.max(1) as f64;
+    eprintln!(
+        "{} profile written to {}",
+        profile.as_str().bold(),
+        output_path.display()
+    );
+    eprintln!("  Hottest functions (self):");
+    for entry in &summary.functions {
+        eprintln!("    {:5.1}%  {}", percent(entry.weight), entry.name);
+    }
+    eprintln!("  Hottest files:");
+    for entry in &summary.files {
+        eprintln!("    {:5.1}%  {}", percent(entry.weight), entry.name);
+    }
+    Ok(())
+}
+
 pub fn execute(args: BuildArgs) -> Result<()> {
     let mut has_errors = false;
 
@@ -423,46 +1204,149 @@ pub fn execute(args: BuildArgs) -> Result<()> {
         );
     }
 
-    let build_input = select_build_input(&args.paths, !args.config.is_empty())?;
-    let config_inputs = parse_config_overrides(&args.config)?;
+    if args.explain.is_some() && args.netlist {
+        anyhow::bail!("--explain cannot be used with --netlist");
+    }
+
+    if args.analyze && args.netlist {
+        anyhow::bail!("--analyze cannot be used with --netlist");
+    }
+
+    if args.kicad_erc && args.netlist {
+        anyhow::bail!("--kicad-erc cannot be used with --netlist");
+    }
+
+    let single_file_flag = if !args.config.is_empty() {
+        Some("--config")
+    } else if args.inputs_file.is_some() {
+        Some("--inputs-file")
+    } else if args.secret_inputs_file.is_some() {
+        Some("--secret-inputs-file")
+    } else if args.explain.is_some() {
+        Some("--explain")
+    } else {
+        None
+    };
+    let build_input = select_build_input(&args.paths, single_file_flag)?;
+    let config_inputs = collect_config_inputs(&args.config, args.inputs_file.as_deref())?;
+    let secrets = load_secret_inputs(args.secret_inputs_file.as_deref())?;
+    let target_flags = parse_target_flags(&args.target)?;
+
+    let overrides = args
+        .override_package
+        .iter()
+        .map(|value| crate::resolve::parse_package_override(value))
+        .collect::<Result<Vec<_>>>()?;
 
     // Resolve dependencies before finding .zen files
-    let resolution = crate::resolve::resolve(build_input.resolve_path(), args.offline)?;
+    let (resolution, overrides) = crate::resolve::resolve_with_overrides(
+        build_input.resolve_path(),
+        args.offline,
+        &overrides,
+    )?;
     let workspace_root = resolution.workspace_info.root.clone();
+    print_path_patches(&resolution.workspace_info, &overrides);
 
     let zen_files = build_input.collect_zen_files(&resolution.workspace_info)?;
-
-    let eval_state = BuildEvalState::new(resolution);
+    let net_registry = resolution.workspace_info.workspace_config().nets;
+    let lints = resolution
+        .workspace_info
+        .config
+        .as_ref()
+        .map(|config| config.lints.clone())
+        .unwrap_or_default();
+    let capacitance = args
+        .analyze
+        .then(|| NetLoading::from_config(&resolution.workspace_info.workspace_config().capacitance))
+        .transpose()?;
+    let bom_strict = resolution.workspace_info.workspace_config().bom.strict;
+    let stamp = args
+        .stamp
+        .then(|| crate::stamp::workspace_stamp(&resolution.workspace_info))
+        .transpose()?;
+
+    let profiler = args
+        .eval_profile
+        .map(|profile| Arc::new(Profiler::new(profile.kind(), workspace_root.clone())));
+    let mut eval_state = BuildEvalState::new(resolution)
+        .with_secret_inputs(secrets)
+        .with_target_flags(target_flags);
+    if let Some(profiler) = &profiler {
+        eval_state = eval_state.with_profiler(profiler.clone());
+    }
 
     // Process each .zen file
     let deny_warnings = args.deny.contains(&"warnings".to_string());
     let mut has_warnings = false;
     let mut diagnostics_report = BTreeMap::new();
+    let mut board_nets = Vec::new();
     for zen_path in &zen_files {
         let file_name = zen_path.file_name().unwrap().to_string_lossy();
         let build_result = eval_state.build(
             zen_path,
             config_inputs.clone(),
-            create_diagnostics_passes(&args.suppress, &args.warn),
+            create_diagnostics_passes_with_lints(&args.suppress, &args.warn, &lints),
             deny_warnings,
             &mut has_errors,
             &mut has_warnings,
         );
 
+        let mut override_diagnostics = package_override_diagnostics(zen_path, &overrides);
+        override_diagnostics.apply_passes(&create_diagnostics_passes_with_lints(
+            &args.suppress,
+            &args.warn,
+            &lints,
+        ));
+        if override_diagnostics.error_count() > 0
+            || (deny_warnings && override_diagnostics.warning_count() > 0)
+        {
+            has_errors = true;
+        }
+
         if args.diagnostics.is_some() {
             let source_file = workspace_relative_path(zen_path, &workspace_root);
-            diagnostics_report.insert(
+            let mut report =
+                diagnostics_report_for_file(source_file.clone(), &build_result.diagnostics);
+            report.extend(diagnostics_report_for_file(
                 source_file.clone(),
-                diagnostics_report_for_file(source_file, &build_result.diagnostics),
-            );
+                &override_diagnostics,
+            ));
+            diagnostics_report.insert(source_file, report);
+        }
+
+        if let Some(target) = &args.explain
+            && let Some(eval_output) = &build_result.eval_output
+        {
+            let steps = explain_parameter(&eval_output.module_tree(), target)?;
+            print_parameter_trace(&steps, eval_output.secret_inputs(), &workspace_root);
         }
 
-        let Some(schematic) = build_result.schematic else {
+        let Some(mut schematic) = build_result.schematic else {
             continue;
         };
+        if !overrides.is_empty() {
+            record_package_overrides(&mut schematic, &overrides);
+        }
+        schematic.stamp = stamp.clone();
+
+        if !net_registry.is_empty()
+            && let Some(board) = eval_state.board_name_for_zen(zen_path)
+        {
+            board_nets.push(BoardNets {
+                board,
+                source_path: zen_path.to_string_lossy().into_owned(),
+                nets: board_net_names(&schematic),
+            });
+        }
+
+        let export = if args.anonymize {
+            Cow::Owned(schematic.anonymized())
+        } else {
+            Cow::Borrowed(&schematic)
+        };
 
         if args.netlist {
-            match schematic.to_json() {
+            match export.to_json() {
                 Ok(json) => println!("{json}"),
                 Err(e) => {
                     eprintln!("Error serializing netlist to JSON: {e}");
@@ -472,12 +1356,115 @@ pub fn execute(args: BuildArgs) -> Result<()> {
         } else {
             print_build_success(&file_name, &schematic);
         }
+
+        if let Some(loading) = &capacitance {
+            let mut diagnostics = Diagnostics::default();
+            diagnostics
+                .diagnostics
+                .extend(loading.analyze(zen_path, &schematic)?);
+            diagnostics.apply_passes(&create_diagnostics_passes_with_lints(
+                &args.suppress,
+                &args.warn,
+                &lints,
+            ));
+            if diagnostics.error_count() > 0 || (deny_warnings && diagnostics.warning_count() > 0) {
+                has_errors = true;
+            }
+        }
+
+        if args.kicad_erc {
+            let mut diagnostics = Diagnostics::default();
+            diagnostics
+                .diagnostics
+                .extend(kicad_erc_diagnostics(&schematic)?);
+            diagnostics.apply_passes(&create_diagnostics_passes_with_lints(
+                &args.suppress,
+                &args.warn,
+                &lints,
+            ));
+            if diagnostics.error_count() > 0 || (deny_warnings && diagnostics.warning_count() > 0) {
+                has_errors = true;
+            }
+        }
+
+        if !args.netlist
+            && let Some(budget) = eval_state
+                .board_budget_for_zen(zen_path)
+                .filter(|budget| budget != &BudgetConfig::default())
+        {
+            let mut diagnostics = Diagnostics::default();
+            diagnostics.diagnostics.extend(budget_diagnostics(
+                zen_path,
+                &schematic,
+                &budget,
+                bom_strict,
+                args.offline,
+            )?);
+            diagnostics.apply_passes(&create_diagnostics_passes_with_lints(
+                &args.suppress,
+                &args.warn,
+                &lints,
+            ));
+            if diagnostics.error_count() > 0 || (deny_warnings && diagnostics.warning_count() > 0) {
+                has_errors = true;
+            }
+        }
+
+        let mut emitted = HashSet::new();
+        let output_dir = if args.emit.is_empty() {
+            PathBuf::new()
+        } else {
+            emit_dir(zen_path, &schematic)?
+        };
+        for format in args.emit.iter().map(|emit| emit.netlist_format()) {
+            if !emitted.insert(format) {
+                continue;
+            }
+            let output_path = emit_netlist(zen_path, &output_dir, &export, format, args.anonymize)?;
+            if !args.netlist {
+                eprintln!("  {} {}", "Wrote".dimmed(), output_path.display());
+            }
+        }
+    }
+
+    // Boards are built one at a time; names shared between them are only
+    // visible once all of them are built.
+    if board_nets.len() > 1 {
+        let mut diagnostics = Diagnostics::default();
+        diagnostics
+            .diagnostics
+            .extend(check_net_name_collisions(&net_registry, &board_nets));
+        diagnostics.apply_passes(&create_diagnostics_passes_with_lints(
+            &args.suppress,
+            &args.warn,
+            &lints,
+        ));
+        if diagnostics.error_count() > 0 || (deny_warnings && diagnostics.warning_count() > 0) {
+            has_errors = true;
+        }
+    }
+
+    let mut diagnostics = Diagnostics::default();
+    diagnostics
+        .diagnostics
+        .extend(lock_drift_diagnostics(&eval_state.lock_drift()));
+    diagnostics.apply_passes(&create_diagnostics_passes_with_lints(
+        &args.suppress,
+        &args.warn,
+        &lints,
+    ));
+    if diagnostics.error_count() > 0 || (deny_warnings && diagnostics.warning_count() > 0) {
+        has_errors = true;
     }
 
     if let Some(output_path) = &args.diagnostics {
         write_diagnostics_report(output_path, &diagnostics_report)?;
     }
 
+    if let (Some(profile), Some(profiler)) = (args.eval_profile, &profiler) {
+        write_profile(profile, profiler)?;
+    }
+
     if has_errors {
         anyhow::bail!("Build failed with errors");
     }
@@ -15,9 +15,24 @@ pub(crate) struct MetadataInput<'a> {
     pub staging_dir: &'a Path,
     pub zen_path: &'a Path,
     pub layout_path: Option<&'a Path>,
+    pub layout_stats: Option<&'a pcb_layout::layout_stats::LayoutStats>,
+    pub stamp: &'a pcb_sch::stamp::Stamp,
     pub description: Option<&'a str>,
+    /// `[[board.revisions]]`, oldest first.
+    pub revisions: &'a [pcb_sch::revision::Revision],
     pub include_kicad_version: bool,
     pub bom_strict: bool,
+    pub workspace_info: &'a pcb_zen::WorkspaceInfo,
+    pub eval: EvalSettings,
+}
+
+/// Evaluation settings used to produce a release's `netlist.json`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct EvalSettings {
+    /// Dependencies were resolved without network access.
+    pub offline: bool,
+    /// Dependencies were read from the staged `vendor/` directory.
+    pub vendored: bool,
 }
 
 pub(crate) struct SourceBundlePlan<'a> {
@@ -160,16 +175,32 @@ fn create_metadata_json(input: &MetadataInput<'_>) -> serde_json::Value {
         release_obj["layout_path"] = serde_json::json!(layout_path);
     }
 
+    release_obj["stamp"] = serde_json::json!(input.stamp);
+
+    if let Some(layout_stats) = input.layout_stats {
+        release_obj["layout_stats"] = serde_json::json!(layout_stats);
+    }
+
     if let Some(description) = input.description
         && !description.is_empty()
     {
         release_obj["description"] = serde_json::json!(description);
     }
 
+    if let Some(revision) = input.revisions.last() {
+        release_obj["revision"] = serde_json::json!(revision);
+        release_obj["revisions"] = serde_json::json!(input.revisions);
+    }
+
     if input.bom_strict {
         release_obj["bom"] = serde_json::json!({ "strict": true });
     }
 
+    let assets = asset_provenance_json(input.workspace_info);
+    if !assets.is_empty() {
+        release_obj["assets"] = serde_json::Value::Array(assets);
+    }
+
     let workspace_root = input.workspace_root;
     let (branch, remotes) = {
         let _span = info_span!("collect_git_metadata").entered();
@@ -195,6 +226,7 @@ fn create_metadata_json(input: &MetadataInput<'_>) -> serde_json::Value {
         "platform": std::env::consts::OS,
         "arch": std::env::consts::ARCH,
         "cli_version": env!("CARGO_PKG_VERSION"),
+        "cli_git_sha": option_env!("PCB_GIT_SHA").unwrap_or("unknown"),
     });
 
     if input.include_kicad_version {
@@ -207,13 +239,103 @@ fn create_metadata_json(input: &MetadataInput<'_>) -> serde_json::Value {
         system_obj["kicad_version"] = serde_json::Value::String(kicad_version);
     }
 
+    let mut build_obj = serde_json::json!({
+        "eval": {
+            "offline": input.eval.offline,
+            "vendored": input.eval.vendored,
+        },
+        "dependencies_sha256": dependency_manifest_digest(input.workspace_info),
+    });
+    if let Ok(netlist) = fs::read_to_string(input.staging_dir.join("netlist.json")) {
+        build_obj["netlist_sha256"] =
+            serde_json::Value::String(netlist_digest(&netlist, input.staging_dir));
+    }
+
     serde_json::json!({
         "release": release_obj,
         "system": system_obj,
-        "git": git_obj
+        "git": git_obj,
+        "build": build_obj
     })
 }
 
+/// SHA256 of a staged netlist with `release_root` stripped from its source
+/// paths, so the same sources hash the same wherever the release is extracted.
+pub(crate) fn netlist_digest(netlist_json: &str, release_root: &Path) -> String {
+    let mut root = release_root.display().to_string();
+    if !root.ends_with(std::path::MAIN_SEPARATOR) {
+        root.push(std::path::MAIN_SEPARATOR);
+    }
+    // Paths appear JSON-escaped in the netlist (backslashes doubled on Windows).
+    let escaped = serde_json::to_string(&root).unwrap_or_default();
+    let escaped = escaped.trim_matches('"');
+    let relative = netlist_json.replace(escaped, "").replace(&root, "");
+    pcb_zen::provenance::sha256_hex(relative.as_bytes())
+}
+
+/// SHA256 over the workspace's hydrated `pcb.toml` manifests, which pin every
+/// resolved dependency version.
+fn dependency_manifest_digest(workspace_info: &pcb_zen::WorkspaceInfo) -> String {
+    let mut manifests: Vec<PathBuf> = std::iter::once(PathBuf::from("pcb.toml"))
+        .chain(
+            workspace_info
+                .packages
+                .values()
+                .map(|pkg| pkg.rel_path.join("pcb.toml")),
+        )
+        .collect();
+    manifests.sort();
+    manifests.dedup();
+
+    let mut content = Vec::new();
+    for rel_path in manifests {
+        let Ok(bytes) = fs::read(workspace_info.root.join(&rel_path)) else {
+            continue;
+        };
+        content.extend(rel_path.to_string_lossy().replace('\\', "/").as_bytes());
+        content.push(0);
+        content.extend(bytes);
+        content.push(0);
+    }
+    pcb_zen::provenance::sha256_hex(&content)
+}
+
+/// Provenance of vendored assets across the workspace, with paths relative to the root.
+fn asset_provenance_json(workspace_info: &pcb_zen::WorkspaceInfo) -> Vec<serde_json::Value> {
+    let root = &workspace_info.root;
+    let packages = workspace_info
+        .config
+        .iter()
+        .map(|config| (PathBuf::new(), config))
+        .chain(
+            workspace_info
+                .packages
+                .values()
+                .map(|pkg| (pkg.rel_path.clone(), &pkg.config)),
+        );
+
+    let mut assets = Vec::new();
+    for (rel_dir, config) in packages {
+        for entry in &config.provenance {
+            let path = rel_dir.join(&entry.path);
+            let mut asset = serde_json::json!({
+                "path": path.to_string_lossy().replace('\\', "/"),
+                "url": entry.url,
+                "sha256": entry.sha256,
+            });
+            if entry.embedded {
+                asset["embedded"] = serde_json::Value::Bool(true);
+            } else if let Ok(bytes) = fs::read(root.join(&path)) {
+                asset["verified"] = serde_json::Value::Bool(
+                    pcb_zen::provenance::sha256_hex(&bytes) == entry.sha256,
+                );
+            }
+            assets.push(asset);
+        }
+    }
+    assets
+}
+
 fn get_git_remotes(path: &Path) -> serde_json::Value {
     let mut remotes = serde_json::Map::new();
     let Some(remote_list) = git::run_output_opt(path, &["remote"]) else {
@@ -0,0 +1,20 @@
+use std::io;
+
+use clap::Args;
+use clap_complete::Shell;
+
+/// Binary name users invoke; completions register for this name.
+pub(crate) const BIN_NAME: &str = "pcb";
+
+#[derive(Args, Debug)]
+#[command(about = "Print a shell completion script")]
+pub struct CompletionsArgs {
+    /// Shell to generate completions for
+    #[arg(value_enum)]
+    pub shell: Shell,
+}
+
+pub fn execute(args: CompletionsArgs, mut cmd: clap::Command) -> anyhow::Result<()> {
+    clap_complete::generate(args.shell, &mut cmd, BIN_NAME, &mut io::stdout());
+    Ok(())
+}
@@ -1,10 +1,92 @@
-use anyhow::{Result, bail};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use pcb_zen_core::lang::secret::SecretInputs;
 use serde_json::Value as JsonValue;
 use starlark::collections::SmallMap;
 
 pub const CONFIG_ARG_HELP: &str = "Override root config() parameters. Repeat as needed.\n\
      Values are passed as strings and coerced by config() based on the declared parameter type.";
 
+pub const SECRET_INPUTS_FILE_ARG_HELP: &str = "Read secret root config() values from a JSON object file.\n\
+     Secret values are redacted from build outputs; so are PCB_SECRET_<NAME> environment variables.";
+
+pub const TARGET_ARG_HELP: &str = "Set a build flag that every module reads as target.<KEY>. Repeat as needed.\n\
+     Overrides [board.target] in pcb.toml.";
+
+pub const INPUTS_FILE_ARG_HELP: &str = "Read root config() values from a JSON or TOML object file.\n\
+     Values keep their JSON/TOML types; --config/--input entries override them.";
+
+/// Environment variables with this prefix supply secret root config() values,
+/// e.g. `PCB_SECRET_provisioning_key` for `config("provisioning_key", str)`.
+pub const SECRET_ENV_PREFIX: &str = "PCB_SECRET_";
+
+/// Secret inputs from `PCB_SECRET_*` environment variables, overridden by
+/// the entries of `secret_inputs_file` if given.
+pub fn load_secret_inputs(secret_inputs_file: Option<&Path>) -> Result<SecretInputs> {
+    let mut secrets = secrets_from_vars(std::env::vars());
+    let Some(path) = secret_inputs_file else {
+        return Ok(secrets);
+    };
+
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let JsonValue::Object(entries) = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?
+    else {
+        bail!("{} must contain a JSON object", path.display());
+    };
+    for (name, value) in entries {
+        secrets.insert(name, value);
+    }
+    Ok(secrets)
+}
+
+fn secrets_from_vars(vars: impl IntoIterator<Item = (String, String)>) -> SecretInputs {
+    let mut secrets = SecretInputs::default();
+    for (key, value) in vars {
+        if let Some(name) = key.strip_prefix(SECRET_ENV_PREFIX)
+            && !name.is_empty()
+        {
+            secrets.insert(name, JsonValue::String(value));
+        }
+    }
+    secrets
+}
+
+/// Root config() values from a `.json` or `.toml` file holding one object,
+/// e.g. `{"voltage": 3.3, "variant": "LITE"}`.
+pub fn load_inputs_file(path: &Path) -> Result<SmallMap<String, JsonValue>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let value = if path.extension().is_some_and(|ext| ext == "toml") {
+        let table: toml::Table = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        serde_json::to_value(table)?
+    } else {
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?
+    };
+    let JsonValue::Object(entries) = value else {
+        bail!("{} must contain a JSON object", path.display());
+    };
+    Ok(entries.into_iter().collect())
+}
+
+/// Root config() values from `--inputs-file`, overridden by `--config` entries.
+pub fn collect_config_inputs(
+    raw_configs: &[String],
+    inputs_file: Option<&Path>,
+) -> Result<SmallMap<String, JsonValue>> {
+    let mut inputs = match inputs_file {
+        Some(path) => load_inputs_file(path)?,
+        None => SmallMap::new(),
+    };
+    inputs.extend(parse_config_overrides(raw_configs)?);
+    Ok(inputs)
+}
+
 pub fn parse_config_overrides(raw_configs: &[String]) -> Result<SmallMap<String, JsonValue>> {
     let mut parsed = SmallMap::new();
 
@@ -23,9 +105,26 @@ pub fn parse_config_overrides(raw_configs: &[String]) -> Result<SmallMap<String,
     Ok(parsed)
 }
 
+/// Build flags from `--target KEY=VALUE` entries; later entries win.
+pub fn parse_target_flags(raw_flags: &[String]) -> Result<BTreeMap<String, String>> {
+    let mut flags = BTreeMap::new();
+    for raw in raw_flags {
+        let Some((key, value)) = raw.split_once('=') else {
+            bail!("Invalid --target '{raw}'. Expected key=value");
+        };
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            bail!("Invalid --target '{raw}'. Key must be a non-empty identifier");
+        }
+        flags.insert(key.to_string(), value.to_string());
+    }
+    Ok(flags)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::parse_config_overrides;
+    use super::{
+        collect_config_inputs, parse_config_overrides, parse_target_flags, secrets_from_vars,
+    };
     use serde_json::Value as JsonValue;
 
     #[test]
@@ -67,4 +166,46 @@ mod tests {
             "Invalid --config 'missing_separator'. Expected key=value"
         );
     }
+
+    #[test]
+    fn config_overrides_take_precedence_over_inputs_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("params.toml");
+        std::fs::write(&path, "voltage = 3.3\nvariant = \"LITE\"\n").unwrap();
+
+        let inputs = collect_config_inputs(&["variant=FULL".to_string()], Some(&path)).unwrap();
+
+        assert_eq!(inputs.get("voltage"), Some(&serde_json::json!(3.3)));
+        assert_eq!(
+            inputs.get("variant"),
+            Some(&JsonValue::String("FULL".to_string()))
+        );
+    }
+
+    #[test]
+    fn secrets_from_vars_only_reads_prefixed_variables() {
+        let secrets = secrets_from_vars([
+            ("PCB_SECRET_license_id".to_string(), "L-1234".to_string()),
+            ("PCB_SECRET_".to_string(), "ignored".to_string()),
+            ("HOME".to_string(), "/root".to_string()),
+        ]);
+
+        assert_eq!(secrets.names().collect::<Vec<_>>(), vec!["license_id"]);
+        assert_eq!(secrets.leaked_in("L-1234"), Some("license_id"));
+    }
+
+    #[test]
+    fn parse_target_flags_keeps_last_value() {
+        let raw = vec![
+            "variant=lite".to_string(),
+            "region=EU".to_string(),
+            "variant=full".to_string(),
+        ];
+        let flags = parse_target_flags(&raw).unwrap();
+        assert_eq!(flags["variant"], "full");
+        assert_eq!(flags["region"], "EU");
+
+        assert!(parse_target_flags(&["variant".to_string()]).is_err());
+        assert!(parse_target_flags(&["board.variant=lite".to_string()]).is_err());
+    }
 }
@@ -0,0 +1,169 @@
+//! Local crash reports for panics.
+//!
+//! The panic hook writes a plain-text report (version, OS, redacted command
+//! line, panic message and location, backtrace) to
+//! `<cache dir>/pcb/crash-reports/` and prints its path. Reports never leave
+//! the machine; users attach them to bug reports themselves. Set
+//! `PCB_CRASH_REPORTS=off` to skip writing them.
+
+use std::fmt::Write as _;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+const REDACTED: &str = "<redacted>";
+
+/// Flags whose value is replaced in reports because it may hold a credential
+/// or design parameter, e.g. `--config`, `--input`, `--token`.
+const SENSITIVE_FLAG_PARTS: &[&str] = &["config", "input", "token", "secret", "key", "password"];
+
+/// Install the crash-report panic hook in front of the default one.
+pub(crate) fn install_panic_hook() {
+    let disabled = std::env::var("PCB_CRASH_REPORTS")
+        .is_ok_and(|value| matches!(value.as_str(), "0" | "off" | "false"));
+    if disabled {
+        return;
+    }
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        match write_report(info) {
+            Some(path) => eprintln!(
+                "\n{} pcb crashed. A crash report was saved to {}\n\
+                 It has not been sent anywhere; please attach it when reporting this issue.",
+                "Error:".red(),
+                path.display()
+            ),
+            None => eprintln!("\n{} pcb crashed.", "Error:".red()),
+        }
+    }));
+}
+
+fn reports_dir() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("pcb").join("crash-reports"))
+}
+
+fn write_report(info: &PanicHookInfo<'_>) -> Option<PathBuf> {
+    let dir = reports_dir()?;
+    std::fs::create_dir_all(&dir).ok()?;
+    let now = chrono::Utc::now();
+    let path = dir.join(format!(
+        "crash-{}-{}.txt",
+        now.format("%Y%m%dT%H%M%SZ"),
+        std::process::id()
+    ));
+    let args: Vec<String> = std::env::args().collect();
+    std::fs::write(&path, render_report(info, &redact_args(&args), now)).ok()?;
+    Some(path)
+}
+
+fn render_report(
+    info: &PanicHookInfo<'_>,
+    args: &[String],
+    time: chrono::DateTime<chrono::Utc>,
+) -> String {
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "<non-string panic payload>".to_string());
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_else(|| "<unknown>".to_string());
+    let thread = std::thread::current();
+
+    let mut out = String::new();
+    writeln!(out, "pcb {}", env!("CARGO_PKG_VERSION")).unwrap();
+    writeln!(out, "time: {}", time.to_rfc3339()).unwrap();
+    writeln!(
+        out,
+        "os: {} {}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+    .unwrap();
+    writeln!(out, "command: {}", args.join(" ")).unwrap();
+    writeln!(out, "thread: {}", thread.name().unwrap_or("<unnamed>")).unwrap();
+    writeln!(out, "location: {location}").unwrap();
+    writeln!(out, "message: {}", redact_home(&message)).unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "backtrace:").unwrap();
+    writeln!(out, "{}", std::backtrace::Backtrace::force_capture()).unwrap();
+    out
+}
+
+/// Command line with sensitive flag values and the home directory removed.
+fn redact_args(args: &[String]) -> Vec<String> {
+    let mut out = Vec::with_capacity(args.len());
+    let mut redact_next = false;
+    for arg in args {
+        if redact_next {
+            out.push(REDACTED.to_string());
+            redact_next = false;
+            continue;
+        }
+        let Some(flag) = arg.strip_prefix("--") else {
+            out.push(redact_home(arg));
+            continue;
+        };
+        let (name, value) = match flag.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (flag, None),
+        };
+        let sensitive = SENSITIVE_FLAG_PARTS.iter().any(|part| name.contains(part));
+        match (sensitive, value) {
+            (true, Some(_)) => out.push(format!("--{name}={REDACTED}")),
+            (true, None) => {
+                out.push(arg.clone());
+                redact_next = true;
+            }
+            (false, _) => out.push(redact_home(arg)),
+        }
+    }
+    out
+}
+
+fn redact_home(text: &str) -> String {
+    match dirs::home_dir() {
+        Some(home) if !home.as_os_str().is_empty() => {
+            text.replace(home.to_string_lossy().as_ref(), "~")
+        }
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_sensitive_flag_values() {
+        let args: Vec<String> = [
+            "pcb",
+            "build",
+            "Board.zen",
+            "--config",
+            "key=abc",
+            "--inputs-file=inputs.json",
+            "--offline",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        assert_eq!(
+            redact_args(&args),
+            [
+                "pcb",
+                "build",
+                "Board.zen",
+                "--config",
+                REDACTED,
+                "--inputs-file=<redacted>",
+                "--offline"
+            ]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Local datasheet copies for `pcb open --datasheet` and release archives.
+//!
+//! Components carry their datasheet as a normalized attribute: an http(s)
+//! URL, a `package://` URI, or a filesystem path. Remote datasheets are
+//! downloaded once into `<workspace>/datasheets/` and recorded with their
+//! SHA256 in `datasheets/datasheets.toml`, so later lookups work offline.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use pcb_sch::{InstanceKind, Schematic};
+use pcb_zen::provenance::sha256_hex;
+use pcb_zen_core::config::AssetProvenance;
+use serde::{Deserialize, Serialize};
+
+/// Directory (relative to the workspace root) holding downloaded datasheets.
+pub(crate) const DATASHEETS_DIR: &str = "datasheets";
+
+const INDEX_FILE: &str = "datasheets.toml";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DatasheetIndex {
+    #[serde(default, rename = "datasheet")]
+    entries: Vec<AssetProvenance>,
+}
+
+impl DatasheetIndex {
+    fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(INDEX_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn save(&mut self, dir: &Path) -> Result<()> {
+        self.entries.sort_by(|a, b| a.url.cmp(&b.url));
+        let path = dir.join(INDEX_FILE);
+        fs::write(&path, toml::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Datasheet attribute of every component, keyed by reference designator.
+pub(crate) fn component_datasheets(schematic: &Schematic) -> BTreeMap<String, String> {
+    schematic
+        .instances
+        .values()
+        .filter(|instance| instance.kind == InstanceKind::Component)
+        .filter_map(|instance| {
+            let refdes = instance.reference_designator.clone()?;
+            let datasheet = instance.string_attr(&[pcb_zen_core::attrs::DATASHEET])?;
+            Some((refdes, datasheet))
+        })
+        .collect()
+}
+
+fn is_url(datasheet: &str) -> bool {
+    datasheet.starts_with("http://") || datasheet.starts_with("https://")
+}
+
+/// Return a local PDF for `datasheet`, downloading and caching remote ones.
+///
+/// Cached downloads are re-used only while their hash still matches the
+/// index; with `offline` set, a missing download is an error.
+pub(crate) fn local_datasheet(
+    schematic: &Schematic,
+    workspace_root: &Path,
+    datasheet: &str,
+    offline: bool,
+) -> Result<PathBuf> {
+    if !is_url(datasheet) {
+        let path = if datasheet.starts_with(pcb_sch::PACKAGE_URI_PREFIX) {
+            schematic.resolve_package_uri(datasheet)?
+        } else {
+            PathBuf::from(datasheet)
+        };
+        if !path.exists() {
+            bail!("Datasheet not found: {}", path.display());
+        }
+        return Ok(path);
+    }
+
+    let dir = workspace_root.join(DATASHEETS_DIR);
+    let mut index = DatasheetIndex::load(&dir)?;
+    if let Some(entry) = index.entries.iter().find(|entry| entry.url == datasheet) {
+        let path = dir.join(&entry.path);
+        if let Ok(bytes) = fs::read(&path)
+            && sha256_hex(&bytes).eq_ignore_ascii_case(&entry.sha256)
+        {
+            return Ok(path);
+        }
+    }
+    if offline {
+        bail!("Datasheet {datasheet} is not cached and --offline was given");
+    }
+
+    let bytes = download_pdf(datasheet)?;
+    let sha256 = sha256_hex(&bytes);
+    let file_name = format!("{}-{}.pdf", file_stem_for_url(datasheet), &sha256[..12]);
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(&file_name);
+    fs::write(&path, &bytes).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    index.entries.retain(|entry| entry.url != datasheet);
+    index.entries.push(AssetProvenance {
+        path: file_name,
+        url: datasheet.to_owned(),
+        sha256,
+        embedded: false,
+    });
+    index.save(&dir)?;
+    Ok(path)
+}
+
+fn download_pdf(url: &str) -> Result<Vec<u8>> {
+    let bytes = reqwest::blocking::Client::new()
+        .get(url)
+        .header(reqwest::header::USER_AGENT, "pcb")
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.bytes())
+        .with_context(|| format!("Failed to download datasheet {url}"))?;
+    if !bytes.starts_with(b"%PDF") {
+        bail!("Datasheet {url} did not return a PDF");
+    }
+    Ok(bytes.to_vec())
+}
+
+/// File stem derived from the last URL path segment, e.g. `tps54331` for
+/// `https://www.ti.com/lit/ds/symlink/tps54331.pdf?ts=1`.
+fn file_stem_for_url(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let segment = path.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+    let segment = segment
+        .strip_suffix(".pdf")
+        .or_else(|| segment.strip_suffix(".PDF"))
+        .unwrap_or(segment);
+    let stem: String = segment
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.is_empty() {
+        "datasheet".to_owned()
+    } else {
+        stem
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_stem_for_url_uses_last_segment() {
+        assert_eq!(
+            file_stem_for_url("https://www.ti.com/lit/ds/symlink/tps54331.pdf?ts=1"),
+            "tps54331"
+        );
+        assert_eq!(
+            file_stem_for_url("https://example.com/docs/LM 358.PDF"),
+            "LM_358"
+        );
+        assert_eq!(file_stem_for_url("https://example.com/"), "example_com");
+    }
+}
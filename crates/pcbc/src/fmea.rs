@@ -0,0 +1,52 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::build::create_diagnostics_passes;
+use crate::config_input::{CONFIG_ARG_HELP, parse_config_overrides};
+use anyhow::{Context, Result};
+use clap::Args;
+use pcb_sch::fmea::fmea_csv;
+
+#[derive(Args, Debug, Clone)]
+#[command(about = "Export an FMEA worksheet skeleton from failure-mode annotations as CSV")]
+pub struct FmeaArgs {
+    /// .zen file to process
+    #[arg(value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    pub file: PathBuf,
+
+    #[arg(long = "config", value_name = "KEY=VALUE", help = CONFIG_ARG_HELP)]
+    pub config: Vec<String>,
+
+    /// Write to this file instead of stdout
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    /// Disable network access (offline mode) - only use vendored dependencies
+    #[arg(long = "offline")]
+    pub offline: bool,
+}
+
+pub fn execute(args: FmeaArgs) -> Result<()> {
+    crate::file_walker::require_zen_file(&args.file)?;
+    let config_inputs = parse_config_overrides(&args.config)?;
+    let resolution_result = crate::resolve::resolve(Some(&args.file), args.offline)?;
+
+    let file_name = args.file.file_name().unwrap().to_string_lossy();
+    let eval_output = pcb_zen::eval(&args.file, resolution_result, config_inputs)
+        .output_result()
+        .map_err(|mut diagnostics| {
+            diagnostics.apply_passes(&create_diagnostics_passes(&[], &[]));
+            anyhow::anyhow!("Failed to build {} - cannot export FMEA", file_name)
+        })?;
+    let schematic = eval_output
+        .to_schematic()
+        .context("Failed to convert to schematic")?;
+
+    let rendered = fmea_csv(&schematic.fmea_rows())?;
+    match &args.output {
+        Some(path) => std::fs::write(path, rendered)
+            .with_context(|| format!("Failed to write {}", path.display()))?,
+        None => write!(io::stdout().lock(), "{rendered}")?,
+    }
+    Ok(())
+}
@@ -31,6 +31,11 @@ pub struct FmtArgs {
     /// Show diffs instead of writing files
     #[arg(long)]
     pub diff: bool,
+
+    /// Also organize load() statements in .zen files: merge loads of the same
+    /// file, drop duplicate and unused symbols, and sort them into groups
+    #[arg(long)]
+    pub fix_imports: bool,
 }
 
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
@@ -362,7 +367,7 @@ fn process_targets(
 
 pub fn execute(args: FmtArgs) -> Result<()> {
     // Create a ruff formatter instance
-    let formatter = RuffFormatter::default();
+    let formatter = RuffFormatter::default().with_fix_imports(args.fix_imports);
     let op = FmtOp::from_args(&args);
 
     // Print version info in debug mode
@@ -376,7 +381,12 @@ pub fn execute(args: FmtArgs) -> Result<()> {
         if !files_needing_format.is_empty() {
             eprintln!("\n{} files need formatting.", files_needing_format.len());
             eprintln!(
-                "\nRun 'pcb fmt {}' to format these files.",
+                "\nRun 'pcb fmt {}{}' to format these files.",
+                if args.fix_imports {
+                    "--fix-imports "
+                } else {
+                    ""
+                },
                 files_needing_format
                     .iter()
                     .map(|p| p.to_string_lossy())
@@ -0,0 +1,423 @@
+//! Git forge operations for `pcb publish`: version tags and releases with the
+//! board archive attached, on GitHub, GitLab, or Gitea.
+//!
+//! The forge is chosen from the host of the remote URL. `PCB_FORGE` names it
+//! for self-hosted instances whose host does not give it away. Releases are
+//! created with the forge's token from `GITHUB_TOKEN`, `GITLAB_TOKEN`, or
+//! `GITEA_TOKEN`.
+
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use pcb_zen::git;
+use reqwest::blocking::{Client, RequestBuilder, multipart};
+use serde_json::{Value, json};
+
+/// A release created on a forge.
+#[derive(Debug, Clone)]
+pub(crate) struct ForgeRelease {
+    /// Forge-specific release identifier used for asset uploads.
+    pub(crate) id: String,
+    /// Web page of the release.
+    pub(crate) url: String,
+}
+
+pub(crate) trait Forge {
+    fn name(&self) -> &'static str;
+
+    fn create_tag(&self, repo_root: &Path, tag: &str, message: &str) -> Result<()> {
+        git::create_tag(repo_root, tag, message)
+    }
+
+    fn push_tag(&self, repo_root: &Path, tag: &str, remote: &str) -> Result<()> {
+        git::push_tag(repo_root, tag, remote)
+    }
+
+    /// Create a release for the already pushed `tag`.
+    fn create_release(&self, tag: &str, title: &str, notes: &str) -> Result<ForgeRelease>;
+
+    /// Attach the file at `path` to `release`.
+    fn upload_asset(&self, release: &ForgeRelease, path: &Path) -> Result<()>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ForgeKind {
+    GitHub,
+    GitLab,
+    Gitea,
+}
+
+impl ForgeKind {
+    fn parse(name: &str) -> Result<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "github" => Ok(Self::GitHub),
+            "gitlab" => Ok(Self::GitLab),
+            "gitea" | "forgejo" => Ok(Self::Gitea),
+            other => bail!("Unknown PCB_FORGE '{other}'. Expected github, gitlab, or gitea"),
+        }
+    }
+
+    fn from_host(host: &str) -> Option<Self> {
+        match host {
+            "github.com" => Some(Self::GitHub),
+            "gitlab.com" => Some(Self::GitLab),
+            "codeberg.org" | "gitea.com" => Some(Self::Gitea),
+            _ if host.starts_with("github.") => Some(Self::GitHub),
+            _ if host.starts_with("gitlab.") => Some(Self::GitLab),
+            _ if host.starts_with("gitea.") || host.starts_with("forgejo.") => Some(Self::Gitea),
+            _ => None,
+        }
+    }
+
+    fn token_var(self) -> &'static str {
+        match self {
+            Self::GitHub => "GITHUB_TOKEN",
+            Self::GitLab => "GITLAB_TOKEN",
+            Self::Gitea => "GITEA_TOKEN",
+        }
+    }
+}
+
+/// Repository location on a forge, from a `host/owner/repo` path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Repository {
+    host: String,
+    /// `owner/repo`, or `group/subgroup/repo` on GitLab.
+    path: String,
+}
+
+impl Repository {
+    fn parse(repository: &str) -> Result<Self> {
+        match repository.split_once('/') {
+            Some((host, path)) if path.contains('/') => Ok(Self {
+                host: host.to_string(),
+                path: path.trim_end_matches('/').to_string(),
+            }),
+            _ => bail!("Cannot tell the owner and name of repository '{repository}'"),
+        }
+    }
+}
+
+/// The forge hosting `remote_url`, authenticated from the environment.
+pub(crate) fn from_remote_url(remote_url: &str) -> Result<Box<dyn Forge>> {
+    let repository = Repository::parse(&git::parse_remote_url(remote_url)?)?;
+    let kind = match std::env::var("PCB_FORGE") {
+        Ok(name) if !name.trim().is_empty() => ForgeKind::parse(&name)?,
+        _ => ForgeKind::from_host(&repository.host).with_context(|| {
+            format!(
+                "Cannot tell which forge hosts {}. Set PCB_FORGE to github, gitlab, or gitea",
+                repository.host
+            )
+        })?,
+    };
+    let token = std::env::var(kind.token_var())
+        .ok()
+        .filter(|token| !token.trim().is_empty())
+        .with_context(|| {
+            format!(
+                "Set {} to create releases on {}",
+                kind.token_var(),
+                repository.host
+            )
+        })?;
+
+    let client = Client::new();
+    Ok(match kind {
+        ForgeKind::GitHub => Box::new(GitHub {
+            api: github_api_base(&repository.host),
+            repository,
+            token,
+            client,
+        }),
+        ForgeKind::GitLab => Box::new(GitLab {
+            repository,
+            token,
+            client,
+        }),
+        ForgeKind::Gitea => Box::new(Gitea {
+            repository,
+            token,
+            client,
+        }),
+    })
+}
+
+fn github_api_base(host: &str) -> String {
+    if host == "github.com" {
+        "https://api.github.com".to_string()
+    } else {
+        // GitHub Enterprise Server
+        format!("https://{host}/api/v3")
+    }
+}
+
+fn file_name(path: &Path) -> Result<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .with_context(|| format!("{} is not a file", path.display()))
+}
+
+fn query_escape(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Send `request` and parse the JSON response, naming `action` on failure.
+fn send_json(request: RequestBuilder, action: &str) -> Result<Value> {
+    let response = request
+        .header(reqwest::header::USER_AGENT, "pcb")
+        .send()
+        .with_context(|| format!("Failed to {action}"))?;
+    let status = response.status();
+    let body = response.text().unwrap_or_default();
+    if !status.is_success() {
+        bail!("Failed to {action}: {status} {}", body.trim());
+    }
+    serde_json::from_str(&body).with_context(|| format!("Failed to {action}: invalid response"))
+}
+
+fn json_str<'a>(value: &'a Value, pointer: &str) -> Result<&'a str> {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .with_context(|| format!("Forge response is missing {pointer}"))
+}
+
+fn json_id(value: &Value) -> Result<String> {
+    value
+        .get("id")
+        .and_then(Value::as_u64)
+        .map(|id| id.to_string())
+        .context("Forge response is missing the release id")
+}
+
+struct GitHub {
+    api: String,
+    repository: Repository,
+    token: String,
+    client: Client,
+}
+
+impl Forge for GitHub {
+    fn name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    fn create_release(&self, tag: &str, title: &str, notes: &str) -> Result<ForgeRelease> {
+        let url = format!("{}/repos/{}/releases", self.api, self.repository.path);
+        let release = send_json(
+            self.client
+                .post(url)
+                .bearer_auth(&self.token)
+                .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+                .json(&json!({ "tag_name": tag, "name": title, "body": notes })),
+            "create GitHub release",
+        )?;
+        Ok(ForgeRelease {
+            id: json_id(&release)?,
+            url: json_str(&release, "/html_url")?.to_string(),
+        })
+    }
+
+    fn upload_asset(&self, release: &ForgeRelease, path: &Path) -> Result<()> {
+        let uploads = if self.api == "https://api.github.com" {
+            "https://uploads.github.com".to_string()
+        } else {
+            format!("https://{}/api/uploads", self.repository.host)
+        };
+        let url = format!(
+            "{uploads}/repos/{}/releases/{}/assets",
+            self.repository.path, release.id
+        );
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        send_json(
+            self.client
+                .post(format!("{url}?name={}", query_escape(&file_name(path)?)))
+                .bearer_auth(&self.token)
+                .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+                .body(bytes),
+            "upload GitHub release asset",
+        )?;
+        Ok(())
+    }
+}
+
+struct GitLab {
+    repository: Repository,
+    token: String,
+    client: Client,
+}
+
+impl GitLab {
+    fn project_api(&self) -> String {
+        format!(
+            "https://{}/api/v4/projects/{}",
+            self.repository.host,
+            self.repository.path.replace('/', "%2F")
+        )
+    }
+}
+
+impl Forge for GitLab {
+    fn name(&self) -> &'static str {
+        "GitLab"
+    }
+
+    fn create_release(&self, tag: &str, title: &str, notes: &str) -> Result<ForgeRelease> {
+        let release = send_json(
+            self.client
+                .post(format!("{}/releases", self.project_api()))
+                .header("PRIVATE-TOKEN", &self.token)
+                .json(&json!({ "tag_name": tag, "name": title, "description": notes })),
+            "create GitLab release",
+        )?;
+        let url = json_str(&release, "/_links/self").map_or_else(
+            |_| {
+                format!(
+                    "https://{}/{}/-/releases/{tag}",
+                    self.repository.host, self.repository.path
+                )
+            },
+            str::to_string,
+        );
+        Ok(ForgeRelease {
+            id: tag.to_string(),
+            url,
+        })
+    }
+
+    fn upload_asset(&self, release: &ForgeRelease, path: &Path) -> Result<()> {
+        // GitLab releases link to files rather than hold them: upload the file
+        // to the project, then link it from the release.
+        let name = file_name(path)?;
+        let form = multipart::Form::new()
+            .file("file", path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let upload = send_json(
+            self.client
+                .post(format!("{}/uploads", self.project_api()))
+                .header("PRIVATE-TOKEN", &self.token)
+                .multipart(form),
+            "upload GitLab release asset",
+        )?;
+        let link = match json_str(&upload, "/full_path") {
+            Ok(full_path) => format!("https://{}{full_path}", self.repository.host),
+            Err(_) => format!(
+                "https://{}/{}{}",
+                self.repository.host,
+                self.repository.path,
+                json_str(&upload, "/url")?
+            ),
+        };
+        send_json(
+            self.client
+                .post(format!(
+                    "{}/releases/{}/assets/links",
+                    self.project_api(),
+                    query_escape(&release.id)
+                ))
+                .header("PRIVATE-TOKEN", &self.token)
+                .json(&json!({ "name": name, "url": link, "link_type": "package" })),
+            "link GitLab release asset",
+        )?;
+        Ok(())
+    }
+}
+
+struct Gitea {
+    repository: Repository,
+    token: String,
+    client: Client,
+}
+
+impl Gitea {
+    fn repo_api(&self) -> String {
+        format!(
+            "https://{}/api/v1/repos/{}",
+            self.repository.host, self.repository.path
+        )
+    }
+}
+
+impl Forge for Gitea {
+    fn name(&self) -> &'static str {
+        "Gitea"
+    }
+
+    fn create_release(&self, tag: &str, title: &str, notes: &str) -> Result<ForgeRelease> {
+        let release = send_json(
+            self.client
+                .post(format!("{}/releases", self.repo_api()))
+                .header(
+                    reqwest::header::AUTHORIZATION,
+                    format!("token {}", self.token),
+                )
+                .json(&json!({ "tag_name": tag, "name": title, "body": notes })),
+            "create Gitea release",
+        )?;
+        Ok(ForgeRelease {
+            id: json_id(&release)?,
+            url: json_str(&release, "/html_url")?.to_string(),
+        })
+    }
+
+    fn upload_asset(&self, release: &ForgeRelease, path: &Path) -> Result<()> {
+        let form = multipart::Form::new()
+            .file("attachment", path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        send_json(
+            self.client
+                .post(format!(
+                    "{}/releases/{}/assets?name={}",
+                    self.repo_api(),
+                    release.id,
+                    query_escape(&file_name(path)?)
+                ))
+                .header(
+                    reqwest::header::AUTHORIZATION,
+                    format!("token {}", self.token),
+                )
+                .multipart(form),
+            "upload Gitea release asset",
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forge_kind_from_host() {
+        assert_eq!(ForgeKind::from_host("github.com"), Some(ForgeKind::GitHub));
+        assert_eq!(
+            ForgeKind::from_host("gitlab.example.com"),
+            Some(ForgeKind::GitLab)
+        );
+        assert_eq!(ForgeKind::from_host("codeberg.org"), Some(ForgeKind::Gitea));
+        assert_eq!(ForgeKind::from_host("git.example.com"), None);
+        assert_eq!(ForgeKind::parse("Forgejo").unwrap(), ForgeKind::Gitea);
+        assert!(ForgeKind::parse("bitbucket").is_err());
+    }
+
+    #[test]
+    fn repository_keeps_nested_groups() {
+        let repository = Repository::parse("gitlab.com/acme/hw/boards").unwrap();
+        assert_eq!(repository.host, "gitlab.com");
+        assert_eq!(repository.path, "acme/hw/boards");
+        assert!(Repository::parse("gitlab.com/acme").is_err());
+        assert_eq!(
+            github_api_base("github.acme.com"),
+            "https://github.acme.com/api/v3"
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::build::{BuildEvalState, create_diagnostics_passes};
+use anyhow::{Result, bail};
+use clap::{Args, ValueEnum};
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::{Cell, Color, Table};
+use pcb_sch::query::glob_match;
+use pcb_sch::{Instance, InstanceKind, Schematic};
+use serde::Serialize;
+
+#[derive(ValueEnum, Debug, Clone, Default)]
+pub enum GrepFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl std::fmt::Display for GrepFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GrepFormat::Text => write!(f, "text"),
+            GrepFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+#[command(about = "Find boards that use a component or module")]
+pub struct GrepArgs {
+    /// Manufacturer part number, e.g. `TPS54331DR` or `STM32G0*`. Alternatives match too.
+    #[arg(
+        long,
+        value_name = "MPN",
+        required_unless_present = "module",
+        conflicts_with = "module"
+    )]
+    pub component: Option<String>,
+
+    /// Module file, e.g. `Regulator.zen` or `github.com/acme/lib/Regulator.zen`
+    #[arg(long, value_name = "MODULE")]
+    pub module: Option<String>,
+
+    /// Only search boards under this directory or file (defaults to the whole workspace)
+    #[arg(value_name = "PATH", value_hint = clap::ValueHint::AnyPath)]
+    pub path: Option<PathBuf>,
+
+    /// Output format
+    #[arg(short, long, default_value_t = GrepFormat::Text)]
+    pub format: GrepFormat,
+
+    /// Disable network access (offline mode) - only use vendored dependencies
+    #[arg(long = "offline")]
+    pub offline: bool,
+}
+
+/// One instance using the searched component or module.
+#[derive(Debug, Serialize)]
+struct Usage {
+    board: String,
+    instance: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refdes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mpn: Option<String>,
+    /// Defining file, as `<package url>/<path>` when it belongs to a package.
+    source: String,
+    /// Resolved version of the defining package; `None` for workspace packages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+}
+
+enum Target {
+    Component(String),
+    Module(String),
+}
+
+pub fn execute(args: GrepArgs) -> Result<()> {
+    let target = match (&args.component, &args.module) {
+        (Some(mpn), _) => Target::Component(mpn.to_ascii_lowercase()),
+        (None, Some(module)) => Target::Module(module.trim_start_matches("./").to_string()),
+        (None, None) => unreachable!("clap requires --component or --module"),
+    };
+
+    let resolution = crate::resolve::resolve(args.path.as_deref(), args.offline)?;
+    let workspace_root = resolution.workspace_info.root.clone();
+    let scope = args.path.as_deref().map(Path::canonicalize).transpose()?;
+    let boards: Vec<(String, PathBuf)> = resolution
+        .workspace_info
+        .boards()
+        .into_values()
+        .map(|board| {
+            let zen_path = board.absolute_zen_path(&workspace_root);
+            (board.name, zen_path)
+        })
+        .filter(|(_, zen_path)| {
+            scope
+                .as_ref()
+                .is_none_or(|scope| zen_path.starts_with(scope))
+        })
+        .collect();
+    if boards.is_empty() {
+        bail!("No boards found to search");
+    }
+
+    let eval_state = BuildEvalState::new(resolution);
+    let mut has_errors = false;
+    let mut failed = 0;
+    let mut usages = Vec::new();
+    for (board, zen_path) in &boards {
+        let result = eval_state.build(
+            zen_path,
+            Default::default(),
+            create_diagnostics_passes(&[], &[]),
+            false,
+            &mut has_errors,
+            &mut false,
+        );
+        match result.schematic {
+            Some(schematic) => usages.extend(find_usages(board, &schematic, &target)),
+            None => failed += 1,
+        }
+    }
+    usages.sort_by(|a, b| (&a.board, &a.instance).cmp(&(&b.board, &b.instance)));
+
+    let mut writer = io::stdout().lock();
+    match args.format {
+        GrepFormat::Json => writeln!(writer, "{}", serde_json::to_string_pretty(&usages)?)?,
+        GrepFormat::Text if usages.is_empty() => {
+            let needle = args.component.as_ref().or(args.module.as_ref()).unwrap();
+            eprintln!("No usages of {needle} in {} board(s)", boards.len());
+        }
+        GrepFormat::Text => writeln!(writer, "{}", usage_table(&usages))?,
+    }
+
+    if failed > 0 {
+        bail!("{failed} board(s) failed to build; results are incomplete");
+    }
+    Ok(())
+}
+
+fn find_usages(board: &str, schematic: &Schematic, target: &Target) -> Vec<Usage> {
+    schematic
+        .instances
+        .iter()
+        .filter(|(instance_ref, _)| !instance_ref.instance_path.is_empty())
+        .filter_map(|(instance_ref, instance)| {
+            let (source, version) =
+                package_source(&instance.type_ref.source_path, &schematic.package_roots);
+            let mpn = match target {
+                Target::Component(pattern) => Some(matching_mpn(instance, pattern)?),
+                Target::Module(module) => {
+                    let is_module = matches!(
+                        instance.kind,
+                        InstanceKind::Module | InstanceKind::Component
+                    );
+                    if !is_module || !Path::new(&source).ends_with(module) {
+                        return None;
+                    }
+                    instance.mpn()
+                }
+            };
+            Some(Usage {
+                board: board.to_string(),
+                instance: instance_ref.instance_path.join("."),
+                refdes: instance.reference_designator.clone(),
+                mpn,
+                source,
+                version,
+            })
+        })
+        .collect()
+}
+
+/// The primary or alternative MPN of a component matching `pattern`, which
+/// must already be lowercase.
+fn matching_mpn(instance: &Instance, pattern: &str) -> Option<String> {
+    if instance.kind != InstanceKind::Component {
+        return None;
+    }
+    instance
+        .mpn()
+        .into_iter()
+        .chain(instance.alternatives_attr().into_iter().map(|alt| alt.mpn))
+        .find(|mpn| glob_match(pattern.as_bytes(), mpn.to_ascii_lowercase().as_bytes()))
+}
+
+/// `<package url>/<path>` of `path` and the owning package's resolved version,
+/// using the longest matching package root. Files outside every package are
+/// shown as-is.
+fn package_source(
+    path: &Path,
+    package_roots: &BTreeMap<String, PathBuf>,
+) -> (String, Option<String>) {
+    let Some((coord, rel)) = package_roots
+        .iter()
+        .filter_map(|(coord, root)| Some((coord, root, path.strip_prefix(root).ok()?)))
+        .max_by_key(|(_, root, _)| root.as_os_str().len())
+        .map(|(coord, _, rel)| (coord, rel))
+    else {
+        return (path.display().to_string(), None);
+    };
+
+    let (url, version) = match coord.rsplit_once('@') {
+        Some((url, version)) => (url, Some(version.to_string())),
+        None => (coord.as_str(), None),
+    };
+    let rel = rel.to_string_lossy().replace('\\', "/");
+    let source = if rel.is_empty() {
+        url.to_string()
+    } else {
+        format!("{url}/{rel}")
+    };
+    (source, version)
+}
+
+fn usage_table(usages: &[Usage]) -> Table {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED);
+    table.set_header(
+        ["Board", "Instance", "Refdes", "MPN", "Source", "Version"]
+            .map(|title| Cell::new(title).fg(Color::Cyan)),
+    );
+    for usage in usages {
+        table.add_row(vec![
+            Cell::new(&usage.board),
+            Cell::new(&usage.instance),
+            Cell::new(usage.refdes.as_deref().unwrap_or("-")),
+            Cell::new(usage.mpn.as_deref().unwrap_or("-")),
+            Cell::new(&usage.source),
+            Cell::new(usage.version.as_deref().unwrap_or("workspace")),
+        ]);
+    }
+    table
+}
@@ -10,9 +10,10 @@ use super::*;
 use anyhow::{Context, Result};
 use log::debug;
 use pcb_component_gen as component_gen;
+use pcb_eda::kicad::symbol_writer::{BoxPin, SymbolDefinition, symbol_library_text};
 use pcb_sexpr::Sexpr;
 use pcb_sexpr::find_child_list;
-use pcb_sexpr::formatter::{FormatMode, format_tree};
+use pcb_sexpr::formatter::{KicadFileKind, format_kicad};
 use pcb_sexpr::kicad::symbol::{
     kicad_symbol_lib_items_mut, rewrite_symbol_properties, symbol_names, symbol_properties,
 };
@@ -316,8 +317,6 @@ fn compute_set_footprint_sync_hook_patches_by_refdes(
     pcb_text: &str,
     desired_by_refdes: &BTreeMap<KiCadRefDes, String>,
 ) -> std::result::Result<PatchSet, anyhow::Error> {
-    const UUID_NAMESPACE_URL: Uuid = Uuid::from_u128(0x6ba7b811_9dad_11d1_80b4_00c04fd430c8); // uuid.NAMESPACE_URL
-
     let root_list = board
         .as_list()
         .ok_or_else(|| anyhow::anyhow!("KiCad PCB root is not a list"))?;
@@ -380,7 +379,7 @@ fn compute_set_footprint_sync_hook_patches_by_refdes(
         // Note: This overwrites KiCad's schematic association path. That's intentional: once a
         // KiCad project is adopted into Zener, Zener becomes the source of truth and the layout
         // sync pipeline relies on this deterministic KIID path.
-        let uuid = Uuid::new_v5(&UUID_NAMESPACE_URL, desired.as_bytes()).to_string();
+        let uuid = Uuid::new_v5(&Uuid::NAMESPACE_URL, desired.as_bytes()).to_string();
         for span in path_spans {
             patches.replace_string(span, &format!("/{uuid}/{uuid}"));
         }
@@ -1960,27 +1959,31 @@ fn render_component_symbol(
         .filter(|k| schematic_lib_symbols.contains_key(k))
         .or_else(|| unit.and_then(|u| u.lib_id.clone()));
 
-    let Some(lib_id) = lib_id else {
-        anyhow::bail!(
-            "Missing schematic lib_id/lib_name for {}",
-            component.netlist.refdes.as_str()
-        );
-    };
-
-    let Some(sym) = schematic_lib_symbols.get(&lib_id) else {
-        anyhow::bail!(
-            "Missing embedded lib_symbol {} for {}",
-            lib_id.as_str(),
-            component.netlist.refdes.as_str()
-        );
+    let embedded = lib_id
+        .as_ref()
+        .and_then(|lib_id| schematic_lib_symbols.get(lib_id));
+    let library_text = match embedded {
+        Some(sym) => pcb_eda::kicad::symbol_library::wrap_symbol_as_library(sym, "pcb import"),
+        // No symbol to copy: fall back to a box symbol built from the footprint pads.
+        None => {
+            synthesize_box_symbol(component_name, component).with_context(|| match &lib_id {
+                Some(lib_id) => format!(
+                    "Missing embedded lib_symbol {} for {}",
+                    lib_id.as_str(),
+                    component.netlist.refdes.as_str()
+                ),
+                None => format!(
+                    "Missing schematic lib_id/lib_name for {}",
+                    component.netlist.refdes.as_str()
+                ),
+            })?
+        }
     };
-
-    let library_text = pcb_eda::kicad::symbol_library::wrap_symbol_as_library(sym, "pcb import");
     let parsed = pcb_eda::SymbolLibrary::from_string(&library_text, "kicad_sym")
-        .context("Failed to parse embedded KiCad symbol as a symbol library")?;
+        .context("Failed to parse KiCad symbol library")?;
     let symbol = parsed
         .first_symbol()
-        .context("Embedded symbol library contained no symbols")?
+        .context("Symbol library contained no symbols")?
         .clone();
 
     Ok(RenderedComponentSymbol {
@@ -1990,6 +1993,59 @@ fn render_component_symbol(
     })
 }
 
+/// Build a `.kicad_sym` library with a rectangular symbol that has one passive
+/// pin per footprint pad, for parts whose schematic symbol isn't available.
+fn synthesize_box_symbol(component_name: &str, component: &ImportComponentData) -> Result<String> {
+    let layout = component
+        .layout
+        .as_ref()
+        .context("No footprint pads to derive a symbol from")?;
+    let mut pad_numbers: Vec<&str> = layout
+        .pads
+        .keys()
+        .map(KiCadPinNumber::as_str)
+        .filter(|number| !number.is_empty())
+        .collect();
+    anyhow::ensure!(
+        !pad_numbers.is_empty(),
+        "Footprint has no numbered pads to derive a symbol from"
+    );
+    pad_numbers.sort_by_key(|&number| pad_order(number));
+
+    let pins: Vec<BoxPin> = pad_numbers
+        .into_iter()
+        .map(|number| BoxPin::new(number, number, "passive"))
+        .collect();
+    let reference: String = component
+        .netlist
+        .refdes
+        .as_str()
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect();
+    let symbol = SymbolDefinition::box_symbol(
+        component_name,
+        if reference.is_empty() {
+            "U"
+        } else {
+            &reference
+        },
+        &pins,
+    );
+    debug!(
+        "Synthesized box symbol for {} with {} pins",
+        component.netlist.refdes.as_str(),
+        pins.len()
+    );
+    Ok(symbol_library_text(&[symbol], "pcb import"))
+}
+
+/// Numeric pads in numeric order, then named pads (`EP`, `A1`, ...) by name.
+fn pad_order(number: &str) -> (bool, Option<u64>, &str) {
+    let numeric = number.parse::<u64>().ok();
+    (numeric.is_none(), numeric, number)
+}
+
 fn patch_symbol_footprint_property(library_text: &str, footprint_stem: &str) -> Result<String> {
     let mut parsed = pcb_sexpr::parse(library_text).map_err(|e| anyhow::anyhow!(e))?;
     let root = kicad_symbol_lib_items_mut(&mut parsed).context("Not a KiCad symbol library")?;
@@ -2003,7 +2059,7 @@ fn patch_symbol_footprint_property(library_text: &str, footprint_stem: &str) ->
     let mut props = symbol_properties(symbol_items);
     props.insert("Footprint".to_string(), footprint_stem.to_string());
     rewrite_symbol_properties(symbol_items, &props);
-    Ok(format_tree(&parsed, FormatMode::Normal))
+    Ok(format_kicad(&parsed, KicadFileKind::Symbol))
 }
 
 #[derive(Debug, Clone)]
@@ -2292,6 +2348,13 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn box_symbol_pads_sort_numeric_first() {
+        let mut pads = vec!["EP", "10", "A1", "2", "1"];
+        pads.sort_by_key(|&number| pad_order(number));
+        assert_eq!(pads, vec!["1", "2", "10", "A1", "EP"]);
+    }
+
     fn make_anchor(symbol_uuid: &str) -> KiCadUuidPathKey {
         KiCadUuidPathKey {
             sheetpath_tstamps: "/".to_string(),
@@ -1,13 +1,20 @@
+use crate::build::{BuildEvalState, create_diagnostics_passes};
+use crate::package_graph::{GraphFormat, GraphKind, PackageGraph, PackageKind};
 use anyhow::{Context, Result};
 use clap::Args;
 use colored::Colorize as ColoredExt;
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::{Cell, Color, Table};
+use pcb_docgen::{ModuleDoc, ParamDoc};
 use pcb_eda::kicad::symbol_library::KicadSymbolLibrary;
+use pcb_layout::layout_stats::{LayoutStats, read_layout_stats};
 use pcb_ui::{Style, StyledText};
 use pcb_zen::workspace::{SymbolFileInfo, WorkspaceInfo, WorkspacePackage};
 use pcb_zen_core::config::PcbToml;
+use pcb_zen_core::diagnostics::diagnostic_kind;
 use pcb_zen_core::resolution::ResolutionResult;
 use serde::Serialize;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::env;
 use std::path::{Path, PathBuf};
 
@@ -20,6 +27,33 @@ pub struct InfoArgs {
 
     /// Optional path to start discovery from (defaults to current directory)
     pub path: Option<String>,
+
+    /// Show the io()/config() signature of a module instead of workspace information
+    #[arg(long, value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    pub module: Option<PathBuf>,
+
+    /// With --module, also draw the module's footprint in the terminal
+    /// (requires a terminal with the kitty graphics protocol)
+    #[arg(long, requires = "module")]
+    pub footprint: bool,
+
+    /// Render a dependency graph instead of workspace information
+    #[arg(long, value_enum, value_name = "KIND", conflicts_with = "module")]
+    pub graph: Option<GraphKind>,
+
+    /// Graph output format
+    #[arg(long, value_enum, default_value = "mermaid", requires = "graph")]
+    pub graph_format: GraphFormat,
+
+    /// Also report statistics of each board's layout: size, copper layers,
+    /// track length per net class, vias, and components per side
+    #[arg(long, conflicts_with_all = ["module", "graph"])]
+    pub layout: bool,
+
+    /// Also report every use of a deprecated function, module, or parameter
+    /// in the workspace's boards
+    #[arg(long, conflicts_with_all = ["module", "graph"])]
+    pub deprecations: bool,
 }
 
 #[derive(Debug, Clone, Copy, clap::ValueEnum)]
@@ -40,6 +74,27 @@ struct InfoJson {
     external_dependencies: BTreeMap<String, PackageMetadata>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     errors: Vec<pcb_zen::workspace::DiscoveryError>,
+    /// Layout statistics by board name, with `--layout`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    layouts: BTreeMap<String, LayoutStats>,
+    /// Uses of deprecated APIs, with `--deprecations`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    deprecations: Vec<DeprecationUse>,
+}
+
+/// A use of a deprecated API, found while building the workspace's boards.
+#[derive(Debug, Serialize)]
+struct DeprecationUse {
+    /// Workspace-relative path of the file with the use
+    path: PathBuf,
+    /// 1-based line of the use
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    /// Diagnostic kind, e.g. `deprecated.function`
+    kind: String,
+    message: String,
+    /// Boards whose build reached the use
+    boards: BTreeSet<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -77,11 +132,63 @@ enum PackageSource {
     Other,
 }
 
+#[derive(Debug, Serialize)]
+struct ModuleInfoJson {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    ios: Vec<ModuleParamJson>,
+    configs: Vec<ModuleParamJson>,
+    example: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    footprint: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+struct ModuleParamJson {
+    name: String,
+    #[serde(rename = "type")]
+    type_repr: String,
+    required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    range: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    direction: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help: Option<String>,
+}
+
+impl From<&ParamDoc> for ModuleParamJson {
+    fn from(param: &ParamDoc) -> Self {
+        Self {
+            name: param.name.clone(),
+            type_repr: param.type_repr.clone(),
+            required: !param.has_default && !param.optional,
+            default: (param.has_default && !param.default_repr.is_empty())
+                .then(|| param.default_repr.clone()),
+            allowed: param.allowed_repr.clone(),
+            range: param.range_repr.clone(),
+            direction: param.direction.as_ref().map(ToString::to_string),
+            help: param.help.clone(),
+        }
+    }
+}
+
 fn is_default<T: Default + PartialEq>(value: &T) -> bool {
     *value == T::default()
 }
 
 pub fn execute(args: InfoArgs) -> Result<()> {
+    if let Some(module) = &args.module {
+        return execute_module(module, args.format, args.footprint);
+    }
+
     let start_path = match &args.path {
         Some(path) => Path::new(path).to_path_buf(),
         None => env::current_dir()?,
@@ -91,15 +198,86 @@ pub fn execute(args: InfoArgs) -> Result<()> {
     let mut workspace_info = resolution.workspace_info.clone();
     pcb_zen::workspace::enrich_git_metadata(&mut workspace_info);
 
+    if let Some(GraphKind::Packages) = args.graph {
+        let graph = package_graph(&workspace_info, &resolution);
+        print!("{}", graph.render(args.graph_format));
+        return Ok(());
+    }
+
+    let layouts = if args.layout {
+        board_layout_stats(&resolution)?
+    } else {
+        BTreeMap::new()
+    };
+
+    let deprecations = if args.deprecations {
+        board_deprecations(&resolution)
+    } else {
+        Vec::new()
+    };
+
     match args.format {
         OutputFormat::Human => {
             let external_dependencies = external_dependencies(&workspace_info, &resolution)?;
             print_human_readable(&workspace_info, &external_dependencies);
+            print_layout_stats(&layouts);
+            if args.deprecations {
+                print_deprecations(&deprecations);
+            }
         }
         OutputFormat::Json => {
             populate_package_file_discovery(&mut workspace_info)?;
-            print_json(&info_json(&workspace_info, &resolution)?)?;
+            let mut info = info_json(&workspace_info, &resolution)?;
+            info.layouts = layouts;
+            info.deprecations = deprecations;
+            print_json(&info)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn execute_module(path: &Path, format: OutputFormat, draw_footprint: bool) -> Result<()> {
+    crate::file_walker::require_zen_file(path)?;
+    let resolution = crate::resolve::resolve(Some(path), false)?;
+
+    let display_path = path.to_string_lossy().replace('\\', "/");
+    let module = pcb_docgen::document_module(path, &display_path, &resolution)?
+        .with_context(|| format!("{} is a library, not a module", path.display()))?;
+    let example = pcb_docgen::render_example(&module, &display_path);
+
+    let footprint_path = module
+        .footprint
+        .as_ref()
+        .and_then(|footprint| Some(path.parent()?.join(footprint)));
+
+    match format {
+        OutputFormat::Human => {
+            print_module_human_readable(&module, &example);
+            if draw_footprint {
+                match &footprint_path {
+                    Some(footprint_path) => draw_footprint_preview(footprint_path)?,
+                    None => eprintln!(
+                        "{} No footprint found next to {}",
+                        pcb_ui::icons::warning(),
+                        path.display()
+                    ),
+                }
+            }
         }
+        OutputFormat::Json => print_json(&ModuleInfoJson {
+            path: module.path.clone(),
+            summary: module.file_doc.as_ref().map(|doc| doc.summary.clone()),
+            description: module
+                .file_doc
+                .as_ref()
+                .map(|doc| doc.description.clone())
+                .filter(|description| !description.is_empty()),
+            ios: module.signature.ios.iter().map(Into::into).collect(),
+            configs: module.signature.configs.iter().map(Into::into).collect(),
+            example,
+            footprint: footprint_path,
+        })?,
     }
 
     Ok(())
@@ -118,9 +296,204 @@ fn info_json(ws: &WorkspaceInfo, resolution: &ResolutionResult) -> Result<InfoJs
         packages,
         external_dependencies: external_dependencies(ws, resolution)?,
         errors: ws.errors.clone(),
+        layouts: BTreeMap::new(),
+        deprecations: Vec::new(),
     })
 }
 
+/// Build every board and read the statistics of its `.kicad_pcb`. Boards
+/// without a layout are left out.
+fn board_layout_stats(resolution: &ResolutionResult) -> Result<BTreeMap<String, LayoutStats>> {
+    let workspace_root = resolution.workspace_info.root.clone();
+    let boards = resolution.workspace_info.boards();
+    let eval_state = BuildEvalState::new(resolution.clone());
+
+    let mut layouts = BTreeMap::new();
+    for (name, board) in boards {
+        let zen_path = board.absolute_zen_path(&workspace_root);
+        let result = eval_state.build(
+            &zen_path,
+            Default::default(),
+            create_diagnostics_passes(&[], &[]),
+            false,
+            &mut false,
+            &mut false,
+        );
+        let Some(schematic) = result.schematic else {
+            anyhow::bail!("Failed to build {}", zen_path.display());
+        };
+        let Some(layout_dir) = pcb_layout::utils::resolve_layout_dir(&schematic)? else {
+            continue;
+        };
+        let Some(kicad_files) = pcb_layout::utils::discover_kicad_files(&layout_dir)? else {
+            continue;
+        };
+        layouts.insert(name, read_layout_stats(&kicad_files.kicad_pcb())?);
+    }
+    Ok(layouts)
+}
+
+fn print_layout_stats(layouts: &BTreeMap<String, LayoutStats>) {
+    for (name, stats) in layouts {
+        println!();
+        println!(
+            "{}",
+            format!("Layout: {name}").with_style(Style::Blue).bold()
+        );
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL_CONDENSED);
+        table.set_header(["Statistic", "Value"].map(|title| {
+            Cell::new(title)
+                .fg(Color::Blue)
+                .add_attribute(comfy_table::Attribute::Bold)
+        }));
+        if let Some(outline) = &stats.outline {
+            table.add_row([
+                "Board size".to_string(),
+                format!("{:.2} × {:.2} mm", outline.width_mm, outline.height_mm),
+            ]);
+            table.add_row([
+                "Board area".to_string(),
+                format!("{:.2} mm²", outline.area_mm2),
+            ]);
+        }
+        table.add_row(["Copper layers".to_string(), stats.copper_layers.to_string()]);
+        for (class, length) in &stats.track_length_mm {
+            table.add_row([format!("Track length ({class})"), format!("{length:.2} mm")]);
+        }
+        let vias = &stats.vias;
+        table.add_row([
+            "Vias".to_string(),
+            format!(
+                "{} ({} through, {} blind/buried, {} micro)",
+                vias.total(),
+                vias.through,
+                vias.blind,
+                vias.micro
+            ),
+        ]);
+        table.add_row([
+            "Components".to_string(),
+            format!(
+                "{} front, {} back",
+                stats.components.front, stats.components.back
+            ),
+        ]);
+        println!("{table}");
+    }
+}
+
+/// Build every board and collect the uses of deprecated APIs it reports,
+/// merging uses reached from more than one board.
+fn board_deprecations(resolution: &ResolutionResult) -> Vec<DeprecationUse> {
+    let workspace_root = resolution.workspace_info.root.clone();
+    let boards = resolution.workspace_info.boards();
+    let eval_state = BuildEvalState::new(resolution.clone());
+
+    let mut uses: BTreeMap<(PathBuf, Option<usize>, String, String), BTreeSet<String>> =
+        BTreeMap::new();
+    for (name, board) in boards {
+        let zen_path = board.absolute_zen_path(&workspace_root);
+        let result = eval_state.build(
+            &zen_path,
+            Default::default(),
+            Vec::new(),
+            false,
+            &mut false,
+            &mut false,
+        );
+        if result.eval_output.is_none() {
+            eprintln!(
+                "{} {name}: failed to evaluate; its deprecations may be incomplete",
+                pcb_ui::icons::warning()
+            );
+        }
+        for diagnostic in result.diagnostics.iter() {
+            let Some(kind) =
+                diagnostic_kind(diagnostic).filter(|kind| kind.starts_with("deprecated."))
+            else {
+                continue;
+            };
+            // Wrapped warnings point at the board; the innermost one is the use.
+            let site = diagnostic.innermost();
+            let path = Path::new(&site.path);
+            let path = path.strip_prefix(&workspace_root).unwrap_or(path);
+            let line = site.span.map(|span| span.begin.line + 1);
+            uses.entry((path.to_path_buf(), line, kind, site.body.clone()))
+                .or_default()
+                .insert(name.clone());
+        }
+    }
+
+    uses.into_iter()
+        .map(|((path, line, kind, message), boards)| DeprecationUse {
+            path,
+            line,
+            kind,
+            message,
+            boards,
+        })
+        .collect()
+}
+
+fn print_deprecations(deprecations: &[DeprecationUse]) {
+    println!();
+    println!("{}", "Deprecations".with_style(Style::Blue).bold());
+    if deprecations.is_empty() {
+        println!("No deprecated functions, modules, or parameters in use");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED);
+    table.set_header(["Location", "Boards", "Message"].map(|title| {
+        Cell::new(title)
+            .fg(Color::Blue)
+            .add_attribute(comfy_table::Attribute::Bold)
+    }));
+    for deprecation in deprecations {
+        let location = match deprecation.line {
+            Some(line) => format!("{}:{line}", deprecation.path.display()),
+            None => deprecation.path.display().to_string(),
+        };
+        let boards: Vec<&str> = deprecation.boards.iter().map(String::as_str).collect();
+        table.add_row([location, boards.join(", "), deprecation.message.clone()]);
+    }
+    println!("{table}");
+}
+
+fn package_graph(ws: &WorkspaceInfo, resolution: &ResolutionResult) -> PackageGraph {
+    let mut graph = PackageGraph::default();
+    for (module_path, pkg) in &ws.packages {
+        graph.add_package(module_path, PackageKind::Workspace, pkg.version.as_deref());
+    }
+    for (module_path, versions) in resolution.remote_package_versions() {
+        for version in &versions {
+            graph.add_package(&module_path, PackageKind::External, Some(version));
+        }
+    }
+
+    for (module_path, pkg) in &ws.packages {
+        for (dep, spec) in &pkg.config.dependencies.direct {
+            graph.add_dependency(module_path, dep, spec);
+        }
+    }
+    for (coord, root) in resolution.package_roots() {
+        let Some((module_path, _)) = external_package_coord(ws, &coord, &root) else {
+            continue;
+        };
+        let Ok(config) = PcbToml::from_path(&root.join("pcb.toml")) else {
+            continue;
+        };
+        for (dep, spec) in &config.dependencies.direct {
+            graph.add_dependency(module_path, dep, spec);
+        }
+    }
+
+    graph
+}
+
 fn metadata_for_workspace_package(pkg: &WorkspacePackage) -> PackageMetadata {
     PackageMetadata {
         version: pkg.version.clone(),
@@ -401,6 +774,92 @@ fn print_human_readable(
     }
 }
 
+fn print_module_human_readable(module: &ModuleDoc, example: &str) {
+    println!("{}", module.path.as_str().with_style(Style::Blue).bold());
+    if let Some(doc) = &module.file_doc {
+        println!("{}", doc.summary);
+        if !doc.description.is_empty() {
+            println!();
+            println!("{}", doc.description);
+        }
+    }
+
+    if !module.signature.ios.is_empty() {
+        println!();
+        println!("{}", "IO".with_style(Style::Blue).bold());
+        for io in &module.signature.ios {
+            let direction = io
+                .direction
+                .as_ref()
+                .map(|d| format!(" ({d})"))
+                .unwrap_or_default();
+            println!(
+                "  {} {}{}",
+                io.name.bold(),
+                io.type_repr,
+                direction.dimmed()
+            );
+            print_param_help(io);
+        }
+    }
+
+    if !module.signature.configs.is_empty() {
+        println!();
+        println!("{}", "Config".with_style(Style::Blue).bold());
+        for param in &module.signature.configs {
+            let default = if param.has_default && !param.default_repr.is_empty() {
+                format!("= {}", param.default_repr)
+            } else if param.has_default || param.optional {
+                "optional".to_string()
+            } else {
+                "required".to_string()
+            };
+            println!(
+                "  {} {} {}",
+                param.name.bold(),
+                param.type_repr,
+                default.dimmed()
+            );
+            if let Some(allowed) = &param.allowed_repr {
+                println!("    allowed: {allowed}");
+            }
+            if let Some(range) = &param.range_repr {
+                println!("    range: {range}");
+            }
+            print_param_help(param);
+        }
+    }
+
+    if let Some(footprint) = &module.footprint {
+        println!();
+        println!("{} {footprint}", "Footprint".with_style(Style::Blue).bold());
+    }
+
+    println!();
+    println!("{}", "Example".with_style(Style::Blue).bold());
+    for line in example.lines() {
+        println!("  {line}");
+    }
+}
+
+/// Draw a footprint's pads, silkscreen, and courtyard below the module info.
+fn draw_footprint_preview(path: &Path) -> Result<()> {
+    let footprint = pcb_eda::KicadFootprint::from_file(path)
+        .with_context(|| format!("Failed to read footprint {}", path.display()))?;
+    let mask = pcb_ir::dialects::artwork::compose_to_mask(
+        &pcb_eda::kicad::footprint_preview::footprint_artwork(&footprint),
+    );
+    println!();
+    pcb_ir::render::to_terminal(&mask, &pcb_ir::render::RenderOptions::default())
+        .map_err(anyhow::Error::msg)
+}
+
+fn print_param_help(param: &ParamDoc) {
+    if let Some(help) = param.help.as_deref().filter(|help| !help.is_empty()) {
+        println!("    {}", help.dimmed());
+    }
+}
+
 fn print_package_line(pkg: &WorkspacePackage) {
     let is_root = pkg.rel_path.as_os_str().is_empty();
 
@@ -0,0 +1,30 @@
+//! Process-wide Ctrl-C handling.
+//!
+//! `ctrlc` allows a single handler per process, so every command that wants to
+//! react to Ctrl-C shares the token returned by [`ctrl_c_token`]. The first
+//! Ctrl-C cancels the token so the command can clean up; a second one exits
+//! immediately with status 130.
+
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use pcb_zen_core::cancel::CancellationToken;
+
+static CTRL_C: OnceLock<CancellationToken> = OnceLock::new();
+
+/// Token cancelled when the user presses Ctrl-C. Installs the handler on first use.
+pub(crate) fn ctrl_c_token() -> Result<CancellationToken> {
+    if let Some(token) = CTRL_C.get() {
+        return Ok(token.clone());
+    }
+    let token = CancellationToken::new();
+    let handler_token = token.clone();
+    ctrlc::set_handler(move || {
+        if handler_token.is_cancelled() {
+            std::process::exit(130);
+        }
+        handler_token.cancel();
+    })
+    .context("Failed to set Ctrl-C handler")?;
+    Ok(CTRL_C.get_or_init(|| token).clone())
+}
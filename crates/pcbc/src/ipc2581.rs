@@ -49,6 +49,30 @@ enum Commands {
         #[arg(long)]
         exclude_dnp: bool,
     },
+    /// List the pads and components connected to a net
+    Net {
+        /// IPC-2581 XML file to inspect
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        file: PathBuf,
+        /// Net name, for example GND
+        net: String,
+        #[arg(short, long, default_value = "text")]
+        format: OutputFormat,
+        #[arg(short, long, default_value = "mm")]
+        units: UnitFormat,
+    },
+    /// Show a component's package, placement, and pins
+    Component {
+        /// IPC-2581 XML file to inspect
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        file: PathBuf,
+        /// Reference designator, for example U3
+        refdes: String,
+        #[arg(short, long, default_value = "text")]
+        format: OutputFormat,
+        #[arg(short, long, default_value = "mm")]
+        units: UnitFormat,
+    },
     /// Edit IPC-2581 data
     Edit {
         #[command(subcommand)]
@@ -257,6 +281,18 @@ pub fn execute(args: Ipc2581Args) -> anyhow::Result<()> {
                 exclude_dnp,
             },
         ),
+        Commands::Net {
+            file,
+            net,
+            format,
+            units,
+        } => commands::net::execute(&file, &net, format, units),
+        Commands::Component {
+            file,
+            refdes,
+            format,
+            units,
+        } => commands::component::execute(&file, &refdes, format, units),
         Commands::Edit { command } => match command {
             EditCommands::Bom {
                 file,
@@ -1,9 +1,17 @@
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 use clap::Args;
-use pcb_layout::{process_layout, utils as layout_utils};
+use pcb_kicad::project::KicadProject;
+use pcb_layout::footprint_drift::{FootprintDrift, detect_footprint_drift, mark_for_reimport};
+use pcb_layout::return_path::{
+    DEFAULT_STITCH_DISTANCE_MM, ReturnPathIssue, ReturnPathOptions, ReturnPathViolation,
+    check_return_paths,
+};
+use pcb_layout::routing_status::{NetClassRouting, NetRouting, routing_status};
+use pcb_layout::{process_layout_with_cancellation, utils as layout_utils};
 use pcb_sch::Schematic;
 use pcb_ui::prelude::*;
 use serde::Serialize;
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
 use crate::build::{BuildEvalState, create_diagnostics_passes};
@@ -46,9 +54,62 @@ pub struct LayoutArgs {
     #[arg(long = "no-sync", conflicts_with_all = ["temp", "check"])]
     pub no_sync: bool,
 
+    /// Report routing completion of the existing layout per net class and list
+    /// unrouted nets, without updating it
+    #[arg(long = "status", conflicts_with_all = ["temp", "check", "no_sync"])]
+    pub status: bool,
+
+    /// List footprints in the existing layout that differ from their library
+    /// footprint, without updating it
+    #[arg(long = "footprint-drift", conflicts_with_all = ["temp", "check", "no_sync", "status"])]
+    pub footprint_drift: bool,
+
+    /// Check tracks of the given net classes for nearby ground stitching vias
+    /// and an unbroken ground plane on an adjacent layer, without updating
+    /// the layout (`--return-paths=USB,HDMI`)
+    #[arg(
+        long = "return-paths",
+        value_name = "NETCLASS",
+        require_equals = true,
+        value_delimiter = ',',
+        conflicts_with_all = ["temp", "check", "no_sync", "status", "footprint_drift"]
+    )]
+    pub return_paths: Option<Vec<String>>,
+
+    /// Largest distance from a --return-paths track to a ground via, in mm
+    #[arg(
+        long = "stitch-distance",
+        value_name = "MM",
+        requires = "return_paths",
+        default_value_t = DEFAULT_STITCH_DISTANCE_MM
+    )]
+    pub stitch_distance: f64,
+
+    /// Re-import footprints that differ from their library footprint, keeping
+    /// their position, rotation, and side. Takes reference designators or
+    /// component paths (`--update-footprints=R1,U3`); re-imports every
+    /// differing footprint when empty
+    #[arg(
+        long = "update-footprints",
+        value_name = "REF",
+        num_args = 0..=1,
+        require_equals = true,
+        value_delimiter = ',',
+        conflicts_with_all = ["temp", "no_sync", "check", "status", "footprint_drift", "return_paths"]
+    )]
+    pub update_footprints: Option<Vec<String>>,
+
     /// Output format
     #[arg(short = 'f', long, value_enum, default_value_t = LayoutOutputFormat::Human)]
     pub format: LayoutOutputFormat,
+
+    /// Write the workspace's git describe and source hash into the
+    /// PCB_GIT_DESCRIBE and PCB_SOURCE_HASH text variables
+    #[arg(
+        long = "stamp",
+        conflicts_with_all = ["no_sync", "status", "footprint_drift", "return_paths"]
+    )]
+    pub stamp: bool,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
@@ -85,21 +146,65 @@ pub fn execute(mut args: LayoutArgs) -> Result<()> {
 
     // Resolve dependencies before building
     let resolution_result = crate::resolve::resolve(Some(&args.file), args.offline)?;
+    let stamp = args
+        .stamp
+        .then(|| crate::stamp::workspace_stamp(&resolution_result.workspace_info))
+        .transpose()?;
 
     let zen_path = &args.file;
     let file_name = zen_path.file_name().unwrap().to_string_lossy().to_string();
 
-    let build_result = BuildEvalState::new(resolution_result).build(
-        zen_path,
-        config_inputs,
-        create_diagnostics_passes(&args.suppress, &[]),
-        false,
-        &mut false.clone(),
-        &mut false.clone(),
-    );
-    let Some(schematic) = build_result.schematic else {
+    let cancel = crate::interrupt::ctrl_c_token()?;
+    let build_result = BuildEvalState::new(resolution_result)
+        .with_cancellation(cancel.clone())
+        .build(
+            zen_path,
+            config_inputs,
+            create_diagnostics_passes(&args.suppress, &[]),
+            false,
+            &mut false.clone(),
+            &mut false.clone(),
+        );
+    cancel.check(format!("evaluation of {file_name}"))?;
+    let Some(mut schematic) = build_result.schematic else {
         anyhow::bail!("Build failed");
     };
+    schematic.stamp = stamp;
+
+    if args.status {
+        let result = resolve_existing_layout(zen_path, &schematic)?;
+        let Some(pcb_file) = &result.pcb_file else {
+            bail!("{} has no layout_path", zen_path.display());
+        };
+        return print_routing_status(pcb_file, args.format, zen_path, &file_name);
+    }
+
+    if args.footprint_drift {
+        let result = resolve_existing_layout(zen_path, &schematic)?;
+        let Some(pcb_file) = &result.pcb_file else {
+            bail!("{} has no layout_path", zen_path.display());
+        };
+        return print_footprint_drift(pcb_file, &schematic, args.format, zen_path, &file_name);
+    }
+
+    if let Some(net_classes) = &args.return_paths {
+        let result = resolve_existing_layout(zen_path, &schematic)?;
+        let Some(pcb_file) = &result.pcb_file else {
+            bail!("{} has no layout_path", zen_path.display());
+        };
+        let ground_nets: BTreeSet<String> = schematic
+            .nets
+            .iter()
+            .filter(|(_, net)| net.kind == "Ground")
+            .map(|(name, _)| name.clone())
+            .collect();
+        let options = ReturnPathOptions {
+            net_classes,
+            ground_nets: &ground_nets,
+            stitch_distance_mm: args.stitch_distance,
+        };
+        return print_return_paths(pcb_file, &options, args.format, zen_path, &file_name);
+    }
 
     if args.no_sync {
         let result = resolve_existing_layout(zen_path, &schematic)?;
@@ -136,8 +241,26 @@ pub fn execute(mut args: LayoutArgs) -> Result<()> {
         format!("{file_name}: Generating layout")
     };
     let spinner = Spinner::builder(spinner_msg).hidden(hide_progress).start();
+    let reimport = match &args.update_footprints {
+        Some(selection) => mark_footprints_for_reimport(zen_path, &schematic, selection)?,
+        None => None,
+    };
     let mut diagnostics = pcb_zen_core::Diagnostics::default();
-    let result = process_layout(&schematic, args.temp, args.check, &mut diagnostics)?;
+    let result = process_layout_with_cancellation(
+        &schematic,
+        args.temp,
+        args.check,
+        &cancel,
+        &mut diagnostics,
+    );
+    // A failed sync leaves the marked FPIDs behind; put the original back.
+    if result.is_err()
+        && let Some((pcb_file, original)) = &reimport
+    {
+        std::fs::write(pcb_file, original)
+            .with_context(|| format!("Failed to restore {}", pcb_file.display()))?;
+    }
+    let result = result?;
     spinner.finish();
 
     let Some(layout_result) = result else {
@@ -160,28 +283,45 @@ pub fn execute(mut args: LayoutArgs) -> Result<()> {
         return Ok(());
     };
     let pcb_file = layout_result.pcb_file.clone();
-    let display_pcb_file = layout_result.display_pcb_file().to_path_buf();
 
     print_layout_result(
         &LayoutCommandResult {
             source_file: zen_path.to_path_buf(),
             layout_dir: Some(layout_result.layout_dir.clone()),
-            pcb_file: Some(display_pcb_file.clone()),
+            pcb_file: Some(layout_result.display_pcb_file().to_path_buf()),
         },
         args.format,
         zen_path,
         &file_name,
     )?;
+    for board in &layout_result.boards {
+        print_layout_result(
+            &LayoutCommandResult {
+                source_file: zen_path.to_path_buf(),
+                layout_dir: Some(board.layout_dir.clone()),
+                pcb_file: Some(board.display_pcb_file().to_path_buf()),
+            },
+            args.format,
+            zen_path,
+            &file_name,
+        )?;
+    }
 
     // Run DRC in check mode.
     if args.check {
         let spinner = Spinner::builder(format!("{file_name}: Running DRC checks"))
             .hidden(hide_progress)
             .start();
-        let drc_output = tempfile::NamedTempFile::new()?;
-        let working_dir = pcb_file.parent();
-        let report = pcb_kicad::run_drc(&pcb_file, false, working_dir, drc_output.path())?;
-        report.add_to_diagnostics(&mut diagnostics, &display_pcb_file.to_string_lossy());
+        for board in std::iter::once(&layout_result).chain(&layout_result.boards) {
+            let drc_output = tempfile::NamedTempFile::new()?;
+            let working_dir = board.pcb_file.parent();
+            let report =
+                pcb_kicad::run_drc(&board.pcb_file, false, working_dir, drc_output.path())?;
+            report.add_to_diagnostics(
+                &mut diagnostics,
+                &board.display_pcb_file().to_string_lossy(),
+            );
+        }
         spinner.finish();
     }
 
@@ -225,6 +365,284 @@ fn resolve_existing_layout(zen_path: &Path, schematic: &Schematic) -> Result<Lay
     })
 }
 
+/// Mark the drifted footprints named by `selection` (all of them when empty)
+/// for re-import by the next sync. Returns the layout file and its contents
+/// before marking, or `None` when nothing was marked.
+fn mark_footprints_for_reimport(
+    zen_path: &Path,
+    schematic: &Schematic,
+    selection: &[String],
+) -> Result<Option<(PathBuf, String)>> {
+    let result = resolve_existing_layout(zen_path, schematic)?;
+    let Some(pcb_file) = result.pcb_file else {
+        bail!("{} has no layout_path", zen_path.display());
+    };
+    let text = std::fs::read_to_string(&pcb_file)
+        .with_context(|| format!("Failed to read {}", pcb_file.display()))?;
+    let drift = detect_footprint_drift(schematic, &text)?;
+
+    let mut paths: BTreeSet<String> = BTreeSet::new();
+    if selection.is_empty() {
+        paths.extend(drift.iter().map(|fp| fp.path.clone()));
+    }
+    for selector in selection {
+        let Some(fp) = drift
+            .iter()
+            .find(|fp| fp.reference.as_deref() == Some(selector.as_str()) || &fp.path == selector)
+        else {
+            bail!("{selector} does not differ from its library footprint");
+        };
+        paths.insert(fp.path.clone());
+    }
+    if paths.is_empty() {
+        return Ok(None);
+    }
+
+    let (patched, _) = mark_for_reimport(&text, &paths)?;
+    std::fs::write(&pcb_file, patched)
+        .with_context(|| format!("Failed to write {}", pcb_file.display()))?;
+    Ok(Some((pcb_file, text)))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FootprintDriftResult<'a> {
+    pcb_file: &'a Path,
+    footprints: Vec<FootprintDrift>,
+}
+
+fn print_footprint_drift(
+    pcb_file: &Path,
+    schematic: &Schematic,
+    format: LayoutOutputFormat,
+    zen_path: &Path,
+    file_name: &str,
+) -> Result<()> {
+    let text = std::fs::read_to_string(pcb_file)
+        .with_context(|| format!("Failed to read {}", pcb_file.display()))?;
+    let result = FootprintDriftResult {
+        pcb_file,
+        footprints: detect_footprint_drift(schematic, &text)?,
+    };
+    match format {
+        LayoutOutputFormat::Json => println!("{}", serde_json::to_string_pretty(&result)?),
+        LayoutOutputFormat::Human => {
+            let relative_path = zen_path
+                .parent()
+                .and_then(|parent| pcb_file.strip_prefix(parent).ok())
+                .unwrap_or(pcb_file);
+            if result.footprints.is_empty() {
+                println!(
+                    "{} {} ({}): all footprints match their library",
+                    pcb_ui::icons::success(),
+                    file_name.with_style(Style::Green).bold(),
+                    relative_path.display()
+                );
+                return Ok(());
+            }
+            println!(
+                "{} ({}): {} footprint(s) differ from their library",
+                file_name.with_style(Style::Yellow).bold(),
+                relative_path.display(),
+                result.footprints.len()
+            );
+            for fp in &result.footprints {
+                println!(
+                    "  {:<8} {} ({})",
+                    fp.reference.as_deref().unwrap_or("?"),
+                    fp.path,
+                    fp.fpid.dimmed()
+                );
+            }
+            println!(
+                "Run 'pcb layout {} --update-footprints' to re-import them.",
+                zen_path.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NetClassStatus {
+    #[serde(flatten)]
+    routing: NetClassRouting,
+    completion_percent: f64,
+}
+
+impl From<NetClassRouting> for NetClassStatus {
+    fn from(routing: NetClassRouting) -> Self {
+        Self {
+            completion_percent: routing.completion_percent(),
+            routing,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LayoutStatusResult<'a> {
+    pcb_file: &'a Path,
+    total: NetClassStatus,
+    net_classes: Vec<NetClassStatus>,
+    unrouted_nets: Vec<&'a NetRouting>,
+}
+
+fn print_routing_status(
+    pcb_file: &Path,
+    format: LayoutOutputFormat,
+    zen_path: &Path,
+    file_name: &str,
+) -> Result<()> {
+    let text = std::fs::read_to_string(pcb_file)
+        .with_context(|| format!("Failed to read {}", pcb_file.display()))?;
+    let board = pcb_sexpr::parse(&text)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {e}", pcb_file.display()))?;
+    let netclass_patterns = KicadProject::load_if_exists(&pcb_file.with_extension("kicad_pro"))?
+        .map(|project| project.netclass_patterns())
+        .unwrap_or_default();
+    let status = routing_status(&board, &netclass_patterns);
+
+    let result = LayoutStatusResult {
+        pcb_file,
+        total: status.total().into(),
+        net_classes: status.net_classes().into_iter().map(Into::into).collect(),
+        unrouted_nets: status.unrouted_nets().collect(),
+    };
+    match format {
+        LayoutOutputFormat::Json => println!("{}", serde_json::to_string_pretty(&result)?),
+        LayoutOutputFormat::Human => {
+            let relative_path = zen_path
+                .parent()
+                .and_then(|parent| pcb_file.strip_prefix(parent).ok())
+                .unwrap_or(pcb_file);
+            let total = &result.total;
+            println!(
+                "{} ({}): {:.1}% routed, {}/{} connections, {}/{} nets",
+                file_name.with_style(Style::Green).bold(),
+                relative_path.display(),
+                total.completion_percent,
+                total.routing.connections - total.routing.unrouted_connections,
+                total.routing.connections,
+                total.routing.routed_nets,
+                total.routing.nets,
+            );
+            for class in &result.net_classes {
+                println!(
+                    "  {:<16} {:>5.1}%  {}/{} connections  {}/{} nets",
+                    class.routing.name,
+                    class.completion_percent,
+                    class.routing.connections - class.routing.unrouted_connections,
+                    class.routing.connections,
+                    class.routing.routed_nets,
+                    class.routing.nets,
+                );
+            }
+            if !result.unrouted_nets.is_empty() {
+                println!("{}", "Unrouted nets:".bold());
+                for net in &result.unrouted_nets {
+                    println!(
+                        "  {} ({}): {} of {} connections unrouted",
+                        net.name,
+                        net.net_class.dimmed(),
+                        net.unrouted_connections,
+                        net.connections()
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReturnPathResult<'a> {
+    pcb_file: &'a Path,
+    net_classes: &'a [String],
+    stitch_distance_mm: f64,
+    nets_checked: usize,
+    violations: Vec<ReturnPathViolation>,
+}
+
+fn print_return_paths(
+    pcb_file: &Path,
+    options: &ReturnPathOptions,
+    format: LayoutOutputFormat,
+    zen_path: &Path,
+    file_name: &str,
+) -> Result<()> {
+    let text = std::fs::read_to_string(pcb_file)
+        .with_context(|| format!("Failed to read {}", pcb_file.display()))?;
+    let board = pcb_sexpr::parse(&text)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {e}", pcb_file.display()))?;
+    let netclass_patterns = KicadProject::load_if_exists(&pcb_file.with_extension("kicad_pro"))?
+        .map(|project| project.netclass_patterns())
+        .unwrap_or_default();
+    let report = check_return_paths(&board, &netclass_patterns, options);
+
+    let result = ReturnPathResult {
+        pcb_file,
+        net_classes: options.net_classes,
+        stitch_distance_mm: options.stitch_distance_mm,
+        nets_checked: report.nets_checked,
+        violations: report.violations,
+    };
+    match format {
+        LayoutOutputFormat::Json => println!("{}", serde_json::to_string_pretty(&result)?),
+        LayoutOutputFormat::Human => {
+            let relative_path = zen_path
+                .parent()
+                .and_then(|parent| pcb_file.strip_prefix(parent).ok())
+                .unwrap_or(pcb_file);
+            if result.violations.is_empty() {
+                println!(
+                    "{} {} ({}): {} net(s) in {} have stitching vias and a continuous reference plane",
+                    pcb_ui::icons::success(),
+                    file_name.with_style(Style::Green).bold(),
+                    relative_path.display(),
+                    result.nets_checked,
+                    result.net_classes.join(", ")
+                );
+                return Ok(());
+            }
+            println!(
+                "{} ({}): {} return path issue(s) on {} checked net(s)",
+                file_name.with_style(Style::Yellow).bold(),
+                relative_path.display(),
+                result.violations.len(),
+                result.nets_checked
+            );
+            for violation in &result.violations {
+                let detail = match violation.issue {
+                    ReturnPathIssue::Stitching => match violation.via_distance_mm {
+                        Some(via) => format!(
+                            "{:.1} mm beyond {:.1} mm of a ground via (up to {via:.1} mm)",
+                            violation.length_mm, result.stitch_distance_mm
+                        ),
+                        None => "no ground vias on the board".to_string(),
+                    },
+                    ReturnPathIssue::ReferencePlane => format!(
+                        "{:.1} mm without ground on {}",
+                        violation.length_mm,
+                        violation.reference_layers.join(" or ")
+                    ),
+                };
+                println!(
+                    "  {} ({}) {} at ({:.2}, {:.2}): {detail}",
+                    violation.net,
+                    violation.net_class.dimmed(),
+                    violation.layer,
+                    violation.x,
+                    violation.y
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
 fn print_layout_result(
     result: &LayoutCommandResult,
     format: LayoutOutputFormat,
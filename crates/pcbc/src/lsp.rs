@@ -6,7 +6,11 @@ pub struct LspArgs {}
 const RESOLVE_DATASHEET_METHOD: &str = "pcb/resolveDatasheet";
 
 pub fn execute(_args: LspArgs) -> anyhow::Result<()> {
-    pcb_zen::lsp_with_custom_request_handler(false, handle_custom_request)
+    let ctx = pcb_zen::lsp::LspEvalContext::default()
+        .set_eager(false)
+        .with_custom_request_handler(handle_custom_request)
+        .with_exported_symbols(pcb_docgen::index::indexed_symbols);
+    pcb_zen::lsp_with_context(ctx)
 }
 
 fn handle_custom_request(
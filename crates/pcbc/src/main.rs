@@ -2,7 +2,7 @@
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 use colored::Colorize;
 use env_logger::Env;
 use std::ffi::OsString;
@@ -11,44 +11,62 @@ use std::process::Command;
 
 const BUNDLED_EXTERNAL_COMMANDS: &[&str] = &["rectify"];
 
+mod analyze;
+mod artifact_server;
 mod bom;
 mod build;
 mod bundle;
 mod changelog;
 mod codegen;
+mod completions;
 mod config_input;
+mod crash;
+mod datasheet;
 mod doc;
 mod drc;
 mod embed_step;
 mod file_walker;
+mod fmea;
 mod fmt;
+mod forge;
 mod gerber;
+mod grep;
 mod import;
 mod info;
+mod interrupt;
 mod ipc2581;
 mod kq;
 mod layout;
 mod list;
 mod lsp;
+mod man;
 mod migrate;
 mod mod_cmd;
 mod new;
 mod open;
+mod package_graph;
 #[path = "mod/mod.rs"]
 mod pcb_mod;
+mod pinout;
 mod preview;
 mod publish;
+mod query;
 mod release;
 mod remote_sandbox;
 mod route;
 mod sandbox_uri;
+mod session;
+mod signing;
 mod sim;
+mod stamp;
 mod test;
 mod update;
 mod vendor;
+mod verify;
 
 mod profiling;
 mod resolve;
+mod resolve_cmd;
 mod tty;
 
 #[derive(Parser)]
@@ -64,6 +82,10 @@ struct Cli {
     #[arg(long = "profile", global = true, value_name = "PATH", hide = true)]
     profile: Option<std::path::PathBuf>,
 
+    /// Maximum number of packages to fetch in parallel
+    #[arg(short = 'j', long = "jobs", global = true, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    jobs: Option<u64>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -82,7 +104,7 @@ enum Commands {
     Test(test::TestArgs),
 
     /// Migrate PCB projects
-    #[command(alias = "m")]
+    #[command(alias = "m", alias = "upgrade")]
     Migrate(migrate::MigrateArgs),
 
     /// Manage package dependency manifests
@@ -106,9 +128,27 @@ enum Commands {
     /// Generate Bill of Materials (BOM)
     Bom(bom::BomArgs),
 
+    /// Query components, modules, and nets of a design
+    Query(query::QueryArgs),
+
+    /// Find boards that use a component or module
+    Grep(grep::GrepArgs),
+
+    /// Export a component's pin-to-net mapping (CSV, XDC, QSF)
+    Pinout(pinout::PinoutArgs),
+
+    /// Export an FMEA worksheet skeleton (CSV)
+    Fmea(fmea::FmeaArgs),
+
     /// Display workspace and board information
     Info(info::InfoArgs),
 
+    /// Analyze packages, e.g. component library health
+    Analyze(analyze::AnalyzeArgs),
+
+    /// Show how a load() path resolves from a given file
+    Resolve(resolve_cmd::ResolveArgs),
+
     /// Import KiCad projects into a Zener board repository
     Import(import::ImportArgs),
 
@@ -130,6 +170,12 @@ enum Commands {
     #[command(hide = true)]
     Lsp(lsp::LspArgs),
 
+    /// Print a shell completion script (bash, zsh, fish, powershell, elvish)
+    Completions(completions::CompletionsArgs),
+
+    /// Generate man pages
+    Man(man::ManArgs),
+
     /// Open PCB layout files
     #[command(alias = "o")]
     Open(open::OpenArgs),
@@ -141,9 +187,15 @@ enum Commands {
     /// Build and upload a preview release for a board
     Preview(preview::PreviewArgs),
 
+    /// Inspect release archives
+    Release(release::ReleaseArgs),
+
     /// Vendor external dependencies
     Vendor(vendor::VendorArgs),
 
+    /// Verify vendored assets against recorded provenance
+    Verify(verify::VerifyArgs),
+
     /// Reserved subcommand for future use
     Fork,
 
@@ -181,7 +233,12 @@ enum Commands {
 }
 
 fn main() {
+    crash::install_panic_hook();
     if let Err(e) = run() {
+        if let Some(cancelled) = pcb_zen_core::cancel::Cancelled::find(&e) {
+            eprintln!("{} during {}", "Cancelled".yellow(), cancelled.stage);
+            std::process::exit(130);
+        }
         eprintln!("{} {e}", "Error:".red());
         for cause in e.chain().skip(1) {
             eprintln!("  {cause}");
@@ -209,6 +266,10 @@ fn run() -> anyhow::Result<()> {
     // Initialize profiling if --profile is passed (guard must be held until end of run)
     let _profile_guard = profiling::init(cli.profile);
 
+    if let Some(jobs) = cli.jobs {
+        pcb_zen::package_resolver::set_fetch_jobs(jobs as usize);
+    }
+
     match cli.command {
         Commands::Auth(args) => {
             let ctx = pcb_diode_api::WorkspaceContext::from_cwd()?;
@@ -224,17 +285,27 @@ fn run() -> anyhow::Result<()> {
         Commands::New(args) => new::execute(args),
         Commands::Update(args) => update::execute(args),
         Commands::Bom(args) => bom::execute(args),
+        Commands::Query(args) => query::execute(args),
+        Commands::Grep(args) => grep::execute(args),
+        Commands::Pinout(args) => pinout::execute(args),
+        Commands::Fmea(args) => fmea::execute(args),
         Commands::Info(args) => info::execute(args),
+        Commands::Analyze(args) => analyze::execute(args),
+        Commands::Resolve(args) => resolve_cmd::execute(args),
         Commands::Import(args) => import::execute(args),
         Commands::Doc(args) => doc::execute(args),
         Commands::Changelog(args) => changelog::execute(args),
         Commands::Layout(args) => layout::execute(args),
         Commands::Fmt(args) => fmt::execute(args),
         Commands::Lsp(args) => lsp::execute(args),
+        Commands::Completions(args) => completions::execute(args, Cli::command()),
+        Commands::Man(args) => man::execute(args, Cli::command()),
         Commands::Open(args) => open::execute(args),
         Commands::Publish(args) => publish::execute(args),
         Commands::Preview(args) => preview::execute(args),
+        Commands::Release(args) => release::execute(args),
         Commands::Vendor(args) => vendor::execute(args),
+        Commands::Verify(args) => verify::execute(args),
         Commands::Fork => {
             println!("`pcb fork` is a reserved subcommand for future use.");
             Ok(())
@@ -0,0 +1,56 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Args;
+
+use crate::completions::BIN_NAME;
+
+#[derive(Args, Debug)]
+#[command(about = "Generate man pages")]
+pub struct ManArgs {
+    /// Write `pcb.1` and one page per subcommand (`pcb-build.1`, ...) into
+    /// this directory instead of printing `pcb.1` to stdout
+    #[arg(long, value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
+    pub out_dir: Option<PathBuf>,
+}
+
+pub fn execute(args: ManArgs, cmd: clap::Command) -> anyhow::Result<()> {
+    let cmd = cmd.name(BIN_NAME).bin_name(BIN_NAME);
+    match args.out_dir {
+        Some(dir) => {
+            std::fs::create_dir_all(&dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+            clap_mangen::generate_to(cmd, &dir)
+                .with_context(|| format!("Failed to write man pages to {}", dir.display()))?;
+            eprintln!("Wrote man pages to {}", dir.display());
+        }
+        None => {
+            let mut out = Vec::new();
+            clap_mangen::Man::new(cmd).render(&mut out)?;
+            io::stdout().write_all(&out)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_page_per_subcommand() {
+        let cmd = clap::Command::new("pcbc")
+            .subcommand(clap::Command::new("build").about("Build PCB projects"));
+        let dir = tempfile::tempdir().unwrap();
+        execute(
+            ManArgs {
+                out_dir: Some(dir.path().to_path_buf()),
+            },
+            cmd,
+        )
+        .unwrap();
+        assert!(dir.path().join("pcb.1").exists());
+        assert!(dir.path().join("pcb-build.1").exists());
+    }
+}
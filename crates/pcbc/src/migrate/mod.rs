@@ -1,18 +1,25 @@
 use anyhow::{Context, Result, bail};
 use clap::Args;
+use pcb_zen::codemod::{Codemod, apply_codemods};
 use pcb_zen_core::config::{
     PcbToml, parse_pcb_version, pcb_version_from_cargo, pcb_version_is_older,
 };
+use similar::TextDiff;
 use std::collections::BTreeSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use toml_edit::{DocumentMut, Item, value};
 
+mod moved;
+
 type PcbLane = (u32, u32);
 
 struct Migration {
     target: PcbLane,
-    apply: fn(&Path) -> Result<()>,
+    /// `.zen` rewrites for this lane, such as renamed builtins or changed signatures.
+    codemods: &'static [Codemod],
+    /// Any other changes, e.g. manifest edits. Skipped by `--dry-run`.
+    apply: Option<fn(&Path) -> Result<()>>,
 }
 
 // Add migrations by target lane; the manifest version is bumped only after all apply.
@@ -25,13 +32,29 @@ pub struct MigrateArgs {
     /// One or more paths to consider for migration.
     #[arg(value_name = "PATHS", value_hint = clap::ValueHint::AnyPath)]
     pub paths: Vec<PathBuf>,
+
+    /// Print the .zen changes as diffs without writing any files
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Instead of version migrations, add moved() directives for components
+    /// whose path changed since the last layout sync
+    #[arg(long)]
+    pub moved: bool,
+
+    /// With --moved, accept unambiguous matches without asking and skip ambiguous ones
+    #[arg(long, requires = "moved")]
+    pub auto: bool,
 }
 
 /// Execute the `migrate` command
 pub fn execute(args: MigrateArgs) -> Result<()> {
+    if args.moved {
+        return moved::execute(&args.paths, args.auto, args.dry_run);
+    }
     let roots = migration_roots(args.paths)?;
     for root in roots {
-        migrate_workspace(&root)?;
+        migrate_workspace(&root, args.dry_run)?;
     }
     Ok(())
 }
@@ -94,13 +117,18 @@ fn find_migration_root(start: &Path) -> Result<PathBuf> {
         .unwrap_or(start_dir))
 }
 
-fn migrate_workspace(root: &Path) -> Result<()> {
+fn migrate_workspace(root: &Path, dry_run: bool) -> Result<()> {
     let pcb_toml_path = root.join("pcb.toml");
     let original = fs::read_to_string(&pcb_toml_path)
         .with_context(|| format!("Failed to read {}", pcb_toml_path.display()))?;
     let (content, removed_members) = remove_workspace_members(&original)
         .with_context(|| format!("Failed to update {}", pcb_toml_path.display()))?;
-    if removed_members {
+    if removed_members && dry_run {
+        println!(
+            "pcb: would remove deprecated [workspace].members from {}",
+            pcb_toml_path.display()
+        );
+    } else if removed_members {
         fs::write(&pcb_toml_path, &content)
             .with_context(|| format!("Failed to write {}", pcb_toml_path.display()))?;
         println!(
@@ -144,8 +172,16 @@ fn migrate_workspace(root: &Path) -> Result<()> {
     }
 
     let from_lane = existing.and_then(parse_pcb_version);
-    run_versioned_migrations(root, from_lane, target_lane)?;
+    run_versioned_migrations(root, from_lane, target_lane, dry_run)?;
 
+    if dry_run {
+        println!(
+            "pcb: would set {} pcb-version = \"{}\"",
+            pcb_toml_path.display(),
+            target
+        );
+        return Ok(());
+    }
     write_workspace_pcb_version(&pcb_toml_path, &target)?;
 
     if let Some(previous) = existing {
@@ -204,24 +240,85 @@ fn remove_workspace_members(content: &str) -> Result<(String, bool)> {
     }
 }
 
-fn run_versioned_migrations(root: &Path, from: Option<PcbLane>, to: PcbLane) -> Result<()> {
-    for migration in MIGRATIONS {
-        if from.is_none_or(|from| from < migration.target) && migration.target <= to {
-            (migration.apply)(root).with_context(|| {
+fn run_versioned_migrations(
+    root: &Path,
+    from: Option<PcbLane>,
+    to: PcbLane,
+    dry_run: bool,
+) -> Result<()> {
+    let pending: Vec<&Migration> = MIGRATIONS
+        .iter()
+        .filter(|migration| {
+            from.is_none_or(|from| from < migration.target) && migration.target <= to
+        })
+        .collect();
+
+    // Codemods from every pending lane run in one pass per file, in lane order.
+    let codemods: Vec<Codemod> = pending
+        .iter()
+        .flat_map(|migration| migration.codemods.iter().copied())
+        .collect();
+    let rewritten = rewrite_zen_files(root, &codemods, dry_run)?;
+    if rewritten > 0 && !dry_run {
+        println!("pcb: rewrote {rewritten} .zen file(s)");
+    }
+
+    for migration in pending {
+        if let Some(apply) = migration.apply.filter(|_| !dry_run) {
+            apply(root).with_context(|| {
                 format!(
                     "Failed to apply migration for pcb-version {}",
                     format_lane(migration.target)
                 )
             })?;
-            println!(
-                "pcb: applied migration for pcb-version {}",
-                format_lane(migration.target)
-            );
         }
+        println!(
+            "pcb: {} migration for pcb-version {}",
+            if dry_run { "would apply" } else { "applied" },
+            format_lane(migration.target)
+        );
     }
     Ok(())
 }
 
+/// Apply `codemods` to every .zen file under `root`. With `dry_run`, print a
+/// unified diff per changed file instead of writing it. Returns the number of
+/// changed files.
+fn rewrite_zen_files(root: &Path, codemods: &[Codemod], dry_run: bool) -> Result<usize> {
+    if codemods.is_empty() {
+        return Ok(0);
+    }
+
+    let mut changed = 0;
+    for path in crate::file_walker::collect_zen_files(&[root])? {
+        let source = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let display = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        let updated = apply_codemods(&display, &source, codemods)?;
+        if updated == source {
+            continue;
+        }
+        changed += 1;
+        if dry_run {
+            let diff = TextDiff::from_lines(source.as_str(), updated.as_str());
+            print!(
+                "{}",
+                diff.unified_diff()
+                    .context_radius(3)
+                    .header(&format!("old/{display}"), &format!("new/{display}"))
+            );
+        } else {
+            fs::write(&path, updated)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+    }
+    Ok(changed)
+}
+
 fn format_lane((major, minor): PcbLane) -> String {
     format!("{major}.{minor}")
 }
@@ -319,6 +416,29 @@ members = "not this"
         assert!(output.contains("[dependencies]\nmembers = \"not this\""));
     }
 
+    #[test]
+    fn rewrite_zen_files_honors_dry_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("workspace");
+        fs::create_dir(&root).unwrap();
+        let path = root.join("board.zen");
+        fs::write(&path, "vcc = OldNet(\"VCC\")\n").unwrap();
+        let codemods = [Codemod::RenameFunction {
+            from: "OldNet",
+            to: "Net",
+        }];
+
+        assert_eq!(rewrite_zen_files(&root, &codemods, true).unwrap(), 1);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "vcc = OldNet(\"VCC\")\n"
+        );
+
+        assert_eq!(rewrite_zen_files(&root, &codemods, false).unwrap(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "vcc = Net(\"VCC\")\n");
+        assert_eq!(rewrite_zen_files(&root, &codemods, false).unwrap(), 0);
+    }
+
     #[test]
     fn writes_workspace_pcb_version_from_current_manifest() {
         let dir = tempfile::tempdir().unwrap();
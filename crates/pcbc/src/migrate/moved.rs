@@ -0,0 +1,201 @@
+//! `pcb migrate --moved`: write moved() directives for components whose
+//! instance path changed since the layout was last synced.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use inquire::{Confirm, Select};
+use pcb_layout::{InferredMove, collapse_moves, infer_moved_paths};
+use pcb_sexpr::board::extract_footprints;
+use similar::TextDiff;
+
+use crate::build::{BuildEvalState, create_diagnostics_passes};
+
+const SKIP: &str = "Skip";
+
+pub(super) fn execute(paths: &[PathBuf], auto: bool, dry_run: bool) -> Result<()> {
+    if !auto && !crate::tty::is_interactive() {
+        bail!(
+            "`pcb migrate --moved` asks before each rename; pass --auto to run non-interactively"
+        );
+    }
+
+    let starts = if paths.is_empty() {
+        vec![std::env::current_dir()?]
+    } else {
+        paths.to_vec()
+    };
+    for start in starts {
+        migrate_boards(&start, auto, dry_run)?;
+    }
+    Ok(())
+}
+
+fn migrate_boards(path: &Path, auto: bool, dry_run: bool) -> Result<()> {
+    let resolution = crate::resolve::resolve(Some(path), false)?;
+    let workspace_root = resolution.workspace_info.root.clone();
+    let scope = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", path.display()))?;
+    let boards: Vec<(String, PathBuf)> = resolution
+        .workspace_info
+        .boards()
+        .into_values()
+        .map(|board| {
+            let zen_path = board.absolute_zen_path(&workspace_root);
+            (board.name, zen_path)
+        })
+        .filter(|(_, zen_path)| zen_path.starts_with(&scope))
+        .collect();
+    if boards.is_empty() {
+        bail!("No boards found under {}", path.display());
+    }
+
+    let eval_state = BuildEvalState::new(resolution);
+    for (name, zen_path) in &boards {
+        migrate_board(&eval_state, name, zen_path, auto, dry_run)?;
+    }
+    Ok(())
+}
+
+fn migrate_board(
+    eval_state: &BuildEvalState,
+    name: &str,
+    zen_path: &Path,
+    auto: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let mut has_errors = false;
+    let result = eval_state.build(
+        zen_path,
+        Default::default(),
+        create_diagnostics_passes(&[], &[]),
+        false,
+        &mut has_errors,
+        &mut false,
+    );
+    let Some(schematic) = result.schematic else {
+        bail!("Failed to build {}", zen_path.display());
+    };
+
+    let Some(layout_dir) = pcb_layout::utils::resolve_layout_dir(&schematic)? else {
+        println!("pcb: {name} has no layout; skipping");
+        return Ok(());
+    };
+    let Some(kicad_files) = pcb_layout::utils::discover_kicad_files(&layout_dir)? else {
+        println!("pcb: {name} has no layout yet; skipping");
+        return Ok(());
+    };
+    let pcb_file = kicad_files.kicad_pcb();
+    let text = fs::read_to_string(&pcb_file)
+        .with_context(|| format!("Failed to read {}", pcb_file.display()))?;
+    let board = pcb_sexpr::parse(&text)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {e}", pcb_file.display()))?;
+
+    let inferred = infer_moved_paths(&board, &schematic)?;
+    if inferred.is_empty() {
+        println!("pcb: {name}: no moved components found");
+        return Ok(());
+    }
+
+    let mut taken: HashSet<String> = HashSet::new();
+    let mut accepted = Vec::new();
+    for inferred_move in &inferred {
+        let candidates: Vec<&String> = inferred_move
+            .candidates
+            .iter()
+            .filter(|candidate| !taken.contains(*candidate))
+            .collect();
+        if candidates.is_empty() {
+            continue;
+        }
+        let choice = if auto {
+            auto_choice(inferred_move, &candidates)
+        } else {
+            prompt_choice(inferred_move, &candidates)?
+        };
+        if let Some(new) = choice {
+            taken.insert(new.clone());
+            accepted.push((inferred_move.old.clone(), new));
+        }
+    }
+    if accepted.is_empty() {
+        println!("pcb: {name}: no moves accepted");
+        return Ok(());
+    }
+
+    let layout_paths: BTreeSet<String> = extract_footprints(&board)
+        .map_err(anyhow::Error::msg)?
+        .into_iter()
+        .filter_map(|fp| fp.properties.get("Path").cloned())
+        .collect();
+    let directives = collapse_moves(&accepted, &layout_paths);
+    write_directives(zen_path, &directives, dry_run)?;
+    println!(
+        "pcb: {} {} moved() directive(s) to {}",
+        if dry_run { "would add" } else { "added" },
+        directives.len(),
+        zen_path.display()
+    );
+    Ok(())
+}
+
+fn auto_choice(inferred_move: &InferredMove, candidates: &[&String]) -> Option<String> {
+    if inferred_move.ambiguous {
+        let options: Vec<&str> = candidates.iter().map(|c| c.as_str()).collect();
+        eprintln!(
+            "pcb: skipping {}: ambiguous between {}",
+            inferred_move.old,
+            options.join(", ")
+        );
+        return None;
+    }
+    Some(candidates[0].clone())
+}
+
+fn prompt_choice(inferred_move: &InferredMove, candidates: &[&String]) -> Result<Option<String>> {
+    if !inferred_move.ambiguous || candidates.len() == 1 {
+        let accepted = Confirm::new(&format!("{} → {}?", inferred_move.old, candidates[0]))
+            .with_default(true)
+            .prompt()
+            .map_err(|e| anyhow::anyhow!("Prompt cancelled: {}", e))?;
+        return Ok(accepted.then(|| candidates[0].clone()));
+    }
+
+    let mut options: Vec<&str> = candidates.iter().map(|c| c.as_str()).collect();
+    options.push(SKIP);
+    let selected = Select::new(&format!("{} moved to", inferred_move.old), options)
+        .prompt()
+        .map_err(|e| anyhow::anyhow!("Prompt cancelled: {}", e))?;
+    Ok((selected != SKIP).then(|| selected.to_string()))
+}
+
+/// Append `moved(old, new)` calls to the board file. With `dry_run`, print
+/// the diff instead.
+fn write_directives(zen_path: &Path, directives: &[(String, String)], dry_run: bool) -> Result<()> {
+    let source = fs::read_to_string(zen_path)
+        .with_context(|| format!("Failed to read {}", zen_path.display()))?;
+    let mut updated = source.clone();
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push('\n');
+    for (old, new) in directives {
+        updated.push_str(&format!("moved({old:?}, {new:?})\n"));
+    }
+
+    if dry_run {
+        let display = zen_path.display().to_string();
+        let diff = TextDiff::from_lines(source.as_str(), updated.as_str());
+        print!(
+            "{}",
+            diff.unified_diff()
+                .context_radius(3)
+                .header(&format!("old/{display}"), &format!("new/{display}"))
+        );
+        return Ok(());
+    }
+    fs::write(zen_path, updated).with_context(|| format!("Failed to write {}", zen_path.display()))
+}
@@ -21,7 +21,7 @@ use pcb_zen_core::resolution::FrozenResolutionMap;
 use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 
-use self::request::resolve_direct_dependency_request;
+use self::request::{resolve_direct_dependency_request, resolve_upgrade_request};
 use self::target::{AddTarget, discover_add_targets, discover_package_target};
 use self::writeback::{ManifestEdit, plan_package_manifest};
 
@@ -109,6 +109,40 @@ pub fn execute_mod_add(args: ModAddArgs) -> Result<()> {
     )
 }
 
+/// Upgrade direct dependencies of the package in the current directory.
+///
+/// With `dry_run`, resolves the upgrade and prints the version changes
+/// instead of writing manifests.
+pub(crate) fn execute_update(
+    dependency: Option<&str>,
+    breaking: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let (workspace, target) = load_single_target_workspace("pcb update")?;
+    let current_config = load_target_manifest(&target)?;
+    let overrides = upgrade_overrides(&workspace, &current_config, dependency, breaking)?;
+
+    if dry_run {
+        let mut resolver = PackageResolver::new(workspace.clone())?;
+        let before = resolver.resolve_package(&target.package_url)?;
+        let after = resolver
+            .resolve_package_with_direct_overrides(&target.package_url, Some(&overrides))?;
+        let changes =
+            crate::update::version_changes(&before.resolved_remote, &after.resolved_remote);
+        crate::update::print_changes(&changes);
+        return Ok(());
+    }
+
+    run_resolution(
+        &workspace,
+        std::slice::from_ref(&target),
+        false,
+        Some((&target.package_url, &overrides)),
+        false,
+        SyncMode::Write,
+    )
+}
+
 pub fn execute_sync(args: SyncArgs) -> Result<()> {
     let cwd = std::env::current_dir()?;
     execute_sync_from(&cwd, args)
@@ -229,7 +263,12 @@ fn add_overrides(
     let current_config = load_target_manifest(target)?;
 
     if args.upgrade {
-        return upgrade_overrides(workspace, &current_config, args.dependency.as_deref());
+        return upgrade_overrides(
+            workspace,
+            &current_config,
+            args.dependency.as_deref(),
+            false,
+        );
     }
 
     let Some(dependency) = args.dependency.as_deref() else {
@@ -244,6 +283,7 @@ fn upgrade_overrides(
     workspace: &WorkspaceInfo,
     current_config: &PcbToml,
     dependency: Option<&str>,
+    breaking: bool,
 ) -> Result<DirectOverrides> {
     let mut overrides = BTreeMap::new();
 
@@ -263,7 +303,7 @@ fn upgrade_overrides(
             bail!("not a remote dependency: {}", dependency);
         }
         validate_mod_add_target(workspace, module_path)?;
-        let (_, spec) = resolve_direct_dependency_request(module_path, current_config)?;
+        let spec = resolve_upgrade_request(module_path, current_config, breaking)?;
         overrides.insert(module_path.clone(), spec);
         return Ok(overrides);
     }
@@ -272,7 +312,7 @@ fn upgrade_overrides(
         if !is_remote_dependency(workspace, module_path, spec) {
             continue;
         }
-        let (_, spec) = resolve_direct_dependency_request(module_path, current_config)?;
+        let spec = resolve_upgrade_request(module_path, current_config, breaking)?;
         overrides.insert(module_path.clone(), spec);
     }
 
@@ -33,6 +33,23 @@ pub(crate) fn resolve_direct_dependency_request(
     ))
 }
 
+/// Resolve the newest stable version for an existing direct dependency.
+///
+/// Stays in the dependency's current compatibility lane unless `breaking`.
+pub(crate) fn resolve_upgrade_request(
+    module_path: &str,
+    current_config: &PcbToml,
+    breaking: bool,
+) -> Result<DependencySpec> {
+    if !breaking {
+        return resolve_direct_dependency_request(module_path, current_config)
+            .map(|(_, spec)| spec);
+    }
+    let version = resolve_requested_version(module_path, RequestedVersion::Latest, None)
+        .with_context(|| format!("Failed to resolve requested dependency {}", module_path))?;
+    Ok(DependencySpec::Version(version.to_string()))
+}
+
 fn parse_dependency_request(raw: &str) -> Result<(&str, RequestedVersion)> {
     let raw = raw.trim();
     let Some((module_path, selector)) = raw.rsplit_once('@') else {
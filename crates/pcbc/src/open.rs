@@ -1,8 +1,11 @@
 use anyhow::{Context, Result};
 use clap::Args;
 use pcb_layout::utils;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 
+use crate::artifact_server::Artifact;
+
 #[derive(Args, Debug)]
 pub struct OpenArgs {
     /// Path to .zen/.kicad_pcb file or diode:// sandbox URI
@@ -12,15 +15,31 @@ pub struct OpenArgs {
     /// Disable network access (offline mode) - only use vendored dependencies
     #[arg(long = "offline")]
     pub offline: bool,
+
+    /// Open the datasheet of the component with this reference designator
+    /// instead of the layout
+    #[arg(long, value_name = "REF")]
+    pub datasheet: Option<String>,
+
+    /// Serve the board's netlist, BOM, and layout render read-only over HTTP
+    /// at this address instead of opening KiCad
+    #[arg(long, value_name = "ADDR", conflicts_with = "datasheet")]
+    pub serve: Option<SocketAddr>,
 }
 
 pub fn execute(args: OpenArgs) -> Result<()> {
     if let Some(uri) = crate::sandbox_uri::parse_sandbox_file_arg(&args.file)? {
+        if args.datasheet.is_some() || args.serve.is_some() {
+            anyhow::bail!("--datasheet and --serve require a local .zen file");
+        }
         crate::sandbox_uri::require_remote_openable_file(&uri)?;
         return crate::remote_sandbox::execute_open(uri, args);
     }
 
     if crate::sandbox_uri::is_kicad_pcb_path(&args.file) {
+        if args.datasheet.is_some() || args.serve.is_some() {
+            anyhow::bail!("--datasheet and --serve require a local .zen file");
+        }
         return open_pcb_file(&args.file);
     }
 
@@ -28,6 +47,7 @@ pub fn execute(args: OpenArgs) -> Result<()> {
 
     // Resolve dependencies before evaluating
     let resolution_result = crate::resolve::resolve(Some(&args.file), args.offline)?;
+    let workspace_root = resolution_result.workspace_info.root.clone();
 
     let zen_path = &args.file;
     let file_name = zen_path.file_name().unwrap().to_string_lossy();
@@ -42,6 +62,27 @@ pub fn execute(args: OpenArgs) -> Result<()> {
     let Some(schematic) = output.to_schematic_with_diagnostics().output else {
         anyhow::bail!("Build failed for {}", file_name);
     };
+
+    if let Some(refdes) = &args.datasheet {
+        let datasheets = crate::datasheet::component_datasheets(&schematic);
+        let datasheet = datasheets
+            .get(refdes)
+            .ok_or_else(|| anyhow::anyhow!("{refdes} has no datasheet in {file_name}"))?;
+        let path = crate::datasheet::local_datasheet(
+            &schematic,
+            &workspace_root,
+            datasheet,
+            args.offline,
+        )?;
+        return open::that(&path)
+            .with_context(|| format!("Failed to open datasheet {}", path.display()));
+    }
+
+    if let Some(addr) = args.serve {
+        let artifacts = build_artifacts(zen_path, &schematic)?;
+        return crate::artifact_server::serve(addr, &file_name, artifacts);
+    }
+
     let layout_dir = utils::resolve_layout_dir(&schematic)?
         .ok_or_else(|| anyhow::anyhow!("No layout path defined in {}", file_name))?;
 
@@ -69,3 +110,65 @@ fn open_pcb_file(path: &Path) -> Result<()> {
     })?;
     Ok(())
 }
+
+/// Netlist, BOM, and, when the board has a layout and KiCad is installed, a
+/// layout render of `schematic`.
+fn build_artifacts(zen_path: &Path, schematic: &pcb_sch::Schematic) -> Result<Vec<Artifact>> {
+    let layout_dir = utils::resolve_layout_dir(schematic)?;
+    let layout_path = match &layout_dir {
+        Some(dir) => utils::discover_kicad_files(dir)?
+            .map(|files| files.kicad_pcb())
+            .filter(|path| path.exists()),
+        None => None,
+    };
+
+    let mut bom = crate::bom::generate_bom_with_fallback(schematic.bom(), layout_dir.as_deref())?;
+    crate::bom::apply_bom_rules(&mut bom, zen_path)?;
+
+    let mut artifacts = vec![
+        Artifact {
+            name: "netlist.json",
+            content_type: "application/json",
+            description: "Netlist",
+            body: schematic.to_json()?.into_bytes(),
+        },
+        Artifact {
+            name: "bom.json",
+            content_type: "application/json",
+            description: "Bill of materials",
+            body: bom.filter_excluded().ungrouped_json().into_bytes(),
+        },
+    ];
+
+    match &layout_path {
+        Some(path) => match render_layout_svg(path) {
+            Ok(svg) => artifacts.push(Artifact {
+                name: "layout.svg",
+                content_type: "image/svg+xml",
+                description: "Layout render",
+                body: svg,
+            }),
+            Err(e) => eprintln!("Warning: Not serving a layout render: {e:#}"),
+        },
+        None => eprintln!("Warning: No layout found; not serving a layout render"),
+    }
+    Ok(artifacts)
+}
+
+fn render_layout_svg(kicad_pcb: &Path) -> Result<Vec<u8>> {
+    let dir = tempfile::tempdir()?;
+    let svg = dir.path().join("layout.svg");
+    pcb_kicad::KiCadCliBuilder::new()
+        .command("pcb")
+        .subcommand("export")
+        .subcommand("svg")
+        .arg("--layers")
+        .arg("F.Cu,B.Cu,F.Silkscreen,B.Silkscreen,Edge.Cuts")
+        .arg("--exclude-drawing-sheet")
+        .arg("--output")
+        .arg(svg.to_string_lossy())
+        .arg(kicad_pcb.to_string_lossy())
+        .run()
+        .context("Failed to render layout")?;
+    std::fs::read(&svg).with_context(|| format!("Failed to read {}", svg.display()))
+}
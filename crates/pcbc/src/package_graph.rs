@@ -0,0 +1,302 @@
+//! Package dependency graph rendering for `pcb info --graph packages`.
+//!
+//! Nodes are module paths: workspace members plus every external package in
+//! the resolution. Edges carry the requirement written in the dependent's
+//! `pcb.toml`. Two things are highlighted:
+//!
+//! * duplicates: an external package resolved at more than one version;
+//! * conflicts: a package required in more than one compatibility lane
+//!   (or by different git refs / paths) across its dependents.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+use pcb_zen_core::config::DependencySpec;
+use pcb_zen_core::resolution::compatibility_lane;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GraphKind {
+    /// pcb.toml dependencies between workspace members and external packages
+    Packages,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GraphFormat {
+    /// Mermaid flowchart
+    Mermaid,
+    /// Graphviz DOT
+    Dot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageKind {
+    Workspace,
+    External,
+}
+
+#[derive(Debug)]
+struct Node {
+    kind: PackageKind,
+    versions: BTreeSet<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct PackageGraph {
+    nodes: BTreeMap<String, Node>,
+    edges: BTreeSet<(String, String, String)>,
+}
+
+impl PackageGraph {
+    /// Add a package, merging versions when it is already present.
+    pub fn add_package(&mut self, module_path: &str, kind: PackageKind, version: Option<&str>) {
+        let node = self
+            .nodes
+            .entry(module_path.to_string())
+            .or_insert_with(|| Node {
+                kind,
+                versions: BTreeSet::new(),
+            });
+        if kind == PackageKind::Workspace {
+            node.kind = kind;
+        }
+        if let Some(version) = version {
+            node.versions.insert(version.to_string());
+        }
+    }
+
+    pub fn add_dependency(&mut self, from: &str, to: &str, spec: &DependencySpec) {
+        self.add_package(to, PackageKind::External, None);
+        self.edges
+            .insert((from.to_string(), to.to_string(), requirement(spec)));
+    }
+
+    /// External packages resolved at more than one version.
+    fn duplicates(&self) -> BTreeSet<&str> {
+        self.nodes
+            .iter()
+            .filter(|(_, node)| node.kind == PackageKind::External && node.versions.len() > 1)
+            .map(|(path, _)| path.as_str())
+            .collect()
+    }
+
+    /// Packages whose dependents ask for incompatible requirements.
+    fn conflicts(&self) -> BTreeSet<&str> {
+        let mut lanes: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for (_, to, req) in &self.edges {
+            lanes.entry(to).or_default().insert(requirement_lane(req));
+        }
+        lanes
+            .into_iter()
+            .filter(|(_, lanes)| lanes.len() > 1)
+            .map(|(path, _)| path)
+            .collect()
+    }
+
+    fn label(&self, path: &str) -> String {
+        let versions = &self.nodes[path].versions;
+        if versions.is_empty() {
+            path.to_string()
+        } else {
+            let versions: Vec<&str> = versions.iter().map(String::as_str).collect();
+            format!("{path}\n{}", versions.join(", "))
+        }
+    }
+
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Mermaid => self.to_mermaid(),
+            GraphFormat::Dot => self.to_dot(),
+        }
+    }
+
+    fn to_mermaid(&self) -> String {
+        let ids: BTreeMap<&str, String> = self
+            .nodes
+            .keys()
+            .enumerate()
+            .map(|(i, path)| (path.as_str(), format!("n{i}")))
+            .collect();
+        let duplicates = self.duplicates();
+        let conflicts = self.conflicts();
+
+        let mut out = String::from("graph LR\n");
+        for (path, node) in &self.nodes {
+            let label = self
+                .label(path)
+                .replace('"', "#quot;")
+                .replace('\n', "<br/>");
+            let class = if duplicates.contains(path.as_str()) || conflicts.contains(path.as_str()) {
+                ":::problem"
+            } else if node.kind == PackageKind::Workspace {
+                ":::member"
+            } else {
+                ""
+            };
+            writeln!(out, "    {}[\"{label}\"]{class}", ids[path.as_str()]).unwrap();
+        }
+        let mut conflicting_links = Vec::new();
+        for (i, (from, to, req)) in self.edges.iter().enumerate() {
+            writeln!(
+                out,
+                "    {} -->|\"{}\"| {}",
+                ids[from.as_str()],
+                req.replace('"', "#quot;"),
+                ids[to.as_str()]
+            )
+            .unwrap();
+            if conflicts.contains(to.as_str()) {
+                conflicting_links.push(i.to_string());
+            }
+        }
+        out.push_str("    classDef member fill:#e8f0fe,stroke:#4a6fa5\n");
+        out.push_str("    classDef problem fill:#fdecea,stroke:#d33,stroke-width:2px\n");
+        if !conflicting_links.is_empty() {
+            writeln!(
+                out,
+                "    linkStyle {} stroke:#d33,stroke-width:2px",
+                conflicting_links.join(",")
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    fn to_dot(&self) -> String {
+        let duplicates = self.duplicates();
+        let conflicts = self.conflicts();
+
+        let mut out = String::from("digraph packages {\n    rankdir=LR;\n    node [shape=box];\n");
+        for (path, node) in &self.nodes {
+            let mut attrs = vec![format!("label={}", dot_quote(&self.label(path)))];
+            if node.kind == PackageKind::Workspace {
+                attrs.push("style=filled".to_string());
+                attrs.push("fillcolor=\"#e8f0fe\"".to_string());
+            }
+            if duplicates.contains(path.as_str()) || conflicts.contains(path.as_str()) {
+                attrs.push("color=red".to_string());
+                attrs.push("penwidth=2".to_string());
+            }
+            writeln!(out, "    {} [{}];", dot_quote(path), attrs.join(", ")).unwrap();
+        }
+        for (from, to, req) in &self.edges {
+            let color = if conflicts.contains(to.as_str()) {
+                ", color=red"
+            } else {
+                ""
+            };
+            writeln!(
+                out,
+                "    {} -> {} [label={}{color}];",
+                dot_quote(from),
+                dot_quote(to),
+                dot_quote(req)
+            )
+            .unwrap();
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Requirement as written in `pcb.toml`.
+fn requirement(spec: &DependencySpec) -> String {
+    match spec {
+        DependencySpec::Version(version) => version.clone(),
+        DependencySpec::Detailed(detail) => {
+            if let Some(version) = &detail.version {
+                version.clone()
+            } else if let Some(rev) = &detail.rev {
+                format!("rev {rev}")
+            } else if let Some(branch) = &detail.branch {
+                format!("branch {branch}")
+            } else if let Some(path) = &detail.path {
+                format!("path {path}")
+            } else {
+                "*".to_string()
+            }
+        }
+    }
+}
+
+/// Compatibility lane a requirement selects (`0.3`, `1`, ...); non-version
+/// requirements are their own lane. Caret requirements are the default, so
+/// `^0.3.1` and `0.3` share a lane.
+fn requirement_lane(req: &str) -> String {
+    let version = req.trim().trim_start_matches('^').trim_start();
+    pcb_zen_core::parse_relaxed_version(version)
+        .map(|version| compatibility_lane(&version))
+        .unwrap_or_else(|| req.to_string())
+}
+
+fn dot_quote(s: &str) -> String {
+    format!(
+        "\"{}\"",
+        s.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(v: &str) -> DependencySpec {
+        DependencySpec::Version(v.to_string())
+    }
+
+    fn graph() -> PackageGraph {
+        let mut graph = PackageGraph::default();
+        graph.add_package("acme/boards/main", PackageKind::Workspace, None);
+        graph.add_package("acme/modules/psu", PackageKind::Workspace, Some("0.2.0"));
+        graph.add_package("vendor/parts", PackageKind::External, Some("0.3.1"));
+        graph.add_package("vendor/parts", PackageKind::External, Some("0.4.0"));
+        graph.add_package("vendor/passives", PackageKind::External, Some("1.2.0"));
+        graph.add_dependency("acme/boards/main", "acme/modules/psu", &version("0.2"));
+        graph.add_dependency("acme/boards/main", "vendor/parts", &version("0.4"));
+        graph.add_dependency("acme/modules/psu", "vendor/parts", &version("^0.3.1"));
+        graph.add_dependency("acme/boards/main", "vendor/passives", &version("1.0"));
+        graph.add_dependency("acme/modules/psu", "vendor/passives", &version("1.2"));
+        graph
+    }
+
+    #[test]
+    fn flags_duplicates_and_lane_conflicts() {
+        let graph = graph();
+        assert_eq!(graph.duplicates(), BTreeSet::from(["vendor/parts"]));
+        assert_eq!(graph.conflicts(), BTreeSet::from(["vendor/parts"]));
+    }
+
+    #[test]
+    fn caret_requirements_share_their_version_lane() {
+        assert_eq!(requirement_lane("^0.3.1"), "0.3");
+        assert_eq!(requirement_lane("^ 0.3"), requirement_lane("0.3.4"));
+        assert_eq!(requirement_lane("^1.2"), requirement_lane("1"));
+        assert_eq!(requirement_lane("rev abc123"), "rev abc123");
+
+        let mut graph = PackageGraph::default();
+        graph.add_dependency("a", "vendor/parts", &version("^0.3.1"));
+        graph.add_dependency("b", "vendor/parts", &version("0.3"));
+        assert!(graph.conflicts().is_empty());
+    }
+
+    #[test]
+    fn renders_mermaid_and_dot() {
+        let graph = graph();
+        let mermaid = graph.render(GraphFormat::Mermaid);
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains("    n0[\"acme/boards/main\"]:::member\n"));
+        assert!(mermaid.contains("    n2[\"vendor/parts<br/>0.3.1, 0.4.0\"]:::problem\n"));
+        assert!(mermaid.contains("    n1 -->|\"^0.3.1\"| n2\n"));
+        assert!(mermaid.contains("    linkStyle 1,3 stroke:#d33,stroke-width:2px\n"));
+
+        let dot = graph.render(GraphFormat::Dot);
+        assert!(dot.contains(
+            "    \"vendor/parts\" [label=\"vendor/parts\\n0.3.1, 0.4.0\", color=red, penwidth=2];\n"
+        ));
+        assert!(dot.contains(
+            "    \"acme/modules/psu\" -> \"vendor/parts\" [label=\"^0.3.1\", color=red];\n"
+        ));
+        assert!(dot.ends_with("}\n"));
+    }
+}
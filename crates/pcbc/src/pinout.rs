@@ -0,0 +1,161 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::build::create_diagnostics_passes;
+use crate::config_input::{CONFIG_ARG_HELP, parse_config_overrides};
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use pcb_sch::Schematic;
+use pcb_sch::pinout::{PinoutFormat, component_pinout, connector_refdes, export_pinout};
+
+/// Markdown document of every connector written by `--connectors -o DIR`.
+pub const CONNECTORS_MARKDOWN: &str = "connectors.md";
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default)]
+pub enum PinoutArgFormat {
+    #[default]
+    Csv,
+    Md,
+    Svg,
+    Xdc,
+    Qsf,
+}
+
+impl std::fmt::Display for PinoutArgFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", PinoutFormat::from(*self).extension())
+    }
+}
+
+impl From<PinoutArgFormat> for PinoutFormat {
+    fn from(format: PinoutArgFormat) -> Self {
+        match format {
+            PinoutArgFormat::Csv => PinoutFormat::Csv,
+            PinoutArgFormat::Md => PinoutFormat::Markdown,
+            PinoutArgFormat::Svg => PinoutFormat::Svg,
+            PinoutArgFormat::Xdc => PinoutFormat::Xdc,
+            PinoutArgFormat::Qsf => PinoutFormat::Qsf,
+        }
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Export a component's pin-to-net mapping, connector pinout docs, or FPGA constraints"
+)]
+pub struct PinoutArgs {
+    /// .zen file to process
+    #[arg(value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    pub file: PathBuf,
+
+    /// Reference designator of the component, e.g. U1
+    #[arg(value_name = "REF", required_unless_present = "connectors")]
+    pub refdes: Option<String>,
+
+    /// Document every connector: Markdown on stdout, or with `-o DIR` a
+    /// Markdown document plus a CSV and SVG per connector
+    #[arg(long, conflicts_with_all = ["refdes", "format"])]
+    pub connectors: bool,
+
+    #[arg(long = "config", value_name = "KEY=VALUE", help = CONFIG_ARG_HELP)]
+    pub config: Vec<String>,
+
+    /// Output format
+    #[arg(short, long, default_value_t = PinoutArgFormat::Csv)]
+    pub format: PinoutArgFormat,
+
+    /// Write to this file (a directory with --connectors) instead of stdout
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    /// Disable network access (offline mode) - only use vendored dependencies
+    #[arg(long = "offline")]
+    pub offline: bool,
+}
+
+pub fn execute(args: PinoutArgs) -> Result<()> {
+    crate::file_walker::require_zen_file(&args.file)?;
+    let config_inputs = parse_config_overrides(&args.config)?;
+    let resolution_result = crate::resolve::resolve(Some(&args.file), args.offline)?;
+
+    let file_name = args.file.file_name().unwrap().to_string_lossy();
+    let eval_output = pcb_zen::eval(&args.file, resolution_result, config_inputs)
+        .output_result()
+        .map_err(|mut diagnostics| {
+            diagnostics.apply_passes(&create_diagnostics_passes(&[], &[]));
+            anyhow::anyhow!("Failed to build {} - cannot export pinout", file_name)
+        })?;
+    let schematic = eval_output
+        .to_schematic()
+        .context("Failed to convert to schematic")?;
+
+    if args.connectors {
+        return match &args.output {
+            Some(dir) => {
+                let count = write_connector_pinouts(&schematic, dir)?;
+                if count == 0 {
+                    anyhow::bail!("No connectors in {file_name}");
+                }
+                eprintln!("Wrote pinouts of {count} connectors to {}", dir.display());
+                Ok(())
+            }
+            None => {
+                let markdown = connectors_markdown(&schematic);
+                if markdown.is_empty() {
+                    anyhow::bail!("No connectors in {file_name}");
+                }
+                write!(io::stdout().lock(), "{markdown}")?;
+                Ok(())
+            }
+        };
+    }
+
+    let refdes = args.refdes.as_deref().unwrap_or_default();
+    let pins = component_pinout(&schematic, refdes)
+        .ok_or_else(|| anyhow::anyhow!("No component {refdes} in {file_name}"))?;
+    let rendered = export_pinout(refdes, &pins, args.format.into());
+
+    match &args.output {
+        Some(path) => std::fs::write(path, rendered)
+            .with_context(|| format!("Failed to write {}", path.display()))?,
+        None => write!(io::stdout().lock(), "{rendered}")?,
+    }
+    Ok(())
+}
+
+/// Markdown pinout tables of every connector of `schematic`, or an empty
+/// string if it has none.
+pub fn connectors_markdown(schematic: &Schematic) -> String {
+    connector_refdes(schematic)
+        .iter()
+        .filter_map(|refdes| {
+            let pins = component_pinout(schematic, refdes)?;
+            Some(export_pinout(refdes, &pins, PinoutFormat::Markdown))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Write `connectors.md` and a CSV and SVG per connector of `schematic` into
+/// `dir`. Returns the number of connectors; nothing is written if it is zero.
+pub fn write_connector_pinouts(schematic: &Schematic, dir: &Path) -> Result<usize> {
+    let connectors = connector_refdes(schematic);
+    if connectors.is_empty() {
+        return Ok(0);
+    }
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    for refdes in &connectors {
+        let Some(pins) = component_pinout(schematic, refdes) else {
+            continue;
+        };
+        for format in [PinoutFormat::Csv, PinoutFormat::Svg] {
+            let path = dir.join(format!("{refdes}.{}", format.extension()));
+            std::fs::write(&path, export_pinout(refdes, &pins, format))
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+    }
+    let path = dir.join(CONNECTORS_MARKDOWN);
+    std::fs::write(&path, connectors_markdown(schematic))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(connectors.len())
+}
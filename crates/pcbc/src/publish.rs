@@ -19,12 +19,13 @@ use semver::Version;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::env;
 use std::fmt;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::file_walker::{collect_zen_files, resolve_board_target};
 use crate::pcb_mod::sync_targets;
 use crate::pcb_mod::target::add_target_for_package;
-use crate::release;
+use crate::signing::{self, SignArgs};
+use crate::{forge, release};
 
 /// Version bump type for publishing
 #[derive(Clone, Copy, Debug, ValueEnum, PartialEq, Eq)]
@@ -155,6 +156,11 @@ pub struct PublishArgs {
     #[arg(long, hide = true)]
     pub no_push: bool,
 
+    /// List the files each package would publish, with sizes and warnings,
+    /// without building, tagging, or pushing
+    #[arg(long)]
+    pub dry_run: bool,
+
     /// Suppress diagnostics by kind or severity
     #[arg(short = 'S', long = "suppress", value_name = "KIND")]
     pub suppress: Vec<String>,
@@ -163,10 +169,32 @@ pub struct PublishArgs {
     #[arg(long, value_enum, num_args(0..=1), require_equals(true), default_missing_value("interactive"))]
     pub bump: Option<BumpType>,
 
+    /// Also create a release with the board archive on the repository's forge
+    /// (GitHub, GitLab, or Gitea)
+    #[arg(long)]
+    pub forge_release: bool,
+
     /// Exclude specific manufacturing artifacts from the release (can be specified multiple times)
     #[arg(long, value_enum)]
     pub exclude: Vec<release::ArtifactType>,
 
+    /// Publish a board release even though the workspace pcb.toml has [patch] entries
+    #[arg(long)]
+    pub allow_patches: bool,
+
+    /// Compare the board's BOM with a previous release archive (.zip) and add
+    /// the part changes to the release notes
+    #[arg(long, value_name = "ARCHIVE", value_hint = clap::ValueHint::FilePath)]
+    pub bom_diff_against: Option<PathBuf>,
+
+    /// Publish without prompting when parts were added, removed, or substituted
+    /// since the --bom-diff-against release
+    #[arg(long, requires = "bom_diff_against")]
+    pub accept_bom_changes: bool,
+
+    #[command(flatten)]
+    pub sign: SignArgs,
+
     /// Path to publish from (defaults to current directory).
     /// If a .zen file is provided, publishes a board release.
     /// Otherwise, publishes dirty packages in the workspace.
@@ -177,8 +205,7 @@ pub struct PublishArgs {
 struct PublishCandidate {
     next_version: Version,
     tag_name: String,
-    content_hash: String,
-    manifest_hash: String,
+    hashes: pcb_canonical::PackageHashes,
 }
 
 /// Tracks local git state created during publishing.
@@ -464,9 +491,23 @@ pub fn execute(args: PublishArgs) -> Result<()> {
 
     // If path ends in .zen, route to board publish
     if path.extension().is_some_and(|ext| ext == "zen") {
+        if args.dry_run {
+            bail!("--dry-run previews package publishes; build a board release with `pcb release`");
+        }
         return publish_board(&path, &args);
     }
 
+    if args.sign.enabled() {
+        bail!("--sign applies to board releases; pass the board's .zen file");
+    }
+    if args.bom_diff_against.is_some() {
+        bail!("--bom-diff-against applies to board releases; pass the board's .zen file");
+    }
+
+    if args.dry_run {
+        return preview_packages(&path);
+    }
+
     // Otherwise, publish packages
     publish_packages(&path, &args)
 }
@@ -478,11 +519,11 @@ pub fn execute(args: PublishArgs) -> Result<()> {
 /// - Versioned release (--bump provided): preflight checks, fetch tags, build, upload, tag, push
 fn publish_board(zen_path: &Path, args: &PublishArgs) -> Result<()> {
     let target = resolve_board_target(zen_path, "publish")?;
-    ensure_board_publish_has_no_workspace_overrides(&target.workspace)?;
+    ensure_board_publish_has_no_workspace_overrides(&target.workspace, args.allow_patches)?;
 
     // Local hash release: no --bump, just build the archive
     if args.bump.is_none() {
-        let _zip_path = release::build_board_release(
+        let zip_path = release::build_board_release(
             target.workspace,
             target.zen_path,
             target.board_name,
@@ -491,6 +532,8 @@ fn publish_board(zen_path: &Path, args: &PublishArgs) -> Result<()> {
             args.exclude.clone(),
             false,
         )?;
+        review_bom_changes(&zip_path, args)?;
+        sign_release(&zip_path, &args.sign)?;
         return Ok(());
     }
 
@@ -512,6 +555,17 @@ fn publish_board(zen_path: &Path, args: &PublishArgs) -> Result<()> {
         None
     };
 
+    // Resolve the forge and its token before building, so a missing token
+    // does not fail the release after the tag is pushed.
+    let forge = match (&remote, args.forge_release) {
+        (Some(r), true) => Some(forge::from_remote_url(&git::get_remote_url_for(
+            &workspace.root,
+            r,
+        )?)?),
+        (None, true) => bail!("--forge-release cannot be used with --no-push"),
+        (_, false) => None,
+    };
+
     // Compute current version from tags (after fetch)
     let tag_prefix = tags::compute_tag_prefix(Some(&pkg_rel_path), workspace.path());
     let all_tags = git::list_all_tags(&workspace.root).unwrap_or_default();
@@ -532,7 +586,7 @@ fn publish_board(zen_path: &Path, args: &PublishArgs) -> Result<()> {
     let tag_name = tags::build_tag_name(&tag_prefix, &next_version);
 
     // Build the release archive
-    let _zip_path = release::build_board_release(
+    let zip_path = release::build_board_release(
         workspace.clone(),
         board_path,
         board_name.clone(),
@@ -541,13 +595,16 @@ fn publish_board(zen_path: &Path, args: &PublishArgs) -> Result<()> {
         args.exclude.clone(),
         false,
     )?;
+    // Gate on BOM changes before anything leaves the machine.
+    let bom_notes = review_bom_changes(&zip_path, args)?;
+    let signature = sign_release(&zip_path, &args.sign)?;
 
     // Upload to API (must succeed before creating tag)
     if remote.is_some() {
         let ws_name = release_workspace_name(&workspace)?;
         let ctx = pcb_diode_api::WorkspaceContext::from_workspace_root(&workspace.root);
         eprintln!("Uploading release to Diode...");
-        let result = pcb_diode_api::upload_release(&_zip_path, &ws_name, &ctx)?;
+        let result = pcb_diode_api::upload_release(&zip_path, &ws_name, &ctx)?;
         if let Some(release_id) = result.release_id {
             eprintln!(
                 "{} Release uploaded: {}",
@@ -567,24 +624,127 @@ fn publish_board(zen_path: &Path, args: &PublishArgs) -> Result<()> {
     }
 
     // Create git tag
-    git::create_tag(
-        &workspace.root,
-        &tag_name,
-        &format!("Release {} version {}", board_name, next_version),
-    )
+    let mut tag_message = format!("Release {} version {}", board_name, next_version);
+    if let Some(notes) = &bom_notes {
+        tag_message.push_str("\n\n");
+        tag_message.push_str(notes);
+    }
+    match &forge {
+        Some(forge) => forge.create_tag(&workspace.root, &tag_name, &tag_message),
+        None => git::create_tag(&workspace.root, &tag_name, &tag_message),
+    }
     .context("Failed to create git tag")?;
     eprintln!("{} Created tag {}", "✓".green(), tag_name.bold());
 
     // Push tag to remote
     if let Some(ref r) = remote {
         eprintln!("Pushing tag to {}...", r.cyan());
-        git::push_tag(&workspace.root, &tag_name, r).context("Failed to push tag")?;
+        match &forge {
+            Some(forge) => forge.push_tag(&workspace.root, &tag_name, r),
+            None => git::push_tag(&workspace.root, &tag_name, r),
+        }
+        .context("Failed to push tag")?;
         eprintln!("{} Pushed {}", "✓".green(), tag_name.bold());
     }
 
+    if let Some(forge) = &forge {
+        eprintln!("Creating {} release...", forge.name());
+        let release = forge.create_release(
+            &tag_name,
+            &format!("{board_name} v{next_version}"),
+            &tag_message,
+        )?;
+        forge.upload_asset(&release, &zip_path)?;
+        if let Some(signature) = &signature {
+            forge.upload_asset(&release, signature)?;
+        }
+        eprintln!(
+            "{} {} release created: {}",
+            "✓".green(),
+            forge.name(),
+            release.url.cyan()
+        );
+    }
+
     Ok(())
 }
 
+/// Compare the new release's BOM with `--bom-diff-against` and require the
+/// changes to be accepted. Returns the markdown delta for the release notes.
+fn review_bom_changes(zip_path: &Path, args: &PublishArgs) -> Result<Option<String>> {
+    let Some(baseline) = &args.bom_diff_against else {
+        return Ok(None);
+    };
+    if args.exclude.contains(&release::ArtifactType::Bom) {
+        bail!("--bom-diff-against needs the release BOM; drop --exclude bom");
+    }
+    let before = release::read_archive_bom(baseline)?;
+    let after = release::read_archive_bom(zip_path)?;
+    let diff = pcb_sch::bom::diff::diff_released_boms(&before, &after);
+
+    let baseline_name = baseline
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| baseline.display().to_string());
+    let notes = diff.to_markdown(&baseline_name);
+    if diff.is_empty() {
+        eprintln!(
+            "{} BOM unchanged since {}",
+            "✓".green(),
+            baseline_name.bold()
+        );
+        return Ok(Some(notes));
+    }
+
+    eprintln!(
+        "{} BOM changed since {}: {}",
+        "!".yellow().bold(),
+        baseline_name.bold(),
+        diff.summary()
+    );
+    for change in &diff.changes {
+        let line = match (&change.before, &change.after) {
+            (Some(before), Some(after)) => format!("{before} → {after}"),
+            (Some(before), None) => format!("{} {before}", "-".red()),
+            (None, Some(after)) => format!("{} {after}", "+".green()),
+            (None, None) => continue,
+        };
+        eprintln!("  {:<8} {line}", change.designator);
+    }
+
+    if args.accept_bom_changes {
+        return Ok(Some(notes));
+    }
+    if !crate::tty::is_interactive() {
+        bail!(
+            "BOM changed since {baseline_name}; review the changes above and pass --accept-bom-changes to publish"
+        );
+    }
+    let accepted = Confirm::new("Publish with these BOM changes?")
+        .with_default(false)
+        .prompt()
+        .unwrap_or(false);
+    if !accepted {
+        bail!("Publish cancelled: BOM changes not accepted");
+    }
+    Ok(Some(notes))
+}
+
+/// Sign the release archive when requested. Returns the signature bundle path.
+fn sign_release(zip_path: &Path, sign: &SignArgs) -> Result<Option<PathBuf>> {
+    if !sign.enabled() {
+        return Ok(None);
+    }
+    eprintln!("Signing release archive...");
+    let bundle = signing::sign_archive(zip_path, sign)?;
+    eprintln!(
+        "{} Signature: {}",
+        "✓".green(),
+        bundle.display().to_string().cyan()
+    );
+    Ok(Some(bundle))
+}
+
 fn release_workspace_name(workspace: &WorkspaceInfo) -> Result<String> {
     if let Some(name) = workspace.config.as_ref().and_then(|config| {
         config
@@ -621,7 +781,10 @@ fn workspace_name_from_repository(repository: &str) -> Option<String> {
         })
 }
 
-fn ensure_board_publish_has_no_workspace_overrides(workspace: &WorkspaceInfo) -> Result<()> {
+fn ensure_board_publish_has_no_workspace_overrides(
+    workspace: &WorkspaceInfo,
+    allow_patches: bool,
+) -> Result<()> {
     if env::var("CI").is_ok() {
         return Ok(());
     }
@@ -639,7 +802,7 @@ fn ensure_board_publish_has_no_workspace_overrides(workspace: &WorkspaceInfo) ->
     let violations = manifests
         .into_iter()
         .flat_map(|(manifest_path, manifest)| {
-            collect_unpublishable_manifest_entries(&manifest_path, manifest)
+            collect_unpublishable_manifest_entries(&manifest_path, manifest, allow_patches)
         })
         .collect::<Vec<_>>();
 
@@ -649,16 +812,20 @@ fn ensure_board_publish_has_no_workspace_overrides(workspace: &WorkspaceInfo) ->
 
     bail!(
         "Board publish is blocked outside CI when workspace manifests contain [patch] directives or branch/rev dependencies.\n\
-         Remove those entries or publish in CI.\n\
+         Remove those entries, pass --allow-patches to keep [patch] entries, or publish in CI.\n\
          Found:\n{}",
         violations.join("\n")
     );
 }
 
-fn collect_unpublishable_manifest_entries(manifest_path: &Path, manifest: &PcbToml) -> Vec<String> {
+fn collect_unpublishable_manifest_entries(
+    manifest_path: &Path,
+    manifest: &PcbToml,
+    allow_patches: bool,
+) -> Vec<String> {
     let mut violations = Vec::new();
 
-    if !manifest.patch.is_empty() {
+    if !allow_patches && !manifest.patch.is_empty() {
         let mut patched_urls: Vec<_> = manifest.patch.keys().cloned().collect();
         patched_urls.sort();
         violations.push(format!(
@@ -739,19 +906,7 @@ fn publish_packages(start_path: &Path, args: &PublishArgs) -> Result<()> {
         );
     }
 
-    // Get dirty packages
-    let directly_dirty: HashSet<String> = workspace
-        .packages
-        .iter()
-        .filter(|(_, p)| p.dirty)
-        .map(|(url, _)| url.clone())
-        .collect();
-
-    // Expand to include packages that depend on dirty packages (transitively)
-    // These need to be published because their pcb.toml will be bumped
-    let dirty_urls = expand_dirty_set(&workspace, &directly_dirty);
-
-    let waves = compute_publish_waves(&workspace, &dirty_urls)?;
+    let waves = dirty_publish_waves(&workspace)?;
 
     if waves.is_empty() {
         println!("{}", "No packages to publish".green());
@@ -843,6 +998,203 @@ fn publish_packages(start_path: &Path, args: &PublishArgs) -> Result<()> {
     Ok(())
 }
 
+/// Publish waves for the dirty packages and everything that depends on them.
+fn dirty_publish_waves(workspace: &WorkspaceInfo) -> Result<Vec<Vec<String>>> {
+    let directly_dirty: HashSet<String> = workspace
+        .packages
+        .iter()
+        .filter(|(_, p)| p.dirty)
+        .map(|(url, _)| url.clone())
+        .collect();
+
+    // Expand to include packages that depend on dirty packages (transitively)
+    // These need to be published because their pcb.toml will be bumped
+    let dirty_urls = expand_dirty_set(workspace, &directly_dirty);
+
+    compute_publish_waves(workspace, &dirty_urls)
+}
+
+/// Files at least this large are called out by `--dry-run`.
+const LARGE_FILE_BYTES: u64 = 1 << 20;
+
+/// What `--dry-run` reports for one package.
+struct PackagePreview {
+    entries: Vec<pcb_canonical::CanonicalEntry>,
+    archive_size: u64,
+    content_hash: String,
+    warnings: Vec<String>,
+}
+
+/// `pcb publish --dry-run`: show what each package to be published contains
+/// and fail if any canonical archive exceeds `[workspace.publish]
+/// max-package-size`.
+fn preview_packages(start_path: &Path) -> Result<()> {
+    let file_provider = DefaultFileProvider::new();
+    let mut workspace = get_workspace_info(&file_provider, start_path)?;
+    pcb_zen::workspace::enrich_git_metadata(&mut workspace);
+
+    if !workspace.errors.is_empty() {
+        for err in &workspace.errors {
+            eprintln!("{}", err.error);
+        }
+        bail!("Found {} invalid pcb.toml file(s)", workspace.errors.len());
+    }
+
+    let waves = dirty_publish_waves(&workspace)?;
+    if waves.is_empty() {
+        println!("{}", "No packages to publish".green());
+        return Ok(());
+    }
+
+    let budget = workspace
+        .workspace_config()
+        .publish
+        .max_package_size_bytes()?;
+    let mut over_budget = Vec::new();
+    for url in waves.iter().flatten() {
+        let pkg = &workspace.packages[url];
+        let preview = preview_package(&pkg.dir(&workspace.root))?;
+
+        println!();
+        println!("{}", url.bold());
+        for entry in &preview.entries {
+            println!("  {:>10}  {}", format_size(entry.size), entry.path);
+        }
+        println!(
+            "  {} files, {} archive, {}",
+            preview.entries.len(),
+            format_size(preview.archive_size),
+            preview.content_hash.dimmed()
+        );
+        for warning in &preview.warnings {
+            println!("  {} {}", "warning:".yellow(), warning);
+        }
+        if let Some(budget) = budget
+            && preview.archive_size > budget
+        {
+            println!(
+                "  {} archive exceeds the {} size budget",
+                "error:".red(),
+                format_size(budget)
+            );
+            over_budget.push(url.clone());
+        }
+    }
+
+    if !over_budget.is_empty() {
+        bail!(
+            "{} package(s) exceed [workspace.publish] max-package-size: {}",
+            over_budget.len(),
+            over_budget.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn preview_package(pkg_dir: &Path) -> Result<PackagePreview> {
+    let entries = pcb_canonical::canonical_tar_entries(pkg_dir, None)?;
+    let mut archive = ByteCounter::default();
+    pcb_canonical::create_canonical_tar(pkg_dir, &mut archive, None)?;
+    let content_hash = pcb_canonical::compute_content_hash_from_dir(pkg_dir)?;
+
+    let mut warnings = Vec::new();
+    for entry in &entries {
+        if entry.size >= LARGE_FILE_BYTES {
+            warnings.push(format!(
+                "{} is large ({})",
+                entry.path,
+                format_size(entry.size)
+            ));
+        }
+        if is_binary_file(&entry.source)? {
+            warnings.push(format!("{} is a binary file", entry.path));
+        }
+    }
+
+    let included: HashSet<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+    for path in tracked_files(pkg_dir) {
+        if !included.contains(path.as_str()) && !is_expected_exclusion(pkg_dir, &path) {
+            warnings.push(format!(
+                "{path} is tracked by git but excluded from the package"
+            ));
+        }
+    }
+
+    Ok(PackagePreview {
+        entries,
+        archive_size: archive.0,
+        content_hash,
+        warnings,
+    })
+}
+
+/// Git-tracked files under `dir`, relative to it. Empty outside a git repo.
+fn tracked_files(dir: &Path) -> Vec<String> {
+    git::run_output_opt(dir, &["ls-files", "-z"])
+        .map(|listing| {
+            listing
+                .split('\0')
+                .filter(|path| !path.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Files the canonical archive leaves out by design: resolver state and
+/// anything inside a nested package.
+fn is_expected_exclusion(pkg_dir: &Path, rel_path: &str) -> bool {
+    let rel_path = Path::new(rel_path);
+    if rel_path.file_name().is_some_and(|name| name == "pcb.sum") {
+        return true;
+    }
+    rel_path
+        .ancestors()
+        .skip(1)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .any(|dir| pkg_dir.join(dir).join("pcb.toml").is_file())
+}
+
+fn is_binary_file(path: &Path) -> Result<bool> {
+    use std::io::Read;
+
+    let mut head = Vec::with_capacity(8000);
+    std::fs::File::open(path)?
+        .take(8000)
+        .read_to_end(&mut head)?;
+    Ok(head.contains(&0))
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} {}", UNITS[unit])
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// `Write` sink that only counts bytes.
+#[derive(Default)]
+struct ByteCounter(u64);
+
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 fn publish_package_waves(
     workspace: &mut WorkspaceInfo,
     bump_map: &BTreeMap<String, ReleaseBump>,
@@ -935,18 +1287,14 @@ fn build_candidates(
             let next_version = compute_next_version(current.as_ref(), bump);
             let tag_name = compute_tag_name(pkg, &next_version, workspace);
 
-            let pkg_dir = pkg.dir(&workspace.root);
-            let content_hash = pcb_canonical::compute_content_hash_from_dir(&pkg_dir)?;
-            let manifest_content = std::fs::read_to_string(pkg_dir.join("pcb.toml"))?;
-            let manifest_hash = pcb_canonical::compute_manifest_hash(&manifest_content);
+            let hashes = pcb_canonical::PackageHashes::compute(&pkg.dir(&workspace.root))?;
 
             Ok((
                 url.clone(),
                 PublishCandidate {
                     next_version,
                     tag_name,
-                    content_hash,
-                    manifest_hash,
+                    hashes,
                 },
             ))
         })
@@ -1071,10 +1419,7 @@ fn compute_tag_name(
 }
 
 fn format_tag_message(url: &str, c: &PublishCandidate) -> String {
-    format!(
-        "{} v{} {}\n{} v{}/pcb.toml {}",
-        url, c.next_version, c.content_hash, url, c.next_version, c.manifest_hash
-    )
+    c.hashes.tag_annotation(url, &c.next_version.to_string())
 }
 
 fn infer_self_bump(
@@ -1498,6 +1843,33 @@ P1 = io(Net)
         assert_eq!(release_workspace_name(&workspace).unwrap(), "XYZ");
     }
 
+    #[test]
+    fn preview_package_lists_files_and_flags_binary_and_large_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let pkg = &dir.path().join("pkg");
+        std::fs::create_dir(pkg).unwrap();
+        std::fs::write(pkg.join("pcb.toml"), "[dependencies]\n").unwrap();
+        std::fs::write(pkg.join("Module.zen"), "x = 1\n").unwrap();
+        std::fs::write(pkg.join("pcb.sum"), "").unwrap();
+        std::fs::write(pkg.join("logo.png"), [0x89, b'P', b'N', b'G', 0, 0]).unwrap();
+        std::fs::write(pkg.join("big.step"), vec![b'a'; LARGE_FILE_BYTES as usize]).unwrap();
+        std::fs::create_dir(pkg.join("nested")).unwrap();
+        std::fs::write(pkg.join("nested/pcb.toml"), "").unwrap();
+
+        let preview = preview_package(pkg).unwrap();
+        let paths: Vec<_> = preview.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["Module.zen", "big.step", "logo.png", "pcb.toml"]);
+        assert!(preview.archive_size > LARGE_FILE_BYTES);
+        assert_eq!(
+            preview.warnings,
+            ["big.step is large (1.0 MiB)", "logo.png is a binary file"]
+        );
+
+        assert!(is_expected_exclusion(pkg, "pcb.sum"));
+        assert!(is_expected_exclusion(pkg, "nested/pcb.toml"));
+        assert!(!is_expected_exclusion(pkg, ".gitattributes"));
+    }
+
     #[test]
     fn release_workspace_name_handles_repository_url_forms() {
         assert_eq!(
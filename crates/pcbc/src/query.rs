@@ -0,0 +1,76 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::build::create_diagnostics_passes;
+use crate::config_input::{CONFIG_ARG_HELP, parse_config_overrides};
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use pcb_sch::query::{Query, to_csv};
+
+#[derive(ValueEnum, Debug, Clone, Default)]
+pub enum QueryFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+impl std::fmt::Display for QueryFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryFormat::Json => write!(f, "json"),
+            QueryFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+#[command(about = "Query components, modules, and nets of a design")]
+pub struct QueryArgs {
+    /// .zen file to process
+    #[arg(value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    pub file: PathBuf,
+
+    /// Selector, e.g. `components[mpn~="STM32*"].footprint` or `nets[kind=power].name`
+    #[arg(value_name = "QUERY")]
+    pub query: String,
+
+    #[arg(long = "config", value_name = "KEY=VALUE", help = CONFIG_ARG_HELP)]
+    pub config: Vec<String>,
+
+    /// Output format
+    #[arg(short, long, default_value_t = QueryFormat::Json)]
+    pub format: QueryFormat,
+
+    /// Disable network access (offline mode) - only use vendored dependencies
+    #[arg(long = "offline")]
+    pub offline: bool,
+}
+
+pub fn execute(args: QueryArgs) -> Result<()> {
+    // Reject malformed selectors before spending time on evaluation.
+    let query: Query = args.query.parse()?;
+
+    crate::file_walker::require_zen_file(&args.file)?;
+    let config_inputs = parse_config_overrides(&args.config)?;
+    let resolution_result = crate::resolve::resolve(Some(&args.file), args.offline)?;
+
+    let file_name = args.file.file_name().unwrap().to_string_lossy();
+    let eval_output = pcb_zen::eval(&args.file, resolution_result, config_inputs)
+        .output_result()
+        .map_err(|mut diagnostics| {
+            diagnostics.apply_passes(&create_diagnostics_passes(&[], &[]));
+            anyhow::anyhow!("Failed to build {} - cannot run query", file_name)
+        })?;
+    let mut schematic = eval_output
+        .to_schematic()
+        .context("Failed to convert to schematic")?;
+    schematic.annotate_net_stats();
+
+    let results = query.evaluate(&schematic);
+    let mut writer = io::stdout().lock();
+    match args.format {
+        QueryFormat::Json => writeln!(writer, "{}", serde_json::to_string_pretty(&results)?)?,
+        QueryFormat::Csv => write!(writer, "{}", to_csv(&results, query.field.as_deref())?)?,
+    }
+    Ok(())
+}
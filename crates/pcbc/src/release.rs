@@ -1,20 +1,24 @@
 use anyhow::{Context, Result};
-use clap::ValueEnum;
+use clap::{Args, Subcommand, ValueEnum};
 use log::{debug, warn};
 use pcb_kicad::{KiCadCliBuilder, ensure_board_compatible_with_installed_kicad};
 use pcb_layout::utils as layout_utils;
 use pcb_ui::{Colorize, Spinner, Style, StyledText};
 
 use crate::bom::generate_bom_with_fallback;
-use crate::bundle::{self, MetadataInput, SourceBundlePlan};
+use crate::bundle::{self, EvalSettings, MetadataInput, SourceBundlePlan};
+use crate::session::DesignSession;
+use crate::signing::{self, Signer};
 use pcb_zen::WorkspaceInfo;
 use pcb_zen::workspace::WorkspaceInfoExt;
 use pcb_zen_core::EvalOutput;
-use pcb_zen_core::resolution::ResolutionResult;
+use pcb_zen_core::cancel::{CancellationToken, Cancelled};
 
 use inquire::Confirm;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::io::{BufWriter, Write};
+use std::io::{BufWriter, Read, Write};
+use std::sync::OnceLock;
 use std::time::Instant;
 
 use chrono::Utc;
@@ -29,6 +33,8 @@ use pcb_zen::git;
 pub enum ArtifactType {
     Drc,
     Bom,
+    Datasheets,
+    Pinouts,
     Gerbers,
     Cpl,
     Assembly,
@@ -44,6 +50,8 @@ impl ArtifactType {
         match self {
             ArtifactType::Drc => "Running KiCad DRC checks",
             ArtifactType::Bom => "Generating design BOM",
+            ArtifactType::Datasheets => "Collecting datasheets",
+            ArtifactType::Pinouts => "Generating connector pinouts",
             ArtifactType::Gerbers => "Generating gerber files",
             ArtifactType::Cpl => "Generating pick-and-place file",
             ArtifactType::Assembly => "Generating assembly drawings",
@@ -59,6 +67,8 @@ impl ArtifactType {
         match self {
             ArtifactType::Drc => run_kicad_drc,
             ArtifactType::Bom => generate_design_bom,
+            ArtifactType::Datasheets => |info, _| collect_datasheets(info),
+            ArtifactType::Pinouts => generate_connector_pinouts,
             ArtifactType::Gerbers => generate_gerbers,
             ArtifactType::Cpl => generate_cpl,
             ArtifactType::Assembly => generate_assembly_drawings,
@@ -73,7 +83,9 @@ impl ArtifactType {
     fn requires_layout(&self) -> bool {
         match self {
             ArtifactType::Bom => false, // BOM is generated from schematic
-            _ => true,                  // All other artifacts require KiCad layout files
+            ArtifactType::Datasheets => false,
+            ArtifactType::Pinouts => false,
+            _ => true, // All other artifacts require KiCad layout files
         }
     }
 }
@@ -98,22 +110,28 @@ struct ReleaseInfo {
     git_hash: String,
     staging_dir: PathBuf,
     layout: Option<ReleaseLayout>,
-    schematic: pcb_sch::Schematic,
+    /// Design evaluated from the workspace sources, shared by every release stage.
+    session: DesignSession,
+    /// Schematic built from the staged sources by `validate_build`.
+    staged_schematic: OnceLock<pcb_sch::Schematic>,
+    /// Git describe and source hash stamped into the layout and IPC-2581 file.
+    stamp: pcb_sch::stamp::Stamp,
     output_dir: PathBuf,
     output_name: String,
     suppress: Vec<String>,
-    resolution: ResolutionResult,
     root_package_url: Option<String>,
     allow_errors: bool,
+    /// Checked between release tasks; Ctrl-C stops the release at the next task.
+    cancel: CancellationToken,
 }
 
 impl ReleaseInfo {
     fn workspace_info(&self) -> &pcb_zen::WorkspaceInfo {
-        &self.resolution.workspace_info
+        &self.session.resolution().workspace_info
     }
 
     fn workspace_root(&self) -> &Path {
-        &self.resolution.workspace_info.root
+        &self.session.resolution().workspace_info.root
     }
 
     fn has_layout(&self) -> bool {
@@ -150,6 +168,8 @@ const BASE_TASKS: &[(&str, TaskFn)] = &[
 const MANUFACTURING_ARTIFACTS: &[ArtifactType] = &[
     ArtifactType::Drc, // Run DRC checks first, before generating any manufacturing files
     ArtifactType::Bom,
+    ArtifactType::Datasheets,
+    ArtifactType::Pinouts,
     ArtifactType::Gerbers,
     ArtifactType::Cpl,
     ArtifactType::Assembly,
@@ -230,13 +250,32 @@ fn confirm_continue_on_error(spinner: Option<&Spinner>, allow_errors: bool, mess
     }
 }
 
+/// A cancelled release leaves nothing behind: drop the half-populated staging
+/// directory and any partially written archive. Other failures keep them for
+/// inspection.
+fn discard_if_cancelled(result: Result<()>, staging_dir: &Path, zip_path: &Path) -> Result<()> {
+    if let Err(e) = &result
+        && Cancelled::find(e).is_some()
+    {
+        let _ = bundle::remove_dir_all_with_permissions(staging_dir);
+        let _ = fs::remove_file(zip_path);
+    }
+    result
+}
+
 /// Execute a list of tasks with proper error handling and UI feedback
 fn execute_tasks(info: &ReleaseInfo, tasks: &[(&str, TaskFn)], start_time: Instant) -> Result<()> {
     for (name, task) in tasks {
+        let stage = format!("release task '{name}'");
+        info.cancel.check(&*stage)?;
         let spinner = Spinner::builder(*name).start();
 
         let task_start = Instant::now();
-        task(info, &spinner)?;
+        let result = task(info, &spinner);
+        // Ctrl-C also interrupts tools spawned by the task (e.g. kicad-cli);
+        // report that failure as the cancellation.
+        info.cancel.check(stage)?;
+        result?;
         let task_duration = task_start.elapsed().as_secs_f64();
         let cumulative_duration = start_time.elapsed().as_secs_f64();
 
@@ -251,6 +290,183 @@ fn execute_tasks(info: &ReleaseInfo, tasks: &[(&str, TaskFn)], start_time: Insta
     Ok(())
 }
 
+#[derive(Args)]
+pub struct ReleaseArgs {
+    #[command(subcommand)]
+    command: ReleaseCommand,
+}
+
+#[derive(Subcommand)]
+enum ReleaseCommand {
+    /// Re-evaluate a release archive and check that it reproduces its netlist
+    Verify {
+        /// Release archive (.zip) created by `pcb publish` or `pcb preview`
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        archive: PathBuf,
+
+        /// Also check the archive's signature, read from `<archive>.sigstore.json`
+        /// unless a bundle path is given
+        #[arg(long, value_name = "BUNDLE", num_args = 0..=1, require_equals = true)]
+        signature: Option<Option<PathBuf>>,
+
+        /// Public key (or KMS URI) the archive must be signed with
+        #[arg(
+            long,
+            value_name = "KEY",
+            requires = "signature",
+            conflicts_with = "certificate_identity"
+        )]
+        key: Option<String>,
+
+        /// Signer identity a keyless signature must carry, e.g. an email or CI workflow URL
+        #[arg(long, value_name = "IDENTITY", requires_all = ["signature", "certificate_oidc_issuer"])]
+        certificate_identity: Option<String>,
+
+        /// OIDC issuer a keyless signature must carry, e.g. https://token.actions.githubusercontent.com
+        #[arg(long, value_name = "URL", requires = "certificate_identity")]
+        certificate_oidc_issuer: Option<String>,
+    },
+}
+
+pub fn execute(args: ReleaseArgs) -> Result<()> {
+    match args.command {
+        ReleaseCommand::Verify {
+            archive,
+            signature,
+            key,
+            certificate_identity,
+            certificate_oidc_issuer,
+        } => {
+            if let Some(bundle) = signature {
+                let signer = match (key, certificate_identity, certificate_oidc_issuer) {
+                    (Some(key), _, _) => Signer::Key(key),
+                    (None, Some(identity), Some(issuer)) => Signer::Identity { identity, issuer },
+                    _ => anyhow::bail!(
+                        "--signature needs --key, or --certificate-identity with --certificate-oidc-issuer"
+                    ),
+                };
+                let bundle = bundle.unwrap_or_else(|| signing::bundle_path(&archive));
+                signing::verify_archive(&archive, &bundle, &signer)?;
+                eprintln!(
+                    "{} {}",
+                    "✓".green().bold(),
+                    format!("Signature verified ({})", bundle.display()).bold()
+                );
+            }
+            verify_release(&archive)
+        }
+    }
+}
+
+/// Read `bom/design_bom.json` from a release archive.
+pub(crate) fn read_archive_bom(
+    archive_path: &Path,
+) -> Result<Vec<pcb_sch::bom::diff::ReleasedBomLine>> {
+    let archive = fs::File::open(archive_path)
+        .with_context(|| format!("Failed to open {}", archive_path.display()))?;
+    let mut archive = zip::ZipArchive::new(archive)
+        .with_context(|| format!("{} is not a release archive", archive_path.display()))?;
+    let mut json = String::new();
+    archive
+        .by_name("bom/design_bom.json")
+        .with_context(|| format!("{} has no bom/design_bom.json", archive_path.display()))?
+        .read_to_string(&mut json)?;
+    pcb_sch::bom::diff::parse_released_bom(&json)
+        .with_context(|| format!("Failed to parse BOM in {}", archive_path.display()))
+}
+
+/// Extract a release archive, rebuild its staged sources the same way
+/// `validate_build` did, and compare the netlist hash with the recorded one.
+fn verify_release(archive_path: &Path) -> Result<()> {
+    let extract_dir = tempfile::tempdir()?;
+    let archive = fs::File::open(archive_path)
+        .with_context(|| format!("Failed to open {}", archive_path.display()))?;
+    zip::ZipArchive::new(archive)?
+        .extract(extract_dir.path())
+        .context("Failed to extract release archive")?;
+    // Resolution works on canonical paths, so strip the same prefix from the netlist.
+    let root = &extract_dir.path().canonicalize()?;
+
+    let metadata: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(root.join("metadata.json"))
+            .context("Release archive has no metadata.json")?,
+    )
+    .context("Failed to parse metadata.json")?;
+    let zen_file = metadata["release"]["zen_file"]
+        .as_str()
+        .context("metadata.json is missing release.zen_file")?;
+    let expected = match metadata["build"]["netlist_sha256"].as_str() {
+        Some(hash) => hash.to_string(),
+        None => bundle::netlist_digest(
+            &fs::read_to_string(root.join("netlist.json"))
+                .context("Release archive has no netlist.json")?,
+            root,
+        ),
+    };
+
+    for (key, label, current) in [
+        (
+            "cli_version",
+            "pcb version",
+            env!("CARGO_PKG_VERSION").to_string(),
+        ),
+        (
+            "cli_git_sha",
+            "pcb git SHA",
+            option_env!("PCB_GIT_SHA").unwrap_or("unknown").to_string(),
+        ),
+        (
+            "kicad_version",
+            "KiCad version",
+            pcb_kicad::get_kicad_version().unwrap_or_else(|_| "unknown".to_string()),
+        ),
+        ("platform", "platform", std::env::consts::OS.to_string()),
+    ] {
+        if let Some(recorded) = metadata["system"][key].as_str()
+            && recorded != current
+        {
+            eprintln!(
+                "{} {label} differs: release used {recorded}, running {current}",
+                "Warning:".yellow()
+            );
+        }
+    }
+
+    let spinner = Spinner::builder("Re-evaluating release sources").start();
+    let zen_path = root.join("src").join(zen_file);
+    let resolution = crate::resolve::resolve(Some(&zen_path), true)?;
+    let (mut has_errors, mut has_warnings) = (false, false);
+    let secrets = crate::config_input::load_secret_inputs(None)?;
+    let schematic = spinner.suspend(|| {
+        crate::build::BuildEvalState::new(resolution)
+            .with_secret_inputs(secrets)
+            .build(
+                &zen_path,
+                Default::default(),
+                crate::build::create_diagnostics_passes(&[], &[]),
+                false,
+                &mut has_errors,
+                &mut has_warnings,
+            )
+            .schematic
+    });
+    spinner.finish();
+    let schematic = schematic.context("Failed to build release sources")?;
+    let actual = bundle::netlist_digest(&schematic.to_json()?, root);
+
+    if actual != expected {
+        eprintln!("    expected netlist sha256 {}", expected.dimmed());
+        eprintln!("    actual   netlist sha256 {}", actual.dimmed());
+        anyhow::bail!("Release {} does not reproduce", archive_path.display());
+    }
+    eprintln!(
+        "{} {}",
+        "✓".green().bold(),
+        format!("Release reproduces netlist (sha256 {})", &actual[..12]).bold()
+    );
+    Ok(())
+}
+
 /// Build a release for a board file. Used by `pcb publish --board`.
 /// If version is provided (e.g. "v1.2.3"), uses that. Otherwise uses git commit hash.
 /// Takes pre-resolved workspace info to avoid duplicate resolution.
@@ -272,12 +488,14 @@ pub fn build_board_release(
         let package_url = workspace.package_url_for_zen(&zen_path);
 
         info_spinner.set_message("Resolving dependencies");
-        let resolution = crate::resolve::resolve(Some(&zen_path), false)?;
+        // Secrets come from the environment so CI can provide them without
+        // them appearing on the command line or in the release.
+        let secrets = crate::config_input::load_secret_inputs(None)?;
+        let session = DesignSession::resolve(&zen_path, false)?.with_secret_inputs(secrets);
         info_spinner.set_message("Evaluating zen file");
 
-        // Evaluate the zen file (still needed for schematic)
-        // Pass resolution so Module() paths resolve correctly
-        let eval_result = pcb_zen::eval(&zen_path, resolution.clone(), Default::default());
+        // Evaluate once; later stages reuse the cached output and schematic.
+        let eval_result = session.eval();
 
         let has_eval_errors = eval_result.diagnostics.has_errors();
         if has_eval_errors || eval_result.output.is_none() {
@@ -307,9 +525,9 @@ pub fn build_board_release(
 
         info_spinner.finish();
 
-        let eval_output = eval_result.output.unwrap();
+        let eval_output = eval_result.output.as_ref().unwrap();
 
-        let workspace_root = &resolution.workspace_info.root;
+        let workspace_root = &session.resolution().workspace_info.root;
 
         // Get git hash for metadata
         let git_hash = git::rev_parse_head(workspace_root).unwrap_or_else(|| "unknown".to_string());
@@ -338,7 +556,7 @@ pub fn build_board_release(
         }
         fs::create_dir_all(&staging_dir)?;
 
-        let layout = match discover_layout_from_output(&eval_output)? {
+        let layout = match discover_layout_from_output(eval_output)? {
             Some(discovered) => match discovered
                 .kicad_files
                 .kicad_pro
@@ -358,7 +576,9 @@ pub fn build_board_release(
             None => None,
         };
 
-        let schematic = eval_output.to_schematic()?;
+        // Convert up front so schematic errors surface before any staging work.
+        session.schematic()?;
+        let stamp = crate::stamp::workspace_stamp(&session.resolution().workspace_info)?;
 
         let info = ReleaseInfo {
             zen_path,
@@ -367,13 +587,15 @@ pub fn build_board_release(
             git_hash,
             staging_dir,
             layout,
-            schematic,
+            session,
+            staged_schematic: OnceLock::new(),
+            stamp,
             output_dir,
             output_name,
             suppress,
-            resolution,
             root_package_url: package_url,
             allow_errors,
+            cancel: crate::interrupt::ctrl_c_token()?,
         };
 
         let elapsed = start_time.elapsed().as_secs_f64();
@@ -395,19 +617,15 @@ pub fn build_board_release(
         ensure_board_compatible_with_installed_kicad(&kicad_pcb_path)?;
     }
 
-    // Execute base tasks
-    execute_tasks(&release_info, BASE_TASKS, start_time)?;
-
-    // Execute manufacturing tasks
-    let manufacturing_tasks = get_manufacturing_tasks(&exclude, release_info.has_layout());
-    execute_tasks(&release_info, &manufacturing_tasks, start_time)?;
-
-    // Execute finalization tasks
-    execute_tasks(&release_info, FINALIZATION_TASKS, start_time)?;
-
     // Calculate archive path
     let zip_path = archive_zip_path(&release_info);
 
+    let manufacturing_tasks = get_manufacturing_tasks(&exclude, release_info.has_layout());
+    let executed = execute_tasks(&release_info, BASE_TASKS, start_time)
+        .and_then(|()| execute_tasks(&release_info, &manufacturing_tasks, start_time))
+        .and_then(|()| execute_tasks(&release_info, FINALIZATION_TASKS, start_time));
+    discard_if_cancelled(executed, &release_info.staging_dir, &zip_path)?;
+
     eprintln!(
         "{} {}",
         "✓".green(),
@@ -525,7 +743,7 @@ pub(crate) fn discover_layout_from_output(output: &EvalOutput) -> Result<Option<
 /// Copy source files and vendor dependencies
 fn copy_sources(info: &ReleaseInfo, _spinner: &Spinner) -> Result<()> {
     bundle::stage_source_bundle(&SourceBundlePlan {
-        resolution: &info.resolution,
+        resolution: info.session.resolution(),
         root_package_url: info.root_package_url.as_deref(),
         staged_src: &info.staging_dir.join("src"),
     })
@@ -533,52 +751,13 @@ fn copy_sources(info: &ReleaseInfo, _spinner: &Spinner) -> Result<()> {
 
 fn update_kicad_pro_release_variables(
     kicad_pro_path: &Path,
-    version: &str,
-    git_hash: &str,
+    variables: &[(&str, &str)],
 ) -> Result<()> {
-    // Read the existing .kicad_pro file
-    let content = fs::read_to_string(kicad_pro_path).with_context(|| {
-        format!(
-            "Failed to read .kicad_pro file: {}",
-            kicad_pro_path.display()
-        )
-    })?;
-
-    // Parse as JSON
-    let mut project: serde_json::Value = serde_json::from_str(&content).with_context(|| {
-        format!(
-            "Failed to parse .kicad_pro file as JSON: {}",
-            kicad_pro_path.display()
-        )
-    })?;
-
-    let project = project
-        .as_object_mut()
-        .context("KiCad project file root must be a JSON object")?;
-    let text_vars = project
-        .entry("text_variables")
-        .or_insert_with(|| serde_json::json!({}));
-    if !text_vars.is_object() {
-        *text_vars = serde_json::json!({});
+    let mut project = pcb_kicad::project::KicadProject::load(kicad_pro_path)?;
+    for (name, value) in variables {
+        project.set_text_variable(name, value);
     }
-    let text_vars = text_vars.as_object_mut().unwrap();
-
-    for (key, value) in [("PCB_VERSION", version), ("PCB_GIT_HASH", git_hash)] {
-        text_vars.insert(
-            key.to_string(),
-            serde_json::Value::String(value.to_string()),
-        );
-    }
-
-    // Write back to file with pretty formatting
-    let mut updated_content = serde_json::to_string_pretty(&project)?;
-    updated_content.push('\n');
-    fs::write(kicad_pro_path, updated_content).with_context(|| {
-        format!(
-            "Failed to write updated .kicad_pro file: {}",
-            kicad_pro_path.display()
-        )
-    })?;
+    project.save()?;
 
     debug!("Updated text variables in: {}", kicad_pro_path.display());
 
@@ -587,8 +766,7 @@ fn update_kicad_pro_release_variables(
 
 fn update_kicad_pcb_release_variables(
     kicad_pcb_path: &Path,
-    version: &str,
-    git_hash: &str,
+    variables: &[(&str, &str)],
 ) -> Result<()> {
     let content = fs::read_to_string(kicad_pcb_path).with_context(|| {
         format!(
@@ -603,7 +781,7 @@ fn update_kicad_pcb_release_variables(
 
     let mut patches = pcb_sexpr::PatchSet::new();
     let mut inserted = String::new();
-    for (key, value) in [("PCB_VERSION", version), ("PCB_GIT_HASH", git_hash)] {
+    for &(key, value) in variables {
         let value_node = root_items.iter().find_map(|item| {
             let items = item.as_list()?;
             (items.first().and_then(|item| item.as_sym()) == Some("property")
@@ -668,9 +846,14 @@ fn substitute_variables(info: &ReleaseInfo, _spinner: &Spinner) -> Result<()> {
     // Use short hash (7 chars) for variable substitution
     let short_hash = &info.git_hash[..7.min(info.git_hash.len())];
 
-    let kicad_pro_path = kicad_files.kicad_pro.clone();
-    update_kicad_pro_release_variables(&kicad_pro_path, &info.version, short_hash)?;
-    update_kicad_pcb_release_variables(&kicad_files.kicad_pcb(), &info.version, short_hash)?;
+    let variables = [
+        ("PCB_VERSION", info.version.as_str()),
+        ("PCB_GIT_HASH", short_hash),
+        ("PCB_GIT_DESCRIBE", info.stamp.git_describe.as_str()),
+        ("PCB_SOURCE_HASH", info.stamp.short_source_hash()),
+    ];
+    update_kicad_pro_release_variables(&kicad_files.kicad_pro, &variables)?;
+    update_kicad_pcb_release_variables(&kicad_files.kicad_pcb(), &variables)?;
     Ok(())
 }
 
@@ -703,15 +886,17 @@ fn validate_build(info: &ReleaseInfo, spinner: &Spinner) -> Result<()> {
             zen_file_rel.display().to_string(),
         )));
 
-        let schematic = crate::build::build(
-            &staged_zen_path,
-            Default::default(),
-            passes,
-            false, // don't deny warnings - we'll prompt user instead
-            &mut has_errors,
-            &mut has_warnings,
-            staged_resolution,
-        );
+        let schematic = crate::build::BuildEvalState::new(staged_resolution)
+            .with_secret_inputs(info.session.secret_inputs().clone())
+            .build(
+                &staged_zen_path,
+                Default::default(),
+                passes,
+                false, // don't deny warnings - we'll prompt user instead
+                &mut has_errors,
+                &mut has_warnings,
+            )
+            .schematic;
         (has_errors, has_warnings, schematic)
     });
 
@@ -743,11 +928,11 @@ fn validate_build(info: &ReleaseInfo, spinner: &Spinner) -> Result<()> {
 
     // Write fp-lib-table with correct vendor/ paths to staged layout directory
     // The staged schematic has footprint paths pointing to src/vendor/ instead of .pcb/cache
-    if let Some(ref sch) = schematic {
+    if let Some(sch) = schematic {
         if let Some(staged_layout_dir) = info.staged_layout_dir()
             && staged_layout_dir.exists()
         {
-            pcb_layout::utils::write_footprint_library_table(&staged_layout_dir, sch)
+            pcb_layout::utils::write_footprint_library_table(&staged_layout_dir, &sch)
                 .context("Failed to write fp-lib-table for staged layout")?;
         }
 
@@ -755,6 +940,9 @@ fn validate_build(info: &ReleaseInfo, spinner: &Spinner) -> Result<()> {
         let netlist_json = sch.to_json().context("Failed to serialize netlist")?;
         fs::write(info.staging_dir.join("netlist.json"), &netlist_json)
             .context("Failed to write netlist.json")?;
+
+        // Later stages (DRC) reuse the staged schematic instead of re-reading netlist.json.
+        let _ = info.staged_schematic.set(sch);
     }
 
     Ok(())
@@ -763,7 +951,7 @@ fn validate_build(info: &ReleaseInfo, spinner: &Spinner) -> Result<()> {
 /// Generate design BOM JSON file (with optional KiCad fallback if layout exists)
 fn generate_design_bom(info: &ReleaseInfo, _spinner: &Spinner) -> Result<()> {
     // Generate BOM entries from the schematic
-    let bom = info.schematic.bom();
+    let bom = info.session.schematic()?.bom();
 
     // Create bom directory in staging
     let bom_dir = info.staging_dir.join("bom");
@@ -774,7 +962,8 @@ fn generate_design_bom(info: &ReleaseInfo, _spinner: &Spinner) -> Result<()> {
         .layout
         .as_ref()
         .map(|l| info.workspace_root().join(l.layout_dir_rel()));
-    let final_bom = generate_bom_with_fallback(bom, layout_path.as_deref())?;
+    let mut final_bom = generate_bom_with_fallback(bom, layout_path.as_deref())?;
+    crate::bom::apply_bom_rules(&mut final_bom, &info.zen_path)?;
 
     // Write design BOM as JSON
     let bom_file = bom_dir.join("design_bom.json");
@@ -784,6 +973,99 @@ fn generate_design_bom(info: &ReleaseInfo, _spinner: &Spinner) -> Result<()> {
     Ok(())
 }
 
+/// Write connector pinout tables and drawings into `pinouts/`.
+fn generate_connector_pinouts(info: &ReleaseInfo, _spinner: &Spinner) -> Result<()> {
+    crate::pinout::write_connector_pinouts(
+        info.session.schematic()?,
+        &info.staging_dir.join("pinouts"),
+    )?;
+    Ok(())
+}
+
+/// Copy every component datasheet into `datasheets/` with a JSON manifest.
+///
+/// Nothing is downloaded: remote datasheets are taken from the workspace
+/// cache. A datasheet that is not cached, or a local file that does not exist,
+/// is reported and recorded in the manifest with `"missing": true`.
+fn collect_datasheets(info: &ReleaseInfo) -> Result<()> {
+    let schematic = info.session.schematic()?;
+
+    let mut designators_by_source: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (refdes, datasheet) in crate::datasheet::component_datasheets(schematic) {
+        designators_by_source
+            .entry(datasheet)
+            .or_default()
+            .push(refdes);
+    }
+    if designators_by_source.is_empty() {
+        return Ok(());
+    }
+
+    let out_dir = info.staging_dir.join(crate::datasheet::DATASHEETS_DIR);
+    fs::create_dir_all(&out_dir)?;
+
+    let mut manifest = Vec::new();
+    let mut used_names = HashSet::new();
+    for (source, designators) in designators_by_source {
+        // `pcb open --datasheet` fills the cache.
+        let local = match crate::datasheet::local_datasheet(
+            schematic,
+            info.workspace_root(),
+            &source,
+            true,
+        ) {
+            Ok(path) => path,
+            Err(e) => {
+                warn!(
+                    "Datasheet for {} is missing from the release: {e:#}",
+                    designators.join(", ")
+                );
+                manifest.push(serde_json::json!({
+                    "source": source,
+                    "missing": true,
+                    "designators": designators,
+                }));
+                continue;
+            }
+        };
+        let bytes = fs::read(&local)
+            .with_context(|| format!("Failed to read datasheet {}", local.display()))?;
+        let sha256 = pcb_zen::provenance::sha256_hex(&bytes);
+
+        let stem = local
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "datasheet".to_owned());
+        let mut file_name = format!("{stem}.pdf");
+        if !used_names.insert(file_name.clone()) {
+            file_name = format!("{stem}-{}.pdf", &sha256[..12]);
+            used_names.insert(file_name.clone());
+        }
+        fs::write(out_dir.join(&file_name), &bytes)?;
+
+        manifest.push(serde_json::json!({
+            "file": file_name,
+            "source": source,
+            "sha256": sha256,
+            "designators": designators,
+        }));
+    }
+
+    fs::write(
+        out_dir.join("manifest.json"),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    Ok(())
+}
+
+/// `[[board.revisions]]` of the board being released, oldest first.
+fn board_revisions(info: &ReleaseInfo) -> Vec<pcb_sch::revision::Revision> {
+    info.workspace_info()
+        .board_config_for_zen(&info.zen_path)
+        .map(|board| board.revisions)
+        .unwrap_or_default()
+}
+
 /// Write release metadata to JSON file
 fn write_metadata(info: &ReleaseInfo, _spinner: &Spinner) -> Result<()> {
     let board_description = info
@@ -791,6 +1073,13 @@ fn write_metadata(info: &ReleaseInfo, _spinner: &Spinner) -> Result<()> {
         .board_info_for_zen(&info.zen_path)
         .map(|b| b.description)
         .filter(|d: &String| !d.is_empty());
+    let layout_stats = info
+        .staged_pcb_path()
+        .filter(|path| path.exists())
+        .map(|path| pcb_layout::layout_stats::read_layout_stats(&path))
+        .transpose()?;
+
+    let revisions = board_revisions(info);
 
     bundle::write_metadata_json(&MetadataInput {
         name: &info.board_name,
@@ -800,9 +1089,19 @@ fn write_metadata(info: &ReleaseInfo, _spinner: &Spinner) -> Result<()> {
         staging_dir: &info.staging_dir,
         zen_path: &info.zen_path,
         layout_path: info.layout.as_ref().map(|layout| layout.layout_dir_rel()),
+        layout_stats: layout_stats.as_ref(),
+        stamp: &info.stamp,
         description: board_description.as_deref(),
+        revisions: &revisions,
         include_kicad_version: true,
         bom_strict: info.workspace_info().workspace_config().bom.strict,
+        workspace_info: info.workspace_info(),
+        // netlist.json comes from `validate_build`, an offline build of the
+        // staged sources and their vendored dependencies.
+        eval: EvalSettings {
+            offline: true,
+            vendored: true,
+        },
     })
 }
 
@@ -1112,6 +1411,21 @@ fn generate_ipc2581(info: &ReleaseInfo, _spinner: &Spinner) -> Result<()> {
         .run()
         .context("Failed to generate IPC-2581 file")?;
 
+    // Record the sources the board was generated from in the file's history,
+    // and label it with the board revision when pcb.toml declares one.
+    let ipc_xml = fs::read_to_string(&ipc2581_path).context("Failed to read IPC-2581 file")?;
+    let revision = board_revisions(info).last().cloned();
+    let comment = match &revision {
+        Some(revision) => format!("{} generated from {}", revision.label(), info.stamp.label()),
+        None => format!("Generated from {}", info.stamp.label()),
+    };
+    let mut ipc_xml = pcb_ipc2581_tools::utils::history::append_file_revision(&ipc_xml, &comment)?;
+    if let Some(revision) = &revision {
+        ipc_xml =
+            pcb_ipc2581_tools::utils::history::label_file_revision(&ipc_xml, &revision.revision)?;
+    }
+    fs::write(&ipc2581_path, ipc_xml).context("Failed to write IPC-2581 file")?;
+
     // Generate HTML export from the IPC-2581 XML file (silently, without printing)
     let ipc2581_html_path = manufacturing_dir.join("ipc2581.html");
     let ipc_content = pcb_ipc2581_tools::utils::file::load_ipc_file(&ipc2581_path)
@@ -1212,18 +1526,28 @@ fn generate_vrml_model(info: &ReleaseInfo, _spinner: &Spinner) -> Result<()> {
     Ok(())
 }
 
-/// Run KiCad DRC checks on the layout file
-fn run_kicad_drc(info: &ReleaseInfo, spinner: &Spinner) -> Result<()> {
-    let mut diagnostics = pcb_zen_core::Diagnostics::default();
+/// Schematic built from the staged sources, falling back to the staged netlist.json.
+fn staged_schematic(info: &ReleaseInfo) -> Result<&pcb_sch::Schematic> {
+    if let Some(schematic) = info.staged_schematic.get() {
+        return Ok(schematic);
+    }
+
     let netlist_json_path = info.staging_dir.join("netlist.json");
     let netlist_json = fs::read_to_string(&netlist_json_path)
         .with_context(|| format!("Failed to read {}", netlist_json_path.display()))?;
-    let staged_schematic: pcb_sch::Schematic = serde_json::from_str(&netlist_json)
+    let schematic: pcb_sch::Schematic = serde_json::from_str(&netlist_json)
         .with_context(|| format!("Failed to parse {}", netlist_json_path.display()))?;
+    Ok(info.staged_schematic.get_or_init(|| schematic))
+}
+
+/// Run KiCad DRC checks on the layout file
+fn run_kicad_drc(info: &ReleaseInfo, spinner: &Spinner) -> Result<()> {
+    let mut diagnostics = pcb_zen_core::Diagnostics::default();
+    let staged_schematic = staged_schematic(info)?;
 
     // Collect diagnostics from layout sync check (run on staged sources/layout).
     let Some(layout_result) =
-        pcb_layout::process_layout(&staged_schematic, false, true, &mut diagnostics)?
+        pcb_layout::process_layout(staged_schematic, false, true, &mut diagnostics)?
     else {
         anyhow::bail!("No layout directory for DRC checks");
     };
@@ -1275,6 +1599,37 @@ fn run_kicad_drc(info: &ReleaseInfo, spinner: &Spinner) -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn cancelled_release_removes_staging_dir_and_archive() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let staging_dir = temp_dir.path().join("Board-1.0.0");
+        let zip_path = temp_dir.path().join("Board-1.0.0.zip");
+        let populate = || -> Result<()> {
+            fs::create_dir_all(staging_dir.join("manufacturing"))?;
+            fs::write(staging_dir.join("manufacturing/gerbers.zip"), "partial")?;
+            fs::write(&zip_path, "partial")?;
+            Ok(())
+        };
+
+        populate()?;
+        let failed = discard_if_cancelled(
+            Err(anyhow::anyhow!("kicad-cli failed")),
+            &staging_dir,
+            &zip_path,
+        );
+        assert!(failed.is_err());
+        assert!(staging_dir.exists() && zip_path.exists());
+
+        let cancelled = Cancelled {
+            stage: "release task 'Gerbers'".to_string(),
+        };
+        let err = discard_if_cancelled(Err(cancelled.into()), &staging_dir, &zip_path).unwrap_err();
+        assert!(Cancelled::find(&err).is_some());
+        assert!(!staging_dir.exists());
+        assert!(!zip_path.exists());
+        Ok(())
+    }
+
     #[test]
     fn update_kicad_pro_release_variables_adds_missing_release_variables() -> Result<()> {
         let temp_dir = tempfile::tempdir()?;
@@ -1289,7 +1644,10 @@ mod tests {
 }"#,
         )?;
 
-        update_kicad_pro_release_variables(&kicad_pro_path, "1.2.3", "abcdef0")?;
+        update_kicad_pro_release_variables(
+            &kicad_pro_path,
+            &[("PCB_VERSION", "1.2.3"), ("PCB_GIT_HASH", "abcdef0")],
+        )?;
 
         let content = fs::read_to_string(&kicad_pro_path)?;
         assert!(
@@ -1334,7 +1692,10 @@ mod tests {
 "#,
         )?;
 
-        update_kicad_pcb_release_variables(&kicad_pcb_path, "1.2.3", "abcdef0")?;
+        update_kicad_pcb_release_variables(
+            &kicad_pcb_path,
+            &[("PCB_VERSION", "1.2.3"), ("PCB_GIT_HASH", "abcdef0")],
+        )?;
 
         let content = fs::read_to_string(&kicad_pcb_path)?;
         assert!(content.contains(r#"(property "PCB_VERSION" "1.2.3")"#));
@@ -5,16 +5,14 @@ use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use pcb_diode_api::{
     ExecSyncRequest, SandboxClient, SandboxFileUri, SandboxLockGuard, SandboxLockOptions,
 };
+use pcb_zen_core::cancel::CancellationToken;
+use pcb_zen_core::{DefaultFileProvider, FileMetadata, FileProvider};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::{
-    Arc,
-    atomic::{AtomicBool, Ordering},
-    mpsc::{self, Receiver, RecvTimeoutError},
-};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
 use std::thread;
 use std::time::{Duration, SystemTime};
 use uuid::Uuid;
@@ -87,7 +85,13 @@ pub fn execute_open(uri: SandboxFileUri, args: OpenArgs) -> Result<()> {
             check: false,
             suppress: Vec::new(),
             no_sync: true,
+            status: false,
+            footprint_drift: false,
+            return_paths: None,
+            stitch_distance: pcb_layout::return_path::DEFAULT_STITCH_DISTANCE_MM,
+            update_footprints: None,
             format: LayoutOutputFormat::Human,
+            stamp: false,
         };
         status.set_message("Running pcb layout in sandbox...");
         let result = run_remote_layout(&client, &uri, &layout_args)?;
@@ -198,9 +202,9 @@ fn open_layout_and_sync(
         )
     })?;
     sync_session.mark_active()?;
-    let running = install_shutdown_flag()?;
+    let interrupted = crate::interrupt::ctrl_c_token()?;
     status.set_message(format!("Opening {}...", local.pcb_file.display()));
-    let watcher = LocalLayoutWatcher::new(&local.local_layout_dir)?;
+    let mut watcher = LocalLayoutWatcher::new(&local.local_layout_dir)?;
     let mut session = match pcb_kicad::open_pcbnew_session(&local.pcb_file) {
         Ok(session) => session,
         Err(err) => {
@@ -219,8 +223,8 @@ fn open_layout_and_sync(
         local,
         &lock,
         &mut session,
-        &watcher,
-        &running,
+        &mut watcher,
+        &interrupted,
         &status,
     ) {
         SyncOutcome::Clean(stats) => {
@@ -275,8 +279,8 @@ fn run_local_sync_loop(
     local: &LocalLayout,
     lock: &SandboxLockGuard,
     session: &mut pcb_kicad::PcbnewSession,
-    watcher: &LocalLayoutWatcher,
-    running: &AtomicBool,
+    watcher: &mut LocalLayoutWatcher,
+    interrupted: &CancellationToken,
     status: &pcb_ui::Spinner,
 ) -> SyncOutcome {
     loop {
@@ -285,7 +289,7 @@ fn run_local_sync_loop(
             Ok(None) => {}
             Err(err) => return recoverable_outcome(RecoverableStopReason::SyncFailed, err),
         }
-        if !running.load(Ordering::SeqCst) {
+        if interrupted.is_cancelled() {
             break;
         }
         if !lock.is_active() {
@@ -332,6 +336,10 @@ fn recoverable_outcome(reason: RecoverableStopReason, error: anyhow::Error) -> S
 struct LocalLayoutWatcher {
     _watcher: RecommendedWatcher,
     rx: Receiver<notify::Result<notify::Event>>,
+    files: DefaultFileProvider,
+    /// Metadata of each path as of its last event, so repeated notifications
+    /// for the same write don't trigger another sync.
+    metadata: HashMap<PathBuf, FileMetadata>,
 }
 
 impl LocalLayoutWatcher {
@@ -347,39 +355,54 @@ impl LocalLayoutWatcher {
         Ok(Self {
             _watcher: watcher,
             rx,
+            files: DefaultFileProvider::new(),
+            metadata: HashMap::new(),
         })
     }
 
-    fn changed_with_timeout(&self, timeout: Duration) -> Result<bool> {
+    fn changed_with_timeout(&mut self, timeout: Duration) -> Result<bool> {
         let mut changed = match self.rx.recv_timeout(timeout) {
-            Ok(event) => relevant_watch_event(event?)?,
+            Ok(event) => self.relevant_watch_event(event?),
             Err(RecvTimeoutError::Timeout) => return Ok(false),
             Err(RecvTimeoutError::Disconnected) => bail!("Local layout watcher stopped"),
         };
 
         thread::sleep(WATCH_DEBOUNCE);
-        for event in self.rx.try_iter() {
-            changed |= relevant_watch_event(event?)?;
+        let events: Vec<_> = self.rx.try_iter().collect();
+        for event in events {
+            changed |= self.relevant_watch_event(event?);
         }
         Ok(changed)
     }
-}
 
-fn relevant_watch_event(event: notify::Event) -> Result<bool> {
-    if matches!(event.kind, EventKind::Access(_)) {
-        return Ok(false);
+    fn relevant_watch_event(&mut self, event: notify::Event) -> bool {
+        if matches!(event.kind, EventKind::Access(_)) {
+            return false;
+        }
+        let mut changed = false;
+        for path in event
+            .paths
+            .iter()
+            .filter(|path| !should_skip_sync_path(path))
+        {
+            changed |= self.metadata_changed(path);
+        }
+        changed
     }
-    Ok(event.paths.iter().any(|path| !should_skip_sync_path(path)))
-}
 
-fn install_shutdown_flag() -> Result<Arc<AtomicBool>> {
-    let running = Arc::new(AtomicBool::new(true));
-    let flag = Arc::clone(&running);
-    ctrlc::set_handler(move || {
-        flag.store(false, Ordering::SeqCst);
-    })
-    .context("Failed to set Ctrl-C handler")?;
-    Ok(running)
+    /// Record the current metadata of `path`; false if it is unchanged since
+    /// the last event. Removed paths always count as changed.
+    fn metadata_changed(&mut self, path: &Path) -> bool {
+        match self.files.metadata(path) {
+            Ok(metadata) => {
+                self.metadata.insert(path.to_path_buf(), metadata.clone()) != Some(metadata)
+            }
+            Err(_) => {
+                self.metadata.remove(path);
+                true
+            }
+        }
+    }
 }
 
 fn run_remote_layout(
@@ -403,6 +426,14 @@ fn run_remote_layout(
     if args.check {
         command.push("--check".to_string());
     }
+    if args.stamp {
+        command.push("--stamp".to_string());
+    }
+    match args.update_footprints.as_deref() {
+        Some([]) => command.push("--update-footprints".to_string()),
+        Some(selection) => command.push(format!("--update-footprints={}", selection.join(","))),
+        None => {}
+    }
     for config in &args.config {
         command.push("--config".to_string());
         command.push(config.clone());
@@ -1,10 +1,12 @@
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 use pcb_zen_core::DefaultFileProvider;
 use pcb_zen_core::resolution::ResolutionResult;
 use tracing::instrument;
 
+use pcb_zen::workspace::apply_package_overrides;
 use pcb_zen::{get_workspace_info, resolve_workspace_dependencies};
 
 /// Resolve dependencies for read-style commands such as build, bom, layout, and open.
@@ -13,8 +15,18 @@ use pcb_zen::{get_workspace_info, resolve_workspace_dependencies};
 ///
 /// This helper must not modify source dependency state. Dependency hydration and vendoring
 /// belong to explicit write commands such as `pcb sync` and `pcb vendor`.
-#[instrument(name = "resolve_dependencies", skip_all)]
 pub fn resolve(input_path: Option<&Path>, offline: bool) -> Result<ResolutionResult> {
+    resolve_with_overrides(input_path, offline, &[]).map(|(resolution, _)| resolution)
+}
+
+/// [`resolve`] with packages replaced by local directories for this invocation
+/// (`--override-package`). Returns the applied overrides keyed by package URL.
+#[instrument(name = "resolve_dependencies", skip_all)]
+pub fn resolve_with_overrides(
+    input_path: Option<&Path>,
+    offline: bool,
+    overrides: &[(String, PathBuf)],
+) -> Result<(ResolutionResult, BTreeMap<String, PathBuf>)> {
     let cwd;
     let path = match input_path {
         // Handle both None and empty paths (e.g., "file.zen".parent() returns Some(""))
@@ -24,7 +36,8 @@ pub fn resolve(input_path: Option<&Path>, offline: bool) -> Result<ResolutionRes
             &cwd
         }
     };
-    let workspace_info = get_workspace_info(&DefaultFileProvider::new(), path)?;
+    let file_provider = DefaultFileProvider::new();
+    let mut workspace_info = get_workspace_info(&file_provider, path)?;
 
     // Fail on workspace discovery errors (invalid pcb.toml files)
     if !workspace_info.errors.is_empty() {
@@ -37,5 +50,22 @@ pub fn resolve(input_path: Option<&Path>, offline: bool) -> Result<ResolutionRes
         );
     }
 
-    resolve_workspace_dependencies(workspace_info, path, offline)
+    let applied = apply_package_overrides(&file_provider, &mut workspace_info, overrides)?;
+    let resolution = resolve_workspace_dependencies(workspace_info, path, offline)?;
+    Ok((resolution, applied))
+}
+
+/// Parse an `--override-package TARGET=PATH` value. Relative paths are taken
+/// from the current directory.
+pub fn parse_package_override(value: &str) -> Result<(String, PathBuf)> {
+    let Some((target, path)) = value.split_once('=') else {
+        bail!("Invalid --override-package '{value}': expected TARGET=PATH, e.g. @stdlib=../stdlib");
+    };
+    let (target, path) = (target.trim(), path.trim());
+    if target.is_empty() || path.is_empty() {
+        bail!("Invalid --override-package '{value}': expected TARGET=PATH, e.g. @stdlib=../stdlib");
+    }
+    let path = std::fs::canonicalize(path)
+        .with_context(|| format!("Override path for {target} does not exist: {path}"))?;
+    Ok((target.to_string(), path))
 }
@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use pcb_ui::{Style, StyledText};
+use pcb_zen_core::{DefaultFileProvider, EvalContextConfig, FileProvider};
+
+#[derive(Args, Debug)]
+#[command(about = "Show how a load() path resolves from a given file")]
+pub struct ResolveArgs {
+    /// File the load() is written in
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    pub file: PathBuf,
+
+    /// Load string to resolve, e.g. "@stdlib/interfaces.zen"
+    pub load: String,
+
+    /// Disable network access (offline mode) - only use vendored dependencies
+    #[arg(long = "offline")]
+    pub offline: bool,
+}
+
+pub fn execute(args: ResolveArgs) -> Result<()> {
+    let file_provider = Arc::new(DefaultFileProvider::new());
+    let file = file_provider
+        .canonicalize(&args.file)
+        .with_context(|| format!("File not found: {}", args.file.display()))?;
+
+    let mut resolution = crate::resolve::resolve(file.parent(), args.offline)?;
+    resolution.canonicalize_keys(file_provider.as_ref());
+    let config =
+        EvalContextConfig::new(file_provider, Arc::new(resolution)).set_source_path(file.clone());
+    let (result, trace) = config.trace_resolve_path(&args.load, &file);
+
+    println!(
+        "{} from {}",
+        args.load.as_str().with_style(Style::Blue),
+        file.display()
+    );
+    for (i, step) in trace.iter().enumerate() {
+        println!("  {}. {step}", i + 1);
+    }
+    match result {
+        Ok(path) => {
+            println!("{}", path.display().to_string().with_style(Style::Green));
+            Ok(())
+        }
+        Err(e) => Err(e.context(format!("Failed to resolve {}", args.load))),
+    }
+}
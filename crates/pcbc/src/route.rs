@@ -9,8 +9,6 @@ use pcb_layout::utils;
 use pcb_ui::prelude::*;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 use std::time::{Duration, Instant};
 use tempfile::NamedTempFile;
@@ -92,12 +90,7 @@ pub fn execute(args: RouteArgs) -> Result<()> {
         .unwrap_or_else(|| board_name.to_string());
 
     // Set up Ctrl+C handler
-    let running = Arc::new(AtomicBool::new(true));
-    let r = running.clone();
-    ctrlc::set_handler(move || {
-        r.store(false, Ordering::SeqCst);
-    })
-    .context("Failed to set Ctrl+C handler")?;
+    let interrupted = crate::interrupt::ctrl_c_token()?;
 
     // Start routing
     println!(
@@ -139,7 +132,7 @@ pub fn execute(args: RouteArgs) -> Result<()> {
     let mut consecutive_errors = 0;
     let mut board_opened = false;
 
-    while running.load(Ordering::SeqCst) {
+    while !interrupted.is_cancelled() {
         match routing::get_routing_status(&ctx, &job_id) {
             Ok(status) => {
                 consecutive_errors = 0;
@@ -192,7 +185,7 @@ pub fn execute(args: RouteArgs) -> Result<()> {
 
         // Poll every 3s with Ctrl+C responsiveness
         for _ in 0..30 {
-            if !running.load(Ordering::SeqCst) {
+            if interrupted.is_cancelled() {
                 break;
             }
             thread::sleep(Duration::from_millis(100));
@@ -200,7 +193,7 @@ pub fn execute(args: RouteArgs) -> Result<()> {
     }
 
     // Handle Ctrl+C
-    if !running.load(Ordering::SeqCst) {
+    if interrupted.is_cancelled() {
         println!();
         println!("Stopping routing job...");
         let _ = routing::stop_routing(&ctx, &job_id);
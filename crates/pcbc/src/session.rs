@@ -0,0 +1,77 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::Result;
+use pcb_sch::Schematic;
+use pcb_zen_core::lang::secret::SecretInputs;
+use pcb_zen_core::resolution::ResolutionResult;
+use pcb_zen_core::{EvalOutput, WithDiagnostics};
+
+/// A design evaluated at most once per invocation.
+///
+/// Multi-stage commands such as `pcb publish` and `pcb preview` hand the session
+/// to each stage so that evaluation and schematic conversion are not repeated.
+pub struct DesignSession {
+    zen_path: PathBuf,
+    resolution: ResolutionResult,
+    secrets: SecretInputs,
+    eval: OnceLock<WithDiagnostics<EvalOutput>>,
+    schematic: OnceLock<Schematic>,
+}
+
+impl DesignSession {
+    pub fn new(zen_path: impl Into<PathBuf>, resolution: ResolutionResult) -> Self {
+        Self {
+            zen_path: zen_path.into(),
+            resolution,
+            secrets: SecretInputs::default(),
+            eval: OnceLock::new(),
+            schematic: OnceLock::new(),
+        }
+    }
+
+    /// Resolve dependencies for `zen_path` and create a session around the result.
+    pub fn resolve(zen_path: &Path, offline: bool) -> Result<Self> {
+        let resolution = crate::resolve::resolve(Some(zen_path), offline)?;
+        Ok(Self::new(zen_path, resolution))
+    }
+
+    /// Evaluate with sensitive root inputs, redacted from the schematic.
+    pub fn with_secret_inputs(mut self, secrets: SecretInputs) -> Self {
+        self.secrets = secrets;
+        self
+    }
+
+    pub fn resolution(&self) -> &ResolutionResult {
+        &self.resolution
+    }
+
+    pub fn secret_inputs(&self) -> &SecretInputs {
+        &self.secrets
+    }
+
+    /// Evaluate the design, reusing the cached result on later calls.
+    pub fn eval(&self) -> &WithDiagnostics<EvalOutput> {
+        self.eval.get_or_init(|| {
+            pcb_zen::eval_with_secrets(
+                &self.zen_path,
+                self.resolution.clone(),
+                Default::default(),
+                self.secrets.clone(),
+            )
+        })
+    }
+
+    /// Convert the evaluated design to a schematic, reusing the cached result on later calls.
+    pub fn schematic(&self) -> Result<&Schematic> {
+        if let Some(schematic) = self.schematic.get() {
+            return Ok(schematic);
+        }
+
+        let Some(output) = self.eval().output.as_ref() else {
+            anyhow::bail!("Evaluation failed");
+        };
+        let schematic = output.to_schematic()?;
+        Ok(self.schematic.get_or_init(|| schematic))
+    }
+}
@@ -0,0 +1,113 @@
+//! Release archive signing with [cosign](https://docs.sigstore.dev/cosign/).
+//!
+//! Signatures are Sigstore bundles written next to the archive as
+//! `<archive>.sigstore.json`. The archive holds `metadata.json`, so one
+//! signature covers both. Without a key, cosign signs keyless: it gets a
+//! short-lived certificate for an OIDC identity (a browser login, or the
+//! ambient token in CI) and records the signature in the Rekor transparency
+//! log.
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+
+/// Appended to the archive file name to get its signature bundle.
+const BUNDLE_SUFFIX: &str = ".sigstore.json";
+
+#[derive(Args, Debug, Clone, Default)]
+pub struct SignArgs {
+    /// Sign the release archive with cosign keyless signing (Sigstore OIDC)
+    #[arg(long)]
+    pub sign: bool,
+
+    /// Sign with a cosign private key or KMS URI instead of keyless; implies --sign
+    #[arg(long, value_name = "KEY")]
+    pub sign_key: Option<String>,
+}
+
+impl SignArgs {
+    pub fn enabled(&self) -> bool {
+        self.sign || self.sign_key.is_some()
+    }
+}
+
+/// Who a signature must come from.
+pub enum Signer {
+    /// Public key (or KMS URI) matching the signing key.
+    Key(String),
+    /// Certificate identity and OIDC issuer of a keyless signature.
+    Identity { identity: String, issuer: String },
+}
+
+/// Default signature bundle path for `archive`.
+pub fn bundle_path(archive: &Path) -> PathBuf {
+    let mut path = archive.as_os_str().to_owned();
+    path.push(BUNDLE_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// Sign `archive`, writing the bundle next to it. Returns the bundle path.
+pub fn sign_archive(archive: &Path, args: &SignArgs) -> Result<PathBuf> {
+    let bundle = bundle_path(archive);
+    let mut cmd = cosign();
+    cmd.args(["sign-blob", "--yes", "--bundle"]).arg(&bundle);
+    if let Some(key) = &args.sign_key {
+        cmd.arg("--key").arg(key);
+    }
+    cmd.arg(archive);
+    run(cmd, "sign-blob").with_context(|| format!("Failed to sign {}", archive.display()))?;
+    Ok(bundle)
+}
+
+/// Check that `bundle` is a valid signature of `archive` by `signer`.
+pub fn verify_archive(archive: &Path, bundle: &Path, signer: &Signer) -> Result<()> {
+    if !bundle.exists() {
+        bail!("Signature bundle not found: {}", bundle.display());
+    }
+    let mut cmd = cosign();
+    cmd.args(["verify-blob", "--bundle"]).arg(bundle);
+    match signer {
+        Signer::Key(key) => {
+            cmd.arg("--key").arg(key);
+        }
+        Signer::Identity { identity, issuer } => {
+            cmd.args([
+                OsStr::new("--certificate-identity"),
+                OsStr::new(identity),
+                OsStr::new("--certificate-oidc-issuer"),
+                OsStr::new(issuer),
+            ]);
+        }
+    }
+    cmd.arg(archive);
+    run(cmd, "verify-blob")
+        .with_context(|| format!("Signature check failed for {}", archive.display()))
+}
+
+/// `cosign`, or the binary named by the `COSIGN` environment variable.
+fn cosign() -> Command {
+    let program = std::env::var("COSIGN")
+        .ok()
+        .filter(|path| !path.is_empty())
+        .unwrap_or_else(|| "cosign".to_string());
+    Command::new(program)
+}
+
+/// Run cosign with inherited stdio so keyless signing can prompt for login.
+fn run(mut cmd: Command, subcommand: &str) -> Result<()> {
+    let status = match cmd.status() {
+        Ok(status) => status,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => bail!(
+            "cosign not found. Install it from https://docs.sigstore.dev/cosign/system_config/installation/ \
+             or set the COSIGN environment variable."
+        ),
+        Err(err) => return Err(err).context("Failed to run cosign"),
+    };
+    if !status.success() {
+        bail!("cosign {subcommand} exited with {status}");
+    }
+    Ok(())
+}
@@ -1,7 +1,14 @@
 use anyhow::Result;
 use clap::Args;
 use colored::Colorize;
-use pcb_sim::{gen_sim, has_sim_setup, run_ngspice_captured};
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::{Cell, Color, Table};
+use pcb_sch::Schematic;
+use pcb_sim::op::format_volts;
+use pcb_sim::{
+    OperatingPoint, annotate_schematic, gen_sim, has_sim_setup, parse_operating_point,
+    run_ngspice_captured,
+};
 use pcb_ui::prelude::*;
 use serde_json::Value as JsonValue;
 use starlark::collections::SmallMap;
@@ -42,6 +49,34 @@ pub struct SimArgs {
     /// Show full ngspice output on success
     #[arg(short, long)]
     pub verbose: bool,
+
+    /// Write the schematic JSON with `.op` node voltages recorded as `op_voltage`
+    /// net properties. Only valid when simulating a single file.
+    #[arg(long, value_name = "FILE", value_hint = clap::ValueHint::FilePath, conflicts_with_all = ["netlist", "output"])]
+    pub annotate: Option<PathBuf>,
+}
+
+/// Print the node voltages and source currents of a DC operating point.
+fn print_operating_point(schematic: &Schematic, op: &OperatingPoint) {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED);
+    table.set_header(["Node", "Value"].map(|title| Cell::new(title).fg(Color::Cyan)));
+    for (node, volts) in &op.voltages {
+        // ngspice lowercases node names; show the net name as written
+        let name = schematic
+            .nets
+            .keys()
+            .find(|name| name.to_lowercase() == *node)
+            .unwrap_or(node);
+        table.add_row(vec![Cell::new(name), Cell::new(format_volts(*volts))]);
+    }
+    for (source, amps) in &op.currents {
+        table.add_row(vec![
+            Cell::new(format!("I({source})")),
+            Cell::new(format!("{:.4}mA", amps * 1e3)),
+        ]);
+    }
+    eprintln!("{table}");
 }
 
 fn simulate_one(
@@ -52,7 +87,7 @@ fn simulate_one(
 ) -> Result<bool> {
     let file_name = zen_path.file_name().unwrap().to_string_lossy().to_string();
 
-    let Some(schematic) = build_zen(
+    let Some(mut schematic) = build_zen(
         zen_path,
         config_inputs,
         create_diagnostics_passes(&[], &[]),
@@ -129,6 +164,18 @@ fn simulate_one(
             pcb_ui::icons::success(),
             file_name.with_style(Style::Green).bold(),
         );
+
+        let op = parse_operating_point(&result.output);
+        if let Some(op) = &op {
+            print_operating_point(&schematic, op);
+        }
+        if let Some(annotate_path) = &args.annotate {
+            let Some(op) = &op else {
+                anyhow::bail!("{file_name}: --annotate requires an .op analysis in the sim setup");
+            };
+            annotate_schematic(&mut schematic, op);
+            std::fs::write(annotate_path, schematic.to_json()?)?;
+        }
         Ok(true)
     } else {
         eprintln!(
@@ -167,9 +214,9 @@ pub fn execute(args: SimArgs) -> Result<()> {
     }
 
     // Directory / workspace mode — behave like `pcb build`
-    if args.setup.is_some() || args.output.is_some() || args.netlist {
+    if args.setup.is_some() || args.output.is_some() || args.netlist || args.annotate.is_some() {
         anyhow::bail!(
-            "--setup, --output, and --netlist are only supported when simulating a single file"
+            "--setup, --output, --netlist, and --annotate are only supported when simulating a single file"
         );
     }
 
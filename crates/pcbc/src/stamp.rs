@@ -0,0 +1,51 @@
+//! `--stamp`: trace generated artifacts back to the sources they came from.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use pcb_sch::stamp::Stamp;
+use pcb_zen::WorkspaceInfo;
+use pcb_zen::git;
+
+/// Stamp of `workspace`: its `git describe` and a content hash of every
+/// `.zen` file and package `pcb.toml` in it. Layouts and generated files
+/// are not hashed, so stamping a layout doesn't change the hash.
+pub fn workspace_stamp(workspace: &WorkspaceInfo) -> Result<Stamp> {
+    let root = &workspace.root;
+    let git_describe = git::run_output_opt(root, &["describe", "--tags", "--always", "--dirty"])
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let mut paths = crate::file_walker::collect_zen_files(std::slice::from_ref(root))?;
+    paths.extend(
+        workspace
+            .packages
+            .values()
+            .map(|pkg| pkg.dir(root))
+            .chain([root.clone()])
+            .map(|dir| dir.join("pcb.toml"))
+            .filter(|path| path.is_file()),
+    );
+    paths.sort();
+    paths.dedup();
+
+    let files = paths
+        .iter()
+        .map(|path| {
+            let contents =
+                fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+            let rel_path: PathBuf = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+            Ok((rel_path, contents))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let source_hash = pcb_canonical::compute_content_hash_from_memory_files(
+        files
+            .iter()
+            .map(|(path, contents)| (path.as_path(), contents.as_slice())),
+    )?;
+
+    Ok(Stamp {
+        git_describe,
+        source_hash,
+    })
+}
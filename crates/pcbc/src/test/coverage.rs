@@ -0,0 +1,233 @@
+//! Coverage report for `pcb test --coverage`: which workspace modules a
+//! TestBench exercises, which module check() rules ran under test, and which
+//! io()/config() combinations the test cases passed.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use comfy_table::{Cell, Color, Table, presets::UTF8_FULL_CONDENSED};
+use pcb_ui::prelude::*;
+use pcb_zen_core::lang::test_bench::FrozenTestBenchValue;
+use pcb_zen_core::{FrozenModuleValue, ModulePath};
+use serde::Serialize;
+
+/// Coverage gathered while running TestBenches.
+#[derive(Default)]
+pub struct Coverage {
+    modules: BTreeMap<PathBuf, ModuleCoverage>,
+    /// Files that define a TestBench; they are tests, not modules under test.
+    bench_files: BTreeSet<PathBuf>,
+}
+
+#[derive(Default)]
+struct ModuleCoverage {
+    testbenches: BTreeSet<String>,
+    cases: usize,
+    checks: BTreeSet<String>,
+    params: BTreeSet<String>,
+    exercised_params: BTreeSet<String>,
+    combinations: BTreeSet<BTreeMap<String, String>>,
+}
+
+#[derive(Serialize)]
+pub struct CoverageReport {
+    pub modules: Vec<ModuleReport>,
+    pub summary: CoverageSummary,
+}
+
+#[derive(Serialize)]
+pub struct ModuleReport {
+    /// Module file, relative to the workspace root
+    pub path: String,
+    /// Whether a TestBench instantiates this module directly
+    pub covered: bool,
+    pub testbenches: Vec<String>,
+    pub cases: usize,
+    /// check() messages that ran in instances of this module under test
+    pub checks: Vec<String>,
+    /// io() and config() parameters the module declares
+    pub params: Vec<String>,
+    /// Declared parameters that no test case sets
+    pub untested_params: Vec<String>,
+    /// Distinct parameter values passed by the test cases
+    pub combinations: Vec<BTreeMap<String, String>>,
+}
+
+#[derive(Serialize)]
+pub struct CoverageSummary {
+    pub modules: usize,
+    pub covered_modules: usize,
+    pub checks: usize,
+    pub params: usize,
+    pub exercised_params: usize,
+    pub combinations: usize,
+}
+
+impl Coverage {
+    /// Record the module, checks, and parameters exercised by `testbench`.
+    pub fn record(
+        &mut self,
+        testbench: &FrozenTestBenchValue,
+        module_tree: &BTreeMap<ModulePath, FrozenModuleValue>,
+    ) {
+        self.bench_files
+            .insert(canonical(Path::new(testbench.source_path())));
+
+        let loader = testbench.module_loader();
+        let mut checks: BTreeMap<PathBuf, BTreeSet<String>> = BTreeMap::new();
+        let module = self
+            .modules
+            .entry(canonical(Path::new(&loader.source_path)))
+            .or_default();
+        module.testbenches.insert(testbench.name().to_string());
+        module.params.extend(loader.params.iter().cloned());
+
+        for case in testbench.deferred_cases() {
+            module.cases += 1;
+            module.exercised_params.extend(
+                case.params
+                    .keys()
+                    .filter(|name| loader.params.contains(name))
+                    .cloned(),
+            );
+            module.combinations.insert(
+                case.params
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.to_value().to_str()))
+                    .collect(),
+            );
+
+            // Checks run in the case's instance and every module below it.
+            let case_path = ModulePath::from(case.case_final_name.clone());
+            for (path, instance) in module_tree {
+                if path.starts_with(&case_path) && !instance.checks().is_empty() {
+                    checks
+                        .entry(canonical(Path::new(instance.source_path())))
+                        .or_default()
+                        .extend(instance.checks().iter().cloned());
+                }
+            }
+        }
+
+        for (source, messages) in checks {
+            self.modules
+                .entry(source)
+                .or_default()
+                .checks
+                .extend(messages);
+        }
+    }
+
+    /// Build the report over `zen_files`, the files under test, with paths
+    /// relative to `workspace_root`.
+    pub fn report(self, zen_files: &[PathBuf], workspace_root: &Path) -> CoverageReport {
+        let workspace_root = canonical(workspace_root);
+        let mut modules = self.modules;
+        for file in zen_files {
+            modules.entry(canonical(file)).or_default();
+        }
+
+        let modules: Vec<ModuleReport> = modules
+            .into_iter()
+            .filter(|(path, _)| path.starts_with(&workspace_root))
+            .filter(|(path, module)| {
+                !module.testbenches.is_empty() || !self.bench_files.contains(path)
+            })
+            .map(|(path, module)| ModuleReport {
+                path: path
+                    .strip_prefix(&workspace_root)
+                    .unwrap_or(&path)
+                    .display()
+                    .to_string(),
+                covered: !module.testbenches.is_empty(),
+                testbenches: module.testbenches.into_iter().collect(),
+                cases: module.cases,
+                checks: module.checks.into_iter().collect(),
+                untested_params: module
+                    .params
+                    .difference(&module.exercised_params)
+                    .cloned()
+                    .collect(),
+                params: module.params.into_iter().collect(),
+                combinations: module.combinations.into_iter().collect(),
+            })
+            .collect();
+
+        let summary = CoverageSummary {
+            modules: modules.len(),
+            covered_modules: modules.iter().filter(|m| m.covered).count(),
+            checks: modules.iter().map(|m| m.checks.len()).sum(),
+            params: modules.iter().map(|m| m.params.len()).sum(),
+            exercised_params: modules
+                .iter()
+                .map(|m| m.params.len() - m.untested_params.len())
+                .sum(),
+            combinations: modules.iter().map(|m| m.combinations.len()).sum(),
+        };
+        CoverageReport { modules, summary }
+    }
+}
+
+pub fn output_table(report: &CoverageReport) {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED);
+    table.set_header(
+        [
+            "Module",
+            "TestBenches",
+            "Cases",
+            "Checks",
+            "Params",
+            "Combinations",
+        ]
+        .map(|title| {
+            Cell::new(title)
+                .fg(Color::Blue)
+                .add_attribute(comfy_table::Attribute::Bold)
+        }),
+    );
+
+    for module in &report.modules {
+        let path = if module.covered {
+            Cell::new(&module.path)
+        } else {
+            Cell::new(&module.path).fg(Color::Yellow)
+        };
+        let testbenches = if module.covered {
+            module.testbenches.join(", ")
+        } else {
+            "-".to_string()
+        };
+        let exercised = module.params.len() - module.untested_params.len();
+        table.add_row(vec![
+            path,
+            Cell::new(testbenches),
+            Cell::new(module.cases),
+            Cell::new(module.checks.len()),
+            Cell::new(format!("{exercised}/{}", module.params.len())),
+            Cell::new(module.combinations.len()),
+        ]);
+    }
+
+    println!();
+    println!("{table}");
+
+    let summary = &report.summary;
+    let icon = if summary.covered_modules == summary.modules {
+        pcb_ui::icons::success().with_style(Style::Green)
+    } else {
+        pcb_ui::icons::warning().with_style(Style::Yellow)
+    };
+    println!(
+        "{icon} {}/{} modules covered by a TestBench, {} check() rules run, {}/{} parameters exercised",
+        summary.covered_modules,
+        summary.modules,
+        summary.checks,
+        summary.exercised_params,
+        summary.params
+    );
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
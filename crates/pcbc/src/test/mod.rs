@@ -4,6 +4,7 @@ use comfy_table::{Cell, Color, Table, presets::UTF8_FULL_CONDENSED};
 use log::debug;
 use pcb_ui::prelude::*;
 use pcb_zen_core::ModulePath;
+use pcb_zen_core::lang::error::{BenchTestResult, CheckFailure};
 use serde::Serialize;
 use serde_json::Value as JsonValue;
 use starlark::collections::SmallMap;
@@ -15,6 +16,10 @@ use crate::build::create_diagnostics_passes;
 use crate::config_input::{CONFIG_ARG_HELP, parse_config_overrides};
 use crate::file_walker;
 
+mod coverage;
+
+use coverage::{Coverage, CoverageReport};
+
 #[derive(Args, Debug, Default, Clone)]
 #[command(about = "Run tests in .zen files")]
 pub struct TestArgs {
@@ -43,6 +48,11 @@ pub struct TestArgs {
     /// Output format for test results
     #[arg(short = 'f', long = "format", value_enum, default_value_t = OutputFormat::Table)]
     pub format: OutputFormat,
+
+    /// Report which modules, check() rules, and io() combinations the
+    /// TestBenches exercise
+    #[arg(long = "coverage")]
+    pub coverage: bool,
 }
 
 #[derive(ValueEnum, Clone, Debug, Default)]
@@ -66,6 +76,8 @@ pub struct TestResult {
 pub struct JsonTestOutput {
     pub results: Vec<TestResult>,
     pub summary: TestSummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<CoverageReport>,
 }
 
 #[derive(Serialize)]
@@ -82,7 +94,8 @@ pub fn test(
     passes: Vec<Box<dyn pcb_zen_core::DiagnosticsPass>>,
     resolution_result: pcb_zen_core::resolution::ResolutionResult,
     config_inputs: SmallMap<String, JsonValue>,
-) -> (Vec<pcb_zen_core::lang::error::BenchTestResult>, bool) {
+    coverage: &mut Coverage,
+) -> (Vec<BenchTestResult>, bool) {
     let file_name = zen_path.file_name().unwrap().to_string_lossy();
 
     // Show spinner while testing
@@ -105,9 +118,11 @@ pub fn test(
             );
 
             // Execute checks for each TestBench
+            let module_tree = eval_output.module_tree();
             for testbench in testbenches {
                 let check_diagnostics = execute_testbench_checks(&testbench, &eval_output);
                 diagnostics.diagnostics.extend(check_diagnostics);
+                coverage.record(&testbench, &module_tree);
             }
         }
     }
@@ -115,12 +130,29 @@ pub fn test(
     // Finish spinner before printing diagnostics
     spinner.finish();
 
-    // Collect structured test results before applying passes
-    let test_results: Vec<pcb_zen_core::lang::error::BenchTestResult> = diagnostics
+    // Collect structured test results before applying passes. Failed
+    // module-level check() calls count as failed assertions of their instance.
+    let test_results: Vec<BenchTestResult> = diagnostics
         .diagnostics
         .iter()
-        .filter_map(|diag| diag.downcast_error_ref::<pcb_zen_core::lang::error::BenchTestResult>())
-        .cloned()
+        .filter_map(|diag| {
+            if let Some(result) = diag.downcast_error_ref::<BenchTestResult>() {
+                return Some(result.clone());
+            }
+            let innermost = diag.innermost();
+            let failure = innermost.downcast_error_ref::<CheckFailure>()?;
+            Some(BenchTestResult {
+                test_bench_name: if failure.instance_path.is_empty() {
+                    "<root>".to_string()
+                } else {
+                    failure.instance_path.clone()
+                },
+                case_name: None,
+                check_name: failure.message.clone(),
+                file_path: innermost.path.clone(),
+                passed: false,
+            })
+        })
         .collect();
 
     // Apply all passes including rendering
@@ -181,7 +213,7 @@ fn execute_testbench_checks(
                     call_stack: None,
                     child: None,
                     source_error: Some(Arc::new(
-                        pcb_zen_core::lang::error::BenchTestResult {
+                        BenchTestResult {
                             test_bench_name: testbench.name().to_string(),
                             case_name: Some(deferred_case.case_name.clone()),
                             check_name: "<module evaluation>".to_string(),
@@ -278,16 +310,18 @@ pub fn execute(args: TestArgs) -> Result<()> {
         &resolution_result.workspace_info,
     )?;
 
-    let mut all_test_results: Vec<pcb_zen_core::lang::error::BenchTestResult> = Vec::new();
+    let mut all_test_results: Vec<BenchTestResult> = Vec::new();
     let mut has_errors = false;
+    let mut coverage = Coverage::default();
 
     // Process each .zen file
-    for zen_path in zen_paths {
+    for zen_path in &zen_paths {
         let (results, had_errors_file) = test(
-            &zen_path,
+            zen_path,
             create_diagnostics_passes(&args.suppress, &[]),
             resolution_result.clone(),
             config_inputs.clone(),
+            &mut coverage,
         );
         all_test_results.extend(results);
         if had_errors_file {
@@ -307,11 +341,20 @@ pub fn execute(args: TestArgs) -> Result<()> {
         })
         .collect();
 
+    let coverage = args
+        .coverage
+        .then(|| coverage.report(&zen_paths, &resolution_result.workspace_info.root));
+
     // Output structured results to stdout
     match args.format {
-        OutputFormat::Tap => output_tap(&all_results),
-        OutputFormat::Json => output_json(&all_results)?,
-        OutputFormat::Table => output_table(&all_results),
+        OutputFormat::Tap => output_tap(&all_results, coverage.as_ref()),
+        OutputFormat::Json => output_json(&all_results, coverage)?,
+        OutputFormat::Table => {
+            output_table(&all_results);
+            if let Some(coverage) = &coverage {
+                coverage::output_table(coverage);
+            }
+        }
     }
 
     // Exit with error if there were failures
@@ -323,7 +366,7 @@ pub fn execute(args: TestArgs) -> Result<()> {
     Ok(())
 }
 
-fn output_tap(results: &[TestResult]) {
+fn output_tap(results: &[TestResult], coverage: Option<&CoverageReport>) {
     println!("TAP version 13");
     println!("1..{}", results.len());
 
@@ -346,6 +389,18 @@ fn output_tap(results: &[TestResult]) {
             status, test_num, result.test_bench_name, case_suffix, result.check_name
         );
     }
+
+    if let Some(coverage) = coverage {
+        let summary = &coverage.summary;
+        println!(
+            "# coverage: {}/{} modules, {} check() rules, {}/{} parameters",
+            summary.covered_modules,
+            summary.modules,
+            summary.checks,
+            summary.exercised_params,
+            summary.params
+        );
+    }
 }
 
 fn output_table(results: &[TestResult]) {
@@ -417,7 +472,7 @@ fn output_table(results: &[TestResult]) {
     }
 }
 
-fn output_json(results: &[TestResult]) -> Result<()> {
+fn output_json(results: &[TestResult], coverage: Option<CoverageReport>) -> Result<()> {
     let passed = results.iter().filter(|r| r.status == "pass").count();
     let failed = results.iter().filter(|r| r.status == "fail").count();
 
@@ -428,6 +483,7 @@ fn output_json(results: &[TestResult]) -> Result<()> {
             passed,
             failed,
         },
+        coverage,
     };
 
     println!("{}", serde_json::to_string_pretty(&output)?);
@@ -1,23 +1,242 @@
-//! Legacy dependency update command.
+//! `pcb update`: upgrade direct dependencies and preview what changes.
 
 use anyhow::Result;
 use clap::Args;
-use std::path::PathBuf;
+use colored::Colorize;
+use pcb_zen::package_resolver::ResolvedDepId;
+use pcb_zen::{git, tags};
+use pcb_zen_core::config::split_repo_and_subpath;
+use semver::Version;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Commit subjects shown per dependency in `--dry-run` output.
+const MAX_COMMITS: usize = 10;
 
 #[derive(Args, Debug)]
 #[command(about = "Update dependencies to latest compatible versions")]
 pub struct UpdateArgs {
-    /// Path to workspace or package.
-    #[arg(default_value = ".")]
-    pub path: PathBuf,
+    /// Direct dependency to update, e.g. github.com/acme/foo. Defaults to all
+    /// direct remote dependencies.
+    #[arg(value_name = "DEPENDENCY")]
+    pub dependency: Option<String>,
+
+    /// Print old → new versions with release notes without writing pcb.toml
+    #[arg(long = "dry-run")]
+    pub dry_run: bool,
+
+    /// Allow updates to a newer breaking version (e.g. 0.3 → 0.4, 1.x → 2.x)
+    #[arg(long = "breaking")]
+    pub breaking: bool,
+}
+
+pub fn execute(args: UpdateArgs) -> Result<()> {
+    crate::pcb_mod::execute_update(args.dependency.as_deref(), args.breaking, args.dry_run)
+}
+
+/// Versions of one package before and after an update.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionChange {
+    pub module_path: String,
+    pub old: Vec<Version>,
+    pub new: Vec<Version>,
+}
+
+/// Packages whose selected versions differ between two resolutions.
+pub fn version_changes(
+    before: &BTreeMap<ResolvedDepId, Version>,
+    after: &BTreeMap<ResolvedDepId, Version>,
+) -> Vec<VersionChange> {
+    fn by_path(selected: &BTreeMap<ResolvedDepId, Version>) -> BTreeMap<&str, BTreeSet<&Version>> {
+        let mut versions: BTreeMap<&str, BTreeSet<&Version>> = BTreeMap::new();
+        for (dep_id, version) in selected {
+            versions.entry(&dep_id.path).or_default().insert(version);
+        }
+        versions
+    }
+
+    let before = by_path(before);
+    let after = by_path(after);
+    let paths: BTreeSet<&str> = before.keys().chain(after.keys()).copied().collect();
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let old = before.get(path).cloned().unwrap_or_default();
+            let new = after.get(path).cloned().unwrap_or_default();
+            (old != new).then(|| VersionChange {
+                module_path: path.to_string(),
+                old: old.into_iter().cloned().collect(),
+                new: new.into_iter().cloned().collect(),
+            })
+        })
+        .collect()
+}
+
+pub fn print_changes(changes: &[VersionChange]) {
+    if changes.is_empty() {
+        println!("All dependencies are up to date");
+        return;
+    }
+
+    for change in changes {
+        println!(
+            "{} {} → {}",
+            change.module_path.bold(),
+            format_versions(&change.old).red(),
+            format_versions(&change.new).green()
+        );
+
+        // Release notes only make sense for a straight upgrade of one version.
+        let ([old], [new]) = (change.old.as_slice(), change.new.as_slice()) else {
+            continue;
+        };
+        if new <= old {
+            continue;
+        }
+        let notes = release_notes(&change.module_path, old, new);
+        if let Some(changelog) = &notes.changelog {
+            for line in changelog.lines() {
+                println!("    {}", line.dimmed());
+            }
+        }
+        for subject in notes.commits.iter().take(MAX_COMMITS) {
+            println!("    {} {}", "•".dimmed(), subject);
+        }
+        if notes.commits.len() > MAX_COMMITS {
+            println!(
+                "    {}",
+                format!("… and {} more commits", notes.commits.len() - MAX_COMMITS).dimmed()
+            );
+        }
+    }
+}
 
-    /// Legacy filter retained for CLI compatibility.
-    #[arg(long, short = 'p', hide = true)]
-    pub packages: Vec<String>,
+fn format_versions(versions: &[Version]) -> String {
+    if versions.is_empty() {
+        return "(none)".to_string();
+    }
+    versions
+        .iter()
+        .map(Version::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
-pub fn execute(_args: UpdateArgs) -> Result<()> {
-    anyhow::bail!(
-        "`pcb update` is no longer supported. Use `pcb add -u` from the package directory instead."
+#[derive(Debug, Default)]
+struct ReleaseNotes {
+    commits: Vec<String>,
+    changelog: Option<String>,
+}
+
+/// Commit subjects and CHANGELOG entries between two published versions,
+/// read from the package's source repository. Missing data is left empty.
+fn release_notes(module_path: &str, old: &Version, new: &Version) -> ReleaseNotes {
+    let (repo_url, subpath) = split_repo_and_subpath(module_path);
+    let Ok(source_dir) = pcb_zen::cache_index::ensure_source_repo(repo_url) else {
+        return ReleaseNotes::default();
+    };
+    let prefix = tags::compute_tag_prefix(Some(Path::new(subpath)), None);
+    let old_tag = tags::build_tag_name(&prefix, old);
+    let new_tag = tags::build_tag_name(&prefix, new);
+
+    let commits = git::log_subjects(
+        &source_dir,
+        Some(&format!("{old_tag}..{new_tag}")),
+        Some(Path::new(subpath)),
+    );
+    let changelog_path = if subpath.is_empty() {
+        "CHANGELOG.md".to_string()
+    } else {
+        format!("{subpath}/CHANGELOG.md")
+    };
+    let changelog = git::run_output_opt(
+        &source_dir,
+        &["show", &format!("{new_tag}:{changelog_path}")],
     )
+    .and_then(|content| changelog_excerpt(&content, old, new));
+
+    ReleaseNotes { commits, changelog }
+}
+
+/// Sections of a Keep a Changelog style file for versions in `(old, new]`.
+fn changelog_excerpt(content: &str, old: &Version, new: &Version) -> Option<String> {
+    let mut excerpt = Vec::new();
+    let mut in_range = false;
+    for line in content.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            in_range = heading_version(heading).is_some_and(|v| &v > old && &v <= new);
+        }
+        if in_range {
+            excerpt.push(line);
+        }
+    }
+    let excerpt = excerpt.join("\n").trim().to_string();
+    (!excerpt.is_empty()).then_some(excerpt)
+}
+
+/// Version in a heading like `[1.2.0] - 2025-01-01` or `v1.2.0`.
+fn heading_version(heading: &str) -> Option<Version> {
+    let token = heading.trim_start_matches('[').split([']', ' ']).next()?;
+    tags::parse_version(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(s: &str) -> Version {
+        Version::parse(s).unwrap()
+    }
+
+    fn dep(path: &str, lane: &str) -> ResolvedDepId {
+        ResolvedDepId {
+            path: path.to_string(),
+            lane: lane.to_string(),
+        }
+    }
+
+    #[test]
+    fn reports_changed_packages_only() {
+        let before = BTreeMap::from([
+            (dep("github.com/acme/a", "0.3"), v("0.3.1")),
+            (dep("github.com/acme/b", "1"), v("1.0.0")),
+            (dep("github.com/acme/gone", "1"), v("1.0.0")),
+        ]);
+        let after = BTreeMap::from([
+            (dep("github.com/acme/a", "0.4"), v("0.4.0")),
+            (dep("github.com/acme/b", "1"), v("1.0.0")),
+            (dep("github.com/acme/new", "2"), v("2.1.0")),
+        ]);
+        let changes = version_changes(&before, &after);
+        assert_eq!(
+            changes,
+            vec![
+                VersionChange {
+                    module_path: "github.com/acme/a".to_string(),
+                    old: vec![v("0.3.1")],
+                    new: vec![v("0.4.0")],
+                },
+                VersionChange {
+                    module_path: "github.com/acme/gone".to_string(),
+                    old: vec![v("1.0.0")],
+                    new: vec![],
+                },
+                VersionChange {
+                    module_path: "github.com/acme/new".to_string(),
+                    old: vec![],
+                    new: vec![v("2.1.0")],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_changelog_sections_between_versions() {
+        let changelog = "# Changelog\n\n## [Unreleased]\n- wip\n\n## [1.3.0] - 2025-03-01\n### Added\n- C\n\n## v1.2.0\n- B\n\n## [1.1.0]\n- A\n";
+        assert_eq!(
+            changelog_excerpt(changelog, &v("1.1.0"), &v("1.3.0")).as_deref(),
+            Some("## [1.3.0] - 2025-03-01\n### Added\n- C\n\n## v1.2.0\n- B")
+        );
+        assert_eq!(changelog_excerpt(changelog, &v("1.3.0"), &v("1.3.0")), None);
+    }
 }
@@ -48,7 +48,8 @@ pub fn execute(args: VendorArgs) -> Result<()> {
     };
 
     // Always prune for explicit vendor command
-    let result = vendor_deps(&resolution, &additional_patterns, None, true)?;
+    let cancel = crate::interrupt::ctrl_c_token()?;
+    let result = vendor_deps(&resolution, &additional_patterns, None, true, &cancel)?;
 
     if result.package_count == 0 {
         println!("{} Vendor directory is up to date", "✓".green().bold());
@@ -0,0 +1,105 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use pcb_ui::{Colorize, Style, StyledText};
+use pcb_zen::get_workspace_info;
+use pcb_zen::provenance::{AssetStatus, verify_package_assets};
+use pcb_zen_core::DefaultFileProvider;
+use std::path::{Path, PathBuf};
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    /// Path inside the workspace to verify (defaults to current directory)
+    #[arg(value_name = "PATH", value_hint = clap::ValueHint::AnyPath)]
+    pub path: Option<PathBuf>,
+
+    /// Check vendored assets against the SHA256 recorded in [[provenance]]
+    #[arg(long)]
+    pub assets: bool,
+}
+
+pub fn execute(args: VerifyArgs) -> Result<()> {
+    if !args.assets {
+        anyhow::bail!("Nothing to verify; pass --assets");
+    }
+
+    let start_path = match args.path {
+        Some(path) => path,
+        None => std::env::current_dir().context("Failed to get current directory")?,
+    };
+    let start_path = start_path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", start_path.display()))?;
+    let workspace_info = get_workspace_info(&DefaultFileProvider::new(), &start_path)?;
+    let root = &workspace_info.root;
+
+    let mut manifests: Vec<(PathBuf, _)> = workspace_info
+        .config
+        .iter()
+        .map(|config| (root.clone(), config))
+        .collect();
+    manifests.extend(
+        workspace_info
+            .packages
+            .values()
+            .map(|pkg| (pkg.dir(root), &pkg.config)),
+    );
+
+    let mut total = 0;
+    let mut failures = 0;
+    for (package_dir, config) in manifests {
+        for check in verify_package_assets(&package_dir, config)? {
+            total += 1;
+            let display = display_path(root, &check.path);
+            match &check.status {
+                AssetStatus::Verified => {
+                    println!("{} {}", "✓".green(), display);
+                }
+                AssetStatus::Embedded => {
+                    println!(
+                        "{} {} {}",
+                        "-".dimmed(),
+                        display,
+                        "(embedded, not checked)".dimmed()
+                    );
+                }
+                AssetStatus::Missing => {
+                    failures += 1;
+                    println!("{} {} {}", "✗".red(), display, "missing".red());
+                }
+                AssetStatus::Modified { actual } => {
+                    failures += 1;
+                    println!("{} {} {}", "✗".red(), display, "modified".red());
+                    println!("    expected sha256 {}", check.provenance.sha256.dimmed());
+                    println!("    actual   sha256 {}", actual.dimmed());
+                }
+            }
+            if check.status.is_failure() {
+                println!(
+                    "    source {}",
+                    check.provenance.url.as_str().with_style(Style::Cyan)
+                );
+            }
+        }
+    }
+
+    if total == 0 {
+        println!("No vendored assets with recorded provenance");
+        return Ok(());
+    }
+    if failures > 0 {
+        anyhow::bail!("{failures} of {total} vendored assets failed verification");
+    }
+    println!(
+        "{} {}",
+        "✓".green().bold(),
+        format!("Verified {total} vendored assets").bold()
+    );
+    Ok(())
+}
+
+fn display_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
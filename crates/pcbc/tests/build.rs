@@ -394,6 +394,36 @@ fn test_build_with_config_overrides() {
     assert!(output.contains("(4 components)"), "{output}");
 }
 
+#[test]
+fn test_build_rejects_unknown_override_alias() {
+    let output = Sandbox::new()
+        .with_workspace()
+        .write("board.zen", CONFIGURABLE_BUILD_ZEN)
+        .write("fork/pcb.toml", "")
+        .snapshot_run(
+            "pcbc",
+            ["build", "--override-package", "@nope=fork", "board.zen"],
+        );
+
+    assert!(!output.contains("Exit Code: 0"), "{output}");
+    assert!(output.contains("Unknown package alias @nope"), "{output}");
+}
+
+#[test]
+fn test_build_rejects_unknown_patch_alias() {
+    let output = Sandbox::new()
+        .write(
+            "pcb.toml",
+            "[workspace]\npcb-version = \"0.4\"\n\n[patch]\n\"@nope\" = { path = \"fork\" }\n",
+        )
+        .write("board.zen", CONFIGURABLE_BUILD_ZEN)
+        .write("fork/pcb.toml", "")
+        .snapshot_run("pcbc", ["build", "board.zen"]);
+
+    assert!(!output.contains("Exit Code: 0"), "{output}");
+    assert!(output.contains("Unknown package alias @nope"), "{output}");
+}
+
 #[test]
 fn test_diodes_build() {
     let output = Sandbox::new()
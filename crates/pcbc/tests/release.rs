@@ -183,6 +183,125 @@ fn test_publish_board_source_only() {
     assert_snapshot!("publish_source_only", sb.snapshot_dir(&staging_dir));
 }
 
+#[test]
+fn test_release_verify_round_trip() {
+    let mut sb = Sandbox::new();
+    sb.cwd("src")
+        .write("pcb.toml", PCB_TOML)
+        .write("boards/pcb.toml", BOARD_PCB_TOML)
+        .write("boards/modules/LedModule.zen", LED_MODULE_ZEN)
+        .write("boards/TestBoard.zen", TEST_BOARD_ZEN)
+        .init_git()
+        .commit("Initial commit")
+        .sync();
+
+    sb.run("pcbc", ["build", "boards/TestBoard.zen"])
+        .run()
+        .expect("build failed");
+    sb.run("pcbc", source_only_args("boards/TestBoard.zen"))
+        .run()
+        .expect("Failed to run pcb publish command");
+
+    // The archive is extracted somewhere else, so this only passes when the
+    // recorded netlist hash does not depend on the staging path.
+    let archive = format!("{}.zip", find_staging_dir(&sb, "TestBoard"));
+    sb.run("pcbc", ["release", "verify", archive.as_str()])
+        .run()
+        .expect("release verify failed on a fresh release");
+}
+
+/// Stand-in for cosign: `sign-blob` writes the key into the bundle, and
+/// `verify-blob` succeeds only when the bundle holds the key it is given.
+const FAKE_COSIGN: &str = r#"#!/bin/sh
+cmd=$1
+shift
+while [ $# -gt 1 ]; do
+    case $1 in
+        --bundle) bundle=$2; shift 2 ;;
+        --key) key=$2; shift 2 ;;
+        *) shift ;;
+    esac
+done
+[ -f "$1" ] || exit 2
+case $cmd in
+    sign-blob) printf '%s' "$key" > "$bundle" ;;
+    verify-blob) [ "$(cat "$bundle")" = "$key" ] ;;
+    *) exit 2 ;;
+esac
+"#;
+
+#[test]
+fn test_release_verify_signature() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut sb = Sandbox::new();
+    let cosign = sb.root_path().join("fake-cosign");
+    std::fs::write(&cosign, FAKE_COSIGN).unwrap();
+    std::fs::set_permissions(&cosign, std::fs::Permissions::from_mode(0o755)).unwrap();
+    sb.env("COSIGN", cosign.to_string_lossy());
+
+    sb.cwd("src")
+        .write("pcb.toml", PCB_TOML)
+        .write("boards/pcb.toml", BOARD_PCB_TOML)
+        .write("boards/modules/LedModule.zen", LED_MODULE_ZEN)
+        .write("boards/TestBoard.zen", TEST_BOARD_ZEN)
+        .init_git()
+        .commit("Initial commit")
+        .sync();
+
+    sb.run("pcbc", ["build", "boards/TestBoard.zen"])
+        .run()
+        .expect("build failed");
+    let mut args = source_only_args("boards/TestBoard.zen");
+    args.extend(["--sign-key", "release.key"]);
+    sb.run("pcbc", &args)
+        .run()
+        .expect("Failed to run pcb publish command");
+
+    let archive = format!("{}.zip", find_staging_dir(&sb, "TestBoard"));
+    assert!(
+        sb.root_path()
+            .join("src")
+            .join(format!("{archive}.sigstore.json"))
+            .exists(),
+        "signature bundle not written next to the archive"
+    );
+
+    sb.run(
+        "pcbc",
+        [
+            "release",
+            "verify",
+            archive.as_str(),
+            "--signature",
+            "--key",
+            "release.key",
+        ],
+    )
+    .run()
+    .expect("signature check failed for the signing key");
+
+    let wrong_key = sb
+        .run(
+            "pcbc",
+            [
+                "release",
+                "verify",
+                archive.as_str(),
+                "--signature",
+                "--key",
+                "other.key",
+            ],
+        )
+        .unchecked()
+        .run()
+        .expect("release verify should run");
+    assert!(
+        !wrong_key.status.success(),
+        "accepted a signature from another key"
+    );
+}
+
 #[test]
 fn test_publish_board_with_version() {
     let mut sb = Sandbox::new();
@@ -321,6 +440,44 @@ strict = true
     assert_eq!(metadata_json["release"]["bom"]["strict"], true);
 }
 
+#[test]
+fn test_publish_records_uncached_datasheets_as_missing() {
+    let component = SIMPLE_COMPONENT.replace(
+        r#"datasheet = File("datasheet.txt"),"#,
+        r#"datasheet = "https://example.invalid/R.pdf","#,
+    );
+    let mut sb = Sandbox::new();
+    sb.cwd("src")
+        .write("pcb.toml", PCB_TOML)
+        .write("boards/pcb.toml", BOARD_PCB_TOML)
+        .write("boards/modules/component.zen", &component)
+        .write("boards/modules/test.kicad_mod", TEST_KICAD_MOD)
+        .write("boards/TestBoard.zen", SIMPLE_BOARD_ZEN)
+        .init_git()
+        .commit("Initial commit")
+        .sync();
+
+    // The datasheet is not cached, so the release must not try to fetch it.
+    sb.run("pcbc", source_only_args("boards/TestBoard.zen"))
+        .run()
+        .expect("Failed to run pcb publish command");
+
+    let manifest_path = sb
+        .root_path()
+        .join("src")
+        .join(find_staging_dir(&sb, "TestBoard"))
+        .join("datasheets/manifest.json");
+    let manifest: Value = serde_json::from_reader(File::open(manifest_path).unwrap()).unwrap();
+    assert_eq!(
+        manifest,
+        serde_json::json!([{
+            "source": "https://example.invalid/R.pdf",
+            "missing": true,
+            "designators": ["R1"],
+        }])
+    );
+}
+
 #[test]
 fn test_publish_board_with_file() {
     let mut sb = Sandbox::new();